@@ -0,0 +1,463 @@
+use chrono::NaiveDate;
+use reqwest::{Method, RequestBuilder, Response, StatusCode, header};
+use serde::de::DeserializeOwned;
+use uuid::Uuid;
+
+use crate::error::Error;
+use crate::models::{
+    ArchiveQuery, ArchiveResponse, ChangesQuery, ChangesResponse, CreateProjectRequest, CreateTodoRequest, DateQuery, ErrorResponse,
+    LiveEvent, LiveQuery, ProjectListResponse, ProjectResponse, RenameProjectRequest, SearchQuery, SearchResponse,
+    TodoListResponse, TodoResponse, UpdateTodoRequest,
+};
+use crate::DEFAULT_PORT;
+
+/// Async client for a running `totui` API server.
+#[derive(Debug, Clone)]
+pub struct Client {
+    base_url: String,
+    http: reqwest::Client,
+    token: Option<String>,
+    if_match: Option<u64>,
+}
+
+impl Client {
+    /// Create a client for the server at `base_url`, e.g. `http://127.0.0.1:48372`.
+    pub fn new(base_url: impl Into<String>) -> Self {
+        Self::with_http_client(base_url, reqwest::Client::new())
+    }
+
+    /// Create a client that reuses an existing `reqwest` client.
+    pub fn with_http_client(base_url: impl Into<String>, http: reqwest::Client) -> Self {
+        let base_url = base_url.into().trim_end_matches('/').to_string();
+        Self {
+            base_url,
+            http,
+            token: None,
+            if_match: None,
+        }
+    }
+
+    /// Client for the server the TUI starts on this machine.
+    pub fn local() -> Self {
+        Self::new(format!("http://127.0.0.1:{DEFAULT_PORT}"))
+    }
+
+    pub fn base_url(&self) -> &str {
+        &self.base_url
+    }
+
+    /// A client that sends `token` as `Authorization: Bearer <token>` on every
+    /// request, for servers with `token` set in `[server]`.
+    pub fn bearer_token(self, token: impl Into<String>) -> Self {
+        Self {
+            token: Some(token.into()),
+            ..self
+        }
+    }
+
+    /// A client whose writes to todos send `If-Match` with `revision`, the
+    /// [`TodoListResponse::revision`] the list was read at. The server then
+    /// refuses them with a 409 ([`Error::is_conflict`]) if the day changed
+    /// since, instead of writing over the change.
+    pub fn if_match(&self, revision: u64) -> Self {
+        Self {
+            if_match: Some(revision),
+            ..self.clone()
+        }
+    }
+
+    /// Whether the server is up and answering.
+    pub async fn health(&self) -> Result<bool, Error> {
+        let response = self.request(Method::GET, self.url("/api/health")).send().await?;
+        Ok(response.status().is_success())
+    }
+
+    pub async fn list_projects(&self) -> Result<Vec<ProjectResponse>, Error> {
+        let response = self.request(Method::GET, self.url("/api/projects")).send().await?;
+        let list: ProjectListResponse = parse(response).await?;
+        Ok(list.projects)
+    }
+
+    pub async fn create_project(&self, name: &str) -> Result<ProjectResponse, Error> {
+        let request = CreateProjectRequest { name: name.to_string() };
+        let response = self.request(Method::POST, self.url("/api/projects")).json(&request).send().await?;
+        parse(response).await
+    }
+
+    /// Rename a project. The default project can't be renamed.
+    pub async fn rename_project(&self, name: &str, new_name: &str) -> Result<ProjectResponse, Error> {
+        let request = RenameProjectRequest { name: new_name.to_string() };
+        let response = self
+            .request(Method::PATCH, self.project_url(name))
+            .json(&request)
+            .send()
+            .await?;
+        parse(response).await
+    }
+
+    /// Delete a project and all of its todos. The default project can't be
+    /// deleted.
+    pub async fn delete_project(&self, name: &str) -> Result<(), Error> {
+        let response = self.request(Method::DELETE, self.project_url(name)).send().await?;
+        check(response).await.map(|_| ())
+    }
+
+    pub async fn list_todos(&self, query: &DateQuery) -> Result<TodoListResponse, Error> {
+        let response = self
+            .request(Method::GET, self.url("/api/todos"))
+            .query(query)
+            .send()
+            .await?;
+        parse(response).await
+    }
+
+    pub async fn create_todo(
+        &self,
+        query: &DateQuery,
+        request: &CreateTodoRequest,
+    ) -> Result<TodoResponse, Error> {
+        let response = self
+            .todo_write(self.request(Method::POST, self.url("/api/todos")))
+            .query(query)
+            .json(request)
+            .send()
+            .await?;
+        parse(response).await
+    }
+
+    pub async fn update_todo(
+        &self,
+        id: Uuid,
+        query: &DateQuery,
+        request: &UpdateTodoRequest,
+    ) -> Result<TodoResponse, Error> {
+        let response = self
+            .todo_write(self.request(Method::PATCH, self.url(&format!("/api/todos/{id}"))))
+            .query(query)
+            .json(request)
+            .send()
+            .await?;
+        parse(response).await
+    }
+
+    /// Delete a todo together with its subtasks.
+    pub async fn delete_todo(&self, id: Uuid, query: &DateQuery) -> Result<(), Error> {
+        let response = self
+            .todo_write(self.request(Method::DELETE, self.url(&format!("/api/todos/{id}"))))
+            .query(query)
+            .send()
+            .await?;
+        check(response).await.map(|_| ())
+    }
+
+    /// A project's list for one day.
+    pub async fn list_day_todos(&self, project: &str, date: NaiveDate) -> Result<TodoListResponse, Error> {
+        let response = self.request(Method::GET, self.day_url(project, date, None)).send().await?;
+        parse(response).await
+    }
+
+    /// Add a todo to a project's day, nested under `parent_id` or placed
+    /// after `after` when set.
+    pub async fn create_day_todo(
+        &self,
+        project: &str,
+        date: NaiveDate,
+        request: &CreateTodoRequest,
+    ) -> Result<TodoResponse, Error> {
+        let response = self
+            .todo_write(self.request(Method::POST, self.day_url(project, date, None)))
+            .json(request)
+            .send()
+            .await?;
+        parse(response).await
+    }
+
+    pub async fn update_day_todo(
+        &self,
+        project: &str,
+        date: NaiveDate,
+        id: Uuid,
+        request: &UpdateTodoRequest,
+    ) -> Result<TodoResponse, Error> {
+        let response = self
+            .todo_write(self.request(Method::PATCH, self.day_url(project, date, Some(id))))
+            .json(request)
+            .send()
+            .await?;
+        parse(response).await
+    }
+
+    /// Move a todo and its subtasks to the trash.
+    pub async fn delete_day_todo(&self, project: &str, date: NaiveDate, id: Uuid) -> Result<(), Error> {
+        let response = self
+            .todo_write(self.request(Method::DELETE, self.day_url(project, date, Some(id))))
+            .send()
+            .await?;
+        check(response).await.map(|_| ())
+    }
+
+    /// A project's archived todos, grouped by day.
+    pub async fn archive(&self, project: &str, query: &ArchiveQuery) -> Result<ArchiveResponse, Error> {
+        let response = self
+            .request(Method::GET, format!("{}/archive", self.project_url(project)))
+            .query(query)
+            .send()
+            .await?;
+        parse(response).await
+    }
+
+    /// Todos changed since a cursor, for keeping a copy in sync without
+    /// re-reading every list.
+    pub async fn changes(&self, query: &ChangesQuery) -> Result<ChangesResponse, Error> {
+        let response = self
+            .request(Method::GET, self.url("/api/changes"))
+            .query(query)
+            .send()
+            .await?;
+        parse(response).await
+    }
+
+    /// Live and archived todos matching every word of the query, newest
+    /// first.
+    pub async fn search(&self, query: &SearchQuery) -> Result<SearchResponse, Error> {
+        let response = self
+            .request(Method::GET, self.url("/api/search"))
+            .query(query)
+            .send()
+            .await?;
+        parse(response).await
+    }
+
+    /// Changes as they happen, from `/api/events`. Pass the id of the last
+    /// event seen to pick up after it when reconnecting.
+    pub async fn subscribe_events(&self, query: &LiveQuery, last_event_id: Option<i64>) -> Result<EventStream, Error> {
+        let mut request = self.request(Method::GET, self.url("/api/events")).query(query);
+        if let Some(id) = last_event_id {
+            request = request.header("Last-Event-ID", id.to_string());
+        }
+        let response = check(request.send().await?).await?;
+        Ok(EventStream::new(response))
+    }
+
+    fn request(&self, method: Method, url: impl reqwest::IntoUrl) -> RequestBuilder {
+        let request = self.http.request(method, url);
+        match &self.token {
+            Some(token) => request.bearer_auth(token),
+            None => request,
+        }
+    }
+
+    fn todo_write(&self, request: RequestBuilder) -> RequestBuilder {
+        match self.if_match {
+            Some(revision) => request.header(header::IF_MATCH, format!("\"{revision}\"")),
+            None => request,
+        }
+    }
+
+    fn url(&self, path: &str) -> String {
+        format!("{}{}", self.base_url, path)
+    }
+
+    fn project_url(&self, project: &str) -> String {
+        self.url(&format!("/api/projects/{}", encode_segment(project)))
+    }
+
+    fn day_url(&self, project: &str, date: NaiveDate, id: Option<Uuid>) -> String {
+        let mut url = format!("{}/days/{date}/todos", self.project_url(project));
+        if let Some(id) = id {
+            url.push_str(&format!("/{id}"));
+        }
+        url
+    }
+}
+
+/// The `change` events of a `/api/events` stream, decoded as they arrive.
+#[derive(Debug)]
+pub struct EventStream {
+    response: Response,
+    parser: EventParser,
+}
+
+impl EventStream {
+    fn new(response: Response) -> Self {
+        Self {
+            response,
+            parser: EventParser::default(),
+        }
+    }
+
+    /// The next change, or `None` once the server closes the stream.
+    pub async fn next(&mut self) -> Option<Result<LiveEvent, Error>> {
+        loop {
+            if let Some(event) = self.parser.take_event() {
+                return Some(event);
+            }
+            match self.response.chunk().await {
+                Ok(Some(chunk)) => self.parser.push(&chunk),
+                Ok(None) => return None,
+                Err(e) => return Some(Err(e.into())),
+            }
+        }
+    }
+
+    /// Id of the last event received, to pass to
+    /// [`Client::subscribe_events`] when reconnecting.
+    pub fn last_event_id(&self) -> Option<i64> {
+        self.parser.last_event_id
+    }
+}
+
+/// Splits server-sent events out of the bytes received so far.
+#[derive(Debug, Default)]
+struct EventParser {
+    buffer: Vec<u8>,
+    last_event_id: Option<i64>,
+}
+
+impl EventParser {
+    fn push(&mut self, chunk: &[u8]) {
+        self.buffer.extend(chunk.iter().filter(|&&b| b != b'\r'));
+    }
+
+    /// Decode the first complete `change` event, skipping keep-alive
+    /// comments and other event types.
+    fn take_event(&mut self) -> Option<Result<LiveEvent, Error>> {
+        while let Some(end) = self.buffer.windows(2).position(|pair| pair == b"\n\n") {
+            let block: Vec<u8> = self.buffer.drain(..end + 2).collect();
+            let block = String::from_utf8_lossy(&block);
+            let mut name = "message";
+            let mut data = Vec::new();
+            for line in block.lines() {
+                let (field, value) = line.split_once(':').unwrap_or((line, ""));
+                let value = value.strip_prefix(' ').unwrap_or(value);
+                match field {
+                    "event" => name = value,
+                    "data" => data.push(value),
+                    "id" => self.last_event_id = value.parse().ok().or(self.last_event_id),
+                    _ => {}
+                }
+            }
+            if name == "change" && !data.is_empty() {
+                return Some(serde_json::from_str(&data.join("\n")).map_err(Error::Decode));
+            }
+        }
+        None
+    }
+}
+
+/// Percent-encode a path segment; project names may hold spaces and slashes.
+fn encode_segment(segment: &str) -> String {
+    segment
+        .bytes()
+        .map(|b| match b {
+            b'A'..=b'Z' | b'a'..=b'z' | b'0'..=b'9' | b'-' | b'.' | b'_' | b'~' => (b as char).to_string(),
+            _ => format!("%{b:02X}"),
+        })
+        .collect()
+}
+
+/// Turn error statuses into [`Error::Api`], using the server's error message when present.
+async fn check(response: Response) -> Result<Response, Error> {
+    let status = response.status();
+    if status.is_success() {
+        return Ok(response);
+    }
+
+    let body = response.text().await.unwrap_or_default();
+    let message = serde_json::from_str::<ErrorResponse>(&body)
+        .map(|e| e.error)
+        .unwrap_or_else(|_| fallback_message(status, body));
+    Err(Error::Api {
+        status: status.as_u16(),
+        message,
+    })
+}
+
+async fn parse<T: DeserializeOwned>(response: Response) -> Result<T, Error> {
+    Ok(check(response).await?.json().await?)
+}
+
+fn fallback_message(status: StatusCode, body: String) -> String {
+    if body.trim().is_empty() {
+        status.canonical_reason().unwrap_or("Unknown error").to_string()
+    } else {
+        body
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_base_url_trailing_slash() {
+        let client = Client::new("http://localhost:3000/");
+        assert_eq!(client.url("/api/todos"), "http://localhost:3000/api/todos");
+    }
+
+    #[test]
+    fn test_day_url_encodes_the_project() {
+        let client = Client::new("http://localhost:3000");
+        let date = NaiveDate::from_ymd_opt(2026, 1, 18).unwrap();
+        assert_eq!(
+            client.day_url("Side work", date, Some(Uuid::nil())),
+            "http://localhost:3000/api/projects/Side%20work/days/2026-01-18/todos/00000000-0000-0000-0000-000000000000"
+        );
+    }
+
+    #[test]
+    fn test_if_match_is_sent_on_todo_writes_only() {
+        let client = Client::new("http://localhost:3000").if_match(7);
+        let write = client.todo_write(client.http.delete(client.url("/api/todos/1"))).build().unwrap();
+        assert_eq!(write.headers()[header::IF_MATCH], "\"7\"");
+
+        let plain = Client::new("http://localhost:3000");
+        let write = plain.todo_write(plain.http.delete(plain.url("/api/todos/1"))).build().unwrap();
+        assert!(!write.headers().contains_key(header::IF_MATCH));
+    }
+
+    #[test]
+    fn test_bearer_token_is_sent_on_every_request() {
+        let client = Client::new("http://localhost:3000").bearer_token("secret").if_match(3);
+        let read = client.request(Method::GET, client.url("/api/todos")).build().unwrap();
+        assert_eq!(read.headers()[header::AUTHORIZATION], "Bearer secret");
+        let write = client.todo_write(client.request(Method::DELETE, client.url("/api/todos/1"))).build().unwrap();
+        assert_eq!(write.headers()[header::AUTHORIZATION], "Bearer secret");
+
+        let plain = Client::new("http://localhost:3000");
+        let read = plain.request(Method::GET, plain.url("/api/todos")).build().unwrap();
+        assert!(!read.headers().contains_key(header::AUTHORIZATION));
+    }
+
+    #[test]
+    fn test_event_parser_handles_split_chunks_and_keep_alives() {
+        let mut parser = EventParser::default();
+        let event = r#"{"id":4,"at":"2026-01-18T09:00:00Z","action":"add","kind":"add","origin":"api","project":"default","content":"Milk"}"#;
+        let stream = format!(":\n\nid: 4\r\nevent: change\r\ndata: {event}\r\n\r\n");
+        let (first, second) = stream.as_bytes().split_at(30);
+
+        parser.push(first);
+        assert!(parser.take_event().is_none());
+        parser.push(second);
+        let event = parser.take_event().unwrap().unwrap();
+        assert_eq!(event.id, 4);
+        assert_eq!(event.content.as_deref(), Some("Milk"));
+        assert_eq!(parser.last_event_id, Some(4));
+        assert!(parser.take_event().is_none());
+
+        parser.push(b"event: change\ndata: {\n\n");
+        assert!(matches!(parser.take_event(), Some(Err(Error::Decode(_)))));
+    }
+
+    #[test]
+    fn test_fallback_message() {
+        assert_eq!(
+            fallback_message(StatusCode::NOT_FOUND, String::new()),
+            "Not Found"
+        );
+        assert_eq!(
+            fallback_message(StatusCode::BAD_GATEWAY, "upstream down".to_string()),
+            "upstream down"
+        );
+    }
+}
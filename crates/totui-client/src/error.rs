@@ -0,0 +1,59 @@
+use std::fmt;
+
+/// Errors returned by [`Client`](crate::Client).
+#[derive(Debug)]
+pub enum Error {
+    /// The request could not be sent or the response could not be decoded.
+    Http(reqwest::Error),
+    /// The server answered with an error status.
+    Api { status: u16, message: String },
+    /// A server-sent event could not be decoded.
+    Decode(serde_json::Error),
+}
+
+impl Error {
+    /// HTTP status of an API error, if the server responded.
+    pub fn status(&self) -> Option<u16> {
+        match self {
+            Error::Http(e) => e.status().map(|s| s.as_u16()),
+            Error::Api { status, .. } => Some(*status),
+            Error::Decode(_) => None,
+        }
+    }
+
+    pub fn is_not_found(&self) -> bool {
+        self.status() == Some(404)
+    }
+
+    /// Whether a write sent with [`Client::if_match`](crate::Client::if_match)
+    /// was refused because the day changed since.
+    pub fn is_conflict(&self) -> bool {
+        self.status() == Some(409)
+    }
+}
+
+impl fmt::Display for Error {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Error::Http(e) => write!(f, "request failed: {e}"),
+            Error::Api { status, message } => write!(f, "API error {status}: {message}"),
+            Error::Decode(e) => write!(f, "invalid event: {e}"),
+        }
+    }
+}
+
+impl std::error::Error for Error {
+    fn source(&self) -> Option<&(dyn std::error::Error + 'static)> {
+        match self {
+            Error::Http(e) => Some(e),
+            Error::Decode(e) => Some(e),
+            Error::Api { .. } => None,
+        }
+    }
+}
+
+impl From<reqwest::Error> for Error {
+    fn from(e: reqwest::Error) -> Self {
+        Error::Http(e)
+    }
+}
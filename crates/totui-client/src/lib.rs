@@ -0,0 +1,28 @@
+//! Typed client for the to-tui REST API.
+//!
+//! The request and response types in [`models`] are the same ones the `totui`
+//! server uses, so tools built on this crate stay in step with the API.
+//!
+//! ```no_run
+//! # async fn run() -> Result<(), totui_client::Error> {
+//! use totui_client::{Client, models::{CreateTodoRequest, DateQuery}};
+//!
+//! let client = Client::local();
+//! let query = DateQuery::default();
+//! client.create_todo(&query, &CreateTodoRequest::new("Review PR")).await?;
+//! for todo in client.list_todos(&query).await?.items {
+//!     println!("[{}] {}", todo.state, todo.content);
+//! }
+//! # Ok(())
+//! # }
+//! ```
+
+pub mod client;
+pub mod error;
+pub mod models;
+
+pub use client::{Client, EventStream};
+pub use error::Error;
+
+/// Port the `totui` API server listens on unless told otherwise.
+pub const DEFAULT_PORT: u16 = 48372;
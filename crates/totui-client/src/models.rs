@@ -0,0 +1,441 @@
+//! JSON shapes of the to-tui REST API, shared by the server and the client.
+
+use chrono::{DateTime, NaiveDate, Utc};
+use serde::{Deserialize, Serialize};
+use uuid::Uuid;
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[cfg_attr(feature = "openapi", derive(utoipa::ToSchema))]
+pub struct TodoResponse {
+    pub id: Uuid,
+    pub content: String,
+    /// Checkbox character: ' ', 'x', '*', '?' or '!'
+    pub state: String,
+    pub indent_level: usize,
+    pub parent_id: Option<Uuid>,
+    pub due_date: Option<NaiveDate>,
+    pub description: Option<String>,
+    /// `P0`, `P1`, ...
+    #[serde(default)]
+    pub priority: Option<String>,
+    #[serde(default)]
+    pub estimate_minutes: Option<u32>,
+    /// Who the item is waiting on
+    #[serde(default)]
+    pub waiting_on: Option<String>,
+    #[serde(default)]
+    pub pinned: bool,
+    #[serde(default)]
+    pub link: Option<String>,
+    /// Subtasks and description are folded away
+    #[serde(default)]
+    pub collapsed: bool,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[cfg_attr(feature = "openapi", derive(utoipa::ToSchema))]
+pub struct TodoListResponse {
+    pub date: NaiveDate,
+    pub items: Vec<TodoResponse>,
+    /// The day's revision. Sent back in `If-Match` (see
+    /// [`Client::if_match`](crate::Client::if_match)), a write is refused
+    /// with 409 if the day has changed since.
+    #[serde(default)]
+    pub revision: u64,
+}
+
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[cfg_attr(feature = "openapi", derive(utoipa::ToSchema))]
+pub struct CreateTodoRequest {
+    pub content: String,
+    /// Add as the last subtask of this item
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub parent_id: Option<Uuid>,
+    /// Add as the next sibling of this item, after its subtasks
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub after: Option<Uuid>,
+    /// Checkbox character; defaults to ' '
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub state: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub due_date: Option<NaiveDate>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub description: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub priority: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub estimate_minutes: Option<u32>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub waiting_on: Option<String>,
+    #[serde(default, skip_serializing_if = "std::ops::Not::not")]
+    pub pinned: bool,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub link: Option<String>,
+}
+
+impl CreateTodoRequest {
+    pub fn new(content: impl Into<String>) -> Self {
+        Self {
+            content: content.into(),
+            ..Default::default()
+        }
+    }
+}
+
+/// Partial update; fields left as `None` are not changed.
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[cfg_attr(feature = "openapi", derive(utoipa::ToSchema))]
+pub struct UpdateTodoRequest {
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub content: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub state: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub due_date: Option<NaiveDate>,
+    /// An empty string clears the description
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub description: Option<String>,
+    /// An empty string clears the priority
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub priority: Option<String>,
+    /// 0 clears the estimate
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub estimate_minutes: Option<u32>,
+    /// An empty string clears it
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub waiting_on: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub pinned: Option<bool>,
+    /// An empty string clears the link
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub link: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub collapsed: Option<bool>,
+}
+
+/// Query parameters selecting the day and project. Defaults to today's list
+/// in the default project.
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[cfg_attr(feature = "openapi", derive(utoipa::IntoParams), into_params(parameter_in = Query))]
+pub struct DateQuery {
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub date: Option<NaiveDate>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub project: Option<String>,
+}
+
+impl DateQuery {
+    pub fn for_date(date: NaiveDate) -> Self {
+        Self {
+            date: Some(date),
+            project: None,
+        }
+    }
+
+    pub fn with_project(mut self, project: impl Into<String>) -> Self {
+        self.project = Some(project.into());
+        self
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[cfg_attr(feature = "openapi", derive(utoipa::ToSchema))]
+pub struct ProjectResponse {
+    pub id: Uuid,
+    pub name: String,
+    pub created_at: DateTime<Utc>,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[cfg_attr(feature = "openapi", derive(utoipa::ToSchema))]
+pub struct ProjectListResponse {
+    pub projects: Vec<ProjectResponse>,
+}
+
+/// Body of `POST /api/projects`.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[cfg_attr(feature = "openapi", derive(utoipa::ToSchema))]
+pub struct CreateProjectRequest {
+    pub name: String,
+}
+
+/// Body of `PATCH /api/projects/{name}`.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[cfg_attr(feature = "openapi", derive(utoipa::ToSchema))]
+pub struct RenameProjectRequest {
+    /// The new name
+    pub name: String,
+}
+
+/// Query parameters of `GET /api/changes`.
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[cfg_attr(feature = "openapi", derive(utoipa::IntoParams), into_params(parameter_in = Query))]
+pub struct ChangesQuery {
+    /// The `cursor` of the previous response; 0 reads everything
+    #[serde(default)]
+    pub since: i64,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub project: Option<String>,
+    /// Most changes to return; the server caps it
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub limit: Option<usize>,
+}
+
+impl ChangesQuery {
+    pub fn since(cursor: i64) -> Self {
+        Self {
+            since: cursor,
+            ..Default::default()
+        }
+    }
+
+    pub fn with_project(mut self, project: impl Into<String>) -> Self {
+        self.project = Some(project.into());
+        self
+    }
+}
+
+/// Query parameters of `GET /api/projects/{project}/archive`. Unset fields
+/// match everything.
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[cfg_attr(feature = "openapi", derive(utoipa::IntoParams), into_params(parameter_in = Query))]
+pub struct ArchiveQuery {
+    /// First day, inclusive
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub from: Option<NaiveDate>,
+    /// Last day, inclusive
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub to: Option<NaiveDate>,
+    /// The checkbox character: ' ', 'x', '?' or '!'
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub state: Option<String>,
+    /// Words that must appear in the content or description
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub q: Option<String>,
+}
+
+impl ArchiveQuery {
+    pub fn between(from: NaiveDate, to: NaiveDate) -> Self {
+        Self {
+            from: Some(from),
+            to: Some(to),
+            ..Default::default()
+        }
+    }
+}
+
+/// The matching archived todos of one day.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[cfg_attr(feature = "openapi", derive(utoipa::ToSchema))]
+pub struct ArchivedDayResponse {
+    pub date: NaiveDate,
+    pub items: Vec<TodoResponse>,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[cfg_attr(feature = "openapi", derive(utoipa::ToSchema))]
+pub struct ArchiveResponse {
+    pub project: String,
+    /// Oldest day first
+    pub days: Vec<ArchivedDayResponse>,
+}
+
+/// A todo added, changed or removed since the cursor.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[cfg_attr(feature = "openapi", derive(utoipa::ToSchema))]
+pub struct ChangeResponse {
+    pub change_seq: i64,
+    pub id: Uuid,
+    pub project: String,
+    pub date: NaiveDate,
+    /// The todo as it is now; `None` when it was removed
+    pub todo: Option<TodoResponse>,
+    /// Deleted to the trash, archived or purged
+    pub deleted: bool,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[cfg_attr(feature = "openapi", derive(utoipa::ToSchema))]
+pub struct ChangesResponse {
+    pub changes: Vec<ChangeResponse>,
+    /// Pass as `since` on the next poll
+    pub cursor: i64,
+    /// More changes are waiting; poll again right away
+    pub has_more: bool,
+}
+
+/// Query parameters of `GET /api/search`.
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[cfg_attr(feature = "openapi", derive(utoipa::IntoParams), into_params(parameter_in = Query))]
+pub struct SearchQuery {
+    /// Words that must all appear in the content or description, as words
+    /// or the start of one
+    pub q: String,
+    /// Only search this project
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub project: Option<String>,
+    /// Most hits to return; the server caps it
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub limit: Option<usize>,
+}
+
+impl SearchQuery {
+    pub fn new(q: impl Into<String>) -> Self {
+        Self {
+            q: q.into(),
+            ..Default::default()
+        }
+    }
+}
+
+/// A todo matching a search, live or archived.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[cfg_attr(feature = "openapi", derive(utoipa::ToSchema))]
+pub struct SearchHitResponse {
+    pub id: Uuid,
+    pub date: NaiveDate,
+    pub project: String,
+    pub content: String,
+    /// Checkbox character: ' ', 'x', '*', '?' or '!'
+    pub state: String,
+    /// Rolled over or archived, so no longer on a live list
+    pub archived: bool,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[cfg_attr(feature = "openapi", derive(utoipa::ToSchema))]
+pub struct SearchResponse {
+    /// Newest first
+    pub hits: Vec<SearchHitResponse>,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[cfg_attr(feature = "openapi", derive(utoipa::ToSchema))]
+pub struct ErrorResponse {
+    pub error: String,
+}
+
+impl ErrorResponse {
+    pub fn new(message: impl Into<String>) -> Self {
+        Self {
+            error: message.into(),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_update_request_omits_unset_fields() {
+        let req = UpdateTodoRequest {
+            state: Some("x".to_string()),
+            ..Default::default()
+        };
+        assert_eq!(serde_json::to_string(&req).unwrap(), r#"{"state":"x"}"#);
+    }
+
+    #[test]
+    fn test_create_request_accepts_minimal_body() {
+        let req: CreateTodoRequest = serde_json::from_str(r#"{"content":"Task"}"#).unwrap();
+        assert_eq!(req, CreateTodoRequest::new("Task"));
+    }
+
+    #[test]
+    fn test_todo_response_round_trip() {
+        let todo = TodoResponse {
+            id: Uuid::nil(),
+            content: "Task".to_string(),
+            state: " ".to_string(),
+            indent_level: 1,
+            parent_id: Some(Uuid::nil()),
+            due_date: NaiveDate::from_ymd_opt(2026, 3, 1),
+            description: None,
+            priority: Some("P1".to_string()),
+            estimate_minutes: Some(30),
+            waiting_on: None,
+            pinned: true,
+            link: None,
+            collapsed: false,
+        };
+        let json = serde_json::to_string(&todo).unwrap();
+        assert_eq!(serde_json::from_str::<TodoResponse>(&json).unwrap(), todo);
+    }
+}
+
+/// Query parameters of `GET /api/admin/logs`.
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[cfg_attr(feature = "openapi", derive(utoipa::IntoParams), into_params(parameter_in = Query))]
+pub struct LogsQuery {
+    /// Least severe level to include, such as "debug" or "warn" (default:
+    /// every line the server logged)
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub level: Option<String>,
+    /// Keep the response open and send new lines as they are logged
+    #[serde(default)]
+    pub follow: bool,
+}
+
+/// Body of `PUT /api/admin/log-level`.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[cfg_attr(feature = "openapi", derive(utoipa::ToSchema))]
+pub struct LogLevelRequest {
+    /// A level or RUST_LOG-style filter, such as "debug" or
+    /// "info,to_tui=trace"
+    pub level: String,
+}
+
+/// The server's log filter after `PUT /api/admin/log-level`.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[cfg_attr(feature = "openapi", derive(utoipa::ToSchema))]
+pub struct LogLevelResponse {
+    pub level: String,
+}
+
+/// Query parameters of `GET /api/ws`.
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[cfg_attr(feature = "openapi", derive(utoipa::IntoParams), into_params(parameter_in = Query))]
+pub struct LiveQuery {
+    /// Only send changes to this project
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub project: Option<String>,
+}
+
+/// What happened to a todo, as pushed over `/api/ws`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[cfg_attr(feature = "openapi", derive(utoipa::ToSchema))]
+#[serde(rename_all = "lowercase")]
+pub enum LiveAction {
+    /// Added, or brought back from the trash
+    Add,
+    /// Edited or its state changed
+    Modify,
+    /// Deleted, purged or archived
+    Delete,
+    /// Carried over to a new day
+    Rollover,
+}
+
+/// One change pushed over `/api/ws`, as a JSON text message.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[cfg_attr(feature = "openapi", derive(utoipa::ToSchema))]
+pub struct LiveEvent {
+    /// Increases with every change
+    pub id: i64,
+    pub at: DateTime<Utc>,
+    pub action: LiveAction,
+    /// The history kind the action comes from, e.g. `state` for a
+    /// [`LiveAction::Modify`] that checked an item off
+    pub kind: String,
+    /// `tui`, `cli`, `api`, `mcp`, `editor` or `plugin:<name>`
+    pub origin: String,
+    pub project: String,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub date: Option<NaiveDate>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub todo_id: Option<Uuid>,
+    /// The item's text at the time
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub content: Option<String>,
+}
@@ -2,7 +2,8 @@
 //!
 //! This module defines the event types that plugins can subscribe to and handle.
 //! Events are emitted by the host when todo items are created, modified, completed,
-//! deleted, or when a project is loaded.
+//! deleted, when a project is loaded, and at points in the app's lifecycle: a
+//! rollover, a change of day, a switch of project and exit.
 
 use abi_stable::std_types::{RString, RVec};
 use abi_stable::StableAbi;
@@ -30,6 +31,14 @@ pub enum FfiEventType {
     OnDelete = 3,
     /// Emitted when a project is loaded.
     OnLoad = 4,
+    /// Emitted after incomplete items are rolled over to today.
+    OnRollover = 5,
+    /// Emitted when the day on screen changes.
+    OnDayChange = 6,
+    /// Emitted after switching to another project.
+    OnProjectSwitch = 7,
+    /// Emitted when the app exits.
+    OnShutdown = 8,
 }
 
 // ============================================================================
@@ -119,6 +128,44 @@ pub enum FfiEvent {
         /// Current date in YYYY-MM-DD format.
         date: RString,
     },
+    /// Incomplete items were rolled over from an earlier day to today.
+    OnRollover {
+        /// Name of the project rolled over.
+        project_name: RString,
+        /// Day the items came from, in YYYY-MM-DD format.
+        from_date: RString,
+        /// Day they were rolled over to, in YYYY-MM-DD format.
+        to_date: RString,
+        /// Number of items rolled over, subtasks included.
+        item_count: u32,
+    },
+    /// The day on screen changed, by navigating to another date or by
+    /// midnight passing.
+    OnDayChange {
+        /// Name of the current project.
+        project_name: RString,
+        /// Day shown before, in YYYY-MM-DD format.
+        from_date: RString,
+        /// Day shown now, in YYYY-MM-DD format.
+        to_date: RString,
+    },
+    /// Another project was opened. Its list is loaded by the time this fires.
+    OnProjectSwitch {
+        /// Name of the project left.
+        from_project: RString,
+        /// Name of the project opened.
+        to_project: RString,
+        /// Day shown, in YYYY-MM-DD format.
+        date: RString,
+    },
+    /// The app is exiting, after its last save. Commands returned are not
+    /// applied.
+    OnShutdown {
+        /// Name of the current project.
+        project_name: RString,
+        /// Day shown, in YYYY-MM-DD format.
+        date: RString,
+    },
 }
 
 // ============================================================================
@@ -156,20 +203,29 @@ impl FfiEvent {
             FfiEvent::OnComplete { .. } => FfiEventType::OnComplete,
             FfiEvent::OnDelete { .. } => FfiEventType::OnDelete,
             FfiEvent::OnLoad { .. } => FfiEventType::OnLoad,
+            FfiEvent::OnRollover { .. } => FfiEventType::OnRollover,
+            FfiEvent::OnDayChange { .. } => FfiEventType::OnDayChange,
+            FfiEvent::OnProjectSwitch { .. } => FfiEventType::OnProjectSwitch,
+            FfiEvent::OnShutdown { .. } => FfiEventType::OnShutdown,
         }
     }
 
     /// Get the todo item if this event contains one.
     ///
     /// Returns `Some` for OnAdd, OnModify, OnComplete, OnDelete events.
-    /// Returns `None` for OnLoad events (which don't carry a todo).
+    /// Returns `None` for OnLoad and the other lifecycle events (which don't
+    /// carry a todo).
     pub fn todo(&self) -> Option<&FfiTodoItem> {
         match self {
             FfiEvent::OnAdd { todo, .. } => Some(todo),
             FfiEvent::OnModify { todo, .. } => Some(todo),
             FfiEvent::OnComplete { todo } => Some(todo),
             FfiEvent::OnDelete { todo } => Some(todo),
-            FfiEvent::OnLoad { .. } => None,
+            FfiEvent::OnLoad { .. }
+            | FfiEvent::OnRollover { .. }
+            | FfiEvent::OnDayChange { .. }
+            | FfiEvent::OnProjectSwitch { .. }
+            | FfiEvent::OnShutdown { .. } => None,
         }
     }
 }
@@ -198,6 +254,7 @@ mod tests {
             modified_at: 1706000000000,
             completed_at: ROption::RNone,
             position: 0,
+            link: ROption::RNone,
         }
     }
 
@@ -244,6 +301,43 @@ mod tests {
         assert!(matches!(event.event_type(), FfiEventType::OnLoad));
     }
 
+    #[test]
+    fn test_lifecycle_event_types() {
+        let events = [
+            FfiEvent::OnRollover {
+                project_name: "test".into(),
+                from_date: "2026-01-25".into(),
+                to_date: "2026-01-26".into(),
+                item_count: 3,
+            },
+            FfiEvent::OnDayChange {
+                project_name: "test".into(),
+                from_date: "2026-01-26".into(),
+                to_date: "2026-01-25".into(),
+            },
+            FfiEvent::OnProjectSwitch {
+                from_project: "default".into(),
+                to_project: "test".into(),
+                date: "2026-01-26".into(),
+            },
+            FfiEvent::OnShutdown {
+                project_name: "test".into(),
+                date: "2026-01-26".into(),
+            },
+        ];
+        let types: Vec<_> = events.iter().map(FfiEvent::event_type).collect();
+        assert_eq!(
+            types,
+            vec![
+                FfiEventType::OnRollover,
+                FfiEventType::OnDayChange,
+                FfiEventType::OnProjectSwitch,
+                FfiEventType::OnShutdown,
+            ]
+        );
+        assert!(events.iter().all(|event| event.todo().is_none()));
+    }
+
     #[test]
     fn test_todo_returns_some_for_add() {
         let todo = make_test_todo();
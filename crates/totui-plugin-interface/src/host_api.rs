@@ -214,7 +214,8 @@ pub struct FfiTodoNode {
 
 /// FFI-safe metadata result for batch queries.
 ///
-/// Contains a todo ID and its associated metadata JSON string.
+/// Contains a todo ID, its associated metadata JSON string and host-owned
+/// planning fields such as the effort estimate.
 #[repr(C)]
 #[derive(StableAbi, Clone, Debug)]
 pub struct FfiTodoMetadata {
@@ -222,6 +223,63 @@ pub struct FfiTodoMetadata {
     pub todo_id: RString,
     /// JSON metadata string (empty {} if no metadata)
     pub data: RString,
+    /// Effort estimate in minutes (`@est(...)`), if the todo has one
+    pub estimate_minutes: ROption<u32>,
+}
+
+// ============================================================================
+// FfiHistoryEntry - One change from the host's change log
+// ============================================================================
+
+/// FFI-safe entry of the host's log of changes to todos.
+#[repr(C)]
+#[derive(StableAbi, Clone, Debug)]
+pub struct FfiHistoryEntry {
+    /// When the change was made (RFC 3339)
+    pub at: RString,
+    /// Where it came from: tui, cli, api, mcp, editor or plugin:<name>
+    pub origin: RString,
+    /// add, edit, state, delete, restore, purge, archive or rollover
+    pub kind: RString,
+    /// Project the change was made in
+    pub project: RString,
+    /// Day of the list changed, in YYYY-MM-DD format
+    pub date: ROption<RString>,
+    /// UUID of the todo changed, for changes to a single todo
+    pub todo_id: ROption<RString>,
+    /// The todo's text at the time
+    pub content: ROption<RString>,
+    /// What changed: the fields of an edit, the states of a state change
+    pub detail: ROption<RString>,
+}
+
+// ============================================================================
+// FfiChangeSet - Todos changed since a sync cursor
+// ============================================================================
+
+/// FFI-safe todo added, changed or removed since a cursor.
+#[repr(C)]
+#[derive(StableAbi, Clone, Debug)]
+pub struct FfiChange {
+    /// Number of the change; higher is later
+    pub change_seq: i64,
+    /// UUID of the todo as string
+    pub todo_id: RString,
+    /// Day of the todo's list, in YYYY-MM-DD format
+    pub date: RString,
+    /// The todo as it is now; None when it was archived or purged
+    pub todo: ROption<FfiTodoItem>,
+}
+
+/// FFI-safe page of changes, oldest first.
+#[repr(C)]
+#[derive(StableAbi, Clone, Debug)]
+pub struct FfiChangeSet {
+    pub changes: RVec<FfiChange>,
+    /// Pass as `since` on the next call
+    pub cursor: i64,
+    /// More changes are waiting
+    pub has_more: bool,
 }
 
 // ============================================================================
@@ -268,4 +326,14 @@ pub trait HostApi: Send + Sync {
     /// List projects that have metadata for this plugin.
     #[sabi(last_prefix_field)]
     fn list_projects_with_metadata(&self) -> RVec<RString>;
+
+    /// Changes made to todos in the current project, newest first: all of
+    /// them, or those of one todo when `todo_id` is given. At most `limit`
+    /// entries are returned; 0 returns them all.
+    fn query_history(&self, todo_id: ROption<RString>, limit: u32) -> RVec<FfiHistoryEntry>;
+
+    /// Todos of the current project changed since the cursor `since` (0 for
+    /// all of them), at most `limit` at a time; 0 returns them all. Keep the
+    /// returned cursor to pick up from there next time.
+    fn query_changes(&self, since: i64, limit: u32) -> FfiChangeSet;
 }
@@ -18,7 +18,7 @@ pub mod version;
 pub use config::{FfiConfigField, FfiConfigSchema, FfiConfigType, FfiConfigValue};
 pub use events::{FfiEvent, FfiEventSource, FfiEventType, FfiFieldChange, FfiHookResponse};
 pub use host_api::{
-    FfiCommand, FfiMovePosition, FfiProjectContext, FfiStateFilter, FfiTodoMetadata, FfiTodoNode,
+    FfiChange, FfiChangeSet, FfiCommand, FfiHistoryEntry, FfiMovePosition, FfiProjectContext, FfiStateFilter, FfiTodoMetadata, FfiTodoNode,
     FfiTodoQuery, HostApi, HostApi_TO,
 };
 pub use plugin::{
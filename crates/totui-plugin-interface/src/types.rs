@@ -61,4 +61,6 @@ pub struct FfiTodoItem {
     pub completed_at: ROption<i64>,
     /// Position in the list (0-indexed, set by host during query)
     pub position: u32,
+    /// Source URL (ticket, PR, meeting) shown as a link on the row
+    pub link: ROption<RString>,
 }
@@ -0,0 +1,121 @@
+//! Stopping the server: `POST /api/admin/shutdown`, SIGTERM and Ctrl+C all
+//! end in a graceful shutdown that lets requests in flight finish their
+//! writes.
+//!
+//! The shutdown endpoint takes the admin token as a bearer token. A new one
+//! is made every time the server starts and written to a file only its user
+//! can read, which is where `totui serve stop` picks it up.
+
+use std::sync::Arc;
+
+use anyhow::Result;
+use axum::{
+    Extension,
+    body::Body,
+    http::{HeaderMap, StatusCode},
+    response::{IntoResponse, Response},
+};
+use tokio::sync::watch;
+use uuid::Uuid;
+
+use super::auth::bearer_matches;
+use super::models::{ErrorResponse, ErrorResponseExt};
+
+/// The running server's admin token and stop switch.
+#[derive(Clone)]
+pub struct Admin {
+    token: Arc<str>,
+    stop: Arc<watch::Sender<bool>>,
+}
+
+impl Admin {
+    pub fn new() -> Self {
+        let (stop, _) = watch::channel(false);
+        Self {
+            token: Uuid::new_v4().simple().to_string().into(),
+            stop: Arc::new(stop),
+        }
+    }
+
+    pub fn token(&self) -> &str {
+        &self.token
+    }
+
+    pub fn stop(&self) {
+        self.stop.send_replace(true);
+    }
+
+    /// Resolves once the server has been asked to stop, however it was asked.
+    pub async fn stopped(&self) {
+        let mut stopped = self.stop.subscribe();
+        let _ = stopped.wait_for(|stop| *stop).await;
+    }
+
+    /// Turn SIGTERM and Ctrl+C into a stop. Must be called within the tokio
+    /// runtime.
+    pub fn stop_on_signals(&self) -> Result<()> {
+        #[cfg(unix)]
+        let mut terminate = tokio::signal::unix::signal(tokio::signal::unix::SignalKind::terminate())?;
+        let admin = self.clone();
+        tokio::spawn(async move {
+            #[cfg(unix)]
+            tokio::select! {
+                _ = tokio::signal::ctrl_c() => {}
+                _ = terminate.recv() => {}
+            }
+            #[cfg(not(unix))]
+            let _ = tokio::signal::ctrl_c().await;
+            tracing::info!("Shutting down");
+            admin.stop();
+        });
+        Ok(())
+    }
+
+    pub(super) fn authorized(&self, headers: &HeaderMap) -> bool {
+        bearer_matches(headers, &self.token)
+    }
+}
+
+#[utoipa::path(
+    post,
+    path = "/api/admin/shutdown",
+    tag = "server",
+    description = "Stop the server once requests in flight have finished. Takes the admin token from `server.token` in the data directory as a bearer token.",
+    responses(
+        (status = 202, description = "Shutting down"),
+        (status = 401, description = "Missing or wrong admin token", body = ErrorResponse),
+    )
+)]
+pub async fn shutdown(Extension(admin): Extension<Admin>, headers: HeaderMap) -> Response<Body> {
+    if !admin.authorized(&headers) {
+        return ErrorResponse::unauthorized("Shutting the server down takes its admin token");
+    }
+    tracing::info!("Shutdown requested over the API");
+    admin.stop();
+    StatusCode::ACCEPTED.into_response()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use axum::http::{HeaderValue, header};
+
+    #[tokio::test]
+    async fn test_shutdown_takes_the_admin_token() {
+        let admin = Admin::new();
+        let mut headers = HeaderMap::new();
+
+        let response = shutdown(Extension(admin.clone()), headers.clone()).await;
+        assert_eq!(response.status(), StatusCode::UNAUTHORIZED);
+        headers.insert(header::AUTHORIZATION, HeaderValue::from_static("Bearer nope"));
+        let response = shutdown(Extension(admin.clone()), headers.clone()).await;
+        assert_eq!(response.status(), StatusCode::UNAUTHORIZED);
+        assert!(!*admin.stop.borrow());
+
+        let bearer = format!("Bearer {}", admin.token());
+        headers.insert(header::AUTHORIZATION, HeaderValue::from_str(&bearer).unwrap());
+        let response = shutdown(Extension(admin.clone()), headers).await;
+        assert_eq!(response.status(), StatusCode::ACCEPTED);
+        admin.stopped().await;
+    }
+}
@@ -0,0 +1,77 @@
+//! Bearer tokens: the API token from `token` in `[server]`, which every API
+//! request but the health check and the admin endpoints has to send once it
+//! is set, and the check both kinds of token share. Pages outside `/api/`,
+//! like the web UI, hold no data and stay open.
+
+use std::sync::Arc;
+
+use axum::{
+    Router,
+    body::Body,
+    extract::{Request, State},
+    http::{HeaderMap, header},
+    middleware::{self, Next},
+    response::Response,
+};
+
+use super::models::{ErrorResponse, ErrorResponseExt};
+
+/// Everything else is the web UI's static files
+const API_PATHS: &str = "/api/";
+/// The health check tells `serve status` whether the server is up, so it is
+/// open to everyone.
+const HEALTH_PATH: &str = "/api/health";
+/// The admin endpoints take the admin token instead.
+const ADMIN_PATHS: &str = "/api/admin/";
+
+/// Wrap `router` so it turns away requests without `token`. Without a token
+/// the API stays open.
+pub fn require_token(router: Router, token: Option<&str>) -> Router {
+    match token.map(str::trim).filter(|token| !token.is_empty()) {
+        Some(token) => router.layer(middleware::from_fn_with_state(Arc::<str>::from(token), check_token)),
+        None => router,
+    }
+}
+
+async fn check_token(State(token): State<Arc<str>>, request: Request, next: Next) -> Response<Body> {
+    let path = request.uri().path();
+    let open = !path.starts_with(API_PATHS) || path == HEALTH_PATH || path.starts_with(ADMIN_PATHS);
+    if open || bearer_matches(request.headers(), &token) {
+        return next.run(request).await;
+    }
+    ErrorResponse::unauthorized("Missing or wrong API token; send it as 'Authorization: Bearer <token>'")
+}
+
+/// Whether `headers` carry `token` as a bearer token.
+pub fn bearer_matches(headers: &HeaderMap, token: &str) -> bool {
+    headers
+        .get(header::AUTHORIZATION)
+        .and_then(|value| value.to_str().ok())
+        .and_then(|value| value.strip_prefix("Bearer "))
+        .is_some_and(|sent| constant_time_eq(sent.trim().as_bytes(), token.as_bytes()))
+}
+
+/// Compares without stopping at the first difference, so response times
+/// don't give the token away.
+fn constant_time_eq(a: &[u8], b: &[u8]) -> bool {
+    a.len() == b.len() && a.iter().zip(b).fold(0, |diff, (x, y)| diff | (x ^ y)) == 0
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use axum::http::HeaderValue;
+
+    #[test]
+    fn test_bearer_matches() {
+        let mut headers = HeaderMap::new();
+        assert!(!bearer_matches(&headers, "secret"));
+
+        headers.insert(header::AUTHORIZATION, HeaderValue::from_static("Bearer secre"));
+        assert!(!bearer_matches(&headers, "secret"));
+        headers.insert(header::AUTHORIZATION, HeaderValue::from_static("Basic secret"));
+        assert!(!bearer_matches(&headers, "secret"));
+        headers.insert(header::AUTHORIZATION, HeaderValue::from_static("Bearer secret "));
+        assert!(bearer_matches(&headers, "secret"));
+    }
+}
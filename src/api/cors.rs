@@ -0,0 +1,75 @@
+//! Which browser pages may call the API, from `[server.cors]`.
+
+use anyhow::{Context, Result};
+use axum::http::{HeaderValue, Method};
+use tower_http::cors::{AllowMethods, AllowOrigin, Any, CorsLayer};
+
+use crate::config::CorsConfig;
+
+/// Fails on an origin or method that isn't valid in a header, so a typo
+/// stops the server from starting instead of silently blocking a dashboard.
+pub fn cors_layer(config: &CorsConfig) -> Result<CorsLayer> {
+    let origins = if allows_all(&config.allowed_origins) {
+        AllowOrigin::any()
+    } else {
+        let origins = config
+            .allowed_origins
+            .iter()
+            // Browsers send the origin without a trailing slash
+            .map(|origin| {
+                HeaderValue::from_str(origin.trim_end_matches('/'))
+                    .with_context(|| format!("Invalid CORS origin '{origin}' in [server.cors]"))
+            })
+            .collect::<Result<Vec<_>>>()?;
+        AllowOrigin::list(origins)
+    };
+
+    let methods = if allows_all(&config.allowed_methods) {
+        AllowMethods::any()
+    } else {
+        let methods = config
+            .allowed_methods
+            .iter()
+            .map(|method| {
+                Method::from_bytes(method.trim().to_uppercase().as_bytes())
+                    .with_context(|| format!("Invalid CORS method '{method}' in [server.cors]"))
+            })
+            .collect::<Result<Vec<_>>>()?;
+        AllowMethods::list(methods)
+    };
+
+    Ok(CorsLayer::new()
+        .allow_origin(origins)
+        .allow_methods(methods)
+        .allow_headers(Any))
+}
+
+fn allows_all(values: &[String]) -> bool {
+    values.is_empty() || values.iter().any(|value| value.trim() == "*")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_cors_layer_checks_the_config() {
+        assert!(cors_layer(&CorsConfig::default()).is_ok());
+        assert!(cors_layer(&CorsConfig {
+            allowed_origins: vec!["http://localhost:5173/".to_string()],
+            allowed_methods: vec!["get".to_string(), "PATCH".to_string()],
+        })
+        .is_ok());
+
+        let bad_origin = CorsConfig {
+            allowed_origins: vec!["http://localhost\n".to_string()],
+            ..CorsConfig::default()
+        };
+        assert!(cors_layer(&bad_origin).is_err());
+        let bad_method = CorsConfig {
+            allowed_methods: vec!["GET POST".to_string()],
+            ..CorsConfig::default()
+        };
+        assert!(cors_layer(&bad_method).is_err());
+    }
+}
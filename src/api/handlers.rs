@@ -1,24 +1,40 @@
 use axum::{
     Json,
+    body::Body,
     extract::{Path, Query},
-    http::StatusCode,
-    response::IntoResponse,
+    http::{HeaderMap, HeaderValue, StatusCode, header, header::InvalidHeaderValue},
+    response::{IntoResponse, Response},
 };
-use chrono::Local;
+use chrono::{Local, NaiveDate};
 use uuid::Uuid;
 
-use crate::project::{ProjectRegistry, DEFAULT_PROJECT_NAME};
-use crate::storage::file::{load_todo_list_for_project, save_todo_list_for_project};
-use crate::todo::TodoItem;
+use crate::project::{validate_project_name, ProjectRegistry, DEFAULT_PROJECT_NAME};
+use crate::storage::file::{
+    load_todo_list_with_revision, save_todo_list_if_current, trash_todos_if_current,
+};
+use crate::storage::database::{query_archived_days, search_todos, ArchiveFilter};
+use crate::storage::revision::stale_day;
+use crate::storage::sync::changes_since;
+use crate::todo::{Estimate, Priority, TodoItem, TodoList};
 
 use super::models::{
-    CreateTodoRequest, DateQuery, ErrorResponse, ProjectListResponse, ProjectResponse,
-    TodoListResponse, TodoResponse, UpdateTodoRequest, parse_state,
+    ArchiveQuery, ArchiveResponse, ArchivedDayResponse, ChangesQuery, ChangesResponse,
+    CreateProjectRequest, CreateTodoRequest, DateQuery, ErrorResponse, ErrorResponseExt,
+    ProjectListResponse, ProjectResponse, RenameProjectRequest, SearchQuery, SearchResponse,
+    TodoListResponse, TodoResponse, UpdateTodoRequest, change_response, parse_state,
+    project_response, search_hit_response, todo_response,
 };
 
+/// Most changes returned by one `GET /api/changes`
+const MAX_CHANGES: usize = 1000;
+/// Hits returned by one `GET /api/search`, unless it asks for fewer
+const MAX_SEARCH_HITS: usize = 200;
+/// Hits returned when `GET /api/search` sets no limit
+const DEFAULT_SEARCH_HITS: usize = 50;
+
 /// Helper to get project name with validation
 #[allow(clippy::result_large_err)]
-fn get_validated_project(project: Option<String>) -> Result<String, axum::response::Response<axum::body::Body>> {
+fn get_validated_project(project: Option<String>) -> Result<String, Response<Body>> {
     let project_name = project.unwrap_or_else(|| DEFAULT_PROJECT_NAME.to_string());
 
     // Validate project exists
@@ -34,79 +50,349 @@ fn get_validated_project(project: Option<String>) -> Result<String, axum::respon
     Ok(project_name)
 }
 
+#[utoipa::path(
+    get,
+    path = "/api/todos",
+    tag = "todos",
+    params(DateQuery),
+    responses(
+        (status = 200, body = TodoListResponse, headers(("ETag" = String, description = "The day's revision, quoted"))),
+        (status = 404, description = "No such project", body = ErrorResponse),
+    )
+)]
 pub async fn list_todos(Query(query): Query<DateQuery>) -> impl IntoResponse {
     let date = query.date.unwrap_or_else(|| Local::now().date_naive());
-    let project_name = match get_validated_project(query.project) {
-        Ok(p) => p,
-        Err(e) => return e,
-    };
+    match get_validated_project(query.project) {
+        Ok(project_name) => list_day(&project_name, date),
+        Err(e) => e,
+    }
+}
+
+#[utoipa::path(
+    post,
+    path = "/api/todos",
+    tag = "todos",
+    params(
+        DateQuery,
+        ("If-Match" = Option<String>, Header, description = "The `revision` of the day's list; the write is refused if the day changed since"),
+    ),
+    request_body = CreateTodoRequest,
+    responses(
+        (status = 201, body = TodoResponse),
+        (status = 400, description = "Invalid state or unknown `parent_id`/`after`", body = ErrorResponse),
+        (status = 404, description = "No such project", body = ErrorResponse),
+        (status = 409, description = "The day changed since the `If-Match` revision, or during the write", body = ErrorResponse),
+        (status = 422, description = "Invalid `link`", body = ErrorResponse),
+    )
+)]
+pub async fn create_todo(
+    Query(query): Query<DateQuery>,
+    headers: HeaderMap,
+    Json(req): Json<CreateTodoRequest>,
+) -> impl IntoResponse {
+    let date = query.date.unwrap_or_else(|| Local::now().date_naive());
+    match (get_validated_project(query.project), if_match(&headers)) {
+        (Ok(project_name), Ok(expected)) => create_in_day(&project_name, date, expected, req),
+        (Err(e), _) | (_, Err(e)) => e,
+    }
+}
 
-    match load_todo_list_for_project(&project_name, date) {
-        Ok(list) => {
+#[utoipa::path(
+    delete,
+    path = "/api/todos/{id}",
+    tag = "todos",
+    params(
+        ("id" = Uuid, Path),
+        DateQuery,
+        ("If-Match" = Option<String>, Header, description = "The `revision` of the day's list; the write is refused if the day changed since"),
+    ),
+    responses(
+        (status = 204, description = "Moved to the trash with its subtasks"),
+        (status = 404, description = "No such project or todo", body = ErrorResponse),
+        (status = 409, description = "The day changed since the `If-Match` revision, or during the write", body = ErrorResponse),
+    )
+)]
+pub async fn delete_todo(
+    Path(id): Path<Uuid>,
+    Query(query): Query<DateQuery>,
+    headers: HeaderMap,
+) -> impl IntoResponse {
+    let date = query.date.unwrap_or_else(|| Local::now().date_naive());
+    match (get_validated_project(query.project), if_match(&headers)) {
+        (Ok(project_name), Ok(expected)) => delete_in_day(&project_name, date, expected, id),
+        (Err(e), _) | (_, Err(e)) => e,
+    }
+}
+
+#[utoipa::path(
+    patch,
+    path = "/api/todos/{id}",
+    tag = "todos",
+    params(
+        ("id" = Uuid, Path),
+        DateQuery,
+        ("If-Match" = Option<String>, Header, description = "The `revision` of the day's list; the write is refused if the day changed since"),
+    ),
+    request_body = UpdateTodoRequest,
+    responses(
+        (status = 200, body = TodoResponse),
+        (status = 400, description = "Invalid state or priority", body = ErrorResponse),
+        (status = 404, description = "No such project or todo", body = ErrorResponse),
+        (status = 409, description = "The day changed since the `If-Match` revision, or during the write", body = ErrorResponse),
+        (status = 422, description = "Invalid `link`", body = ErrorResponse),
+    )
+)]
+pub async fn update_todo(
+    Path(id): Path<Uuid>,
+    Query(query): Query<DateQuery>,
+    headers: HeaderMap,
+    Json(req): Json<UpdateTodoRequest>,
+) -> impl IntoResponse {
+    let date = query.date.unwrap_or_else(|| Local::now().date_naive());
+    match (get_validated_project(query.project), if_match(&headers)) {
+        (Ok(project_name), Ok(expected)) => update_in_day(&project_name, date, expected, id, req),
+        (Err(e), _) | (_, Err(e)) => e,
+    }
+}
+
+#[utoipa::path(
+    get,
+    path = "/api/projects/{project}/days/{date}/todos",
+    tag = "todos",
+    params(("project" = String, Path), ("date" = NaiveDate, Path)),
+    responses(
+        (status = 200, body = TodoListResponse, headers(("ETag" = String, description = "The day's revision, quoted"))),
+        (status = 404, description = "No such project", body = ErrorResponse),
+    )
+)]
+pub async fn list_day_todos(Path((project, date)): Path<(String, NaiveDate)>) -> impl IntoResponse {
+    match get_validated_project(Some(project)) {
+        Ok(project_name) => list_day(&project_name, date),
+        Err(e) => e,
+    }
+}
+
+#[utoipa::path(
+    post,
+    path = "/api/projects/{project}/days/{date}/todos",
+    tag = "todos",
+    params(
+        ("project" = String, Path),
+        ("date" = NaiveDate, Path),
+        ("If-Match" = Option<String>, Header, description = "The `revision` of the day's list; the write is refused if the day changed since"),
+    ),
+    request_body = CreateTodoRequest,
+    responses(
+        (status = 201, body = TodoResponse),
+        (status = 400, description = "Invalid state or unknown `parent_id`/`after`", body = ErrorResponse),
+        (status = 404, description = "No such project", body = ErrorResponse),
+        (status = 409, description = "The day changed since the `If-Match` revision, or during the write", body = ErrorResponse),
+        (status = 422, description = "Invalid `link`", body = ErrorResponse),
+    )
+)]
+pub async fn create_day_todo(
+    Path((project, date)): Path<(String, NaiveDate)>,
+    headers: HeaderMap,
+    Json(req): Json<CreateTodoRequest>,
+) -> impl IntoResponse {
+    match (get_validated_project(Some(project)), if_match(&headers)) {
+        (Ok(project_name), Ok(expected)) => create_in_day(&project_name, date, expected, req),
+        (Err(e), _) | (_, Err(e)) => e,
+    }
+}
+
+#[utoipa::path(
+    patch,
+    path = "/api/projects/{project}/days/{date}/todos/{id}",
+    tag = "todos",
+    params(
+        ("project" = String, Path),
+        ("date" = NaiveDate, Path),
+        ("id" = Uuid, Path),
+        ("If-Match" = Option<String>, Header, description = "The `revision` of the day's list; the write is refused if the day changed since"),
+    ),
+    request_body = UpdateTodoRequest,
+    responses(
+        (status = 200, body = TodoResponse),
+        (status = 400, description = "Invalid state or priority", body = ErrorResponse),
+        (status = 404, description = "No such project or todo", body = ErrorResponse),
+        (status = 409, description = "The day changed since the `If-Match` revision, or during the write", body = ErrorResponse),
+        (status = 422, description = "Invalid `link`", body = ErrorResponse),
+    )
+)]
+pub async fn update_day_todo(
+    Path((project, date, id)): Path<(String, NaiveDate, Uuid)>,
+    headers: HeaderMap,
+    Json(req): Json<UpdateTodoRequest>,
+) -> impl IntoResponse {
+    match (get_validated_project(Some(project)), if_match(&headers)) {
+        (Ok(project_name), Ok(expected)) => update_in_day(&project_name, date, expected, id, req),
+        (Err(e), _) | (_, Err(e)) => e,
+    }
+}
+
+#[utoipa::path(
+    delete,
+    path = "/api/projects/{project}/days/{date}/todos/{id}",
+    tag = "todos",
+    params(
+        ("project" = String, Path),
+        ("date" = NaiveDate, Path),
+        ("id" = Uuid, Path),
+        ("If-Match" = Option<String>, Header, description = "The `revision` of the day's list; the write is refused if the day changed since"),
+    ),
+    responses(
+        (status = 204, description = "Moved to the trash with its subtasks"),
+        (status = 404, description = "No such project or todo", body = ErrorResponse),
+        (status = 409, description = "The day changed since the `If-Match` revision, or during the write", body = ErrorResponse),
+    )
+)]
+pub async fn delete_day_todo(
+    Path((project, date, id)): Path<(String, NaiveDate, Uuid)>,
+    headers: HeaderMap,
+) -> impl IntoResponse {
+    match (get_validated_project(Some(project)), if_match(&headers)) {
+        (Ok(project_name), Ok(expected)) => delete_in_day(&project_name, date, expected, id),
+        (Err(e), _) | (_, Err(e)) => e,
+    }
+}
+
+fn list_day(project_name: &str, date: NaiveDate) -> Response<Body> {
+    match load_todo_list_with_revision(project_name, date) {
+        Ok((list, revision)) => {
             let response = TodoListResponse {
                 date: list.date,
-                items: list.items.iter().map(TodoResponse::from).collect(),
+                items: list.items.iter().map(todo_response).collect(),
+                revision,
             };
-            (StatusCode::OK, Json(response)).into_response()
+            match etag(revision) {
+                Ok(tag) => ([(header::ETAG, tag)], Json(response)).into_response(),
+                Err(e) => ErrorResponse::internal(e),
+            }
         }
         Err(e) => ErrorResponse::internal(e),
     }
 }
 
-pub async fn create_todo(
-    Query(query): Query<DateQuery>,
-    Json(req): Json<CreateTodoRequest>,
-) -> impl IntoResponse {
-    let date = query.date.unwrap_or_else(|| Local::now().date_naive());
-    let project_name = match get_validated_project(query.project) {
-        Ok(p) => p,
-        Err(e) => return e,
+fn etag(revision: u64) -> Result<HeaderValue, InvalidHeaderValue> {
+    HeaderValue::try_from(format!("\"{revision}\""))
+}
+
+/// The revision a write was made against, from `If-Match`. Without one, or
+/// with `*`, the write goes to the day as it is.
+#[allow(clippy::result_large_err)]
+fn if_match(headers: &HeaderMap) -> Result<Option<u64>, Response<Body>> {
+    let Some(value) = headers.get(header::IF_MATCH) else {
+        return Ok(None);
     };
+    let value = value.to_str().unwrap_or_default().trim();
+    if value == "*" {
+        return Ok(None);
+    }
+    value
+        .trim_start_matches("W/")
+        .trim_matches('"')
+        .parse()
+        .map(Some)
+        .map_err(|_| ErrorResponse::bad_request("If-Match must be a revision from the day's list"))
+}
 
-    let mut list = match load_todo_list_for_project(&project_name, date) {
-        Ok(l) => l,
-        Err(e) => return ErrorResponse::internal(e),
+/// The day as loaded for a write, and the revision to save it against.
+#[allow(clippy::result_large_err)]
+fn load_for_write(
+    project_name: &str,
+    date: NaiveDate,
+    expected: Option<u64>,
+) -> Result<(TodoList, u64), Response<Body>> {
+    let (list, revision) = load_todo_list_with_revision(project_name, date).map_err(ErrorResponse::internal)?;
+    match expected {
+        Some(expected) if expected != revision => Err(stale(revision)),
+        _ => Ok((list, revision)),
+    }
+}
+
+/// 409 with the day's current revision in `ETag`.
+fn stale(revision: u64) -> Response<Body> {
+    let mut response = ErrorResponse::conflict("The list was changed elsewhere; load it again and retry");
+    if let Ok(tag) = etag(revision) {
+        response.headers_mut().insert(header::ETAG, tag);
+    }
+    response
+}
+
+fn save_failed(e: anyhow::Error) -> Response<Body> {
+    match stale_day(&e) {
+        Some(day) => stale(day.revision),
+        None => ErrorResponse::internal(e),
+    }
+}
+
+fn create_in_day(project_name: &str, date: NaiveDate, expected: Option<u64>, req: CreateTodoRequest) -> Response<Body> {
+    let (mut list, revision) = match load_for_write(project_name, date, expected) {
+        Ok(loaded) => loaded,
+        Err(e) => return e,
     };
 
-    let (indent_level, insert_index) = if let Some(parent_id) = req.parent_id {
+    let (indent_level, insert_index, parent_id) = if let Some(after) = req.after {
+        let Some(idx) = list.items.iter().position(|item| item.id == after) else {
+            return ErrorResponse::bad_request("Sibling not found");
+        };
+        let (_, end) = match list.get_item_range(idx) {
+            Ok(range) => range,
+            Err(e) => return ErrorResponse::internal(e),
+        };
+        (list.items[idx].indent_level, end, list.items[idx].parent_id)
+    } else if let Some(parent_id) = req.parent_id {
         match list.find_insert_position_for_child(parent_id) {
-            Some((indent, idx)) => (indent, idx),
+            Some((indent, idx)) => (indent, idx, Some(parent_id)),
             None => return ErrorResponse::bad_request("Parent not found"),
         }
     } else {
-        (0, list.items.len())
+        (0, list.items.len(), None)
     };
 
     let mut item = TodoItem::new(req.content, indent_level);
-    item.parent_id = req.parent_id;
+    item.parent_id = parent_id;
     item.due_date = req.due_date;
     item.description = req.description;
+    item.waiting_on = req.waiting_on;
+    item.pinned = req.pinned;
+    if let Some(link) = req.link
+        && let Err(e) = item.set_link(&link)
+    {
+        return ErrorResponse::unprocessable(e);
+    }
+    item.estimate = req.estimate_minutes.map(Estimate::from_minutes);
+    if let Some(priority) = req.priority {
+        match priority.parse::<Priority>() {
+            Ok(priority) => item.priority = Some(priority),
+            Err(e) => return ErrorResponse::bad_request(e),
+        }
+    }
+    if let Some(state_str) = req.state {
+        match parse_state(&state_str) {
+            Some(state) => item.set_state(state),
+            None => return invalid_state(&state_str),
+        }
+    }
 
-    let response = TodoResponse::from(&item);
+    let response = todo_response(&item);
     list.items.insert(insert_index, item);
 
-    if let Err(e) = save_todo_list_for_project(&list, &project_name) {
-        return ErrorResponse::internal(e);
+    if let Err(e) = save_todo_list_if_current(&list, project_name, revision) {
+        return save_failed(e);
     }
 
     (StatusCode::CREATED, Json(response)).into_response()
 }
 
-pub async fn delete_todo(
-    Path(id): Path<Uuid>,
-    Query(query): Query<DateQuery>,
-) -> impl IntoResponse {
-    let date = query.date.unwrap_or_else(|| Local::now().date_naive());
-    let project_name = match get_validated_project(query.project) {
-        Ok(p) => p,
+/// Deletes the item with its subtasks into the trash, as the TUI does.
+fn delete_in_day(project_name: &str, date: NaiveDate, expected: Option<u64>, id: Uuid) -> Response<Body> {
+    let (mut list, revision) = match load_for_write(project_name, date, expected) {
+        Ok(loaded) => loaded,
         Err(e) => return e,
     };
 
-    let mut list = match load_todo_list_for_project(&project_name, date) {
-        Ok(l) => l,
-        Err(e) => return ErrorResponse::internal(e),
-    };
-
     let Some(idx) = list.items.iter().position(|item| item.id == id) else {
         return ErrorResponse::not_found("Todo not found");
     };
@@ -116,32 +402,28 @@ pub async fn delete_todo(
         Err(e) => return ErrorResponse::internal(e),
     };
 
-    list.items.drain(start..end);
+    let ids: Vec<Uuid> = list.items.drain(start..end).map(|item| item.id).collect();
     list.recalculate_parent_ids();
 
-    if let Err(e) = save_todo_list_for_project(&list, &project_name) {
-        return ErrorResponse::internal(e);
+    if let Err(e) = trash_todos_if_current(&list, &ids, project_name, revision) {
+        return save_failed(e);
     }
 
     StatusCode::NO_CONTENT.into_response()
 }
 
-pub async fn update_todo(
-    Path(id): Path<Uuid>,
-    Query(query): Query<DateQuery>,
-    Json(req): Json<UpdateTodoRequest>,
-) -> impl IntoResponse {
-    let date = query.date.unwrap_or_else(|| Local::now().date_naive());
-    let project_name = match get_validated_project(query.project) {
-        Ok(p) => p,
+fn update_in_day(
+    project_name: &str,
+    date: NaiveDate,
+    expected: Option<u64>,
+    id: Uuid,
+    req: UpdateTodoRequest,
+) -> Response<Body> {
+    let (mut list, revision) = match load_for_write(project_name, date, expected) {
+        Ok(loaded) => loaded,
         Err(e) => return e,
     };
 
-    let mut list = match load_todo_list_for_project(&project_name, date) {
-        Ok(l) => l,
-        Err(e) => return ErrorResponse::internal(e),
-    };
-
     let Some(item) = list.items.iter_mut().find(|item| item.id == id) else {
         return ErrorResponse::not_found("Todo not found");
     };
@@ -152,12 +434,8 @@ pub async fn update_todo(
 
     if let Some(state_str) = req.state {
         match parse_state(&state_str) {
-            Some(state) => item.state = state,
-            None => {
-                return ErrorResponse::bad_request(format!(
-                    "Invalid state: {state_str}. Use ' ', 'x', '?', or '!'"
-                ));
-            }
+            Some(state) => item.set_state(state),
+            None => return invalid_state(&state_str),
         }
     }
 
@@ -166,29 +444,69 @@ pub async fn update_todo(
     }
 
     if let Some(description) = req.description {
-        item.description = if description.is_empty() {
-            None
-        } else {
-            Some(description)
+        item.description = non_empty(description);
+    }
+
+    if let Some(priority) = req.priority {
+        item.priority = match non_empty(priority).map(|p| p.parse::<Priority>()).transpose() {
+            Ok(priority) => priority,
+            Err(e) => return ErrorResponse::bad_request(e),
         };
     }
 
-    let response = TodoResponse::from(&*item);
+    if let Some(minutes) = req.estimate_minutes {
+        item.estimate = (minutes > 0).then(|| Estimate::from_minutes(minutes));
+    }
 
-    if let Err(e) = save_todo_list_for_project(&list, &project_name) {
-        return ErrorResponse::internal(e);
+    if let Some(waiting_on) = req.waiting_on {
+        item.waiting_on = non_empty(waiting_on);
+    }
+
+    if let Some(pinned) = req.pinned {
+        item.pinned = pinned;
+    }
+
+    if let Some(link) = req.link
+        && let Err(e) = item.set_link(&link)
+    {
+        return ErrorResponse::unprocessable(e);
+    }
+
+    if let Some(collapsed) = req.collapsed {
+        item.collapsed = collapsed;
+    }
+
+    let response = todo_response(item);
+
+    if let Err(e) = save_todo_list_if_current(&list, project_name, revision) {
+        return save_failed(e);
     }
 
     (StatusCode::OK, Json(response)).into_response()
 }
 
+/// Empty strings clear a field.
+fn non_empty(value: String) -> Option<String> {
+    (!value.is_empty()).then_some(value)
+}
+
+fn invalid_state(state: &str) -> Response<Body> {
+    ErrorResponse::bad_request(format!("Invalid state: {state}. Use ' ', 'x', '?', or '!'"))
+}
+
+#[utoipa::path(
+    get,
+    path = "/api/projects",
+    tag = "projects",
+    responses((status = 200, body = ProjectListResponse))
+)]
 pub async fn list_projects() -> impl IntoResponse {
     match ProjectRegistry::load() {
         Ok(registry) => {
             let projects: Vec<ProjectResponse> = registry
                 .list_sorted()
                 .iter()
-                .map(|p| ProjectResponse::from(*p))
+                .map(|p| project_response(p))
                 .collect();
             let response = ProjectListResponse { projects };
             (StatusCode::OK, Json(response)).into_response()
@@ -196,3 +514,422 @@ pub async fn list_projects() -> impl IntoResponse {
         Err(e) => ErrorResponse::internal(e),
     }
 }
+
+#[utoipa::path(
+    post,
+    path = "/api/projects",
+    tag = "projects",
+    request_body = CreateProjectRequest,
+    responses(
+        (status = 201, body = ProjectResponse),
+        (status = 400, description = "Invalid name", body = ErrorResponse),
+        (status = 409, description = "Name taken", body = ErrorResponse),
+    )
+)]
+pub async fn create_project(Json(req): Json<CreateProjectRequest>) -> impl IntoResponse {
+    create_project_named(&req.name)
+}
+
+#[utoipa::path(
+    patch,
+    path = "/api/projects/{name}",
+    tag = "projects",
+    params(("name" = String, Path)),
+    request_body = RenameProjectRequest,
+    responses(
+        (status = 200, body = ProjectResponse),
+        (status = 400, description = "Invalid new name", body = ErrorResponse),
+        (status = 403, description = "The default project", body = ErrorResponse),
+        (status = 404, description = "No such project", body = ErrorResponse),
+        (status = 409, description = "New name taken", body = ErrorResponse),
+    )
+)]
+pub async fn rename_project(
+    Path(name): Path<String>,
+    Json(req): Json<RenameProjectRequest>,
+) -> impl IntoResponse {
+    rename_project_to(&name, &req.name)
+}
+
+#[utoipa::path(
+    delete,
+    path = "/api/projects/{name}",
+    tag = "projects",
+    params(("name" = String, Path)),
+    responses(
+        (status = 204, description = "Deleted with its directory"),
+        (status = 403, description = "The default project", body = ErrorResponse),
+        (status = 404, description = "No such project", body = ErrorResponse),
+    )
+)]
+pub async fn delete_project(Path(name): Path<String>) -> impl IntoResponse {
+    delete_project_named(&name)
+}
+
+fn create_project_named(name: &str) -> Response<Body> {
+    if let Err(e) = validate_project_name(name) {
+        return ErrorResponse::bad_request(e.to_string());
+    }
+    let mut registry = match ProjectRegistry::load() {
+        Ok(r) => r,
+        Err(e) => return ErrorResponse::internal(e),
+    };
+    if registry.get_by_name(name).is_some() {
+        return ErrorResponse::conflict(format!("Project already exists: {name}"));
+    }
+
+    match registry.create(name) {
+        Ok(project) => (StatusCode::CREATED, Json(project_response(project))).into_response(),
+        Err(e) => ErrorResponse::internal(e),
+    }
+}
+
+fn rename_project_to(name: &str, new_name: &str) -> Response<Body> {
+    if name == DEFAULT_PROJECT_NAME {
+        return ErrorResponse::forbidden("The default project can't be renamed");
+    }
+    if let Err(e) = validate_project_name(new_name) {
+        return ErrorResponse::bad_request(e.to_string());
+    }
+    let mut registry = match ProjectRegistry::load() {
+        Ok(r) => r,
+        Err(e) => return ErrorResponse::internal(e),
+    };
+    if registry.get_by_name(name).is_none() {
+        return ErrorResponse::not_found(format!("Project not found: {name}"));
+    }
+    if registry.get_by_name(new_name).is_some() {
+        return ErrorResponse::conflict(format!("Project already exists: {new_name}"));
+    }
+
+    if let Err(e) = registry.rename(name, new_name) {
+        return ErrorResponse::internal(e);
+    }
+    match registry.get_by_name(new_name) {
+        Some(project) => (StatusCode::OK, Json(project_response(project))).into_response(),
+        None => ErrorResponse::internal("Renamed project went missing"),
+    }
+}
+
+fn delete_project_named(name: &str) -> Response<Body> {
+    if name == DEFAULT_PROJECT_NAME {
+        return ErrorResponse::forbidden("The default project can't be deleted");
+    }
+    let mut registry = match ProjectRegistry::load() {
+        Ok(r) => r,
+        Err(e) => return ErrorResponse::internal(e),
+    };
+    if registry.get_by_name(name).is_none() {
+        return ErrorResponse::not_found(format!("Project not found: {name}"));
+    }
+
+    match registry.delete(name) {
+        Ok(()) => StatusCode::NO_CONTENT.into_response(),
+        Err(e) => ErrorResponse::internal(e),
+    }
+}
+
+#[utoipa::path(
+    get,
+    path = "/api/projects/{project}/archive",
+    tag = "archive",
+    params(("project" = String, Path), ArchiveQuery),
+    responses(
+        (status = 200, body = ArchiveResponse),
+        (status = 400, description = "Invalid state, or `from` after `to`", body = ErrorResponse),
+        (status = 404, description = "No such project", body = ErrorResponse),
+    )
+)]
+pub async fn list_archive(
+    Path(project): Path<String>,
+    Query(query): Query<ArchiveQuery>,
+) -> impl IntoResponse {
+    match get_validated_project(Some(project)) {
+        Ok(project_name) => archive_of(project_name, query),
+        Err(e) => e,
+    }
+}
+
+fn archive_of(project: String, query: ArchiveQuery) -> Response<Body> {
+    let state = match query.state.as_deref().map(|s| (s, parse_state(s))) {
+        Some((s, None)) => return invalid_state(s),
+        Some((_, state)) => state,
+        None => None,
+    };
+    if let (Some(from), Some(to)) = (query.from, query.to)
+        && from > to
+    {
+        return ErrorResponse::bad_request(format!("from ({from}) is after to ({to})"));
+    }
+
+    let filter = ArchiveFilter {
+        project: Some(project.clone()),
+        from: query.from,
+        to: query.to,
+        state,
+        text: query.q,
+    };
+    match query_archived_days(&filter) {
+        Ok(days) => {
+            let days = days
+                .iter()
+                .map(|day| ArchivedDayResponse {
+                    date: day.date,
+                    items: day.items.iter().map(todo_response).collect(),
+                })
+                .collect();
+            (StatusCode::OK, Json(ArchiveResponse { project, days })).into_response()
+        }
+        Err(e) => ErrorResponse::internal(e),
+    }
+}
+
+#[utoipa::path(
+    get,
+    path = "/api/search",
+    tag = "todos",
+    params(SearchQuery),
+    description = "Search the content and descriptions of live and archived todos on every date, the same search the TUI and `totui search` use.",
+    responses(
+        (status = 200, body = SearchResponse),
+        (status = 400, description = "The query has no words", body = ErrorResponse),
+        (status = 404, description = "No such project", body = ErrorResponse),
+    )
+)]
+pub async fn search(Query(query): Query<SearchQuery>) -> impl IntoResponse {
+    search_for(query)
+}
+
+fn search_for(query: SearchQuery) -> Response<Body> {
+    if query.q.trim().is_empty() {
+        return ErrorResponse::bad_request("q needs at least one word to search for");
+    }
+    let project = match query.project {
+        Some(project) => match get_validated_project(Some(project)) {
+            Ok(project) => Some(project),
+            Err(e) => return e,
+        },
+        None => None,
+    };
+    let limit = query.limit.unwrap_or(DEFAULT_SEARCH_HITS).clamp(1, MAX_SEARCH_HITS);
+
+    match search_todos(&query.q, project.as_deref(), limit) {
+        Ok(hits) => {
+            let hits = hits.iter().map(search_hit_response).collect();
+            (StatusCode::OK, Json(SearchResponse { hits })).into_response()
+        }
+        Err(e) => ErrorResponse::internal(e),
+    }
+}
+
+#[utoipa::path(
+    get,
+    path = "/api/changes",
+    tag = "sync",
+    params(ChangesQuery),
+    responses((status = 200, body = ChangesResponse))
+)]
+pub async fn list_changes(Query(query): Query<ChangesQuery>) -> impl IntoResponse {
+    let limit = query.limit.unwrap_or(MAX_CHANGES).clamp(1, MAX_CHANGES);
+    match changes_since(query.since, query.project.as_deref(), limit) {
+        Ok(set) => {
+            let response = ChangesResponse {
+                changes: set.changes.iter().map(change_response).collect(),
+                cursor: set.cursor,
+                has_more: set.has_more,
+            };
+            (StatusCode::OK, Json(response)).into_response()
+        }
+        Err(e) => ErrorResponse::internal(e),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::storage::database::{init_database, load_deleted_todos_for_project};
+    use crate::storage::file::load_todo_list_for_project;
+    use serial_test::serial;
+    use tempfile::TempDir;
+
+    fn create(date: NaiveDate, req: CreateTodoRequest) -> Uuid {
+        let response = create_in_day("default", date, None, req);
+        assert_eq!(response.status(), StatusCode::CREATED);
+        load_todo_list_for_project("default", date).unwrap().items.last().unwrap().id
+    }
+
+    #[test]
+    #[serial]
+    fn test_day_todos_nest_and_go_to_the_trash() {
+        let temp_dir = TempDir::new().unwrap();
+        std::fs::create_dir_all(temp_dir.path().join(".to-tui")).unwrap();
+        unsafe {
+            std::env::set_var("HOME", temp_dir.path());
+        }
+        init_database().unwrap();
+        let date = NaiveDate::from_ymd_opt(2026, 1, 18).unwrap();
+
+        let release = create(date, CreateTodoRequest::new("Release"));
+        create(date, CreateTodoRequest::new("Groceries"));
+        let docs = CreateTodoRequest {
+            parent_id: Some(release),
+            priority: Some("P1".to_string()),
+            ..CreateTodoRequest::new("Docs")
+        };
+        assert_eq!(create_in_day("default", date, None, docs).status(), StatusCode::CREATED);
+        let changelog = CreateTodoRequest {
+            after: Some(release),
+            ..CreateTodoRequest::new("Changelog")
+        };
+        assert_eq!(create_in_day("default", date, None, changelog).status(), StatusCode::CREATED);
+
+        let list = load_todo_list_for_project("default", date).unwrap();
+        let contents: Vec<_> = list.items.iter().map(|item| item.content.as_str()).collect();
+        assert_eq!(contents, vec!["Release", "Docs", "Changelog", "Groceries"]);
+        assert_eq!(list.items[1].parent_id, Some(release));
+        assert_eq!(list.items[1].priority, Some(Priority::P1));
+        assert_eq!(list.items[2].indent_level, 0);
+
+        let bad = CreateTodoRequest {
+            priority: Some("urgent".to_string()),
+            ..CreateTodoRequest::new("Nope")
+        };
+        assert_eq!(create_in_day("default", date, None, bad).status(), StatusCode::BAD_REQUEST);
+
+        assert_eq!(delete_in_day("default", date, None, release).status(), StatusCode::NO_CONTENT);
+        let list = load_todo_list_for_project("default", date).unwrap();
+        assert_eq!(list.items.len(), 2);
+        let trash: Vec<_> = load_deleted_todos_for_project("default")
+            .unwrap()
+            .into_iter()
+            .map(|deleted| deleted.item.content)
+            .collect();
+        assert_eq!(trash, vec!["Release", "Docs"]);
+    }
+
+    #[test]
+    #[serial]
+    fn test_links_are_checked_like_in_the_tui() {
+        let temp_dir = TempDir::new().unwrap();
+        std::fs::create_dir_all(temp_dir.path().join(".to-tui")).unwrap();
+        unsafe {
+            std::env::set_var("HOME", temp_dir.path());
+        }
+        init_database().unwrap();
+        let date = NaiveDate::from_ymd_opt(2026, 1, 18).unwrap();
+
+        let bad = CreateTodoRequest {
+            link: Some("not a link".to_string()),
+            ..CreateTodoRequest::new("Review")
+        };
+        assert_eq!(create_in_day("default", date, None, bad).status(), StatusCode::UNPROCESSABLE_ENTITY);
+        let review = create(
+            date,
+            CreateTodoRequest {
+                link: Some("github.com/org/repo/pull/7".to_string()),
+                ..CreateTodoRequest::new("Review")
+            },
+        );
+
+        let bad = UpdateTodoRequest {
+            link: Some("PROJ-123".to_string()),
+            ..Default::default()
+        };
+        assert_eq!(update_in_day("default", date, None, review, bad).status(), StatusCode::UNPROCESSABLE_ENTITY);
+        let list = load_todo_list_for_project("default", date).unwrap();
+        assert_eq!(list.items.len(), 1);
+        assert_eq!(list.items[0].link.as_deref(), Some("https://github.com/org/repo/pull/7"));
+
+        let clear = UpdateTodoRequest {
+            link: Some(String::new()),
+            ..Default::default()
+        };
+        assert_eq!(update_in_day("default", date, None, review, clear).status(), StatusCode::OK);
+        assert_eq!(load_todo_list_for_project("default", date).unwrap().items[0].link, None);
+    }
+
+    #[test]
+    #[serial]
+    fn test_writes_against_an_old_revision_are_refused() {
+        let temp_dir = TempDir::new().unwrap();
+        std::fs::create_dir_all(temp_dir.path().join(".to-tui")).unwrap();
+        unsafe {
+            std::env::set_var("HOME", temp_dir.path());
+        }
+        init_database().unwrap();
+        let date = NaiveDate::from_ymd_opt(2026, 1, 18).unwrap();
+        let milk = create(date, CreateTodoRequest::new("Milk"));
+        let (_, revision) = load_todo_list_with_revision("default", date).unwrap();
+
+        let response = create_in_day("default", date, Some(revision), CreateTodoRequest::new("Eggs"));
+        assert_eq!(response.status(), StatusCode::CREATED);
+        let response = create_in_day("default", date, Some(revision), CreateTodoRequest::new("Bread"));
+        assert_eq!(response.status(), StatusCode::CONFLICT);
+        let (_, current) = load_todo_list_with_revision("default", date).unwrap();
+        assert_eq!(response.headers()[header::ETAG], etag(current).unwrap());
+        assert_eq!(delete_in_day("default", date, Some(revision), milk).status(), StatusCode::CONFLICT);
+        assert_eq!(delete_in_day("default", date, Some(current), milk).status(), StatusCode::NO_CONTENT);
+        let (list, _) = load_todo_list_with_revision("default", date).unwrap();
+        assert!(list.items.iter().all(|item| item.id != milk));
+        let trash = crate::storage::database::load_deleted_todos_for_project("default").unwrap();
+        assert!(trash.iter().any(|deleted| deleted.item.id == milk));
+
+        let mut headers = HeaderMap::new();
+        assert_eq!(if_match(&headers).unwrap(), None);
+        headers.insert(header::IF_MATCH, HeaderValue::from_static("\"12\""));
+        assert_eq!(if_match(&headers).unwrap(), Some(12));
+        headers.insert(header::IF_MATCH, HeaderValue::from_static("*"));
+        assert_eq!(if_match(&headers).unwrap(), None);
+        headers.insert(header::IF_MATCH, HeaderValue::from_static("\"abc\""));
+        assert_eq!(if_match(&headers).unwrap_err().status(), StatusCode::BAD_REQUEST);
+    }
+
+    #[test]
+    #[serial]
+    fn test_search_checks_the_query_and_project() {
+        let temp_dir = TempDir::new().unwrap();
+        std::fs::create_dir_all(temp_dir.path().join(".to-tui")).unwrap();
+        unsafe {
+            std::env::set_var("HOME", temp_dir.path());
+        }
+        init_database().unwrap();
+        ProjectRegistry::load().unwrap().ensure_default_project().unwrap();
+        let date = NaiveDate::from_ymd_opt(2026, 1, 18).unwrap();
+        create(date, CreateTodoRequest::new("Fix the login page"));
+
+        assert_eq!(search_for(SearchQuery::new("logi")).status(), StatusCode::OK);
+
+        assert_eq!(search_for(SearchQuery::new("  ")).status(), StatusCode::BAD_REQUEST);
+        let missing = SearchQuery {
+            project: Some("Missing".to_string()),
+            ..SearchQuery::new("login")
+        };
+        assert_eq!(search_for(missing).status(), StatusCode::NOT_FOUND);
+    }
+
+    #[test]
+    #[serial]
+    fn test_project_routes_guard_the_default_project() {
+        let temp_dir = TempDir::new().unwrap();
+        std::fs::create_dir_all(temp_dir.path().join(".to-tui")).unwrap();
+        unsafe {
+            std::env::set_var("HOME", temp_dir.path());
+        }
+        init_database().unwrap();
+        ProjectRegistry::load().unwrap().ensure_default_project().unwrap();
+
+        assert_eq!(create_project_named("Work").status(), StatusCode::CREATED);
+        assert_eq!(create_project_named("Work").status(), StatusCode::CONFLICT);
+        assert_eq!(create_project_named("../etc").status(), StatusCode::BAD_REQUEST);
+
+        assert_eq!(rename_project_to("default", "Home").status(), StatusCode::FORBIDDEN);
+        assert_eq!(rename_project_to("Missing", "Home").status(), StatusCode::NOT_FOUND);
+        assert_eq!(rename_project_to("Work", "default").status(), StatusCode::CONFLICT);
+        assert_eq!(rename_project_to("Work", "Side work").status(), StatusCode::OK);
+
+        assert_eq!(delete_project_named("default").status(), StatusCode::FORBIDDEN);
+        assert_eq!(delete_project_named("Side work").status(), StatusCode::NO_CONTENT);
+        let names: Vec<String> = ProjectRegistry::load().unwrap().projects.into_iter().map(|p| p.name).collect();
+        assert_eq!(names, vec!["default"]);
+    }
+}
@@ -0,0 +1,241 @@
+//! `Idempotency-Key` on POST and PATCH: the first successful response to a
+//! key is recorded, and a retry with the same key and request gets it back
+//! without the write happening again.
+//!
+//! Failed requests aren't recorded, so a client can fix the request and
+//! retry under the same key. A key reused for a different request gets 422,
+//! and one whose first request is still running gets 409.
+
+use std::collections::HashSet;
+use std::sync::{Arc, Mutex};
+
+use axum::{
+    Json, Router,
+    body::{Body, to_bytes},
+    extract::{Request, State},
+    http::{HeaderName, HeaderValue, Method, StatusCode, header},
+    middleware::{self, Next},
+    response::{IntoResponse, Response},
+};
+use sha2::{Digest, Sha256};
+
+use crate::storage::idempotency::{StoredResponse, load_response, save_response};
+
+use super::models::{ErrorResponse, ErrorResponseExt};
+
+const IDEMPOTENCY_KEY: HeaderName = HeaderName::from_static("idempotency-key");
+/// Set on responses that were recorded earlier rather than made now
+const IDEMPOTENT_REPLAYED: HeaderName = HeaderName::from_static("idempotent-replayed");
+const MAX_KEY_LEN: usize = 255;
+
+struct Idempotency {
+    /// Keys whose first request is still running
+    in_flight: Mutex<HashSet<String>>,
+    max_body_bytes: usize,
+}
+
+/// Wrap `router` so writes honor `Idempotency-Key`. Request bodies are read
+/// up to `max_body_bytes` to tell requests apart.
+pub fn apply(router: Router, max_body_bytes: usize) -> Router {
+    let state = Arc::new(Idempotency {
+        in_flight: Mutex::new(HashSet::new()),
+        max_body_bytes,
+    });
+    router.layer(middleware::from_fn_with_state(state, idempotent))
+}
+
+async fn idempotent(State(state): State<Arc<Idempotency>>, request: Request, next: Next) -> Response<Body> {
+    if !matches!(*request.method(), Method::POST | Method::PATCH) {
+        return next.run(request).await;
+    }
+    let Some(key) = request.headers().get(IDEMPOTENCY_KEY) else {
+        return next.run(request).await;
+    };
+    let key = match key.to_str().map(str::trim) {
+        Ok(key) if !key.is_empty() && key.len() <= MAX_KEY_LEN => key.to_string(),
+        _ => return ErrorResponse::bad_request(format!("Idempotency-Key must be 1 to {MAX_KEY_LEN} visible characters")),
+    };
+
+    let (parts, body) = request.into_parts();
+    let Ok(body) = to_bytes(body, state.max_body_bytes).await else {
+        return (
+            StatusCode::PAYLOAD_TOO_LARGE,
+            Json(ErrorResponse::new("Request body is too large")),
+        )
+            .into_response();
+    };
+    let request_hash = {
+        let mut hash = Sha256::new();
+        hash.update(parts.method.as_str());
+        hash.update([0]);
+        hash.update(parts.uri.to_string());
+        hash.update([0]);
+        hash.update(&body);
+        format!("{:x}", hash.finalize())
+    };
+
+    let Some(_claim) = Claim::take(&state, &key) else {
+        return ErrorResponse::conflict("A request with this Idempotency-Key is still in progress");
+    };
+    match load_response(&key) {
+        Ok(Some(stored)) if stored.request_hash == request_hash => return replay(stored),
+        Ok(Some(_)) => {
+            return (
+                StatusCode::UNPROCESSABLE_ENTITY,
+                Json(ErrorResponse::new("This Idempotency-Key was already used for a different request")),
+            )
+                .into_response();
+        }
+        Ok(None) => {}
+        Err(e) => return ErrorResponse::internal(e),
+    }
+
+    let response = next.run(Request::from_parts(parts, Body::from(body))).await;
+    if !response.status().is_success() {
+        return response;
+    }
+    let (parts, body) = response.into_parts();
+    let body = match to_bytes(body, usize::MAX).await {
+        Ok(body) => body,
+        Err(e) => return ErrorResponse::internal(e),
+    };
+    let stored = StoredResponse {
+        request_hash,
+        status: parts.status.as_u16(),
+        content_type: parts
+            .headers
+            .get(header::CONTENT_TYPE)
+            .and_then(|value| value.to_str().ok())
+            .map(str::to_string),
+        body: body.to_vec(),
+    };
+    // The write went through either way, so a failure here only costs the
+    // client its protection against a retry
+    if let Err(e) = save_response(&key, &stored) {
+        tracing::warn!("Failed to record Idempotency-Key response: {e:#}");
+    }
+    Response::from_parts(parts, Body::from(body))
+}
+
+fn replay(stored: StoredResponse) -> Response<Body> {
+    let status = StatusCode::from_u16(stored.status).unwrap_or(StatusCode::OK);
+    let mut response = (status, stored.body).into_response();
+    let headers = response.headers_mut();
+    if let Some(content_type) = stored.content_type.and_then(|value| HeaderValue::from_str(&value).ok()) {
+        headers.insert(header::CONTENT_TYPE, content_type);
+    }
+    headers.insert(IDEMPOTENT_REPLAYED, HeaderValue::from_static("true"));
+    response
+}
+
+/// A key marked in flight until dropped.
+struct Claim<'a> {
+    state: &'a Idempotency,
+    key: String,
+}
+
+impl<'a> Claim<'a> {
+    fn take(state: &'a Idempotency, key: &str) -> Option<Self> {
+        let mut in_flight = state.in_flight.lock().unwrap_or_else(|e| e.into_inner());
+        in_flight.insert(key.to_string()).then(|| Self {
+            state,
+            key: key.to_string(),
+        })
+    }
+}
+
+impl Drop for Claim<'_> {
+    fn drop(&mut self) {
+        let mut in_flight = self.state.in_flight.lock().unwrap_or_else(|e| e.into_inner());
+        in_flight.remove(&self.key);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::storage::database::init_database;
+    use axum::routing::post;
+    use serial_test::serial;
+    use std::sync::atomic::{AtomicUsize, Ordering};
+    use tempfile::TempDir;
+    use tower::ServiceExt;
+
+    /// A router whose `/api/todos` write counts how often it ran, and whose
+    /// `/api/broken` write always fails.
+    fn counting_router(writes: Arc<AtomicUsize>) -> Router {
+        let created = writes.clone();
+        let router = Router::new()
+            .route(
+                "/api/todos",
+                post(move || async move {
+                    let n = created.fetch_add(1, Ordering::SeqCst) + 1;
+                    (StatusCode::CREATED, format!("write {n}"))
+                }),
+            )
+            .route(
+                "/api/broken",
+                post(move || async move {
+                    writes.fetch_add(1, Ordering::SeqCst);
+                    ErrorResponse::internal("disk full")
+                }),
+            );
+        apply(router, 1024)
+    }
+
+    async fn send(router: &Router, path: &str, key: &str, body: &'static str) -> (StatusCode, bool, String) {
+        let request = Request::post(path)
+            .header(IDEMPOTENCY_KEY, key)
+            .body(Body::from(body))
+            .unwrap();
+        let response = router.clone().oneshot(request).await.unwrap();
+        let status = response.status();
+        let replayed = response.headers().contains_key(IDEMPOTENT_REPLAYED);
+        let body = to_bytes(response.into_body(), usize::MAX).await.unwrap();
+        (status, replayed, String::from_utf8_lossy(&body).into_owned())
+    }
+
+    #[tokio::test]
+    #[serial]
+    async fn test_repeated_keys_get_the_recorded_response() {
+        let temp_dir = TempDir::new().unwrap();
+        std::fs::create_dir_all(temp_dir.path().join(".to-tui")).unwrap();
+        unsafe {
+            std::env::set_var("HOME", temp_dir.path());
+        }
+        init_database().unwrap();
+        let writes = Arc::new(AtomicUsize::new(0));
+        let router = counting_router(writes.clone());
+
+        let first = send(&router, "/api/todos", "k1", r#"{"content":"Milk"}"#).await;
+        assert_eq!(first, (StatusCode::CREATED, false, "write 1".to_string()));
+        let retry = send(&router, "/api/todos", "k1", r#"{"content":"Milk"}"#).await;
+        assert_eq!(retry, (StatusCode::CREATED, true, "write 1".to_string()));
+        assert_eq!(writes.load(Ordering::SeqCst), 1);
+
+        // The same key for another request is refused without writing
+        let reused = send(&router, "/api/todos", "k1", r#"{"content":"Eggs"}"#).await;
+        assert_eq!(reused.0, StatusCode::UNPROCESSABLE_ENTITY);
+        assert_eq!(writes.load(Ordering::SeqCst), 1);
+
+        // Failures aren't recorded, so a retry runs again
+        let failed = send(&router, "/api/broken", "k2", "{}").await;
+        assert_eq!(failed.0, StatusCode::INTERNAL_SERVER_ERROR);
+        let retry = send(&router, "/api/broken", "k2", "{}").await;
+        assert_eq!((retry.0, retry.1), (StatusCode::INTERNAL_SERVER_ERROR, false));
+        assert_eq!(writes.load(Ordering::SeqCst), 3);
+    }
+
+    #[test]
+    fn test_a_key_is_claimed_once_at_a_time() {
+        let state = Idempotency {
+            in_flight: Mutex::new(HashSet::new()),
+            max_body_bytes: 1024,
+        };
+        let claim = Claim::take(&state, "key").unwrap();
+        assert!(Claim::take(&state, "key").is_none());
+        assert!(Claim::take(&state, "other").is_some());
+        drop(claim);
+        assert!(Claim::take(&state, "key").is_some());
+    }
+}
@@ -0,0 +1,209 @@
+//! Request limits, so a runaway client can't keep SQLite busy or fill the
+//! disk: a rate limit per client and a cap on request bodies.
+//!
+//! Clients are told apart by their address, which unlike a header they
+//! can't change from one request to the next. Everything over a Unix socket
+//! counts as one client.
+
+use std::collections::HashMap;
+use std::net::SocketAddr;
+use std::sync::{Arc, Mutex};
+use std::time::{Duration, Instant};
+
+use axum::{
+    Json, Router,
+    body::Body,
+    extract::{ConnectInfo, DefaultBodyLimit, Request, State},
+    http::{StatusCode, header},
+    middleware::{self, Next},
+    response::{IntoResponse, Response},
+};
+
+use crate::config::ServerConfig;
+
+use super::models::ErrorResponse;
+
+/// Past this many clients, ones that have been quiet long enough to have a
+/// full allowance again are forgotten, and if that isn't enough, the one
+/// heard from longest ago.
+const MAX_TRACKED_CLIENTS: usize = 4096;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Limits {
+    /// 0 means no limit
+    pub requests_per_minute: u32,
+    pub max_body_bytes: usize,
+}
+
+impl From<&ServerConfig> for Limits {
+    fn from(config: &ServerConfig) -> Self {
+        Self {
+            requests_per_minute: config.rate_limit_per_minute,
+            max_body_bytes: config.max_body_kb.saturating_mul(1024),
+        }
+    }
+}
+
+impl Limits {
+    /// Wrap `router` in the body cap and, when there is one, the rate limit.
+    pub fn apply(self, router: Router) -> Router {
+        let router = router.layer(DefaultBodyLimit::max(self.max_body_bytes));
+        if self.requests_per_minute == 0 {
+            return router;
+        }
+        let limiter = Arc::new(RateLimiter::new(self.requests_per_minute));
+        router.layer(middleware::from_fn_with_state(limiter, rate_limit))
+    }
+}
+
+/// A token bucket per client, refilled continuously at the per-minute rate.
+#[derive(Debug)]
+struct RateLimiter {
+    capacity: f64,
+    per_second: f64,
+    buckets: Mutex<HashMap<String, Bucket>>,
+}
+
+#[derive(Debug)]
+struct Bucket {
+    tokens: f64,
+    updated: Instant,
+}
+
+impl RateLimiter {
+    fn new(requests_per_minute: u32) -> Self {
+        let capacity = f64::from(requests_per_minute);
+        Self {
+            capacity,
+            per_second: capacity / 60.0,
+            buckets: Mutex::new(HashMap::new()),
+        }
+    }
+
+    /// Take a token for `client`, or say how long until one is free.
+    fn check(&self, client: &str, now: Instant) -> Result<(), Duration> {
+        let mut buckets = self.buckets.lock().unwrap_or_else(|e| e.into_inner());
+
+        if buckets.len() >= MAX_TRACKED_CLIENTS && !buckets.contains_key(client) {
+            let refill_time = Duration::from_secs_f64(self.capacity / self.per_second);
+            buckets.retain(|_, bucket| now.duration_since(bucket.updated) < refill_time);
+            if buckets.len() >= MAX_TRACKED_CLIENTS {
+                let oldest = buckets
+                    .iter()
+                    .min_by_key(|(_, bucket)| bucket.updated)
+                    .map(|(client, _)| client.clone());
+                if let Some(oldest) = oldest {
+                    buckets.remove(&oldest);
+                }
+            }
+        }
+
+        let bucket = buckets.entry(client.to_string()).or_insert(Bucket {
+            tokens: self.capacity,
+            updated: now,
+        });
+        let elapsed = now.duration_since(bucket.updated).as_secs_f64();
+        bucket.tokens = (bucket.tokens + elapsed * self.per_second).min(self.capacity);
+        bucket.updated = now;
+
+        if bucket.tokens >= 1.0 {
+            bucket.tokens -= 1.0;
+            Ok(())
+        } else {
+            Err(Duration::from_secs_f64((1.0 - bucket.tokens) / self.per_second))
+        }
+    }
+}
+
+async fn rate_limit(State(limiter): State<Arc<RateLimiter>>, request: Request, next: Next) -> Response<Body> {
+    // Health checks decide whether the server is up, so they are never refused
+    if request.uri().path() == "/api/health" {
+        return next.run(request).await;
+    }
+
+    match limiter.check(&client_key(&request), Instant::now()) {
+        Ok(()) => next.run(request).await,
+        Err(wait) => {
+            let retry_after = (wait.as_secs_f64().ceil() as u64).max(1);
+            (
+                StatusCode::TOO_MANY_REQUESTS,
+                [(header::RETRY_AFTER, retry_after.to_string())],
+                Json(ErrorResponse::new(format!(
+                    "Too many requests, try again in {retry_after}s"
+                ))),
+            )
+                .into_response()
+        }
+    }
+}
+
+fn client_key(request: &Request) -> String {
+    match request.extensions().get::<ConnectInfo<SocketAddr>>() {
+        Some(ConnectInfo(addr)) => format!("address {}", addr.ip()),
+        None => "local".to_string(),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_rate_limiter_refills_per_client() {
+        let limiter = RateLimiter::new(2);
+        let start = Instant::now();
+
+        assert!(limiter.check("a", start).is_ok());
+        assert!(limiter.check("a", start).is_ok());
+        let wait = limiter.check("a", start).unwrap_err();
+        assert_eq!(wait, Duration::from_secs(30));
+        // Another client has its own allowance
+        assert!(limiter.check("b", start).is_ok());
+
+        assert!(limiter.check("a", start + Duration::from_secs(29)).is_err());
+        assert!(limiter.check("a", start + Duration::from_secs(31)).is_ok());
+    }
+
+    #[test]
+    fn test_rate_limiter_stays_bounded() {
+        let limiter = RateLimiter::new(2);
+        let start = Instant::now();
+
+        for i in 0..MAX_TRACKED_CLIENTS + 10 {
+            assert!(limiter.check(&format!("client {i}"), start + Duration::from_millis(i as u64)).is_ok());
+        }
+
+        let buckets = limiter.buckets.lock().unwrap();
+        assert_eq!(buckets.len(), MAX_TRACKED_CLIENTS);
+        assert!(!buckets.contains_key("client 0"));
+        assert!(buckets.contains_key(&format!("client {}", MAX_TRACKED_CLIENTS + 9)));
+    }
+
+    #[tokio::test]
+    async fn test_rotating_authorization_headers_share_an_allowance() {
+        use axum::routing::get;
+        use tokio::io::{AsyncReadExt, AsyncWriteExt};
+
+        let limits = Limits { requests_per_minute: 2, max_body_bytes: 1024 };
+        let app = limits.apply(Router::new().route("/api/todos", get(|| async { "[]" })));
+        let listener = tokio::net::TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let addr = listener.local_addr().unwrap();
+        tokio::spawn(async move {
+            axum::serve(listener, app.into_make_service_with_connect_info::<SocketAddr>()).await
+        });
+
+        let mut statuses = Vec::new();
+        for i in 0..3 {
+            let mut stream = tokio::net::TcpStream::connect(addr).await.unwrap();
+            let request = format!(
+                "GET /api/todos HTTP/1.1\r\nHost: localhost\r\nAuthorization: Bearer guess-{i}\r\nConnection: close\r\n\r\n"
+            );
+            stream.write_all(request.as_bytes()).await.unwrap();
+            let mut response = String::new();
+            stream.read_to_string(&mut response).await.unwrap();
+            statuses.push(response.split(' ').nth(1).unwrap_or_default().to_string());
+        }
+
+        assert_eq!(statuses, ["200", "200", "429"]);
+    }
+}
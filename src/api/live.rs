@@ -0,0 +1,314 @@
+//! Live updates over `/api/ws` and `/api/events`.
+//!
+//! The server watches the database the same way the TUI does. Whenever it is
+//! written to, by this server or any other process, the history events
+//! recorded since the last look are sent to every connected client: as JSON
+//! text messages on a WebSocket, or as server-sent events for clients behind
+//! proxies that get in the way of WebSockets. Event ids are history ids, so
+//! a client that reconnects with `Last-Event-ID` gets what it missed.
+
+use std::collections::VecDeque;
+use std::convert::Infallible;
+use std::sync::Arc;
+
+use anyhow::Result;
+use axum::{
+    Extension,
+    extract::{
+        Query,
+        ws::{Message, WebSocket, WebSocketUpgrade},
+    },
+    http::HeaderMap,
+    response::{
+        Response,
+        sse::{self, KeepAlive, Sse},
+    },
+};
+use futures_util::{Stream, stream};
+use tokio::sync::{broadcast, mpsc};
+use tracing::warn;
+
+use crate::storage::history::{load_events, Event, HistoryQuery};
+use crate::storage::watcher::watch_database;
+
+use super::models::{live_event, LiveEvent, LiveQuery};
+
+/// Events a socket may fall behind by before it starts missing some
+const BACKLOG: usize = 256;
+
+/// The feed every socket subscribes to.
+#[derive(Clone)]
+pub struct LiveUpdates {
+    events: broadcast::Sender<Arc<LiveEvent>>,
+}
+
+impl LiveUpdates {
+    /// Start following the database. Must be called within the tokio runtime.
+    pub fn start() -> Self {
+        let (events, _) = broadcast::channel(BACKLOG);
+        let (tx, mut rx) = mpsc::unbounded_channel();
+        let watcher = watch_database(move || {
+            let _ = tx.send(());
+        });
+        if watcher.is_none() {
+            warn!("Could not watch the database, /api/ws will not send updates");
+        }
+
+        let sender = events.clone();
+        tokio::spawn(async move {
+            let _watcher = watcher;
+            let mut cursor = latest_event_id().unwrap_or(0);
+            while rx.recv().await.is_some() {
+                // One write touches the database and its WAL several times
+                while rx.try_recv().is_ok() {}
+                match events_after(cursor) {
+                    Ok(events) => {
+                        for event in events {
+                            cursor = event.id;
+                            if let Some(live) = live_event(&event) {
+                                let _ = sender.send(Arc::new(live));
+                            }
+                        }
+                    }
+                    Err(e) => warn!("Could not read new history events: {e}"),
+                }
+            }
+        });
+
+        Self { events }
+    }
+}
+
+fn latest_event_id() -> Result<i64> {
+    let query = HistoryQuery {
+        limit: 1,
+        ..HistoryQuery::default()
+    };
+    Ok(load_events(&query)?.first().map_or(0, |event| event.id))
+}
+
+/// Events recorded after the one with id `cursor`, oldest first.
+fn events_after(cursor: i64) -> Result<Vec<Event>> {
+    let query = HistoryQuery {
+        after: Some(cursor),
+        ..HistoryQuery::default()
+    };
+    let mut events = load_events(&query)?;
+    events.reverse();
+    Ok(events)
+}
+
+/// What one client is sent: the events it missed, if it said where it left
+/// off, then new ones as they come.
+struct Feed {
+    missed: VecDeque<Arc<LiveEvent>>,
+    events: broadcast::Receiver<Arc<LiveEvent>>,
+    /// Id of the last event sent
+    sent: i64,
+    project: Option<String>,
+}
+
+impl Feed {
+    /// Subscribes before reading what was missed, so nothing falls in between;
+    /// events read twice are skipped by id.
+    fn new(live: &LiveUpdates, last_event_id: Option<i64>, project: Option<String>) -> Self {
+        let mut feed = Self {
+            missed: VecDeque::new(),
+            events: live.events.subscribe(),
+            sent: last_event_id.unwrap_or(0),
+            project,
+        };
+        if last_event_id.is_some() {
+            feed.catch_up();
+        }
+        feed
+    }
+
+    fn catch_up(&mut self) {
+        match events_after(self.sent) {
+            Ok(events) => self.missed = events.iter().filter_map(live_event).map(Arc::new).collect(),
+            Err(e) => warn!("Could not read missed history events: {e}"),
+        }
+    }
+
+    /// The next event for this client; None once the server stops.
+    async fn next(&mut self) -> Option<Arc<LiveEvent>> {
+        loop {
+            let event = match self.missed.pop_front() {
+                Some(event) => event,
+                None => match self.events.recv().await {
+                    Ok(event) => event,
+                    // Fell behind the broadcast: read the rest from the history
+                    Err(broadcast::error::RecvError::Lagged(_)) => {
+                        self.catch_up();
+                        continue;
+                    }
+                    Err(broadcast::error::RecvError::Closed) => return None,
+                },
+            };
+            if event.id <= self.sent || self.project.as_ref().is_some_and(|project| *project != event.project) {
+                continue;
+            }
+            self.sent = event.id;
+            return Some(event);
+        }
+    }
+}
+
+#[utoipa::path(
+    get,
+    path = "/api/ws",
+    tag = "sync",
+    params(LiveQuery),
+    responses((
+        status = 101,
+        description = "WebSocket sending a JSON `LiveEvent` text message for every change",
+        body = LiveEvent,
+    ))
+)]
+pub async fn ws(
+    upgrade: WebSocketUpgrade,
+    Query(query): Query<LiveQuery>,
+    Extension(live): Extension<LiveUpdates>,
+) -> Response {
+    let feed = Feed::new(&live, None, query.project);
+    upgrade.on_upgrade(move |socket| forward(socket, feed))
+}
+
+async fn forward(mut socket: WebSocket, mut feed: Feed) {
+    loop {
+        tokio::select! {
+            event = feed.next() => {
+                let Some(event) = event else {
+                    break;
+                };
+                let Ok(text) = serde_json::to_string(&*event) else {
+                    continue;
+                };
+                if socket.send(Message::Text(text.into())).await.is_err() {
+                    break;
+                }
+            },
+            message = socket.recv() => match message {
+                Some(Ok(Message::Close(_)) | Err(_)) | None => break,
+                Some(Ok(_)) => {}
+            },
+        }
+    }
+}
+
+#[utoipa::path(
+    get,
+    path = "/api/events",
+    tag = "sync",
+    params(
+        LiveQuery,
+        ("Last-Event-ID" = Option<i64>, Header, description = "Id of the last event received; what came after it is sent first"),
+    ),
+    responses((
+        status = 200,
+        description = "Server-sent `change` events, each a JSON `LiveEvent`",
+        body = LiveEvent,
+        content_type = "text/event-stream",
+    ))
+)]
+pub async fn sse(
+    headers: HeaderMap,
+    Query(query): Query<LiveQuery>,
+    Extension(live): Extension<LiveUpdates>,
+) -> Sse<impl Stream<Item = Result<sse::Event, Infallible>>> {
+    let last_event_id = headers
+        .get("last-event-id")
+        .and_then(|id| id.to_str().ok())
+        .and_then(|id| id.trim().parse().ok());
+    let feed = Feed::new(&live, last_event_id, query.project);
+
+    let events = stream::unfold(feed, |mut feed| async move {
+        let event = feed.next().await?;
+        let message = sse::Event::default()
+            .id(event.id.to_string())
+            .event("change")
+            .json_data(&*event)
+            .unwrap_or_default();
+        Some((Ok(message), feed))
+    });
+    Sse::new(events).keep_alive(KeepAlive::default())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::storage::database::{init_database, save_todo_list_for_project};
+    use crate::todo::{TodoList, TodoState};
+    use chrono::NaiveDate;
+    use serial_test::serial;
+    use std::path::PathBuf;
+    use tempfile::TempDir;
+    use totui_client::models::LiveAction;
+
+    #[test]
+    #[serial]
+    fn test_events_after_the_cursor_become_live_events() {
+        let temp_dir = TempDir::new().unwrap();
+        std::fs::create_dir_all(temp_dir.path().join(".to-tui")).unwrap();
+        unsafe {
+            std::env::set_var("HOME", temp_dir.path());
+        }
+        init_database().unwrap();
+        let date = NaiveDate::from_ymd_opt(2026, 1, 18).unwrap();
+        let mut list = TodoList::new(date, PathBuf::from("/tmp/2026-01-18.md"));
+        list.add_item("Milk".to_string());
+        save_todo_list_for_project(&list, "default").unwrap();
+        let cursor = latest_event_id().unwrap();
+
+        list.items[0].set_state(TodoState::Checked);
+        list.add_item("Eggs".to_string());
+        save_todo_list_for_project(&list, "default").unwrap();
+
+        let live: Vec<LiveEvent> = events_after(cursor).unwrap().iter().filter_map(live_event).collect();
+        let actions: Vec<(LiveAction, &str)> = live.iter().map(|e| (e.action, e.kind.as_str())).collect();
+        assert_eq!(actions.len(), 2);
+        assert!(actions.contains(&(LiveAction::Modify, "state")));
+        assert!(actions.contains(&(LiveAction::Add, "add")));
+        assert!(live.iter().all(|e| e.id > cursor && e.date == Some(date)));
+        assert!(events_after(live.last().unwrap().id).unwrap().is_empty());
+    }
+
+    #[tokio::test]
+    #[serial]
+    async fn test_feed_resumes_after_the_last_event_id() {
+        let temp_dir = TempDir::new().unwrap();
+        std::fs::create_dir_all(temp_dir.path().join(".to-tui")).unwrap();
+        unsafe {
+            std::env::set_var("HOME", temp_dir.path());
+        }
+        init_database().unwrap();
+        let date = NaiveDate::from_ymd_opt(2026, 1, 18).unwrap();
+        let mut list = TodoList::new(date, PathBuf::from("/tmp/2026-01-18.md"));
+        list.add_item("Milk".to_string());
+        save_todo_list_for_project(&list, "default").unwrap();
+        let last_event_id = latest_event_id().unwrap();
+        list.add_item("Eggs".to_string());
+        save_todo_list_for_project(&list, "default").unwrap();
+        let missed = events_after(last_event_id).unwrap();
+
+        let (events, _) = broadcast::channel(BACKLOG);
+        let live = LiveUpdates { events };
+        let mut feed = Feed::new(&live, Some(last_event_id), None);
+        // The watcher broadcasting what was already replayed, then something new
+        let replayed = Arc::new(live_event(&missed[0]).unwrap());
+        let mut new = (*replayed).clone();
+        new.id += 1;
+        new.project = "other".to_string();
+        live.events.send(replayed).unwrap();
+        live.events.send(Arc::new(new.clone())).unwrap();
+
+        assert_eq!(feed.next().await.unwrap().content.as_deref(), Some("Eggs"));
+        assert_eq!(*feed.next().await.unwrap(), new);
+
+        let mut other = Feed::new(&live, None, Some("default".to_string()));
+        live.events.send(Arc::new(new)).unwrap();
+        drop(live);
+        assert!(other.next().await.is_none());
+    }
+}
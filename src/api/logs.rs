@@ -0,0 +1,302 @@
+//! The server's own log, for diagnosing it without a restart: the last lines
+//! are kept in memory for `GET /api/admin/logs`, and `PUT
+//! /api/admin/log-level` swaps the filter while the server runs.
+//!
+//! Only lines the filter lets through are kept, so turn the level up first
+//! to see debug output.
+
+use std::collections::VecDeque;
+use std::convert::Infallible;
+use std::io;
+use std::str::FromStr;
+use std::sync::{Arc, Mutex};
+
+use anyhow::{Context, Result};
+use axum::{
+    Extension, Json,
+    body::Body,
+    extract::Query,
+    http::{HeaderMap, header},
+    response::{IntoResponse, Response},
+};
+use futures_util::{StreamExt, stream};
+use tokio::sync::broadcast::{self, error::RecvError};
+use tracing::{Level, Metadata, Subscriber};
+use tracing_subscriber::{
+    EnvFilter, Registry,
+    field::MakeExt,
+    fmt::{FormatFields, MakeWriter, format},
+    layer::SubscriberExt,
+    reload,
+    util::SubscriberInitExt,
+};
+
+use super::admin::Admin;
+use super::models::{ErrorResponse, ErrorResponseExt, LogLevelRequest, LogLevelResponse, LogsQuery};
+
+/// Lines kept for `GET /api/admin/logs`
+const RECENT_LINES: usize = 1000;
+/// Lines a follower may fall behind by before it skips ahead
+const FOLLOW_BACKLOG: usize = 256;
+
+/// Handle on the log of the running server.
+#[derive(Clone)]
+pub struct ServerLog {
+    filter: reload::Handle<EnvFilter, Registry>,
+    lines: Arc<LogLines>,
+}
+
+#[derive(Debug, Clone)]
+struct LogLine {
+    level: Level,
+    text: String,
+}
+
+struct LogLines {
+    recent: Mutex<VecDeque<LogLine>>,
+    live: broadcast::Sender<LogLine>,
+}
+
+impl ServerLog {
+    /// Log to stdout through `filter`, keeping recent lines for the API.
+    /// Sets the global subscriber, so call it once.
+    pub fn init(filter: EnvFilter) -> Result<Self> {
+        let (log, subscriber) = Self::build(filter);
+        subscriber.try_init().context("Failed to set up logging")?;
+        Ok(log)
+    }
+
+    fn build(filter: EnvFilter) -> (Self, impl Subscriber + Send + Sync + 'static) {
+        let (live, _) = broadcast::channel(FOLLOW_BACKLOG);
+        let lines = Arc::new(LogLines {
+            recent: Mutex::new(VecDeque::with_capacity(RECENT_LINES)),
+            live,
+        });
+        let (filter, handle) = reload::Layer::new(filter);
+        let subscriber = tracing_subscriber::registry()
+            .with(filter)
+            .with(tracing_subscriber::fmt::layer())
+            .with(
+                tracing_subscriber::fmt::layer()
+                    .with_ansi(false)
+                    .fmt_fields(plain_fields())
+                    .with_writer(LineWriter(lines.clone())),
+            );
+        (Self { filter: handle, lines }, subscriber)
+    }
+
+    /// Replace the filter with `directives`, such as "debug" or
+    /// "info,to_tui=trace".
+    pub fn set_level(&self, directives: &str) -> Result<String> {
+        let filter = EnvFilter::try_new(directives.trim())
+            .with_context(|| format!("Invalid log level '{directives}'"))?;
+        self.filter.reload(filter).context("Failed to change the log level")?;
+        self.level()
+    }
+
+    pub fn level(&self) -> Result<String> {
+        self.filter
+            .with_current(|filter| filter.to_string())
+            .context("Failed to read the log level")
+    }
+
+    /// Kept lines at `level` or more severe, oldest first.
+    fn recent(&self, level: Level) -> String {
+        let recent = self.lines.recent.lock().unwrap_or_else(|e| e.into_inner());
+        render(recent.iter(), level)
+    }
+}
+
+/// Fields as the default formatter writes them. Spans keep their formatted
+/// fields per formatter type, so without a type of its own the kept lines
+/// would share the colored ones written to stdout.
+fn plain_fields() -> impl for<'w> FormatFields<'w> + Send + Sync + 'static {
+    format::debug_fn(|writer, field, value| match field.name() {
+        "message" => write!(writer, "{value:?}"),
+        name => write!(writer, "{name}={value:?}"),
+    })
+    .delimited(" ")
+}
+
+fn render<'a>(lines: impl Iterator<Item = &'a LogLine>, level: Level) -> String {
+    lines.filter(|line| line.level <= level).map(|line| line.text.as_str()).collect()
+}
+
+impl LogLines {
+    fn push(&self, line: LogLine) {
+        let mut recent = self.recent.lock().unwrap_or_else(|e| e.into_inner());
+        if recent.len() == RECENT_LINES {
+            recent.pop_front();
+        }
+        recent.push_back(line.clone());
+        // Sent under the lock, so a new follower sees each line exactly once
+        let _ = self.live.send(line);
+    }
+}
+
+/// Collects what the formatter writes for one event into one line.
+struct LineWriter(Arc<LogLines>);
+
+impl<'a> MakeWriter<'a> for LineWriter {
+    type Writer = EventWriter;
+
+    fn make_writer(&'a self) -> Self::Writer {
+        self.writer(Level::INFO)
+    }
+
+    fn make_writer_for(&'a self, meta: &Metadata<'_>) -> Self::Writer {
+        self.writer(*meta.level())
+    }
+}
+
+impl LineWriter {
+    fn writer(&self, level: Level) -> EventWriter {
+        EventWriter {
+            lines: self.0.clone(),
+            level,
+            text: Vec::new(),
+        }
+    }
+}
+
+struct EventWriter {
+    lines: Arc<LogLines>,
+    level: Level,
+    text: Vec<u8>,
+}
+
+impl io::Write for EventWriter {
+    fn write(&mut self, buf: &[u8]) -> io::Result<usize> {
+        self.text.extend_from_slice(buf);
+        Ok(buf.len())
+    }
+
+    fn flush(&mut self) -> io::Result<()> {
+        Ok(())
+    }
+}
+
+impl Drop for EventWriter {
+    fn drop(&mut self) {
+        if !self.text.is_empty() {
+            self.lines.push(LogLine {
+                level: self.level,
+                text: String::from_utf8_lossy(&self.text).into_owned(),
+            });
+        }
+    }
+}
+
+#[utoipa::path(
+    get,
+    path = "/api/admin/logs",
+    tag = "server",
+    params(LogsQuery),
+    description = "The server's recent log lines as text. With `follow=true` the response stays open and new lines are sent as they are logged. Takes the admin token from `server.token` in the data directory as a bearer token.",
+    responses(
+        (status = 200, description = "Log lines, oldest first", body = String, content_type = "text/plain"),
+        (status = 400, description = "Unknown level", body = ErrorResponse),
+        (status = 401, description = "Missing or wrong admin token", body = ErrorResponse),
+    )
+)]
+pub async fn logs(
+    Extension(admin): Extension<Admin>,
+    Extension(log): Extension<ServerLog>,
+    headers: HeaderMap,
+    Query(query): Query<LogsQuery>,
+) -> Response<Body> {
+    if !admin.authorized(&headers) {
+        return ErrorResponse::unauthorized("Reading the server log takes its admin token");
+    }
+    let level = match query.level.as_deref().map(Level::from_str).transpose() {
+        Ok(level) => level.unwrap_or(Level::TRACE),
+        Err(_) => return ErrorResponse::bad_request("Unknown level; use error, warn, info, debug or trace"),
+    };
+    let text_plain = [(header::CONTENT_TYPE, "text/plain; charset=utf-8")];
+
+    if !query.follow {
+        return (text_plain, log.recent(level)).into_response();
+    }
+
+    let (recent, live) = {
+        let recent = log.lines.recent.lock().unwrap_or_else(|e| e.into_inner());
+        (render(recent.iter(), level), log.lines.live.subscribe())
+    };
+    let new_lines = stream::unfold(live, move |mut live| async move {
+        loop {
+            match live.recv().await {
+                Ok(line) if line.level <= level => return Some((line.text, live)),
+                Ok(_) => {}
+                Err(RecvError::Lagged(skipped)) => {
+                    return Some((format!("... skipped {skipped} lines\n"), live));
+                }
+                Err(RecvError::Closed) => return None,
+            }
+        }
+    });
+    let body = stream::once(async move { recent })
+        .chain(new_lines)
+        .map(Ok::<_, Infallible>);
+    (text_plain, Body::from_stream(body)).into_response()
+}
+
+#[utoipa::path(
+    put,
+    path = "/api/admin/log-level",
+    tag = "server",
+    request_body = LogLevelRequest,
+    description = "Change what the server logs until it restarts. Takes the admin token from `server.token` in the data directory as a bearer token.",
+    responses(
+        (status = 200, description = "The filter now in use", body = LogLevelResponse),
+        (status = 400, description = "Invalid level", body = ErrorResponse),
+        (status = 401, description = "Missing or wrong admin token", body = ErrorResponse),
+    )
+)]
+pub async fn set_log_level(
+    Extension(admin): Extension<Admin>,
+    Extension(log): Extension<ServerLog>,
+    headers: HeaderMap,
+    Json(request): Json<LogLevelRequest>,
+) -> Response<Body> {
+    if !admin.authorized(&headers) {
+        return ErrorResponse::unauthorized("Changing the log level takes the server's admin token");
+    }
+    match log.set_level(&request.level) {
+        Ok(level) => {
+            tracing::info!("Log level changed to {level}");
+            Json(LogLevelResponse { level }).into_response()
+        }
+        Err(e) => ErrorResponse::bad_request(format!("{e:#}")),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_log_keeps_lines_and_changes_level() {
+        let (log, subscriber) = ServerLog::build(EnvFilter::new("info"));
+        tracing::subscriber::with_default(subscriber, || {
+            tracing::info!("first");
+            tracing::debug!("hidden");
+            log.set_level("debug").unwrap();
+            tracing::debug!("second");
+            tracing::warn!("third");
+
+            assert_eq!(log.level().unwrap(), "debug");
+            assert!(log.set_level("info,[").is_err());
+            assert_eq!(log.level().unwrap(), "debug");
+        });
+
+        let all = log.recent(Level::TRACE);
+        assert!(all.contains("first"), "{all}");
+        assert!(!all.contains("hidden"), "{all}");
+        assert!(all.contains("second"), "{all}");
+        assert_eq!(all.lines().count(), 3, "{all}");
+
+        let warnings = log.recent(Level::WARN);
+        assert_eq!(warnings.lines().count(), 1, "{warnings}");
+        assert!(warnings.contains("third"), "{warnings}");
+    }
+}
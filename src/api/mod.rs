@@ -1,5 +1,17 @@
+pub mod admin;
+pub mod auth;
+pub mod cors;
 pub mod handlers;
+pub mod idempotency;
+pub mod limits;
+pub mod live;
+pub mod logs;
 pub mod models;
+pub mod openapi;
 pub mod routes;
+#[cfg(unix)]
+pub mod socket;
+#[cfg(feature = "web-ui")]
+pub mod web;
 
 pub use routes::create_router;
@@ -1,108 +1,147 @@
 use axum::{Json, body::Body, http::StatusCode, response::{IntoResponse, Response}};
-use chrono::{DateTime, NaiveDate, Utc};
-use serde::{Deserialize, Serialize};
-use uuid::Uuid;
 
 use crate::project::Project;
-use crate::todo::{TodoItem, TodoState};
-
-#[derive(Debug, Serialize)]
-pub struct TodoResponse {
-    pub id: Uuid,
-    pub content: String,
-    pub state: String,
-    pub indent_level: usize,
-    pub parent_id: Option<Uuid>,
-    pub due_date: Option<NaiveDate>,
-    pub description: Option<String>,
+use crate::storage::database::SearchHit;
+use crate::storage::history::{Event, EventKind};
+use crate::storage::sync::Change;
+use crate::todo::{Estimate, TodoItem, TodoState};
+
+// The wire types live in totui-client so Rust tooling can share them
+pub use totui_client::models::{
+    ArchiveQuery, ArchiveResponse, ArchivedDayResponse, ChangeResponse, ChangesQuery,
+    ChangesResponse, CreateProjectRequest, CreateTodoRequest, DateQuery, ErrorResponse, LiveAction,
+    LiveEvent, LiveQuery, LogLevelRequest, LogLevelResponse, LogsQuery, ProjectListResponse,
+    ProjectResponse, RenameProjectRequest, SearchHitResponse, SearchQuery, SearchResponse,
+    TodoListResponse, TodoResponse, UpdateTodoRequest,
+};
+
+pub fn todo_response(item: &TodoItem) -> TodoResponse {
+    TodoResponse {
+        id: item.id,
+        content: item.content.clone(),
+        state: item.state.to_char().to_string(),
+        indent_level: item.indent_level,
+        parent_id: item.parent_id,
+        due_date: item.due_date,
+        description: item.description.clone(),
+        priority: item.priority.map(String::from),
+        estimate_minutes: item.estimate.map(Estimate::minutes),
+        waiting_on: item.waiting_on.clone(),
+        pinned: item.pinned,
+        link: item.link.clone(),
+        collapsed: item.collapsed,
+    }
 }
 
-impl From<&TodoItem> for TodoResponse {
-    fn from(item: &TodoItem) -> Self {
-        Self {
+pub fn change_response(change: &Change) -> ChangeResponse {
+    match change {
+        Change::Updated {
+            change_seq,
+            project,
+            date,
+            item,
+        } => ChangeResponse {
+            change_seq: *change_seq,
             id: item.id,
-            content: item.content.clone(),
-            state: item.state.to_char().to_string(),
-            indent_level: item.indent_level,
-            parent_id: item.parent_id,
-            due_date: item.due_date,
-            description: item.description.clone(),
-        }
+            project: project.clone(),
+            date: *date,
+            todo: Some(todo_response(item)),
+            deleted: item.deleted_at.is_some(),
+        },
+        Change::Removed {
+            change_seq,
+            project,
+            date,
+            id,
+        } => ChangeResponse {
+            change_seq: *change_seq,
+            id: *id,
+            project: project.clone(),
+            date: *date,
+            todo: None,
+            deleted: true,
+        },
     }
 }
 
-#[derive(Debug, Serialize)]
-pub struct TodoListResponse {
-    pub date: NaiveDate,
-    pub items: Vec<TodoResponse>,
-}
-
-#[derive(Debug, Deserialize)]
-pub struct CreateTodoRequest {
-    pub content: String,
-    pub parent_id: Option<Uuid>,
-    pub due_date: Option<NaiveDate>,
-    pub description: Option<String>,
+/// The live update for a history event; None for kinds it doesn't know.
+pub fn live_event(event: &Event) -> Option<LiveEvent> {
+    let action = match event.kind.parse().ok()? {
+        EventKind::Add | EventKind::Restore => LiveAction::Add,
+        EventKind::Edit | EventKind::State => LiveAction::Modify,
+        EventKind::Delete | EventKind::Purge | EventKind::Archive => LiveAction::Delete,
+        EventKind::Rollover => LiveAction::Rollover,
+    };
+    Some(LiveEvent {
+        id: event.id,
+        at: event.at,
+        action,
+        kind: event.kind.clone(),
+        origin: event.origin.clone(),
+        project: event.project.clone(),
+        date: event.date,
+        todo_id: event.todo_id.as_deref().and_then(|id| id.parse().ok()),
+        content: event.content.clone(),
+    })
 }
 
-#[derive(Debug, Deserialize)]
-pub struct UpdateTodoRequest {
-    pub content: Option<String>,
-    pub state: Option<String>,
-    pub due_date: Option<NaiveDate>,
-    pub description: Option<String>,
+pub fn project_response(project: &Project) -> ProjectResponse {
+    ProjectResponse {
+        id: project.id,
+        name: project.name.clone(),
+        created_at: project.created_at,
+    }
 }
 
-#[derive(Debug, Deserialize)]
-pub struct DateQuery {
-    pub date: Option<NaiveDate>,
-    pub project: Option<String>,
+pub fn search_hit_response(hit: &SearchHit) -> SearchHitResponse {
+    SearchHitResponse {
+        id: hit.id,
+        date: hit.date,
+        project: hit.project.clone(),
+        content: hit.content.clone(),
+        state: hit.state.to_char().to_string(),
+        archived: hit.archived,
+    }
 }
 
-#[derive(Debug, Serialize)]
-pub struct ProjectResponse {
-    pub id: Uuid,
-    pub name: String,
-    pub created_at: DateTime<Utc>,
+/// Axum responses for [`ErrorResponse`].
+pub trait ErrorResponseExt {
+    fn internal(e: impl std::fmt::Display) -> Response<Body>;
+    fn not_found(message: impl Into<String>) -> Response<Body>;
+    fn bad_request(message: impl Into<String>) -> Response<Body>;
+    fn unauthorized(message: impl Into<String>) -> Response<Body>;
+    fn forbidden(message: impl Into<String>) -> Response<Body>;
+    fn conflict(message: impl Into<String>) -> Response<Body>;
+    fn unprocessable(message: impl Into<String>) -> Response<Body>;
 }
 
-impl From<&Project> for ProjectResponse {
-    fn from(project: &Project) -> Self {
-        Self {
-            id: project.id,
-            name: project.name.clone(),
-            created_at: project.created_at,
-        }
+impl ErrorResponseExt for ErrorResponse {
+    fn internal(e: impl std::fmt::Display) -> Response<Body> {
+        (StatusCode::INTERNAL_SERVER_ERROR, Json(Self::new(e.to_string()))).into_response()
     }
-}
 
-#[derive(Debug, Serialize)]
-pub struct ProjectListResponse {
-    pub projects: Vec<ProjectResponse>,
-}
+    fn not_found(message: impl Into<String>) -> Response<Body> {
+        (StatusCode::NOT_FOUND, Json(Self::new(message))).into_response()
+    }
 
-#[derive(Debug, Serialize)]
-pub struct ErrorResponse {
-    pub error: String,
-}
+    fn bad_request(message: impl Into<String>) -> Response<Body> {
+        (StatusCode::BAD_REQUEST, Json(Self::new(message))).into_response()
+    }
 
-impl ErrorResponse {
-    pub fn new(message: impl Into<String>) -> Self {
-        Self {
-            error: message.into(),
-        }
+    fn unauthorized(message: impl Into<String>) -> Response<Body> {
+        (StatusCode::UNAUTHORIZED, Json(Self::new(message))).into_response()
     }
 
-    pub fn internal(e: impl std::fmt::Display) -> Response<Body> {
-        (StatusCode::INTERNAL_SERVER_ERROR, Json(Self::new(e.to_string()))).into_response()
+    fn forbidden(message: impl Into<String>) -> Response<Body> {
+        (StatusCode::FORBIDDEN, Json(Self::new(message))).into_response()
     }
 
-    pub fn not_found(message: impl Into<String>) -> Response<Body> {
-        (StatusCode::NOT_FOUND, Json(Self::new(message))).into_response()
+    fn conflict(message: impl Into<String>) -> Response<Body> {
+        (StatusCode::CONFLICT, Json(Self::new(message))).into_response()
     }
 
-    pub fn bad_request(message: impl Into<String>) -> Response<Body> {
-        (StatusCode::BAD_REQUEST, Json(Self::new(message))).into_response()
+    fn unprocessable(message: impl Into<String>) -> Response<Body> {
+        (StatusCode::UNPROCESSABLE_ENTITY, Json(Self::new(message))).into_response()
     }
 }
 
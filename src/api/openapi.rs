@@ -0,0 +1,120 @@
+//! The OpenAPI document of the API, served at `/api/openapi.json` and
+//! browsable at `/api/docs`.
+
+use utoipa::OpenApi;
+
+use super::models::{
+    ArchiveResponse, ArchivedDayResponse, ChangeResponse, ChangesResponse, CreateProjectRequest,
+    CreateTodoRequest, ErrorResponse, LiveAction, LiveEvent, LogLevelRequest, LogLevelResponse,
+    ProjectListResponse, ProjectResponse, RenameProjectRequest, SearchHitResponse, SearchResponse,
+    TodoListResponse, TodoResponse, UpdateTodoRequest,
+};
+use super::{admin, handlers, live, logs, routes};
+
+#[derive(OpenApi)]
+#[openapi(
+    info(
+        title = "to-tui API",
+        description = "Todos, projects and the archive of a to-tui installation, and a feed of changes to them."
+    ),
+    paths(
+        routes::health_check,
+        admin::shutdown,
+        logs::logs,
+        logs::set_log_level,
+        handlers::list_projects,
+        handlers::create_project,
+        handlers::rename_project,
+        handlers::delete_project,
+        handlers::list_day_todos,
+        handlers::create_day_todo,
+        handlers::update_day_todo,
+        handlers::delete_day_todo,
+        handlers::list_todos,
+        handlers::create_todo,
+        handlers::update_todo,
+        handlers::delete_todo,
+        handlers::list_archive,
+        handlers::search,
+        handlers::list_changes,
+        live::ws,
+        live::sse,
+    ),
+    components(schemas(
+        TodoResponse,
+        TodoListResponse,
+        CreateTodoRequest,
+        UpdateTodoRequest,
+        ProjectResponse,
+        ProjectListResponse,
+        CreateProjectRequest,
+        RenameProjectRequest,
+        LogLevelRequest,
+        LogLevelResponse,
+        ArchiveResponse,
+        ArchivedDayResponse,
+        ChangeResponse,
+        ChangesResponse,
+        SearchHitResponse,
+        SearchResponse,
+        LiveAction,
+        LiveEvent,
+        ErrorResponse,
+    )),
+    tags(
+        (name = "todos", description = "A project's todos for a day"),
+        (name = "projects"),
+        (name = "archive", description = "Archived todos of past days"),
+        (name = "sync", description = "Following changes, by polling or live"),
+        (name = "server"),
+    )
+)]
+pub struct ApiDoc;
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_every_route_is_documented() {
+        let doc = serde_json::to_value(ApiDoc::openapi()).unwrap();
+        let mut routes: Vec<String> = doc["paths"]
+            .as_object()
+            .unwrap()
+            .iter()
+            .flat_map(|(path, item)| item.as_object().unwrap().keys().map(move |method| format!("{method} {path}")))
+            .collect();
+        routes.sort();
+        assert_eq!(
+            routes,
+            vec![
+                "delete /api/projects/{name}",
+                "delete /api/projects/{project}/days/{date}/todos/{id}",
+                "delete /api/todos/{id}",
+                "get /api/admin/logs",
+                "get /api/changes",
+                "get /api/events",
+                "get /api/health",
+                "get /api/projects",
+                "get /api/projects/{project}/archive",
+                "get /api/projects/{project}/days/{date}/todos",
+                "get /api/search",
+                "get /api/todos",
+                "get /api/ws",
+                "patch /api/projects/{name}",
+                "patch /api/projects/{project}/days/{date}/todos/{id}",
+                "patch /api/todos/{id}",
+                "post /api/admin/shutdown",
+                "post /api/projects",
+                "post /api/projects/{project}/days/{date}/todos",
+                "post /api/todos",
+                "put /api/admin/log-level",
+            ]
+        );
+        // Bodies refer to schemas the document has
+        let text = doc.to_string();
+        for schema in doc["components"]["schemas"].as_object().unwrap().keys() {
+            assert!(text.contains(&format!("#/components/schemas/{schema}")), "{schema} is unused");
+        }
+    }
+}
@@ -1,29 +1,80 @@
+use anyhow::Result;
 use axum::{
-    Router,
-    routing::{delete, get, patch, post},
+    Extension, Router,
+    routing::{delete, get, patch, post, put},
 };
-use tower_http::cors::{Any, CorsLayer};
+use utoipa::OpenApi;
+use utoipa_swagger_ui::SwaggerUi;
 use tower_http::trace::TraceLayer;
 
+use crate::config::ServerConfig;
+
+use super::admin::{self, Admin};
+use super::auth;
+use super::cors::cors_layer;
 use super::handlers;
+use super::idempotency;
+use super::limits::Limits;
+use super::openapi::ApiDoc;
+use super::live::{self, LiveUpdates};
+use super::logs::{self, ServerLog};
 
-async fn health_check() -> &'static str {
+#[utoipa::path(
+    get,
+    path = "/api/health",
+    tag = "server",
+    responses((status = 200, description = "The server is up", body = String, example = "ok"))
+)]
+pub(super) async fn health_check() -> &'static str {
     "ok"
 }
 
-pub fn create_router() -> Router {
-    let cors = CorsLayer::new()
-        .allow_origin(Any)
-        .allow_methods(Any)
-        .allow_headers(Any);
+pub fn create_router(live: LiveUpdates, admin: Admin, log: ServerLog, server: &ServerConfig) -> Result<Router> {
+    let cors = cors_layer(&server.cors)?;
 
-    Router::new()
+    let router = Router::new()
         .route("/api/health", get(health_check))
-        .route("/api/projects", get(handlers::list_projects))
+        .route("/api/admin/shutdown", post(admin::shutdown))
+        .route("/api/admin/logs", get(logs::logs))
+        .route("/api/admin/log-level", put(logs::set_log_level))
+        .route(
+            "/api/projects",
+            get(handlers::list_projects).post(handlers::create_project),
+        )
+        .route(
+            "/api/projects/{name}",
+            patch(handlers::rename_project).delete(handlers::delete_project),
+        )
+        .route("/api/projects/{project}/archive", get(handlers::list_archive))
+        .route("/api/search", get(handlers::search))
+        .route("/api/changes", get(handlers::list_changes))
+        .route("/api/ws", get(live::ws))
+        .route("/api/events", get(live::sse))
         .route("/api/todos", get(handlers::list_todos))
         .route("/api/todos", post(handlers::create_todo))
         .route("/api/todos/{id}", delete(handlers::delete_todo))
         .route("/api/todos/{id}", patch(handlers::update_todo))
+        .route(
+            "/api/projects/{project}/days/{date}/todos",
+            get(handlers::list_day_todos).post(handlers::create_day_todo),
+        )
+        .route(
+            "/api/projects/{project}/days/{date}/todos/{id}",
+            patch(handlers::update_day_todo).delete(handlers::delete_day_todo),
+        )
+        .merge(SwaggerUi::new("/api/docs").url("/api/openapi.json", ApiDoc::openapi()));
+    #[cfg(feature = "web-ui")]
+    let router = router.merge(super::web::routes());
+    let router = router
+        .layer(Extension(live))
+        .layer(Extension(admin))
+        .layer(Extension(log));
+
+    let limits = Limits::from(server);
+    let router = idempotency::apply(router, limits.max_body_bytes);
+    let router = auth::require_token(router, server.token.as_deref());
+    Ok(limits
+        .apply(router)
         .layer(TraceLayer::new_for_http())
-        .layer(cors)
+        .layer(cors))
 }
@@ -0,0 +1,128 @@
+//! Listening on a Unix domain socket (`--socket`).
+//!
+//! The socket is bound inside a fresh directory only this user can enter,
+//! made private, and only then moved to its path, so nobody else can connect
+//! in the window between binding and changing its permissions.
+
+use std::fs::{self, DirBuilder};
+use std::os::unix::fs::{DirBuilderExt, FileTypeExt, PermissionsExt};
+use std::os::unix::net::UnixStream;
+use std::path::{Path, PathBuf};
+
+use anyhow::{Context, Result, bail};
+use tokio::net::UnixListener;
+
+/// Listen on `path`. A socket left there by a server that was killed is
+/// replaced; anything else at the path is left alone and refused.
+pub fn bind(path: &Path) -> Result<UnixListener> {
+    clear_stale(path)?;
+
+    let staging = staging_dir(path)?;
+    let result = bind_in(&staging, path);
+    let _ = fs::remove_dir_all(&staging);
+    result
+}
+
+fn clear_stale(path: &Path) -> Result<()> {
+    let metadata = match fs::symlink_metadata(path) {
+        Ok(metadata) => metadata,
+        Err(e) if e.kind() == std::io::ErrorKind::NotFound => return Ok(()),
+        Err(e) => return Err(e).with_context(|| format!("Failed to inspect {}", path.display())),
+    };
+    if !metadata.file_type().is_socket() {
+        bail!("{} exists and is not a socket; refusing to replace it", path.display());
+    }
+    if UnixStream::connect(path).is_ok() {
+        bail!("A server is already listening on {}", path.display());
+    }
+    fs::remove_file(path).with_context(|| format!("Failed to remove stale socket {}", path.display()))
+}
+
+fn staging_dir(path: &Path) -> Result<PathBuf> {
+    let parent = match path.parent() {
+        Some(parent) if !parent.as_os_str().is_empty() => parent,
+        _ => Path::new("."),
+    };
+    let name = path.file_name().map(|name| name.to_string_lossy()).unwrap_or_default();
+    let staging = parent.join(format!(".{name}.{}", std::process::id()));
+    DirBuilder::new()
+        .mode(0o700)
+        .create(&staging)
+        .with_context(|| format!("Failed to create {}", staging.display()))?;
+    Ok(staging)
+}
+
+fn bind_in(staging: &Path, path: &Path) -> Result<UnixListener> {
+    let staged = staging.join("socket");
+    let listener =
+        UnixListener::bind(&staged).with_context(|| format!("Failed to listen on {}", path.display()))?;
+    fs::set_permissions(&staged, fs::Permissions::from_mode(0o600))?;
+    fs::rename(&staged, path).with_context(|| format!("Failed to listen on {}", path.display()))?;
+    Ok(listener)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use axum::{Router, routing::get};
+    use std::io::{Read, Write};
+    use std::os::unix::net::UnixListener as StdUnixListener;
+    use tempfile::TempDir;
+
+    #[tokio::test]
+    async fn test_bind_replaces_stale_socket() {
+        let dir = TempDir::new().unwrap();
+        let path = dir.path().join("totui.sock");
+        drop(StdUnixListener::bind(&path).unwrap());
+        assert!(path.exists());
+
+        let _listener = bind(&path).unwrap();
+
+        assert!(fs::symlink_metadata(&path).unwrap().file_type().is_socket());
+        assert_eq!(fs::metadata(&path).unwrap().permissions().mode() & 0o777, 0o600);
+        assert_eq!(fs::read_dir(dir.path()).unwrap().count(), 1);
+    }
+
+    #[tokio::test]
+    async fn test_bind_refuses_a_file_that_is_not_a_socket() {
+        let dir = TempDir::new().unwrap();
+        let path = dir.path().join("notes.md");
+        fs::write(&path, "# keep me").unwrap();
+
+        assert!(bind(&path).is_err());
+        assert_eq!(fs::read_to_string(&path).unwrap(), "# keep me");
+    }
+
+    #[tokio::test]
+    async fn test_bind_refuses_a_socket_in_use() {
+        let dir = TempDir::new().unwrap();
+        let path = dir.path().join("totui.sock");
+        let _first = bind(&path).unwrap();
+
+        assert!(bind(&path).is_err());
+    }
+
+    #[tokio::test]
+    async fn test_bound_socket_serves_requests() {
+        let dir = TempDir::new().unwrap();
+        let path = dir.path().join("totui.sock");
+        let listener = bind(&path).unwrap();
+        let app = Router::new().route("/api/health", get(|| async { "ok" }));
+        tokio::spawn(async move { axum::serve(listener, app).await });
+
+        let response = tokio::task::spawn_blocking(move || {
+            let mut stream = UnixStream::connect(&path).unwrap();
+            stream
+                .write_all(b"GET /api/health HTTP/1.1\r\nHost: localhost\r\nConnection: close\r\n\r\n")
+                .unwrap();
+            let mut response = String::new();
+            stream.read_to_string(&mut response).unwrap();
+            response
+        })
+        .await
+        .unwrap();
+
+        assert!(response.starts_with("HTTP/1.1 200"));
+        assert!(response.ends_with("ok"));
+    }
+}
@@ -0,0 +1,159 @@
+// Today's list for one project, through the REST API. When the server has
+// an API token it is asked for once and kept in localStorage.
+"use strict";
+
+const TOKEN_KEY = "totui-token";
+const PROJECT_KEY = "totui-project";
+const REFRESH_MS = 30000;
+
+const projectSelect = document.getElementById("project");
+const list = document.getElementById("todos");
+const status = document.getElementById("status");
+const form = document.getElementById("add");
+const contentInput = document.getElementById("content");
+
+let project = localStorage.getItem(PROJECT_KEY) || "default";
+// Revision of the list on screen, so a change made elsewhere since isn't
+// overwritten
+let revision = null;
+
+async function api(method, path, body, headers = {}) {
+  const token = localStorage.getItem(TOKEN_KEY);
+  if (token) {
+    headers.Authorization = `Bearer ${token}`;
+  }
+  if (body !== undefined) {
+    headers["Content-Type"] = "application/json";
+  }
+  const response = await fetch(path, {
+    method,
+    headers,
+    body: body === undefined ? undefined : JSON.stringify(body),
+  });
+
+  if (response.status === 401) {
+    const entered = prompt("API token (token in [server] in the config)");
+    if (entered) {
+      localStorage.setItem(TOKEN_KEY, entered.trim());
+      return api(method, path, body, headers);
+    }
+  }
+  if (!response.ok) {
+    const error = await response.json().catch(() => ({}));
+    throw new Error(error.error || `${response.status} ${response.statusText}`);
+  }
+  return response.status === 204 ? null : response.json();
+}
+
+function todosPath(id = "") {
+  return `/api/todos${id ? `/${id}` : ""}?project=${encodeURIComponent(project)}`;
+}
+
+// crypto.randomUUID needs HTTPS, which a server on the LAN may not have
+function newKey() {
+  return `${Date.now().toString(36)}-${Math.random().toString(36).slice(2)}`;
+}
+
+function render(items) {
+  list.replaceChildren(
+    ...items.map((item) => {
+      const li = document.createElement("li");
+      li.classList.toggle("done", item.state === "x");
+      li.style.paddingLeft = `${item.indent_level * 1.5}rem`;
+
+      const label = document.createElement("label");
+      const checkbox = document.createElement("input");
+      checkbox.type = "checkbox";
+      checkbox.checked = item.state === "x";
+      checkbox.addEventListener("change", () => setDone(item, checkbox.checked));
+      label.append(checkbox);
+
+      if (item.state !== " " && item.state !== "x") {
+        const state = document.createElement("span");
+        state.className = "state";
+        state.textContent = item.state;
+        label.append(state);
+      }
+      const content = document.createElement("span");
+      content.textContent = item.content;
+      label.append(content);
+
+      li.append(label);
+      return li;
+    }),
+  );
+  status.textContent = items.length ? "" : "Nothing on the list yet.";
+}
+
+async function refresh() {
+  try {
+    const day = await api("GET", todosPath());
+    revision = day.revision;
+    document.querySelector("h1").textContent = day.date;
+    render(day.items);
+  } catch (error) {
+    status.textContent = error.message;
+  }
+}
+
+async function setDone(item, done) {
+  const headers = revision === null ? {} : { "If-Match": `"${revision}"` };
+  let failure = "";
+  try {
+    await api("PATCH", todosPath(item.id), { state: done ? "x" : " " }, headers);
+  } catch (error) {
+    failure = error.message;
+  }
+  await refresh();
+  if (failure) {
+    status.textContent = failure;
+  }
+}
+
+async function loadProjects() {
+  try {
+    const { projects } = await api("GET", "/api/projects");
+    if (!projects.some((p) => p.name === project)) {
+      project = "default";
+    }
+    projectSelect.replaceChildren(
+      ...projects.map((p) => new Option(p.name, p.name, false, p.name === project)),
+    );
+  } catch (error) {
+    status.textContent = error.message;
+  }
+}
+
+projectSelect.addEventListener("change", () => {
+  project = projectSelect.value;
+  localStorage.setItem(PROJECT_KEY, project);
+  refresh();
+});
+
+form.addEventListener("submit", async (event) => {
+  event.preventDefault();
+  const content = contentInput.value.trim();
+  if (!content) {
+    return;
+  }
+  try {
+    await api("POST", todosPath(), { content }, { "Idempotency-Key": newKey() });
+    contentInput.value = "";
+  } catch (error) {
+    status.textContent = error.message;
+  }
+  refresh();
+});
+
+document.addEventListener("visibilitychange", () => {
+  if (!document.hidden) {
+    refresh();
+  }
+});
+setInterval(() => {
+  if (!document.hidden) {
+    refresh();
+  }
+}, REFRESH_MS);
+
+loadProjects().then(refresh);
@@ -0,0 +1,36 @@
+//! A small web page for today's list, built in with the `web-ui` feature, so
+//! a phone on the LAN can check todos off and add new ones. It only talks to
+//! the REST API, and the assets are compiled into the binary.
+
+use axum::{Router, http::header, response::IntoResponse, routing::get};
+
+const INDEX_HTML: &str = include_str!("index.html");
+const APP_JS: &str = include_str!("app.js");
+const STYLE_CSS: &str = include_str!("style.css");
+
+/// The page at `/` and its assets.
+pub fn routes() -> Router {
+    Router::new()
+        .route("/", get(|| async { asset("text/html; charset=utf-8", INDEX_HTML) }))
+        .route("/app.js", get(|| async { asset("text/javascript; charset=utf-8", APP_JS) }))
+        .route("/style.css", get(|| async { asset("text/css; charset=utf-8", STYLE_CSS) }))
+}
+
+// Assets change with the binary, so browsers should check back every time
+fn asset(content_type: &'static str, body: &'static str) -> impl IntoResponse {
+    (
+        [(header::CONTENT_TYPE, content_type), (header::CACHE_CONTROL, "no-cache")],
+        body,
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_index_links_the_served_assets() {
+        assert!(INDEX_HTML.contains(r#"src="/app.js""#));
+        assert!(INDEX_HTML.contains(r#"href="/style.css""#));
+    }
+}
@@ -0,0 +1,123 @@
+//! In-session activity log, shown by the `:timeline` popup.
+//!
+//! Only lives as long as the TUI session; nothing is written to disk.
+
+use chrono::{DateTime, Local};
+use std::collections::VecDeque;
+use std::fmt;
+
+/// Oldest entries are dropped past this many
+const MAX_ENTRIES: usize = 500;
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Activity {
+    Added,
+    Completed,
+    Cancelled,
+    Reopened,
+    Deleted,
+    Archived,
+    /// Moved to another day or project, described by the destination
+    Moved(String),
+}
+
+impl fmt::Display for Activity {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Activity::Added => write!(f, "added"),
+            Activity::Completed => write!(f, "completed"),
+            Activity::Cancelled => write!(f, "cancelled"),
+            Activity::Reopened => write!(f, "reopened"),
+            Activity::Deleted => write!(f, "deleted"),
+            Activity::Archived => write!(f, "archived"),
+            Activity::Moved(to) => write!(f, "moved to {to}"),
+        }
+    }
+}
+
+#[derive(Debug, Clone)]
+pub struct ActivityEntry {
+    pub at: DateTime<Local>,
+    pub activity: Activity,
+    pub content: String,
+}
+
+#[derive(Debug, Default)]
+pub struct ActivityLog {
+    entries: VecDeque<ActivityEntry>,
+}
+
+impl ActivityLog {
+    pub fn record(&mut self, activity: Activity, content: impl Into<String>) {
+        if self.entries.len() == MAX_ENTRIES {
+            self.entries.pop_front();
+        }
+        self.entries.push_back(ActivityEntry {
+            at: Local::now(),
+            activity,
+            content: content.into(),
+        });
+    }
+
+    /// Entries oldest first.
+    pub fn entries(&self) -> impl DoubleEndedIterator<Item = &ActivityEntry> {
+        self.entries.iter()
+    }
+
+    pub fn len(&self) -> usize {
+        self.entries.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.entries.is_empty()
+    }
+
+    /// Counts per kind of activity, e.g. "3 added, 5 completed, 1 moved".
+    pub fn summary(&self) -> String {
+        let count = |matches: fn(&Activity) -> bool| {
+            self.entries.iter().filter(|e| matches(&e.activity)).count()
+        };
+        let parts = [
+            (count(|a| *a == Activity::Added), "added"),
+            (count(|a| *a == Activity::Completed), "completed"),
+            (count(|a| *a == Activity::Cancelled), "cancelled"),
+            (count(|a| matches!(a, Activity::Moved(_))), "moved"),
+            (count(|a| *a == Activity::Archived), "archived"),
+            (count(|a| *a == Activity::Deleted), "deleted"),
+        ];
+        parts
+            .iter()
+            .filter(|(n, _)| *n > 0)
+            .map(|(n, label)| format!("{n} {label}"))
+            .collect::<Vec<_>>()
+            .join(", ")
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_summary_counts_each_kind() {
+        let mut log = ActivityLog::default();
+        log.record(Activity::Added, "Write report");
+        log.record(Activity::Completed, "Write report");
+        log.record(Activity::Added, "Call plumber");
+        log.record(Activity::Moved("2026-10-18".to_string()), "Call plumber");
+        log.record(Activity::Reopened, "Write report");
+
+        assert_eq!(log.summary(), "2 added, 1 completed, 1 moved");
+        assert_eq!(log.entries().last().unwrap().activity, Activity::Reopened);
+    }
+
+    #[test]
+    fn test_oldest_entries_are_dropped() {
+        let mut log = ActivityLog::default();
+        for i in 0..MAX_ENTRIES + 3 {
+            log.record(Activity::Added, format!("item {i}"));
+        }
+        assert_eq!(log.len(), MAX_ENTRIES);
+        assert_eq!(log.entries().next().unwrap().content, "item 3");
+    }
+}
@@ -0,0 +1,213 @@
+//! Parsing and completion for the `:` command line.
+//!
+//! Any navigate-mode action can be run by name (`:toggle-pin`), alongside a
+//! few commands that take an argument:
+//!
+//! - `:move-to-project <name>` moves the selected item to another project
+//! - `:sort priority` / `:sort due` sorts the list
+//! - `:goto <date>` opens another day, accepting the same dates as `@due`
+//! - `:plugin run <plugin> [action | input]` runs a plugin action or generator
+//! - `:timeline` shows the session activity timeline
+//! - `:undolist` browses the undo history, like vim's
+
+use super::AppState;
+use crate::keybindings::Action;
+use crate::project::ProjectRegistry;
+use crate::utils::fuzzy::fuzzy_filter;
+
+/// How many completions the palette lists at once
+pub const MAX_COMPLETIONS: usize = 8;
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum SortKey {
+    Priority,
+    Due,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Command {
+    Action(Action),
+    MoveToProject(String),
+    Sort(SortKey),
+    Goto(String),
+    /// `rest` is an action name or generator input, possibly empty
+    PluginRun { plugin: String, rest: String },
+    Timeline,
+}
+
+/// Parse a command line, without the leading `:`.
+pub fn parse(input: &str) -> Result<Command, String> {
+    let input = input.trim();
+    let (name, arg) = match input.split_once(char::is_whitespace) {
+        Some((name, arg)) => (name, arg.trim()),
+        None => (input, ""),
+    };
+    let require_arg = |usage: &str| {
+        if arg.is_empty() {
+            Err(format!("Usage: :{usage}"))
+        } else {
+            Ok(arg.to_string())
+        }
+    };
+
+    match name {
+        "timeline" | "tl" => Ok(Command::Timeline),
+        "undolist" | "undol" => Ok(Command::Action(Action::OpenUndoHistory)),
+        "goto" | "go" => require_arg("goto <date>").map(Command::Goto),
+        "move-to-project" | "move_to_project" | "mv" if !arg.is_empty() => {
+            Ok(Command::MoveToProject(arg.to_string()))
+        }
+        "sort" => match arg {
+            "priority" | "p" => Ok(Command::Sort(SortKey::Priority)),
+            "due" | "d" => Ok(Command::Sort(SortKey::Due)),
+            _ => Err("Usage: :sort priority|due".to_string()),
+        },
+        "plugin" => {
+            let Some(rest) = arg.strip_prefix("run").filter(|r| r.is_empty() || r.starts_with(' ')) else {
+                return Err("Usage: :plugin run <plugin> [action|input]".to_string());
+            };
+            let rest = rest.trim();
+            let (plugin, rest) = rest.split_once(char::is_whitespace).unwrap_or((rest, ""));
+            if plugin.is_empty() {
+                return Err("Usage: :plugin run <plugin> [action|input]".to_string());
+            }
+            Ok(Command::PluginRun {
+                plugin: plugin.to_string(),
+                rest: rest.trim().to_string(),
+            })
+        }
+        _ if arg.is_empty() => name
+            .replace('-', "_")
+            .parse()
+            .map(Command::Action)
+            .map_err(|_| format!("Unknown command: {name}")),
+        _ => Err(format!("Unknown command: {input}")),
+    }
+}
+
+/// An entry in the palette's completion list.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Completion {
+    /// Command line the completion fills in
+    pub text: String,
+    /// Shown dimmed next to the text, e.g. the action's key
+    pub hint: String,
+}
+
+impl Completion {
+    fn new(text: impl Into<String>, hint: impl Into<String>) -> Self {
+        Self {
+            text: text.into(),
+            hint: hint.into(),
+        }
+    }
+
+    /// Whether accepting this completion should leave room for an argument.
+    pub fn takes_argument(&self) -> bool {
+        self.text == "goto"
+    }
+}
+
+/// Everything the palette can complete, collected when the prompt opens.
+pub fn candidates(state: &AppState) -> Vec<Completion> {
+    let mut candidates = vec![
+        Completion::new("timeline", "session activity"),
+        Completion::new("undolist", "undo history"),
+        Completion::new("goto", "<date>"),
+        Completion::new("sort priority", ""),
+        Completion::new("sort due", ""),
+    ];
+
+    let registry = ProjectRegistry::load().unwrap_or_default();
+    for project in registry.list_sorted() {
+        if project.name != state.current_project.name {
+            candidates.push(Completion::new(
+                format!("move-to-project {}", project.name),
+                "project",
+            ));
+        }
+    }
+
+    let mut plugins: Vec<_> = state.plugin_loader.loaded_plugins().collect();
+    plugins.sort_by(|a, b| a.name.cmp(&b.name));
+    for plugin in plugins {
+        candidates.push(Completion::new(
+            format!("plugin run {}", plugin.name),
+            plugin.description.clone(),
+        ));
+    }
+    let mut plugin_actions: Vec<_> = state
+        .plugin_action_registry
+        .actions_by_plugin()
+        .into_values()
+        .flatten()
+        .collect();
+    plugin_actions.sort_by(|a, b| a.namespace.cmp(&b.namespace));
+    for action in plugin_actions {
+        candidates.push(Completion::new(
+            format!("plugin run {} {}", action.plugin_name, action.action_name),
+            action.description.clone(),
+        ));
+    }
+
+    for (action, keys) in state.keybindings.navigate_actions() {
+        let keys: Vec<String> = keys.iter().map(ToString::to_string).collect();
+        candidates.push(Completion::new(action.to_string().replace('_', "-"), keys.join(" ")));
+    }
+    candidates
+}
+
+/// Completions matching the typed command line, best first.
+pub fn complete<'a>(candidates: &'a [Completion], input: &str) -> Vec<&'a Completion> {
+    // Spaces are word separators in the candidates too, so match without them
+    let query: String = input.split_whitespace().collect();
+    fuzzy_filter(candidates, &query, |c| c.text.as_str())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_commands() {
+        assert_eq!(parse("toggle-pin"), Ok(Command::Action(Action::TogglePin)));
+        assert_eq!(parse("toggle_pin"), Ok(Command::Action(Action::TogglePin)));
+        assert_eq!(parse("sort due"), Ok(Command::Sort(SortKey::Due)));
+        assert_eq!(
+            parse("move-to-project  work "),
+            Ok(Command::MoveToProject("work".to_string()))
+        );
+        assert_eq!(parse("goto 2024-11-03"), Ok(Command::Goto("2024-11-03".to_string())));
+        assert_eq!(
+            parse("plugin run github fetch issues"),
+            Ok(Command::PluginRun {
+                plugin: "github".to_string(),
+                rest: "fetch issues".to_string(),
+            })
+        );
+        assert_eq!(parse("tl"), Ok(Command::Timeline));
+    }
+
+    #[test]
+    fn test_parse_errors() {
+        assert!(parse("goto").is_err());
+        assert!(parse("sort name").is_err());
+        assert!(parse("plugin list").is_err());
+        assert!(parse("plugin run").is_err());
+        assert_eq!(parse("frobnicate"), Err("Unknown command: frobnicate".to_string()));
+        // Actions do not take arguments
+        assert!(parse("toggle-pin now").is_err());
+    }
+
+    #[test]
+    fn test_complete_ignores_spaces() {
+        let candidates = vec![
+            Completion::new("sort priority", ""),
+            Completion::new("sort due", ""),
+            Completion::new("toggle-state", "x"),
+        ];
+        let matches = complete(&candidates, "sort d");
+        assert_eq!(matches[0].text, "sort due");
+        assert_eq!(complete(&candidates, "").len(), 3);
+    }
+}
@@ -1,22 +1,36 @@
+use super::activity::Activity;
+use super::command::{self, Command, SortKey};
 use super::mode::Mode;
+use super::macros::{MacroPrompt, MacroStep, Macros, LAST_PLAYED};
+use super::registers::{self, Registers};
 use super::state::{
-    AppState, MoveToProjectSubState, PluginResultSource, PluginSubState, PluginsModalState,
-    PluginsTab, ProjectSubState,
+    installed_rows, matching_projects, AppState, InstalledRow, MoveToProjectSubState,
+    PluginResultSource, PluginSubState, PluginsModalState, PluginsTab, ProjectSubState,
 };
 use crate::clipboard::{copy_to_clipboard, CopyResult};
 use crate::config::Config;
-use crate::keybindings::{Action, KeyBinding, KeyLookupResult};
+use crate::keybindings::{Action, KeyBinding, KeyLookupResult, Modal, ModalAction};
 use crate::plugin::{
     marketplace::PluginEntry, CommandExecutor, GeneratorInfo, PluginAction, PluginErrorKind,
     PluginHostApiImpl, PluginLoadError,
 };
 use crate::project::{Project, ProjectRegistry, DEFAULT_PROJECT_NAME};
-use crate::storage::file::save_todo_list_for_project;
+use crate::storage::attachments::{attach, format_size, list_attachments};
+use crate::storage::conflict::DailyFileConflict;
+use crate::storage::history::with_plugin_origin;
+use crate::storage::markdown::parse_waiting;
+use crate::storage::revision::StaleSave;
 use crate::storage::{execute_rollover_for_project, find_rollover_candidates_for_project, soft_delete_todos_for_project};
-use crate::utils::paths::{get_dailies_dir_for_project, get_logs_dir, get_project_dir};
+use crate::utils::paths::{expand_home, get_logs_dir};
 use crate::utils::cursor::{set_mouse_cursor_default, set_mouse_cursor_pointer};
+use crate::todo::estimate::extract_estimate_tag;
+use crate::todo::item::normalize_link;
+use crate::todo::{Estimate, Filter, Priority, TodoState};
+use crate::ui::components::help::help_lines;
+use crate::ui::components::status_bar;
+use crate::utils::dateparse::{extract_due_tag, parse_date_relative_to};
 use crate::utils::unicode::{
-    next_char_boundary, next_word_boundary, prev_char_boundary, prev_word_boundary,
+    display_width, next_char_boundary, next_word_boundary, prev_char_boundary, prev_word_boundary,
 };
 use crate::utils::upgrade::{check_write_permission, prepare_binary, replace_and_restart, PluginUpgradeSubState, UpgradeSubState};
 use abi_stable::sabi_trait::TD_Opaque;
@@ -24,15 +38,23 @@ use abi_stable::std_types::RBox;
 use anyhow::Result;
 use crossterm::event::{KeyCode, KeyEvent, KeyModifiers, MouseButton, MouseEvent, MouseEventKind};
 use std::collections::HashSet;
-use std::fs;
+use std::path::{Path, PathBuf};
 use totui_plugin_interface::{
     call_plugin_execute_with_host, FfiConfigType, FfiConfigValue, FfiEvent, FfiEventSource,
     FfiFieldChange, HostApi_TO,
 };
 
-/// Total number of lines in the help content (must match render_help_overlay)
-const HELP_TOTAL_LINES: u16 = 58;
+/// How far the help overlay can scroll: its content minus the popup's inner
+/// height (80% of the terminal, minus borders).
+fn help_max_scroll(state: &AppState) -> u16 {
+    let inner_height = ((state.terminal_height * 80) / 100).saturating_sub(2);
+    (help_lines(state).len() as u16).saturating_sub(inner_height)
+}
 const GITHUB_URL: &str = "https://github.com/grimurjonsson/to-tui";
+/// Upper bound for count prefixes, so a stray run of digits stays harmless
+const MAX_COUNT: usize = 999;
+/// Longest gap between two clicks on an item's text that edits it
+const DOUBLE_CLICK_INTERVAL: std::time::Duration = std::time::Duration::from_millis(400);
 
 pub fn handle_key_event(key: KeyEvent, state: &mut AppState) -> Result<()> {
     // Handle Ctrl+C / Cmd+C for copying mouse text selection
@@ -63,7 +85,7 @@ pub fn handle_key_event(key: KeyEvent, state: &mut AppState) -> Result<()> {
                     state.set_status_message(msg);
                 }
                 Err(e) => {
-                    state.set_status_message(format!("Copy failed: {}", e));
+                    state.set_error(format!("Copy failed: {}", e));
                 }
             }
         }
@@ -76,13 +98,35 @@ pub fn handle_key_event(key: KeyEvent, state: &mut AppState) -> Result<()> {
         state.clear_mouse_selection();
     }
 
+    // Ctrl+q quits from anywhere, even mid-edit, asking first if needed
+    if key.code == KeyCode::Char('q')
+        && key.modifiers.contains(KeyModifiers::CONTROL)
+        && state.mode != Mode::ConfirmQuit
+    {
+        state.request_quit();
+        return Ok(());
+    }
+
+    if state.show_timeline {
+        match key.code {
+            KeyCode::Up | KeyCode::Char('k') => {
+                state.timeline_scroll = state.timeline_scroll.saturating_sub(1);
+            }
+            KeyCode::Down | KeyCode::Char('j') => {
+                let max_scroll = state.activity.len().saturating_sub(1) as u16;
+                state.timeline_scroll = state.timeline_scroll.saturating_add(1).min(max_scroll);
+            }
+            KeyCode::Esc | KeyCode::Enter | KeyCode::Char('q') => {
+                state.show_timeline = false;
+            }
+            _ => {}
+        }
+        return Ok(());
+    }
+
     // Handle help overlay scrolling when help is visible
     if state.show_help {
-        // Calculate max scroll based on terminal height
-        // Help popup is 80% of terminal height, minus 2 for borders
-        let popup_height = (state.terminal_height * 80) / 100;
-        let inner_height = popup_height.saturating_sub(2);
-        let max_scroll = HELP_TOTAL_LINES.saturating_sub(inner_height);
+        let max_scroll = help_max_scroll(state);
 
         match key.code {
             KeyCode::Up | KeyCode::Char('k') => {
@@ -109,12 +153,30 @@ pub fn handle_key_event(key: KeyEvent, state: &mut AppState) -> Result<()> {
         Mode::Visual => handle_visual_mode(key, state)?,
         Mode::Edit => handle_edit_mode(key, state)?,
         Mode::ConfirmDelete => handle_confirm_delete_mode(key, state)?,
+        Mode::ConfirmQuit => handle_confirm_quit_mode(key, state)?,
         Mode::Plugin => handle_plugin_mode(key, state)?,
         Mode::Rollover => handle_rollover_mode(key, state)?,
         Mode::UpgradePrompt => handle_upgrade_prompt_mode(key, state)?,
         Mode::ProjectSelect => handle_project_select_mode(key, state)?,
         Mode::MoveToProject => handle_move_to_project_mode(key, state)?,
         Mode::EditDescription => handle_edit_description_mode(key, state)?,
+        Mode::EditEstimate => handle_edit_estimate_mode(key, state)?,
+        Mode::MoveToDate => handle_move_to_date_mode(key, state)?,
+        Mode::EditDue => handle_edit_due_mode(key, state)?,
+        Mode::Search => handle_search_mode(key, state)?,
+        Mode::EditLink => handle_edit_link_mode(key, state)?,
+        Mode::PlanReview => handle_plan_review_mode(key, state)?,
+        Mode::Filter => handle_filter_mode(key, state)?,
+        Mode::Command => handle_command_mode(key, state)?,
+        Mode::WeekView => handle_week_view_mode(key, state)?,
+        Mode::Calendar => handle_calendar_mode(key, state)?,
+        Mode::ThemePicker => handle_theme_picker_mode(key, state),
+        Mode::UndoHistory => handle_undo_history_mode(key, state)?,
+        Mode::SearchResults => handle_search_results_mode(key, state)?,
+        Mode::Trash => handle_trash_mode(key, state)?,
+        Mode::FileConflict => handle_file_conflict_mode(key, state)?,
+        Mode::StaleSave => handle_stale_save_mode(key, state)?,
+        Mode::AttachFile => handle_attach_file_mode(key, state)?,
     }
     Ok(())
 }
@@ -122,9 +184,7 @@ pub fn handle_key_event(key: KeyEvent, state: &mut AppState) -> Result<()> {
 pub fn handle_mouse_event(mouse: MouseEvent, state: &mut AppState) -> Result<()> {
     // Handle scroll events in help overlay
     if state.show_help {
-        let popup_height = (state.terminal_height * 80) / 100;
-        let inner_height = popup_height.saturating_sub(2);
-        let max_scroll = HELP_TOTAL_LINES.saturating_sub(inner_height);
+        let max_scroll = help_max_scroll(state);
 
         match mouse.kind {
             MouseEventKind::ScrollUp => {
@@ -216,7 +276,7 @@ fn handle_left_click(state: &mut AppState, clicked_row: usize, clicked_col: usiz
             format!("v{}", env!("CARGO_PKG_VERSION"))
         };
 
-        let version_start = state.terminal_width.saturating_sub(version_text.len() as u16) as usize;
+        let version_start = state.terminal_width.saturating_sub(display_width(&version_text) as u16) as usize;
         let github_start = version_start.saturating_sub(github_link.len());
         let github_end = version_start - 1;
 
@@ -229,6 +289,14 @@ fn handle_left_click(state: &mut AppState, clicked_row: usize, clicked_col: usiz
             state.open_upgrade_modal();
             return Ok(());
         }
+
+        if state.mode == Mode::Navigate
+            && status_bar::overdue_columns(state).is_some_and(|columns| columns.contains(&clicked_col))
+        {
+            state.clear_selection();
+            state.jump_to_first_overdue();
+            return Ok(());
+        }
     }
 
     // Item clicks only work in Navigate mode
@@ -277,22 +345,30 @@ fn handle_left_click(state: &mut AppState, clicked_row: usize, clicked_col: usiz
             }
             ClickZone::Content => {
                 state.cursor_position = item_idx;
+                let now = std::time::Instant::now();
+                let double_click = state.last_content_click.is_some_and(|(at, idx)| {
+                    idx == item_idx && now.duration_since(at) <= DOUBLE_CLICK_INTERVAL
+                });
+                if double_click {
+                    state.last_content_click = None;
+                    state.sync_list_state();
+                    enter_edit_mode(state);
+                    return Ok(());
+                }
+                state.last_content_click = Some((now, item_idx));
             }
         }
         state.sync_list_state();
     }
 
-    if state.unsaved_changes {
-        save_todo_list_for_project(&state.todo_list, &state.current_project.name)?;
-        state.unsaved_changes = false;
-        state.last_save_time = Some(std::time::Instant::now());
-    }
+    state.schedule_save();
 
     Ok(())
 }
 
 /// Check if the mouse is over a clickable link in the status bar.
-/// Returns true if over the GitHub link or the upgrade version text (when available).
+/// Returns true if over the GitHub link, the upgrade version text (when
+/// available) or the overdue count.
 fn is_mouse_over_status_bar_link(state: &AppState, row: usize, col: usize) -> bool {
     // Check if on status bar (bottom row)
     if row != state.terminal_height.saturating_sub(1) as usize {
@@ -311,7 +387,7 @@ fn is_mouse_over_status_bar_link(state: &AppState, row: usize, col: usize) -> bo
     // Layout: ... {github_link} {version_text} {trailing_space}
     // version_text ends at terminal_width - 1 (trailing space)
     let version_end = state.terminal_width.saturating_sub(1) as usize;
-    let version_start = version_end.saturating_sub(version_text.len());
+    let version_start = version_end.saturating_sub(display_width(&version_text));
     let github_start = version_start.saturating_sub(github_link_with_space.len());
     let github_end = github_start + github_link.len(); // exclude the trailing space
 
@@ -325,6 +401,10 @@ fn is_mouse_over_status_bar_link(state: &AppState, row: usize, col: usize) -> bo
         return true;
     }
 
+    if status_bar::overdue_columns(state).is_some_and(|columns| columns.contains(&col)) {
+        return true;
+    }
+
     false
 }
 
@@ -339,9 +419,10 @@ fn map_click_to_item(
     clicked_row: usize,
     clicked_col: usize,
 ) -> Option<(usize, ClickZone)> {
-    let list_start_row = 1;
+    // Below the list's top border and the breadcrumb line, if any
+    let list_start_row = 1 + state.breadcrumb_height() as usize;
 
-    if clicked_row < list_start_row {
+    if clicked_row < list_start_row || clicked_col >= state.list_area_width() as usize {
         return None;
     }
 
@@ -353,7 +434,7 @@ fn map_click_to_item(
     let mut current_visual_row = 0;
     let mut list_item_count = 0;
 
-    let hidden_indices = state.todo_list.build_hidden_indices();
+    let hidden_indices = state.hidden_indices();
 
     for (idx, item) in state.todo_list.items.iter().enumerate() {
         if hidden_indices.contains(&idx) {
@@ -371,16 +452,17 @@ fn map_click_to_item(
             continue;
         }
 
-        let item_height = calculate_item_visual_height(state, idx, item);
+        let item_height = state.item_line_height(idx);
 
         if visual_row >= current_visual_row && visual_row < current_visual_row + item_height {
-            let indent_width = item.indent_level * 2;
-            let fold_icon_end = indent_width + 2;
-            let checkbox_end = fold_icon_end + 4;
+            // Column within the row, past the left border and the gutter
+            let col = clicked_col.saturating_sub(1 + state.gutter_width());
+            let fold_icon_end = item.indent_level * 2 + state.theme.glyphs.fold_width();
+            let checkbox_end = state.item_content_offset(idx);
 
-            let zone = if clicked_col < fold_icon_end {
+            let zone = if col < fold_icon_end {
                 ClickZone::FoldIcon
-            } else if clicked_col < checkbox_end {
+            } else if col < checkbox_end {
                 ClickZone::Checkbox
             } else {
                 ClickZone::Content
@@ -394,7 +476,7 @@ fn map_click_to_item(
 
         // Account for expanded description box as separate ListItem with its own visual height
         if !item.collapsed && item.description.is_some() {
-            let desc_height = calculate_description_visual_height(state, item);
+            let desc_height = state.description_line_height(item);
             // Click on description box area - treat as clicking the parent item
             if visual_row >= current_visual_row && visual_row < current_visual_row + desc_height {
                 return Some((idx, ClickZone::Content));
@@ -415,8 +497,7 @@ fn calculate_expected_offset(state: &AppState) -> usize {
         None => return 0,
     };
 
-    // Viewport height = terminal height - borders (2) - status bar (1)
-    let viewport_height = state.terminal_height.saturating_sub(3).max(1) as usize;
+    let viewport_height = state.list_viewport_height();
 
     // Current offset from list_state (may be stale but gives us a starting point)
     let current_offset = state.list_state.offset();
@@ -435,82 +516,6 @@ fn calculate_expected_offset(state: &AppState) -> usize {
     current_offset
 }
 
-fn calculate_item_visual_height(
-    state: &AppState,
-    idx: usize,
-    item: &crate::todo::TodoItem,
-) -> usize {
-    // Calculate available width for content (terminal - borders)
-    let available_width = state.terminal_width.saturating_sub(2) as usize;
-
-    // Calculate prefix width: indent + fold_icon + checkbox
-    let indent_width = item.indent_level * 2;
-    let fold_icon_width = 2; // "▶ " or "▼ " or "  "
-    let checkbox_width = 4; // "[x] "
-    let prefix_width = indent_width + fold_icon_width + checkbox_width;
-
-    // Content area width
-    let content_max_width = available_width.saturating_sub(prefix_width);
-
-    // Calculate wrapped height for the todo content
-    let due_date_len = item
-        .due_date
-        .map(|_| 13) // " [YYYY-MM-DD]"
-        .unwrap_or(0);
-
-    let has_children = state.todo_list.has_children(idx);
-    let collapse_indicator_len = if item.collapsed && has_children {
-        8 // " (X/Y)" rough estimate
-    } else {
-        0
-    };
-
-    let content_len = item.content.len() + due_date_len + collapse_indicator_len;
-    let wrapped_lines = if content_max_width > 0 {
-        content_len.div_ceil(content_max_width)
-    } else {
-        1
-    };
-
-    // Note: Description boxes are handled separately via calculate_description_visual_height
-    wrapped_lines.max(1)
-}
-
-/// Calculate the visual height of an expanded description box.
-/// Description boxes have: top border (1) + content lines + bottom border (1)
-fn calculate_description_visual_height(
-    state: &AppState,
-    item: &crate::todo::TodoItem,
-) -> usize {
-    if let Some(ref desc) = item.description {
-        // Calculate box width similar to rendering
-        let available_width = state.terminal_width.saturating_sub(2) as usize;
-        let box_indent_width = item.indent_level * 2 + 4; // base indent + "    "
-        let inner_width = available_width.saturating_sub(box_indent_width + 4); // 4 for borders and padding
-
-        // Count wrapped lines
-        let mut line_count = 0;
-        for paragraph in desc.split('\n') {
-            if paragraph.is_empty() {
-                line_count += 1;
-            } else {
-                let para_len = paragraph.len();
-                let wrapped = if inner_width > 0 {
-                    para_len.div_ceil(inner_width)
-                } else {
-                    1
-                };
-                line_count += wrapped.max(1);
-            }
-        }
-
-        // top border + content lines + bottom border
-        line_count + 2
-    } else {
-        0
-    }
-}
-
 fn handle_navigate_mode(key: KeyEvent, state: &mut AppState) -> Result<()> {
     let pending = if let (Some(pending_key), Some(pending_time)) =
         (state.pending_key.take(), state.pending_key_time.take())
@@ -525,16 +530,108 @@ fn handle_navigate_mode(key: KeyEvent, state: &mut AppState) -> Result<()> {
         None
     };
 
+    // `Qa` starts recording macro a, `@a` replays it
+    if let Some(prompt) = state.awaiting_macro.take() {
+        if let KeyCode::Char(c) = key.code {
+            match prompt {
+                MacroPrompt::Record if Macros::is_valid_name(c) => {
+                    state.macros.start_recording(c);
+                }
+                MacroPrompt::Play { count } if Macros::is_valid_name(c) || c == LAST_PLAYED => {
+                    play_macro(c, count, state)?;
+                }
+                _ => {}
+            }
+        }
+        return Ok(());
+    }
+
+    // `"a` names the register for the next yank or paste
+    if state.awaiting_register {
+        state.awaiting_register = false;
+        if let KeyCode::Char(c) = key.code
+            && Registers::is_valid_name(c)
+        {
+            state.pending_register = Some(c);
+        }
+        return Ok(());
+    }
+    if pending.is_none()
+        && key.code == KeyCode::Char('"')
+        && state.keybindings.lookup_navigate(&key, None) == KeyLookupResult::None
+    {
+        state.awaiting_register = true;
+        return Ok(());
+    }
+
+    // Digits build up a count for the next action (5j, 3dd), unless bound themselves
+    if pending.is_none()
+        && let KeyCode::Char(c @ '0'..='9') = key.code
+        && (c != '0' || state.pending_count.is_some())
+        && !key.modifiers.intersects(KeyModifiers::CONTROL | KeyModifiers::ALT)
+        && state.keybindings.lookup_navigate(&key, None) == KeyLookupResult::None
+    {
+        let digit = c.to_digit(10).unwrap_or(0) as usize;
+        let count = state.pending_count.unwrap_or(0) * 10 + digit;
+        state.pending_count = Some(count.min(MAX_COUNT));
+        return Ok(());
+    }
+
+    // While matches are highlighted, search bindings (n/N/Esc) win
+    if pending.is_none()
+        && state.search_query.is_some()
+        && let Some(action) = state.keybindings.get_search_action(&key)
+    {
+        state.pending_count = None;
+        state.macros.record(MacroStep {
+            action,
+            count: None,
+            register: None,
+        });
+        return execute_navigate_action(action, state);
+    }
+
     match state.keybindings.lookup_navigate(&key, pending) {
         KeyLookupResult::Pending => {
             state.pending_key = Some(KeyBinding::from_event(&key));
             state.pending_key_time = Some(std::time::Instant::now());
             return Ok(());
         }
+        KeyLookupResult::Action(Action::RecordMacro) => {
+            state.pending_count = None;
+            state.pending_register = None;
+            match state.macros.stop_recording() {
+                Some((name, len)) => {
+                    state.set_status_message(format!("Recorded {len} action(s) into @{name}"));
+                }
+                None => state.awaiting_macro = Some(MacroPrompt::Record),
+            }
+        }
+        KeyLookupResult::Action(Action::PlayMacro) => {
+            let count = state.pending_count.take().unwrap_or(1);
+            state.pending_register = None;
+            state.awaiting_macro = Some(MacroPrompt::Play { count });
+        }
+        KeyLookupResult::Action(Action::RepeatChange) => {
+            let count = state.pending_count.take();
+            repeat_last_change(count, state)?;
+        }
         KeyLookupResult::Action(action) => {
-            execute_navigate_action(action, state)?;
+            let step = MacroStep {
+                action,
+                count: state.pending_count.take(),
+                register: state.pending_register,
+            };
+            state.macros.record(step);
+            if action.is_repeatable_change() {
+                state.last_change = Some(step);
+            }
+            execute_counted_action(action, step.count, state)?;
+            state.pending_register = None;
         }
         KeyLookupResult::None => {
+            state.pending_count = None;
+            state.pending_register = None;
             // Check plugin actions when host keybinding returns None
             let binding = KeyBinding::from_event(&key);
             if let Some(plugin_action) = state.plugin_action_registry.lookup(&binding) {
@@ -543,12 +640,81 @@ fn handle_navigate_mode(key: KeyEvent, state: &mut AppState) -> Result<()> {
         }
     }
 
-    if state.unsaved_changes {
-        save_todo_list_for_project(&state.todo_list, &state.current_project.name)?;
-        state.unsaved_changes = false;
-        state.last_save_time = Some(std::time::Instant::now());
+    state.schedule_save();
+
+    Ok(())
+}
+
+/// Run `action` `count` times as a single undo step. Stops early once the
+/// action leaves navigate mode, e.g. `3dd` reaching an item with children.
+/// For `gg` and `G` the count is a line number instead.
+fn execute_counted_action(action: Action, count: Option<usize>, state: &mut AppState) -> Result<()> {
+    if let (Action::GoToTop | Action::GoToBottom, Some(line)) = (action, count) {
+        state.clear_selection();
+        state.go_to_line(line);
+        return Ok(());
+    }
+    if action == Action::OpenAttachment {
+        open_attachment(count.unwrap_or(1), state);
+        return Ok(());
+    }
+    let count = count.unwrap_or(1);
+    if count <= 1 || !action.repeats_with_count() {
+        return execute_navigate_action(action, state);
+    }
+
+    let undo_depth = state.undo_stack.len();
+    for _ in 0..count {
+        execute_navigate_action(action, state)?;
+        if state.mode != Mode::Navigate {
+            break;
+        }
     }
+    // Keep only the snapshot from before the first repeat
+    state.undo_stack.truncate(undo_depth + 1);
+    Ok(())
+}
+
+/// `.`: make the last change again on the current item. A count replaces
+/// the one the change was made with.
+fn repeat_last_change(count: Option<usize>, state: &mut AppState) -> Result<()> {
+    let Some(change) = state.last_change else {
+        return Ok(());
+    };
+    let step = MacroStep {
+        count: count.or(change.count),
+        ..change
+    };
+    state.macros.record(step);
+    state.pending_register = step.register;
+    execute_counted_action(step.action, step.count, state)?;
+    state.pending_register = None;
+    Ok(())
+}
 
+/// Replay macro `name` `count` times as a single undo step. Stops once an
+/// action leaves navigate mode. While another macro is being recorded, the
+/// replayed actions go into it.
+fn play_macro(name: char, count: usize, state: &mut AppState) -> Result<()> {
+    let Some(steps) = state.macros.play(name) else {
+        state.set_warning(format!("Macro @{name} is empty"));
+        return Ok(());
+    };
+
+    let undo_depth = state.undo_stack.len();
+    'replay: for _ in 0..count {
+        for &step in &steps {
+            state.macros.record(step);
+            state.pending_register = step.register;
+            execute_counted_action(step.action, step.count, state)?;
+            state.pending_register = None;
+            if state.mode != Mode::Navigate {
+                break 'replay;
+            }
+        }
+    }
+    // Keep only the snapshot from before the first action
+    state.undo_stack.truncate(undo_depth + 1);
     Ok(())
 }
 
@@ -557,6 +723,12 @@ fn execute_navigate_action(action: Action, state: &mut AppState) -> Result<()> {
         action,
         Action::ToggleState
             | Action::CycleState
+            | Action::SetStateTodo
+            | Action::SetStateDone
+            | Action::SetStateInProgress
+            | Action::SetStateQuestion
+            | Action::SetStateImportant
+            | Action::SetStateCancelled
             | Action::Delete
             | Action::NewItem
             | Action::NewItemSameLevel
@@ -568,18 +740,35 @@ fn execute_navigate_action(action: Action, state: &mut AppState) -> Result<()> {
             | Action::OutdentWithChildren
             | Action::MoveItemUp
             | Action::MoveItemDown
+            | Action::PasteBelow
+            | Action::PasteAbove
             | Action::ToggleCollapse
+            | Action::CollapseAll
+            | Action::ExpandAll
+            | Action::ExpandOneLevel
             | Action::Undo
+            | Action::OpenUndoHistory
             | Action::CyclePriority
+            | Action::TogglePin
             | Action::SortByPriority
             | Action::MoveToProject
+            | Action::MoveToDate
+            | Action::SetDueDate
             | Action::EditDescription
+            | Action::EditEstimate
+            | Action::EditLink
+            | Action::AttachFile
+            | Action::ArchiveItem
     );
 
     if state.is_readonly() && dominated_by_readonly {
         return Ok(());
     }
 
+    if state.split_focused() && execute_split_pane_action(action, state, dominated_by_readonly) {
+        return Ok(());
+    }
+
     match action {
         Action::MoveUp => {
             state.clear_selection();
@@ -589,41 +778,120 @@ fn execute_navigate_action(action: Action, state: &mut AppState) -> Result<()> {
             state.clear_selection();
             state.move_cursor_down();
         }
+        Action::GoToTop => {
+            state.clear_selection();
+            state.go_to_line(1);
+        }
+        Action::GoToBottom => {
+            state.clear_selection();
+            state.go_to_line(usize::MAX);
+        }
         Action::ToggleVisual => {
             state.start_or_extend_selection();
             state.mode = Mode::Visual;
         }
         Action::ExitVisual => {}
+        // Read the macro name from the next key in handle_navigate_mode
+        Action::RecordMacro | Action::PlayMacro | Action::RepeatChange => {}
         Action::ToggleState => {
             state.toggle_current_item_state();
         }
         Action::CycleState => {
             state.cycle_current_item_state();
         }
+        Action::SetStateTodo => {
+            state.set_current_item_state(TodoState::Empty);
+        }
+        Action::SetStateDone => {
+            state.set_current_item_state(TodoState::Checked);
+        }
+        Action::SetStateInProgress => {
+            state.set_current_item_state(TodoState::InProgress);
+        }
+        Action::SetStateQuestion => {
+            state.set_current_item_state(TodoState::Question);
+        }
+        Action::SetStateImportant => {
+            state.set_current_item_state(TodoState::Exclamation);
+        }
+        Action::SetStateCancelled => {
+            state.set_current_item_state(TodoState::Cancelled);
+        }
         Action::CyclePriority => {
             state.cycle_priority();
         }
+        Action::TogglePin => {
+            state.toggle_pin();
+        }
         Action::SortByPriority => {
             state.sort_by_priority();
         }
-        Action::Delete => {
-            if !state.todo_list.items.is_empty() {
-                let has_children = state.todo_list.has_children(state.cursor_position);
-                if has_children {
-                    let (_, end) = state
-                        .todo_list
-                        .get_item_range(state.cursor_position)
-                        .unwrap_or((state.cursor_position, state.cursor_position + 1));
-                    let subtask_count = end - state.cursor_position - 1;
-                    state.pending_delete_subtask_count = Some(subtask_count);
-                    state.mode = Mode::ConfirmDelete;
-                } else {
-                    state.save_undo();
-                    delete_current_item(state)?;
-                    save_todo_list_for_project(&state.todo_list, &state.current_project.name)?;
-                    state.unsaved_changes = false;
-                    state.last_save_time = Some(std::time::Instant::now());
-                }
+        Action::ToggleWaitingFilter => {
+            state.toggle_waiting_filter();
+        }
+        Action::OpenFilterMenu => {
+            state.mode = Mode::Filter;
+        }
+        Action::StartCommand => {
+            state.start_command();
+        }
+        Action::ToggleShortIds => {
+            state.toggle_short_ids();
+        }
+        Action::ToggleLineNumbers => {
+            state.toggle_line_numbers();
+        }
+        Action::ToggleSinkCompleted => {
+            state.toggle_sink_completed();
+        }
+        Action::ToggleSplitView => {
+            state.toggle_split_view();
+        }
+        Action::SwitchPane => {
+            state.switch_split_focus();
+        }
+        Action::ToggleDetailPane => {
+            state.toggle_detail_pane();
+        }
+        Action::OpenThemePicker => {
+            state.open_theme_picker();
+        }
+        Action::StartSearch => {
+            state.start_search();
+        }
+        Action::SearchNext => {
+            state.jump_to_match(true);
+        }
+        Action::SearchPrev => {
+            state.jump_to_match(false);
+        }
+        Action::ClearSearch => {
+            state.clear_search();
+        }
+        Action::ArchiveItem => match state.archive_current_item() {
+            Ok(count) => {
+                state.set_status_message(format!("Archived {} item(s)", count));
+            }
+            Err(e) => {
+                state.set_error(e.to_string());
+            }
+        },
+        Action::Delete if !state.todo_list.items.is_empty() => {
+            let has_children = state.todo_list.has_children(state.cursor_position);
+            if has_children {
+                let (_, end) = state
+                    .todo_list
+                    .get_item_range(state.cursor_position)
+                    .unwrap_or((state.cursor_position, state.cursor_position + 1));
+                let subtask_count = end - state.cursor_position - 1;
+                state.pending_delete_subtask_count = Some(subtask_count);
+                state.mode = Mode::ConfirmDelete;
+            } else {
+                state.save_undo();
+                delete_current_item(state)?;
+                state.save_list()?;
+                state.unsaved_changes = false;
+                state.last_save_time = Some(std::time::Instant::now());
             }
         }
         Action::NewItem => {
@@ -638,23 +906,61 @@ fn execute_navigate_action(action: Action, state: &mut AppState) -> Result<()> {
         Action::EnterEditMode => {
             enter_edit_mode(state);
         }
-        Action::EditDescription => {
-            if state.selected_item().is_some() {
-                let description = state.selected_item().and_then(|item| item.description.clone());
-                let desc_buffer: Vec<String> = match &description {
-                    Some(desc) => desc.split('\n').map(String::from).collect(),
-                    None => vec![String::new()],
-                };
-                let last_line = desc_buffer.last().map_or(0, |l| l.len());
-                let desc_cursor_row = desc_buffer.len() - 1;
-                state.desc_original = description;
-                state.desc_buffer = desc_buffer;
-                state.desc_cursor_row = desc_cursor_row;
-                state.desc_cursor_col = last_line;
-                state.desc_scroll_offset = 0;
-                state.mode = Mode::EditDescription;
+        Action::EditDescription if state.selected_item().is_some() => {
+            let description = state.selected_item().and_then(|item| item.description.clone());
+            let desc_buffer: Vec<String> = match &description {
+                Some(desc) => desc.split('\n').map(String::from).collect(),
+                None => vec![String::new()],
+            };
+            let last_line = desc_buffer.last().map_or(0, |l| l.len());
+            let desc_cursor_row = desc_buffer.len() - 1;
+            state.desc_original = description;
+            state.desc_buffer = desc_buffer;
+            state.desc_cursor_row = desc_cursor_row;
+            state.desc_cursor_col = last_line;
+            state.desc_scroll_offset = 0;
+            state.mode = Mode::EditDescription;
+        }
+        Action::EditEstimate if state.selected_item().is_some() => {
+            let current = state
+                .selected_item()
+                .and_then(|item| item.estimate)
+                .map(|e| e.to_tag_str())
+                .unwrap_or_default();
+            state.edit_cursor_pos = current.len();
+            state.edit_buffer = current;
+            state.mode = Mode::EditEstimate;
+        }
+        Action::EditLink if state.selected_item().is_some() => {
+            let current = state
+                .selected_item()
+                .and_then(|item| item.link.clone())
+                .unwrap_or_default();
+            state.edit_cursor_pos = current.len();
+            state.edit_buffer = current;
+            state.mode = Mode::EditLink;
+        }
+        Action::OpenLink => match state.selected_item().and_then(|item| item.link.clone()) {
+            // The file may have been edited by hand, so check again before
+            // handing the link to the system opener
+            Some(link) if normalize_link(&link).is_err() => {
+                state.set_error(format!("Refusing to open {link}: only http, https and mailto links are opened"));
             }
-        }
+            Some(link) => {
+                if let Err(e) = open::that(&link) {
+                    state.set_error(format!("Failed to open {link}: {e}"));
+                } else {
+                    state.set_status_message(format!("Opened {link}"));
+                }
+            }
+            None => state.set_warning("No link on this item (U to add one)".to_string()),
+        },
+        Action::AttachFile if state.selected_item().is_some() => {
+            state.edit_buffer.clear();
+            state.edit_cursor_pos = 0;
+            state.mode = Mode::AttachFile;
+        }
+        Action::OpenAttachment => open_attachment(1, state),
         Action::Indent => {
             if let Some((start, end)) = state.get_selection_range() {
                 state.save_undo();
@@ -737,25 +1043,42 @@ fn execute_navigate_action(action: Action, state: &mut AppState) -> Result<()> {
         Action::CollapseOrParent => {
             state.collapse_or_move_to_parent();
         }
+        Action::CollapseAll => {
+            state.collapse_all();
+        }
+        Action::ExpandAll => {
+            state.expand_all();
+        }
+        Action::ExpandOneLevel => {
+            state.expand_one_level();
+        }
+        Action::FocusSubtree => {
+            state.toggle_focus();
+        }
+        // Saved with the rest of the event's changes
         Action::Undo => {
-            if state.undo() {
-                save_todo_list_for_project(&state.todo_list, &state.current_project.name)?;
-                state.last_save_time = Some(std::time::Instant::now());
-            }
+            state.undo();
+        }
+        Action::OpenUndoHistory => {
+            state.open_undo_history();
+        }
+        Action::OpenTrash => {
+            state.open_trash();
         }
         Action::ToggleHelp => {
             state.show_help = !state.show_help;
         }
-        Action::CloseHelp => {
-            if state.show_help {
-                state.show_help = false;
-            }
+        Action::CloseHelp if state.show_help => {
+            state.show_help = false;
+        }
+        Action::CloseHelp if state.focus_root.is_some() => {
+            state.exit_focus();
         }
         Action::Quit => {
             if state.show_help {
                 state.show_help = false;
             } else {
-                state.should_quit = true;
+                state.request_quit();
             }
         }
         Action::PrevDay => {
@@ -767,6 +1090,12 @@ fn execute_navigate_action(action: Action, state: &mut AppState) -> Result<()> {
         Action::GoToToday => {
             state.navigate_to_today()?;
         }
+        Action::OpenWeekView => {
+            state.open_week_view();
+        }
+        Action::OpenCalendar => {
+            state.open_calendar();
+        }
         Action::OpenPluginMenu => {
             state.open_plugins_modal();
         }
@@ -786,35 +1115,22 @@ fn execute_navigate_action(action: Action, state: &mut AppState) -> Result<()> {
         Action::MoveToProject => {
             state.open_move_to_project_modal();
         }
+        Action::MoveToDate if state.selected_item().is_some() => {
+            state.edit_buffer.clear();
+            state.edit_cursor_pos = 0;
+            state.mode = Mode::MoveToDate;
+        }
+        Action::SetDueDate => {
+            state.open_due_prompt();
+        }
         Action::Yank => {
-            if let Some(item) = state.selected_item() {
-                let text = item.content.clone();
-                // Truncate display text if too long
-                let display_text = if text.len() > 40 {
-                    format!("{}...", &text[..37])
-                } else {
-                    text.clone()
-                };
-                match copy_to_clipboard(&text) {
-                    Ok(CopyResult::SystemClipboard) => {
-                        state.set_status_message(format!("Copied: {}", display_text));
-                    }
-                    Ok(CopyResult::InternalBuffer { file_path }) => {
-                        // Headless fallback - saved to internal buffer and file
-                        let msg = match file_path {
-                            Some(path) => format!(
-                                "Copied to buffer (no clipboard): {} | Saved to {}",
-                                display_text,
-                                path.display()
-                            ),
-                            None => format!("Copied to buffer (no clipboard): {}", display_text),
-                        };
-                        state.set_status_message(msg);
-                    }
-                    Err(e) => {
-                        state.set_status_message(format!("Copy failed: {}", e));
-                    }
-                }
+            yank_to_register(state);
+        }
+        Action::PasteBelow | Action::PasteAbove => {
+            let register = state.pending_register;
+            if state.paste(register, action == Action::PasteAbove).is_none() {
+                let name = register.unwrap_or(registers::UNNAMED);
+                state.set_warning(format!("Register \"{name} is empty"));
             }
         }
         Action::CopyLogPath => {
@@ -837,12 +1153,12 @@ fn execute_navigate_action(action: Action, state: &mut AppState) -> Result<()> {
                             state.set_status_message(msg);
                         }
                         Err(e) => {
-                            state.set_status_message(format!("Could not copy log path: {}", e));
+                            state.set_error(format!("Could not copy log path: {}", e));
                         }
                     }
                 }
                 Err(e) => {
-                    state.set_status_message(format!("Could not copy log path: {}", e));
+                    state.set_error(format!("Could not copy log path: {}", e));
                 }
             }
         }
@@ -851,21 +1167,67 @@ fn execute_navigate_action(action: Action, state: &mut AppState) -> Result<()> {
     Ok(())
 }
 
-fn handle_visual_mode(key: KeyEvent, state: &mut AppState) -> Result<()> {
-    if let Some(action) = state.keybindings.get_visual_action(&key) {
-        execute_visual_action(action, state)?;
+/// Run `action` in the split view's other day while it has focus. Returns
+/// false for actions it leaves to the list.
+fn execute_split_pane_action(action: Action, state: &mut AppState, mutates: bool) -> bool {
+    match action {
+        Action::MoveUp => state.split_view_move_cursor(-1),
+        Action::MoveDown => state.split_view_move_cursor(1),
+        Action::GoToTop => state.split_view_move_cursor(isize::MIN),
+        Action::GoToBottom => state.split_view_move_cursor(isize::MAX),
+        Action::PrevDay => state.split_view_shift_day(-1),
+        Action::NextDay => state.split_view_shift_day(1),
+        Action::Yank => state.yank_from_split_view(state.pending_register),
+        Action::CloseHelp if !state.show_help => state.switch_split_focus(),
+        _ if mutates => {
+            state.set_status_message("This day is read-only here (Ctrl+w w back to the list)".to_string());
+        }
+        _ => return false,
     }
+    true
+}
 
-    if state.unsaved_changes {
-        save_todo_list_for_project(&state.todo_list, &state.current_project.name)?;
-        state.unsaved_changes = false;
-        state.last_save_time = Some(std::time::Instant::now());
+fn handle_visual_mode(key: KeyEvent, state: &mut AppState) -> Result<()> {
+    match state.keybindings.get_visual_action(&key) {
+        Some(Action::RepeatChange) => {
+            if let Some(change) = state.last_change {
+                execute_visual_action(change.action, state)?;
+            }
+        }
+        Some(action) => {
+            if action.is_repeatable_change() {
+                state.last_change = Some(MacroStep {
+                    action,
+                    count: None,
+                    register: None,
+                });
+            }
+            execute_visual_action(action, state)?;
+        }
+        None => {}
     }
 
+    state.schedule_save();
+
     Ok(())
 }
 
 fn execute_visual_action(action: Action, state: &mut AppState) -> Result<()> {
+    let modifies_list = matches!(
+        action,
+        Action::Indent
+            | Action::Outdent
+            | Action::Undo
+            | Action::Delete
+            | Action::ToggleState
+            | Action::CyclePriority
+            | Action::SetDueDate
+            | Action::MoveToProject
+    );
+    if state.is_readonly() && modifies_list {
+        return Ok(());
+    }
+
     match action {
         Action::MoveUp => {
             state.move_cursor_up();
@@ -881,11 +1243,40 @@ fn execute_visual_action(action: Action, state: &mut AppState) -> Result<()> {
             state.clear_selection();
             state.mode = Mode::Navigate;
         }
-        Action::Undo => {
-            if state.undo() {
-                save_todo_list_for_project(&state.todo_list, &state.current_project.name)?;
-                state.last_save_time = Some(std::time::Instant::now());
+        Action::Yank => {
+            yank_to_register(state);
+            if let Some((start, _)) = state.get_selection_range() {
+                state.cursor_position = start;
+            }
+            state.clear_selection();
+            state.mode = Mode::Navigate;
+        }
+        Action::Delete => {
+            if let Some((start, end)) = state.target_range() {
+                state.save_undo();
+                delete_range(state, start, end)?;
+                state.cursor_position = start;
+                state.clamp_cursor();
+                state.unsaved_changes = true;
             }
+            state.clear_selection();
+            state.mode = Mode::Navigate;
+        }
+        Action::ToggleState => {
+            state.toggle_selection_state();
+        }
+        Action::CyclePriority => {
+            state.cycle_selection_priority();
+        }
+        Action::SetDueDate => {
+            state.open_due_prompt();
+        }
+        Action::MoveToProject => {
+            state.open_move_to_project_modal();
+        }
+        // Saved with the rest of the event's changes
+        Action::Undo => {
+            state.undo();
         }
         Action::Indent => {
             if let Some((start, end)) = state.get_selection_range() {
@@ -929,17 +1320,17 @@ fn execute_visual_action(action: Action, state: &mut AppState) -> Result<()> {
 }
 
 fn handle_confirm_delete_mode(key: KeyEvent, state: &mut AppState) -> Result<()> {
-    match key.code {
-        KeyCode::Char('y') | KeyCode::Char('Y') | KeyCode::Enter => {
+    match state.keybindings.get_modal_action(Modal::ConfirmDelete, &key) {
+        Some(ModalAction::Confirm) => {
             state.save_undo();
             delete_current_item(state)?;
-            save_todo_list_for_project(&state.todo_list, &state.current_project.name)?;
+            state.save_list()?;
             state.unsaved_changes = false;
             state.last_save_time = Some(std::time::Instant::now());
             state.pending_delete_subtask_count = None;
             state.mode = Mode::Navigate;
         }
-        KeyCode::Char('n') | KeyCode::Char('N') | KeyCode::Esc => {
+        Some(ModalAction::Cancel) => {
             state.pending_delete_subtask_count = None;
             state.mode = Mode::Navigate;
         }
@@ -948,15 +1339,49 @@ fn handle_confirm_delete_mode(key: KeyEvent, state: &mut AppState) -> Result<()>
     Ok(())
 }
 
+fn handle_confirm_quit_mode(key: KeyEvent, state: &mut AppState) -> Result<()> {
+    match state.keybindings.get_modal_action(Modal::ConfirmQuit, &key) {
+        Some(ModalAction::Confirm) => {
+            state.mode = state.mode_before_quit;
+            match save_before_quit(state) {
+                Ok(()) => state.should_quit = true,
+                Err(e) => {
+                    tracing::error!("Failed to save before quitting: {e}");
+                    state.set_error(format!("Couldn't save: {e}"));
+                }
+            }
+        }
+        Some(ModalAction::Discard) => {
+            state.should_quit = true;
+        }
+        Some(ModalAction::Cancel) => {
+            state.mode = state.mode_before_quit;
+        }
+        _ => {}
+    }
+    Ok(())
+}
+
+/// Finish an open edit and write the list out.
+fn save_before_quit(state: &mut AppState) -> Result<()> {
+    match state.mode {
+        Mode::Edit => save_edit_buffer(state)?,
+        Mode::EditDescription => save_description(state),
+        _ => {}
+    }
+    state.mode = Mode::Navigate;
+    state.flush_save()
+}
+
 fn handle_rollover_mode(key: KeyEvent, state: &mut AppState) -> Result<()> {
-    match key.code {
-        KeyCode::Tab | KeyCode::Char(' ') => {
+    match state.keybindings.get_modal_action(Modal::Rollover, &key) {
+        Some(ModalAction::Toggle) => {
             // Toggle Don't ask again checkbox
             if let Some(ref mut pending) = state.pending_rollover {
                 pending.remember_choice = !pending.remember_choice;
             }
         }
-        KeyCode::Char('y') | KeyCode::Char('Y') | KeyCode::Enter => {
+        Some(ModalAction::Confirm) => {
             let remember = state
                 .pending_rollover
                 .as_ref()
@@ -965,8 +1390,14 @@ fn handle_rollover_mode(key: KeyEvent, state: &mut AppState) -> Result<()> {
 
             // Execute rollover
             if let Some(pending) = state.pending_rollover.take() {
+                state.flush_save()?;
+                let item_count = pending.items.len();
+                let from_date = state.todo_list.date;
                 let new_list = execute_rollover_for_project(&state.current_project.name, pending.source_date, pending.items)?;
                 state.todo_list = new_list;
+                state.resync()?;
+                state.fire_rollover_event(pending.source_date, item_count);
+                state.fire_day_change_event(from_date);
                 state.cursor_position = 0;
                 state.set_status_message("Rolled over incomplete items".to_string());
             }
@@ -976,7 +1407,7 @@ fn handle_rollover_mode(key: KeyEvent, state: &mut AppState) -> Result<()> {
                 persist_auto_rollover_pref(state, crate::config::AutoRolloverPref::AutoYes);
             }
         }
-        KeyCode::Char('n') | KeyCode::Char('N') | KeyCode::Char('l') | KeyCode::Char('L') | KeyCode::Esc => {
+        Some(ModalAction::Cancel) => {
             let remember = state
                 .pending_rollover
                 .as_ref()
@@ -1005,12 +1436,12 @@ fn persist_auto_rollover_pref(state: &mut AppState, pref: crate::config::AutoRol
             cfg.auto_rollover = pref;
             if let Err(e) = cfg.save() {
                 tracing::error!("Failed to save auto_rollover preference: {e}");
-                state.set_status_message("Couldn't save rollover preference".to_string());
+                state.set_error("Couldn't save rollover preference".to_string());
             }
         }
         Err(e) => {
             tracing::error!("Failed to load config for rollover save: {e}");
-            state.set_status_message("Couldn't save rollover preference".to_string());
+            state.set_error("Couldn't save rollover preference".to_string());
         }
     }
 }
@@ -1021,31 +1452,27 @@ fn handle_upgrade_prompt_mode(key: KeyEvent, state: &mut AppState) -> Result<()>
     match sub_state {
         Some(UpgradeSubState::Prompt) | None => {
             // Initial prompt: Y (download app), P (plugins), N (dismiss), S (skip)
-            match key.code {
-                KeyCode::Char('y') | KeyCode::Char('Y') => {
-                    // Only if there's an app update available
-                    if state.new_version_available.is_some() {
-                        // Check write permission before downloading
-                        if let Err(e) = check_write_permission() {
-                            state.upgrade_sub_state = Some(UpgradeSubState::Error {
-                                message: e.to_string(),
-                            });
-                            return Ok(());
-                        }
-                        state.start_download();
+            match state.keybindings.get_modal_action(Modal::Upgrade, &key) {
+                // Only if there's an app update available
+                Some(ModalAction::Confirm) if state.new_version_available.is_some() => {
+                    // Check write permission before downloading
+                    if let Err(e) = check_write_permission() {
+                        state.upgrade_sub_state = Some(UpgradeSubState::Error {
+                            message: e.to_string(),
+                        });
+                        return Ok(());
                     }
+                    state.start_download();
                 }
-                KeyCode::Char('p') | KeyCode::Char('P') => {
-                    // Enter plugin upgrade flow
-                    if !state.plugin_updates_available.is_empty() {
-                        state.enter_plugin_upgrades();
-                    }
+                // Enter plugin upgrade flow
+                Some(ModalAction::UpgradePlugins) if !state.plugin_updates_available.is_empty() => {
+                    state.enter_plugin_upgrades();
                 }
-                KeyCode::Char('n') | KeyCode::Char('N') | KeyCode::Esc => {
+                Some(ModalAction::Cancel) => {
                     // Dismiss for this session
                     state.dismiss_upgrade_session();
                 }
-                KeyCode::Char('s') | KeyCode::Char('S') => {
+                Some(ModalAction::Skip) => {
                     // Skip this version permanently (app only)
                     if let Some(version) = state.new_version_available.clone() {
                         state.skip_version_permanently(version)?;
@@ -1066,8 +1493,8 @@ fn handle_upgrade_prompt_mode(key: KeyEvent, state: &mut AppState) -> Result<()>
             // Otherwise ignore - download continues
         }
         Some(UpgradeSubState::Error { .. }) => {
-            match key.code {
-                KeyCode::Char('r') | KeyCode::Char('R') | KeyCode::Enter => {
+            match state.keybindings.get_modal_action(Modal::Upgrade, &key) {
+                Some(ModalAction::Retry | ModalAction::Confirm) => {
                     // Check write permission before retrying
                     if let Err(e) = check_write_permission() {
                         state.upgrade_sub_state = Some(UpgradeSubState::Error {
@@ -1078,7 +1505,7 @@ fn handle_upgrade_prompt_mode(key: KeyEvent, state: &mut AppState) -> Result<()>
                     // Retry download
                     state.start_download();
                 }
-                KeyCode::Esc | KeyCode::Char('n') | KeyCode::Char('N') => {
+                Some(ModalAction::Cancel) => {
                     // Dismiss error
                     state.upgrade_sub_state = None;
                     state.show_upgrade_prompt = false;
@@ -1088,8 +1515,8 @@ fn handle_upgrade_prompt_mode(key: KeyEvent, state: &mut AppState) -> Result<()>
             }
         }
         Some(UpgradeSubState::RestartPrompt { downloaded_path }) => {
-            match key.code {
-                KeyCode::Char('y') | KeyCode::Char('Y') | KeyCode::Enter => {
+            match state.keybindings.get_modal_action(Modal::Upgrade, &key) {
+                Some(ModalAction::Confirm) => {
                     // Check write permission first (should already be checked, but verify)
                     if let Err(e) = check_write_permission() {
                         state.upgrade_sub_state = Some(UpgradeSubState::Error {
@@ -1118,7 +1545,7 @@ fn handle_upgrade_prompt_mode(key: KeyEvent, state: &mut AppState) -> Result<()>
                         }
                     }
                 }
-                KeyCode::Char('n') | KeyCode::Char('N') | KeyCode::Esc => {
+                Some(ModalAction::Cancel) => {
                     // Clean up downloaded file
                     let _ = std::fs::remove_file(&downloaded_path);
                     state.upgrade_sub_state = None;
@@ -1142,42 +1569,38 @@ fn handle_plugin_upgrade_mode(
 ) -> Result<()> {
     match plugin_sub_state {
         PluginUpgradeSubState::PluginList { updates, selected_index } => {
-            match key.code {
-                KeyCode::Up | KeyCode::Char('k') => {
-                    if *selected_index > 0 {
-                        let new_index = selected_index - 1;
-                        state.upgrade_sub_state = Some(UpgradeSubState::PluginUpgrades(
-                            PluginUpgradeSubState::PluginList {
-                                updates: updates.clone(),
-                                selected_index: new_index,
-                            },
-                        ));
-                    }
-                }
-                KeyCode::Down | KeyCode::Char('j') => {
-                    if *selected_index < updates.len().saturating_sub(1) {
-                        let new_index = selected_index + 1;
-                        state.upgrade_sub_state = Some(UpgradeSubState::PluginUpgrades(
-                            PluginUpgradeSubState::PluginList {
-                                updates: updates.clone(),
-                                selected_index: new_index,
-                            },
-                        ));
-                    }
+            match state.keybindings.get_modal_action(Modal::Upgrade, &key) {
+                Some(ModalAction::Up) if *selected_index > 0 => {
+                    let new_index = selected_index - 1;
+                    state.upgrade_sub_state = Some(UpgradeSubState::PluginUpgrades(
+                        PluginUpgradeSubState::PluginList {
+                            updates: updates.clone(),
+                            selected_index: new_index,
+                        },
+                    ));
                 }
-                KeyCode::Enter => {
-                    // Start download for selected plugin
-                    if let Some(plugin) = updates.get(*selected_index) {
+                Some(ModalAction::Down) if *selected_index < updates.len().saturating_sub(1) => {
+                    let new_index = selected_index + 1;
+                    state.upgrade_sub_state = Some(UpgradeSubState::PluginUpgrades(
+                        PluginUpgradeSubState::PluginList {
+                            updates: updates.clone(),
+                            selected_index: new_index,
+                        },
+                    ));
+                }
+                Some(ModalAction::Confirm) => {
+                    // Start download for selected plugin
+                    if let Some(plugin) = updates.get(*selected_index) {
                         state.start_plugin_download(plugin);
                     }
                 }
-                KeyCode::Char('a') | KeyCode::Char('A') => {
+                Some(ModalAction::UpgradeAll) => {
                     // Update all plugins - start with first one
                     if let Some(plugin) = updates.first() {
                         state.start_plugin_download(plugin);
                     }
                 }
-                KeyCode::Esc => {
+                Some(ModalAction::Cancel) => {
                     // Go back to main upgrade prompt
                     state.exit_plugin_upgrades();
                 }
@@ -1202,32 +1625,28 @@ fn handle_plugin_upgrade_mode(
                 }
             }
         }
-        PluginUpgradeSubState::Complete { remaining_updates, .. } => {
-            match key.code {
-                KeyCode::Enter => {
+        PluginUpgradeSubState::Complete { .. } => {
+            match state.keybindings.get_modal_action(Modal::Upgrade, &key) {
+                Some(ModalAction::Confirm) => {
                     // Continue to next plugin or exit
                     state.continue_plugin_upgrades();
                 }
-                KeyCode::Esc => {
+                Some(ModalAction::Cancel) => {
                     // Exit plugin upgrade flow
-                    if remaining_updates.is_empty() {
-                        state.exit_plugin_upgrades();
-                    } else {
-                        state.exit_plugin_upgrades();
-                    }
+                    state.exit_plugin_upgrades();
                 }
                 _ => {}
             }
         }
         PluginUpgradeSubState::Error { plugin_name, remaining_updates, .. } => {
-            match key.code {
-                KeyCode::Char('r') | KeyCode::Char('R') => {
+            match state.keybindings.get_modal_action(Modal::Upgrade, &key) {
+                Some(ModalAction::Retry) => {
                     // Retry - find the plugin and start download again
                     if let Some(plugin) = state.plugin_updates_available.iter().find(|p| &p.plugin_name == plugin_name) {
                         state.start_plugin_download(&plugin.clone());
                     }
                 }
-                KeyCode::Enter => {
+                Some(ModalAction::Confirm) => {
                     // Continue to next plugin
                     if !remaining_updates.is_empty() {
                         state.continue_plugin_upgrades();
@@ -1235,7 +1654,7 @@ fn handle_plugin_upgrade_mode(
                         state.exit_plugin_upgrades();
                     }
                 }
-                KeyCode::Esc => {
+                Some(ModalAction::Cancel) => {
                     // Exit plugin upgrade flow
                     state.exit_plugin_upgrades();
                 }
@@ -1257,27 +1676,21 @@ fn handle_edit_mode(key: KeyEvent, state: &mut AppState) -> Result<()> {
                 save_edit_buffer(state)?;
                 new_item_at_same_level(state);
             }
-            Action::EditBackspace => {
-                if state.edit_cursor_pos > 0 {
-                    let prev_boundary =
-                        prev_char_boundary(&state.edit_buffer, state.edit_cursor_pos);
-                    state
-                        .edit_buffer
-                        .drain(prev_boundary..state.edit_cursor_pos);
-                    state.edit_cursor_pos = prev_boundary;
-                }
+            Action::EditBackspace if state.edit_cursor_pos > 0 => {
+                let prev_boundary =
+                    prev_char_boundary(&state.edit_buffer, state.edit_cursor_pos);
+                state
+                    .edit_buffer
+                    .drain(prev_boundary..state.edit_cursor_pos);
+                state.edit_cursor_pos = prev_boundary;
             }
-            Action::EditLeft => {
-                if state.edit_cursor_pos > 0 {
-                    state.edit_cursor_pos =
-                        prev_char_boundary(&state.edit_buffer, state.edit_cursor_pos);
-                }
+            Action::EditLeft if state.edit_cursor_pos > 0 => {
+                state.edit_cursor_pos =
+                    prev_char_boundary(&state.edit_buffer, state.edit_cursor_pos);
             }
-            Action::EditRight => {
-                if state.edit_cursor_pos < state.edit_buffer.len() {
-                    state.edit_cursor_pos =
-                        next_char_boundary(&state.edit_buffer, state.edit_cursor_pos);
-                }
+            Action::EditRight if state.edit_cursor_pos < state.edit_buffer.len() => {
+                state.edit_cursor_pos =
+                    next_char_boundary(&state.edit_buffer, state.edit_cursor_pos);
             }
             Action::EditWordLeft => {
                 state.edit_cursor_pos =
@@ -1368,29 +1781,80 @@ fn insert_item_above(state: &mut AppState) {
     state.sync_list_state_for_new_item();
 }
 
+/// Yank into the pending register, also copying the text to the clipboard.
+fn yank_to_register(state: &mut AppState) {
+    let Some((start, end)) = state.yank(state.pending_register) else {
+        return;
+    };
+    let items = &state.todo_list.items[start..end];
+    let base_indent = items.iter().map(|item| item.indent_level).min().unwrap_or(0);
+    let text = items
+        .iter()
+        .map(|item| format!("{}{}", "  ".repeat(item.indent_level - base_indent), item.content))
+        .collect::<Vec<_>>()
+        .join("\n");
+    // Truncate display text if too long
+    let display_text = if items.len() > 1 {
+        format!("{} items", items.len())
+    } else if text.len() > 40 {
+        format!("{}...", &text[..prev_char_boundary(&text, 38)])
+    } else {
+        text.clone()
+    };
+    match copy_to_clipboard(&text) {
+        Ok(CopyResult::SystemClipboard) => {
+            state.set_status_message(format!("Copied: {}", display_text));
+        }
+        Ok(CopyResult::InternalBuffer { file_path }) => {
+            // Headless fallback - saved to internal buffer and file
+            let msg = match file_path {
+                Some(path) => format!(
+                    "Copied to buffer (no clipboard): {} | Saved to {}",
+                    display_text,
+                    path.display()
+                ),
+                None => format!("Copied to buffer (no clipboard): {}", display_text),
+            };
+            state.set_status_message(msg);
+        }
+        Err(e) => {
+            state.set_error(format!("Copy failed: {}", e));
+        }
+    }
+}
+
 fn delete_current_item(state: &mut AppState) -> Result<()> {
     if state.todo_list.items.is_empty() {
         return Ok(());
     }
 
-    let date = state.todo_list.date;
     let (start, end) = state
         .todo_list
         .get_item_range(state.cursor_position)
         .unwrap_or((state.cursor_position, state.cursor_position + 1));
+    delete_range(state, start, end)
+}
 
-    // Fire OnDelete event BEFORE deletion (to capture item data)
-    if let Some(item) = state.todo_list.items.get(state.cursor_position) {
-        let ffi_item: totui_plugin_interface::FfiTodoItem = item.into();
-        let event = FfiEvent::OnDelete { todo: ffi_item };
-        state.fire_event(event);
-    }
-
+/// Delete rows `start..end`. Children go with their parent, so only the
+/// topmost items are logged and reported to plugins.
+fn delete_range(state: &mut AppState, start: usize, end: usize) -> Result<()> {
+    let date = state.todo_list.date;
     let ids: Vec<_> = state.todo_list.items[start..end]
         .iter()
         .map(|item| item.id)
         .collect();
 
+    for index in start..end {
+        let item = &state.todo_list.items[index];
+        if item.parent_id.is_some_and(|parent| ids.contains(&parent)) {
+            continue;
+        }
+        // Fire OnDelete event BEFORE deletion (to capture item data)
+        let ffi_item: totui_plugin_interface::FfiTodoItem = item.into();
+        state.record_activity(Activity::Deleted, index);
+        state.fire_event(FfiEvent::OnDelete { todo: ffi_item });
+    }
+
     soft_delete_todos_for_project(&ids, date, &state.current_project.name)?;
     state.todo_list.remove_item_range(start, end)?;
     state.clamp_cursor();
@@ -1413,6 +1877,19 @@ fn save_edit_buffer(state: &mut AppState) -> Result<()> {
 
     state.save_undo();
 
+    // Resolve inline `@due(...)`, `@est(...)` and `@waiting(...)` tags,
+    // e.g. "@due(next friday) @est(30m) @waiting(alice)"
+    let (content, mut due_date) = extract_due_tag(&state.edit_buffer);
+    let (content, mut estimate) = extract_estimate_tag(&content);
+    let (content, mut waiting_on) = parse_waiting(&content);
+    if content.trim().is_empty() {
+        due_date = None;
+        estimate = None;
+        waiting_on = None;
+    } else {
+        state.edit_buffer = content;
+    }
+
     // Track whether this is a new item or content edit
     let was_creating = state.is_creating_new_item;
     let mut new_item_index: Option<usize> = None;
@@ -1467,8 +1944,22 @@ fn save_edit_buffer(state: &mut AppState) -> Result<()> {
         new_item_index = Some(state.cursor_position);
     }
 
+    let idx = new_item_index.unwrap_or(state.cursor_position);
+    if let Some(item) = state.todo_list.items.get_mut(idx) {
+        if due_date.is_some() {
+            item.due_date = due_date;
+        }
+        if estimate.is_some() {
+            item.estimate = estimate;
+        }
+        if waiting_on.is_some() {
+            item.waiting_on = waiting_on;
+        }
+    }
+
     // Fire appropriate event based on whether this was a new item or edit
     if let Some(idx) = new_item_index {
+        state.record_activity(Activity::Added, idx);
         // New item created - fire OnAdd
         if let Some(item) = state.todo_list.items.get(idx) {
             let ffi_item: totui_plugin_interface::FfiTodoItem = item.into();
@@ -1537,7 +2028,6 @@ fn handle_plugin_mode(key: KeyEvent, state: &mut AppState) -> Result<()> {
             Ok(())
         }
         PluginSubState::Error { message } => handle_plugin_error(key, state, message),
-        PluginSubState::Preview { items } => handle_plugin_preview(key, state, items),
     }
 }
 
@@ -1615,7 +2105,6 @@ fn handle_plugins_modal(
             }
             Ok(())
         }
-        PluginsModalState::Preview { items } => handle_plugins_modal_preview(key, state, items),
         PluginsModalState::Error { message } => handle_plugins_modal_error(key, state, message),
     }
 }
@@ -1633,11 +2122,11 @@ fn handle_plugins_tabs(
     marketplace_error: Option<String>,
     marketplace_name: String,
 ) -> Result<()> {
-    match key.code {
-        KeyCode::Esc | KeyCode::Char('q') => {
+    match state.keybindings.get_modal_action(Modal::Plugins, &key) {
+        Some(ModalAction::Cancel) => {
             state.close_plugins_modal();
         }
-        KeyCode::Tab | KeyCode::BackTab => {
+        Some(ModalAction::NextTab) => {
             // Switch tabs
             active_tab = match active_tab {
                 PluginsTab::Installed => PluginsTab::Marketplace,
@@ -1672,7 +2161,7 @@ fn handle_plugins_tabs(
                 marketplace_name: marketplace_name.clone(),
             });
         }
-        KeyCode::Up | KeyCode::Char('k') => {
+        Some(ModalAction::Up) => {
             match active_tab {
                 PluginsTab::Installed => {
                     installed_index = installed_index.saturating_sub(1);
@@ -1691,10 +2180,12 @@ fn handle_plugins_tabs(
                 marketplace_name: marketplace_name.clone(),
             });
         }
-        KeyCode::Down | KeyCode::Char('j') => {
+        Some(ModalAction::Down) => {
             match active_tab {
                 PluginsTab::Installed => {
-                    let max = state.plugin_loader.loaded_plugins().count().saturating_sub(1);
+                    let max = installed_rows(&state.plugin_loader, &state.plugin_action_registry)
+                        .len()
+                        .saturating_sub(1);
                     if installed_index < max {
                         installed_index += 1;
                     }
@@ -1719,58 +2210,79 @@ fn handle_plugins_tabs(
                 marketplace_name: marketplace_name.clone(),
             });
         }
-        KeyCode::Enter => {
+        Some(ModalAction::Confirm) => {
             match active_tab {
                 PluginsTab::Installed => {
-                    // Get selected plugin from loader (sorted by name for stable ordering)
-                    let mut plugins: Vec<_> = state.plugin_loader.loaded_plugins().collect();
-                    plugins.sort_by(|a, b| a.name.cmp(&b.name));
-
-                    if let Some(plugin) = plugins.get(installed_index) {
-                        if !plugin.session_disabled {
-                            // Check if plugin has a Select field in its config schema
-                            let schema = plugin.plugin.config_schema();
-                            let first_select = schema
-                                .fields
-                                .iter()
-                                .find(|f| f.field_type == FfiConfigType::Select);
-
-                            if let Some(select_field) = first_select {
-                                // Show SelectInput modal with parsed options
-                                let options = parse_select_options(&select_field.options);
-                                state.plugins_modal_state = Some(PluginsModalState::SelectInput {
-                                    plugin_name: plugin.name.clone(),
-                                    field_name: select_field.name.to_string(),
-                                    options,
-                                    selected_index: 0,
+                    let rows = installed_rows(&state.plugin_loader, &state.plugin_action_registry);
+                    let selected = rows.get(installed_index).copied();
+
+                    match selected {
+                        Some(InstalledRow::Action(action)) => {
+                            let disabled = state
+                                .plugin_loader
+                                .loaded_plugins()
+                                .any(|p| p.name == action.plugin_name && p.session_disabled);
+                            if disabled {
+                                state.plugins_modal_state = Some(PluginsModalState::Error {
+                                    message: format!(
+                                        "Plugin '{}' is disabled for this session",
+                                        action.plugin_name
+                                    ),
                                 });
                             } else {
-                                // No Select field, use regular text Input
-                                state.plugins_modal_state = Some(PluginsModalState::Input {
-                                    plugin_name: plugin.name.clone(),
-                                    input_buffer: String::new(),
-                                    cursor_pos: 0,
+                                let action = action.clone();
+                                state.close_plugins_modal();
+                                execute_plugin_action(action, state)?;
+                                state.schedule_save();
+                            }
+                        }
+                        Some(InstalledRow::Plugin(plugin)) => {
+                            if !plugin.session_disabled {
+                                // Check if plugin has a Select field in its config schema
+                                let schema = plugin.plugin.config_schema();
+                                let first_select = schema
+                                    .fields
+                                    .iter()
+                                    .find(|f| f.field_type == FfiConfigType::Select);
+
+                                if let Some(select_field) = first_select {
+                                    // Show SelectInput modal with parsed options
+                                    let options = parse_select_options(&select_field.options);
+                                    state.plugins_modal_state = Some(PluginsModalState::SelectInput {
+                                        plugin_name: plugin.name.clone(),
+                                        field_name: select_field.name.to_string(),
+                                        options,
+                                        selected_index: 0,
+                                    });
+                                } else {
+                                    // No Select field, use regular text Input
+                                    state.plugins_modal_state = Some(PluginsModalState::Input {
+                                        plugin_name: plugin.name.clone(),
+                                        input_buffer: String::new(),
+                                        cursor_pos: 0,
+                                    });
+                                }
+                            } else {
+                                state.plugins_modal_state = Some(PluginsModalState::Error {
+                                    message: format!(
+                                        "Plugin '{}' is disabled for this session",
+                                        plugin.name
+                                    ),
                                 });
                             }
-                        } else {
-                            state.plugins_modal_state = Some(PluginsModalState::Error {
-                                message: format!(
-                                    "Plugin '{}' is disabled for this session",
-                                    plugin.name
-                                ),
+                        }
+                        None => {
+                            // No plugins available
+                            state.plugins_modal_state = Some(PluginsModalState::Tabs {
+                                active_tab,
+                                installed_index,
+                                marketplace_index,
+                                marketplace_plugins,
+                                marketplace_loading,
+                                marketplace_error,
+                                marketplace_name: marketplace_name.clone(),
                             });
                         }
-                    } else {
-                        // No plugins available
-                        state.plugins_modal_state = Some(PluginsModalState::Tabs {
-                            active_tab,
-                            installed_index,
-                            marketplace_index,
-                            marketplace_plugins,
-                            marketplace_loading,
-                            marketplace_error,
-                            marketplace_name: marketplace_name.clone(),
-                        });
                     }
                 }
                 PluginsTab::Marketplace => {
@@ -2053,8 +2565,8 @@ fn handle_plugins_modal_details(
         .map(|c| c.marketplaces.default)
         .unwrap_or_else(|_| DEFAULT_MARKETPLACE.to_string());
 
-    match key.code {
-        KeyCode::Esc | KeyCode::Char('q') | KeyCode::Backspace => {
+    match state.keybindings.get_modal_action(Modal::Plugins, &key) {
+        Some(ModalAction::Cancel) => {
             // Go back to Marketplace tab
             state.plugins_modal_state = Some(PluginsModalState::Tabs {
                 active_tab: PluginsTab::Marketplace,
@@ -2066,7 +2578,7 @@ fn handle_plugins_modal_details(
                 marketplace_name,
             });
         }
-        KeyCode::Char('i') | KeyCode::Enter => {
+        Some(ModalAction::Confirm) => {
             // Install the plugin
             let plugin_name = plugin.name.clone();
             let plugin_version = plugin.version.clone();
@@ -2144,40 +2656,10 @@ fn handle_plugins_modal_details(
     Ok(())
 }
 
-/// Handle preview in plugins modal
-fn handle_plugins_modal_preview(
-    key: KeyEvent,
-    state: &mut AppState,
-    items: Vec<crate::todo::TodoItem>,
-) -> Result<()> {
-    match key.code {
-        KeyCode::Char('y') | KeyCode::Char('Y') | KeyCode::Enter => {
-            let count = items.len();
-            state.save_undo();
-            for item in items {
-                state.todo_list.items.push(item);
-            }
-            state.unsaved_changes = true;
-            save_todo_list_for_project(&state.todo_list, &state.current_project.name)?;
-            state.unsaved_changes = false;
-            state.last_save_time = Some(std::time::Instant::now());
-            state.set_status_message(format!("Added {} item(s) from plugin", count));
-            state.close_plugins_modal();
-        }
-        KeyCode::Char('n') | KeyCode::Char('N') | KeyCode::Esc => {
-            state.close_plugins_modal();
-        }
-        _ => {
-            state.plugins_modal_state = Some(PluginsModalState::Preview { items });
-        }
-    }
-    Ok(())
-}
-
 /// Handle error in plugins modal
 fn handle_plugins_modal_error(key: KeyEvent, state: &mut AppState, message: String) -> Result<()> {
-    match key.code {
-        KeyCode::Esc | KeyCode::Enter | KeyCode::Char('q') => {
+    match state.keybindings.get_modal_action(Modal::Plugins, &key) {
+        Some(ModalAction::Cancel | ModalAction::Confirm) => {
             state.close_plugins_modal();
         }
         _ => {
@@ -2193,18 +2675,18 @@ fn handle_plugin_selecting(
     plugins: Vec<crate::plugin::GeneratorInfo>,
     mut selected_index: usize,
 ) -> Result<()> {
-    match key.code {
-        KeyCode::Esc | KeyCode::Char('q') => {
+    match state.keybindings.get_modal_action(Modal::Plugins, &key) {
+        Some(ModalAction::Cancel) => {
             state.close_plugin_menu();
         }
-        KeyCode::Up | KeyCode::Char('k') => {
+        Some(ModalAction::Up) => {
             selected_index = selected_index.saturating_sub(1);
             state.plugin_state = Some(PluginSubState::Selecting {
                 plugins,
                 selected_index,
             });
         }
-        KeyCode::Down | KeyCode::Char('j') => {
+        Some(ModalAction::Down) => {
             if selected_index < plugins.len().saturating_sub(1) {
                 selected_index += 1;
             }
@@ -2213,7 +2695,7 @@ fn handle_plugin_selecting(
                 selected_index,
             });
         }
-        KeyCode::Enter => {
+        Some(ModalAction::Confirm) => {
             if let Some(plugin) = plugins.get(selected_index) {
                 if plugin.available {
                     state.plugin_state = Some(PluginSubState::InputPrompt {
@@ -2321,8 +2803,8 @@ fn handle_plugin_input(
 }
 
 fn handle_plugin_error(key: KeyEvent, state: &mut AppState, message: String) -> Result<()> {
-    match key.code {
-        KeyCode::Esc | KeyCode::Enter | KeyCode::Char('q') => {
+    match state.keybindings.get_modal_action(Modal::Plugins, &key) {
+        Some(ModalAction::Cancel | ModalAction::Confirm) => {
             state.close_plugin_menu();
         }
         _ => {
@@ -2332,35 +2814,6 @@ fn handle_plugin_error(key: KeyEvent, state: &mut AppState, message: String) ->
     Ok(())
 }
 
-fn handle_plugin_preview(
-    key: KeyEvent,
-    state: &mut AppState,
-    items: Vec<crate::todo::TodoItem>,
-) -> Result<()> {
-    match key.code {
-        KeyCode::Char('y') | KeyCode::Char('Y') | KeyCode::Enter => {
-            let count = items.len();
-            state.save_undo();
-            for item in items {
-                state.todo_list.items.push(item);
-            }
-            state.unsaved_changes = true;
-            save_todo_list_for_project(&state.todo_list, &state.current_project.name)?;
-            state.unsaved_changes = false;
-            state.last_save_time = Some(std::time::Instant::now());
-            state.set_status_message(format!("Added {count} item(s) from plugin"));
-            state.close_plugin_menu();
-        }
-        KeyCode::Char('n') | KeyCode::Char('N') | KeyCode::Esc => {
-            state.close_plugin_menu();
-        }
-        _ => {
-            state.plugin_state = Some(PluginSubState::Preview { items });
-        }
-    }
-    Ok(())
-}
-
 fn handle_project_select_mode(key: KeyEvent, state: &mut AppState) -> Result<()> {
     let project_state = match state.project_state.take() {
         Some(ps) => ps,
@@ -2374,7 +2827,8 @@ fn handle_project_select_mode(key: KeyEvent, state: &mut AppState) -> Result<()>
         ProjectSubState::Selecting {
             projects,
             selected_index,
-        } => handle_project_selecting(key, state, projects, selected_index),
+            query,
+        } => handle_project_selecting(key, state, projects, selected_index, query),
         ProjectSubState::CreateInput {
             input_buffer,
             cursor_pos,
@@ -2395,32 +2849,69 @@ fn handle_project_selecting(
     state: &mut AppState,
     projects: Vec<Project>,
     mut selected_index: usize,
+    mut query: String,
 ) -> Result<()> {
+    // Plain typing filters the list; management keys take a modifier
+    let typing = !key.modifiers.intersects(KeyModifiers::CONTROL | KeyModifiers::ALT);
     match key.code {
-        KeyCode::Esc | KeyCode::Char('q') => {
+        KeyCode::Char(c) if typing => {
+            query.push(c);
+            state.project_state = Some(ProjectSubState::Selecting {
+                projects,
+                selected_index: 0,
+                query,
+            });
+            return Ok(());
+        }
+        KeyCode::Backspace if !query.is_empty() => {
+            query.pop();
+            state.project_state = Some(ProjectSubState::Selecting {
+                projects,
+                selected_index: 0,
+                query,
+            });
+            return Ok(());
+        }
+        _ => {}
+    }
+
+    let matches = matching_projects(&projects, &query);
+    let match_count = matches.len();
+    let selected = matches.get(selected_index).map(|project| (*project).clone());
+
+    match state.keybindings.get_modal_action(Modal::ProjectSelect, &key) {
+        // Esc clears the filter before it closes the switcher
+        Some(ModalAction::Cancel) if !query.is_empty() => {
+            state.project_state = Some(ProjectSubState::Selecting {
+                projects,
+                selected_index: 0,
+                query: String::new(),
+            });
+        }
+        Some(ModalAction::Cancel) => {
             state.close_project_modal();
         }
-        KeyCode::Up | KeyCode::Char('k') => {
+        Some(ModalAction::Up) => {
             selected_index = selected_index.saturating_sub(1);
             state.project_state = Some(ProjectSubState::Selecting {
                 projects,
                 selected_index,
+                query,
             });
         }
-        KeyCode::Down | KeyCode::Char('j') => {
-            if selected_index < projects.len().saturating_sub(1) {
+        Some(ModalAction::Down) => {
+            if selected_index < match_count.saturating_sub(1) {
                 selected_index += 1;
             }
             state.project_state = Some(ProjectSubState::Selecting {
                 projects,
                 selected_index,
+                query,
             });
         }
-        KeyCode::Enter => {
-            if let Some(project) = projects.get(selected_index) {
+        Some(ModalAction::Confirm) => match selected {
+            Some(project) => {
                 if project.name != state.current_project.name {
-                    let project = project.clone();
-
                     // Save last_used_project to config
                     if let Ok(mut config) = Config::load() {
                         config.last_used_project = Some(project.name.clone());
@@ -2432,51 +2923,78 @@ fn handle_project_selecting(
                 }
                 state.close_project_modal();
             }
-        }
-        KeyCode::Char('n') => {
+            None => {
+                state.project_state = Some(ProjectSubState::Selecting {
+                    projects,
+                    selected_index,
+                    query,
+                });
+            }
+        },
+        Some(ModalAction::New) => {
             // Start creating a new project
             state.project_state = Some(ProjectSubState::CreateInput {
                 input_buffer: String::new(),
                 cursor_pos: 0,
             });
         }
-        KeyCode::Char('r') => {
+        Some(ModalAction::Rename) => {
             // Start renaming the selected project
-            if let Some(project) = projects.get(selected_index) {
-                if project.name == DEFAULT_PROJECT_NAME {
-                    state.set_status_message("Cannot rename the default project".to_string());
+            match selected {
+                Some(project) if project.name == DEFAULT_PROJECT_NAME => {
+                    state.set_warning("Cannot rename the default project".to_string());
                     state.project_state = Some(ProjectSubState::Selecting {
                         projects,
                         selected_index,
+                        query,
                     });
-                } else {
+                }
+                Some(project) => {
                     state.project_state = Some(ProjectSubState::RenameInput {
-                        project_name: project.name.clone(),
                         input_buffer: project.name.clone(),
                         cursor_pos: project.name.len(),
+                        project_name: project.name,
+                    });
+                }
+                None => {
+                    state.project_state = Some(ProjectSubState::Selecting {
+                        projects,
+                        selected_index,
+                        query,
                     });
                 }
             }
         }
-        KeyCode::Char('d') => {
+        Some(ModalAction::Delete) => {
             // Start deleting the selected project
-            if let Some(project) = projects.get(selected_index) {
-                if project.name == DEFAULT_PROJECT_NAME {
-                    state.set_status_message("Cannot delete the default project".to_string());
+            match selected {
+                Some(project) if project.name == DEFAULT_PROJECT_NAME => {
+                    state.set_warning("Cannot delete the default project".to_string());
                     state.project_state = Some(ProjectSubState::Selecting {
                         projects,
                         selected_index,
+                        query,
                     });
-                } else if project.name == state.current_project.name {
+                }
+                Some(project) if project.name == state.current_project.name => {
                     state
-                        .set_status_message("Cannot delete the currently active project".to_string());
+                        .set_warning("Cannot delete the currently active project".to_string());
                     state.project_state = Some(ProjectSubState::Selecting {
                         projects,
                         selected_index,
+                        query,
                     });
-                } else {
+                }
+                Some(project) => {
                     state.project_state = Some(ProjectSubState::ConfirmDelete {
-                        project_name: project.name.clone(),
+                        project_name: project.name,
+                    });
+                }
+                None => {
+                    state.project_state = Some(ProjectSubState::Selecting {
+                        projects,
+                        selected_index,
+                        query,
                     });
                 }
             }
@@ -2485,6 +3003,7 @@ fn handle_project_selecting(
             state.project_state = Some(ProjectSubState::Selecting {
                 projects,
                 selected_index,
+                query,
             });
         }
     }
@@ -2510,9 +3029,6 @@ fn handle_project_create_input(
             match registry.create(&name) {
                 Ok(project) => {
                     let project = project.clone();
-                    // Create the project directory
-                    let dailies_dir = get_dailies_dir_for_project(&project.name)?;
-                    fs::create_dir_all(&dailies_dir)?;
 
                     state.set_status_message(format!("Created project '{}'", project.name));
 
@@ -2525,7 +3041,7 @@ fn handle_project_create_input(
                     state.close_project_modal();
                 }
                 Err(e) => {
-                    state.set_status_message(format!("Error: {}", e));
+                    state.set_error(format!("Error: {}", e));
                     state.open_project_modal();
                 }
             }
@@ -2610,13 +3126,6 @@ fn handle_project_rename_input(
             let mut registry = ProjectRegistry::load()?;
             match registry.rename(&project_name, &new_name) {
                 Ok(()) => {
-                    // Rename the project directory
-                    let old_dir = get_project_dir(&project_name)?;
-                    let new_dir = get_project_dir(&new_name)?;
-                    if old_dir.exists() {
-                        fs::rename(&old_dir, &new_dir)?;
-                    }
-
                     state.set_status_message(format!(
                         "Renamed '{}' to '{}'",
                         project_name, new_name
@@ -2624,7 +3133,7 @@ fn handle_project_rename_input(
                     state.open_project_modal();
                 }
                 Err(e) => {
-                    state.set_status_message(format!("Error: {}", e));
+                    state.set_error(format!("Error: {}", e));
                     state.open_project_modal();
                 }
             }
@@ -2696,30 +3205,24 @@ fn handle_project_confirm_delete(
     state: &mut AppState,
     project_name: String,
 ) -> Result<()> {
-    match key.code {
-        KeyCode::Char('y') | KeyCode::Char('Y') => {
+    match state.keybindings.get_modal_action(Modal::ConfirmDelete, &key) {
+        Some(ModalAction::Confirm) => {
             // Delete the project
             let mut registry = ProjectRegistry::load()?;
             match registry.delete(&project_name) {
                 Ok(()) => {
-                    // Delete the project directory
-                    let project_dir = get_project_dir(&project_name)?;
-                    if project_dir.exists() {
-                        fs::remove_dir_all(&project_dir)?;
-                    }
-
                     // TODO: Also delete todos from database for this project
 
                     state.set_status_message(format!("Deleted project '{}'", project_name));
                     state.open_project_modal();
                 }
                 Err(e) => {
-                    state.set_status_message(format!("Error: {}", e));
+                    state.set_error(format!("Error: {}", e));
                     state.open_project_modal();
                 }
             }
         }
-        KeyCode::Char('n') | KeyCode::Char('N') | KeyCode::Esc => {
+        Some(ModalAction::Cancel) => {
             // Cancel - go back to project list
             state.open_project_modal();
         }
@@ -2744,20 +3247,22 @@ fn handle_move_to_project_mode(key: KeyEvent, state: &mut AppState) -> Result<()
             projects,
             mut selected_index,
             item_index,
+            item_end,
         } => {
-            match key.code {
-                KeyCode::Esc | KeyCode::Char('q') => {
+            match state.keybindings.get_modal_action(Modal::ProjectSelect, &key) {
+                Some(ModalAction::Cancel) => {
                     state.close_move_to_project_modal();
                 }
-                KeyCode::Up | KeyCode::Char('k') => {
+                Some(ModalAction::Up) => {
                     selected_index = selected_index.saturating_sub(1);
                     state.move_to_project_state = Some(MoveToProjectSubState::Selecting {
                         projects,
                         selected_index,
                         item_index,
+                        item_end,
                     });
                 }
-                KeyCode::Down | KeyCode::Char('j') => {
+                Some(ModalAction::Down) => {
                     if selected_index < projects.len().saturating_sub(1) {
                         selected_index += 1;
                     }
@@ -2765,9 +3270,10 @@ fn handle_move_to_project_mode(key: KeyEvent, state: &mut AppState) -> Result<()
                         projects,
                         selected_index,
                         item_index,
+                        item_end,
                     });
                 }
-                KeyCode::Enter => {
+                Some(ModalAction::Confirm) => {
                     if let Some(dest_project) = projects.get(selected_index) {
                         let dest_project = dest_project.clone();
                         // Re-set state temporarily so execute_move_to_project can read item_index
@@ -2775,6 +3281,7 @@ fn handle_move_to_project_mode(key: KeyEvent, state: &mut AppState) -> Result<()
                             projects: projects.clone(),
                             selected_index,
                             item_index,
+                            item_end,
                         });
 
                         match state.execute_move_to_project(&dest_project) {
@@ -2785,12 +3292,12 @@ fn handle_move_to_project_mode(key: KeyEvent, state: &mut AppState) -> Result<()
                                     dest_project.name
                                 ));
                                 // Save source list
-                                save_todo_list_for_project(&state.todo_list, &state.current_project.name)?;
+                                state.save_list()?;
                                 state.unsaved_changes = false;
                                 state.last_save_time = Some(std::time::Instant::now());
                             }
                             Err(e) => {
-                                state.set_status_message(format!("Move failed: {}", e));
+                                state.set_error(format!("Move failed: {}", e));
                             }
                         }
                         state.close_move_to_project_modal();
@@ -2801,6 +3308,7 @@ fn handle_move_to_project_mode(key: KeyEvent, state: &mut AppState) -> Result<()
                         projects,
                         selected_index,
                         item_index,
+                        item_end,
                     });
                 }
             }
@@ -2809,24 +3317,28 @@ fn handle_move_to_project_mode(key: KeyEvent, state: &mut AppState) -> Result<()
     Ok(())
 }
 
+/// Write the description editor's text to the selected item.
+fn save_description(state: &mut AppState) {
+    state.save_undo();
+    let joined = state.desc_buffer.join("\n");
+    let description = if joined.trim().is_empty() {
+        None
+    } else {
+        Some(joined)
+    };
+    if let Some(item) = state.selected_item_mut() {
+        item.description = description;
+        item.modified_at = chrono::Utc::now();
+        item.collapsed = false;
+    }
+    state.unsaved_changes = true;
+}
+
 fn handle_edit_description_mode(key: KeyEvent, state: &mut AppState) -> Result<()> {
     match key.code {
         KeyCode::Esc => {
-            // Save description
-            state.save_undo();
-            let joined = state.desc_buffer.join("\n");
-            let description = if joined.trim().is_empty() {
-                None
-            } else {
-                Some(joined)
-            };
-            if let Some(item) = state.selected_item_mut() {
-                item.description = description;
-                item.modified_at = chrono::Utc::now();
-                item.collapsed = false;
-            }
-            state.unsaved_changes = true;
-            save_todo_list_for_project(&state.todo_list, &state.current_project.name)?;
+            save_description(state);
+            state.save_list()?;
             state.unsaved_changes = false;
             state.last_save_time = Some(std::time::Instant::now());
             state.mode = Mode::Navigate;
@@ -2880,13 +3392,11 @@ fn handle_edit_description_mode(key: KeyEvent, state: &mut AppState) -> Result<(
                 state.desc_buffer[state.desc_cursor_row].push_str(&next_line);
             }
         }
-        KeyCode::Left => {
-            if state.desc_cursor_col > 0 {
-                state.desc_cursor_col = prev_char_boundary(
-                    &state.desc_buffer[state.desc_cursor_row],
-                    state.desc_cursor_col,
-                );
-            }
+        KeyCode::Left if state.desc_cursor_col > 0 => {
+            state.desc_cursor_col = prev_char_boundary(
+                &state.desc_buffer[state.desc_cursor_row],
+                state.desc_cursor_col,
+            );
         }
         KeyCode::Right => {
             let line_len = state.desc_buffer[state.desc_cursor_row].len();
@@ -2897,32 +3407,28 @@ fn handle_edit_description_mode(key: KeyEvent, state: &mut AppState) -> Result<(
                 );
             }
         }
-        KeyCode::Up => {
-            if state.desc_cursor_row > 0 {
-                state.desc_cursor_row -= 1;
-                let line_len = state.desc_buffer[state.desc_cursor_row].len();
-                state.desc_cursor_col = state.desc_cursor_col.min(line_len);
-                // Snap to valid char boundary
-                while state.desc_cursor_col > 0
-                    && !state.desc_buffer[state.desc_cursor_row]
-                        .is_char_boundary(state.desc_cursor_col)
-                {
-                    state.desc_cursor_col -= 1;
-                }
+        KeyCode::Up if state.desc_cursor_row > 0 => {
+            state.desc_cursor_row -= 1;
+            let line_len = state.desc_buffer[state.desc_cursor_row].len();
+            state.desc_cursor_col = state.desc_cursor_col.min(line_len);
+            // Snap to valid char boundary
+            while state.desc_cursor_col > 0
+                && !state.desc_buffer[state.desc_cursor_row]
+                    .is_char_boundary(state.desc_cursor_col)
+            {
+                state.desc_cursor_col -= 1;
             }
         }
-        KeyCode::Down => {
-            if state.desc_cursor_row + 1 < state.desc_buffer.len() {
-                state.desc_cursor_row += 1;
-                let line_len = state.desc_buffer[state.desc_cursor_row].len();
-                state.desc_cursor_col = state.desc_cursor_col.min(line_len);
-                // Snap to valid char boundary
-                while state.desc_cursor_col > 0
-                    && !state.desc_buffer[state.desc_cursor_row]
-                        .is_char_boundary(state.desc_cursor_col)
-                {
-                    state.desc_cursor_col -= 1;
-                }
+        KeyCode::Down if state.desc_cursor_row + 1 < state.desc_buffer.len() => {
+            state.desc_cursor_row += 1;
+            let line_len = state.desc_buffer[state.desc_cursor_row].len();
+            state.desc_cursor_col = state.desc_cursor_col.min(line_len);
+            // Snap to valid char boundary
+            while state.desc_cursor_col > 0
+                && !state.desc_buffer[state.desc_cursor_row]
+                    .is_char_boundary(state.desc_cursor_col)
+            {
+                state.desc_cursor_col -= 1;
             }
         }
         KeyCode::Home => {
@@ -2941,42 +3447,986 @@ fn handle_edit_description_mode(key: KeyEvent, state: &mut AppState) -> Result<(
     Ok(())
 }
 
-/// Execute a plugin action triggered by keybinding.
-///
-/// This function:
-/// 1. Shows a status message while running
-/// 2. Finds the loaded plugin
-/// 3. Calls execute_with_host with the action name as input
-/// 4. Processes returned commands
-/// 5. Shows completion message or error popup
-fn execute_plugin_action(action: PluginAction, state: &mut AppState) -> Result<()> {
-    // Show status message while running
-    state.set_status_message(format!("Running {}...", action.action_name));
-
-    // Find the loaded plugin
-    let loaded_plugin = state
-        .plugin_loader
-        .loaded_plugins()
-        .find(|p| p.name == action.plugin_name);
+fn handle_edit_estimate_mode(key: KeyEvent, state: &mut AppState) -> Result<()> {
+    match key.code {
+        KeyCode::Esc => {
+            state.edit_buffer.clear();
+            state.edit_cursor_pos = 0;
+            state.mode = Mode::Navigate;
+        }
+        KeyCode::Enter => {
+            let input = state.edit_buffer.trim();
+            let estimate = if input.is_empty() {
+                None
+            } else {
+                match input.parse::<Estimate>() {
+                    Ok(estimate) => Some(estimate),
+                    Err(e) => {
+                        state.set_warning(e);
+                        return Ok(());
+                    }
+                }
+            };
 
-    let loaded_plugin = match loaded_plugin {
-        Some(p) => p,
-        None => {
-            state.pending_plugin_errors.push(PluginLoadError {
-                plugin_name: action.plugin_name.clone(),
-                error_kind: PluginErrorKind::Other("Plugin not loaded".to_string()),
-                message: format!("Plugin '{}' is not loaded", action.plugin_name),
+            state.save_undo();
+            if let Some(item) = state.selected_item_mut() {
+                item.estimate = estimate;
+                item.modified_at = chrono::Utc::now();
+            }
+            state.set_status_message(match estimate {
+                Some(e) => format!("Estimate: {e}"),
+                None => "Estimate cleared".to_string(),
             });
-            state.show_plugin_error_popup = true;
-            return Ok(());
+            state.unsaved_changes = true;
+            state.save_list()?;
+            state.unsaved_changes = false;
+            state.last_save_time = Some(std::time::Instant::now());
+            state.edit_buffer.clear();
+            state.edit_cursor_pos = 0;
+            state.mode = Mode::Navigate;
         }
-    };
-
-    // Build enabled projects set (for now, just current project)
-    let mut enabled_projects = HashSet::new();
-    enabled_projects.insert(state.current_project.name.clone());
-
-    // Create HostApi implementation
+        KeyCode::Backspace if state.edit_cursor_pos > 0 => {
+            let prev = prev_char_boundary(&state.edit_buffer, state.edit_cursor_pos);
+            state.edit_buffer.drain(prev..state.edit_cursor_pos);
+            state.edit_cursor_pos = prev;
+        }
+        KeyCode::Left if state.edit_cursor_pos > 0 => {
+            state.edit_cursor_pos = prev_char_boundary(&state.edit_buffer, state.edit_cursor_pos);
+        }
+        KeyCode::Right if state.edit_cursor_pos < state.edit_buffer.len() => {
+            state.edit_cursor_pos = next_char_boundary(&state.edit_buffer, state.edit_cursor_pos);
+        }
+        KeyCode::Home => {
+            state.edit_cursor_pos = 0;
+        }
+        KeyCode::End => {
+            state.edit_cursor_pos = state.edit_buffer.len();
+        }
+        KeyCode::Char(c) => {
+            state.edit_buffer.insert(state.edit_cursor_pos, c);
+            state.edit_cursor_pos += c.len_utf8();
+        }
+        _ => {}
+    }
+    Ok(())
+}
+
+fn handle_edit_link_mode(key: KeyEvent, state: &mut AppState) -> Result<()> {
+    match key.code {
+        KeyCode::Esc => {
+            state.edit_buffer.clear();
+            state.edit_cursor_pos = 0;
+            state.mode = Mode::Navigate;
+        }
+        KeyCode::Enter => {
+            let input = state.edit_buffer.clone();
+            if let Err(e) = normalize_link(&input) {
+                state.set_warning(e);
+                return Ok(());
+            }
+
+            state.save_undo();
+            let mut result = Ok(None);
+            if let Some(item) = state.selected_item_mut() {
+                result = item.set_link(&input).map(|()| item.link.clone());
+            }
+            let link = match result {
+                Ok(link) => link,
+                Err(e) => {
+                    state.set_error(e);
+                    return Ok(());
+                }
+            };
+            state.set_status_message(match link {
+                Some(link) => format!("Link: {link}"),
+                None => "Link cleared".to_string(),
+            });
+            state.unsaved_changes = true;
+            state.save_list()?;
+            state.unsaved_changes = false;
+            state.last_save_time = Some(std::time::Instant::now());
+            state.edit_buffer.clear();
+            state.edit_cursor_pos = 0;
+            state.mode = Mode::Navigate;
+        }
+        KeyCode::Backspace if state.edit_cursor_pos > 0 => {
+            let prev = prev_char_boundary(&state.edit_buffer, state.edit_cursor_pos);
+            state.edit_buffer.drain(prev..state.edit_cursor_pos);
+            state.edit_cursor_pos = prev;
+        }
+        KeyCode::Left if state.edit_cursor_pos > 0 => {
+            state.edit_cursor_pos = prev_char_boundary(&state.edit_buffer, state.edit_cursor_pos);
+        }
+        KeyCode::Right if state.edit_cursor_pos < state.edit_buffer.len() => {
+            state.edit_cursor_pos = next_char_boundary(&state.edit_buffer, state.edit_cursor_pos);
+        }
+        KeyCode::Home => {
+            state.edit_cursor_pos = 0;
+        }
+        KeyCode::End => {
+            state.edit_cursor_pos = state.edit_buffer.len();
+        }
+        KeyCode::Char(c) => {
+            state.edit_buffer.insert(state.edit_cursor_pos, c);
+            state.edit_cursor_pos += c.len_utf8();
+        }
+        _ => {}
+    }
+    Ok(())
+}
+
+/// The path typed or dropped into the attach prompt. Terminals paste dropped
+/// files quoted, with spaces escaped, or as `file://` URLs.
+fn attachment_path(input: &str) -> Option<PathBuf> {
+    let input = input.trim();
+    let path = if let Some(quoted) = ['\'', '"']
+        .iter()
+        .find_map(|quote| input.strip_prefix(*quote)?.strip_suffix(*quote))
+    {
+        quoted.to_string()
+    } else {
+        input.strip_prefix("file://").unwrap_or(input).replace("\\ ", " ")
+    };
+    if path.is_empty() {
+        return None;
+    }
+    expand_home(Path::new(&path)).ok()
+}
+
+fn open_attachment(n: usize, state: &mut AppState) {
+    let Some(id) = state.selected_item().map(|item| item.id) else {
+        return;
+    };
+    let attachments = match list_attachments(&id) {
+        Ok(attachments) => attachments,
+        Err(e) => {
+            state.set_error(format!("Failed to load attachments: {e:#}"));
+            return;
+        }
+    };
+    let Some(attachment) = attachments.get(n.saturating_sub(1)) else {
+        state.set_warning(match attachments.len() {
+            0 => "No attachments on this item (ga to attach a file)".to_string(),
+            count => format!("This item has {count} attachment(s)"),
+        });
+        return;
+    };
+    match attachment.openable_path().and_then(|path| Ok(open::that(&path)?)) {
+        Ok(()) if attachments.len() > 1 => state.set_status_message(format!(
+            "Opened {} ({n} of {}; {{n}}go opens another)",
+            attachment.name,
+            attachments.len()
+        )),
+        Ok(()) => state.set_status_message(format!("Opened {}", attachment.name)),
+        Err(e) => state.set_error(format!("Failed to open {}: {e:#}", attachment.name)),
+    }
+}
+
+fn handle_attach_file_mode(key: KeyEvent, state: &mut AppState) -> Result<()> {
+    match key.code {
+        KeyCode::Esc => {
+            state.edit_buffer.clear();
+            state.edit_cursor_pos = 0;
+            state.mode = Mode::Navigate;
+        }
+        KeyCode::Enter => {
+            let Some(path) = attachment_path(&state.edit_buffer) else {
+                state.set_warning("Type the path of a file to attach".to_string());
+                return Ok(());
+            };
+            let Some(id) = state.selected_item().map(|item| item.id) else {
+                state.mode = Mode::Navigate;
+                return Ok(());
+            };
+            if !path.is_file() {
+                state.set_warning(format!("Not a file: {}", path.display()));
+                return Ok(());
+            }
+            match attach(&id, &path) {
+                Ok(attachment) => {
+                    state.set_status_message(format!(
+                        "Attached {} ({})",
+                        attachment.name,
+                        format_size(attachment.size)
+                    ));
+                    state.detail_attachments = None;
+                }
+                Err(e) => {
+                    state.set_error(format!("Failed to attach {}: {e:#}", path.display()));
+                    return Ok(());
+                }
+            }
+            state.edit_buffer.clear();
+            state.edit_cursor_pos = 0;
+            state.mode = Mode::Navigate;
+        }
+        KeyCode::Backspace if state.edit_cursor_pos > 0 => {
+            let prev = prev_char_boundary(&state.edit_buffer, state.edit_cursor_pos);
+            state.edit_buffer.drain(prev..state.edit_cursor_pos);
+            state.edit_cursor_pos = prev;
+        }
+        KeyCode::Left if state.edit_cursor_pos > 0 => {
+            state.edit_cursor_pos = prev_char_boundary(&state.edit_buffer, state.edit_cursor_pos);
+        }
+        KeyCode::Right if state.edit_cursor_pos < state.edit_buffer.len() => {
+            state.edit_cursor_pos = next_char_boundary(&state.edit_buffer, state.edit_cursor_pos);
+        }
+        KeyCode::Home => {
+            state.edit_cursor_pos = 0;
+        }
+        KeyCode::End => {
+            state.edit_cursor_pos = state.edit_buffer.len();
+        }
+        KeyCode::Char(c) => {
+            state.edit_buffer.insert(state.edit_cursor_pos, c);
+            state.edit_cursor_pos += c.len_utf8();
+        }
+        _ => {}
+    }
+    Ok(())
+}
+
+fn handle_week_view_mode(key: KeyEvent, state: &mut AppState) -> Result<()> {
+    match key.code {
+        KeyCode::Char('h') | KeyCode::Left | KeyCode::Char('k') | KeyCode::Up => {
+            state.week_view_move(-1);
+        }
+        KeyCode::Char('l') | KeyCode::Right | KeyCode::Char('j') | KeyCode::Down => {
+            state.week_view_move(1);
+        }
+        KeyCode::Char('H') | KeyCode::Char('<') => state.week_view_move(-7),
+        KeyCode::Char('L') | KeyCode::Char('>') => state.week_view_move(7),
+        KeyCode::Char('T') => state.open_week_view_on(state.today),
+        KeyCode::Enter => state.open_week_view_day()?,
+        KeyCode::Esc | KeyCode::Char('q') | KeyCode::Char('w') => state.close_week_view(),
+        _ => {}
+    }
+    Ok(())
+}
+
+fn handle_calendar_mode(key: KeyEvent, state: &mut AppState) -> Result<()> {
+    match key.code {
+        KeyCode::Char('h') | KeyCode::Left => state.calendar_move_days(-1),
+        KeyCode::Char('l') | KeyCode::Right => state.calendar_move_days(1),
+        KeyCode::Char('k') | KeyCode::Up => state.calendar_move_days(-7),
+        KeyCode::Char('j') | KeyCode::Down => state.calendar_move_days(7),
+        KeyCode::Char('[') | KeyCode::Char('H') | KeyCode::PageUp => state.calendar_move_months(-1),
+        KeyCode::Char(']') | KeyCode::Char('L') | KeyCode::PageDown => state.calendar_move_months(1),
+        KeyCode::Char('T') => state.calendar_select(state.today),
+        KeyCode::Enter => state.open_calendar_day()?,
+        KeyCode::Esc | KeyCode::Char('q') | KeyCode::Char('C') => state.close_calendar(),
+        _ => {}
+    }
+    Ok(())
+}
+
+fn handle_undo_history_mode(key: KeyEvent, state: &mut AppState) -> Result<()> {
+    match key.code {
+        KeyCode::Char('j') | KeyCode::Down => state.undo_history_move(1),
+        KeyCode::Char('k') | KeyCode::Up => state.undo_history_move(-1),
+        KeyCode::Char('g') | KeyCode::Home => state.undo_history_selected = 0,
+        KeyCode::Char('G') | KeyCode::End => state.undo_history_move(isize::MAX),
+        KeyCode::Enter => {
+            state.mode = Mode::Navigate;
+            let steps = state.undo_history_selected + 1;
+            if state.undo_steps(steps) {
+                state.save_list()?;
+                state.last_save_time = Some(std::time::Instant::now());
+                let noun = if steps == 1 { "change" } else { "changes" };
+                state.set_status_message(format!("Undid {steps} {noun}"));
+            }
+        }
+        KeyCode::Esc | KeyCode::Char('q') => state.mode = Mode::Navigate,
+        _ => {}
+    }
+    Ok(())
+}
+
+fn handle_search_results_mode(key: KeyEvent, state: &mut AppState) -> Result<()> {
+    match key.code {
+        KeyCode::Char('j') | KeyCode::Down => state.search_results_move(1),
+        KeyCode::Char('k') | KeyCode::Up => state.search_results_move(-1),
+        KeyCode::Char('g') | KeyCode::Home => state.search_results_selected = 0,
+        KeyCode::Char('G') | KeyCode::End => state.search_results_move(isize::MAX),
+        KeyCode::Enter => state.open_search_result()?,
+        KeyCode::Esc | KeyCode::Char('q') => state.mode = Mode::Navigate,
+        _ => {}
+    }
+    Ok(())
+}
+
+fn handle_trash_mode(key: KeyEvent, state: &mut AppState) -> Result<()> {
+    match key.code {
+        KeyCode::Char('j') | KeyCode::Down => state.trash_move(1),
+        KeyCode::Char('k') | KeyCode::Up => state.trash_move(-1),
+        KeyCode::Char('g') | KeyCode::Home => state.trash_selected = 0,
+        KeyCode::Char('G') | KeyCode::End => state.trash_move(isize::MAX),
+        KeyCode::Char('r') | KeyCode::Enter => state.restore_from_trash()?,
+        KeyCode::Char('d') | KeyCode::Delete => state.purge_from_trash()?,
+        KeyCode::Esc | KeyCode::Char('q') => state.mode = Mode::Navigate,
+        _ => {}
+    }
+    Ok(())
+}
+
+fn handle_file_conflict_mode(key: KeyEvent, state: &mut AppState) -> Result<()> {
+    match key.code {
+        KeyCode::Char('f') => state.resolve_file_conflict(DailyFileConflict::keep_file)?,
+        KeyCode::Char('d') => state.resolve_file_conflict(DailyFileConflict::keep_database)?,
+        KeyCode::Char('m') | KeyCode::Enter => state.resolve_file_conflict(DailyFileConflict::merge)?,
+        KeyCode::Esc | KeyCode::Char('q') => state.dismiss_file_conflict(),
+        _ => {}
+    }
+    Ok(())
+}
+
+fn handle_stale_save_mode(key: KeyEvent, state: &mut AppState) -> Result<()> {
+    match key.code {
+        KeyCode::Char('m') | KeyCode::Enter => state.resolve_stale_save(StaleSave::merge)?,
+        KeyCode::Char('k') => state.resolve_stale_save(StaleSave::keep_mine)?,
+        KeyCode::Char('t') => state.resolve_stale_save(StaleSave::take_theirs)?,
+        _ => {}
+    }
+    Ok(())
+}
+
+fn handle_theme_picker_mode(key: KeyEvent, state: &mut AppState) {
+    match key.code {
+        KeyCode::Char('j') | KeyCode::Down => state.theme_picker_move(1),
+        KeyCode::Char('k') | KeyCode::Up => state.theme_picker_move(-1),
+        KeyCode::Enter => state.confirm_theme_picker(),
+        KeyCode::Esc | KeyCode::Char('q') => state.cancel_theme_picker(),
+        _ => {}
+    }
+}
+
+fn handle_command_mode(key: KeyEvent, state: &mut AppState) -> Result<()> {
+    let ctrl = key.modifiers.contains(KeyModifiers::CONTROL);
+    match key.code {
+        KeyCode::Esc => state.close_command(),
+        // Backspace on an empty prompt leaves it, like vim
+        KeyCode::Backspace if state.edit_buffer.is_empty() => state.close_command(),
+        KeyCode::Enter => {
+            let mut input = state.edit_buffer.trim().to_string();
+            // An unfinished command runs the highlighted completion instead
+            if command::parse(&input).is_err()
+                && let Some(completion) = state.command_completions().get(state.command_selected)
+                && !completion.takes_argument()
+            {
+                input = completion.text.clone();
+            }
+            state.close_command();
+            run_command(&input, state)?;
+        }
+        KeyCode::Tab => {
+            if let Some(completion) = state.command_completions().get(state.command_selected) {
+                let mut text = completion.text.clone();
+                if completion.takes_argument() {
+                    text.push(' ');
+                }
+                state.edit_cursor_pos = text.len();
+                state.edit_buffer = text;
+                state.command_selected = 0;
+            }
+        }
+        code if code == KeyCode::Down || (ctrl && code == KeyCode::Char('n')) => {
+            let count = state.command_completions().len().min(command::MAX_COMPLETIONS);
+            if state.command_selected + 1 < count {
+                state.command_selected += 1;
+            }
+        }
+        code if code == KeyCode::Up || (ctrl && code == KeyCode::Char('p')) => {
+            state.command_selected = state.command_selected.saturating_sub(1);
+        }
+        KeyCode::Backspace if state.edit_cursor_pos > 0 => {
+            let prev = prev_char_boundary(&state.edit_buffer, state.edit_cursor_pos);
+            state.edit_buffer.drain(prev..state.edit_cursor_pos);
+            state.edit_cursor_pos = prev;
+            state.command_selected = 0;
+        }
+        KeyCode::Left => {
+            state.edit_cursor_pos = prev_char_boundary(&state.edit_buffer, state.edit_cursor_pos);
+        }
+        KeyCode::Right => {
+            state.edit_cursor_pos = next_char_boundary(&state.edit_buffer, state.edit_cursor_pos);
+        }
+        KeyCode::Char(c) if !ctrl => {
+            state.edit_buffer.insert(state.edit_cursor_pos, c);
+            state.edit_cursor_pos += c.len_utf8();
+            state.command_selected = 0;
+        }
+        _ => {}
+    }
+    Ok(())
+}
+
+/// Run a `:` command typed in navigate mode.
+fn run_command(input: &str, state: &mut AppState) -> Result<()> {
+    if input.is_empty() {
+        return Ok(());
+    }
+    let command = match command::parse(input) {
+        Ok(command) => command,
+        Err(message) => {
+            state.set_warning(message);
+            return Ok(());
+        }
+    };
+
+    match command {
+        Command::Action(action) => execute_navigate_action(action, state)?,
+        Command::Timeline => state.open_timeline(),
+        Command::Sort(SortKey::Priority) => state.sort_by_priority(),
+        Command::Sort(SortKey::Due) => state.sort_by_due(),
+        Command::Goto(date) => match parse_date_relative_to(&date, state.today) {
+            Ok(date) => state.navigate_to_date(date)?,
+            Err(e) => state.set_warning(e.to_string()),
+        },
+        Command::MoveToProject(name) => move_selected_to_project(&name, state)?,
+        Command::PluginRun { plugin, rest } => run_plugin_command(&plugin, &rest, state)?,
+    }
+    Ok(())
+}
+
+/// `:move-to-project <name>`: the move-to-project modal without the picker.
+fn move_selected_to_project(name: &str, state: &mut AppState) -> Result<()> {
+    if state.is_readonly() || state.selected_item().is_none() {
+        return Ok(());
+    }
+    if name == state.current_project.name {
+        state.set_status_message(format!("Already in '{name}'"));
+        return Ok(());
+    }
+    let registry = ProjectRegistry::load().unwrap_or_default();
+    let Some(dest_project) = registry.get_by_name(name).cloned() else {
+        state.set_warning(format!("No project named '{name}'"));
+        return Ok(());
+    };
+
+    state.move_to_project_state = Some(MoveToProjectSubState::Selecting {
+        projects: vec![dest_project.clone()],
+        selected_index: 0,
+        item_index: state.cursor_position,
+        item_end: state.todo_list.get_item_range(state.cursor_position)?.1,
+    });
+    match state.execute_move_to_project(&dest_project) {
+        Ok(count) => {
+            state.set_status_message(format!("Moved {} item(s) to '{}'", count, dest_project.name));
+            state.save_list()?;
+            state.unsaved_changes = false;
+            state.last_save_time = Some(std::time::Instant::now());
+        }
+        Err(e) => state.set_error(format!("Move failed: {}", e)),
+    }
+    state.close_move_to_project_modal();
+    Ok(())
+}
+
+/// `:plugin run <plugin> [action|input]`: runs a registered plugin action by
+/// name, otherwise calls the plugin's generator with the rest as input, or
+/// opens its input prompt when there is none.
+fn run_plugin_command(plugin_name: &str, rest: &str, state: &mut AppState) -> Result<()> {
+    let namespace = format!("plugin:{plugin_name}:{rest}");
+    if let Some(action) = state.plugin_action_registry.lookup_by_namespace(&namespace) {
+        return execute_plugin_action(action.clone(), state);
+    }
+
+    let Some(plugin) = state
+        .plugin_loader
+        .loaded_plugins()
+        .find(|p| p.name == plugin_name)
+    else {
+        state.set_warning(format!("Plugin '{plugin_name}' is not loaded"));
+        return Ok(());
+    };
+    if plugin.session_disabled {
+        state.set_warning(format!("Plugin '{plugin_name}' is disabled for this session"));
+        return Ok(());
+    }
+
+    state.mode = Mode::Plugin;
+    if rest.is_empty() {
+        state.plugins_modal_state = Some(PluginsModalState::Input {
+            plugin_name: plugin_name.to_string(),
+            input_buffer: String::new(),
+            cursor_pos: 0,
+        });
+        return Ok(());
+    }
+
+    state.plugins_modal_state = Some(PluginsModalState::Executing {
+        plugin_name: plugin_name.to_string(),
+    });
+    match state.plugin_loader.spawn_generate(plugin_name, rest) {
+        Ok(rx) => {
+            state.plugin_result_rx = Some(rx);
+            state.plugin_result_source = Some(PluginResultSource::PluginsModal);
+        }
+        Err(e) => {
+            state.plugins_modal_state = Some(PluginsModalState::Error { message: e.message });
+        }
+    }
+    Ok(())
+}
+
+fn handle_filter_mode(key: KeyEvent, state: &mut AppState) -> Result<()> {
+    if state.filter_tag_input {
+        match key.code {
+            KeyCode::Esc => {
+                state.filter_tag_input = false;
+                state.edit_buffer.clear();
+                state.edit_cursor_pos = 0;
+            }
+            KeyCode::Enter => {
+                let Some(filter) = Filter::tag(&state.edit_buffer) else {
+                    state.set_warning("Enter a single #tag".to_string());
+                    return Ok(());
+                };
+                state.filter_tag_input = false;
+                state.edit_buffer.clear();
+                state.edit_cursor_pos = 0;
+                state.toggle_filter(filter);
+            }
+            KeyCode::Backspace if state.edit_cursor_pos > 0 => {
+                let prev = prev_char_boundary(&state.edit_buffer, state.edit_cursor_pos);
+                state.edit_buffer.drain(prev..state.edit_cursor_pos);
+                state.edit_cursor_pos = prev;
+            }
+            KeyCode::Char(c) => {
+                state.edit_buffer.insert(state.edit_cursor_pos, c);
+                state.edit_cursor_pos += c.len_utf8();
+            }
+            _ => {}
+        }
+        return Ok(());
+    }
+
+    match key.code {
+        KeyCode::Esc | KeyCode::Enter | KeyCode::Char('f') | KeyCode::Char('q') => {
+            state.mode = Mode::Navigate;
+        }
+        KeyCode::Char('c') => state.toggle_filter(Filter::HideCompleted),
+        KeyCode::Char('w') => state.toggle_waiting_filter(),
+        KeyCode::Char('#') => {
+            state.filter_tag_input = true;
+            state.edit_buffer.clear();
+            state.edit_cursor_pos = 0;
+        }
+        KeyCode::Char('r') | KeyCode::Backspace => {
+            state.clear_filters();
+        }
+        KeyCode::Char(c @ '0'..='9') => {
+            let level = c as u8 - b'0';
+            if level < state.theme.priority_levels()
+                && let Some(priority) = Priority::new(level)
+            {
+                state.toggle_filter(Filter::Priority(priority));
+            }
+        }
+        KeyCode::Char(c) => {
+            if let Some(todo_state) = TodoState::from_char(c).filter(|s| *s != TodoState::Empty) {
+                state.toggle_filter(Filter::State(todo_state));
+            } else if c == 'o' {
+                state.toggle_filter(Filter::State(TodoState::Empty));
+            }
+        }
+        _ => {}
+    }
+    Ok(())
+}
+
+fn handle_plan_review_mode(key: KeyEvent, state: &mut AppState) -> Result<()> {
+    let Some(review) = state.plan_review.as_mut() else {
+        state.mode = Mode::Navigate;
+        return Ok(());
+    };
+
+    if review.editing {
+        match key.code {
+            KeyCode::Esc => {
+                review.editing = false;
+                state.edit_buffer.clear();
+                state.edit_cursor_pos = 0;
+            }
+            KeyCode::Enter => {
+                review.set_selected_content(&state.edit_buffer);
+                review.editing = false;
+                state.edit_buffer.clear();
+                state.edit_cursor_pos = 0;
+            }
+            KeyCode::Backspace if state.edit_cursor_pos > 0 => {
+                let prev = prev_char_boundary(&state.edit_buffer, state.edit_cursor_pos);
+                state.edit_buffer.drain(prev..state.edit_cursor_pos);
+                state.edit_cursor_pos = prev;
+            }
+            KeyCode::Left if state.edit_cursor_pos > 0 => {
+                state.edit_cursor_pos = prev_char_boundary(&state.edit_buffer, state.edit_cursor_pos);
+            }
+            KeyCode::Right if state.edit_cursor_pos < state.edit_buffer.len() => {
+                state.edit_cursor_pos = next_char_boundary(&state.edit_buffer, state.edit_cursor_pos);
+            }
+            KeyCode::Home => {
+                state.edit_cursor_pos = 0;
+            }
+            KeyCode::End => {
+                state.edit_cursor_pos = state.edit_buffer.len();
+            }
+            KeyCode::Char(c) => {
+                state.edit_buffer.insert(state.edit_cursor_pos, c);
+                state.edit_cursor_pos += c.len_utf8();
+            }
+            _ => {}
+        }
+        return Ok(());
+    }
+
+    match key.code {
+        KeyCode::Char('j') | KeyCode::Down => review.move_down(),
+        KeyCode::Char('k') | KeyCode::Up => review.move_up(),
+        KeyCode::Char(' ') | KeyCode::Char('x') => review.toggle_selected(),
+        KeyCode::Char('a') => review.set_all(false),
+        KeyCode::Char('r') => review.set_all(true),
+        KeyCode::Tab | KeyCode::Char('>') => review.indent_selected(),
+        KeyCode::BackTab | KeyCode::Char('<') => review.outdent_selected(),
+        KeyCode::Char('e') => {
+            if let Some(content) = review.selected_content() {
+                state.edit_buffer = content.to_string();
+                state.edit_cursor_pos = state.edit_buffer.len();
+                review.editing = true;
+            }
+        }
+        KeyCode::Char('t') => state.toggle_plan_review_target(),
+        KeyCode::Char('y') | KeyCode::Char('Y') | KeyCode::Enter => {
+            let added = state.apply_plan_review();
+            if added > 0 {
+                state.save_list()?;
+                state.unsaved_changes = false;
+                state.last_save_time = Some(std::time::Instant::now());
+            }
+        }
+        KeyCode::Esc | KeyCode::Char('q') => {
+            state.cancel_plan_review();
+            state.set_status_message("Plan discarded".to_string());
+        }
+        _ => {}
+    }
+    Ok(())
+}
+
+/// Handle text pasted into the terminal (bracketed paste).
+///
+/// A multi-line outline pasted in navigate mode opens the plan review; in
+/// text inputs the paste is inserted as a single line. Inputs with their own
+/// buffers get it typed in, with line breaks kept in the description.
+pub fn handle_paste_event(text: String, state: &mut AppState) -> Result<()> {
+    let editing_plan = state.plan_review.as_ref().is_some_and(|review| review.editing);
+    match state.mode {
+        Mode::Navigate if !state.show_help => {
+            state.open_pasted_plan(&text);
+        }
+        Mode::PlanReview if !editing_plan => {}
+        Mode::Edit
+        | Mode::EditEstimate
+        | Mode::EditLink
+        | Mode::AttachFile
+        | Mode::MoveToDate
+        | Mode::EditDue
+        | Mode::Search
+        | Mode::Command
+        | Mode::PlanReview => {
+            let line = text.split_whitespace().collect::<Vec<_>>().join(" ");
+            state.edit_buffer.insert_str(state.edit_cursor_pos, &line);
+            state.edit_cursor_pos += line.len();
+            if state.mode == Mode::Search {
+                state.update_incremental_search();
+            }
+        }
+        _ if takes_typing(state) => type_pasted(&text, state)?,
+        _ => {}
+    }
+    Ok(())
+}
+
+/// Whether the current mode is reading typed text into a buffer of its own.
+fn takes_typing(state: &AppState) -> bool {
+    match state.mode {
+        Mode::EditDescription => true,
+        Mode::Filter => state.filter_tag_input,
+        Mode::ProjectSelect => matches!(
+            state.project_state,
+            Some(
+                ProjectSubState::Selecting { .. }
+                    | ProjectSubState::CreateInput { .. }
+                    | ProjectSubState::RenameInput { .. }
+            )
+        ),
+        Mode::Plugin => {
+            matches!(state.plugins_modal_state, Some(PluginsModalState::Input { .. }))
+                || matches!(state.plugin_state, Some(PluginSubState::InputPrompt { .. }))
+        }
+        _ => false,
+    }
+}
+
+/// Type `text` into the current input key by key. Line breaks become new
+/// lines in the description and spaces everywhere else, so a paste never
+/// submits a prompt.
+fn type_pasted(text: &str, state: &mut AppState) -> Result<()> {
+    let text = if state.mode == Mode::EditDescription {
+        text.replace("\r\n", "\n").replace('\r', "\n")
+    } else {
+        text.split_whitespace().collect::<Vec<_>>().join(" ")
+    };
+    for c in text.chars() {
+        let code = match c {
+            '\n' => KeyCode::Enter,
+            c => KeyCode::Char(c),
+        };
+        handle_key_event(KeyEvent::new(code, KeyModifiers::NONE), state)?;
+    }
+    Ok(())
+}
+
+fn handle_move_to_date_mode(key: KeyEvent, state: &mut AppState) -> Result<()> {
+    let today = chrono::Local::now().date_naive();
+    match key.code {
+        KeyCode::Esc => {
+            state.edit_buffer.clear();
+            state.edit_cursor_pos = 0;
+            state.mode = Mode::Navigate;
+        }
+        KeyCode::Enter => {
+            let date = match parse_date_relative_to(&state.edit_buffer, today) {
+                Ok(date) => date,
+                Err(e) => {
+                    state.set_error(e.to_string());
+                    return Ok(());
+                }
+            };
+
+            match state.move_current_item_to_date(date) {
+                Ok(count) => {
+                    state.set_status_message(format!(
+                        "Moved {} item(s) to {}",
+                        count,
+                        date.format("%a %Y-%m-%d")
+                    ));
+                    state.save_list()?;
+                    state.unsaved_changes = false;
+                    state.last_save_time = Some(std::time::Instant::now());
+                }
+                Err(e) => {
+                    state.set_error(format!("Move failed: {}", e));
+                }
+            }
+            state.edit_buffer.clear();
+            state.edit_cursor_pos = 0;
+            state.mode = Mode::Navigate;
+        }
+        // Step the date a day at a time, starting from the list's own day
+        KeyCode::Up | KeyCode::Down => {
+            let current = parse_date_relative_to(&state.edit_buffer, today)
+                .unwrap_or(state.todo_list.date);
+            let step = if key.code == KeyCode::Up { 1 } else { -1 };
+            state.edit_buffer = (current + chrono::Duration::days(step))
+                .format("%Y-%m-%d")
+                .to_string();
+            state.edit_cursor_pos = state.edit_buffer.len();
+        }
+        KeyCode::Backspace if state.edit_cursor_pos > 0 => {
+            let prev = prev_char_boundary(&state.edit_buffer, state.edit_cursor_pos);
+            state.edit_buffer.drain(prev..state.edit_cursor_pos);
+            state.edit_cursor_pos = prev;
+        }
+        KeyCode::Left if state.edit_cursor_pos > 0 => {
+            state.edit_cursor_pos = prev_char_boundary(&state.edit_buffer, state.edit_cursor_pos);
+        }
+        KeyCode::Right if state.edit_cursor_pos < state.edit_buffer.len() => {
+            state.edit_cursor_pos = next_char_boundary(&state.edit_buffer, state.edit_cursor_pos);
+        }
+        KeyCode::Home => {
+            state.edit_cursor_pos = 0;
+        }
+        KeyCode::End => {
+            state.edit_cursor_pos = state.edit_buffer.len();
+        }
+        KeyCode::Char(c) => {
+            state.edit_buffer.insert(state.edit_cursor_pos, c);
+            state.edit_cursor_pos += c.len_utf8();
+        }
+        _ => {}
+    }
+    Ok(())
+}
+
+/// Due date prompt for one item or a visual selection. An empty input clears
+/// the due date.
+fn handle_edit_due_mode(key: KeyEvent, state: &mut AppState) -> Result<()> {
+    let today = chrono::Local::now().date_naive();
+    match key.code {
+        KeyCode::Esc => {
+            state.edit_buffer.clear();
+            state.edit_cursor_pos = 0;
+            state.due_targets.clear();
+            state.mode = Mode::Navigate;
+        }
+        KeyCode::Enter => {
+            let due = if state.edit_buffer.trim().is_empty() {
+                None
+            } else {
+                match parse_date_relative_to(&state.edit_buffer, today) {
+                    Ok(date) => Some(date),
+                    Err(e) => {
+                        state.set_error(e.to_string());
+                        return Ok(());
+                    }
+                }
+            };
+
+            let count = state.apply_due_date(due);
+            let message = match due {
+                Some(date) => format!("Due {} for {} item(s)", date.format("%a %Y-%m-%d"), count),
+                None => format!("Cleared due date on {} item(s)", count),
+            };
+            state.set_status_message(message);
+            state.save_list()?;
+            state.unsaved_changes = false;
+            state.last_save_time = Some(std::time::Instant::now());
+            state.edit_buffer.clear();
+            state.edit_cursor_pos = 0;
+            state.mode = Mode::Navigate;
+        }
+        KeyCode::Up | KeyCode::Down | KeyCode::PageUp | KeyCode::PageDown => {
+            let current = parse_date_relative_to(&state.edit_buffer, today).unwrap_or(today);
+            let step = match key.code {
+                KeyCode::Up => 1,
+                KeyCode::Down => -1,
+                KeyCode::PageUp => 7,
+                _ => -7,
+            };
+            state.edit_buffer = (current + chrono::Duration::days(step))
+                .format("%Y-%m-%d")
+                .to_string();
+            state.edit_cursor_pos = state.edit_buffer.len();
+        }
+        // Empty input clears the due date on Enter
+        KeyCode::Char('x') if key.modifiers.contains(KeyModifiers::CONTROL) => {
+            state.edit_buffer.clear();
+            state.edit_cursor_pos = 0;
+        }
+        KeyCode::Backspace if state.edit_cursor_pos > 0 => {
+            let prev = prev_char_boundary(&state.edit_buffer, state.edit_cursor_pos);
+            state.edit_buffer.drain(prev..state.edit_cursor_pos);
+            state.edit_cursor_pos = prev;
+        }
+        KeyCode::Left if state.edit_cursor_pos > 0 => {
+            state.edit_cursor_pos = prev_char_boundary(&state.edit_buffer, state.edit_cursor_pos);
+        }
+        KeyCode::Right if state.edit_cursor_pos < state.edit_buffer.len() => {
+            state.edit_cursor_pos = next_char_boundary(&state.edit_buffer, state.edit_cursor_pos);
+        }
+        KeyCode::Home => {
+            state.edit_cursor_pos = 0;
+        }
+        KeyCode::End => {
+            state.edit_cursor_pos = state.edit_buffer.len();
+        }
+        KeyCode::Char(c) => {
+            state.edit_buffer.insert(state.edit_cursor_pos, c);
+            state.edit_cursor_pos += c.len_utf8();
+        }
+        _ => {}
+    }
+    Ok(())
+}
+
+fn handle_search_mode(key: KeyEvent, state: &mut AppState) -> Result<()> {
+    match key.code {
+        KeyCode::Esc => {
+            state.cancel_search();
+            return Ok(());
+        }
+        KeyCode::Enter => {
+            state.confirm_search();
+            return Ok(());
+        }
+        KeyCode::Tab => {
+            state.search_all_dates();
+            return Ok(());
+        }
+        // Backspace on an empty prompt leaves search, like vim
+        KeyCode::Backspace if state.edit_buffer.is_empty() => {
+            state.cancel_search();
+            return Ok(());
+        }
+        KeyCode::Backspace if state.edit_cursor_pos > 0 => {
+            let prev = prev_char_boundary(&state.edit_buffer, state.edit_cursor_pos);
+            state.edit_buffer.drain(prev..state.edit_cursor_pos);
+            state.edit_cursor_pos = prev;
+        }
+        KeyCode::Left if state.edit_cursor_pos > 0 => {
+            state.edit_cursor_pos = prev_char_boundary(&state.edit_buffer, state.edit_cursor_pos);
+            return Ok(());
+        }
+        KeyCode::Right if state.edit_cursor_pos < state.edit_buffer.len() => {
+            state.edit_cursor_pos = next_char_boundary(&state.edit_buffer, state.edit_cursor_pos);
+            return Ok(());
+        }
+        KeyCode::Home => {
+            state.edit_cursor_pos = 0;
+            return Ok(());
+        }
+        KeyCode::End => {
+            state.edit_cursor_pos = state.edit_buffer.len();
+            return Ok(());
+        }
+        KeyCode::Char(c) if !key.modifiers.contains(KeyModifiers::CONTROL) => {
+            state.edit_buffer.insert(state.edit_cursor_pos, c);
+            state.edit_cursor_pos += c.len_utf8();
+        }
+        _ => return Ok(()),
+    }
+
+    state.update_incremental_search();
+    Ok(())
+}
+
+/// Execute a plugin action triggered by keybinding.
+///
+/// This function:
+/// 1. Shows a status message while running
+/// 2. Finds the loaded plugin
+/// 3. Calls execute_with_host with the action name as input
+/// 4. Processes returned commands
+/// 5. Shows completion message or error popup
+fn execute_plugin_action(action: PluginAction, state: &mut AppState) -> Result<()> {
+    // Show status message while running
+    state.set_status_message(format!("Running {}...", action.action_name));
+
+    // Find the loaded plugin
+    let loaded_plugin = state
+        .plugin_loader
+        .loaded_plugins()
+        .find(|p| p.name == action.plugin_name);
+
+    let loaded_plugin = match loaded_plugin {
+        Some(p) => p,
+        None => {
+            state.pending_plugin_errors.push(PluginLoadError {
+                plugin_name: action.plugin_name.clone(),
+                error_kind: PluginErrorKind::Other("Plugin not loaded".to_string()),
+                message: format!("Plugin '{}' is not loaded", action.plugin_name),
+            });
+            state.show_plugin_error_popup = true;
+            return Ok(());
+        }
+    };
+
+    // Build enabled projects set (for now, just current project)
+    let mut enabled_projects = HashSet::new();
+    enabled_projects.insert(state.current_project.name.clone());
+
+    // Create HostApi implementation
     let host_api = PluginHostApiImpl::new(
         &state.todo_list,
         &state.current_project,
@@ -2998,15 +4448,23 @@ fn execute_plugin_action(action: PluginAction, state: &mut AppState) -> Result<(
     match result.into_result() {
         Ok(commands) => {
             if !commands.is_empty() {
+                // Saved apart from the user's changes, so the history credits the plugin
+                state.flush_save()?;
                 state.save_undo();
                 let mut executor = CommandExecutor::new(action.plugin_name.clone());
                 let commands_vec: Vec<_> = commands.into_iter().collect();
                 if let Err(e) = executor.execute_batch(commands_vec, &mut state.todo_list) {
-                    state.set_status_message(format!("Error: {}", e));
-                } else {
+                    state.set_error(format!("Error: {}", e));
+                } else if let Err(e) = with_plugin_origin(&action.plugin_name, || {
+                    state.save_list()
+                }) {
                     state.unsaved_changes = true;
+                    state.set_error(format!("Couldn't save: {e}"));
+                } else {
                     state.set_status_message(format!("{} complete", action.action_name));
                 }
+                // Commands may have changed the metadata the detail pane shows
+                state.detail_metadata = None;
             } else {
                 state.set_status_message(format!("{} complete", action.action_name));
             }
@@ -3093,3 +4551,474 @@ mod rollover_tests {
         assert_eq!(state.auto_rollover_pref, before);
     }
 }
+
+#[cfg(test)]
+mod count_tests {
+    use super::*;
+    use crate::keybindings::KeybindingCache;
+    use crate::plugin::{PluginActionRegistry, PluginLoader};
+    use crate::todo::{TodoItem, TodoList};
+    use crate::ui::theme::Theme;
+    use chrono::Local;
+
+    fn make_state(count: usize) -> AppState {
+        let todo_list = TodoList {
+            date: Local::now().date_naive(),
+            items: (0..count).map(|i| TodoItem::new(format!("Item {i}"), 0)).collect(),
+            file_path: std::path::PathBuf::from("/tmp/test.md"),
+        };
+        AppState::new(
+            todo_list,
+            Theme::default(),
+            KeybindingCache::default(),
+            1000,
+            None,
+            None,
+            Project::default_project(),
+            PluginLoader::new(),
+            vec![],
+            PluginActionRegistry::new(),
+            crate::config::AutoRolloverPref::Ask,
+        )
+    }
+
+    fn key(c: char) -> KeyEvent {
+        KeyEvent::new(KeyCode::Char(c), KeyModifiers::NONE)
+    }
+
+    #[test]
+    fn count_prefix_repeats_motion() {
+        let mut state = make_state(20);
+        handle_navigate_mode(key('1'), &mut state).unwrap();
+        handle_navigate_mode(key('2'), &mut state).unwrap();
+        assert_eq!(state.pending_count, Some(12));
+        handle_navigate_mode(key('j'), &mut state).unwrap();
+        assert_eq!(state.cursor_position, 12);
+        assert_eq!(state.pending_count, None);
+
+        // A leading 0 is not a count
+        handle_navigate_mode(key('0'), &mut state).unwrap();
+        assert_eq!(state.pending_count, None);
+    }
+
+    #[test]
+    fn macro_records_actions_and_replays_with_count() {
+        let mut state = make_state(20);
+        for c in ['Q', 'a', '2', 'j', 'j', 'Q'] {
+            handle_navigate_mode(key(c), &mut state).unwrap();
+        }
+        assert_eq!(state.cursor_position, 3);
+        assert_eq!(state.macros.recording(), None);
+
+        for c in ['2', '@', 'a'] {
+            handle_navigate_mode(key(c), &mut state).unwrap();
+        }
+        assert_eq!(state.cursor_position, 9);
+        handle_navigate_mode(key('@'), &mut state).unwrap();
+        handle_navigate_mode(key('@'), &mut state).unwrap();
+        assert_eq!(state.cursor_position, 12);
+    }
+
+    #[test]
+    fn dot_repeats_the_last_change() {
+        let mut state = make_state(4);
+        state.last_change = Some(MacroStep {
+            action: Action::MoveItemDown,
+            count: None,
+            register: None,
+        });
+        repeat_last_change(None, &mut state).unwrap();
+        assert_eq!(state.todo_list.items[1].content, "Item 0");
+        repeat_last_change(Some(2), &mut state).unwrap();
+        assert_eq!(state.todo_list.items[3].content, "Item 0");
+    }
+
+    #[test]
+    fn counted_action_is_one_undo_step() {
+        let mut state = make_state(4);
+        state.cursor_position = 3;
+        execute_counted_action(Action::MoveItemUp, Some(2), &mut state).unwrap();
+        assert_eq!(state.todo_list.items[1].content, "Item 3");
+        assert_eq!(state.undo_stack.len(), 1);
+        state.undo();
+        assert_eq!(state.todo_list.items[3].content, "Item 3");
+    }
+}
+
+#[cfg(test)]
+mod tag_tests {
+    use super::*;
+    use crate::keybindings::KeybindingCache;
+    use crate::plugin::{PluginActionRegistry, PluginLoader};
+    use crate::todo::{Estimate, TodoItem, TodoList};
+    use crate::ui::theme::Theme;
+    use chrono::Local;
+
+    fn edit(text: &str) -> TodoItem {
+        let todo_list = TodoList {
+            date: Local::now().date_naive(),
+            items: vec![TodoItem::new("Item".to_string(), 0)],
+            file_path: std::path::PathBuf::from("/tmp/test.md"),
+        };
+        let mut state = AppState::new(
+            todo_list,
+            Theme::default(),
+            KeybindingCache::default(),
+            1000,
+            None,
+            None,
+            Project::default_project(),
+            PluginLoader::new(),
+            vec![],
+            PluginActionRegistry::new(),
+            crate::config::AutoRolloverPref::Ask,
+        );
+        state.edit_buffer = text.to_string();
+        save_edit_buffer(&mut state).unwrap();
+        state.todo_list.items.remove(0)
+    }
+
+    #[test]
+    fn typed_tags_set_fields_and_bad_ones_stay() {
+        let item = edit("Call vendor @est(1h) @waiting(bob)");
+        assert_eq!(item.content, "Call vendor");
+        assert_eq!(item.estimate, Some(Estimate::from_minutes(60)));
+        assert_eq!(item.waiting_on.as_deref(), Some("bob"));
+
+        let item = edit("Call vendor @est(later) @waiting(bob)");
+        assert_eq!(item.content, "Call vendor @est(later)");
+        assert_eq!(item.estimate, None);
+        assert_eq!(item.waiting_on.as_deref(), Some("bob"));
+    }
+}
+
+#[cfg(test)]
+mod mouse_tests {
+    use super::*;
+    use crate::keybindings::KeybindingCache;
+    use crate::plugin::{PluginActionRegistry, PluginLoader};
+    use crate::todo::{TodoItem, TodoList};
+    use crate::ui::theme::Theme;
+    use chrono::Local;
+
+    fn make_state() -> AppState {
+        let todo_list = TodoList {
+            date: Local::now().date_naive(),
+            items: vec![TodoItem::new("First".into(), 0), TodoItem::new("Second".into(), 0)],
+            file_path: std::path::PathBuf::from("/tmp/test.md"),
+        };
+        AppState::new(
+            todo_list,
+            Theme::default(),
+            KeybindingCache::default(),
+            1000,
+            None,
+            None,
+            Project::default_project(),
+            PluginLoader::new(),
+            vec![],
+            PluginActionRegistry::new(),
+            crate::config::AutoRolloverPref::Ask,
+        )
+    }
+
+    #[test]
+    fn double_click_on_content_edits_the_item() {
+        let mut state = make_state();
+        // Row 2 is the second item, column 20 is past its checkbox
+        handle_left_click(&mut state, 2, 20).unwrap();
+        assert_eq!(state.cursor_position, 1);
+        assert_eq!(state.mode, Mode::Navigate);
+
+        handle_left_click(&mut state, 2, 20).unwrap();
+        assert_eq!(state.mode, Mode::Edit);
+        assert_eq!(state.edit_buffer, "Second");
+    }
+
+    #[test]
+    fn clicks_on_different_items_are_not_a_double_click() {
+        let mut state = make_state();
+        handle_left_click(&mut state, 1, 20).unwrap();
+        handle_left_click(&mut state, 2, 20).unwrap();
+        assert_eq!(state.mode, Mode::Navigate);
+
+        // Too slow
+        state.last_content_click = state
+            .last_content_click
+            .map(|(at, idx)| (at - DOUBLE_CLICK_INTERVAL * 2, idx));
+        handle_left_click(&mut state, 2, 20).unwrap();
+        assert_eq!(state.mode, Mode::Navigate);
+    }
+}
+
+#[cfg(test)]
+mod project_select_tests {
+    use super::*;
+    use crate::keybindings::KeybindingCache;
+    use crate::plugin::{PluginActionRegistry, PluginLoader};
+    use crate::todo::TodoList;
+    use crate::ui::theme::Theme;
+    use chrono::Local;
+
+    fn make_state() -> AppState {
+        let todo_list = TodoList {
+            date: Local::now().date_naive(),
+            items: vec![],
+            file_path: std::path::PathBuf::from("/tmp/test.md"),
+        };
+        let mut state = AppState::new(
+            todo_list,
+            Theme::default(),
+            KeybindingCache::default(),
+            1000,
+            None,
+            None,
+            Project::default_project(),
+            PluginLoader::new(),
+            vec![],
+            PluginActionRegistry::new(),
+            crate::config::AutoRolloverPref::Ask,
+        );
+        state.project_state = Some(ProjectSubState::Selecting {
+            projects: ["default", "home", "work", "newsletter"].map(Project::new).to_vec(),
+            selected_index: 0,
+            query: String::new(),
+        });
+        state
+    }
+
+    fn press(state: &mut AppState, code: KeyCode, modifiers: KeyModifiers) {
+        handle_project_select_mode(KeyEvent::new(code, modifiers), state).unwrap();
+    }
+
+    fn shown(state: &AppState) -> (Vec<String>, usize) {
+        let Some(ProjectSubState::Selecting { projects, selected_index, query }) = &state.project_state
+        else {
+            panic!("not selecting");
+        };
+        let names = matching_projects(projects, query).iter().map(|p| p.name.clone()).collect();
+        (names, *selected_index)
+    }
+
+    #[test]
+    fn typing_filters_and_management_keys_need_ctrl() {
+        let mut state = make_state();
+        press(&mut state, KeyCode::Char('n'), KeyModifiers::NONE);
+        press(&mut state, KeyCode::Char('w'), KeyModifiers::NONE);
+        assert_eq!(shown(&state), (vec!["newsletter".to_string()], 0));
+
+        press(&mut state, KeyCode::Backspace, KeyModifiers::NONE);
+        press(&mut state, KeyCode::Backspace, KeyModifiers::NONE);
+        press(&mut state, KeyCode::Char('o'), KeyModifiers::NONE);
+        press(&mut state, KeyCode::Down, KeyModifiers::NONE);
+        assert_eq!(shown(&state).1, 1);
+
+        // Esc clears the filter, then closes
+        press(&mut state, KeyCode::Esc, KeyModifiers::NONE);
+        assert_eq!(shown(&state).0.len(), 4);
+
+        press(&mut state, KeyCode::Char('n'), KeyModifiers::CONTROL);
+        assert!(matches!(state.project_state, Some(ProjectSubState::CreateInput { .. })));
+    }
+
+    #[test]
+    fn pasting_filters_the_list_and_fills_the_name_prompt() {
+        let mut state = make_state();
+        state.mode = Mode::ProjectSelect;
+        handle_paste_event("news".to_string(), &mut state).unwrap();
+        assert_eq!(shown(&state), (vec!["newsletter".to_string()], 0));
+
+        state.project_state = Some(ProjectSubState::CreateInput {
+            input_buffer: String::new(),
+            cursor_pos: 0,
+        });
+        handle_paste_event("Side\nwork".to_string(), &mut state).unwrap();
+        assert!(matches!(
+            &state.project_state,
+            Some(ProjectSubState::CreateInput { input_buffer, .. }) if input_buffer == "Side work"
+        ));
+    }
+
+    #[test]
+    fn pasting_into_a_description_keeps_its_lines() {
+        let mut state = make_state();
+        state.mode = Mode::EditDescription;
+        handle_paste_event("Steps:\r\n1. Call\n2. Book".to_string(), &mut state).unwrap();
+        assert_eq!(state.desc_buffer, vec!["Steps:", "1. Call", "2. Book"]);
+        assert_eq!((state.desc_cursor_row, state.desc_cursor_col), (2, 7));
+    }
+}
+
+#[cfg(test)]
+mod attachment_tests {
+    use super::*;
+
+    #[test]
+    fn dropped_paths_are_unquoted() {
+        let expected = Some(PathBuf::from("/tmp/My Receipt.pdf"));
+        assert_eq!(attachment_path("/tmp/My Receipt.pdf"), expected);
+        assert_eq!(attachment_path("'/tmp/My Receipt.pdf' "), expected);
+        assert_eq!(attachment_path("\"/tmp/My Receipt.pdf\""), expected);
+        assert_eq!(attachment_path("/tmp/My\\ Receipt.pdf\n"), expected);
+        assert_eq!(attachment_path("file:///tmp/My Receipt.pdf"), expected);
+        assert_eq!(attachment_path("  "), None);
+    }
+}
+
+#[cfg(test)]
+mod plugin_modal_tests {
+    use super::*;
+    use crate::keybindings::KeybindingCache;
+    use crate::plugin::{LoadedPlugin, PluginActionRegistry, PluginLoader};
+    use crate::todo::TodoList;
+    use crate::ui::theme::Theme;
+    use abi_stable::std_types::{RHashMap, RResult, RString, RVec};
+    use chrono::Local;
+    use std::collections::HashMap;
+    use std::sync::{Arc, Mutex};
+    use totui_plugin_interface::{
+        FfiCommand, FfiConfigSchema, FfiEventType, FfiHookResponse, FfiTodoItem, Plugin, Plugin_TO,
+        UpdateNotifier,
+    };
+
+    /// Inputs `Echo` was run with
+    static RUNS: Mutex<Vec<String>> = Mutex::new(Vec::new());
+
+    /// A plugin that records the actions it is asked to run.
+    #[derive(Debug)]
+    struct Echo;
+
+    impl Plugin for Echo {
+        fn name(&self) -> RString {
+            "echo".into()
+        }
+
+        fn version(&self) -> RString {
+            "1.0.0".into()
+        }
+
+        fn min_interface_version(&self) -> RString {
+            "0.1.0".into()
+        }
+
+        fn generate(&self, _input: RString) -> RResult<RVec<FfiTodoItem>, RString> {
+            RResult::ROk(RVec::new())
+        }
+
+        fn config_schema(&self) -> FfiConfigSchema {
+            FfiConfigSchema::empty()
+        }
+
+        fn execute_with_host(
+            &self,
+            input: RString,
+            _host: HostApi_TO<'_, RBox<()>>,
+        ) -> RResult<RVec<FfiCommand>, RString> {
+            RUNS.lock().unwrap().push(input.into());
+            RResult::ROk(RVec::new())
+        }
+
+        fn on_config_loaded(&self, _config: RHashMap<RString, FfiConfigValue>) {}
+
+        fn subscribed_events(&self) -> RVec<FfiEventType> {
+            RVec::new()
+        }
+
+        fn on_event(&self, _event: FfiEvent) -> RResult<FfiHookResponse, RString> {
+            RResult::ROk(FfiHookResponse::default())
+        }
+
+        fn set_notifier(&self, _notifier: UpdateNotifier) {}
+    }
+
+    /// The `echo` plugin with a `ping` action on a key and an unbound `zap`.
+    fn make_state() -> AppState {
+        let mut loader = PluginLoader::new();
+        loader.insert(LoadedPlugin {
+            plugin: Arc::new(Plugin_TO::from_value(Echo, TD_Opaque)),
+            name: "echo".to_string(),
+            version: "1.0.0".to_string(),
+            description: "Echoes".to_string(),
+            session_disabled: false,
+        });
+        let manifest = toml::from_str(
+            r#"
+            name = "echo"
+            version = "1.0.0"
+            description = "Echoes"
+
+            [actions.ping]
+            description = "Ping"
+            default_keybinding = "<C-y>"
+
+            [actions.zap]
+            description = "Zap"
+            "#,
+        )
+        .unwrap();
+        let mut registry = PluginActionRegistry::new();
+        registry.register_plugin(&manifest, &HashMap::new(), &KeybindingCache::default());
+
+        let date = Local::now().date_naive();
+        let mut state = AppState::new(
+            TodoList::new(date, PathBuf::from("/tmp/test.md")),
+            Theme::default(),
+            KeybindingCache::default(),
+            1000,
+            None,
+            None,
+            Project::default_project(),
+            loader,
+            vec![],
+            registry,
+            crate::config::AutoRolloverPref::Ask,
+        );
+        state.mode = Mode::Plugin;
+        state
+    }
+
+    fn installed_tab(installed_index: usize) -> PluginsModalState {
+        PluginsModalState::Tabs {
+            active_tab: PluginsTab::Installed,
+            installed_index,
+            marketplace_index: 0,
+            marketplace_plugins: None,
+            marketplace_loading: false,
+            marketplace_error: None,
+            marketplace_name: "official".to_string(),
+        }
+    }
+
+    #[test]
+    fn test_installed_tab_lists_actions_with_and_without_keys() {
+        let state = make_state();
+        let rows = installed_rows(&state.plugin_loader, &state.plugin_action_registry);
+        let labels: Vec<_> = rows
+            .iter()
+            .map(|row| match row {
+                InstalledRow::Plugin(plugin) => plugin.name.clone(),
+                InstalledRow::Action(action) => format!("{} {}", action.action_name, action.keybinding.is_some()),
+            })
+            .collect();
+        assert_eq!(labels, vec!["echo", "ping true", "zap false"]);
+    }
+
+    #[test]
+    fn test_enter_on_an_action_runs_it() {
+        let mut state = make_state();
+        RUNS.lock().unwrap().clear();
+
+        // The unbound action runs from the modal, which then closes
+        let enter = KeyEvent::new(KeyCode::Enter, KeyModifiers::NONE);
+        handle_plugins_modal(enter, &mut state, installed_tab(2)).unwrap();
+        assert_eq!(*RUNS.lock().unwrap(), vec!["zap".to_string()]);
+        assert!(state.plugins_modal_state.is_none());
+        assert_eq!(state.mode, Mode::Navigate);
+
+        // Enter on the plugin itself still asks for generator input
+        state.mode = Mode::Plugin;
+        handle_plugins_modal(enter, &mut state, installed_tab(0)).unwrap();
+        assert!(matches!(state.plugins_modal_state, Some(PluginsModalState::Input { .. })));
+        assert_eq!(RUNS.lock().unwrap().len(), 1);
+    }
+}
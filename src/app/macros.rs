@@ -0,0 +1,116 @@
+//! Keyboard macros, like vim's `q` and `@`.
+//!
+//! A macro is the list of actions dispatched in navigate mode while it was
+//! being recorded, each with its count and register. Replaying runs the
+//! actions again, so a macro works the same whatever keys they are bound to.
+//! An uppercase name (`A`) appends to a macro instead of replacing it.
+
+use crate::keybindings::Action;
+use std::collections::HashMap;
+
+/// Name that replays the last macro played, as in `@@`
+pub const LAST_PLAYED: char = '@';
+
+/// An action as it was dispatched while recording.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct MacroStep {
+    pub action: Action,
+    pub count: Option<usize>,
+    pub register: Option<char>,
+}
+
+/// What the key after the record or play binding names a macro for.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum MacroPrompt {
+    Record,
+    Play { count: usize },
+}
+
+#[derive(Debug, Default)]
+pub struct Macros {
+    macros: HashMap<char, Vec<MacroStep>>,
+    /// Name and steps of the macro being recorded
+    recording: Option<(char, Vec<MacroStep>)>,
+    last_played: Option<char>,
+}
+
+impl Macros {
+    pub fn is_valid_name(name: char) -> bool {
+        name.is_ascii_alphabetic()
+    }
+
+    /// Name of the macro being recorded, if any.
+    pub fn recording(&self) -> Option<char> {
+        self.recording.as_ref().map(|(name, _)| *name)
+    }
+
+    pub fn start_recording(&mut self, name: char) {
+        let steps = if name.is_ascii_uppercase() {
+            self.macros.get(&name.to_ascii_lowercase()).cloned().unwrap_or_default()
+        } else {
+            Vec::new()
+        };
+        self.recording = Some((name.to_ascii_lowercase(), steps));
+    }
+
+    /// Stop recording and store the macro. Returns its name and length, or
+    /// None when nothing was being recorded.
+    pub fn stop_recording(&mut self) -> Option<(char, usize)> {
+        let (name, steps) = self.recording.take()?;
+        let len = steps.len();
+        self.macros.insert(name, steps);
+        Some((name, len))
+    }
+
+    /// Add a step to the macro being recorded, if any.
+    pub fn record(&mut self, step: MacroStep) {
+        if let Some((_, steps)) = &mut self.recording {
+            steps.push(step);
+        }
+    }
+
+    /// Steps of the named macro for replay; `@` names the last one played.
+    /// None when the macro is empty.
+    pub fn play(&mut self, name: char) -> Option<Vec<MacroStep>> {
+        let name = if name == LAST_PLAYED {
+            self.last_played?
+        } else {
+            name.to_ascii_lowercase()
+        };
+        let steps = self.macros.get(&name).filter(|steps| !steps.is_empty())?;
+        self.last_played = Some(name);
+        Some(steps.clone())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn step(action: Action) -> MacroStep {
+        MacroStep {
+            action,
+            count: None,
+            register: None,
+        }
+    }
+
+    #[test]
+    fn test_record_append_and_replay_last() {
+        let mut macros = Macros::default();
+        macros.record(step(Action::MoveDown));
+        macros.start_recording('a');
+        macros.record(step(Action::ToggleState));
+        assert_eq!(macros.stop_recording(), Some(('a', 1)));
+
+        macros.start_recording('A');
+        macros.record(step(Action::MoveDown));
+        assert_eq!(macros.stop_recording(), Some(('a', 2)));
+
+        assert_eq!(macros.play(LAST_PLAYED), None);
+        let steps = macros.play('a').unwrap();
+        assert_eq!(steps, vec![step(Action::ToggleState), step(Action::MoveDown)]);
+        assert_eq!(macros.play(LAST_PLAYED), Some(steps));
+        assert_eq!(macros.play('b'), None);
+    }
+}
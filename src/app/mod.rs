@@ -1,6 +1,13 @@
+pub mod activity;
+pub mod command;
 pub mod event;
+pub mod macros;
 pub mod mode;
+pub mod registers;
 pub mod state;
+pub mod toast;
+pub mod undo;
+pub mod view_cache;
 
 pub use mode::Mode;
 pub use state::AppState;
@@ -7,12 +7,30 @@ pub enum Mode {
     Edit,
     Visual,
     ConfirmDelete,
+    ConfirmQuit,
     Plugin,
     Rollover,
     UpgradePrompt,
     ProjectSelect,
     MoveToProject,
     EditDescription,
+    EditEstimate,
+    MoveToDate,
+    EditDue,
+    Search,
+    EditLink,
+    PlanReview,
+    Filter,
+    Command,
+    WeekView,
+    Calendar,
+    ThemePicker,
+    UndoHistory,
+    SearchResults,
+    Trash,
+    FileConflict,
+    StaleSave,
+    AttachFile,
 }
 
 impl fmt::Display for Mode {
@@ -22,12 +40,30 @@ impl fmt::Display for Mode {
             Mode::Edit => write!(f, "INSERT"),
             Mode::Visual => write!(f, "VISUAL"),
             Mode::ConfirmDelete => write!(f, "CONFIRM"),
+            Mode::ConfirmQuit => write!(f, "QUIT"),
             Mode::Plugin => write!(f, "PLUGIN"),
             Mode::Rollover => write!(f, "ROLLOVER"),
             Mode::UpgradePrompt => write!(f, "UPGRADE"),
             Mode::ProjectSelect => write!(f, "PROJECT"),
             Mode::MoveToProject => write!(f, "MOVE"),
             Mode::EditDescription => write!(f, "DESCRIBE"),
+            Mode::EditEstimate => write!(f, "ESTIMATE"),
+            Mode::MoveToDate => write!(f, "MOVE"),
+            Mode::EditDue => write!(f, "DUE"),
+            Mode::Search => write!(f, "SEARCH"),
+            Mode::EditLink => write!(f, "LINK"),
+            Mode::PlanReview => write!(f, "REVIEW"),
+            Mode::Filter => write!(f, "FILTER"),
+            Mode::Command => write!(f, "COMMAND"),
+            Mode::WeekView => write!(f, "WEEK"),
+            Mode::Calendar => write!(f, "CALENDAR"),
+            Mode::ThemePicker => write!(f, "THEME"),
+            Mode::UndoHistory => write!(f, "UNDO"),
+            Mode::SearchResults => write!(f, "RESULTS"),
+            Mode::Trash => write!(f, "TRASH"),
+            Mode::FileConflict => write!(f, "CONFLICT"),
+            Mode::StaleSave => write!(f, "CONFLICT"),
+            Mode::AttachFile => write!(f, "ATTACH"),
         }
     }
 }
@@ -0,0 +1,102 @@
+//! Yank registers, like vim's.
+//!
+//! Every yank fills the unnamed register. `"a` to `"z` name a register for the
+//! next yank or paste, and an uppercase name (`"A`) appends to it.
+
+use crate::todo::TodoItem;
+use chrono::Utc;
+use std::collections::HashMap;
+use uuid::Uuid;
+
+/// Register used when no name is given
+pub const UNNAMED: char = '"';
+
+#[derive(Debug, Default)]
+pub struct Registers {
+    registers: HashMap<char, Vec<TodoItem>>,
+}
+
+impl Registers {
+    pub fn is_valid_name(name: char) -> bool {
+        name.is_ascii_alphabetic()
+    }
+
+    /// Store yanked items, shifted so the shallowest one sits at indent 0.
+    pub fn yank(&mut self, name: Option<char>, items: &[TodoItem]) {
+        let base_indent = items.iter().map(|item| item.indent_level).min().unwrap_or(0);
+        let mut yanked: Vec<TodoItem> = items
+            .iter()
+            .cloned()
+            .map(|mut item| {
+                item.indent_level -= base_indent;
+                item
+            })
+            .collect();
+
+        if let Some(name) = name {
+            let register = self.registers.entry(name.to_ascii_lowercase()).or_default();
+            if name.is_ascii_uppercase() {
+                register.append(&mut yanked);
+            } else {
+                *register = yanked;
+            }
+            yanked = register.clone();
+        }
+        self.registers.insert(UNNAMED, yanked);
+    }
+
+    /// Copies of a register's items to insert at `indent`, with fresh ids and
+    /// timestamps. `None` when the register is empty.
+    pub fn paste(&self, name: Option<char>, indent: usize) -> Option<Vec<TodoItem>> {
+        let name = name.map_or(UNNAMED, |n| n.to_ascii_lowercase());
+        let items = self.registers.get(&name).filter(|items| !items.is_empty())?;
+        let now = Utc::now();
+        Some(
+            items
+                .iter()
+                .cloned()
+                .map(|mut item| {
+                    item.id = Uuid::new_v4();
+                    item.parent_id = None;
+                    item.indent_level += indent;
+                    item.created_at = now;
+                    item.modified_at = now;
+                    item.deleted_at = None;
+                    item
+                })
+                .collect(),
+        )
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_named_registers_and_append() {
+        let mut registers = Registers::default();
+        registers.yank(Some('a'), &[TodoItem::new("Parent".into(), 1), TodoItem::new("Child".into(), 2)]);
+        registers.yank(Some('A'), &[TodoItem::new("Other".into(), 3)]);
+
+        let pasted = registers.paste(Some('a'), 2).unwrap();
+        let contents: Vec<(&str, usize)> =
+            pasted.iter().map(|i| (i.content.as_str(), i.indent_level)).collect();
+        assert_eq!(contents, vec![("Parent", 2), ("Child", 3), ("Other", 2)]);
+
+        // The unnamed register follows the last yank, whatever its name
+        assert_eq!(registers.paste(None, 0).unwrap().len(), 3);
+        assert!(registers.paste(Some('b'), 0).is_none());
+    }
+
+    #[test]
+    fn test_paste_gives_fresh_ids() {
+        let mut registers = Registers::default();
+        let item = TodoItem::new("Task".into(), 0);
+        registers.yank(None, std::slice::from_ref(&item));
+        let first = registers.paste(None, 0).unwrap();
+        let second = registers.paste(None, 0).unwrap();
+        assert_ne!(first[0].id, item.id);
+        assert_ne!(first[0].id, second[0].id);
+    }
+}
@@ -1,29 +1,69 @@
+use super::activity::{Activity, ActivityLog};
+use super::command::{self, Completion};
+use super::macros::{MacroPrompt, MacroStep, Macros};
 use super::mode::Mode;
+use super::registers::Registers;
+use super::toast::{ToastLevel, Toasts};
+use super::undo::{describe_change, UndoEntry};
+use super::view_cache::{self, ItemHeight, ViewCache};
 use crate::keybindings::{KeyBinding, KeybindingCache};
 use crate::plugin::{
-    marketplace::PluginEntry, GeneratorInfo, HookDispatcher, PluginActionRegistry, PluginLoadError,
-    PluginLoader,
+    marketplace::PluginEntry, GeneratorInfo, HookDispatcher, LoadedPlugin, PluginAction,
+    PluginActionRegistry, PluginLoadError, PluginLoader,
 };
 use crate::project::{Project, ProjectRegistry};
-use crate::storage::file::{load_todo_list_for_project, load_todos_for_viewing_in_project};
+use crate::storage::conflict::{find_conflict, DailyFileConflict};
+use crate::storage::history::with_plugin_origin;
+use crate::storage::file::{
+    load_deleted_todos_for_project, load_todo_list_with_revision, load_todos_for_viewing_in_project,
+    purge_deleted_todos_for_project, restore_deleted_todos_for_project, save_todo_list_for_project,
+    save_todo_list_if_current, update_todo_list_for_project,
+};
+use crate::storage::revision::{day_revision, stale_day, StaleDay, StaleSave};
 use crate::storage::rollover::find_rollover_candidates_for_project;
-use crate::storage::UiCache;
-use crate::todo::{PriorityCycle, TodoItem, TodoList};
-use crate::ui::theme::Theme;
+use crate::storage::database::{day_summaries_for_project, search_todos, DaySummary, DeletedTodo, SearchHit};
+use crate::storage::attachments::{copy_attachments, list_attachments, Attachment};
+use crate::storage::metadata::{get_all_todo_metadata, PluginMetadata};
+use crate::storage::undo_history::{MovedItems, UndoHistory, UndoSnapshot};
+use crate::storage::{CachedView, UiCache};
+use crate::todo::plan::{parse_plan, PlanReview};
+use crate::todo::{Filter, PriorityCycle, TodoItem, TodoList, TodoState};
+use crate::config::{BackupConfig, GlyphsConfig, PrioritiesConfig};
+use crate::ui::components::todo_list::{LINK_GLYPH, PINNED_GLYPH};
+use crate::ui::highlight::HighlightRule;
+use crate::ui::theme::{Theme, THEME_NAMES};
+use crate::utils::fuzzy::fuzzy_filter;
+use crate::utils::search;
+use crate::utils::unicode::{display_width, wrapped_line_count};
 use crate::utils::upgrade::{
     get_asset_download_url, spawn_download, DownloadProgress, PluginUpgradeSubState, UpgradeSubState,
 };
 use crate::utils::version_check::{spawn_version_checker, PluginUpdateInfo, VersionCheckResult};
 use anyhow::Result;
-use chrono::{Duration, Local, NaiveDate};
+use chrono::{DateTime, Datelike, Duration, Local, Months, NaiveDate, NaiveDateTime};
 use ratatui::widgets::ListState;
+use std::collections::{HashMap, HashSet};
+use std::fs;
+use std::path::Path;
+use std::rc::Rc;
 use std::sync::mpsc;
 use std::time::Instant;
-use totui_plugin_interface::{FfiEvent, FfiFieldChange};
+use totui_plugin_interface::{FfiEvent, FfiEventSource, FfiFieldChange};
 use tracing::{debug, trace};
 use uuid::Uuid;
 
 const MAX_UNDO_HISTORY: usize = 50;
+/// Changes made within this long of each other are saved together
+const SAVE_DEBOUNCE: std::time::Duration = std::time::Duration::from_millis(500);
+/// How often the daily file is checked for edits made outside totui
+const FILE_CHECK_INTERVAL: std::time::Duration = std::time::Duration::from_secs(2);
+/// Narrower terminals keep the whole width for the list
+const MIN_WIDTH_FOR_DETAIL_PANE: u16 = 80;
+/// Narrowest terminal that shows the split view's second day
+const MIN_WIDTH_FOR_SPLIT_VIEW: u16 = 60;
+
+/// Indent level from which the path to the selected item is shown above the list
+const BREADCRUMB_MIN_INDENT: usize = 2;
 
 /// Tab selection in plugins modal
 #[derive(Debug, Clone, Copy, PartialEq, Eq)]
@@ -32,6 +72,35 @@ pub enum PluginsTab {
     Marketplace,
 }
 
+/// A row of the plugins modal's Installed tab.
+#[derive(Clone, Copy)]
+pub enum InstalledRow<'a> {
+    /// Enter runs the plugin's generator
+    Plugin(&'a LoadedPlugin),
+    /// Enter runs the action, whether or not it has a key
+    Action(&'a PluginAction),
+}
+
+/// Rows of the Installed tab: plugins by name, each followed by its actions.
+pub fn installed_rows<'a>(
+    loader: &'a PluginLoader,
+    registry: &'a PluginActionRegistry,
+) -> Vec<InstalledRow<'a>> {
+    let mut plugins: Vec<_> = loader.loaded_plugins().collect();
+    plugins.sort_by(|a, b| a.name.cmp(&b.name));
+    let mut actions_by_plugin = registry.actions_by_plugin();
+
+    let mut rows = Vec::new();
+    for plugin in plugins {
+        rows.push(InstalledRow::Plugin(plugin));
+        if let Some(mut actions) = actions_by_plugin.remove(&plugin.name) {
+            actions.sort_by(|a, b| a.action_name.cmp(&b.action_name));
+            rows.extend(actions.into_iter().map(InstalledRow::Action));
+        }
+    }
+    rows
+}
+
 /// State for the tabbed plugins modal
 #[derive(Debug, Clone)]
 pub enum PluginsModalState {
@@ -71,10 +140,6 @@ pub enum PluginsModalState {
     Executing {
         plugin_name: String,
     },
-    /// Preview generated items
-    Preview {
-        items: Vec<TodoItem>,
-    },
     /// Error display
     Error {
         message: String,
@@ -106,9 +171,15 @@ pub enum PluginSubState {
     Error {
         message: String,
     },
-    Preview {
-        items: Vec<TodoItem>,
-    },
+}
+
+/// The day as last loaded or saved, and its revision then.
+#[derive(Debug, Clone)]
+struct SyncedDay {
+    project: String,
+    date: NaiveDate,
+    revision: u64,
+    list: TodoList,
 }
 
 /// Holds data for pending rollover from a previous day
@@ -125,7 +196,10 @@ pub struct PendingRollover {
 pub enum ProjectSubState {
     Selecting {
         projects: Vec<Project>,
+        /// Index into the projects matching `query`
         selected_index: usize,
+        /// Typed filter, fuzzy-matched against project names
+        query: String,
     },
     CreateInput {
         input_buffer: String,
@@ -141,6 +215,15 @@ pub enum ProjectSubState {
     },
 }
 
+/// The projects whose names fuzzy-match `query`, best first. An empty query
+/// keeps every project in its original order.
+pub fn matching_projects<'a>(projects: &'a [Project], query: &str) -> Vec<&'a Project> {
+    if query.is_empty() {
+        return projects.iter().collect();
+    }
+    fuzzy_filter(projects.iter(), query, |project| project.name.as_str())
+}
+
 /// Move to project modal sub-state
 #[derive(Debug, Clone)]
 pub enum MoveToProjectSubState {
@@ -148,9 +231,91 @@ pub enum MoveToProjectSubState {
         projects: Vec<Project>,
         selected_index: usize,
         item_index: usize,  // Index of item being moved
+        /// End (exclusive) of the rows being moved, children included
+        item_end: usize,
     },
 }
 
+/// Seven days of lists shown side by side, Monday first.
+#[derive(Debug, Clone)]
+pub struct WeekView {
+    pub start: NaiveDate,
+    pub days: Vec<TodoList>,
+    /// Selected day, 0 = Monday
+    pub selected: usize,
+}
+
+impl WeekView {
+    /// Monday of the week containing `date`.
+    pub fn week_start(date: NaiveDate) -> NaiveDate {
+        date - Duration::days(date.weekday().num_days_from_monday() as i64)
+    }
+
+    pub fn selected_date(&self) -> NaiveDate {
+        self.start + Duration::days(self.selected as i64)
+    }
+}
+
+/// Another day shown read-only beside the list, for reference while planning.
+#[derive(Debug, Clone)]
+pub struct SplitView {
+    pub list: TodoList,
+    /// Selected row among the visible rows
+    pub cursor: usize,
+    /// Navigation keys move in this pane instead of the list
+    pub focused: bool,
+}
+
+impl SplitView {
+    /// Indices of the rows not hidden under a collapsed parent.
+    pub fn visible_indices(&self) -> Vec<usize> {
+        let hidden = self.list.build_hidden_indices();
+        (0..self.list.items.len()).filter(|i| !hidden.contains(i)).collect()
+    }
+
+    /// Index of the selected item in the list.
+    pub fn selected_index(&self) -> Option<usize> {
+        self.visible_indices().get(self.cursor).copied()
+    }
+}
+
+/// Month grid for picking a day to view.
+#[derive(Debug, Clone)]
+pub struct CalendarState {
+    pub selected: NaiveDate,
+    /// Item counts for the days of the selected month that have items
+    pub days: HashMap<NaiveDate, DaySummary>,
+}
+
+impl CalendarState {
+    /// First day of the selected month.
+    pub fn month_start(&self) -> NaiveDate {
+        self.selected.with_day(1).unwrap_or(self.selected)
+    }
+}
+
+/// Theme list with the highlighted theme previewed live.
+#[derive(Debug, Clone)]
+pub struct ThemePicker {
+    /// Index into `THEME_NAMES`
+    pub selected: usize,
+    /// Restored when the picker is cancelled
+    original: Theme,
+    /// Configured priority levels, applied on top of each previewed theme
+    priorities: PrioritiesConfig,
+    /// Configured glyphs, likewise
+    glyphs: GlyphsConfig,
+}
+
+impl ThemePicker {
+    pub fn selected_name(&self) -> &'static str {
+        THEME_NAMES[self.selected]
+    }
+}
+
+/// Most results the all-dates search shows
+const SEARCH_RESULTS_LIMIT: usize = 200;
+
 pub struct AppState {
     pub todo_list: TodoList,
     pub cursor_position: usize,
@@ -158,18 +323,113 @@ pub struct AppState {
     pub edit_buffer: String,
     pub edit_cursor_pos: usize,
     pub should_quit: bool,
+    /// Mode to go back to if the quit prompt is cancelled
+    pub mode_before_quit: Mode,
     pub show_help: bool,
+    /// Only show delegated items (`@waiting(...)`) and their parents
+    pub show_waiting_only: bool,
+    /// View filters chosen from the filter menu; they hide items without changing the list
+    pub active_filters: Vec<Filter>,
+    /// Item whose subtree is shown on its own (focus mode)
+    pub focus_root: Option<Uuid>,
+    /// Whether the filter menu is reading a tag into `edit_buffer`
+    pub filter_tag_input: bool,
+    /// Show short item ids in a gutter left of the list
+    pub show_short_ids: bool,
+    /// Number the visible items in a gutter left of the list
+    pub show_line_numbers: bool,
+    /// Move items below their open siblings when they are marked done
+    pub sink_completed: bool,
+    /// Styles for item text matching the configured `[[highlights]]`
+    pub highlight_rules: Vec<HighlightRule>,
+    /// Show the welcome panel in place of the empty list (no todo saved yet)
+    pub onboarding: bool,
+    pub backup_config: BackupConfig,
+    /// When the newest database backup was taken
+    pub last_backup: Option<NaiveDateTime>,
+    /// Show the day's completion gauge above the list
+    pub show_progress_bar: bool,
+    /// Style markdown in descriptions; false shows them as typed
+    pub render_markdown: bool,
+    /// ASCII glyphs, no animation, and messages first in the status line
+    pub accessible_mode: bool,
+    /// Draw without color: set by accessible mode or the `NO_COLOR` variable
+    pub no_color: bool,
+    /// Show the selected item's details in a pane right of the list
+    pub show_detail_pane: bool,
+    /// Plugin metadata of the item shown in the detail pane, keyed by its id
+    pub detail_metadata: Option<(Uuid, Vec<PluginMetadata>)>,
+    /// Files attached to the item shown in the detail pane, keyed by its id
+    pub detail_attachments: Option<(Uuid, Vec<Attachment>)>,
+    /// Hidden indices and item heights, kept between frames
+    view_cache: ViewCache,
+    /// Confirmed search query; matches stay highlighted until cleared
+    pub search_query: Option<String>,
+    /// Cursor position when the search prompt was opened, restored on cancel
+    search_origin: usize,
+    /// Generated or pasted plan waiting for review before it is inserted
+    pub plan_review: Option<PlanReview>,
+    /// What was added, completed and moved during this session
+    pub activity: ActivityLog,
+    pub show_timeline: bool,
+    pub timeline_scroll: u16,
+    /// Everything the `:` prompt can complete, gathered when it opens
+    pub command_candidates: Vec<Completion>,
+    /// Highlighted row in the `:` prompt's completion list
+    pub command_selected: usize,
+    pub week_view: Option<WeekView>,
+    pub split_view: Option<SplitView>,
+    pub calendar: Option<CalendarState>,
+    pub theme_picker: Option<ThemePicker>,
     pub theme: Theme,
     pub keybindings: KeybindingCache,
     pub pending_key: Option<KeyBinding>,
     pub pending_key_time: Option<Instant>,
+    /// Count typed before a navigate action, e.g. the 5 in `5j`
+    pub pending_count: Option<usize>,
+    pub registers: Registers,
+    /// `"` was typed and the next key names a register
+    pub awaiting_register: bool,
+    /// Register named for the next yank or paste, e.g. the a in `"ay`
+    pub pending_register: Option<char>,
+    pub macros: Macros,
+    /// The record or play binding was typed and the next key names a macro
+    pub awaiting_macro: Option<MacroPrompt>,
+    /// Last change made in navigate or visual mode, repeated by `.`
+    pub last_change: Option<MacroStep>,
+    /// Rows the due date prompt will update
+    pub due_targets: Vec<usize>,
     pub timeoutlen: u64,
     pub unsaved_changes: bool,
     pub last_save_time: Option<Instant>,
+    /// When the pending changes get saved, if a save is scheduled
+    pub save_due: Option<Instant>,
     pub is_creating_new_item: bool,
     pub insert_above: bool,
     pub pending_indent_level: usize,
-    pub undo_stack: Vec<(TodoList, usize)>,
+    pub undo_stack: Vec<UndoEntry>,
+    /// Whether the undo stack is written to disk with each save, so it
+    /// survives a restart
+    pub persist_undo: bool,
+    /// Highlighted row in the undo history, 0 being the latest change
+    pub undo_history_selected: usize,
+    /// Items on any date matching `search_results_query`, newest first
+    pub search_results: Vec<SearchHit>,
+    pub search_results_query: String,
+    pub search_results_selected: usize,
+    /// Soft-deleted items of the project, most recently deleted first
+    pub trash: Vec<DeletedTodo>,
+    /// Highlighted row of the trash, counting only `trash_rows`
+    pub trash_selected: usize,
+    /// The daily file and the database both changed, waiting on the user
+    pub file_conflict: Option<DailyFileConflict>,
+    /// Hash of a file edit the user chose to leave unresolved for now
+    file_conflict_dismissed: Option<String>,
+    /// The day as last loaded or saved, to tell saves made elsewhere since
+    synced: Option<SyncedDay>,
+    /// Unsaved changes to a day saved elsewhere meanwhile, waiting on the user
+    pub stale_save: Option<StaleSave>,
+    last_file_check: Instant,
     pub selection_anchor: Option<usize>,
     pub viewing_date: NaiveDate,
     pub today: NaiveDate,
@@ -179,7 +439,8 @@ pub struct AppState {
     pub plugins_modal_state: Option<PluginsModalState>,
     /// Receiver for marketplace fetch results
     pub marketplace_fetch_rx: Option<mpsc::Receiver<Result<Vec<PluginEntry>, String>>>,
-    pub status_message: Option<(String, Instant)>,
+    /// Notifications stacked above the status bar
+    pub toasts: Toasts,
     pub plugin_result_rx: Option<mpsc::Receiver<Result<Vec<TodoItem>, String>>>,
     pub plugin_result_source: Option<PluginResultSource>,
     pub spinner_frame: usize,
@@ -223,6 +484,8 @@ pub struct AppState {
     pub cursor_is_pointer: bool,
     /// Position where last MouseDown(Left) occurred, for click vs drag detection
     pub mouse_down_pos: Option<(u16, u16)>,
+    /// Time and item of the last click on an item's text, for double clicks
+    pub last_content_click: Option<(Instant, usize)>,
     /// Start of mouse text selection (row, col)
     pub mouse_select_start: Option<(u16, u16)>,
     /// End of mouse text selection (row, col)
@@ -251,6 +514,8 @@ pub struct AppState {
     pub desc_original: Option<String>,
     /// Description editor: vertical scroll offset
     pub desc_scroll_offset: usize,
+    /// Views saved per project and day, written back on quit
+    pub ui_cache: UiCache,
 }
 
 impl AppState {
@@ -271,10 +536,13 @@ impl AppState {
         let today = Local::now().date_naive();
         let viewing_date = todo_list.date;
 
-        // Find cursor position from cached selected_todo_id
-        let cursor_position = ui_cache
-            .as_ref()
-            .and_then(|cache| cache.selected_todo_id)
+        // Restore the view this project and day were left in
+        let ui_cache = ui_cache.unwrap_or_default();
+        let cached_view = ui_cache
+            .view(&current_project.name, viewing_date)
+            .unwrap_or_default();
+        let cursor_position = cached_view
+            .selected_todo_id
             .and_then(|id| Self::find_item_index_by_id(&todo_list, id))
             .unwrap_or(0);
 
@@ -285,18 +553,71 @@ impl AppState {
             edit_buffer: String::new(),
             edit_cursor_pos: 0,
             should_quit: false,
+            mode_before_quit: Mode::Navigate,
             show_help: false,
+            show_waiting_only: false,
+            active_filters: Vec::new(),
+            focus_root: None,
+            filter_tag_input: false,
+            show_short_ids: false,
+            show_line_numbers: false,
+            sink_completed: false,
+            highlight_rules: Vec::new(),
+            onboarding: false,
+            backup_config: BackupConfig::default(),
+            last_backup: None,
+            show_progress_bar: true,
+            accessible_mode: false,
+            no_color: false,
+            render_markdown: true,
+            show_detail_pane: false,
+            detail_metadata: None,
+            detail_attachments: None,
+            view_cache: ViewCache::default(),
+            search_query: None,
+            search_origin: 0,
+            plan_review: None,
+            activity: ActivityLog::default(),
+            show_timeline: false,
+            timeline_scroll: 0,
+            command_candidates: Vec::new(),
+            command_selected: 0,
+            week_view: None,
+            split_view: None,
+            calendar: None,
+            theme_picker: None,
             theme,
             keybindings,
             pending_key: None,
             pending_key_time: None,
+            pending_count: None,
+            registers: Registers::default(),
+            awaiting_register: false,
+            pending_register: None,
+            macros: Macros::default(),
+            awaiting_macro: None,
+            last_change: None,
+            due_targets: Vec::new(),
             timeoutlen,
             unsaved_changes: false,
             last_save_time: None,
+            save_due: None,
             is_creating_new_item: false,
             insert_above: false,
             pending_indent_level: 0,
             undo_stack: Vec::new(),
+            persist_undo: false,
+            undo_history_selected: 0,
+            search_results: Vec::new(),
+            search_results_query: String::new(),
+            search_results_selected: 0,
+            trash: Vec::new(),
+            trash_selected: 0,
+            file_conflict: None,
+            file_conflict_dismissed: None,
+            synced: None,
+            stale_save: None,
+            last_file_check: Instant::now(),
             selection_anchor: None,
             viewing_date,
             today,
@@ -304,7 +625,7 @@ impl AppState {
             plugin_state: None,
             plugins_modal_state: None,
             marketplace_fetch_rx: None,
-            status_message: None,
+            toasts: Toasts::default(),
             plugin_result_rx: None,
             plugin_result_source: None,
             spinner_frame: 0,
@@ -329,6 +650,7 @@ impl AppState {
             move_to_project_state: None,
             cursor_is_pointer: false,
             mouse_down_pos: None,
+            last_content_click: None,
             mouse_select_start: None,
             mouse_select_end: None,
             screen_cells: Vec::new(),
@@ -343,12 +665,72 @@ impl AppState {
             desc_scroll_offset: 0,
             hook_dispatcher: HookDispatcher::new(),
             in_hook_apply: false,
+            ui_cache,
         };
+        state.show_waiting_only = cached_view.show_waiting_only;
+        state.active_filters = cached_view.active_filters;
+        *state.list_state.offset_mut() = cached_view.scroll_offset;
         // Sync list state with cursor position
         state.sync_list_state();
         state
     }
 
+    /// Record the current view in the UI cache and write it to disk.
+    pub fn save_ui_cache(&mut self) -> Result<()> {
+        let view = CachedView {
+            selected_todo_id: self.get_selected_todo_id(),
+            scroll_offset: self.list_state.offset(),
+            active_filters: self.active_filters.clone(),
+            show_waiting_only: self.show_waiting_only,
+        };
+        self.ui_cache
+            .set_view(&self.current_project.name, self.viewing_date, view);
+        self.ui_cache.save()
+    }
+
+    /// Take back the undo stack saved by the last session if it was of the
+    /// same day, and keep saving it from now on.
+    pub fn restore_undo_history(&mut self, history: Option<UndoHistory>) {
+        self.persist_undo = true;
+        let Some(history) = history else {
+            return;
+        };
+        self.undo_stack = history
+            .into_snapshots_for(&self.current_project.name, self.viewing_date)
+            .into_iter()
+            .map(|snapshot| UndoEntry {
+                list: snapshot.list,
+                cursor: snapshot.cursor,
+                saved_at: snapshot.saved_at,
+                moved: snapshot.moved,
+            })
+            .collect();
+        let excess = self.undo_stack.len().saturating_sub(MAX_UNDO_HISTORY);
+        self.undo_stack.drain(..excess);
+    }
+
+    /// Write the undo stack to disk, if it is being persisted.
+    pub fn save_undo_history(&self) -> Result<()> {
+        if !self.persist_undo {
+            return Ok(());
+        }
+        UndoHistory {
+            project: self.current_project.name.clone(),
+            date: self.viewing_date,
+            snapshots: self
+                .undo_stack
+                .iter()
+                .map(|entry| UndoSnapshot {
+                    list: entry.list.clone(),
+                    cursor: entry.cursor,
+                    saved_at: entry.saved_at,
+                    moved: entry.moved.clone(),
+                })
+                .collect(),
+        }
+        .save()
+    }
+
     fn find_item_index_by_id(todo_list: &TodoList, id: Uuid) -> Option<usize> {
         todo_list.items.iter().position(|item| item.id == id)
     }
@@ -362,11 +744,536 @@ impl AppState {
         self.viewing_date != self.today
     }
 
+    /// Indices hidden from the list view: children of collapsed items, plus
+    /// everything outside the active view filter and the focused subtree.
+    pub fn hidden_indices(&self) -> Rc<HashSet<usize>> {
+        let key = view_cache::key(
+            view_cache::list_hasher(&self.todo_list.items),
+            (&self.active_filters, self.show_waiting_only, self.focus_root),
+        );
+        self.view_cache.hidden(key, || {
+            let mut hidden = self.todo_list.build_hidden_indices();
+            if let Some(visible) = self.filter_visible_indices() {
+                hidden.extend((0..self.todo_list.items.len()).filter(|i| !visible.contains(i)));
+            }
+            if let Some((start, end)) = self.focus_range() {
+                hidden.extend((0..start).chain(end..self.todo_list.items.len()));
+            }
+            hidden
+        })
+    }
+
+    /// Range of the focused subtree, or None when not focused or the focused
+    /// item is gone (e.g. after switching days).
+    fn focus_range(&self) -> Option<(usize, usize)> {
+        let index = Self::find_item_index_by_id(&self.todo_list, self.focus_root?)?;
+        self.todo_list.get_item_range(index).ok()
+    }
+
+    /// Show only the current item and its descendants, or leave focus mode.
+    pub fn toggle_focus(&mut self) {
+        if self.focus_root.is_some() {
+            self.exit_focus();
+            return;
+        }
+        let Some(item) = self.todo_list.items.get(self.cursor_position) else {
+            return;
+        };
+        self.focus_root = Some(item.id);
+        *self.list_state.offset_mut() = 0;
+        self.sync_list_state();
+        self.set_status_message("Focused on subtree (Esc to exit)".to_string());
+    }
+
+    pub fn exit_focus(&mut self) {
+        self.focus_root = None;
+        self.sync_list_state();
+    }
+
+    /// Path from the top level down to the focused item, e.g.
+    /// "Release › Docs › README".
+    pub fn focus_breadcrumb(&self) -> Option<String> {
+        let (start, _) = self.focus_range()?;
+        Some(self.path_to(start).join(" › "))
+    }
+
+    /// Contents of the item at `index` and its ancestors, top level first.
+    fn path_to(&self, index: usize) -> Vec<&str> {
+        let mut path = vec![self.todo_list.items[index].content.as_str()];
+        let mut index = index;
+        while let Some(parent) = self.find_parent_index(index) {
+            path.push(self.todo_list.items[parent].content.as_str());
+            index = parent;
+        }
+        path.reverse();
+        path
+    }
+
+    /// Path to the selected item, shown above the list once it is nested two
+    /// levels deep and its ancestors may have scrolled out of view.
+    pub fn breadcrumb(&self) -> Option<Vec<&str>> {
+        self.shows_breadcrumb().then(|| self.path_to(self.cursor_position))
+    }
+
+    fn shows_breadcrumb(&self) -> bool {
+        self.mode != Mode::WeekView
+            && self
+                .todo_list
+                .items
+                .get(self.cursor_position)
+                .is_some_and(|item| item.indent_level >= BREADCRUMB_MIN_INDENT)
+    }
+
+    /// Rows taken by the breadcrumb line above the list.
+    pub fn breadcrumb_height(&self) -> u16 {
+        u16::from(self.shows_breadcrumb())
+    }
+
+    /// Rows available for items inside the list: the terminal minus the
+    /// list borders, the status bar and the breadcrumb.
+    pub fn list_viewport_height(&self) -> usize {
+        self.terminal_height
+            .saturating_sub(3 + self.breadcrumb_height())
+            .max(1) as usize
+    }
+
+    /// Items left visible by the waiting filter and the view filters, or None
+    /// when no filter is active.
+    fn filter_visible_indices(&self) -> Option<HashSet<usize>> {
+        let waiting = self
+            .show_waiting_only
+            .then(|| self.todo_list.waiting_visible_indices());
+        let filtered = (!self.active_filters.is_empty())
+            .then(|| self.todo_list.filter_visible_indices(&self.active_filters));
+        match (waiting, filtered) {
+            (Some(waiting), Some(filtered)) => Some(waiting.intersection(&filtered).copied().collect()),
+            (waiting, filtered) => waiting.or(filtered),
+        }
+    }
+
+    /// Add the filter if it is not active, remove it if it is.
+    /// A filter that would hide every item is not added.
+    pub fn toggle_filter(&mut self, filter: Filter) {
+        if let Some(pos) = self.active_filters.iter().position(|f| *f == filter) {
+            self.active_filters.remove(pos);
+        } else {
+            self.active_filters.push(filter.clone());
+            if !self.todo_list.items.is_empty()
+                && self.filter_visible_indices().is_some_and(|visible| visible.is_empty())
+            {
+                self.active_filters.pop();
+                self.set_warning(format!("No items match {filter}"));
+                return;
+            }
+        }
+        self.snap_cursor_to_visible();
+        self.sync_list_state();
+        match self.filter_summary() {
+            Some(summary) => self.set_status_message(format!("Filter: {summary}")),
+            None => self.set_status_message("Filters cleared".to_string()),
+        }
+    }
+
+    pub fn clear_filters(&mut self) {
+        if self.active_filters.is_empty() {
+            return;
+        }
+        self.active_filters.clear();
+        self.sync_list_state();
+        self.set_status_message("Filters cleared".to_string());
+    }
+
+    /// Active view filters as shown in the status bar, e.g. "hide done, P0".
+    pub fn filter_summary(&self) -> Option<String> {
+        (!self.active_filters.is_empty()).then(|| {
+            self.active_filters
+                .iter()
+                .map(Filter::to_string)
+                .collect::<Vec<_>>()
+                .join(", ")
+        })
+    }
+
+    /// Move the cursor off an item a filter hides, preferring the next visible item.
+    fn snap_cursor_to_visible(&mut self) {
+        let hidden = self.hidden_indices();
+        if !hidden.contains(&self.cursor_position) {
+            return;
+        }
+        let len = self.todo_list.items.len();
+        if let Some(index) = (self.cursor_position + 1..len)
+            .chain((0..self.cursor_position).rev())
+            .find(|i| !hidden.contains(i))
+        {
+            self.cursor_position = index;
+        }
+    }
+
+    /// Toggle the short id gutter.
+    pub fn toggle_short_ids(&mut self) {
+        self.show_short_ids = !self.show_short_ids;
+        let message = if self.show_short_ids {
+            "Showing short ids"
+        } else {
+            "Hiding short ids"
+        };
+        self.set_status_message(message.to_string());
+    }
+
+    /// Width of the short id gutter, including its trailing space (0 when hidden).
+    pub fn short_id_gutter_width(&self) -> usize {
+        if !self.show_short_ids || self.todo_list.items.is_empty() {
+            return 0;
+        }
+        let longest = self.todo_list.short_ids().values().map(String::len).max();
+        longest.map_or(0, |len| len + 1)
+    }
+
+    pub fn toggle_line_numbers(&mut self) {
+        self.show_line_numbers = !self.show_line_numbers;
+    }
+
+    /// Turn sinking of completed items on or off. Turning it on sinks the
+    /// items that are already done.
+    pub fn toggle_sink_completed(&mut self) {
+        self.sink_completed = !self.sink_completed;
+        if self.sink_completed && !self.is_readonly() {
+            self.save_undo();
+            self.sink_completed_items();
+            self.unsaved_changes = true;
+        }
+        self.set_status_message(
+            if self.sink_completed { "Completed items sink to the bottom" } else { "Completed items stay in place" }
+                .to_string(),
+        );
+    }
+
+    /// Move completed items below their open siblings when sinking is on.
+    /// The cursor keeps its row, so it lands on the item that moved up.
+    fn sink_completed_items(&mut self) {
+        if !self.sink_completed {
+            return;
+        }
+        self.todo_list.sink_completed();
+        self.clamp_cursor();
+        self.sync_list_state();
+    }
+
+    /// Width of the line number gutter, including its trailing space (0 when hidden).
+    pub fn line_number_width(&self) -> usize {
+        if !self.show_line_numbers || self.todo_list.items.is_empty() {
+            return 0;
+        }
+        self.todo_list.items.len().to_string().len() + 1
+    }
+
+    /// Width of everything left of the item rows: line numbers and short ids.
+    pub fn gutter_width(&self) -> usize {
+        self.line_number_width() + self.short_id_gutter_width()
+    }
+
+    /// Move to the `line`th visible item, counting from 1. Lines past the end
+    /// go to the last item.
+    pub fn go_to_line(&mut self, line: usize) {
+        let hidden = self.hidden_indices();
+        let visible: Vec<usize> = (0..self.todo_list.items.len())
+            .filter(|i| !hidden.contains(i))
+            .collect();
+        if let Some(&index) = visible.get(line.saturating_sub(1)).or(visible.last()) {
+            self.cursor_position = index;
+        }
+        self.sync_list_state();
+    }
+
+    pub fn toggle_detail_pane(&mut self) {
+        self.show_detail_pane = !self.show_detail_pane;
+        if self.show_detail_pane && self.detail_pane_width() == 0 {
+            self.set_warning("Terminal too narrow for the detail pane".to_string());
+        }
+    }
+
+    /// Width of the detail pane: two fifths of the terminal within limits, or
+    /// 0 when it is hidden, the split view takes its place or the list would
+    /// get too narrow.
+    pub fn detail_pane_width(&self) -> u16 {
+        if !self.show_detail_pane
+            || self.split_view.is_some()
+            || self.terminal_width < MIN_WIDTH_FOR_DETAIL_PANE
+        {
+            return 0;
+        }
+        (self.terminal_width * 2 / 5).clamp(30, 60)
+    }
+
+    /// Width of the split view's second day: half the terminal, or 0 when
+    /// the split is closed or the terminal too narrow.
+    pub fn split_pane_width(&self) -> u16 {
+        if self.split_view.is_none() || self.terminal_width < MIN_WIDTH_FOR_SPLIT_VIEW {
+            return 0;
+        }
+        self.terminal_width / 2
+    }
+
+    /// Width of the list including its borders.
+    pub fn list_area_width(&self) -> u16 {
+        self.terminal_width - self.detail_pane_width() - self.split_pane_width()
+    }
+
+    /// Width available to item rows inside the list borders, minus the gutter.
+    pub fn list_content_width(&self) -> usize {
+        (self.list_area_width().saturating_sub(2) as usize).saturating_sub(self.gutter_width())
+    }
+
+    /// Load plugin metadata and attachments for the selected item if the
+    /// detail pane shows a different one.
+    pub fn refresh_detail_metadata(&mut self) {
+        let Some(id) = self.selected_item().map(|item| item.id) else {
+            self.detail_metadata = None;
+            self.detail_attachments = None;
+            return;
+        };
+        if self.detail_metadata.as_ref().is_none_or(|(shown, _)| *shown != id) {
+            let metadata = get_all_todo_metadata(&id).unwrap_or_else(|e| {
+                debug!("Failed to load metadata for {}: {}", id, e);
+                Vec::new()
+            });
+            self.detail_metadata = Some((id, metadata));
+        }
+        if self.detail_attachments.as_ref().is_none_or(|(shown, _)| *shown != id) {
+            let attachments = list_attachments(&id).unwrap_or_else(|e| {
+                debug!("Failed to load attachments for {}: {}", id, e);
+                Vec::new()
+            });
+            self.detail_attachments = Some((id, attachments));
+        }
+    }
+
+    /// The query to highlight: the prompt while typing, else the confirmed search.
+    pub fn search_pattern(&self) -> Option<&str> {
+        let pattern = if self.mode == Mode::Search {
+            Some(self.edit_buffer.as_str())
+        } else {
+            self.search_query.as_deref()
+        };
+        pattern.filter(|p| !p.is_empty())
+    }
+
+    /// Indices of items whose content or description match the search pattern.
+    pub fn search_matches(&self) -> Vec<usize> {
+        let Some(pattern) = self.search_pattern() else {
+            return Vec::new();
+        };
+        self.todo_list
+            .items
+            .iter()
+            .enumerate()
+            .filter(|(_, item)| {
+                search::contains(&item.content, pattern)
+                    || item
+                        .description
+                        .as_deref()
+                        .is_some_and(|desc| search::contains(desc, pattern))
+            })
+            .map(|(idx, _)| idx)
+            .collect()
+    }
+
+    /// Open the search prompt.
+    pub fn start_search(&mut self) {
+        self.search_origin = self.cursor_position;
+        self.edit_buffer.clear();
+        self.edit_cursor_pos = 0;
+        self.mode = Mode::Search;
+    }
+
+    /// Move to the first match at or after where the search started.
+    pub fn update_incremental_search(&mut self) {
+        let matches = self.search_matches();
+        let target = matches
+            .iter()
+            .find(|&&idx| idx >= self.search_origin)
+            .or(matches.first())
+            .copied()
+            .unwrap_or(self.search_origin);
+        self.jump_to_item(target);
+    }
+
+    /// Keep the typed query highlighted and return to navigate mode.
+    pub fn confirm_search(&mut self) {
+        let query = std::mem::take(&mut self.edit_buffer);
+        self.edit_cursor_pos = 0;
+        self.mode = Mode::Navigate;
+        if query.is_empty() {
+            self.search_query = None;
+            return;
+        }
+        self.search_query = Some(query);
+        let count = self.search_matches().len();
+        if count == 0 {
+            self.set_warning(format!("Pattern not found: {}", self.search_query.as_deref().unwrap_or("")));
+            self.search_query = None;
+        }
+    }
+
+    /// Leave the prompt and go back to where the search started.
+    pub fn cancel_search(&mut self) {
+        self.edit_buffer.clear();
+        self.edit_cursor_pos = 0;
+        self.mode = Mode::Navigate;
+        let origin = self.search_origin.min(self.todo_list.items.len().saturating_sub(1));
+        self.jump_to_item(origin);
+    }
+
+    /// Leave the prompt and search every date of the project for its text.
+    pub fn search_all_dates(&mut self) {
+        let query = self.edit_buffer.trim().to_string();
+        self.cancel_search();
+        if query.is_empty() {
+            return;
+        }
+        if let Err(e) = self.flush_save() {
+            self.set_error(format!("Couldn't save: {e}"));
+            return;
+        }
+        match search_todos(&query, Some(&self.current_project.name), SEARCH_RESULTS_LIMIT) {
+            Ok(hits) if hits.is_empty() => self.set_warning(format!("No todos match {query}")),
+            Ok(hits) => {
+                self.search_results = hits;
+                self.search_results_query = query;
+                self.search_results_selected = 0;
+                self.mode = Mode::SearchResults;
+            }
+            Err(e) => self.set_error(format!("Search failed: {e}")),
+        }
+    }
+
+    pub fn search_results_move(&mut self, delta: isize) {
+        let last = self.search_results.len().saturating_sub(1);
+        self.search_results_selected = self
+            .search_results_selected
+            .saturating_add_signed(delta)
+            .min(last);
+    }
+
+    /// Go to the day of the highlighted result and select the item.
+    pub fn open_search_result(&mut self) -> Result<()> {
+        self.mode = Mode::Navigate;
+        let Some(hit) = self.search_results.get(self.search_results_selected).cloned() else {
+            return Ok(());
+        };
+        if hit.date != self.viewing_date {
+            self.navigate_to_date(hit.date)?;
+        }
+        match Self::find_item_index_by_id(&self.todo_list, hit.id) {
+            Some(index) => self.jump_to_item(index),
+            None => self.set_status_message(format!("\"{}\" was archived from this day", hit.content)),
+        }
+        Ok(())
+    }
+
+    /// Drop the confirmed search and its highlights.
+    pub fn clear_search(&mut self) {
+        self.search_query = None;
+    }
+
+    /// Jump to the next (or previous) match, wrapping around the list.
+    pub fn jump_to_match(&mut self, forward: bool) {
+        let matches = self.search_matches();
+        if matches.is_empty() {
+            return;
+        }
+        let target = if forward {
+            matches.iter().find(|&&idx| idx > self.cursor_position).or(matches.first())
+        } else {
+            matches.iter().rev().find(|&&idx| idx < self.cursor_position).or(matches.last())
+        };
+        if let Some(&target) = target {
+            self.jump_to_item(target);
+            let position = matches.iter().position(|&idx| idx == target).unwrap_or(0) + 1;
+            self.set_status_message(format!("Match {}/{}", position, matches.len()));
+        }
+    }
+
+    /// Open, done and overdue item counts for the status bar. Cancelled
+    /// items count as done.
+    pub fn item_counts(&self) -> (usize, usize, usize) {
+        let items = &self.todo_list.items;
+        let done = items.iter().filter(|item| item.is_complete()).count();
+        let overdue = items.iter().filter(|item| item.is_overdue(self.today)).count();
+        (items.len() - done, done, overdue)
+    }
+
+    /// Put the cursor on the first overdue item. Returns false if there is none.
+    pub fn jump_to_first_overdue(&mut self) -> bool {
+        let today = self.today;
+        match self.todo_list.items.iter().position(|item| item.is_overdue(today)) {
+            Some(index) => {
+                self.jump_to_item(index);
+                true
+            }
+            None => false,
+        }
+    }
+
+    /// Put the cursor on `index`, expanding collapsed parents so it is visible.
+    fn jump_to_item(&mut self, index: usize) {
+        if index >= self.todo_list.items.len() {
+            return;
+        }
+        let mut parent = self.todo_list.items[index].parent_id;
+        while let Some(parent_id) = parent {
+            let Some(parent_idx) = Self::find_item_index_by_id(&self.todo_list, parent_id) else {
+                break;
+            };
+            let item = &mut self.todo_list.items[parent_idx];
+            if item.collapsed {
+                item.collapsed = false;
+                self.unsaved_changes = true;
+            }
+            parent = item.parent_id;
+        }
+        if self.filter_visible_indices().is_some_and(|visible| !visible.contains(&index)) {
+            self.show_waiting_only = false;
+            self.active_filters.clear();
+        }
+        if self.focus_range().is_some_and(|(start, end)| !(start..end).contains(&index)) {
+            self.focus_root = None;
+        }
+        self.cursor_position = index;
+        self.sync_list_state();
+    }
+
+    /// Toggle the filter that shows only items waiting on someone else.
+    pub fn toggle_waiting_filter(&mut self) {
+        self.show_waiting_only = !self.show_waiting_only;
+        if self.show_waiting_only {
+            let visible = self.todo_list.waiting_visible_indices();
+            if visible.is_empty() {
+                self.show_waiting_only = false;
+                self.set_warning("Nothing is waiting on anyone".to_string());
+                return;
+            }
+            if !visible.contains(&self.cursor_position)
+                && let Some(first) = visible.iter().min()
+            {
+                self.cursor_position = *first;
+            }
+            self.set_status_message(format!(
+                "Showing {} waiting item(s)",
+                self.todo_list.items.iter().filter(|i| i.is_waiting()).count()
+            ));
+        } else {
+            self.set_status_message("Showing all items".to_string());
+        }
+        *self.list_state.offset_mut() = 0;
+        self.sync_list_state();
+    }
+
     /// Returns the count of list items rendered (excluding hidden collapsed children,
     /// but including expanded description boxes which are separate ListItems).
     /// Used for scroll position indicator and scrollbar.
     pub fn visible_item_count(&self) -> usize {
-        let hidden = self.todo_list.build_hidden_indices();
+        let hidden = self.hidden_indices();
         let mut count = 0;
         for (i, item) in self.todo_list.items.iter().enumerate() {
             if hidden.contains(&i) {
@@ -386,7 +1293,11 @@ impl AppState {
     /// but accounting for expanded description boxes which are separate ListItems).
     /// Also adjusts scroll offset to keep selected item visible.
     pub fn sync_list_state(&mut self) {
-        let hidden_indices = self.todo_list.build_hidden_indices();
+        // An edit can take the selected item out of the filtered view
+        if self.filter_visible_indices().is_some() {
+            self.snap_cursor_to_visible();
+        }
+        let hidden_indices = self.hidden_indices();
         let mut visible_index = 0;
         for i in 0..self.cursor_position {
             if hidden_indices.contains(&i) {
@@ -403,8 +1314,7 @@ impl AppState {
             self.list_state.select(Some(visible_index));
 
             // Adjust offset to ensure selected item is visible
-            // Viewport height = terminal height - borders (2) - status bar (1)
-            let viewport_height = self.terminal_height.saturating_sub(3).max(1) as usize;
+            let viewport_height = self.list_viewport_height();
             let current_offset = self.list_state.offset();
 
             // If selected item is above viewport, scroll up
@@ -430,7 +1340,7 @@ impl AppState {
             None => return,
         };
 
-        let viewport_height = self.terminal_height.saturating_sub(3).max(1) as usize;
+        let viewport_height = self.list_viewport_height();
         let heights = self.build_visible_item_heights();
 
         if selected >= heights.len() {
@@ -471,27 +1381,51 @@ impl AppState {
     /// Each entry corresponds to one ListItem in the rendered list:
     /// either a todo item or a description box.
     fn build_visible_item_heights(&self) -> Vec<usize> {
-        let hidden = self.todo_list.build_hidden_indices();
-        let available_width = self.terminal_width.saturating_sub(2) as usize;
+        let hidden = self.hidden_indices();
         let mut heights = Vec::new();
 
-        for (idx, item) in self.todo_list.items.iter().enumerate() {
+        for (idx, height) in self.item_heights().iter().enumerate() {
             if hidden.contains(&idx) {
                 continue;
             }
-            heights.push(Self::estimate_item_line_height(item, available_width));
-            if !item.collapsed && item.description.is_some() {
-                heights.push(Self::estimate_description_line_height(item, available_width));
+            heights.push(height.row);
+            if height.description > 0 {
+                heights.push(height.description);
             }
         }
 
         heights
     }
 
+    /// Rows of every item and its expanded description, by index. Measured
+    /// again only when the list or the list width changes.
+    pub fn item_heights(&self) -> Rc<Vec<ItemHeight>> {
+        let priority_labels: Vec<&str> = self.theme.priorities.iter().map(|p| p.label.as_str()).collect();
+        let key = view_cache::key(
+            view_cache::list_hasher(&self.todo_list.items),
+            (self.list_content_width(), self.render_markdown, &self.theme.glyphs, priority_labels),
+        );
+        self.view_cache.heights(key, || {
+            self.todo_list
+                .items
+                .iter()
+                .enumerate()
+                .map(|(idx, item)| ItemHeight {
+                    row: self.measure_item_line_height(idx),
+                    description: if item.collapsed {
+                        0
+                    } else {
+                        self.description_line_height(item)
+                    },
+                })
+                .collect()
+        })
+    }
+
     /// Find the list-item-index past the last entry belonging to the
     /// expanded content of the item at the given list index.
     fn expanded_content_end_index(&self, selected_list_index: usize) -> usize {
-        let hidden = self.todo_list.build_hidden_indices();
+        let hidden = self.hidden_indices();
 
         // Map list-item index back to todo_list index
         let mut list_idx: usize = 0;
@@ -541,120 +1475,459 @@ impl AppState {
             }
         }
 
-        end
+        end
+    }
+
+    /// Checkbox glyph for the item at `idx`, from the theme's glyph set.
+    pub fn item_checkbox(&self, idx: usize) -> String {
+        let Some(item) = self.todo_list.items.get(idx) else {
+            return String::new();
+        };
+        let glyphs = &self.theme.glyphs;
+        if item.is_waiting() {
+            return glyphs.waiting.clone();
+        }
+        if item.state == TodoState::Empty && self.todo_list.has_children(idx) {
+            let (completed, _) = self.todo_list.count_children_stats(idx);
+            if completed > 0 {
+                return glyphs.partial.clone();
+            }
+        }
+        // A changing glyph would make screen readers re-read the line
+        let spinner = if self.accessible_mode { '*' } else { self.get_spinner_char() };
+        glyphs.checkbox(item.state, spinner)
+    }
+
+    /// Priority badge text for `item`, if it has a priority.
+    pub fn item_priority_badge(&self, item: &TodoItem) -> Option<String> {
+        item.priority
+            .map(|p| self.theme.glyphs.priority_badge(&self.theme.priority_style(p).label))
+    }
+
+    /// Columns before the content of the item at `idx`: indent, fold icon,
+    /// priority badge and checkbox, each followed by a space.
+    pub fn item_content_offset(&self, idx: usize) -> usize {
+        let Some(item) = self.todo_list.items.get(idx) else {
+            return 0;
+        };
+        let badge_width = self
+            .item_priority_badge(item)
+            .map_or(0, |badge| display_width(&badge) + 1);
+        item.indent_level * 2
+            + self.theme.glyphs.fold_width()
+            + badge_width
+            + display_width(&self.item_checkbox(idx))
+            + 1
+    }
+
+    /// Rows the item at `idx` takes in the list, wrapped the way the renderer
+    /// wraps it.
+    pub fn item_line_height(&self, idx: usize) -> usize {
+        self.item_heights().get(idx).map_or(0, |height| height.row)
+    }
+
+    fn measure_item_line_height(&self, idx: usize) -> usize {
+        let Some(item) = self.todo_list.items.get(idx) else {
+            return 0;
+        };
+        // Collapsed items with a description are cut to a single row
+        if item.collapsed && item.description.is_some() {
+            return 1;
+        }
+
+        let collapse_indicator = if item.collapsed && self.todo_list.has_children(idx) {
+            let (completed, total) = self.todo_list.count_children_stats(idx);
+            format!(" ({completed}/{total})")
+        } else {
+            String::new()
+        };
+        let content = format!(
+            "{}{}{}{}{}{}{}",
+            if item.pinned { PINNED_GLYPH } else { "" },
+            item.content,
+            if item.link.is_some() { LINK_GLYPH } else { "" },
+            item.estimate
+                .map(|e| format!(" ~{e}"))
+                .unwrap_or_default(),
+            item.waiting_on
+                .as_ref()
+                .map(|who| format!(" @{who}"))
+                .unwrap_or_default(),
+            item.due_date
+                .map(|d| format!(" [{}]", d.format("%Y-%m-%d")))
+                .unwrap_or_default(),
+            collapse_indicator
+        );
+
+        wrapped_line_count(
+            &content,
+            self.list_content_width().saturating_sub(self.item_content_offset(idx)),
+        )
+    }
+
+    /// Rows of the description box under an expanded item, borders included,
+    /// or 0 when it has no description.
+    pub fn description_line_height(&self, item: &TodoItem) -> usize {
+        let Some(ref desc) = item.description else {
+            return 0;
+        };
+        let box_indent_width = item.indent_level * 2 + 4;
+        let inner_width = self.list_content_width().saturating_sub(box_indent_width + 4);
+
+        let lines = if self.render_markdown {
+            crate::ui::markdown::wrap(desc, inner_width).len()
+        } else {
+            wrapped_line_count(desc, inner_width)
+        };
+        lines + 2 // + top/bottom borders
+    }
+
+    /// Sync list_state selection for when creating a new item.
+    /// This accounts for the temporary edit row that appears during new item creation.
+    pub fn sync_list_state_for_new_item(&mut self) {
+        // First get the base visible index
+        self.sync_list_state();
+
+        if !self.is_creating_new_item {
+            return;
+        }
+
+        // Get current selection
+        let current = match self.list_state.selected() {
+            Some(idx) => idx,
+            None => return,
+        };
+
+        if self.insert_above {
+            // New item appears at current position, no change needed
+            // The highlight should stay where it is
+        } else {
+            // New item appears below current item
+            // Need to increment selection to point to the new row
+            let mut offset = 1;
+
+            // If current item has expanded description, that's another ListItem between
+            if let Some(item) = self.todo_list.items.get(self.cursor_position)
+                && !item.collapsed
+                && item.description.is_some()
+            {
+                offset += 1;
+            }
+
+            self.list_state.select(Some(current + offset));
+        }
+    }
+
+    pub fn navigate_to_date(&mut self, date: NaiveDate) -> Result<()> {
+        if date > self.today {
+            return Ok(());
+        }
+        self.flush_save()?;
+        let from_date = self.todo_list.date;
+        let revision = day_revision(date, &self.current_project.name)?;
+        self.todo_list = load_todos_for_viewing_in_project(&self.current_project.name, date)?;
+        self.remember_synced(revision);
+        self.fire_day_change_event(from_date);
+        self.viewing_date = date;
+        self.cursor_position = 0;
+        self.undo_stack.clear();
+        self.unsaved_changes = false;
+        self.mode = Mode::Navigate;
+        self.edit_buffer.clear();
+        self.edit_cursor_pos = 0;
+        self.is_creating_new_item = false;
+        self.insert_above = false;
+        self.sync_list_state();
+        Ok(())
+    }
+
+    /// Open the week view on the week of the day being viewed.
+    pub fn open_week_view(&mut self) {
+        self.open_week_view_on(self.viewing_date);
+    }
+
+    /// Open the week view on the week of `date`, with `date` selected.
+    pub fn open_week_view_on(&mut self, date: NaiveDate) {
+        let start = WeekView::week_start(date);
+        self.show_week(start, (date - start).num_days() as usize);
+        self.mode = Mode::WeekView;
+    }
+
+    /// Load the seven lists of the week starting at `start`.
+    fn show_week(&mut self, start: NaiveDate, selected: usize) {
+        let days = (0..7)
+            .map(|offset| self.load_day_for_viewing(start + Duration::days(offset)))
+            .collect();
+        self.week_view = Some(WeekView {
+            start,
+            days,
+            selected,
+        });
+    }
+
+    /// Another day's list to look at, or an empty one if it has none.
+    fn load_day_for_viewing(&self, date: NaiveDate) -> TodoList {
+        // The open list may have unsaved edits
+        if date == self.todo_list.date {
+            return self.todo_list.clone();
+        }
+        load_todos_for_viewing_in_project(&self.current_project.name, date).unwrap_or_else(|e| {
+            debug!("No list for {}: {}", date, e);
+            TodoList::new(date, std::path::PathBuf::new())
+        })
+    }
+
+    /// Show the day before the one being viewed beside the list, or close
+    /// the split.
+    pub fn toggle_split_view(&mut self) {
+        if self.split_view.take().is_some() {
+            return;
+        }
+        self.split_view = Some(SplitView {
+            list: self.load_day_for_viewing(self.viewing_date - Duration::days(1)),
+            cursor: 0,
+            focused: false,
+        });
+        if self.split_pane_width() == 0 {
+            self.set_warning("Terminal too narrow for the split view".to_string());
+        }
+    }
+
+    /// Move keyboard focus between the list and the split view's other day.
+    pub fn switch_split_focus(&mut self) {
+        match &mut self.split_view {
+            Some(split) => split.focused = !split.focused,
+            None => self.set_status_message("No split open (Ctrl+w v opens one)".to_string()),
+        }
+    }
+
+    /// Whether navigation keys go to the split view's other day.
+    pub fn split_focused(&self) -> bool {
+        self.split_view.as_ref().is_some_and(|split| split.focused)
+    }
+
+    /// Show the day `days` before or after the one in the split view. Days
+    /// after today have no list, so the split stops there.
+    pub fn split_view_shift_day(&mut self, days: i64) {
+        let Some(split) = &self.split_view else {
+            return;
+        };
+        let date = (split.list.date + Duration::days(days)).min(self.today);
+        if date == split.list.date {
+            return;
+        }
+        let list = self.load_day_for_viewing(date);
+        if let Some(split) = &mut self.split_view {
+            split.list = list;
+            split.cursor = 0;
+        }
+    }
+
+    /// Move the split view's selection by `delta` rows, staying in the list.
+    pub fn split_view_move_cursor(&mut self, delta: isize) {
+        if let Some(split) = &mut self.split_view {
+            let last = split.visible_indices().len().saturating_sub(1);
+            split.cursor = split.cursor.saturating_add_signed(delta).min(last);
+        }
+    }
+
+    /// Yank the split view's selected item and its subtasks, to paste into
+    /// the list.
+    pub fn yank_from_split_view(&mut self, register: Option<char>) {
+        let Some(split) = &self.split_view else {
+            return;
+        };
+        let Some((start, end)) = split
+            .selected_index()
+            .and_then(|index| split.list.get_item_range(index).ok())
+        else {
+            return;
+        };
+        let date = split.list.date;
+        self.registers.yank(register, &split.list.items[start..end]);
+        let count = end - start;
+        let noun = if count == 1 { "item" } else { "items" };
+        self.set_status_message(format!("Yanked {count} {noun} from {}", date.format("%Y-%m-%d")));
+    }
+
+    /// Move the week view selection by `days`, loading the next or previous
+    /// week when it moves past either end.
+    pub fn week_view_move(&mut self, days: i64) {
+        let Some(week) = &self.week_view else {
+            return;
+        };
+        let date = week.selected_date() + Duration::days(days);
+        let start = WeekView::week_start(date);
+        let selected = (date - start).num_days() as usize;
+        if start == week.start {
+            if let Some(week) = &mut self.week_view {
+                week.selected = selected;
+            }
+        } else {
+            self.show_week(start, selected);
+        }
+    }
+
+    pub fn close_week_view(&mut self) {
+        self.week_view = None;
+        self.mode = Mode::Navigate;
     }
 
-    /// Estimate the line height of a todo item (how many wrapped lines).
-    fn estimate_item_line_height(item: &TodoItem, available_width: usize) -> usize {
-        let prefix_width = item.indent_level * 2 + 2 + 4; // indent + fold_icon + checkbox
-        let content_max = available_width.saturating_sub(prefix_width);
-        let content = format!(
-            "{}{}",
-            item.content,
-            item.due_date
-                .map(|d| format!(" [{}]", d.format("%Y-%m-%d")))
-                .unwrap_or_default()
-        );
+    /// Leave the week view for the single-day view of the selected day.
+    pub fn open_week_view_day(&mut self) -> Result<()> {
+        let Some(week) = &self.week_view else {
+            return Ok(());
+        };
+        let date = week.selected_date();
+        if date > self.today {
+            self.set_warning("Future days open once they arrive".to_string());
+            return Ok(());
+        }
+        self.close_week_view();
+        if date != self.viewing_date {
+            self.navigate_to_date(date)?;
+        }
+        Ok(())
+    }
+
+    /// Open the theme picker on the configured theme.
+    pub fn open_theme_picker(&mut self) {
+        use crate::config::Config;
+
+        let config = Config::load().unwrap_or_else(|e| {
+            debug!("Theme picker: could not load config: {}", e);
+            Config::default()
+        });
+        let selected = THEME_NAMES
+            .iter()
+            .position(|name| *name == config.theme)
+            .unwrap_or(0);
+        self.theme_picker = Some(ThemePicker {
+            selected,
+            original: self.theme.clone(),
+            priorities: config.priorities,
+            glyphs: config.glyphs,
+        });
+        self.mode = Mode::ThemePicker;
+    }
 
-        Self::count_wrapped_lines(&content, content_max)
+    /// Move the highlight by `delta` themes (wrapping) and preview the theme.
+    pub fn theme_picker_move(&mut self, delta: isize) {
+        let Some(picker) = &mut self.theme_picker else {
+            return;
+        };
+        let count = THEME_NAMES.len() as isize;
+        picker.selected = (picker.selected as isize + delta).rem_euclid(count) as usize;
+        self.theme = Theme::named(picker.selected_name(), &picker.priorities, &picker.glyphs);
     }
 
-    /// Estimate the line height of a description box (borders + wrapped content).
-    fn estimate_description_line_height(item: &TodoItem, available_width: usize) -> usize {
-        if let Some(ref desc) = item.description {
-            let box_indent_width = item.indent_level * 2 + 4;
-            let inner_width = available_width.saturating_sub(box_indent_width + 4);
+    /// Keep the previewed theme and save it to the config file.
+    pub fn confirm_theme_picker(&mut self) {
+        use crate::config::Config;
 
-            Self::count_wrapped_lines(desc, inner_width) + 2 // + top/bottom borders
-        } else {
-            0
+        let Some(picker) = self.theme_picker.take() else {
+            return;
+        };
+        self.mode = Mode::Navigate;
+        let name = picker.selected_name();
+        let saved = Config::load().and_then(|mut config| {
+            config.theme = name.to_string();
+            config.save()
+        });
+        match saved {
+            Ok(()) => self.set_status_message(format!("Theme: {name}")),
+            Err(e) => {
+                tracing::error!("Failed to save theme: {e}");
+                self.set_warning(format!("Theme: {name} (couldn't save to config)"));
+            }
         }
     }
 
-    /// Count wrapped lines using word-boundary wrapping to match render behavior.
-    /// This mirrors `wrap_text` in the UI module.
-    fn count_wrapped_lines(text: &str, max_width: usize) -> usize {
-        if max_width == 0 {
-            return 1;
-        }
-        let mut total_lines = 0;
-        for paragraph in text.split('\n') {
-            if paragraph.is_empty() {
-                total_lines += 1;
-                continue;
-            }
-            let mut current_width = 0;
-            let mut line_has_content = false;
-            for word in paragraph.split_whitespace() {
-                let word_len = word.len();
-                if !line_has_content {
-                    current_width = word_len;
-                    line_has_content = true;
-                } else if current_width + 1 + word_len <= max_width {
-                    current_width += 1 + word_len;
-                } else {
-                    total_lines += 1;
-                    current_width = word_len;
-                }
-            }
-            if line_has_content {
-                total_lines += 1;
-            }
+    /// Close the picker and restore the theme it was opened with.
+    pub fn cancel_theme_picker(&mut self) {
+        if let Some(picker) = self.theme_picker.take() {
+            self.theme = picker.original;
         }
-        total_lines.max(1)
+        self.mode = Mode::Navigate;
     }
 
-    /// Sync list_state selection for when creating a new item.
-    /// This accounts for the temporary edit row that appears during new item creation.
-    pub fn sync_list_state_for_new_item(&mut self) {
-        // First get the base visible index
-        self.sync_list_state();
+    /// Open the calendar on the day being viewed.
+    pub fn open_calendar(&mut self) {
+        self.calendar = Some(CalendarState {
+            selected: self.viewing_date,
+            days: HashMap::new(),
+        });
+        self.load_calendar_month();
+        self.mode = Mode::Calendar;
+    }
 
-        if !self.is_creating_new_item {
+    fn load_calendar_month(&mut self) {
+        let Some(calendar) = &mut self.calendar else {
             return;
+        };
+        let start = calendar.month_start();
+        let end = start
+            .checked_add_months(Months::new(1))
+            .map_or(start, |next| next - Duration::days(1));
+        calendar.days = day_summaries_for_project(start, end, &self.current_project.name)
+            .unwrap_or_else(|e| {
+                debug!("Calendar: could not load day summaries: {}", e);
+                HashMap::new()
+            });
+    }
+
+    /// Move the calendar selection by `days`, loading counts for a new month.
+    pub fn calendar_move_days(&mut self, days: i64) {
+        if let Some(calendar) = &self.calendar {
+            self.calendar_select(calendar.selected + Duration::days(days));
         }
+    }
 
-        // Get current selection
-        let current = match self.list_state.selected() {
-            Some(idx) => idx,
-            None => return,
+    /// Move the calendar selection by whole months, keeping the day where possible.
+    pub fn calendar_move_months(&mut self, months: i32) {
+        let Some(calendar) = &self.calendar else {
+            return;
         };
-
-        if self.insert_above {
-            // New item appears at current position, no change needed
-            // The highlight should stay where it is
+        let moved = if months >= 0 {
+            calendar.selected.checked_add_months(Months::new(months as u32))
         } else {
-            // New item appears below current item
-            // Need to increment selection to point to the new row
-            let mut offset = 1;
-
-            // If current item has expanded description, that's another ListItem between
-            if let Some(item) = self.todo_list.items.get(self.cursor_position)
-                && !item.collapsed
-                && item.description.is_some()
-            {
-                offset += 1;
-            }
+            calendar.selected.checked_sub_months(Months::new(months.unsigned_abs()))
+        };
+        if let Some(date) = moved {
+            self.calendar_select(date);
+        }
+    }
 
-            self.list_state.select(Some(current + offset));
+    pub fn calendar_select(&mut self, date: NaiveDate) {
+        let Some(calendar) = &mut self.calendar else {
+            return;
+        };
+        let month_changed = calendar.month_start() != date.with_day(1).unwrap_or(date);
+        calendar.selected = date;
+        if month_changed {
+            self.load_calendar_month();
         }
     }
 
-    pub fn navigate_to_date(&mut self, date: NaiveDate) -> Result<()> {
+    pub fn close_calendar(&mut self) {
+        self.calendar = None;
+        self.mode = Mode::Navigate;
+    }
+
+    /// Jump to the day selected in the calendar.
+    pub fn open_calendar_day(&mut self) -> Result<()> {
+        let Some(calendar) = &self.calendar else {
+            return Ok(());
+        };
+        let date = calendar.selected;
         if date > self.today {
+            self.set_warning("Future days open once they arrive".to_string());
             return Ok(());
         }
-        self.todo_list = load_todos_for_viewing_in_project(&self.current_project.name, date)?;
-        self.viewing_date = date;
-        self.cursor_position = 0;
-        self.undo_stack.clear();
-        self.unsaved_changes = false;
-        self.mode = Mode::Navigate;
-        self.edit_buffer.clear();
-        self.edit_cursor_pos = 0;
-        self.is_creating_new_item = false;
-        self.insert_above = false;
-        self.sync_list_state();
+        self.close_calendar();
+        if date != self.viewing_date {
+            self.navigate_to_date(date)?;
+        }
         Ok(())
     }
 
@@ -689,11 +1962,19 @@ impl AppState {
         );
         
         self.undo_stack
-            .push((self.todo_list.clone(), self.cursor_position));
+            .push(UndoEntry::new(self.todo_list.clone(), self.cursor_position));
     }
 
     pub fn undo(&mut self) -> bool {
-        if let Some((list, cursor)) = self.undo_stack.pop() {
+        if let Some(entry) = self.undo_stack.pop() {
+            if let Some(moved) = &entry.moved
+                && let Err(e) = self.take_back_moved(moved)
+            {
+                self.set_warning(format!("Can't undo the move to {}: {e}", moved.date.format("%Y-%m-%d")));
+                self.undo_stack.push(entry);
+                return false;
+            }
+            let UndoEntry { list, cursor, .. } = entry;
             let old_ids: Vec<String> = self.todo_list.items.iter().map(|i| i.id.to_string()).collect();
             let new_ids: Vec<String> = list.items.iter().map(|i| i.id.to_string()).collect();
             
@@ -718,12 +1999,181 @@ impl AppState {
         }
     }
 
+    /// Take the items a move put on another day back off it.
+    fn take_back_moved(&self, moved: &MovedItems) -> Result<()> {
+        use crate::storage::file::delete_todos_for_project;
+
+        let project = &self.current_project.name;
+        let moved_ids: Vec<Uuid> = moved.ids.iter().map(|(_, id)| *id).collect();
+        update_todo_list_for_project(project, moved.date, |list| {
+            list.items.retain(|item| !moved_ids.contains(&item.id));
+            list.recalculate_parent_ids();
+            Ok(())
+        })?;
+        // Saving a day left empty keeps its rows, so remove them outright;
+        // they were never there as far as the user is concerned
+        delete_todos_for_project(&moved_ids, moved.date, project)?;
+        // The items get their old ids back, and their attachments with them
+        copy_attachments(moved.ids.iter().map(|&(from, to)| (to, from)))?;
+        Ok(())
+    }
+
+    /// Undo the last `steps` changes at once. Returns false, changing
+    /// nothing, if there are fewer than that to undo.
+    ///
+    /// Steps are undone one by one so moves to other days are taken back
+    /// too; if one of those fails, the steps before it stay undone.
+    pub fn undo_steps(&mut self, steps: usize) -> bool {
+        if steps == 0 || steps > self.undo_stack.len() {
+            return false;
+        }
+        (0..steps).all(|_| self.undo())
+    }
+
+    /// When each undoable change was made and what it did, latest first.
+    pub fn undo_history(&self) -> Vec<(DateTime<Local>, String)> {
+        let afters = self
+            .undo_stack
+            .iter()
+            .skip(1)
+            .map(|entry| &entry.list)
+            .chain(std::iter::once(&self.todo_list));
+        let mut history: Vec<_> = self
+            .undo_stack
+            .iter()
+            .zip(afters)
+            .map(|(entry, after)| (entry.saved_at, describe_change(&entry.list, after)))
+            .collect();
+        history.reverse();
+        history
+    }
+
+    /// Open the undo history on the latest change.
+    pub fn open_undo_history(&mut self) {
+        if self.undo_stack.is_empty() {
+            self.set_status_message("Nothing to undo".to_string());
+            return;
+        }
+        self.undo_history_selected = 0;
+        self.mode = Mode::UndoHistory;
+    }
+
+    pub fn undo_history_move(&mut self, delta: isize) {
+        let last = self.undo_stack.len().saturating_sub(1);
+        self.undo_history_selected = self
+            .undo_history_selected
+            .saturating_add_signed(delta)
+            .min(last);
+    }
+
+    /// Open the trash of the current project.
+    pub fn open_trash(&mut self) {
+        // The trash and the restores made from it work on the saved list
+        if let Err(e) = self.flush_save() {
+            self.set_error(format!("Couldn't save: {e}"));
+            return;
+        }
+        match load_deleted_todos_for_project(&self.current_project.name) {
+            Ok(trash) if trash.is_empty() => self.set_status_message("Trash is empty".to_string()),
+            Ok(trash) => {
+                self.trash = trash;
+                self.trash_selected = 0;
+                self.mode = Mode::Trash;
+            }
+            Err(e) => self.set_error(format!("Failed to load the trash: {e}")),
+        }
+    }
+
+    /// Indices into `trash` of the items shown as rows: those whose parent
+    /// isn't in the trash too. Each row stands for its deleted subtasks as well.
+    pub fn trash_rows(&self) -> Vec<usize> {
+        let ids: HashSet<Uuid> = self.trash.iter().map(|deleted| deleted.item.id).collect();
+        (0..self.trash.len())
+            .filter(|&i| !self.trash[i].item.parent_id.is_some_and(|parent| ids.contains(&parent)))
+            .collect()
+    }
+
+    /// Ids of the trash item at `index` and of its subtasks in the trash.
+    pub fn trash_subtree(&self, index: usize) -> Vec<Uuid> {
+        let mut ids = vec![self.trash[index].item.id];
+        let mut added = true;
+        while added {
+            added = false;
+            for deleted in &self.trash {
+                let item = &deleted.item;
+                if !ids.contains(&item.id) && item.parent_id.is_some_and(|parent| ids.contains(&parent)) {
+                    ids.push(item.id);
+                    added = true;
+                }
+            }
+        }
+        ids
+    }
+
+    pub fn trash_move(&mut self, delta: isize) {
+        let last = self.trash_rows().len().saturating_sub(1);
+        self.trash_selected = self.trash_selected.saturating_add_signed(delta).min(last);
+    }
+
+    /// Put the highlighted trash item and its subtasks back on their list.
+    pub fn restore_from_trash(&mut self) -> Result<()> {
+        let Some(&index) = self.trash_rows().get(self.trash_selected) else {
+            return Ok(());
+        };
+        let ids = self.trash_subtree(index);
+        let DeletedTodo { item, date, .. } = self.trash[index].clone();
+        restore_deleted_todos_for_project(&ids, &self.current_project.name)?;
+
+        if date == self.todo_list.date {
+            // Saving drops live rows the open list doesn't have, so reload it.
+            // Undo snapshots lack the restored items as well and go with it.
+            let cursor = self.cursor_position;
+            self.navigate_to_date(date)?;
+            self.cursor_position = cursor;
+            self.clamp_cursor();
+            self.sync_list_state();
+            if !self.is_readonly() {
+                self.save_list()?;
+            }
+            self.set_status_message(format!("Restored \"{}\"", item.content));
+        } else {
+            self.set_status_message(format!("Restored \"{}\" to {}", item.content, date.format("%Y-%m-%d")));
+        }
+        self.reload_trash();
+        Ok(())
+    }
+
+    /// Delete the highlighted trash item and its subtasks for good.
+    pub fn purge_from_trash(&mut self) -> Result<()> {
+        let Some(&index) = self.trash_rows().get(self.trash_selected) else {
+            return Ok(());
+        };
+        let ids = self.trash_subtree(index);
+        let content = self.trash[index].item.content.clone();
+        purge_deleted_todos_for_project(&ids, &self.current_project.name)?;
+        self.set_status_message(format!("Deleted \"{content}\" for good"));
+        self.reload_trash();
+        Ok(())
+    }
+
+    /// Reload the open trash, closing it once it's empty.
+    fn reload_trash(&mut self) {
+        self.trash = load_deleted_todos_for_project(&self.current_project.name).unwrap_or_default();
+        self.mode = if self.trash.is_empty() { Mode::Navigate } else { Mode::Trash };
+        self.trash_move(0);
+    }
+
     pub fn move_cursor_up(&mut self) {
         if self.cursor_position > 0 {
+            let original = self.cursor_position;
             self.cursor_position -= 1;
             while self.cursor_position > 0 && self.is_item_hidden(self.cursor_position) {
                 self.cursor_position -= 1;
             }
+            // Only reachable with a view filter active: nothing visible above
+            if self.is_item_hidden(self.cursor_position) {
+                self.cursor_position = original;
+            }
         }
         self.sync_list_state();
     }
@@ -731,6 +2181,7 @@ impl AppState {
     pub fn move_cursor_down(&mut self) {
         if !self.todo_list.items.is_empty() && self.cursor_position < self.todo_list.items.len() - 1
         {
+            let original = self.cursor_position;
             self.cursor_position += 1;
             while self.cursor_position < self.todo_list.items.len() - 1
                 && self.is_item_hidden(self.cursor_position)
@@ -743,6 +2194,9 @@ impl AppState {
                     self.cursor_position -= 1;
                 }
             }
+            if self.is_item_hidden(self.cursor_position) {
+                self.cursor_position = original;
+            }
         }
         self.sync_list_state();
     }
@@ -751,6 +2205,12 @@ impl AppState {
         if index >= self.todo_list.items.len() {
             return false;
         }
+        if self.filter_visible_indices().is_some_and(|visible| !visible.contains(&index)) {
+            return true;
+        }
+        if self.focus_range().is_some_and(|(start, end)| !(start..end).contains(&index)) {
+            return true;
+        }
         let mut current_indent = self.todo_list.items[index].indent_level;
         if current_indent == 0 {
             return false;
@@ -770,6 +2230,35 @@ impl AppState {
         false
     }
 
+    /// Quit, or ask first if there are unsaved changes or an edit in progress.
+    pub fn request_quit(&mut self) {
+        if let Err(e) = self.flush_save() {
+            self.set_error(format!("Couldn't save: {e}"));
+        }
+        if self.unsaved_changes || self.has_pending_edit() {
+            self.mode_before_quit = self.mode;
+            self.mode = Mode::ConfirmQuit;
+        } else {
+            self.should_quit = true;
+        }
+    }
+
+    /// Whether the item or description editor holds text that differs from
+    /// the item.
+    pub fn has_pending_edit(&self) -> bool {
+        match self.mode {
+            Mode::Edit if self.is_creating_new_item => !self.edit_buffer.trim().is_empty(),
+            Mode::Edit => self
+                .selected_item()
+                .is_some_and(|item| item.content != self.edit_buffer),
+            Mode::EditDescription => {
+                let text = self.desc_buffer.join("\n");
+                self.desc_original.as_deref().unwrap_or("") != text.trim()
+            }
+            _ => false,
+        }
+    }
+
     pub fn selected_item(&self) -> Option<&TodoItem> {
         self.todo_list.items.get(self.cursor_position)
     }
@@ -859,7 +2348,7 @@ impl AppState {
         if lines.is_empty() {
             None
         } else {
-            Some(clean_selection_text(&lines.join("\n")))
+            Some(clean_selection_text(&self.theme.glyphs.canonicalize(&lines.join("\n"))))
         }
     }
 
@@ -877,6 +2366,179 @@ impl AppState {
         })
     }
 
+    /// Rows a subtree operation acts on: the selected item and its children,
+    /// or in visual mode the whole selection. The end is exclusive.
+    pub fn target_range(&self) -> Option<(usize, usize)> {
+        if self.todo_list.items.is_empty() {
+            return None;
+        }
+        let selection = if self.mode == Mode::Visual {
+            self.get_selection_range()
+        } else {
+            None
+        };
+        let (start, last) = selection.unwrap_or((self.cursor_position, self.cursor_position));
+        let (_, end) = self.todo_list.get_item_range(last).ok()?;
+        Some((start, end))
+    }
+
+    /// Yank the selected item and its children, or in visual mode the whole
+    /// selection, into `register`. Returns the yanked range.
+    pub fn yank(&mut self, register: Option<char>) -> Option<(usize, usize)> {
+        let (start, end) = self.target_range()?;
+        self.registers.yank(register, &self.todo_list.items[start..end]);
+        Some((start, end))
+    }
+
+    /// Check off the visual selection and its children, or reopen it when
+    /// everything is already done.
+    pub fn toggle_selection_state(&mut self) {
+        let Some((start, end)) = self.target_range() else {
+            return;
+        };
+        let all_done = self.todo_list.items[start..end]
+            .iter()
+            .all(|item| item.state == TodoState::Checked);
+        let target_state = if all_done {
+            TodoState::Empty
+        } else {
+            TodoState::Checked
+        };
+
+        self.save_undo();
+        let now = chrono::Utc::now();
+        for index in start..end {
+            if self.todo_list.items[index].state == target_state {
+                continue;
+            }
+            self.todo_list.items[index].state = target_state;
+            self.todo_list.items[index].modified_at = now;
+            let activity = if all_done {
+                Activity::Reopened
+            } else {
+                Activity::Completed
+            };
+            self.record_activity(activity, index);
+            if let Some(todo) = self.todo_to_ffi(index) {
+                self.fire_event(if all_done {
+                    FfiEvent::OnModify {
+                        todo,
+                        field_changed: FfiFieldChange::State,
+                    }
+                } else {
+                    FfiEvent::OnComplete { todo }
+                });
+            }
+        }
+        self.unsaved_changes = true;
+        if self.sink_completed {
+            // The selection's rows no longer hold the same items
+            self.clear_selection();
+            self.mode = Mode::Navigate;
+            self.sink_completed_items();
+        }
+    }
+
+    /// Give every selected row the priority that follows the first row's.
+    pub fn cycle_selection_priority(&mut self) {
+        let Some((start, last)) = self.get_selection_range() else {
+            return;
+        };
+        let levels = self.theme.priority_levels();
+        let priority = self.todo_list.items[start].priority.cycle_priority(levels);
+
+        self.save_undo();
+        let now = chrono::Utc::now();
+        for item in &mut self.todo_list.items[start..=last] {
+            item.priority = priority;
+            item.modified_at = now;
+        }
+        let label = priority
+            .map(|p| self.theme.priority_style(p).label)
+            .unwrap_or_else(|| "None".to_string());
+        self.set_status_message(format!("Priority: {} ({} items)", label, last - start + 1));
+        self.unsaved_changes = true;
+    }
+
+    /// Open the due date prompt for the visual selection or the selected item.
+    pub fn open_due_prompt(&mut self) {
+        let targets: Vec<usize> = match self.get_selection_range() {
+            Some((start, last)) if self.mode == Mode::Visual => (start..=last).collect(),
+            _ if self.selected_item().is_some() => vec![self.cursor_position],
+            _ => return,
+        };
+        self.edit_buffer = match targets.as_slice() {
+            [index] => self.todo_list.items[*index]
+                .due_date
+                .map(|due| due.format("%Y-%m-%d").to_string())
+                .unwrap_or_default(),
+            _ => String::new(),
+        };
+        self.edit_cursor_pos = self.edit_buffer.len();
+        self.due_targets = targets;
+        self.clear_selection();
+        self.mode = Mode::EditDue;
+    }
+
+    /// Set or clear the due date on the prompt's rows. Returns how many changed.
+    pub fn apply_due_date(&mut self, due: Option<NaiveDate>) -> usize {
+        let targets: Vec<usize> = std::mem::take(&mut self.due_targets)
+            .into_iter()
+            .filter(|&index| index < self.todo_list.items.len())
+            .collect();
+        if targets.is_empty() {
+            return 0;
+        }
+
+        self.save_undo();
+        let now = chrono::Utc::now();
+        for &index in &targets {
+            self.todo_list.items[index].due_date = due;
+            self.todo_list.items[index].modified_at = now;
+            if let Some(todo) = self.todo_to_ffi(index) {
+                self.fire_event(FfiEvent::OnModify {
+                    todo,
+                    field_changed: FfiFieldChange::DueDate,
+                });
+            }
+        }
+        self.unsaved_changes = true;
+        targets.len()
+    }
+
+    /// Paste `register` below the selected item's subtree, or above the item,
+    /// at the item's indent level. Returns how many items were pasted.
+    pub fn paste(&mut self, register: Option<char>, above: bool) -> Option<usize> {
+        let (position, indent) = match self.selected_item() {
+            None => (0, 0),
+            Some(item) if above => (self.cursor_position, item.indent_level),
+            Some(item) => {
+                let indent = item.indent_level;
+                let (_, end) = self.todo_list.get_item_range(self.cursor_position).ok()?;
+                (end, indent)
+            }
+        };
+        let items = self.registers.paste(register, indent)?;
+        let count = items.len();
+
+        self.save_undo();
+        self.todo_list.items.splice(position..position, items);
+        self.todo_list.recalculate_parent_ids();
+        for index in position..position + count {
+            if self.todo_list.items[index].indent_level == indent {
+                self.record_activity(Activity::Added, index);
+            }
+            self.fire_event(FfiEvent::OnAdd {
+                todo: (&self.todo_list.items[index]).into(),
+                source: FfiEventSource::Manual,
+            });
+        }
+        self.cursor_position = position;
+        self.unsaved_changes = true;
+        self.sync_list_state();
+        Some(count)
+    }
+
     pub fn is_selected(&self, index: usize) -> bool {
         if let Some((start, end)) = self.get_selection_range() {
             index >= start && index <= end
@@ -899,7 +2561,9 @@ impl AppState {
     }
 
     pub fn move_to_parent(&mut self) {
-        if let Some(parent_idx) = self.find_parent_index(self.cursor_position) {
+        if let Some(parent_idx) = self.find_parent_index(self.cursor_position)
+            && self.focus_range().is_none_or(|(start, _)| parent_idx >= start)
+        {
             self.cursor_position = parent_idx;
         }
     }
@@ -917,8 +2581,9 @@ impl AppState {
         }
 
         let date = self.todo_list.date;
-        let new_list = load_todo_list_for_project(&self.current_project.name, date)?;
+        let (new_list, revision) = load_todo_list_with_revision(&self.current_project.name, date)?;
         self.todo_list = new_list;
+        self.remember_synced(revision);
         self.clamp_cursor();
         self.unsaved_changes = false;
         Ok(())
@@ -1045,14 +2710,21 @@ impl AppState {
     }
 
     pub fn set_status_message(&mut self, message: String) {
-        self.status_message = Some((message, Instant::now()));
+        self.toasts.push(ToastLevel::Info, message);
     }
 
-    pub fn clear_expired_status_message(&mut self) {
-        if let Some((_, time)) = &self.status_message
-            && time.elapsed().as_secs() > 3 {
-                self.status_message = None;
-            }
+    /// Show a message for something that did not happen, e.g. a refused action.
+    pub fn set_warning(&mut self, message: String) {
+        self.toasts.push(ToastLevel::Warn, message);
+    }
+
+    /// Show a failure; it stays up longer than other messages.
+    pub fn set_error(&mut self, message: String) {
+        self.toasts.push(ToastLevel::Error, message);
+    }
+
+    pub fn clear_expired_toasts(&mut self) {
+        self.toasts.clear_expired();
     }
 
     pub fn check_plugin_result(&mut self) {
@@ -1068,8 +2740,12 @@ impl AppState {
                                     message: "Plugin generated no items".to_string(),
                                 });
                             } else {
-                                self.plugins_modal_state =
-                                    Some(PluginsModalState::Preview { items });
+                                let source = match self.plugins_modal_state.take() {
+                                    Some(PluginsModalState::Executing { plugin_name }) => plugin_name,
+                                    _ => "plugin".to_string(),
+                                };
+                                self.close_plugins_modal();
+                                self.open_plan_review(items, source);
                             }
                         }
                         Some(PluginResultSource::PluginSubState) | None => {
@@ -1078,7 +2754,12 @@ impl AppState {
                                     message: "Plugin generated no items".to_string(),
                                 });
                             } else {
-                                self.plugin_state = Some(PluginSubState::Preview { items });
+                                let source = match self.plugin_state.take() {
+                                    Some(PluginSubState::Executing { plugin_name }) => plugin_name,
+                                    _ => "plugin".to_string(),
+                                };
+                                self.close_plugin_menu();
+                                self.open_plan_review(items, source);
                             }
                         }
                     }
@@ -1124,11 +2805,11 @@ impl AppState {
     pub fn check_version_update(&mut self) {
         if let Ok(result) = self.version_check_rx.try_recv() {
             // Handle app update
-            if let Some(app_update) = result.app_update {
-                if app_update.is_newer {
-                    let new_version = app_update.latest_version.clone();
-                    self.new_version_available = Some(new_version.clone());
-                }
+            if let Some(app_update) = result.app_update
+                && app_update.is_newer
+            {
+                let new_version = app_update.latest_version.clone();
+                self.new_version_available = Some(new_version.clone());
             }
 
             // Handle plugin updates
@@ -1532,6 +3213,10 @@ impl AppState {
 
     pub fn get_spinner_char(&self) -> char {
         const SPINNER_FRAMES: [char; 8] = ['⠋', '⠙', '⠹', '⠸', '⠼', '⠴', '⠦', '⠧'];
+        const ASCII_FRAMES: [char; 4] = ['|', '/', '-', '\\'];
+        if self.accessible_mode {
+            return ASCII_FRAMES[self.spinner_frame % ASCII_FRAMES.len()];
+        }
         SPINNER_FRAMES[self.spinner_frame]
     }
 
@@ -1561,6 +3246,12 @@ impl AppState {
         }
 
         self.unsaved_changes = true;
+        let activity = if target_state == TodoState::Checked {
+            Activity::Completed
+        } else {
+            Activity::Reopened
+        };
+        self.record_activity(activity, self.cursor_position);
 
         // Fire event for state change on the main item (not all children)
         if let Some(ffi_item) = self.todo_to_ffi(self.cursor_position) {
@@ -1575,39 +3266,58 @@ impl AppState {
             self.fire_event(event);
         }
 
+        self.sink_completed_items();
         true
     }
 
     /// Cycle the current item's state with undo support.
     /// Returns true if a change was made.
     pub fn cycle_current_item_state(&mut self) -> bool {
-        if self.selected_item().is_some() {
-            self.save_undo();
-            if let Some(item) = self.selected_item_mut() {
-                item.cycle_state();
-                self.unsaved_changes = true;
+        match self.selected_item() {
+            Some(item) => self.set_current_item_state(item.state.cycle()),
+            None => false,
+        }
+    }
 
-                // Fire event for state change
-                if let Some(ffi_item) = self.todo_to_ffi(self.cursor_position) {
-                    let event = if self.todo_list.items[self.cursor_position].state.is_complete() {
-                        FfiEvent::OnComplete { todo: ffi_item }
-                    } else {
-                        FfiEvent::OnModify {
-                            todo: ffi_item,
-                            field_changed: FfiFieldChange::State,
-                        }
-                    };
-                    self.fire_event(event);
-                }
+    /// Set the current item's state with undo support, leaving its children
+    /// alone. Returns true if the state changed.
+    pub fn set_current_item_state(&mut self, state: TodoState) -> bool {
+        if self.selected_item().is_none_or(|item| item.state == state) {
+            return false;
+        }
+        self.save_undo();
+        if let Some(item) = self.selected_item_mut() {
+            let was_complete = item.state.is_complete();
+            item.set_state(state);
+            let activity = match item.state {
+                TodoState::Checked => Some(Activity::Completed),
+                TodoState::Cancelled => Some(Activity::Cancelled),
+                _ => was_complete.then_some(Activity::Reopened),
+            };
+            self.unsaved_changes = true;
+            if let Some(activity) = activity {
+                self.record_activity(activity, self.cursor_position);
+            }
 
-                return true;
+            // Fire event for state change
+            if let Some(ffi_item) = self.todo_to_ffi(self.cursor_position) {
+                let event = if self.todo_list.items[self.cursor_position].state.is_complete() {
+                    FfiEvent::OnComplete { todo: ffi_item }
+                } else {
+                    FfiEvent::OnModify {
+                        todo: ffi_item,
+                        field_changed: FfiFieldChange::State,
+                    }
+                };
+                self.fire_event(event);
             }
         }
-        false
+        self.sink_completed_items();
+        true
     }
 
     /// Cycle the current item's priority with undo support.
-    /// Cycles: None -> P0 -> P1 -> P2 -> None
+    /// Cycles: None -> P0 -> P1 -> ... -> None over the configured levels
     pub fn cycle_priority(&mut self) {
         if self.is_readonly() {
             return;
@@ -1615,20 +3325,169 @@ impl AppState {
 
         if self.selected_item().is_some() {
             self.save_undo();
+            let levels = self.theme.priority_levels();
             if let Some(item) = self.selected_item_mut() {
-                item.priority = item.priority.cycle_priority();
+                item.priority = item.priority.cycle_priority(levels);
                 item.modified_at = chrono::Utc::now();
 
-                let priority_str = item
-                    .priority
-                    .map(|p| p.to_string())
+                let priority = item.priority;
+                let priority_str = priority
+                    .map(|p| self.theme.priority_style(p).label)
                     .unwrap_or_else(|| "None".to_string());
-                self.status_message = Some((format!("Priority: {}", priority_str), std::time::Instant::now()));
+                self.set_status_message(format!("Priority: {}", priority_str));
                 self.unsaved_changes = true;
             }
         }
     }
 
+    /// Pin or unpin the current item. Pinned items stay above their siblings.
+    pub fn toggle_pin(&mut self) {
+        if self.is_readonly() {
+            return;
+        }
+
+        let Some(item) = self.selected_item() else {
+            return;
+        };
+        let id = item.id;
+        let pinned = !item.pinned;
+
+        self.save_undo();
+        if let Some(item) = self.selected_item_mut() {
+            item.pinned = pinned;
+            item.modified_at = chrono::Utc::now();
+        }
+        self.todo_list.float_pinned();
+        if let Some(index) = Self::find_item_index_by_id(&self.todo_list, id) {
+            self.cursor_position = index;
+        }
+        self.sync_list_state();
+        self.unsaved_changes = true;
+        self.set_status_message(if pinned { "Pinned" } else { "Unpinned" }.to_string());
+    }
+
+    /// Open the review for a generated plan. Accepted items go to the end of the list.
+    pub fn open_plan_review(&mut self, items: Vec<TodoItem>, source: impl Into<String>) {
+        self.open_plan_review_under(items, source, None);
+    }
+
+    fn open_plan_review_under(
+        &mut self,
+        items: Vec<TodoItem>,
+        source: impl Into<String>,
+        parent_id: Option<Uuid>,
+    ) {
+        if items.is_empty() {
+            return;
+        }
+        self.plan_review = Some(PlanReview::new(items, source, parent_id));
+        self.mode = Mode::PlanReview;
+    }
+
+    /// Open the review for pasted text if it reads as an outline of two or
+    /// more items. The plan is added as subtasks of the selected item.
+    /// Returns whether the text was taken as a plan.
+    pub fn open_pasted_plan(&mut self, text: &str) -> bool {
+        if self.is_readonly() {
+            return false;
+        }
+        let items = parse_plan(text);
+        if items.len() < 2 {
+            return false;
+        }
+        let parent_id = self.selected_item().map(|item| item.id);
+        self.open_plan_review_under(items, "paste", parent_id);
+        true
+    }
+
+    /// Switch between adding the plan under the selected item and at the top level.
+    pub fn toggle_plan_review_target(&mut self) {
+        let selected = self.selected_item().map(|item| item.id);
+        if let Some(review) = &mut self.plan_review {
+            review.parent_id = match review.parent_id {
+                Some(_) => None,
+                None => selected,
+            };
+        }
+    }
+
+    pub fn cancel_plan_review(&mut self) {
+        self.plan_review = None;
+        self.mode = Mode::Navigate;
+    }
+
+    /// Insert the accepted part of the plan and close the review.
+    /// Returns the number of items added.
+    pub fn apply_plan_review(&mut self) -> usize {
+        let Some(review) = self.plan_review.take() else {
+            return 0;
+        };
+        self.mode = Mode::Navigate;
+
+        let parent_index = review
+            .parent_id
+            .and_then(|id| Self::find_item_index_by_id(&self.todo_list, id));
+        let (insert_at, base_indent) = match parent_index {
+            Some(index) => {
+                let (_, end) = self.todo_list.get_item_range(index).unwrap_or((index, index + 1));
+                (end, self.todo_list.items[index].indent_level + 1)
+            }
+            None => (self.todo_list.items.len(), 0),
+        };
+
+        let items = review.accepted_items(base_indent);
+        let count = items.len();
+        if count == 0 {
+            self.set_status_message("Nothing accepted".to_string());
+            return 0;
+        }
+
+        self.save_undo();
+        for item in &items {
+            self.activity.record(Activity::Added, item.content.clone());
+        }
+        self.todo_list.items.splice(insert_at..insert_at, items);
+        self.todo_list.recalculate_parent_ids();
+        self.jump_to_item(insert_at);
+        self.unsaved_changes = true;
+        self.set_status_message(format!("Added {} item(s) from {}", count, review.source));
+        count
+    }
+
+    /// Log `activity` for the item at `index` in the session timeline.
+    pub fn record_activity(&mut self, activity: Activity, index: usize) {
+        if let Some(item) = self.todo_list.items.get(index) {
+            self.activity.record(activity, item.content.clone());
+        }
+    }
+
+    /// Open the `:` prompt with a fresh completion list.
+    pub fn start_command(&mut self) {
+        self.edit_buffer.clear();
+        self.edit_cursor_pos = 0;
+        self.command_candidates = command::candidates(self);
+        self.command_selected = 0;
+        self.mode = Mode::Command;
+    }
+
+    /// Completions for what has been typed at the `:` prompt, best first.
+    pub fn command_completions(&self) -> Vec<&Completion> {
+        command::complete(&self.command_candidates, &self.edit_buffer)
+    }
+
+    pub fn close_command(&mut self) {
+        self.edit_buffer.clear();
+        self.edit_cursor_pos = 0;
+        self.command_candidates.clear();
+        self.command_selected = 0;
+        self.mode = Mode::Navigate;
+    }
+
+    pub fn open_timeline(&mut self) {
+        self.show_timeline = true;
+        self.timeline_scroll = 0;
+    }
+
     /// Toggle collapse state of the current item if it's collapsible.
     /// Returns true if a change was made.
     pub fn toggle_current_item_collapse(&mut self) -> bool {
@@ -1714,8 +3573,70 @@ impl AppState {
         false
     }
 
+    /// Indices of items that have children.
+    fn parent_indices(&self) -> Vec<usize> {
+        let items = &self.todo_list.items;
+        (0..items.len().saturating_sub(1))
+            .filter(|&i| items[i + 1].indent_level > items[i].indent_level)
+            .collect()
+    }
+
+    /// Set `collapsed` on the given items as one undo step.
+    /// Returns how many items changed.
+    fn set_collapsed(&mut self, indices: &[usize], collapsed: bool) -> usize {
+        let changed: Vec<usize> = indices
+            .iter()
+            .copied()
+            .filter(|&i| self.todo_list.items[i].collapsed != collapsed)
+            .collect();
+        if changed.is_empty() {
+            return 0;
+        }
+        self.save_undo();
+        for &i in &changed {
+            self.todo_list.items[i].collapsed = collapsed;
+        }
+        self.unsaved_changes = true;
+        changed.len()
+    }
+
+    /// Collapse every parent (vim's `zM`). The cursor moves up to the
+    /// top-level item it was under.
+    pub fn collapse_all(&mut self) {
+        let parents = self.parent_indices();
+        if self.set_collapsed(&parents, true) > 0 {
+            let hidden = self.todo_list.build_hidden_indices();
+            while hidden.contains(&self.cursor_position) {
+                self.move_to_parent();
+            }
+            self.sync_list_state();
+        }
+    }
+
+    /// Expand every parent (vim's `zR`).
+    pub fn expand_all(&mut self) {
+        let parents = self.parent_indices();
+        if self.set_collapsed(&parents, false) > 0 {
+            self.sync_list_state();
+        }
+    }
+
+    /// Expand the collapsed parents that are currently visible, revealing one
+    /// more level of the hierarchy (vim's `zr`).
+    pub fn expand_one_level(&mut self) {
+        let hidden = self.todo_list.build_hidden_indices();
+        let parents: Vec<usize> = self
+            .parent_indices()
+            .into_iter()
+            .filter(|i| !hidden.contains(i))
+            .collect();
+        if self.set_collapsed(&parents, false) > 0 {
+            self.sync_list_state();
+        }
+    }
+
     /// Sort todos by priority (P0 first, then P1, P2, None last).
-    /// Children remain grouped under their parent.
+    /// Pinned items stay on top and children remain grouped under their parent.
     pub fn sort_by_priority(&mut self) {
         if self.is_readonly() {
             return;
@@ -1725,7 +3646,21 @@ impl AppState {
         self.todo_list.sort_by_priority();
         self.cursor_position = 0; // Reset cursor to top after sort
         self.sync_list_state();
-        self.status_message = Some(("Sorted by priority".to_string(), std::time::Instant::now()));
+        self.set_status_message("Sorted by priority".to_string());
+    }
+
+    /// Sort todos by due date, soonest first and undated items last.
+    /// Pinned items stay on top and children remain grouped under their parent.
+    pub fn sort_by_due(&mut self) {
+        if self.is_readonly() {
+            return;
+        }
+
+        self.save_undo();
+        self.todo_list.sort_by_due();
+        self.cursor_position = 0;
+        self.sync_list_state();
+        self.set_status_message("Sorted by due date".to_string());
     }
 
     /// Open the rollover modal with the given pending items
@@ -1749,6 +3684,238 @@ impl AppState {
         self.pending_rollover.is_some()
     }
 
+    /// Schedule a save for the changes made so far. Changes that follow
+    /// before it is due go into the same save. Called after each event.
+    pub fn schedule_save(&mut self) {
+        if self.unsaved_changes && self.save_due.is_none() {
+            self.save_due = Some(Instant::now() + SAVE_DEBOUNCE);
+        }
+    }
+
+    /// Save pending changes right away. Called before anything that replaces
+    /// the list or reads the day back from the database.
+    pub fn flush_save(&mut self) -> Result<()> {
+        self.save_due = None;
+        if self.unsaved_changes {
+            self.save_list()?;
+            self.unsaved_changes = false;
+            self.last_save_time = Some(Instant::now());
+            if let Err(e) = self.save_undo_history() {
+                tracing::warn!("Failed to save undo history: {e:#}");
+            }
+        }
+        Ok(())
+    }
+
+    /// Save once a scheduled save is due. Called every tick, which also
+    /// schedules changes made outside key handling (plugin hooks, paste).
+    pub fn check_save_due(&mut self) {
+        self.schedule_save();
+        if self.save_due.is_some_and(|due| Instant::now() >= due)
+            && let Err(e) = self.flush_save()
+        {
+            // A stale day waits on the user in its own prompt
+            if stale_day(&e).is_none() {
+                tracing::error!("Failed to save: {e}");
+                self.set_error(format!("Couldn't save: {e}"));
+            }
+        }
+    }
+
+    /// Save the list without overwriting saves made elsewhere (the API, the
+    /// CLI, another TUI) since the day was loaded. When the day has moved on,
+    /// the list is saved anyway if nothing would be lost by it; otherwise the
+    /// changes wait in `stale_save` for the user, and this fails with
+    /// `StaleDay`.
+    pub fn save_list(&mut self) -> Result<()> {
+        let project = self.current_project.name.clone();
+        if let Some(stale) = &self.stale_save {
+            let revision = stale.revision;
+            self.open_stale_save();
+            return Err(StaleDay { revision }.into());
+        }
+        self.todo_list.fill_short_ids();
+        let Some(synced) = self
+            .synced
+            .take()
+            .filter(|synced| synced.project == project && synced.date == self.todo_list.date)
+        else {
+            save_todo_list_for_project(&self.todo_list, &project)?;
+            return self.resync();
+        };
+
+        let error = match save_todo_list_if_current(&self.todo_list, &project, synced.revision) {
+            Ok(revision) => {
+                self.remember_synced(revision);
+                return Ok(());
+            }
+            Err(e) if stale_day(&e).is_some() => e,
+            Err(e) => {
+                self.synced = Some(synced);
+                return Err(e);
+            }
+        };
+        let (theirs, revision) = load_todo_list_with_revision(&project, self.todo_list.date)?;
+        let stale = StaleSave::new(&project, self.todo_list.clone(), synced.list, theirs, revision);
+        if !stale.needs_decision() {
+            let revision = save_todo_list_if_current(&self.todo_list, &project, revision)?;
+            self.remember_synced(revision);
+            return Ok(());
+        }
+        tracing::info!(project = %project, date = %stale.date, "List changed elsewhere since it was loaded");
+        self.stale_save = Some(stale);
+        self.open_stale_save();
+        Err(error)
+    }
+
+    /// Show the waiting stale save, unless the user is in the middle of
+    /// something else; the next save tries again.
+    fn open_stale_save(&mut self) {
+        if self.mode == Mode::Navigate {
+            self.mode = Mode::StaleSave;
+        }
+    }
+
+    /// Settle the open stale save with the list `resolve` picks, as one undo
+    /// step. The pick is saved against the day as saved elsewhere.
+    pub fn resolve_stale_save(&mut self, resolve: impl Fn(&StaleSave) -> TodoList) -> Result<()> {
+        let Some(stale) = self.stale_save.take() else {
+            return Ok(());
+        };
+        self.mode = Mode::Navigate;
+        self.save_undo();
+        self.todo_list = resolve(&stale);
+        self.synced = Some(SyncedDay {
+            project: stale.project_name,
+            date: stale.date,
+            revision: stale.revision,
+            list: stale.theirs,
+        });
+        self.unsaved_changes = true;
+        self.clamp_cursor();
+        self.flush_save()?;
+        self.set_status_message("Saved alongside the changes made elsewhere".to_string());
+        Ok(())
+    }
+
+    /// Note the list on screen as the day at `revision`.
+    pub fn remember_synced(&mut self, revision: u64) {
+        self.synced = Some(SyncedDay {
+            project: self.current_project.name.clone(),
+            date: self.todo_list.date,
+            revision,
+            list: self.todo_list.clone(),
+        });
+    }
+
+    /// Note the list on screen as the day as it is now. For after writes the
+    /// list already has, such as a rollover.
+    pub fn resync(&mut self) -> Result<()> {
+        let revision = day_revision(self.todo_list.date, &self.current_project.name)?;
+        self.remember_synced(revision);
+        Ok(())
+    }
+
+    /// Look for edits made to today's daily file outside totui. Called every
+    /// tick, to catch what the file watcher misses, so it only checks every
+    /// few seconds, and only when nothing is waiting to be saved. Edits to a file whose day hasn't changed in the
+    /// database since are taken in right away; otherwise the user decides.
+    pub fn check_daily_file(&mut self) {
+        if self.last_file_check.elapsed() >= FILE_CHECK_INTERVAL {
+            self.check_daily_file_now();
+        }
+    }
+
+    /// Called when the file watcher sees a daily file change. Changes to the
+    /// day on screen are checked right away instead of on the next tick.
+    pub fn daily_file_changed(&mut self, path: &Path) {
+        let file_path = &self.todo_list.file_path;
+        let same_file = path == file_path
+            || matches!(
+                (fs::canonicalize(path), fs::canonicalize(file_path)),
+                (Ok(a), Ok(b)) if a == b
+            );
+        if same_file {
+            self.check_daily_file_now();
+        }
+    }
+
+    fn check_daily_file_now(&mut self) {
+        if self.mode != Mode::Navigate || self.unsaved_changes || self.is_readonly() {
+            return;
+        }
+        self.last_file_check = Instant::now();
+
+        let conflict = match find_conflict(&self.current_project.name, self.todo_list.date) {
+            Ok(Some(conflict)) => conflict,
+            Ok(None) => return,
+            Err(e) => {
+                tracing::warn!("Failed to check the daily file: {e}");
+                return;
+            }
+        };
+        if self.file_conflict_dismissed.as_ref() == Some(&conflict.file_hash) {
+            return;
+        }
+
+        if conflict.database_changed {
+            self.file_conflict = Some(conflict);
+            self.mode = Mode::FileConflict;
+        } else {
+            match self.apply_file_resolution(&conflict, conflict.merge()) {
+                Ok(()) => self.set_status_message("Loaded changes made to the daily file".to_string()),
+                Err(e) => self.set_error(format!("Failed to load the daily file: {e}")),
+            }
+        }
+    }
+
+    /// Settle the open file conflict with the list `resolve` picks, as one
+    /// undo step.
+    pub fn resolve_file_conflict(&mut self, resolve: impl Fn(&DailyFileConflict) -> TodoList) -> Result<()> {
+        let Some(conflict) = self.file_conflict.take() else {
+            return Ok(());
+        };
+        self.mode = Mode::Navigate;
+        self.save_undo();
+        self.apply_file_resolution(&conflict, resolve(&conflict))?;
+        self.set_status_message("Daily file and database are in step again".to_string());
+        Ok(())
+    }
+
+    /// Leave the open file conflict for now: the file keeps its edits and
+    /// totui's changes go to the database only, until the file changes again.
+    pub fn dismiss_file_conflict(&mut self) {
+        if let Some(conflict) = self.file_conflict.take() {
+            self.file_conflict_dismissed = Some(conflict.file_hash);
+        }
+        self.mode = Mode::Navigate;
+        self.set_warning("Daily file left as it is; changes are saved to the database only".to_string());
+    }
+
+    fn apply_file_resolution(&mut self, conflict: &DailyFileConflict, list: TodoList) -> Result<()> {
+        conflict.resolve(&list)?;
+        self.todo_list = list;
+        self.file_conflict_dismissed = None;
+        self.clamp_cursor();
+        self.resync()
+    }
+
+    /// Back up the database when the last backup is older than the
+    /// configured interval. Called every tick, so it only compares times
+    /// until a backup is due.
+    pub fn check_backup_due(&mut self) {
+        let now = Local::now().naive_local();
+        if !crate::storage::backup::is_due(&self.backup_config, self.last_backup, now) {
+            return;
+        }
+        // Failures aren't retried until the next interval either
+        self.last_backup = Some(now);
+        if let Err(e) = crate::storage::backup::backup_now(&self.backup_config) {
+            tracing::warn!("Database backup failed: {e:#}");
+            self.set_warning(format!("Database backup failed: {e}"));
+        }
+    }
+
     /// Called every UI tick. If the wall-clock day has rolled past the
     /// currently loaded list and the user is idle, either open the rollover
     /// modal or auto-execute, depending on `auto_rollover_pref`.
@@ -1765,6 +3932,10 @@ impl AppState {
         if today <= self.todo_list.date {
             return;
         }
+        if let Err(e) = self.flush_save() {
+            tracing::error!("Failed to save before midnight rollover: {e}");
+            return;
+        }
 
         // The wall-clock day has crossed past the loaded list. Advancing the
         // view to today must happen regardless of preference — only whether
@@ -1827,7 +3998,13 @@ impl AppState {
         ) {
             Ok(new_list) => {
                 let new_date = new_list.date;
+                let from_date = self.todo_list.date;
                 self.todo_list = new_list;
+                if let Err(e) = self.resync() {
+                    tracing::warn!("Failed to read the day's revision: {e}");
+                }
+                self.fire_rollover_event(source_date, item_count);
+                self.fire_day_change_event(from_date);
                 self.viewing_date = new_date;
                 self.today = new_date;
                 self.cursor_position = 0;
@@ -1841,7 +4018,7 @@ impl AppState {
             }
             Err(e) => {
                 tracing::error!("Auto-rollover failed: {e}");
-                self.set_status_message(format!("Auto-rollover failed: {e}"));
+                self.set_error(format!("Auto-rollover failed: {e}"));
             }
         }
     }
@@ -1850,11 +4027,11 @@ impl AppState {
     /// Used when no incomplete items exist to roll over.
     fn silently_advance_to_today(&mut self) -> anyhow::Result<()> {
         let today = Local::now().date_naive();
-        let new_list = crate::storage::file::load_todo_list_for_project(
-            &self.current_project.name,
-            today,
-        )?;
+        let (new_list, revision) = load_todo_list_with_revision(&self.current_project.name, today)?;
+        let from_date = self.todo_list.date;
         self.todo_list = new_list;
+        self.remember_synced(revision);
+        self.fire_day_change_event(from_date);
         self.viewing_date = today;
         self.today = today;
         self.cursor_position = 0;
@@ -1876,6 +4053,7 @@ impl AppState {
         self.project_state = Some(ProjectSubState::Selecting {
             projects,
             selected_index,
+            query: String::new(),
         });
         self.mode = Mode::ProjectSelect;
     }
@@ -1893,10 +4071,7 @@ impl AppState {
     /// Switch to a different project
     pub fn switch_project(&mut self, project: Project) -> Result<()> {
         // Save any unsaved changes first to the CURRENT project before switching
-        if self.unsaved_changes {
-            crate::storage::file::save_todo_list_for_project(&self.todo_list, &self.current_project.name)?;
-            self.unsaved_changes = false;
-        }
+        self.flush_save()?;
 
         // Check for rollover candidates in the new project BEFORE loading the list
         // (same pattern as startup in main.rs)
@@ -1904,10 +4079,16 @@ impl AppState {
 
         // Load the new project's todo list
         let today = Local::now().date_naive();
-        let new_list = load_todo_list_for_project(&project.name, today)?;
+        let (new_list, revision) = load_todo_list_with_revision(&project.name, today)?;
 
-        self.current_project = project;
+        let from_project = std::mem::replace(&mut self.current_project, project);
         self.todo_list = new_list;
+        self.remember_synced(revision);
+        self.fire_event(FfiEvent::OnProjectSwitch {
+            from_project: from_project.name.into(),
+            to_project: self.current_project.name.clone().into(),
+            date: today.format("%Y-%m-%d").to_string().into(),
+        });
         self.viewing_date = today;
         self.today = today;
         self.cursor_position = 0;
@@ -1937,14 +4118,18 @@ impl AppState {
             .collect();
 
         if projects.is_empty() {
-            self.set_status_message("No other projects to move to".to_string());
+            self.set_warning("No other projects to move to".to_string());
             return;
         }
 
+        let Some((item_index, item_end)) = self.target_range() else {
+            return;
+        };
         self.move_to_project_state = Some(MoveToProjectSubState::Selecting {
             projects,
             selected_index: 0,
-            item_index: self.cursor_position,
+            item_index,
+            item_end,
         });
         self.mode = Mode::MoveToProject;
     }
@@ -1952,6 +4137,7 @@ impl AppState {
     /// Close the move-to-project modal
     pub fn close_move_to_project_modal(&mut self) {
         self.move_to_project_state = None;
+        self.clear_selection();
         self.mode = Mode::Navigate;
     }
 
@@ -1989,46 +4175,143 @@ impl AppState {
     pub fn execute_move_to_project(&mut self, dest_project: &Project) -> Result<usize> {
         use crate::storage::file::{load_todo_list_for_project, save_todo_list_for_project};
 
-        let item_index = match &self.move_to_project_state {
-            Some(MoveToProjectSubState::Selecting { item_index, .. }) => *item_index,
+        let (start, end) = match &self.move_to_project_state {
+            Some(MoveToProjectSubState::Selecting { item_index, item_end, .. }) => (*item_index, *item_end),
             None => return Err(anyhow::anyhow!("No move in progress")),
         };
-
-        // Get the range of the item and its children
-        let (start, end) = self.todo_list.get_item_range(item_index)?;
+        if start >= end || end > self.todo_list.items.len() {
+            return Err(anyhow::anyhow!("Nothing to move"));
+        }
         let items_to_move: Vec<crate::todo::TodoItem> = self.todo_list.items[start..end].to_vec();
         let count = items_to_move.len();
+        let mut new_ids = Vec::new();
 
         // Load destination project's todo list (for today)
         let today = chrono::Local::now().date_naive();
         let mut dest_list = load_todo_list_for_project(&dest_project.name, today)?;
 
-        // Normalize indent levels: make the moved item's root indent 0
-        let base_indent = items_to_move[0].indent_level;
+        // Normalize indent levels: make the shallowest moved item's indent 0
+        let base_indent = items_to_move.iter().map(|item| item.indent_level).min().unwrap_or(0);
         let mut normalized_items: Vec<crate::todo::TodoItem> = items_to_move
             .into_iter()
             .map(|mut item| {
                 item.indent_level = item.indent_level.saturating_sub(base_indent);
-                item.id = uuid::Uuid::new_v4();  // New IDs for destination
+                let id = uuid::Uuid::new_v4();  // New IDs for destination
+                new_ids.push((item.id, id));
+                item.id = id;
                 item.parent_id = None;  // Will be recalculated
                 item
             })
             .collect();
 
-        // Append to destination list
-        dest_list.items.append(&mut normalized_items);
-        dest_list.recalculate_parent_ids();
+        // Append to destination list
+        dest_list.items.append(&mut normalized_items);
+        dest_list.recalculate_parent_ids();
+
+        // Save destination list
+        save_todo_list_for_project(&dest_list, &dest_project.name)?;
+        copy_attachments(new_ids)?;
+        self.record_activity(Activity::Moved(format!("project {}", dest_project.name)), start);
+
+        // Remove from source list
+        self.save_undo();
+        self.todo_list.remove_item_range(start, end)?;
+        self.clamp_cursor();
+        self.unsaved_changes = true;
+
+        Ok(count)
+    }
+
+    /// Move the selected item and its subtree to another day's list.
+    ///
+    /// The destination list is saved right away; the caller saves the current
+    /// list. Fires OnDelete for the original item and OnAdd for its copy.
+    pub fn move_current_item_to_date(&mut self, date: NaiveDate) -> Result<usize> {
+        use crate::storage::file::load_todo_list_for_project;
+
+        if self.todo_list.items.is_empty() {
+            return Err(anyhow::anyhow!("Nothing to move"));
+        }
+        if date == self.todo_list.date {
+            return Err(anyhow::anyhow!("Item is already on {}", date.format("%Y-%m-%d")));
+        }
+
+        let (start, end) = self.todo_list.get_item_range(self.cursor_position)?;
+        let mut dest_list = load_todo_list_for_project(&self.current_project.name, date)?;
+
+        // Same treatment as moving between projects: fresh IDs and a root at indent 0
+        let base_indent = self.todo_list.items[start].indent_level;
+        let mut moved: Vec<TodoItem> = self.todo_list.items[start..end]
+            .iter()
+            .cloned()
+            .map(|mut item| {
+                item.indent_level = item.indent_level.saturating_sub(base_indent);
+                item.id = Uuid::new_v4();
+                item.parent_id = None;
+                item
+            })
+            .collect();
+        let count = moved.len();
+        let dest_root = dest_list.items.len();
+        let moved_ids: Vec<(Uuid, Uuid)> = self.todo_list.items[start..end]
+            .iter()
+            .zip(&moved)
+            .map(|(from, to)| (from.id, to.id))
+            .collect();
+
+        dest_list.items.append(&mut moved);
+        dest_list.recalculate_parent_ids();
+        save_todo_list_for_project(&dest_list, &self.current_project.name)?;
+        copy_attachments(moved_ids.iter().copied())?;
+
+        self.fire_event(FfiEvent::OnDelete {
+            todo: (&self.todo_list.items[start]).into(),
+        });
+        self.fire_event(FfiEvent::OnAdd {
+            todo: (&dest_list.items[dest_root]).into(),
+            source: FfiEventSource::Manual,
+        });
+        self.record_activity(Activity::Moved(date.format("%Y-%m-%d").to_string()), start);
+
+        self.save_undo();
+        if let Some(entry) = self.undo_stack.last_mut() {
+            entry.moved = Some(MovedItems { date, ids: moved_ids });
+        }
+        self.todo_list.remove_item_range(start, end)?;
+        self.clamp_cursor();
+        self.unsaved_changes = true;
+
+        Ok(count)
+    }
+
+    /// Archive the selected item and its subtree immediately.
+    ///
+    /// Only finished subtrees can be archived. The working list is saved first
+    /// so the archive receives the latest version of each item.
+    pub fn archive_current_item(&mut self) -> Result<usize> {
+        use crate::storage::file::archive_todos_by_id_for_project;
 
-        // Save destination list
-        save_todo_list_for_project(&dest_list, &dest_project.name)?;
+        if self.todo_list.items.is_empty() {
+            return Err(anyhow::anyhow!("Nothing to archive"));
+        }
+
+        let (start, end) = self.todo_list.get_item_range(self.cursor_position)?;
+        if !self.todo_list.items[start..end].iter().all(|item| item.is_complete()) {
+            return Err(anyhow::anyhow!("Only finished items can be archived"));
+        }
+
+        self.save_list()?;
+
+        let ids: Vec<Uuid> = self.todo_list.items[start..end].iter().map(|item| item.id).collect();
+        archive_todos_by_id_for_project(&ids, self.todo_list.date, &self.current_project.name)?;
+        self.record_activity(Activity::Archived, start);
 
-        // Remove from source list
         self.save_undo();
         self.todo_list.remove_item_range(start, end)?;
         self.clamp_cursor();
         self.unsaved_changes = true;
 
-        Ok(count)
+        Ok(ids.len())
     }
 
     /// Fire an event to all subscribed plugins.
@@ -2088,16 +4371,19 @@ impl AppState {
             // For hooks, we deliberately skip the snapshot.
             self.in_hook_apply = true;
 
+            // The user's pending changes are saved first, so the history
+            // credits only the hook's changes to the plugin
+            if let Err(e) = self.flush_save() {
+                tracing::warn!(error = %e, "Failed to save before hook commands");
+            }
+
             let mut executor =
                 crate::plugin::command_executor::CommandExecutor::new(result.plugin_name.clone());
 
             match executor.execute_batch(result.commands, &mut self.todo_list) {
                 Ok(_) => {
                     // Save immediately to persist plugin changes
-                    if let Err(e) = crate::storage::file::save_todo_list_for_project(
-                        &self.todo_list,
-                        &self.current_project.name,
-                    ) {
+                    if let Err(e) = with_plugin_origin(&result.plugin_name, || self.save_list()) {
                         tracing::warn!(
                             plugin = %result.plugin_name,
                             error = %e,
@@ -2139,6 +4425,38 @@ impl AppState {
         };
         self.fire_event(event);
     }
+
+    /// Fire OnRollover after `item_count` items were rolled over from
+    /// `from_date` to the list now shown.
+    pub fn fire_rollover_event(&self, from_date: NaiveDate, item_count: usize) {
+        self.fire_event(FfiEvent::OnRollover {
+            project_name: self.current_project.name.clone().into(),
+            from_date: from_date.format("%Y-%m-%d").to_string().into(),
+            to_date: self.todo_list.date.format("%Y-%m-%d").to_string().into(),
+            item_count: item_count as u32,
+        });
+    }
+
+    /// Fire OnDayChange if the list now shown is of another day than
+    /// `from_date`.
+    pub fn fire_day_change_event(&self, from_date: NaiveDate) {
+        if from_date == self.todo_list.date {
+            return;
+        }
+        self.fire_event(FfiEvent::OnDayChange {
+            project_name: self.current_project.name.clone().into(),
+            from_date: from_date.format("%Y-%m-%d").to_string().into(),
+            to_date: self.todo_list.date.format("%Y-%m-%d").to_string().into(),
+        });
+    }
+
+    /// Fire OnShutdown. Called once on exit, after the last save.
+    pub fn fire_shutdown_event(&self) {
+        self.fire_event(FfiEvent::OnShutdown {
+            project_name: self.current_project.name.clone().into(),
+            date: self.todo_list.date.format("%Y-%m-%d").to_string().into(),
+        });
+    }
 }
 
 /// Clean up raw screen-captured text by stripping TUI decorations.
@@ -2333,6 +4651,70 @@ mod tests {
         )
     }
 
+    #[test]
+    fn test_set_state_directly() {
+        let mut state = make_test_state();
+        state.todo_list.add_item("Task".to_string());
+
+        assert!(state.set_current_item_state(TodoState::Checked));
+        assert!(state.todo_list.items[0].completed_at.is_some());
+        // Already done: nothing changes and no undo step is added
+        let undo_depth = state.undo_stack.len();
+        assert!(!state.set_current_item_state(TodoState::Checked));
+        assert_eq!(state.undo_stack.len(), undo_depth);
+
+        assert!(state.set_current_item_state(TodoState::Question));
+        assert!(state.todo_list.items[0].completed_at.is_none());
+        state.undo();
+        assert_eq!(state.todo_list.items[0].state, TodoState::Checked);
+    }
+
+    #[test]
+    #[serial_test::serial]
+    fn test_saves_over_a_day_changed_elsewhere_ask_first() {
+        use crate::storage::file::{load_todo_list_for_project, save_todo_list_for_project};
+
+        let temp_dir = tempfile::TempDir::new().unwrap();
+        std::fs::create_dir_all(temp_dir.path().join(".to-tui")).unwrap();
+        unsafe {
+            std::env::set_var("HOME", temp_dir.path());
+        }
+        crate::storage::database::init_database().unwrap();
+        let mut state = make_test_state();
+        state.todo_list = load_todo_list_for_project("default", state.todo_list.date).unwrap();
+        state.todo_list.add_item("Milk".to_string());
+        state.unsaved_changes = true;
+        state.flush_save().unwrap();
+
+        // The API adds an item while Milk is being checked off here
+        let mut theirs = load_todo_list_for_project("default", state.todo_list.date).unwrap();
+        theirs.add_item("Eggs".to_string());
+        save_todo_list_for_project(&theirs, "default").unwrap();
+        state.set_current_item_state(TodoState::Checked);
+        state.unsaved_changes = true;
+
+        let error = state.flush_save().unwrap_err();
+        assert!(stale_day(&error).is_some());
+        assert_eq!(state.mode, Mode::StaleSave);
+        assert!(state.unsaved_changes);
+
+        state.resolve_stale_save(StaleSave::merge).unwrap();
+        assert_eq!(state.mode, Mode::Navigate);
+        let saved = load_todo_list_for_project("default", state.todo_list.date).unwrap();
+        let contents: Vec<_> = saved.items.iter().map(|item| (item.content.as_str(), item.state)).collect();
+        assert_eq!(contents, vec![("Milk", TodoState::Checked), ("Eggs", TodoState::Empty)]);
+
+        // Moving an item to the trash elsewhere loses nothing here
+        let mut theirs = saved.clone();
+        crate::storage::soft_delete_todos_for_project(&[theirs.items[1].id], theirs.date, "default").unwrap();
+        theirs.items.remove(1);
+        save_todo_list_for_project(&theirs, "default").unwrap();
+        state.todo_list.items.remove(1);
+        state.unsaved_changes = true;
+        state.flush_save().unwrap();
+        assert_eq!(state.mode, Mode::Navigate);
+    }
+
     fn make_test_state_for_date(date: chrono::NaiveDate) -> AppState {
         use crate::keybindings::KeybindingCache;
         use crate::plugin::{PluginActionRegistry, PluginLoader};
@@ -2540,15 +4922,15 @@ mod tests {
         state.dismiss_upgrade_session();
 
         // Verify state after dismissal
-        assert_eq!(state.session_dismissed_upgrade, true);
-        assert_eq!(state.show_upgrade_prompt, false);
+        assert!(state.session_dismissed_upgrade);
+        assert!(!state.show_upgrade_prompt);
         assert_eq!(state.mode, Mode::Navigate);
 
         // Simulate another check - should NOT auto-show because session dismissed
         state.mode = Mode::Navigate;
         let should_show = !state.session_dismissed_upgrade
             && state.skipped_version.as_ref() != state.new_version_available.as_ref();
-        assert_eq!(should_show, false, "Should not auto-show after session dismiss");
+        assert!(!should_show, "Should not auto-show after session dismiss");
     }
 
     #[test]
@@ -3014,4 +5396,806 @@ mod tests {
         assert_eq!(strip_outer_borders("││content││"), "content");
         assert_eq!(strip_outer_borders("│content│█"), "content");
     }
+
+    #[test]
+    fn test_waiting_filter_hides_and_skips_other_items() {
+        let mut state = make_test_state();
+        for content in ["Mine", "Delegated", "Also mine", "Also delegated"] {
+            state.todo_list.add_item(content.to_string());
+        }
+        state.todo_list.items[1].waiting_on = Some("alice".to_string());
+        state.todo_list.items[3].waiting_on = Some("bob".to_string());
+
+        state.toggle_waiting_filter();
+        assert!(state.show_waiting_only);
+        assert_eq!(state.cursor_position, 1);
+        assert_eq!(*state.hidden_indices(), HashSet::from([0, 2]));
+
+        state.move_cursor_down();
+        assert_eq!(state.cursor_position, 3);
+        state.move_cursor_up();
+        assert_eq!(state.cursor_position, 1);
+        state.move_cursor_up();
+        assert_eq!(state.cursor_position, 1);
+
+        state.toggle_waiting_filter();
+        assert!(state.hidden_indices().is_empty());
+    }
+
+    #[test]
+    fn test_waiting_filter_stays_off_without_delegated_items() {
+        let mut state = make_test_state();
+        state.todo_list.add_item("Mine".to_string());
+
+        state.toggle_waiting_filter();
+        assert!(!state.show_waiting_only);
+    }
+
+    #[test]
+    fn test_hide_completed_filter_moves_cursor_and_skips_hidden_items() {
+        let mut state = make_test_state();
+        state.todo_list.add_item("Open".to_string());
+        state.todo_list.add_item("Done".to_string());
+        state.todo_list.add_item("Also open".to_string());
+        state.todo_list.items[1].state = crate::todo::TodoState::Checked;
+        state.cursor_position = 1;
+
+        state.toggle_filter(Filter::HideCompleted);
+        assert_eq!(state.cursor_position, 2);
+        assert!(state.hidden_indices().contains(&1));
+        state.move_cursor_up();
+        assert_eq!(state.cursor_position, 0);
+        assert_eq!(state.filter_summary().as_deref(), Some("hide done"));
+
+        // Completing the selected item takes it out of view
+        state.todo_list.items[0].state = crate::todo::TodoState::Checked;
+        state.sync_list_state();
+        assert_eq!(state.cursor_position, 2);
+
+        state.toggle_filter(Filter::HideCompleted);
+        assert!(state.active_filters.is_empty());
+        assert!(state.hidden_indices().is_empty());
+    }
+
+    #[test]
+    fn test_filter_matching_nothing_is_not_applied() {
+        let mut state = make_test_state();
+        state.todo_list.add_item("Open".to_string());
+
+        state.toggle_filter(Filter::Priority(crate::todo::Priority::P0));
+        assert!(state.active_filters.is_empty());
+    }
+
+    #[test]
+    fn test_state_changes_are_logged_in_timeline() {
+        use crate::app::activity::Activity;
+
+        let mut state = make_test_state();
+        state.todo_list.add_item("Write report".to_string());
+
+        state.toggle_current_item_state();
+        state.toggle_current_item_state();
+        state.cycle_current_item_state(); // [ ] -> [x]
+        state.cycle_current_item_state(); // [x] -> [*]
+
+        let logged: Vec<_> = state.activity.entries().map(|e| e.activity.clone()).collect();
+        assert_eq!(
+            logged,
+            vec![
+                Activity::Completed,
+                Activity::Reopened,
+                Activity::Completed,
+                Activity::Reopened,
+            ]
+        );
+        assert_eq!(state.activity.entries().next().unwrap().content, "Write report");
+    }
+
+    #[test]
+    fn test_week_start_is_monday() {
+        let sunday = NaiveDate::from_ymd_opt(2026, 10, 18).unwrap();
+        let monday = NaiveDate::from_ymd_opt(2026, 10, 12).unwrap();
+        assert_eq!(WeekView::week_start(sunday), monday);
+        assert_eq!(WeekView::week_start(monday), monday);
+
+        let week = WeekView {
+            start: monday,
+            days: Vec::new(),
+            selected: 6,
+        };
+        assert_eq!(week.selected_date(), sunday);
+    }
+
+    #[test]
+    fn test_toggle_pin_moves_item_to_top_and_follows_cursor() {
+        let mut state = make_test_state();
+        state.todo_list.add_item("First".to_string());
+        state.todo_list.add_item("Second".to_string());
+        state.cursor_position = 1;
+
+        state.toggle_pin();
+        assert_eq!(state.todo_list.items[0].content, "Second");
+        assert!(state.todo_list.items[0].pinned);
+        assert_eq!(state.cursor_position, 0);
+
+        state.toggle_pin();
+        assert!(!state.todo_list.items[0].pinned);
+        assert_eq!(state.todo_list.items[0].content, "Second");
+    }
+
+    #[test]
+    fn test_incremental_search_jumps_and_cancel_restores_cursor() {
+        let mut state = make_test_state();
+        state.todo_list.add_item("Write report".to_string());
+        state.todo_list.add_item("Call plumber".to_string());
+        state.todo_list.add_item("Review Report draft".to_string());
+        state.todo_list.items[1].description = Some("ask about the report".to_string());
+        state.cursor_position = 2;
+
+        state.start_search();
+        state.edit_buffer = "report".to_string();
+        state.update_incremental_search();
+        assert_eq!(state.search_matches(), vec![0, 1, 2]);
+        assert_eq!(state.cursor_position, 2);
+
+        state.cancel_search();
+        assert_eq!(state.mode, Mode::Navigate);
+        assert_eq!(state.cursor_position, 2);
+        assert!(state.search_pattern().is_none());
+    }
+
+    #[test]
+    fn test_confirmed_search_cycles_through_matches() {
+        let mut state = make_test_state();
+        state.todo_list.add_item("Fix bug".to_string());
+        state.todo_list.add_item("Lunch".to_string());
+        state.todo_list.add_item("Bug triage".to_string());
+
+        state.start_search();
+        state.edit_buffer = "bug".to_string();
+        state.update_incremental_search();
+        state.confirm_search();
+        assert_eq!(state.search_query.as_deref(), Some("bug"));
+        assert_eq!(state.cursor_position, 0);
+
+        state.jump_to_match(true);
+        assert_eq!(state.cursor_position, 2);
+        state.jump_to_match(true);
+        assert_eq!(state.cursor_position, 0);
+        state.jump_to_match(false);
+        assert_eq!(state.cursor_position, 2);
+
+        state.clear_search();
+        assert!(state.search_matches().is_empty());
+    }
+
+    #[test]
+    fn test_search_expands_collapsed_parent() {
+        let mut state = make_test_state();
+        state.todo_list.add_item("Parent".to_string());
+        state.todo_list.add_item_with_indent("Hidden needle".to_string(), 1);
+        state.todo_list.recalculate_parent_ids();
+        state.todo_list.items[0].collapsed = true;
+
+        state.start_search();
+        state.edit_buffer = "needle".to_string();
+        state.update_incremental_search();
+
+        assert_eq!(state.cursor_position, 1);
+        assert!(!state.todo_list.items[0].collapsed);
+    }
+
+    #[test]
+    fn test_pasted_plan_is_added_under_selected_item() {
+        let mut state = make_test_state();
+        state.todo_list.add_item("Release".to_string());
+        state.todo_list.add_item_with_indent("Existing step".to_string(), 1);
+        state.todo_list.add_item("Other".to_string());
+        state.todo_list.recalculate_parent_ids();
+        state.todo_list.items[0].collapsed = true;
+
+        assert!(!state.open_pasted_plan("just one line"));
+        assert!(state.open_pasted_plan("- Tag\n  - Push tag\n- Announce"));
+        assert_eq!(state.mode, Mode::PlanReview);
+        state.plan_review.as_mut().unwrap().cursor = 2; // Announce
+        state.plan_review.as_mut().unwrap().toggle_selected();
+
+        assert_eq!(state.apply_plan_review(), 2);
+        let items: Vec<_> = state
+            .todo_list
+            .items
+            .iter()
+            .map(|i| (i.indent_level, i.content.as_str()))
+            .collect();
+        assert_eq!(
+            items,
+            vec![
+                (0, "Release"),
+                (1, "Existing step"),
+                (1, "Tag"),
+                (2, "Push tag"),
+                (0, "Other"),
+            ]
+        );
+        assert_eq!(state.todo_list.items[2].parent_id, Some(state.todo_list.items[0].id));
+        assert_eq!(state.cursor_position, 2);
+        assert!(!state.todo_list.items[0].collapsed);
+        assert_eq!(state.mode, Mode::Navigate);
+    }
+
+    #[test]
+    fn test_plan_review_target_toggles_to_end_of_list() {
+        let mut state = make_test_state();
+        state.todo_list.add_item("Release".to_string());
+        state.todo_list.add_item("Other".to_string());
+
+        assert!(state.open_pasted_plan("1. First\n2. Second"));
+        state.toggle_plan_review_target();
+        state.apply_plan_review();
+
+        let last = &state.todo_list.items[3];
+        assert_eq!((last.indent_level, last.content.as_str()), (0, "Second"));
+    }
+
+    #[test]
+    fn test_short_id_gutter_narrows_list() {
+        let mut state = make_test_state();
+        state.terminal_width = 80;
+        state.todo_list.add_item("Mine".to_string());
+        assert_eq!(state.list_content_width(), 78);
+
+        state.toggle_short_ids();
+        assert_eq!(state.short_id_gutter_width(), 3);
+        assert_eq!(state.list_content_width(), 75);
+    }
+
+    #[test]
+    fn test_go_to_line_counts_visible_items() {
+        let mut state = make_test_state();
+        state.terminal_width = 80;
+        state.todo_list.add_item("Parent".to_string());
+        state.todo_list.add_item_with_indent("Hidden".to_string(), 1);
+        state.todo_list.add_item("Second".to_string());
+        state.todo_list.add_item("Third".to_string());
+        state.todo_list.items[0].collapsed = true;
+
+        state.go_to_line(2);
+        assert_eq!(state.cursor_position, 2);
+        state.go_to_line(usize::MAX);
+        assert_eq!(state.cursor_position, 3);
+        state.go_to_line(1);
+        assert_eq!(state.cursor_position, 0);
+
+        assert_eq!(state.line_number_width(), 0);
+        state.toggle_line_numbers();
+        assert_eq!(state.line_number_width(), 2);
+        assert_eq!(state.list_content_width(), 76);
+    }
+
+    #[test]
+    fn test_completed_items_sink_below_open_siblings() {
+        let mut state = make_test_state();
+        state.todo_list.add_item("First".to_string());
+        state.todo_list.add_item_with_indent("First child".to_string(), 1);
+        state.todo_list.add_item("Second".to_string());
+        state.todo_list.add_item("Third".to_string());
+        state.todo_list.items[3].pinned = true;
+        state.sink_completed = true;
+
+        state.toggle_current_item_state();
+        let contents: Vec<_> = state.todo_list.items.iter().map(|i| i.content.as_str()).collect();
+        assert_eq!(contents, vec!["Third", "Second", "First", "First child"]);
+        // The cursor stays on its row, now the next open item
+        assert_eq!(state.cursor_position, 0);
+    }
+
+    #[test]
+    fn test_collapse_all_and_expand_levels() {
+        let mut state = make_test_state();
+        state.todo_list.add_item("Root".to_string());
+        state.todo_list.add_item_with_indent("Child".to_string(), 1);
+        state.todo_list.add_item_with_indent("Grandchild".to_string(), 2);
+        state.todo_list.add_item("Leaf".to_string());
+        state.cursor_position = 2;
+
+        state.collapse_all();
+        let collapsed: Vec<bool> = state.todo_list.items.iter().map(|i| i.collapsed).collect();
+        assert_eq!(collapsed, vec![true, true, false, false]);
+        assert_eq!(state.cursor_position, 0);
+
+        state.expand_one_level();
+        let collapsed: Vec<bool> = state.todo_list.items.iter().map(|i| i.collapsed).collect();
+        assert_eq!(collapsed, vec![false, true, false, false]);
+
+        state.expand_all();
+        assert!(state.todo_list.items.iter().all(|i| !i.collapsed));
+
+        // Each call was a single undo step
+        assert!(state.undo());
+        assert!(state.todo_list.items[1].collapsed);
+    }
+
+    #[test]
+    fn test_focus_hides_everything_outside_the_subtree() {
+        let mut state = make_test_state();
+        state.todo_list.add_item("Release".to_string());
+        state.todo_list.add_item_with_indent("Docs".to_string(), 1);
+        state.todo_list.add_item_with_indent("README".to_string(), 2);
+        state.todo_list.add_item("Groceries".to_string());
+        state.cursor_position = 1;
+
+        state.toggle_focus();
+        let hidden = state.hidden_indices();
+        assert_eq!((0..4).filter(|i| !hidden.contains(i)).collect::<Vec<_>>(), vec![1, 2]);
+        assert_eq!(state.focus_breadcrumb().as_deref(), Some("Release › Docs"));
+
+        state.move_cursor_down();
+        state.move_cursor_down();
+        assert_eq!(state.cursor_position, 2);
+
+        state.toggle_focus();
+        assert!(state.hidden_indices().is_empty());
+        assert!(state.focus_breadcrumb().is_none());
+    }
+
+    #[test]
+    fn test_breadcrumb_shown_from_second_level() {
+        let mut state = make_test_state();
+        state.terminal_height = 24;
+        state.todo_list.add_item("Project Alpha".to_string());
+        state.todo_list.add_item_with_indent("Backend".to_string(), 1);
+        state.todo_list.add_item_with_indent("Auth refactor".to_string(), 2);
+
+        state.cursor_position = 1;
+        assert_eq!(state.breadcrumb(), None);
+        assert_eq!(state.list_viewport_height(), 21);
+
+        state.cursor_position = 2;
+        assert_eq!(
+            state.breadcrumb(),
+            Some(vec!["Project Alpha", "Backend", "Auth refactor"])
+        );
+        assert_eq!(state.list_viewport_height(), 20);
+    }
+
+    #[test]
+    fn test_theme_picker_previews_and_cancel_restores() {
+        let mut state = make_test_state();
+        let original_bg = state.theme.status_bar_bg;
+        state.theme_picker = Some(ThemePicker {
+            selected: 0,
+            original: state.theme.clone(),
+            priorities: PrioritiesConfig::default(),
+            glyphs: GlyphsConfig::default(),
+        });
+        state.mode = Mode::ThemePicker;
+
+        // Wraps around to the last theme
+        state.theme_picker_move(-1);
+        assert_eq!(state.theme_picker.as_ref().unwrap().selected_name(), "high-contrast");
+        assert_eq!(state.theme.status_bar_bg, ratatui::style::Color::White);
+
+        state.cancel_theme_picker();
+        assert_eq!(state.theme.status_bar_bg, original_bg);
+        assert_eq!(state.mode, Mode::Navigate);
+    }
+
+    #[test]
+    fn test_detail_pane_narrows_list() {
+        let mut state = make_test_state();
+        state.terminal_width = 100;
+        state.toggle_detail_pane();
+        assert_eq!(state.detail_pane_width(), 40);
+        assert_eq!(state.list_content_width(), 58);
+
+        state.terminal_width = 79;
+        assert_eq!(state.detail_pane_width(), 0);
+        assert_eq!(state.list_content_width(), 77);
+    }
+
+    #[test]
+    fn test_archive_refuses_unfinished_subtree() {
+        use crate::todo::TodoState;
+
+        let mut state = make_test_state();
+        state.todo_list.add_item("Parent".to_string());
+        state.todo_list.add_item_with_indent("Child".to_string(), 1);
+        state.todo_list.items[0].state = TodoState::Checked;
+
+        let err = state.archive_current_item().unwrap_err();
+        assert_eq!(err.to_string(), "Only finished items can be archived");
+        assert_eq!(state.todo_list.items.len(), 2);
+        assert!(!state.unsaved_changes);
+    }
+
+    #[test]
+    fn test_trash_rows_stand_for_deleted_subtasks() {
+        use crate::storage::database::DeletedTodo;
+
+        let mut state = make_test_state();
+        state.todo_list.add_item("Parent".to_string());
+        state.todo_list.add_item_with_indent("Child".to_string(), 1);
+        state.todo_list.add_item_with_indent("Grandchild".to_string(), 2);
+        state.todo_list.add_item("Other".to_string());
+        state.todo_list.recalculate_parent_ids();
+        let date = state.todo_list.date;
+        // The grandchild went first, the rest later; newest first as loaded
+        state.trash = [1, 0, 3, 2]
+            .into_iter()
+            .map(|i| DeletedTodo {
+                item: state.todo_list.items[i].clone(),
+                date,
+                archived: false,
+            })
+            .collect();
+
+        assert_eq!(state.trash_rows(), vec![1, 2]);
+        let items = &state.todo_list.items;
+        assert_eq!(state.trash_subtree(1), vec![items[0].id, items[1].id, items[2].id]);
+        assert_eq!(state.trash_subtree(2), vec![items[3].id]);
+
+        state.trash_move(5);
+        assert_eq!(state.trash_selected, 1);
+    }
+
+    #[test]
+    fn test_schedule_save_coalesces_changes() {
+        let mut state = make_test_state();
+        state.schedule_save();
+        assert_eq!(state.save_due, None);
+
+        state.unsaved_changes = true;
+        state.schedule_save();
+        let due = state.save_due.unwrap();
+        // Later changes join the save already scheduled
+        state.schedule_save();
+        assert_eq!(state.save_due, Some(due));
+
+        // Not due yet, so nothing is written
+        state.check_save_due();
+        assert!(state.unsaved_changes);
+        assert_eq!(state.save_due, Some(due));
+    }
+
+    #[test]
+    #[serial_test::serial]
+    fn test_pending_save_is_written_before_leaving_the_list() {
+        use crate::storage::file::load_todo_list_for_project;
+
+        let temp_dir = tempfile::TempDir::new().unwrap();
+        std::fs::create_dir_all(temp_dir.path().join(".to-tui")).unwrap();
+        unsafe {
+            std::env::set_var("HOME", temp_dir.path());
+        }
+        crate::storage::database::init_database().unwrap();
+        let mut state = make_test_state();
+        let today = state.todo_list.date;
+        let saved = |project: &str| -> Vec<String> {
+            let list = load_todo_list_for_project(project, today).unwrap();
+            list.items.into_iter().map(|item| item.content).collect()
+        };
+
+        let edit = |state: &mut AppState, content: &str| {
+            state.todo_list.add_item(content.to_string());
+            state.unsaved_changes = true;
+            state.schedule_save();
+            assert!(state.save_due.is_some());
+        };
+
+        edit(&mut state, "Before the day switch");
+        state.navigate_to_date(today - chrono::Duration::days(1)).unwrap();
+        assert_eq!(saved("default"), vec!["Before the day switch"]);
+        assert_eq!(state.save_due, None);
+
+        state.navigate_to_date(today).unwrap();
+        edit(&mut state, "Before the project switch");
+        state.switch_project(Project::new("work")).unwrap();
+        assert_eq!(saved("default"), vec!["Before the day switch", "Before the project switch"]);
+        assert_eq!(state.save_due, None);
+
+        edit(&mut state, "Before quitting");
+        state.request_quit();
+        assert_eq!(saved("work"), vec!["Before quitting"]);
+        assert!(!state.unsaved_changes);
+        assert!(state.should_quit);
+    }
+
+    #[test]
+    #[serial_test::serial]
+    fn test_daily_file_edits_reload_but_own_saves_do_not() {
+        use crate::storage::file::load_todo_list_for_project;
+
+        let temp_dir = tempfile::TempDir::new().unwrap();
+        std::fs::create_dir_all(temp_dir.path().join(".to-tui")).unwrap();
+        unsafe {
+            std::env::set_var("HOME", temp_dir.path());
+        }
+        crate::storage::database::init_database().unwrap();
+        let mut state = make_test_state();
+        state.todo_list = load_todo_list_for_project("default", state.todo_list.date).unwrap();
+        state.todo_list.add_item("Milk".to_string());
+        state.unsaved_changes = true;
+        state.flush_save().unwrap();
+        let path = state.todo_list.file_path.clone();
+        assert!(path.exists());
+
+        let contents = |state: &AppState| -> Vec<String> {
+            state.todo_list.items.iter().map(|item| item.content.clone()).collect()
+        };
+
+        // totui's own write is not taken for an outside edit
+        let milk_id = state.todo_list.items[0].id;
+        state.daily_file_changed(&path);
+        assert_eq!(contents(&state), vec!["Milk"]);
+        assert_eq!(state.todo_list.items[0].id, milk_id);
+        assert_eq!(state.mode, Mode::Navigate);
+
+        let edited = std::fs::read_to_string(&path).unwrap().replace("Milk", "Oat milk") + "- [ ] Eggs\n";
+        std::fs::write(&path, edited).unwrap();
+        // Edits are noticed by their modification time, which may not have
+        // moved on within the same millisecond
+        let later = std::time::SystemTime::now() + std::time::Duration::from_secs(2);
+        std::fs::File::options().write(true).open(&path).unwrap().set_modified(later).unwrap();
+        // Other files are none of this list's business
+        state.daily_file_changed(&path.with_file_name("notes.md"));
+        assert_eq!(contents(&state), vec!["Milk"]);
+
+        state.daily_file_changed(&path);
+        assert_eq!(contents(&state), vec!["Oat milk", "Eggs"]);
+        let saved = load_todo_list_for_project("default", state.todo_list.date).unwrap();
+        assert_eq!(saved.items.len(), 2);
+    }
+
+    #[test]
+    #[serial_test::serial]
+    fn test_undoing_a_move_to_another_date_takes_it_back() {
+        use crate::storage::file::load_todo_list_for_project;
+
+        let temp_dir = tempfile::TempDir::new().unwrap();
+        std::fs::create_dir_all(temp_dir.path().join(".to-tui")).unwrap();
+        unsafe {
+            std::env::set_var("HOME", temp_dir.path());
+        }
+        crate::storage::database::init_database().unwrap();
+        let mut state = make_test_state();
+        let today = state.todo_list.date;
+        let tomorrow = today + Duration::days(1);
+        let saved = |date| -> Vec<String> {
+            let list = load_todo_list_for_project("default", date).unwrap();
+            list.items.into_iter().map(|item| item.content).collect()
+        };
+        state.todo_list = load_todo_list_for_project("default", today).unwrap();
+        state.todo_list.add_item("Report".to_string());
+        state.todo_list.add_item_with_indent("Draft".to_string(), 1);
+        state.todo_list.add_item("Milk".to_string());
+        state.save_list().unwrap();
+        let notes = temp_dir.path().join("notes.txt");
+        std::fs::write(&notes, "outline").unwrap();
+        crate::storage::attachments::attach(&state.todo_list.items[0].id, &notes).unwrap();
+        let attached = |list: &TodoList| list_attachments(&list.items[0].id).unwrap().len();
+
+        assert_eq!(state.move_current_item_to_date(tomorrow).unwrap(), 2);
+        state.save_list().unwrap();
+        assert_eq!(saved(today), vec!["Milk"]);
+        assert_eq!(saved(tomorrow), vec!["Report", "Draft"]);
+        assert_eq!(attached(&load_todo_list_for_project("default", tomorrow).unwrap()), 1);
+
+        assert!(state.undo());
+        state.save_list().unwrap();
+        assert_eq!(saved(today), vec!["Report", "Draft", "Milk"]);
+        assert_eq!(saved(tomorrow), Vec::<String>::new());
+        assert_eq!(attached(&state.todo_list), 1);
+    }
+
+    #[test]
+    fn test_move_to_same_date_is_rejected() {
+        let mut state = make_test_state();
+        state.todo_list.add_item("Task".to_string());
+        let today = state.todo_list.date;
+
+        let err = state.move_current_item_to_date(today).unwrap_err();
+        assert!(err.to_string().starts_with("Item is already on"));
+        assert_eq!(state.todo_list.items.len(), 1);
+        assert!(!state.unsaved_changes);
+    }
+
+    #[test]
+    fn test_yank_and_paste_subtree() {
+        let mut state = make_test_state();
+        state.todo_list.add_item("Parent".to_string());
+        state.todo_list.add_item_with_indent("Child".to_string(), 1);
+        state.todo_list.add_item("Other".to_string());
+
+        assert_eq!(state.yank(None), Some((0, 2)));
+        state.cursor_position = 2;
+        assert_eq!(state.paste(None, false), Some(2));
+
+        let contents: Vec<&str> = state.todo_list.items.iter().map(|i| i.content.as_str()).collect();
+        assert_eq!(contents, vec!["Parent", "Child", "Other", "Parent", "Child"]);
+        assert_eq!(state.cursor_position, 3);
+        assert_ne!(state.todo_list.items[3].id, state.todo_list.items[0].id);
+        assert_eq!(state.todo_list.items[4].parent_id, Some(state.todo_list.items[3].id));
+
+        // Pasting above keeps the target's indent
+        state.cursor_position = 1;
+        state.paste(None, true);
+        assert_eq!(state.todo_list.items[1].content, "Parent");
+        assert_eq!(state.todo_list.items[1].indent_level, 1);
+        assert_eq!(state.todo_list.items[2].indent_level, 2);
+    }
+
+    #[test]
+    fn test_visual_batch_operations_are_single_undo_steps() {
+        let mut state = make_test_state();
+        state.todo_list.add_item("One".to_string());
+        state.todo_list.add_item_with_indent("One child".to_string(), 1);
+        state.todo_list.add_item("Two".to_string());
+        state.todo_list.add_item("Three".to_string());
+        state.mode = Mode::Visual;
+        state.selection_anchor = Some(0);
+        state.cursor_position = 2;
+
+        state.toggle_selection_state();
+        assert!(state.todo_list.items[..3].iter().all(|i| i.state == TodoState::Checked));
+        assert_eq!(state.todo_list.items[3].state, TodoState::Empty);
+        // Everything done already, so the next toggle reopens
+        state.toggle_selection_state();
+        assert!(state.todo_list.items[..3].iter().all(|i| i.state == TodoState::Empty));
+
+        state.cycle_selection_priority();
+        let first = state.todo_list.items[0].priority;
+        assert!(first.is_some());
+        assert!(state.todo_list.items[..3].iter().all(|i| i.priority == first));
+        assert_eq!(state.undo_stack.len(), 3);
+
+        state.open_due_prompt();
+        assert_eq!(state.mode, Mode::EditDue);
+        assert_eq!(state.due_targets, vec![0, 1, 2]);
+        let due = NaiveDate::from_ymd_opt(2026, 3, 1);
+        assert_eq!(state.apply_due_date(due), 3);
+        assert!(state.todo_list.items[..3].iter().all(|i| i.due_date == due));
+        assert_eq!(state.todo_list.items[3].due_date, None);
+
+        state.undo();
+        assert!(state.todo_list.items.iter().all(|i| i.due_date.is_none()));
+    }
+
+    #[test]
+    fn test_undo_history_describes_and_jumps_back() {
+        let mut state = make_test_state();
+        state.todo_list.add_item("One".to_string());
+        state.todo_list.add_item("Two".to_string());
+        state.set_current_item_state(TodoState::Checked);
+        state.cursor_position = 1;
+        state.save_undo();
+        state.todo_list.items[1].indent_level = 1;
+        state.cycle_priority();
+
+        let history: Vec<String> = state.undo_history().into_iter().map(|(_, d)| d).collect();
+        assert_eq!(
+            history,
+            vec![
+                "changed the priority of \"Two\"",
+                "indented \"Two\"",
+                "marked \"One\" done",
+            ]
+        );
+
+        state.open_undo_history();
+        assert_eq!(state.mode, Mode::UndoHistory);
+        state.undo_history_move(5);
+        assert_eq!(state.undo_history_selected, 2);
+
+        assert!(!state.undo_steps(4));
+        assert!(state.undo_steps(2));
+        assert_eq!(state.undo_stack.len(), 1);
+        assert_eq!(state.todo_list.items[1].indent_level, 0);
+        assert_eq!(state.todo_list.items[0].state, TodoState::Checked);
+    }
+
+    #[test]
+    fn test_quit_asks_only_with_pending_changes() {
+        let mut state = make_test_state();
+        state.todo_list.add_item("Task".to_string());
+        state.request_quit();
+        assert!(state.should_quit);
+
+        state.should_quit = false;
+        state.mode = Mode::Edit;
+        state.edit_buffer = "Task".to_string();
+        assert!(!state.has_pending_edit());
+        state.edit_buffer.push_str(" renamed");
+        assert!(state.has_pending_edit());
+
+        state.request_quit();
+        assert!(!state.should_quit);
+        assert_eq!(state.mode, Mode::ConfirmQuit);
+        assert_eq!(state.mode_before_quit, Mode::Edit);
+    }
+
+    #[test]
+    fn test_split_view_moves_and_yanks_from_other_day() {
+        let mut state = make_test_state();
+        let yesterday = state.today - Duration::days(1);
+        let mut items = vec![
+            TodoItem::new("Parent".to_string(), 0),
+            TodoItem::new("Child".to_string(), 1),
+            TodoItem::new("Other".to_string(), 0),
+        ];
+        items[0].collapsed = true;
+        state.split_view = Some(SplitView {
+            list: TodoList::with_items(yesterday, std::path::PathBuf::new(), items),
+            cursor: 0,
+            focused: false,
+        });
+        state.terminal_width = 100;
+        assert_eq!(state.split_pane_width(), 50);
+        assert_eq!(state.list_area_width(), 50);
+
+        state.switch_split_focus();
+        assert!(state.split_focused());
+        // The collapsed child is skipped
+        state.split_view_move_cursor(5);
+        assert_eq!(state.split_view.as_ref().unwrap().selected_index(), Some(2));
+
+        state.split_view_move_cursor(-1);
+        state.yank_from_split_view(None);
+        let pasted = state.registers.paste(None, 0).unwrap();
+        let contents: Vec<&str> = pasted.iter().map(|i| i.content.as_str()).collect();
+        assert_eq!(contents, vec!["Parent", "Child"]);
+
+        // Days after today have no list
+        state.split_view_shift_day(1);
+        state.split_view_shift_day(1);
+        assert_eq!(state.split_view.as_ref().unwrap().list.date, state.today);
+    }
+
+    #[test]
+    fn test_item_counts_and_jump_to_overdue() {
+        let mut state = make_test_state();
+        state.todo_list.add_item("Open".to_string());
+        state.todo_list.add_item("Done".to_string());
+        state.todo_list.add_item("Late parent".to_string());
+        state.todo_list.add_item_with_indent("Late child".to_string(), 1);
+        state.todo_list.items[1].set_state(TodoState::Checked);
+        state.todo_list.items[3].due_date = Some(state.today - Duration::days(2));
+        state.todo_list.items[2].collapsed = true;
+        state.todo_list.recalculate_parent_ids();
+        assert_eq!(state.item_counts(), (3, 1, 1));
+
+        assert!(state.jump_to_first_overdue());
+        assert_eq!(state.cursor_position, 3);
+        assert!(!state.todo_list.items[2].collapsed);
+
+        state.todo_list.items[3].set_state(TodoState::Checked);
+        assert!(!state.jump_to_first_overdue());
+    }
+
+    #[test]
+    fn test_restore_undo_history_only_for_same_day() {
+        let mut state = make_test_state();
+        state.todo_list.add_item("Milk".to_string());
+        let snapshot = UndoSnapshot {
+            list: state.todo_list.clone(),
+            cursor: 0,
+            saved_at: Local::now(),
+            moved: None,
+        };
+        let history = |date| UndoHistory {
+            project: state.current_project.name.clone(),
+            date,
+            snapshots: vec![snapshot.clone()],
+        };
+        let yesterday = history(state.viewing_date - Duration::days(1));
+        let today = history(state.viewing_date);
+
+        state.restore_undo_history(Some(yesterday));
+        assert!(state.persist_undo);
+        assert!(state.undo_stack.is_empty());
+
+        state.restore_undo_history(Some(today));
+        state.todo_list.items.clear();
+        state.undo();
+        assert_eq!(state.todo_list.items[0].content, "Milk");
+    }
 }
@@ -0,0 +1,112 @@
+//! Short-lived notifications shown stacked above the status bar.
+//!
+//! Each toast expires on its own, errors staying up longest, so a quick
+//! follow-up message no longer hides the one before it.
+
+use std::collections::VecDeque;
+use std::time::{Duration, Instant};
+
+/// Most toasts on screen at once; the oldest is dropped beyond this
+const MAX_TOASTS: usize = 4;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ToastLevel {
+    Info,
+    Warn,
+    Error,
+}
+
+impl ToastLevel {
+    fn lifetime(self) -> Duration {
+        match self {
+            ToastLevel::Info => Duration::from_secs(3),
+            ToastLevel::Warn => Duration::from_secs(5),
+            ToastLevel::Error => Duration::from_secs(8),
+        }
+    }
+}
+
+#[derive(Debug, Clone)]
+pub struct Toast {
+    pub level: ToastLevel,
+    pub message: String,
+    shown_at: Instant,
+}
+
+impl Toast {
+    fn is_expired(&self, now: Instant) -> bool {
+        now.duration_since(self.shown_at) > self.level.lifetime()
+    }
+}
+
+#[derive(Debug, Default)]
+pub struct Toasts {
+    queue: VecDeque<Toast>,
+}
+
+impl Toasts {
+    /// Show a message. Repeating the newest message restarts its timer
+    /// instead of stacking a copy.
+    pub fn push(&mut self, level: ToastLevel, message: String) {
+        if let Some(last) = self.queue.back_mut()
+            && last.message == message
+            && last.level == level
+        {
+            last.shown_at = Instant::now();
+            return;
+        }
+        if self.queue.len() == MAX_TOASTS {
+            self.queue.pop_front();
+        }
+        self.queue.push_back(Toast {
+            level,
+            message,
+            shown_at: Instant::now(),
+        });
+    }
+
+    pub fn clear_expired(&mut self) {
+        let now = Instant::now();
+        self.queue.retain(|toast| !toast.is_expired(now));
+    }
+
+    /// Toasts still on screen, oldest first.
+    pub fn visible(&self) -> impl DoubleEndedIterator<Item = &Toast> {
+        let now = Instant::now();
+        self.queue.iter().filter(move |toast| !toast.is_expired(now))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_toasts_stack_and_expire_by_level() {
+        let mut toasts = Toasts::default();
+        toasts.push(ToastLevel::Info, "Saved".to_string());
+        toasts.push(ToastLevel::Error, "Move failed".to_string());
+        toasts.push(ToastLevel::Error, "Move failed".to_string());
+        assert_eq!(toasts.visible().count(), 2);
+        assert_eq!(toasts.visible().last().map(|t| t.level), Some(ToastLevel::Error));
+
+        // Past the info lifetime but within the error one
+        let earlier = Instant::now() - Duration::from_secs(4);
+        for toast in &mut toasts.queue {
+            toast.shown_at = earlier;
+        }
+        toasts.clear_expired();
+        let left: Vec<_> = toasts.visible().map(|t| t.level).collect();
+        assert_eq!(left, vec![ToastLevel::Error]);
+    }
+
+    #[test]
+    fn test_oldest_toast_dropped_when_full() {
+        let mut toasts = Toasts::default();
+        for i in 0..=MAX_TOASTS {
+            toasts.push(ToastLevel::Info, format!("Message {i}"));
+        }
+        assert_eq!(toasts.visible().count(), MAX_TOASTS);
+        assert_eq!(toasts.visible().next().map(|t| t.message.as_str()), Some("Message 1"));
+    }
+}
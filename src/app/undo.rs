@@ -0,0 +1,227 @@
+//! Undo history.
+//!
+//! Every change pushes a snapshot of the list as it was before. The history
+//! browser describes each step by comparing a snapshot with the one after it,
+//! so the places that call `save_undo` don't have to name their change.
+
+use crate::storage::undo_history::MovedItems;
+use crate::todo::{TodoItem, TodoList, TodoState};
+use crate::utils::unicode::{display_width, prefix_fitting};
+use chrono::{DateTime, Local};
+use std::collections::{HashMap, HashSet};
+use uuid::Uuid;
+
+/// Widest item content quoted in a description
+const MAX_QUOTE_WIDTH: usize = 30;
+
+/// Whether a field differs between the old and new copy of an item
+type Differs = fn(&TodoItem, &TodoItem) -> bool;
+
+/// The list and cursor as they were before a change.
+#[derive(Debug, Clone)]
+pub struct UndoEntry {
+    pub list: TodoList,
+    pub cursor: usize,
+    pub saved_at: DateTime<Local>,
+    /// Set when the change also saved items to another day
+    pub moved: Option<MovedItems>,
+}
+
+impl UndoEntry {
+    pub fn new(list: TodoList, cursor: usize) -> Self {
+        Self {
+            list,
+            cursor,
+            saved_at: Local::now(),
+            moved: None,
+        }
+    }
+}
+
+/// Describe the change from `before` to `after` in a few words, such as
+/// `deleted 3 items` or `marked "Buy milk" done`.
+pub fn describe_change(before: &TodoList, after: &TodoList) -> String {
+    let before_by_id: HashMap<Uuid, &TodoItem> =
+        before.items.iter().map(|item| (item.id, item)).collect();
+    let after_ids: HashSet<Uuid> = after.items.iter().map(|item| item.id).collect();
+
+    let added: Vec<&TodoItem> = after
+        .items
+        .iter()
+        .filter(|item| !before_by_id.contains_key(&item.id))
+        .collect();
+    let removed: Vec<&TodoItem> = before
+        .items
+        .iter()
+        .filter(|item| !after_ids.contains(&item.id))
+        .collect();
+    match (added.is_empty(), removed.is_empty()) {
+        (true, true) => {}
+        (false, true) => return format!("added {}", items_label(&added)),
+        (true, false) => return format!("deleted {}", items_label(&removed)),
+        (false, false) => {
+            return format!("added {}, deleted {}", items_label(&added), items_label(&removed));
+        }
+    }
+
+    let pairs: Vec<(&TodoItem, &TodoItem)> = after
+        .items
+        .iter()
+        .filter_map(|item| before_by_id.get(&item.id).map(|old| (*old, item)))
+        .collect();
+    let changed = |differs: Differs| -> Vec<&TodoItem> {
+        pairs
+            .iter()
+            .filter(|(old, new)| differs(old, new))
+            .map(|(_, new)| *new)
+            .collect()
+    };
+
+    let edited = changed(|old, new| old.content != new.content);
+    if !edited.is_empty() {
+        return format!("edited {}", items_label(&edited));
+    }
+
+    let restated = changed(|old, new| old.state != new.state);
+    if let Some(first) = restated.first() {
+        return if restated.iter().all(|item| item.state == first.state) {
+            format!("marked {} {}", items_label(&restated), state_word(first.state))
+        } else {
+            format!("changed the state of {}", items_label(&restated))
+        };
+    }
+
+    let indented = changed(|old, new| old.indent_level < new.indent_level);
+    let outdented = changed(|old, new| old.indent_level > new.indent_level);
+    match (indented.is_empty(), outdented.is_empty()) {
+        (false, true) => return format!("indented {}", items_label(&indented)),
+        (true, false) => return format!("outdented {}", items_label(&outdented)),
+        (false, false) => return "re-indented items".to_string(),
+        (true, true) => {}
+    }
+
+    let reordered = before
+        .items
+        .iter()
+        .map(|item| item.id)
+        .ne(after.items.iter().map(|item| item.id));
+    if reordered {
+        return "reordered items".to_string();
+    }
+
+    let folded = changed(|old, new| old.collapsed != new.collapsed);
+    if let Some(first) = folded.first() {
+        let verb = if first.collapsed { "folded" } else { "unfolded" };
+        return format!("{verb} {}", items_label(&folded));
+    }
+
+    let pinned = changed(|old, new| old.pinned != new.pinned);
+    if let Some(first) = pinned.first() {
+        let verb = if first.pinned { "pinned" } else { "unpinned" };
+        return format!("{verb} {}", items_label(&pinned));
+    }
+
+    let fields: [(&str, Differs); 6] = [
+        ("priority", |old, new| old.priority != new.priority),
+        ("due date", |old, new| old.due_date != new.due_date),
+        ("estimate", |old, new| old.estimate != new.estimate),
+        ("description", |old, new| old.description != new.description),
+        ("link", |old, new| old.link != new.link),
+        ("location", |old, new| old.location != new.location),
+    ];
+    for (field, differs) in fields {
+        let items = changed(differs);
+        if !items.is_empty() {
+            return format!("changed the {field} of {}", items_label(&items));
+        }
+    }
+
+    let other = changed(|old, new| old.modified_at != new.modified_at);
+    if other.is_empty() {
+        "no visible change".to_string()
+    } else {
+        format!("changed {}", items_label(&other))
+    }
+}
+
+/// The quoted content of a single item, or a count.
+fn items_label(items: &[&TodoItem]) -> String {
+    match items {
+        [item] => {
+            let content = item.content.trim();
+            if display_width(content) > MAX_QUOTE_WIDTH {
+                let end = prefix_fitting(content, MAX_QUOTE_WIDTH - 1);
+                format!("\"{}…\"", content[..end].trim_end())
+            } else {
+                format!("\"{content}\"")
+            }
+        }
+        _ => format!("{} items", items.len()),
+    }
+}
+
+fn state_word(state: TodoState) -> &'static str {
+    match state {
+        TodoState::Empty => "open",
+        TodoState::Checked => "done",
+        TodoState::Question => "as a question",
+        TodoState::Exclamation => "important",
+        TodoState::InProgress => "in progress",
+        TodoState::Cancelled => "cancelled",
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use chrono::NaiveDate;
+    use std::path::PathBuf;
+
+    fn list(items: Vec<TodoItem>) -> TodoList {
+        let date = NaiveDate::from_ymd_opt(2025, 1, 1).unwrap();
+        TodoList::with_items(date, PathBuf::from("/tmp/test.md"), items)
+    }
+
+    #[test]
+    fn test_describe_added_and_deleted() {
+        let before = list(vec![
+            TodoItem::new("Buy milk".into(), 0),
+            TodoItem::new("Call Bob".into(), 0),
+            TodoItem::new("Write report".into(), 0),
+        ]);
+        let mut after = before.clone();
+        after.items.truncate(1);
+        assert_eq!(describe_change(&before, &after), "deleted 2 items");
+
+        let mut added = before.clone();
+        added.items.push(TodoItem::new("A much longer task than fits in the quote".into(), 0));
+        assert_eq!(
+            describe_change(&before, &added),
+            "added \"A much longer task than fits…\""
+        );
+    }
+
+    #[test]
+    fn test_describe_state_indent_and_order() {
+        let before = list(vec![
+            TodoItem::new("Buy milk".into(), 0),
+            TodoItem::new("Call Bob".into(), 0),
+        ]);
+
+        let mut done = before.clone();
+        done.items[0].set_state(TodoState::Checked);
+        assert_eq!(describe_change(&before, &done), "marked \"Buy milk\" done");
+
+        let mut indented = before.clone();
+        indented.items[1].indent_level = 1;
+        assert_eq!(describe_change(&before, &indented), "indented \"Call Bob\"");
+
+        let mut moved = before.clone();
+        moved.items.swap(0, 1);
+        assert_eq!(describe_change(&before, &moved), "reordered items");
+
+        let mut pinned = before.clone();
+        pinned.items[1].pinned = true;
+        assert_eq!(describe_change(&before, &pinned), "pinned \"Call Bob\"");
+    }
+}
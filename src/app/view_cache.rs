@@ -0,0 +1,110 @@
+//! Caches for drawing long lists.
+//!
+//! Which items are hidden and how many rows each one wraps to take a walk
+//! over the whole list, and the renderer, scrolling and mouse handling ask
+//! for them several times per frame. Both are kept under a fingerprint of
+//! what they are computed from, so any change to the items, filters or
+//! layout recomputes them on the next read; nothing has to remember to clear
+//! them.
+
+use crate::todo::TodoItem;
+use std::cell::RefCell;
+use std::collections::HashSet;
+use std::hash::{DefaultHasher, Hash, Hasher};
+use std::rc::Rc;
+
+/// Rows one item takes in the list.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct ItemHeight {
+    /// The item's own wrapped lines
+    pub row: usize,
+    /// Its description box, 0 unless the item is expanded and has one
+    pub description: usize,
+}
+
+#[derive(Default)]
+pub struct ViewCache {
+    hidden: RefCell<Option<(u64, Rc<HashSet<usize>>)>>,
+    heights: RefCell<Option<(u64, Rc<Vec<ItemHeight>>)>>,
+}
+
+impl ViewCache {
+    /// Hidden indices for `key`, built again when the key changed.
+    pub fn hidden(&self, key: u64, build: impl FnOnce() -> HashSet<usize>) -> Rc<HashSet<usize>> {
+        cached(&self.hidden, key, build)
+    }
+
+    /// Heights of every item, by index, built again when the key changed.
+    pub fn heights(&self, key: u64, build: impl FnOnce() -> Vec<ItemHeight>) -> Rc<Vec<ItemHeight>> {
+        cached(&self.heights, key, build)
+    }
+}
+
+fn cached<T>(slot: &RefCell<Option<(u64, Rc<T>)>>, key: u64, build: impl FnOnce() -> T) -> Rc<T> {
+    if let Some((cached_key, value)) = slot.borrow().as_ref()
+        && *cached_key == key
+    {
+        return Rc::clone(value);
+    }
+    // Built outside the borrow: building may read other caches
+    let value = Rc::new(build());
+    *slot.borrow_mut() = Some((key, Rc::clone(&value)));
+    value
+}
+
+/// Hasher seeded with everything about the items that shows in the list.
+pub fn list_hasher(items: &[TodoItem]) -> DefaultHasher {
+    let mut hasher = DefaultHasher::new();
+    items.len().hash(&mut hasher);
+    for item in items {
+        item.id.hash(&mut hasher);
+        item.content.hash(&mut hasher);
+        item.state.hash(&mut hasher);
+        item.indent_level.hash(&mut hasher);
+        item.due_date.hash(&mut hasher);
+        item.description.hash(&mut hasher);
+        item.priority.hash(&mut hasher);
+        item.estimate.hash(&mut hasher);
+        item.waiting_on.hash(&mut hasher);
+        item.pinned.hash(&mut hasher);
+        item.link.hash(&mut hasher);
+        item.location.hash(&mut hasher);
+        item.collapsed.hash(&mut hasher);
+    }
+    hasher
+}
+
+/// Finish a key from `hasher` and the other inputs of a cached value.
+pub fn key(mut hasher: DefaultHasher, inputs: impl Hash) -> u64 {
+    inputs.hash(&mut hasher);
+    hasher.finish()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::cell::Cell;
+
+    #[test]
+    fn test_rebuilds_only_when_the_list_changes() {
+        let cache = ViewCache::default();
+        let builds = Cell::new(0);
+        let build = || {
+            builds.set(builds.get() + 1);
+            HashSet::from([1])
+        };
+        let mut items = vec![TodoItem::new("Parent".to_string(), 0), TodoItem::new("Child".to_string(), 1)];
+
+        cache.hidden(key(list_hasher(&items), ()), build);
+        cache.hidden(key(list_hasher(&items), ()), build);
+        assert_eq!(builds.get(), 1);
+
+        items[0].collapsed = true;
+        cache.hidden(key(list_hasher(&items), ()), build);
+        assert_eq!(builds.get(), 2);
+
+        // Other inputs are part of the key too
+        cache.hidden(key(list_hasher(&items), 80usize), build);
+        assert_eq!(builds.get(), 3);
+    }
+}
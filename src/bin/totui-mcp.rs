@@ -1,8 +1,6 @@
 use anyhow::Result;
-use rmcp::{ServiceExt, transport::stdio};
 use std::env;
-use to_tui::mcp::TodoMcpServer;
-use tracing::{debug, error, info};
+use tracing::{debug, info};
 use tracing_subscriber::{EnvFilter, fmt};
 
 const VERSION: &str = env!("CARGO_PKG_VERSION");
@@ -32,29 +30,13 @@ async fn main() -> Result<()> {
     debug!("Debug logging enabled");
     debug!(args = ?args, "Command line arguments");
 
-    debug!("Initializing TodoMcpServer...");
-    let server = TodoMcpServer::new();
-    debug!("TodoMcpServer created successfully");
+    // Same storage locations as totui, including data it would move
+    to_tui::storage::location::init_storage_locations()?;
+    to_tui::storage::encryption::unlock()?;
+    to_tui::storage::history::set_origin(to_tui::storage::history::Origin::Mcp);
 
-    debug!("Setting up stdio transport...");
     info!("Connecting via stdio transport (stdin/stdout)...");
-    
-    let service = match server.serve(stdio()).await {
-        Ok(s) => {
-            info!("MCP service created successfully");
-            debug!("stdio transport connected");
-            s
-        }
-        Err(e) => {
-            error!(error = %e, "Failed to create MCP service");
-            anyhow::bail!("Failed to create MCP service: {}", e);
-        }
-    };
-
-    info!("Server ready, waiting for requests on stdio...");
-    debug!("Entering main service loop");
-
-    service.waiting().await.map_err(|e| anyhow::anyhow!("Service error: {}", e))?;
+    to_tui::mcp::serve(tokio::io::stdin(), tokio::io::stdout()).await?;
 
     info!("Server shutting down gracefully");
     Ok(())
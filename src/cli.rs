@@ -1,7 +1,6 @@
 use clap::{Parser, Subcommand};
-
-/// Default port for the API server
-pub const DEFAULT_API_PORT: u16 = 48372;
+use std::net::IpAddr;
+use std::path::PathBuf;
 
 #[derive(Parser, Debug)]
 #[command(name = "totui")]
@@ -16,25 +15,188 @@ pub struct Cli {
 pub enum Commands {
     Add {
         task: String,
+
+        /// Due date (YYYY-MM-DD or e.g. 'tomorrow', 'next friday', 'in 3 days')
+        #[arg(long)]
+        due: Option<String>,
     },
     Show {
+        /// Date to show (YYYY-MM-DD or e.g. 'yesterday', 'last monday')
         #[arg(short, long)]
         date: Option<String>,
 
         /// Filter by project name
         #[arg(short, long)]
         project: Option<String>,
+
+        /// Only show items waiting on someone else (@waiting)
+        #[arg(short, long)]
+        waiting: bool,
+
+        /// Number items by short id (e.g. `a3`) instead of position
+        #[arg(long)]
+        ids: bool,
+    },
+    /// Search todos on every date, archive included
+    Search {
+        /// Words to look for in item text and descriptions
+        #[arg(required = true)]
+        query: Vec<String>,
+
+        /// Only search this project
+        #[arg(short, long)]
+        project: Option<String>,
+
+        /// Most results to show
+        #[arg(short, long, default_value_t = 50)]
+        limit: usize,
+    },
+    /// Show the log of changes to todos: what changed, when and where from
+    History {
+        /// Only changes to this project
+        #[arg(short, long)]
+        project: Option<String>,
+
+        /// Only changes to the item with this id or id prefix, or the item
+        /// on today's list with this short id
+        #[arg(short, long)]
+        item: Option<String>,
+
+        /// Only this kind of change: add, edit, state, delete, restore,
+        /// purge, archive or rollover
+        #[arg(short, long)]
+        kind: Option<String>,
+
+        /// Most changes to show, newest first
+        #[arg(short, long, default_value_t = 50)]
+        limit: usize,
+
+        /// Print one JSON object per change
+        #[arg(long)]
+        json: bool,
+    },
+    /// Pick one of today's todos interactively (for editor integrations)
+    Pick {
+        /// Print the selected item's id instead of its text
+        #[arg(long)]
+        print_id: bool,
+
+        /// Project (default: "default")
+        #[arg(short, long)]
+        project: Option<String>,
+    },
+    /// Toggle a todo between done and not done by short id, id or id prefix
+    Toggle {
+        id: String,
+
+        /// Project (default: "default")
+        #[arg(short, long)]
+        project: Option<String>,
+    },
+    /// Add a todo that links to a file and line
+    Push {
+        /// Todo text (default: file name and line)
+        text: Option<String>,
+
+        /// File the todo refers to
+        #[arg(long)]
+        file: String,
+
+        /// Line number in the file
+        #[arg(long, default_value_t = 1)]
+        line: u32,
+
+        /// Project (default: "default")
+        #[arg(short, long)]
+        project: Option<String>,
+    },
+    /// Serve the editor JSON protocol on stdin/stdout
+    Rpc,
+    /// Run the MCP server on stdin/stdout, for AI assistants that start it
+    /// as a child process (the same server as `totui-mcp`)
+    Mcp,
+    /// Import todos from another app onto today's lists
+    Import {
+        #[command(subcommand)]
+        source: ImportSource,
     },
     /// Import old markdown files into the archive
     ImportArchive,
+    /// Write every archived day, grouped by project and month, to one
+    /// markdown document
+    ExportArchive {
+        /// Output file (default: print to stdout)
+        path: Option<PathBuf>,
+
+        /// Only this project's archive
+        #[arg(short, long)]
+        project: Option<String>,
+
+        /// Write JSON instead of markdown
+        #[arg(long)]
+        json: bool,
+    },
+    /// Export all todos, config, projects and the plugin list to one archive
+    ExportAll {
+        /// Output file (default: totui-export-YYYY-MM-DD.tar.gz)
+        path: Option<PathBuf>,
+    },
+    /// Restore everything from an archive made by export-all
+    ImportAll {
+        /// Archive created by export-all
+        path: PathBuf,
+
+        /// Replace existing todos on this machine
+        #[arg(long)]
+        force: bool,
+    },
+    /// Check the installation: paths, database health and backups
+    Doctor,
+    /// Check and repair the database
+    Db {
+        #[command(subcommand)]
+        command: DbCommand,
+    },
+    /// Share a project in the shared database with a teammate, or show who
+    /// it is shared with
+    Share {
+        /// Project to share
+        project: String,
+
+        /// User to share it with (default: show who can use it)
+        user: Option<String>,
+
+        /// Stop sharing it with the user
+        #[arg(long, requires = "user")]
+        remove: bool,
+    },
     /// Manage the API server
     Serve {
         #[command(subcommand)]
         command: Option<ServeCommand>,
 
-        /// Port to run the server on
-        #[arg(short, long, global = true, default_value_t = DEFAULT_API_PORT)]
-        port: u16,
+        /// Port to run the server on (default: `port` in [server] in the
+        /// config, else 48372)
+        #[arg(short, long, global = true)]
+        port: Option<u16>,
+
+        /// Address to listen on (default: `bind` in [server] in the config,
+        /// else every interface)
+        #[arg(long, global = true)]
+        bind: Option<IpAddr>,
+
+        /// PEM certificate to serve HTTPS with (default: `tls_cert` in
+        /// [server] in the config)
+        #[arg(long, global = true, requires = "tls_key")]
+        tls_cert: Option<PathBuf>,
+
+        /// PEM private key of the certificate
+        #[arg(long, global = true, requires = "tls_cert")]
+        tls_key: Option<PathBuf>,
+
+        /// Listen on a Unix domain socket instead of a TCP port
+        #[arg(long, global = true, value_name = "PATH", conflicts_with_all = ["port", "bind", "tls_cert"])]
+        socket: Option<PathBuf>,
     },
     /// Generate todos from external sources using plugins
     Generate {
@@ -104,6 +266,115 @@ pub enum PluginCommand {
     },
 }
 
+#[derive(Subcommand, Debug, Clone)]
+pub enum ImportSource {
+    /// Import active tasks from Todoist, each project into the totui project
+    /// of the same name
+    Todoist {
+        /// Todoist API token (Settings > Integrations > Developer)
+        #[arg(long, required_unless_present = "csv", conflicts_with = "csv")]
+        token: Option<String>,
+
+        /// Read a project's CSV export instead of using the API
+        #[arg(long)]
+        csv: Option<PathBuf>,
+
+        /// Project to import the CSV into (default: the file name)
+        #[arg(short, long, requires = "csv")]
+        project: Option<String>,
+
+        /// Show what would be imported without importing it
+        #[arg(long)]
+        dry_run: bool,
+    },
+    /// Import the to-dos of an iCalendar (.ics) file
+    Ics {
+        /// The .ics file
+        path: PathBuf,
+
+        /// Project to import into (default: "default")
+        #[arg(short, long)]
+        project: Option<String>,
+
+        /// Leave out completed to-dos
+        #[arg(long)]
+        skip_completed: bool,
+
+        /// Show what would be imported without importing it
+        #[arg(long)]
+        dry_run: bool,
+    },
+    /// Import reminders from the Reminders app on macOS
+    Reminders {
+        /// Reminders store to read (default: all of the current user's)
+        #[arg(long)]
+        store: Option<PathBuf>,
+
+        /// Project to import into (default: "default")
+        #[arg(short, long)]
+        project: Option<String>,
+
+        /// Leave out completed reminders
+        #[arg(long)]
+        skip_completed: bool,
+
+        /// Show what would be imported without importing it
+        #[arg(long)]
+        dry_run: bool,
+    },
+    /// Import Jira issues matching a JQL query, set up in [importers.jira]
+    Jira {
+        /// JQL query (default: the configured `jql`)
+        jql: Option<String>,
+
+        /// Project to import into (default: the configured `project`)
+        #[arg(short, long)]
+        project: Option<String>,
+
+        /// Show what would be imported without importing it
+        #[arg(long)]
+        dry_run: bool,
+    },
+    /// Import open issues of a GitHub repository, skipping ones imported
+    /// before
+    Github {
+        /// Repository as owner/name
+        repo: String,
+
+        /// Only issues assigned to this user, or `me`
+        #[arg(long)]
+        assignee: Option<String>,
+
+        /// Project to import into (default: "default")
+        #[arg(short, long)]
+        project: Option<String>,
+
+        /// GitHub token (default: $GITHUB_TOKEN or $GH_TOKEN); needed for
+        /// private repositories and `--assignee me`
+        #[arg(long)]
+        token: Option<String>,
+
+        /// Show what would be imported without importing it
+        #[arg(long)]
+        dry_run: bool,
+    },
+}
+
+#[derive(Subcommand, Debug, Clone)]
+pub enum DbCommand {
+    /// Look for corruption, orphaned children, duplicate ids and daily files
+    /// that don't match the database
+    Check {
+        /// Offer to repair each problem found
+        #[arg(long)]
+        repair: bool,
+
+        /// Repair everything without asking
+        #[arg(short, long, requires = "repair")]
+        yes: bool,
+    },
+}
+
 #[derive(Subcommand, Debug, Clone)]
 pub enum ServeCommand {
     /// Start the API server (default if no subcommand given)
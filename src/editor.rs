@@ -0,0 +1,381 @@
+//! Editor integration (e.g. a Neovim plugin).
+//!
+//! `totui rpc` speaks newline-delimited JSON over stdin/stdout. Each request
+//! is one line:
+//!
+//! ```text
+//! {"id": 1, "method": "list", "params": {"project": "work"}}
+//! ```
+//!
+//! and gets exactly one response line, either `{"id": 1, "result": ...}` or
+//! `{"id": 1, "error": "..."}`. See `docs/editor-integration.md` for the
+//! methods. The `pick`, `toggle` and `push` CLI subcommands wrap the same
+//! operations for one-shot use.
+
+use anyhow::{Context, Result, anyhow, bail};
+use chrono::Local;
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+use std::io::{BufRead, Write};
+use std::path::Path;
+use uuid::Uuid;
+
+use crate::project::{ProjectRegistry, DEFAULT_PROJECT_NAME};
+use crate::storage::file::{load_todo_list_for_project, update_todo_list_for_project};
+use crate::todo::short_id::looks_like_short_id;
+use crate::todo::{Location, TodoItem, TodoList};
+
+/// Shortest id prefix accepted when addressing an item.
+pub const MIN_ID_PREFIX_LEN: usize = 4;
+
+/// A request line. `params` is always an object, `{}` when empty.
+#[derive(Debug, Deserialize)]
+pub struct RpcRequest {
+    #[serde(default)]
+    pub id: Value,
+    #[serde(flatten)]
+    pub call: RpcCall,
+}
+
+#[derive(Debug, Deserialize)]
+#[serde(tag = "method", content = "params", rename_all = "snake_case")]
+pub enum RpcCall {
+    /// Today's items
+    List {
+        #[serde(default)]
+        project: Option<String>,
+    },
+    /// Toggle an item between done and not done
+    Toggle {
+        id: String,
+        #[serde(default)]
+        project: Option<String>,
+    },
+    /// Add a todo pointing at a file and line
+    Push {
+        file: String,
+        line: u32,
+        #[serde(default)]
+        text: Option<String>,
+        #[serde(default)]
+        project: Option<String>,
+    },
+}
+
+#[derive(Debug, Serialize)]
+struct RpcResponse {
+    id: Value,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    result: Option<Value>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    error: Option<String>,
+}
+
+/// An item as seen by the editor.
+#[derive(Debug, Clone, Serialize)]
+pub struct EditorItem {
+    pub id: Uuid,
+    /// Per-day short id, e.g. `a3`
+    pub short_id: String,
+    pub content: String,
+    /// Checkbox character: ' ', 'x', '*', '?', '!' or '-'
+    pub state: char,
+    pub indent_level: usize,
+    pub done: bool,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub location: Option<Location>,
+}
+
+impl EditorItem {
+    fn new(item: &TodoItem, short_id: String) -> Self {
+        Self {
+            id: item.id,
+            short_id,
+            content: item.content.clone(),
+            state: item.state.to_char(),
+            indent_level: item.indent_level,
+            done: item.is_complete(),
+            location: item.location.clone(),
+        }
+    }
+
+    /// Editor view of the item at `index`.
+    pub fn at(list: &TodoList, index: usize) -> Self {
+        let item = &list.items[index];
+        let short_id = list.short_ids().remove(&item.id).unwrap_or_default();
+        Self::new(item, short_id)
+    }
+
+    /// Editor view of every item in the list.
+    pub fn all(list: &TodoList) -> Vec<Self> {
+        let mut short_ids = list.short_ids();
+        list.items
+            .iter()
+            .map(|item| Self::new(item, short_ids.remove(&item.id).unwrap_or_default()))
+            .collect()
+    }
+}
+
+/// Find an item by short id (`a3`), full id or unique id prefix.
+pub fn find_by_id(list: &TodoList, id: &str) -> Result<usize> {
+    let id = id.trim().to_lowercase();
+    if looks_like_short_id(&id) {
+        if let Some(index) = list.find_by_short_id(&id) {
+            return Ok(index);
+        }
+        // Longer ones may also be a hex id prefix such as `a123`
+        if id.len() < MIN_ID_PREFIX_LEN {
+            bail!("No item with short id {id}");
+        }
+    }
+    if let Ok(uuid) = Uuid::parse_str(&id) {
+        return list
+            .items
+            .iter()
+            .position(|item| item.id == uuid)
+            .ok_or_else(|| anyhow!("No item with id {id}"));
+    }
+
+    if id.len() < MIN_ID_PREFIX_LEN {
+        bail!("Id prefix must be at least {MIN_ID_PREFIX_LEN} characters");
+    }
+
+    let mut matches = list
+        .items
+        .iter()
+        .enumerate()
+        .filter(|(_, item)| item.id.to_string().starts_with(&id))
+        .map(|(index, _)| index);
+    match (matches.next(), matches.next()) {
+        (Some(index), None) => Ok(index),
+        (Some(_), Some(_)) => Err(anyhow!("Id prefix {id} matches more than one item")),
+        (None, _) => Err(anyhow!("No item with id {id}")),
+    }
+}
+
+/// Toggle the item with the given id and return its new state.
+pub fn toggle_in_list(list: &mut TodoList, id: &str) -> Result<EditorItem> {
+    let index = find_by_id(list, id)?;
+    list.items[index].toggle_state();
+    Ok(EditorItem::at(list, index))
+}
+
+/// Append a top-level todo that links back to `file:line`.
+///
+/// Without `text` the content defaults to the file name and line.
+pub fn push_location(list: &mut TodoList, file: &str, line: u32, text: Option<&str>) -> EditorItem {
+    let content = match text.map(str::trim).filter(|t| !t.is_empty()) {
+        Some(text) => text.to_string(),
+        None => {
+            let name = Path::new(file)
+                .file_name()
+                .map(|n| n.to_string_lossy().into_owned())
+                .unwrap_or_else(|| file.to_string());
+            format!("{name}:{line}")
+        }
+    };
+
+    let mut item = TodoItem::new(content, 0);
+    item.location = Some(Location {
+        file: file.to_string(),
+        line,
+    });
+    list.items.push(item);
+    EditorItem::at(list, list.items.len() - 1)
+}
+
+fn existing_project(project: Option<&str>) -> Result<String> {
+    let project = project.unwrap_or(DEFAULT_PROJECT_NAME).to_string();
+    if project != DEFAULT_PROJECT_NAME && ProjectRegistry::load()?.get_by_name(&project).is_none() {
+        bail!("Project '{project}' not found");
+    }
+    Ok(project)
+}
+
+fn today_list(project: Option<&str>) -> Result<(TodoList, String)> {
+    let project = existing_project(project)?;
+    let list = load_todo_list_for_project(&project, Local::now().date_naive())?;
+    Ok((list, project))
+}
+
+/// Today's items for `project` (default project if None).
+pub fn list_today(project: Option<&str>) -> Result<Vec<EditorItem>> {
+    let (list, _) = today_list(project)?;
+    Ok(EditorItem::all(&list))
+}
+
+/// The id to filter by for `id`: the full id of the item on today's list it
+/// names, or else `id` as given, as a prefix of ids from any day.
+pub fn full_id_today(id: &str, project: Option<&str>) -> Result<String> {
+    let (list, _) = today_list(project)?;
+    match find_by_id(&list, id) {
+        Ok(index) => Ok(list.items[index].id.to_string()),
+        Err(e) if looks_like_short_id(id.trim()) && id.trim().len() < MIN_ID_PREFIX_LEN => Err(e),
+        Err(_) => Ok(id.trim().to_lowercase()),
+    }
+}
+
+/// Toggle an item in today's list and save it.
+pub fn toggle_today(id: &str, project: Option<&str>) -> Result<EditorItem> {
+    let project = existing_project(project)?;
+    update_todo_list_for_project(&project, Local::now().date_naive(), |list| toggle_in_list(list, id))
+}
+
+/// Add a location todo to today's list and save it.
+pub fn push_today(
+    file: &str,
+    line: u32,
+    text: Option<&str>,
+    project: Option<&str>,
+) -> Result<EditorItem> {
+    let project = existing_project(project)?;
+    update_todo_list_for_project(&project, Local::now().date_naive(), |list| {
+        Ok(push_location(list, file, line, text))
+    })
+}
+
+fn dispatch(call: RpcCall) -> Result<Value> {
+    let result = match call {
+        RpcCall::List { project } => serde_json::to_value(list_today(project.as_deref())?)?,
+        RpcCall::Toggle { id, project } => {
+            serde_json::to_value(toggle_today(&id, project.as_deref())?)?
+        }
+        RpcCall::Push {
+            file,
+            line,
+            text,
+            project,
+        } => serde_json::to_value(push_today(
+            &file,
+            line,
+            text.as_deref(),
+            project.as_deref(),
+        )?)?,
+    };
+    Ok(result)
+}
+
+/// Answer one request line.
+pub fn handle_line(line: &str) -> String {
+    let response = match serde_json::from_str::<RpcRequest>(line) {
+        Ok(request) => match dispatch(request.call) {
+            Ok(result) => RpcResponse {
+                id: request.id,
+                result: Some(result),
+                error: None,
+            },
+            Err(e) => RpcResponse {
+                id: request.id,
+                result: None,
+                error: Some(e.to_string()),
+            },
+        },
+        Err(e) => RpcResponse {
+            id: Value::Null,
+            result: None,
+            error: Some(format!("Invalid request: {e}")),
+        },
+    };
+    serde_json::to_string(&response).unwrap_or_default()
+}
+
+/// Serve requests from `input` until it closes.
+pub fn serve(input: impl BufRead, mut output: impl Write) -> Result<()> {
+    for line in input.lines() {
+        let line = line.context("Failed to read request")?;
+        if line.trim().is_empty() {
+            continue;
+        }
+        writeln!(output, "{}", handle_line(&line))?;
+        output.flush()?;
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use chrono::NaiveDate;
+    use std::path::PathBuf;
+
+    fn test_list() -> TodoList {
+        let date = NaiveDate::from_ymd_opt(2026, 3, 1).unwrap();
+        let mut list = TodoList::new(date, PathBuf::from("/tmp/test.md"));
+        list.add_item("First".to_string());
+        list.add_item("Second".to_string());
+        list
+    }
+
+    #[test]
+    fn test_find_by_id_prefix() {
+        let list = test_list();
+        let id = list.items[1].id.to_string();
+
+        assert_eq!(find_by_id(&list, &id).unwrap(), 1);
+        assert_eq!(find_by_id(&list, &id[..8].to_uppercase()).unwrap(), 1);
+        assert!(find_by_id(&list, &id[..2]).is_err());
+        assert!(find_by_id(&list, "ffffffff-0000").is_err());
+    }
+
+    #[test]
+    fn test_find_by_short_id() {
+        let list = test_list();
+        let short_id = list.short_ids()[&list.items[1].id].clone();
+
+        assert_eq!(find_by_id(&list, &short_id).unwrap(), 1);
+        assert_eq!(find_by_id(&list, &short_id.to_uppercase()).unwrap(), 1);
+    }
+
+    #[test]
+    fn test_toggle_in_list() {
+        let mut list = test_list();
+        let id = list.items[0].id.to_string();
+
+        let item = toggle_in_list(&mut list, &id).unwrap();
+        assert!(item.done);
+        assert_eq!(item.state, 'x');
+
+        let item = toggle_in_list(&mut list, &id).unwrap();
+        assert!(!item.done);
+    }
+
+    #[test]
+    fn test_push_location() {
+        let mut list = test_list();
+
+        let item = push_location(&mut list, "/src/app/main.rs", 42, None);
+        assert_eq!(item.content, "main.rs:42");
+        assert_eq!(
+            item.location,
+            Some(Location {
+                file: "/src/app/main.rs".to_string(),
+                line: 42
+            })
+        );
+
+        let item = push_location(&mut list, "/src/lib.rs", 7, Some("Fix the parser"));
+        assert_eq!(item.content, "Fix the parser");
+        assert_eq!(list.items.len(), 4);
+        assert_eq!(list.items[3].description, None);
+        assert_eq!(list.items[3].location.as_ref().map(Location::to_string).as_deref(), Some("/src/lib.rs:7"));
+    }
+
+    #[test]
+    fn test_request_parsing() {
+        let request: RpcRequest =
+            serde_json::from_str(r#"{"id": 7, "method": "toggle", "params": {"id": "abcd"}}"#)
+                .unwrap();
+        assert_eq!(request.id, Value::from(7));
+        assert!(matches!(request.call, RpcCall::Toggle { ref id, project: None } if id == "abcd"));
+
+        let request: RpcRequest = serde_json::from_str(r#"{"id": 1, "method": "list", "params": {}}"#).unwrap();
+        assert!(matches!(request.call, RpcCall::List { project: None }));
+    }
+
+    #[test]
+    fn test_invalid_request_gets_error_line() {
+        let response: Value = serde_json::from_str(&handle_line(r#"{"method": "explode"}"#)).unwrap();
+        assert!(response["error"].as_str().unwrap().starts_with("Invalid request"));
+        assert!(response.get("result").is_none());
+    }
+}
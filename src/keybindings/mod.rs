@@ -1,4 +1,9 @@
+mod modal;
+
+pub use modal::{Modal, ModalAction};
+
 use crossterm::event::{KeyCode, KeyEvent, KeyModifiers};
+use modal::default_modal_bindings;
 use serde::{Deserialize, Serialize};
 use std::collections::{HashMap, HashSet};
 use std::fmt;
@@ -11,6 +16,8 @@ pub enum Action {
     // Navigation
     MoveUp,
     MoveDown,
+    GoToTop,
+    GoToBottom,
 
     // Visual mode
     ToggleVisual,
@@ -19,6 +26,12 @@ pub enum Action {
     // Item manipulation
     ToggleState,
     CycleState,
+    SetStateTodo,
+    SetStateDone,
+    SetStateInProgress,
+    SetStateQuestion,
+    SetStateImportant,
+    SetStateCancelled,
     Delete,
     NewItem,
     NewItemSameLevel,
@@ -44,6 +57,10 @@ pub enum Action {
     ToggleCollapse,
     Expand,
     CollapseOrParent,
+    CollapseAll,
+    ExpandAll,
+    ExpandOneLevel,
+    FocusSubtree,
 
     // Undo
     Undo,
@@ -57,6 +74,8 @@ pub enum Action {
     PrevDay,
     NextDay,
     GoToToday,
+    OpenWeekView,
+    OpenCalendar,
 
     // Plugin
     OpenPluginMenu,
@@ -67,15 +86,59 @@ pub enum Action {
     // Project
     OpenProjectModal,
     MoveToProject,
+    MoveToDate,
+    SetDueDate,
+
+    // Search
+    StartSearch,
+    SearchNext,
+    SearchPrev,
+    ClearSearch,
 
     // Clipboard
     Yank,
+    PasteBelow,
+    PasteAbove,
     CopyLogPath,
 
     // Priority
     CyclePriority,
+    TogglePin,
     SortByPriority,
 
+    // Estimate
+    EditEstimate,
+
+    // Link
+    EditLink,
+    OpenLink,
+
+    // Attachments
+    AttachFile,
+    OpenAttachment,
+
+    // Command line
+    StartCommand,
+
+    // Filters
+    ToggleWaitingFilter,
+    OpenFilterMenu,
+    ToggleShortIds,
+    ToggleLineNumbers,
+    ToggleSinkCompleted,
+    ToggleDetailPane,
+    OpenThemePicker,
+    OpenUndoHistory,
+    OpenTrash,
+    RecordMacro,
+    PlayMacro,
+    RepeatChange,
+    ToggleSplitView,
+    SwitchPane,
+
+    // Archive
+    ArchiveItem,
+
     // Edit mode specific
     EditCancel,
     EditConfirm,
@@ -90,15 +153,167 @@ pub enum Action {
     EditOutdent,
 }
 
+impl Action {
+    /// Whether a count prefix (`5j`, `3dd`) repeats this action.
+    pub fn repeats_with_count(self) -> bool {
+        matches!(
+            self,
+            Action::MoveUp
+                | Action::MoveDown
+                | Action::Delete
+                | Action::Indent
+                | Action::Outdent
+                | Action::IndentWithChildren
+                | Action::OutdentWithChildren
+                | Action::MoveItemUp
+                | Action::MoveItemDown
+                | Action::PasteBelow
+                | Action::PasteAbove
+        )
+    }
+
+    /// Whether this action changes items in a way `.` can repeat.
+    pub fn is_repeatable_change(self) -> bool {
+        matches!(
+            self,
+            Action::ToggleState
+                | Action::CycleState
+                | Action::SetStateTodo
+                | Action::SetStateDone
+                | Action::SetStateInProgress
+                | Action::SetStateQuestion
+                | Action::SetStateImportant
+                | Action::SetStateCancelled
+                | Action::CyclePriority
+                | Action::TogglePin
+                | Action::Delete
+                | Action::Indent
+                | Action::Outdent
+                | Action::IndentWithChildren
+                | Action::OutdentWithChildren
+                | Action::MoveItemUp
+                | Action::MoveItemDown
+                | Action::PasteBelow
+                | Action::PasteAbove
+                | Action::ArchiveItem
+        )
+    }
+
+    /// One-line description shown next to the action's keys in the help overlay.
+    pub fn description(self) -> &'static str {
+        match self {
+            Action::MoveUp => "Move cursor up",
+            Action::MoveDown => "Move cursor down",
+            Action::GoToTop => "Go to the first item ({count} goes to that line)",
+            Action::GoToBottom => "Go to the last item ({count} goes to that line)",
+            Action::ToggleVisual => "Enter visual mode (select multiple)",
+            Action::ExitVisual => "Exit visual mode",
+            Action::ToggleState => "Toggle done/undone",
+            Action::CycleState => "Cycle: [ ]→[x]→[*]→[?]→[!]→[-]",
+            Action::SetStateTodo => "Set state to [ ] (not started)",
+            Action::SetStateDone => "Set state to [x] done",
+            Action::SetStateInProgress => "Set state to [*] in progress",
+            Action::SetStateQuestion => "Set state to [?] question",
+            Action::SetStateImportant => "Set state to [!] important",
+            Action::SetStateCancelled => "Set state to [-] cancelled",
+            Action::Delete => "Delete item (with children)",
+            Action::NewItem => "New item below",
+            Action::NewItemSameLevel => "New item at same indent level",
+            Action::InsertItemAbove => "New item above",
+            Action::EnterEditMode => "Edit current item",
+            Action::EditDescription => "Edit description",
+            Action::Indent => "Indent item",
+            Action::Outdent => "Outdent item",
+            Action::IndentWithChildren => "Indent with children",
+            Action::OutdentWithChildren => "Outdent with children",
+            Action::MoveItemUp => "Move item up (with children)",
+            Action::MoveItemDown => "Move item down (with children)",
+            Action::ToggleCollapse => "Toggle collapse/expand",
+            Action::Expand => "Expand collapsed item",
+            Action::CollapseOrParent => "Collapse item or go to parent",
+            Action::CollapseAll => "Collapse every item with children",
+            Action::ExpandAll => "Expand every item with children",
+            Action::ExpandOneLevel => "Expand one more level",
+            Action::FocusSubtree => "Focus on subtree (Esc exits)",
+            Action::Undo => "Undo last action",
+            Action::ToggleHelp => "Toggle this help",
+            Action::CloseHelp => "Close help, leave focus",
+            Action::Quit => "Quit",
+            Action::PrevDay => "Previous day (archived, readonly)",
+            Action::NextDay => "Next day",
+            Action::GoToToday => "Go to today",
+            Action::OpenWeekView => "Week view (h/l day, H/L week, Enter opens)",
+            Action::OpenCalendar => "Calendar ([ ] month, Enter jumps to day)",
+            Action::OpenPluginMenu => "Open plugins menu",
+            Action::OpenRolloverModal => "Open rollover modal",
+            Action::OpenProjectModal => "Open project switcher",
+            Action::MoveToProject => "Move item (with subtasks) to another project",
+            Action::MoveToDate => "Move item (with subtasks) to another day",
+            Action::SetDueDate => "Set due date",
+            Action::StartSearch => "Search items and descriptions",
+            Action::SearchNext => "Next match",
+            Action::SearchPrev => "Previous match",
+            Action::ClearSearch => "Clear search highlights",
+            Action::Yank => "Yank item and subtasks (also clipboard)",
+            Action::PasteBelow => "Paste below",
+            Action::PasteAbove => "Paste above",
+            Action::CopyLogPath => "Copy log file path to clipboard",
+            Action::CyclePriority => "Cycle priority: none→P0→P1→…→none",
+            Action::TogglePin => "Pin/unpin item (pinned stay on top)",
+            Action::SortByPriority => "Sort items by priority",
+            Action::EditEstimate => "Set effort estimate (30m, 1h30m)",
+            Action::EditLink => "Set link",
+            Action::OpenLink => "Open link in the browser",
+            Action::AttachFile => "Attach a file",
+            Action::OpenAttachment => "Open attachment ({n}go for the nth)",
+            Action::StartCommand => "Run a command or action by name (Tab completes)",
+            Action::ToggleWaitingFilter => "Show only items waiting on others",
+            Action::OpenFilterMenu => "Filter by state, priority or #tag",
+            Action::ToggleShortIds => "Show/hide short item ids",
+            Action::ToggleLineNumbers => "Show/hide line numbers",
+            Action::ToggleSinkCompleted => "Sink done items below open ones",
+            Action::ToggleDetailPane => "Show/hide the detail pane",
+            Action::OpenThemePicker => "Pick a theme (previews as you scroll)",
+            Action::OpenUndoHistory => "Undo history: jump back several changes at once",
+            Action::OpenTrash => "Trash: restore deleted items or delete them for good",
+            Action::RecordMacro => "Record a macro into a register (again to stop)",
+            Action::PlayMacro => "Replay a macro from a register (@ for the last one)",
+            Action::RepeatChange => "Repeat the last change on this item or the selection",
+            Action::ToggleSplitView => "Show the previous day read-only beside the list",
+            Action::SwitchPane => "Switch between the list and the split's other day",
+            Action::ArchiveItem => "Archive finished item (with subtasks)",
+            Action::EditCancel => "Save and exit edit mode",
+            Action::EditConfirm => "Save and create new item below",
+            Action::EditBackspace => "Delete character",
+            Action::EditLeft => "Move cursor left",
+            Action::EditRight => "Move cursor right",
+            Action::EditWordLeft => "Move word left",
+            Action::EditWordRight => "Move word right",
+            Action::EditHome => "Go to start of line",
+            Action::EditEnd => "Go to end of line",
+            Action::EditIndent => "Indent while editing",
+            Action::EditOutdent => "Outdent while editing",
+        }
+    }
+}
+
 impl fmt::Display for Action {
     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
         let s = match self {
             Action::MoveUp => "move_up",
             Action::MoveDown => "move_down",
+            Action::GoToTop => "go_to_top",
+            Action::GoToBottom => "go_to_bottom",
             Action::ToggleVisual => "toggle_visual",
             Action::ExitVisual => "exit_visual",
             Action::ToggleState => "toggle_state",
             Action::CycleState => "cycle_state",
+            Action::SetStateTodo => "set_state_todo",
+            Action::SetStateDone => "set_state_done",
+            Action::SetStateInProgress => "set_state_in_progress",
+            Action::SetStateQuestion => "set_state_question",
+            Action::SetStateImportant => "set_state_important",
+            Action::SetStateCancelled => "set_state_cancelled",
             Action::Delete => "delete",
             Action::NewItem => "new_item",
             Action::NewItemSameLevel => "new_item_same_level",
@@ -114,6 +329,10 @@ impl fmt::Display for Action {
             Action::ToggleCollapse => "toggle_collapse",
             Action::Expand => "expand",
             Action::CollapseOrParent => "collapse_or_parent",
+            Action::CollapseAll => "collapse_all",
+            Action::ExpandAll => "expand_all",
+            Action::ExpandOneLevel => "expand_one_level",
+            Action::FocusSubtree => "focus_subtree",
             Action::Undo => "undo",
             Action::ToggleHelp => "toggle_help",
             Action::CloseHelp => "close_help",
@@ -121,14 +340,46 @@ impl fmt::Display for Action {
             Action::PrevDay => "prev_day",
             Action::NextDay => "next_day",
             Action::GoToToday => "go_to_today",
+            Action::OpenWeekView => "open_week_view",
+            Action::OpenCalendar => "open_calendar",
             Action::OpenPluginMenu => "open_plugin_menu",
             Action::OpenRolloverModal => "open_rollover_modal",
             Action::OpenProjectModal => "open_project_modal",
             Action::MoveToProject => "move_to_project",
+            Action::MoveToDate => "move_to_date",
+            Action::SetDueDate => "set_due_date",
             Action::Yank => "yank",
+            Action::PasteBelow => "paste_below",
+            Action::PasteAbove => "paste_above",
             Action::CopyLogPath => "copy_log_path",
             Action::CyclePriority => "cycle_priority",
+            Action::TogglePin => "toggle_pin",
+            Action::EditLink => "edit_link",
+            Action::OpenLink => "open_link",
+            Action::AttachFile => "attach_file",
+            Action::OpenAttachment => "open_attachment",
+            Action::StartSearch => "start_search",
+            Action::SearchNext => "search_next",
+            Action::SearchPrev => "search_prev",
+            Action::ClearSearch => "clear_search",
             Action::SortByPriority => "sort_by_priority",
+            Action::EditEstimate => "edit_estimate",
+            Action::ToggleWaitingFilter => "toggle_waiting_filter",
+            Action::OpenFilterMenu => "open_filter_menu",
+            Action::StartCommand => "start_command",
+            Action::ToggleShortIds => "toggle_short_ids",
+            Action::ToggleLineNumbers => "toggle_line_numbers",
+            Action::ToggleSinkCompleted => "toggle_sink_completed",
+            Action::ToggleDetailPane => "toggle_detail_pane",
+            Action::OpenThemePicker => "open_theme_picker",
+            Action::OpenUndoHistory => "open_undo_history",
+            Action::OpenTrash => "open_trash",
+            Action::RecordMacro => "record_macro",
+            Action::PlayMacro => "play_macro",
+            Action::RepeatChange => "repeat_change",
+            Action::ToggleSplitView => "toggle_split_view",
+            Action::SwitchPane => "switch_pane",
+            Action::ArchiveItem => "archive_item",
             Action::EditCancel => "edit_cancel",
             Action::EditConfirm => "edit_confirm",
             Action::EditBackspace => "edit_backspace",
@@ -152,10 +403,18 @@ impl FromStr for Action {
         match s.to_lowercase().as_str() {
             "move_up" => Ok(Action::MoveUp),
             "move_down" => Ok(Action::MoveDown),
+            "go_to_top" => Ok(Action::GoToTop),
+            "go_to_bottom" => Ok(Action::GoToBottom),
             "toggle_visual" => Ok(Action::ToggleVisual),
             "exit_visual" => Ok(Action::ExitVisual),
             "toggle_state" => Ok(Action::ToggleState),
             "cycle_state" => Ok(Action::CycleState),
+            "set_state_todo" => Ok(Action::SetStateTodo),
+            "set_state_done" => Ok(Action::SetStateDone),
+            "set_state_in_progress" => Ok(Action::SetStateInProgress),
+            "set_state_question" => Ok(Action::SetStateQuestion),
+            "set_state_important" => Ok(Action::SetStateImportant),
+            "set_state_cancelled" => Ok(Action::SetStateCancelled),
             "delete" => Ok(Action::Delete),
             "new_item" => Ok(Action::NewItem),
             "new_item_same_level" => Ok(Action::NewItemSameLevel),
@@ -171,6 +430,10 @@ impl FromStr for Action {
             "toggle_collapse" => Ok(Action::ToggleCollapse),
             "expand" => Ok(Action::Expand),
             "collapse_or_parent" => Ok(Action::CollapseOrParent),
+            "collapse_all" => Ok(Action::CollapseAll),
+            "expand_all" => Ok(Action::ExpandAll),
+            "expand_one_level" => Ok(Action::ExpandOneLevel),
+            "focus_subtree" => Ok(Action::FocusSubtree),
             "undo" => Ok(Action::Undo),
             "toggle_help" => Ok(Action::ToggleHelp),
             "close_help" => Ok(Action::CloseHelp),
@@ -178,14 +441,46 @@ impl FromStr for Action {
             "prev_day" => Ok(Action::PrevDay),
             "next_day" => Ok(Action::NextDay),
             "go_to_today" => Ok(Action::GoToToday),
+            "open_week_view" => Ok(Action::OpenWeekView),
+            "open_calendar" => Ok(Action::OpenCalendar),
             "open_plugin_menu" => Ok(Action::OpenPluginMenu),
             "open_rollover_modal" => Ok(Action::OpenRolloverModal),
             "open_project_modal" => Ok(Action::OpenProjectModal),
             "move_to_project" => Ok(Action::MoveToProject),
+            "move_to_date" => Ok(Action::MoveToDate),
+            "set_due_date" => Ok(Action::SetDueDate),
             "yank" => Ok(Action::Yank),
+            "paste_below" => Ok(Action::PasteBelow),
+            "paste_above" => Ok(Action::PasteAbove),
             "copy_log_path" => Ok(Action::CopyLogPath),
             "cycle_priority" => Ok(Action::CyclePriority),
+            "toggle_pin" => Ok(Action::TogglePin),
+            "edit_link" => Ok(Action::EditLink),
+            "open_link" => Ok(Action::OpenLink),
+            "attach_file" => Ok(Action::AttachFile),
+            "open_attachment" => Ok(Action::OpenAttachment),
+            "start_search" => Ok(Action::StartSearch),
+            "search_next" => Ok(Action::SearchNext),
+            "search_prev" => Ok(Action::SearchPrev),
+            "clear_search" => Ok(Action::ClearSearch),
             "sort_by_priority" => Ok(Action::SortByPriority),
+            "edit_estimate" => Ok(Action::EditEstimate),
+            "toggle_waiting_filter" => Ok(Action::ToggleWaitingFilter),
+            "open_filter_menu" => Ok(Action::OpenFilterMenu),
+            "start_command" => Ok(Action::StartCommand),
+            "toggle_short_ids" => Ok(Action::ToggleShortIds),
+            "toggle_line_numbers" => Ok(Action::ToggleLineNumbers),
+            "toggle_sink_completed" => Ok(Action::ToggleSinkCompleted),
+            "toggle_detail_pane" => Ok(Action::ToggleDetailPane),
+            "open_theme_picker" => Ok(Action::OpenThemePicker),
+            "open_undo_history" => Ok(Action::OpenUndoHistory),
+            "open_trash" => Ok(Action::OpenTrash),
+            "record_macro" => Ok(Action::RecordMacro),
+            "play_macro" => Ok(Action::PlayMacro),
+            "repeat_change" => Ok(Action::RepeatChange),
+            "toggle_split_view" => Ok(Action::ToggleSplitView),
+            "switch_pane" => Ok(Action::SwitchPane),
+            "archive_item" => Ok(Action::ArchiveItem),
             "edit_cancel" => Ok(Action::EditCancel),
             "edit_confirm" => Ok(Action::EditConfirm),
             "edit_backspace" => Ok(Action::EditBackspace),
@@ -451,6 +746,10 @@ pub struct KeybindingCache {
     edit_single: HashMap<KeyBinding, Action>,
 
     visual_single: HashMap<KeyBinding, Action>,
+
+    search_single: HashMap<KeyBinding, Action>,
+
+    modal_single: HashMap<Modal, HashMap<KeyBinding, ModalAction>>,
 }
 
 impl KeybindingCache {
@@ -496,12 +795,38 @@ impl KeybindingCache {
                 }
         }
 
+        let mut search_single = HashMap::new();
+        for (key_str, action_str) in &config.search {
+            if let (Ok(seq), Ok(action)) =
+                (key_str.parse::<KeySequence>(), action_str.parse::<Action>())
+                && seq.is_single() {
+                    search_single.insert(seq.0[0], action);
+                }
+        }
+
+        let mut modal_single: HashMap<Modal, HashMap<KeyBinding, ModalAction>> = HashMap::new();
+        for (modal_name, bindings) in &config.modal {
+            let Some(modal) = Modal::from_name(modal_name) else {
+                continue;
+            };
+            let table = modal_single.entry(modal).or_default();
+            for (key_str, action_str) in bindings {
+                if let (Ok(seq), Ok(action)) =
+                    (key_str.parse::<KeySequence>(), action_str.parse::<ModalAction>())
+                    && seq.is_single() {
+                        table.insert(seq.0[0], action);
+                    }
+            }
+        }
+
         Self {
             navigate_single,
             navigate_sequences,
             navigate_sequence_starters,
             edit_single,
             visual_single,
+            search_single,
+            modal_single,
         }
     }
 
@@ -543,6 +868,90 @@ impl KeybindingCache {
         let binding = KeyBinding::from_event(event);
         self.visual_single.get(&binding).copied()
     }
+
+    /// Navigate-mode actions with the key sequences bound to them, sorted by
+    /// name. Actions from the default bindings are listed even when unbound.
+    pub fn navigate_actions(&self) -> Vec<(Action, Vec<KeySequence>)> {
+        let mut actions: HashMap<Action, Vec<KeySequence>> = default_navigate_bindings()
+            .values()
+            .filter_map(|name| name.parse().ok())
+            .map(|action| (action, Vec::new()))
+            .collect();
+        for (binding, action) in &self.navigate_single {
+            actions.entry(*action).or_default().push(KeySequence(vec![*binding]));
+        }
+        for (first, second_map) in &self.navigate_sequences {
+            for (second, action) in second_map {
+                actions
+                    .entry(*action)
+                    .or_default()
+                    .push(KeySequence(vec![*first, *second]));
+            }
+        }
+
+        let mut actions: Vec<_> = actions.into_iter().collect();
+        for (_, keys) in &mut actions {
+            keys.sort_by_key(|key| key.to_string());
+        }
+        actions.sort_by_key(|(action, _)| action.to_string());
+        actions
+    }
+
+    /// Key sequences bound to `action` in navigate mode, shortest first.
+    pub fn navigate_keys(&self, action: Action) -> Vec<KeySequence> {
+        let mut keys = single_keys(&self.navigate_single, action);
+        for (first, second_map) in &self.navigate_sequences {
+            for (second, bound) in second_map {
+                if *bound == action {
+                    keys.push(KeySequence(vec![*first, *second]));
+                }
+            }
+        }
+        sort_keys(keys)
+    }
+
+    /// Keys bound to `action` in visual mode, shortest first.
+    pub fn visual_keys(&self, action: Action) -> Vec<KeySequence> {
+        sort_keys(single_keys(&self.visual_single, action))
+    }
+
+    /// Keys bound to `action` in edit mode, shortest first.
+    pub fn edit_keys(&self, action: Action) -> Vec<KeySequence> {
+        sort_keys(single_keys(&self.edit_single, action))
+    }
+
+    /// Keys bound to `action` while search matches are highlighted, shortest first.
+    pub fn search_keys(&self, action: Action) -> Vec<KeySequence> {
+        sort_keys(single_keys(&self.search_single, action))
+    }
+
+    pub fn get_modal_action(&self, modal: Modal, event: &KeyEvent) -> Option<ModalAction> {
+        let binding = KeyBinding::from_event(event);
+        self.modal_single.get(&modal)?.get(&binding).copied()
+    }
+
+    /// Bindings that take precedence over navigate mode while a search is active.
+    pub fn get_search_action(&self, event: &KeyEvent) -> Option<Action> {
+        let binding = KeyBinding::from_event(event);
+        self.search_single.get(&binding).copied()
+    }
+}
+
+fn single_keys(bindings: &HashMap<KeyBinding, Action>, action: Action) -> Vec<KeySequence> {
+    bindings
+        .iter()
+        .filter(|(_, bound)| **bound == action)
+        .map(|(binding, _)| KeySequence(vec![*binding]))
+        .collect()
+}
+
+/// Plain keys before named ones (`j` before `<Down>`), then alphabetical.
+fn sort_keys(mut keys: Vec<KeySequence>) -> Vec<KeySequence> {
+    keys.sort_by_cached_key(|key| {
+        let s = key.to_string();
+        (s.starts_with('<'), s.len(), s)
+    });
+    keys
 }
 
 impl Default for KeybindingCache {
@@ -562,6 +971,14 @@ pub struct KeybindingsConfig {
     #[serde(default)]
     pub visual: HashMap<String, String>,
 
+    /// Active in navigate mode while search matches are highlighted
+    #[serde(default)]
+    pub search: HashMap<String, String>,
+
+    /// One table per modal, e.g. `[keybindings.modal.confirm_delete]`
+    #[serde(default)]
+    pub modal: HashMap<String, HashMap<String, String>>,
+
     /// Plugin keybinding overrides.
     /// Structure: plugins.{plugin_name}.{action_name} = "keybinding"
     /// Example: plugins.jira.fetch = "<C-j>"
@@ -583,6 +1000,15 @@ impl KeybindingsConfig {
         for (key, value) in defaults.visual {
             self.visual.entry(key).or_insert(value);
         }
+        for (key, value) in defaults.search {
+            self.search.entry(key).or_insert(value);
+        }
+        for (modal, bindings) in defaults.modal {
+            let table = self.modal.entry(modal).or_default();
+            for (key, value) in bindings {
+                table.entry(key).or_insert(value);
+            }
+        }
         // plugins has no defaults - user overrides only
 
         self
@@ -595,6 +1021,8 @@ impl Default for KeybindingsConfig {
             navigate: default_navigate_bindings(),
             edit: default_edit_bindings(),
             visual: default_visual_bindings(),
+            search: default_search_bindings(),
+            modal: default_modal_bindings(),
             plugins: HashMap::new(),
         }
     }
@@ -607,9 +1035,17 @@ fn default_navigate_bindings() -> HashMap<String, String> {
     m.insert("j".to_string(), "move_down".to_string());
     m.insert("<Up>".to_string(), "move_up".to_string());
     m.insert("<Down>".to_string(), "move_down".to_string());
+    m.insert("gg".to_string(), "go_to_top".to_string());
+    m.insert("G".to_string(), "go_to_bottom".to_string());
     m.insert("v".to_string(), "toggle_visual".to_string());
     m.insert("x".to_string(), "toggle_state".to_string());
     m.insert("<Space>".to_string(), "cycle_state".to_string());
+    m.insert("t<Space>".to_string(), "set_state_todo".to_string());
+    m.insert("tx".to_string(), "set_state_done".to_string());
+    m.insert("t*".to_string(), "set_state_in_progress".to_string());
+    m.insert("t?".to_string(), "set_state_question".to_string());
+    m.insert("t!".to_string(), "set_state_important".to_string());
+    m.insert("t-".to_string(), "set_state_cancelled".to_string());
     m.insert("dd".to_string(), "delete".to_string());
     m.insert("n".to_string(), "new_item".to_string());
     m.insert("o".to_string(), "new_item".to_string());
@@ -636,21 +1072,52 @@ fn default_navigate_bindings() -> HashMap<String, String> {
     m.insert("l".to_string(), "expand".to_string());
     m.insert("<Left>".to_string(), "collapse_or_parent".to_string());
     m.insert("h".to_string(), "collapse_or_parent".to_string());
+    m.insert("zM".to_string(), "collapse_all".to_string());
+    m.insert("zR".to_string(), "expand_all".to_string());
+    m.insert("zr".to_string(), "expand_one_level".to_string());
+    m.insert("F".to_string(), "focus_subtree".to_string());
     m.insert("u".to_string(), "undo".to_string());
+    m.insert("gu".to_string(), "open_undo_history".to_string());
+    m.insert("gd".to_string(), "open_trash".to_string());
+    m.insert("Q".to_string(), "record_macro".to_string());
+    m.insert("@".to_string(), "play_macro".to_string());
+    m.insert(".".to_string(), "repeat_change".to_string());
     m.insert("?".to_string(), "toggle_help".to_string());
     m.insert("<Esc>".to_string(), "close_help".to_string());
     m.insert("q".to_string(), "quit".to_string());
     m.insert("<".to_string(), "prev_day".to_string());
     m.insert(">".to_string(), "next_day".to_string());
     m.insert("T".to_string(), "go_to_today".to_string());
-    m.insert("p".to_string(), "cycle_priority".to_string());
-    m.insert("P".to_string(), "open_plugin_menu".to_string());
+    m.insert("w".to_string(), "open_week_view".to_string());
+    m.insert("C".to_string(), "open_calendar".to_string());
+    m.insert("+".to_string(), "cycle_priority".to_string());
+    m.insert("*".to_string(), "toggle_pin".to_string());
+    m.insert("gp".to_string(), "open_plugin_menu".to_string());
     m.insert("R".to_string(), "open_rollover_modal".to_string());
     m.insert("<C-p>".to_string(), "open_project_modal".to_string());
     m.insert("m".to_string(), "move_to_project".to_string());
+    m.insert("M".to_string(), "move_to_date".to_string());
     m.insert("y".to_string(), "yank".to_string());
+    m.insert("p".to_string(), "paste_below".to_string());
+    m.insert("P".to_string(), "paste_above".to_string());
     m.insert("L".to_string(), "copy_log_path".to_string());
     m.insert("s".to_string(), "sort_by_priority".to_string());
+    m.insert("E".to_string(), "edit_estimate".to_string());
+    m.insert("U".to_string(), "edit_link".to_string());
+    m.insert("gx".to_string(), "open_link".to_string());
+    m.insert("ga".to_string(), "attach_file".to_string());
+    m.insert("go".to_string(), "open_attachment".to_string());
+    m.insert("W".to_string(), "toggle_waiting_filter".to_string());
+    m.insert("f".to_string(), "open_filter_menu".to_string());
+    m.insert("#".to_string(), "toggle_short_ids".to_string());
+    m.insert("K".to_string(), "toggle_detail_pane".to_string());
+    m.insert("<C-w>v".to_string(), "toggle_split_view".to_string());
+    m.insert("<C-w>w".to_string(), "switch_pane".to_string());
+    m.insert("D".to_string(), "set_due_date".to_string());
+    m.insert("<C-t>".to_string(), "open_theme_picker".to_string());
+    m.insert("/".to_string(), "start_search".to_string());
+    m.insert(":".to_string(), "start_command".to_string());
+    m.insert("A".to_string(), "archive_item".to_string());
 
     m
 }
@@ -687,6 +1154,13 @@ fn default_visual_bindings() -> HashMap<String, String> {
     m.insert("<Tab>".to_string(), "indent".to_string());
     m.insert("<BackTab>".to_string(), "outdent".to_string());
     m.insert("u".to_string(), "undo".to_string());
+    m.insert("y".to_string(), "yank".to_string());
+    m.insert("d".to_string(), "delete".to_string());
+    m.insert("x".to_string(), "toggle_state".to_string());
+    m.insert("+".to_string(), "cycle_priority".to_string());
+    m.insert("@".to_string(), "set_due_date".to_string());
+    m.insert("m".to_string(), "move_to_project".to_string());
+    m.insert(".".to_string(), "repeat_change".to_string());
     m.insert("v".to_string(), "exit_visual".to_string());
     m.insert("<Esc>".to_string(), "exit_visual".to_string());
     m.insert("q".to_string(), "exit_visual".to_string());
@@ -694,6 +1168,16 @@ fn default_visual_bindings() -> HashMap<String, String> {
     m
 }
 
+fn default_search_bindings() -> HashMap<String, String> {
+    let mut m = HashMap::new();
+
+    m.insert("n".to_string(), "search_next".to_string());
+    m.insert("N".to_string(), "search_prev".to_string());
+    m.insert("<Esc>".to_string(), "clear_search".to_string());
+
+    m
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -770,6 +1254,42 @@ mod tests {
         assert_eq!(result, KeyLookupResult::Action(Action::MoveDown));
     }
 
+    #[test]
+    fn test_modal_lookup_and_remap() {
+        let cache = KeybindingCache::default();
+        let y = KeyEvent::new(KeyCode::Char('y'), KeyModifiers::NONE);
+        assert_eq!(
+            cache.get_modal_action(Modal::ConfirmDelete, &y),
+            Some(ModalAction::Confirm)
+        );
+        let shifted = KeyEvent::new(KeyCode::Char('y'), KeyModifiers::SHIFT);
+        assert_eq!(
+            cache.get_modal_action(Modal::ConfirmDelete, &shifted),
+            Some(ModalAction::Confirm)
+        );
+
+        // Dvorak-style remap: t moves down in the project list, defaults stay
+        let mut config = KeybindingsConfig {
+            navigate: HashMap::new(),
+            edit: HashMap::new(),
+            visual: HashMap::new(),
+            search: HashMap::new(),
+            modal: HashMap::new(),
+            plugins: HashMap::new(),
+        };
+        config
+            .modal
+            .entry("project_select".to_string())
+            .or_default()
+            .insert("t".to_string(), "down".to_string());
+        let cache = KeybindingCache::from_config(&config.merge_with_defaults());
+        let t = KeyEvent::new(KeyCode::Char('t'), KeyModifiers::NONE);
+        let j = KeyEvent::new(KeyCode::Char('j'), KeyModifiers::NONE);
+        assert_eq!(cache.get_modal_action(Modal::ProjectSelect, &t), Some(ModalAction::Down));
+        assert_eq!(cache.get_modal_action(Modal::ProjectSelect, &j), Some(ModalAction::Down));
+        assert_eq!(cache.get_modal_action(Modal::Rollover, &t), None);
+    }
+
     #[test]
     fn test_cache_sequence_lookup() {
         let cache = KeybindingCache::default();
@@ -784,6 +1304,23 @@ mod tests {
         assert_eq!(result2, KeyLookupResult::Action(Action::Delete));
     }
 
+    #[test]
+    fn test_state_keys_follow_the_markdown_chars() {
+        let cache = KeybindingCache::default();
+        let t = KeyBinding::from_event(&KeyEvent::new(KeyCode::Char('t'), KeyModifiers::NONE));
+        let press = |c| KeyEvent::new(KeyCode::Char(c), KeyModifiers::NONE);
+
+        assert_eq!(cache.lookup_navigate(&press('t'), None), KeyLookupResult::Pending);
+        assert_eq!(
+            cache.lookup_navigate(&press('!'), Some(t)),
+            KeyLookupResult::Action(Action::SetStateImportant)
+        );
+        assert_eq!(
+            cache.lookup_navigate(&press(' '), Some(t)),
+            KeyLookupResult::Action(Action::SetStateTodo)
+        );
+    }
+
     #[test]
     fn test_action_roundtrip() {
         let action = Action::MoveUp;
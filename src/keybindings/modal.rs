@@ -0,0 +1,217 @@
+//! Keybindings for the confirm and picker modals.
+//!
+//! Each modal has its own table under `[keybindings.modal.<name>]`, mapping
+//! keys to the small set of [`ModalAction`]s the modal understands.
+
+use std::collections::HashMap;
+use std::fmt;
+use std::str::FromStr;
+
+/// Modals with their own keybinding table
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum Modal {
+    /// Delete confirmation, also used when deleting a project
+    ConfirmDelete,
+    /// Quitting with unsaved changes
+    ConfirmQuit,
+    Rollover,
+    /// Plugins modal tabs, marketplace details and plugin errors
+    Plugins,
+    /// Project switcher, also used by the move-to-project picker
+    ProjectSelect,
+    /// Upgrade prompt and plugin upgrades
+    Upgrade,
+}
+
+impl Modal {
+    pub const ALL: [Modal; 6] = [
+        Modal::ConfirmDelete,
+        Modal::ConfirmQuit,
+        Modal::Rollover,
+        Modal::Plugins,
+        Modal::ProjectSelect,
+        Modal::Upgrade,
+    ];
+
+    /// Name of the modal's table in the config file
+    pub fn name(self) -> &'static str {
+        match self {
+            Modal::ConfirmDelete => "confirm_delete",
+            Modal::ConfirmQuit => "confirm_quit",
+            Modal::Rollover => "rollover",
+            Modal::Plugins => "plugins",
+            Modal::ProjectSelect => "project_select",
+            Modal::Upgrade => "upgrade",
+        }
+    }
+
+    pub fn from_name(name: &str) -> Option<Self> {
+        Self::ALL.into_iter().find(|modal| modal.name() == name)
+    }
+}
+
+/// Actions available in modals. Each modal handles the ones that apply to it.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum ModalAction {
+    Confirm,
+    Cancel,
+    /// Go ahead without saving
+    Discard,
+    Up,
+    Down,
+    NextTab,
+    /// Flip the modal's checkbox ("don't ask again")
+    Toggle,
+    New,
+    Rename,
+    Delete,
+    /// Skip this app version permanently
+    Skip,
+    Retry,
+    /// Open the plugin upgrades from the upgrade prompt
+    UpgradePlugins,
+    /// Upgrade every plugin in the list
+    UpgradeAll,
+}
+
+impl fmt::Display for ModalAction {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let s = match self {
+            ModalAction::Confirm => "confirm",
+            ModalAction::Cancel => "cancel",
+            ModalAction::Discard => "discard",
+            ModalAction::Up => "up",
+            ModalAction::Down => "down",
+            ModalAction::NextTab => "next_tab",
+            ModalAction::Toggle => "toggle",
+            ModalAction::New => "new",
+            ModalAction::Rename => "rename",
+            ModalAction::Delete => "delete",
+            ModalAction::Skip => "skip",
+            ModalAction::Retry => "retry",
+            ModalAction::UpgradePlugins => "upgrade_plugins",
+            ModalAction::UpgradeAll => "upgrade_all",
+        };
+        write!(f, "{s}")
+    }
+}
+
+impl FromStr for ModalAction {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s.to_lowercase().as_str() {
+            "confirm" => Ok(ModalAction::Confirm),
+            "cancel" => Ok(ModalAction::Cancel),
+            "discard" => Ok(ModalAction::Discard),
+            "up" => Ok(ModalAction::Up),
+            "down" => Ok(ModalAction::Down),
+            "next_tab" => Ok(ModalAction::NextTab),
+            "toggle" => Ok(ModalAction::Toggle),
+            "new" => Ok(ModalAction::New),
+            "rename" => Ok(ModalAction::Rename),
+            "delete" => Ok(ModalAction::Delete),
+            "skip" => Ok(ModalAction::Skip),
+            "retry" => Ok(ModalAction::Retry),
+            "upgrade_plugins" => Ok(ModalAction::UpgradePlugins),
+            "upgrade_all" => Ok(ModalAction::UpgradeAll),
+            _ => Err(format!("Unknown modal action: {s}")),
+        }
+    }
+}
+
+/// Default tables for every modal, keyed by modal name.
+pub fn default_modal_bindings() -> HashMap<String, HashMap<String, String>> {
+    Modal::ALL
+        .into_iter()
+        .map(|modal| (modal.name().to_string(), default_bindings_for(modal)))
+        .collect()
+}
+
+fn default_bindings_for(modal: Modal) -> HashMap<String, String> {
+    let pairs: &[(&str, &str)] = match modal {
+        Modal::ConfirmDelete => &[
+            ("y", "confirm"),
+            ("Y", "confirm"),
+            ("<Enter>", "confirm"),
+            ("n", "cancel"),
+            ("N", "cancel"),
+            ("<Esc>", "cancel"),
+        ],
+        Modal::ConfirmQuit => &[
+            ("s", "confirm"),
+            ("S", "confirm"),
+            ("y", "confirm"),
+            ("Y", "confirm"),
+            ("<Enter>", "confirm"),
+            ("d", "discard"),
+            ("D", "discard"),
+            ("n", "discard"),
+            ("N", "discard"),
+            ("<Esc>", "cancel"),
+            ("c", "cancel"),
+            ("C", "cancel"),
+        ],
+        Modal::Rollover => &[
+            ("y", "confirm"),
+            ("Y", "confirm"),
+            ("<Enter>", "confirm"),
+            ("n", "cancel"),
+            ("N", "cancel"),
+            ("l", "cancel"),
+            ("L", "cancel"),
+            ("<Esc>", "cancel"),
+            ("<Tab>", "toggle"),
+            ("<Space>", "toggle"),
+        ],
+        Modal::Plugins => &[
+            ("k", "up"),
+            ("<Up>", "up"),
+            ("j", "down"),
+            ("<Down>", "down"),
+            ("<Tab>", "next_tab"),
+            ("<BackTab>", "next_tab"),
+            ("<Enter>", "confirm"),
+            ("i", "confirm"),
+            ("<Esc>", "cancel"),
+            ("q", "cancel"),
+            ("<BS>", "cancel"),
+        ],
+        Modal::ProjectSelect => &[
+            ("k", "up"),
+            ("<Up>", "up"),
+            ("j", "down"),
+            ("<Down>", "down"),
+            ("<Enter>", "confirm"),
+            ("<Esc>", "cancel"),
+            ("q", "cancel"),
+            ("<C-n>", "new"),
+            ("<C-r>", "rename"),
+            ("<C-d>", "delete"),
+        ],
+        Modal::Upgrade => &[
+            ("y", "confirm"),
+            ("Y", "confirm"),
+            ("<Enter>", "confirm"),
+            ("n", "cancel"),
+            ("N", "cancel"),
+            ("<Esc>", "cancel"),
+            ("k", "up"),
+            ("<Up>", "up"),
+            ("j", "down"),
+            ("<Down>", "down"),
+            ("s", "skip"),
+            ("S", "skip"),
+            ("r", "retry"),
+            ("R", "retry"),
+            ("p", "upgrade_plugins"),
+            ("P", "upgrade_plugins"),
+            ("a", "upgrade_all"),
+            ("A", "upgrade_all"),
+        ],
+    };
+    pairs
+        .iter()
+        .map(|(key, action)| (key.to_string(), action.to_string()))
+        .collect()
+}
@@ -1,5 +1,6 @@
 pub mod clipboard;
 pub mod config;
+pub mod editor;
 pub mod keybindings;
 pub mod mcp;
 pub mod plugin;
@@ -12,28 +12,31 @@ use to_tui::storage;
 use to_tui::todo;
 use to_tui::utils;
 
-use anyhow::{Result, anyhow};
+use anyhow::{Context, Result, anyhow};
 use chrono::Local;
 use clap::Parser;
-use cli::{Cli, Commands, DEFAULT_API_PORT, PluginCommand, ServeCommand};
-use config::Config;
+use cli::{Cli, Commands, DbCommand, ImportSource, PluginCommand, ServeCommand};
+use config::{Config, ServerConfig, TlsFiles};
 use plugin::{PluginActionRegistry, PluginLoader, PluginManager};
 use plugin::config::{generate_config_template, PluginConfigLoader};
 use utils::paths::{get_logs_dir, get_plugin_config_dir, get_plugin_config_path};
 use keybindings::KeybindingCache;
+use std::collections::HashMap;
 use std::env;
 use std::fs;
 use std::io::{Read, Write};
-use std::net::TcpStream;
+use std::net::{IpAddr, Ipv4Addr, Ipv6Addr, SocketAddr, TcpStream};
 use std::panic;
+use std::path::{Path, PathBuf};
 use std::process::{Command, Stdio};
 use std::time::Duration;
 use project::{Project, ProjectRegistry, DEFAULT_PROJECT_NAME};
 use storage::file::{file_exists_for_project, load_todo_list_for_project};
-use storage::file::save_todo_list_for_project;
+use storage::file::update_todo_list_for_project;
 use storage::{ensure_installation_ready, find_rollover_candidates_for_project, UiCache};
+use storage::history::{self, HistoryQuery, Origin};
 use ui::theme::Theme;
-use utils::paths::{get_crash_log_path, get_daily_file_path_for_project, get_pid_file_path};
+use utils::paths::{get_crash_log_path, get_daily_file_path_for_project, get_pid_file_path, get_server_token_path};
 
 /// Load today's todo list for a specific project without prompting for rollover.
 /// Creates an empty list if no existing todos are found.
@@ -73,6 +76,9 @@ fn install_crash_handler() {
     let default_hook = panic::take_hook();
 
     panic::set_hook(Box::new(move |panic_info| {
+        // Count crashes shortly after TUI startup towards crash-loop detection
+        plugin::quarantine::record_crash();
+
         // Try to write to crash log
         if let Ok(crash_log_path) = get_crash_log_path() {
             let timestamp = Local::now().format("%Y-%m-%d %H:%M:%S");
@@ -135,16 +141,15 @@ fn init_file_logging() -> Option<tracing_appender::non_blocking::WorkerGuard> {
 
     // Roll previous log file if it's from a different day
     let log_file_path = logs_dir.join("totui.log");
-    if log_file_path.exists() {
-        if let Ok(metadata) = fs::metadata(&log_file_path) {
-            if let Ok(modified) = metadata.modified() {
-                let modified_date = chrono::DateTime::<Local>::from(modified).format("%Y-%m-%d").to_string();
-                let today = Local::now().format("%Y-%m-%d").to_string();
-                if modified_date != today {
-                    let rolled_name = logs_dir.join(format!("totui.log.{}", modified_date));
-                    let _ = fs::rename(&log_file_path, rolled_name);
-                }
-            }
+    if log_file_path.exists()
+        && let Ok(metadata) = fs::metadata(&log_file_path)
+        && let Ok(modified) = metadata.modified()
+    {
+        let modified_date = chrono::DateTime::<Local>::from(modified).format("%Y-%m-%d").to_string();
+        let today = Local::now().format("%Y-%m-%d").to_string();
+        if modified_date != today {
+            let rolled_name = logs_dir.join(format!("totui.log.{}", modified_date));
+            let _ = fs::rename(&log_file_path, rolled_name);
         }
     }
 
@@ -173,24 +178,165 @@ fn main() -> Result<()> {
     // Install crash handler first thing
     install_crash_handler();
 
+    let cli = Cli::parse();
+
+    // Resolve the config and data directories, moving data if they changed
+    let mut config = storage::location::init_storage_locations()?;
+
     // Ensure installation is properly set up (handles v1 -> v2 migration)
     ensure_installation_ready()?;
 
-    let cli = Cli::parse();
-    let config = Config::load()?;
+    // Ask for the passphrase while the terminal is still ours
+    storage::encryption::unlock()?;
+
+    // Empty the trash of items past the retention window
+    if config.trash_retention_days > 0 {
+        let cutoff = chrono::Utc::now() - chrono::Duration::days(config.trash_retention_days.into());
+        match storage::file::purge_deleted_todos_before(cutoff) {
+            Ok(0) => {}
+            Ok(count) => tracing::info!(count, "Purged items deleted over {} days ago", config.trash_retention_days),
+            Err(e) => tracing::warn!("Failed to purge old deleted items: {e:#}"),
+        }
+    }
+
+    // Fold archived months past the retention window into summaries
+    if config.archive_retention_months > 0 {
+        let cutoff = storage::archive_compaction::retention_cutoff(
+            Local::now().date_naive(),
+            config.archive_retention_months,
+        );
+        match storage::archive_compaction::compact_archive_before(cutoff) {
+            Ok(0) => {}
+            Ok(count) => tracing::info!(count, "Compacted archived items from before {cutoff}"),
+            Err(e) => tracing::warn!("Failed to compact the archive: {e:#}"),
+        }
+    }
+
+    history::set_origin(match &cli.command {
+        None => Origin::Tui,
+        Some(Commands::Serve { .. }) => Origin::Api,
+        Some(Commands::Rpc | Commands::Push { .. }) => Origin::Editor,
+        Some(Commands::Mcp) => Origin::Mcp,
+        Some(_) => Origin::Cli,
+    });
 
     match cli.command {
-        Some(Commands::Add { task }) => {
-            handle_add(task)?;
+        Some(Commands::Add { task, due }) => {
+            handle_add(task, due)?;
+        }
+        Some(Commands::Show { date, project, waiting, ids }) => {
+            handle_show(date, project, waiting, ids)?;
+        }
+        Some(Commands::Search { query, project, limit }) => {
+            handle_search(&query.join(" "), project.as_deref(), limit)?;
+        }
+        Some(Commands::History {
+            project,
+            item,
+            kind,
+            limit,
+            json,
+        }) => {
+            handle_history(project, item, kind, limit, json)?;
         }
-        Some(Commands::Show { date, project }) => {
-            handle_show(date, project)?;
+        Some(Commands::Pick { print_id, project }) => {
+            handle_pick(print_id, project)?;
         }
+        Some(Commands::Toggle { id, project }) => {
+            let item = to_tui::editor::toggle_today(&id, project.as_deref())?;
+            println!("[{}] {}", item.state, item.content);
+        }
+        Some(Commands::Push {
+            text,
+            file,
+            line,
+            project,
+        }) => {
+            let file = std::path::absolute(&file).unwrap_or_else(|_| PathBuf::from(&file));
+            let item = to_tui::editor::push_today(
+                &file.to_string_lossy(),
+                line,
+                text.as_deref(),
+                project.as_deref(),
+            )?;
+            println!("{}", item.id);
+        }
+        Some(Commands::Rpc) => {
+            to_tui::editor::serve(std::io::stdin().lock(), std::io::stdout().lock())?;
+        }
+        Some(Commands::Mcp) => {
+            run_mcp_stdio()?;
+        }
+        Some(Commands::Import { source }) => match source {
+            ImportSource::Todoist { token, csv, project, dry_run } => {
+                handle_import_todoist(token, csv, project, dry_run)?;
+            }
+            ImportSource::Ics {
+                path,
+                project,
+                skip_completed,
+                dry_run,
+            } => {
+                handle_import_ics(&path, project, skip_completed, dry_run)?;
+            }
+            ImportSource::Reminders {
+                store,
+                project,
+                skip_completed,
+                dry_run,
+            } => {
+                handle_import_reminders(store, project, skip_completed, dry_run)?;
+            }
+            ImportSource::Jira { jql, project, dry_run } => {
+                handle_import_jira(&config, jql, project, dry_run)?;
+            }
+            ImportSource::Github {
+                repo,
+                assignee,
+                project,
+                token,
+                dry_run,
+            } => {
+                handle_import_github(&repo, assignee, project, token, dry_run)?;
+            }
+        },
         Some(Commands::ImportArchive) => {
             handle_import_archive()?;
         }
-        Some(Commands::Serve { command, port }) => {
-            handle_serve_command(command, port)?;
+        Some(Commands::ExportArchive { path, project, json }) => {
+            handle_export_archive(path, project, json)?;
+        }
+        Some(Commands::ExportAll { path }) => {
+            handle_export_all(path)?;
+        }
+        Some(Commands::ImportAll { path, force }) => {
+            handle_import_all(&path, force)?;
+        }
+        Some(Commands::Doctor) => {
+            handle_doctor(&config)?;
+        }
+        Some(Commands::Db { command }) => match command {
+            DbCommand::Check { repair, yes } => handle_db_check(&config, repair, yes)?,
+        },
+        Some(Commands::Share { project, user, remove }) => {
+            handle_share(&project, user.as_deref(), remove)?;
+        }
+        Some(Commands::Serve {
+            command,
+            port,
+            bind,
+            tls_cert,
+            tls_key,
+            socket,
+        }) => {
+            let endpoint = Endpoint::resolve(&config.server, Listen {
+                port,
+                bind,
+                tls_cert,
+                tls_key,
+                socket,
+            })?;
+            handle_serve_command(command, &endpoint, &config.server)?;
         }
         Some(Commands::Generate {
             generator,
@@ -209,8 +355,9 @@ fn main() -> Result<()> {
             let _log_guard = init_file_logging();
 
             tracing::info!("totui starting");
+            plugin::quarantine::mark_session_start();
 
-            ensure_server_running(DEFAULT_API_PORT)?;
+            ensure_server_running(&Endpoint::resolve(&config.server, Listen::default())?)?;
 
             // Determine which project to load
             let current_project = get_current_project(&config)?;
@@ -224,6 +371,23 @@ fn main() -> Result<()> {
 
             // Discover plugins and load config
             let mut plugin_manager = PluginManager::discover()?;
+
+            // If the last launches crashed right after startup, disable recently
+            // installed or updated plugins before loading anything
+            let quarantined = match plugin::quarantine::quarantine_if_crash_looping(
+                &plugin_manager,
+                &mut config.plugins,
+            ) {
+                Ok(names) => names,
+                Err(e) => {
+                    tracing::error!("Crash-loop check failed: {e}");
+                    Vec::new()
+                }
+            };
+            if !quarantined.is_empty() {
+                tracing::warn!(plugins = ?quarantined, "Crash loop detected, quarantining plugins");
+                config.save()?;
+            }
             plugin_manager.apply_config(&config.plugins);
 
             // Load dynamic plugins with config validation
@@ -262,6 +426,16 @@ fn main() -> Result<()> {
                 .collect();
             plugin_errors.extend(config_as_load_errors);
 
+            // Tell the user which plugins were quarantined and how to restore them
+            plugin_errors.extend(quarantined.into_iter().map(|name| plugin::PluginLoadError {
+                message: format!(
+                    "Disabled after repeated crashes at startup. Re-enable with 'totui plugin enable {}'",
+                    name
+                ),
+                plugin_name: name,
+                error_kind: plugin::PluginErrorKind::Quarantined,
+            }));
+
             // Build plugin action registry from loaded plugins
             let plugin_action_registry = {
                 let mut registry = PluginActionRegistry::new();
@@ -307,6 +481,34 @@ fn main() -> Result<()> {
                 plugin_action_registry,
                 config.auto_rollover,
             );
+            if let Err(e) = state.resync() {
+                tracing::warn!("Failed to read the day's revision: {e:#}");
+            }
+            state.restore_undo_history(storage::undo_history::UndoHistory::load().unwrap_or_else(|e| {
+                tracing::warn!("Failed to load undo history: {e:#}");
+                None
+            }));
+            state.show_short_ids = config.show_short_ids;
+            state.onboarding = !storage::database::has_any_todos().unwrap_or(true);
+            state.backup_config = config.backup.clone();
+            state.last_backup = storage::backup::last_backup_time().unwrap_or_else(|e| {
+                tracing::warn!("Failed to list backups: {e:#}");
+                None
+            });
+            state.show_detail_pane = config.show_detail_pane;
+            state.show_line_numbers = config.show_line_numbers;
+            state.sink_completed = config.sink_completed;
+            let (highlight_rules, highlight_errors) = ui::highlight::compile_rules(&config.highlights);
+            state.highlight_rules = highlight_rules;
+            for error in highlight_errors {
+                tracing::warn!("{error:#}");
+                state.set_warning(format!("{error:#}"));
+            }
+            state.show_progress_bar = config.show_progress_bar;
+            state.render_markdown = config.markdown_descriptions;
+            state.accessible_mode = config.accessible_mode;
+            state.no_color = config.accessible_mode
+                || std::env::var_os("NO_COLOR").is_some_and(|value| !value.is_empty());
 
             // Apply the rollover preference for any incomplete items left over
             // from a previous day. Honors auto_rollover (AutoYes rolls silently,
@@ -325,9 +527,21 @@ fn main() -> Result<()> {
                 tracing::info!("{} dynamic plugin(s) loaded", loaded_count);
             }
 
-            let state = ui::run_tui(state)?;
+            let state = ui::run_tui(state);
+            storage::attachments::remove_opened_copies();
+            let state = state?;
+
+            if state.backup_config.enabled && state.backup_config.on_exit
+                && let Err(e) = storage::backup::backup_now(&state.backup_config)
+            {
+                tracing::warn!("Database backup at exit failed: {e:#}");
+                eprintln!("Database backup failed: {e:#}");
+            }
 
             tracing::info!("totui exiting gracefully");
+            if let Err(e) = plugin::quarantine::record_clean_exit() {
+                tracing::warn!("Failed to record clean exit: {e}");
+            }
 
             // Print release URL if user requested it
             if let Some(url) = state.pending_release_url {
@@ -340,66 +554,365 @@ fn main() -> Result<()> {
     Ok(())
 }
 
-fn handle_serve_command(command: Option<ServeCommand>, port: u16) -> Result<()> {
+fn handle_doctor(config: &Config) -> Result<()> {
+    let data_dir = utils::paths::get_to_tui_dir()?;
+    let db_path = utils::paths::get_database_path()?;
+    let config_path = utils::paths::get_config_path()?;
+    let backups_dir = utils::paths::get_backups_dir()?;
+
+    println!("Version   {}", env!("CARGO_PKG_VERSION"));
+    println!("Data      {}", data_dir.display());
+
+    let size = std::fs::metadata(&db_path).map(|meta| meta.len()).unwrap_or(0);
+    let health = match storage::database::quick_check() {
+        Ok(problems) if problems.is_empty() => "integrity ok".to_string(),
+        Ok(problems) => format!("{} integrity problem(s): {}", problems.len(), problems.join("; ")),
+        Err(e) => format!("could not be checked: {e:#}"),
+    };
+    println!("Database  {} ({} KB, {health})", db_path.display(), size.div_ceil(1024));
+
+    if config_path.exists() {
+        println!("Config    {}", config_path.display());
+    } else {
+        println!("Config    {} (not found, using defaults)", config_path.display());
+    }
+    println!("Logs      {}", get_logs_dir()?.display());
+
+    let backups = storage::backup::list_backups(&backups_dir)?;
+    let last = match backups.first() {
+        Some(backup) => format!("last {}", backup.created.format("%Y-%m-%d %H:%M")),
+        None => "none yet".to_string(),
+    };
+    let backup = &config.backup;
+    let schedule = if !backup.enabled {
+        "automatic backups off".to_string()
+    } else {
+        let mut when = Vec::new();
+        if backup.interval_hours > 0 {
+            when.push(format!("every {}h", backup.interval_hours));
+        }
+        if backup.on_exit {
+            when.push("at exit".to_string());
+        }
+        if when.is_empty() {
+            when.push("never".to_string());
+        }
+        format!("{}, keeping {}", when.join(" and "), backup.keep.max(1))
+    };
+    println!(
+        "Backups   {} in {}, {last} ({schedule})",
+        backups.len(),
+        backups_dir.display()
+    );
+
+    Ok(())
+}
+
+fn handle_db_check(config: &Config, repair: bool, yes: bool) -> Result<()> {
+    use dialoguer::Confirm;
+
+    let problems = storage::integrity::check()?;
+    if problems.is_empty() {
+        println!("No problems found");
+        return Ok(());
+    }
+
+    if !repair {
+        for problem in &problems {
+            println!("{problem}");
+        }
+        println!("{} problem(s) found", problems.len());
+        if problems.iter().any(|problem| problem.repair_description().is_some()) {
+            println!("Run `totui db check --repair` to fix them");
+        }
+        return Ok(());
+    }
+
+    let mut backed_up = false;
+    let mut repaired = 0;
+    for problem in &problems {
+        println!("{problem}");
+        let Some(description) = problem.repair_description() else {
+            println!(
+                "Can't be repaired here; restore a backup from {}",
+                utils::paths::get_backups_dir()?.display()
+            );
+            continue;
+        };
+        let confirmed = yes
+            || Confirm::new()
+                .with_prompt(format!("Repair: {description}?"))
+                .default(false)
+                .interact()?;
+        if !confirmed {
+            continue;
+        }
+        if !backed_up {
+            let backup = storage::backup::backup_now(&config.backup)?;
+            println!("Backed up the database to {}", backup.display());
+            backed_up = true;
+        }
+        match problem.repair() {
+            Ok(()) => repaired += 1,
+            Err(e) => eprintln!("Repair failed: {e:#}"),
+        }
+    }
+    println!("Repaired {repaired} of {} problem(s)", problems.len());
+    Ok(())
+}
+
+fn handle_share(project: &str, user: Option<&str>, remove: bool) -> Result<()> {
+    let store = storage::shared::store().context("No shared database is set up; set [shared] url in config.toml")?;
+    if let Some(user) = user {
+        store.share_project(project, user, !remove)?;
+        if remove {
+            println!("Stopped sharing {project} with {user}");
+        } else {
+            println!("Shared {project} with {user}");
+        }
+        return Ok(());
+    }
+    match store.project_access(project)? {
+        None => println!("Nobody saved to {project} in the shared database yet"),
+        Some(access) if access.members.is_empty() => println!("{project} belongs to {} and isn't shared", access.owner),
+        Some(access) => println!(
+            "{project} belongs to {} and is shared with {}",
+            access.owner,
+            access.members.join(", ")
+        ),
+    }
+    Ok(())
+}
+
+/// Where the API server listens.
+#[derive(Debug, Clone)]
+enum Endpoint {
+    Tcp {
+        bind: IpAddr,
+        port: u16,
+        tls: Option<TlsFiles>,
+    },
+    /// A Unix domain socket, for local integrations without a TCP port
+    Socket(PathBuf),
+}
+
+/// Where to listen as given on the command line; anything left out comes
+/// from `[server]` in the config.
+#[derive(Debug, Default)]
+struct Listen {
+    port: Option<u16>,
+    bind: Option<IpAddr>,
+    tls_cert: Option<PathBuf>,
+    tls_key: Option<PathBuf>,
+    socket: Option<PathBuf>,
+}
+
+impl Endpoint {
+    /// A socket in the config only applies when no port or address is given
+    /// on the command line.
+    fn resolve(server: &ServerConfig, listen: Listen) -> Result<Self> {
+        let socket = match listen.socket {
+            Some(path) => Some(path),
+            None if listen.port.is_none() && listen.bind.is_none() => server.socket.clone(),
+            None => None,
+        };
+        Ok(match socket {
+            Some(path) => Endpoint::Socket(std::path::absolute(utils::paths::expand_home(&path)?)?),
+            None => Endpoint::Tcp {
+                bind: listen.bind.unwrap_or(server.bind),
+                port: listen.port.unwrap_or(server.port),
+                tls: server.tls(listen.tls_cert, listen.tls_key)?,
+            },
+        })
+    }
+}
+
+/// The address to reach a server listening on `bind` at from this machine.
+fn local_addr(bind: IpAddr, port: u16) -> SocketAddr {
+    let ip = match bind {
+        IpAddr::V4(ip) if ip.is_unspecified() => IpAddr::V4(Ipv4Addr::LOCALHOST),
+        IpAddr::V6(ip) if ip.is_unspecified() => IpAddr::V6(Ipv6Addr::LOCALHOST),
+        ip => ip,
+    };
+    SocketAddr::new(ip, port)
+}
+
+impl std::fmt::Display for Endpoint {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Endpoint::Tcp { bind, port, .. } if bind.is_unspecified() => write!(f, "port {port}"),
+            Endpoint::Tcp { bind, port, .. } => write!(f, "{}", SocketAddr::new(*bind, *port)),
+            Endpoint::Socket(path) => write!(f, "{}", path.display()),
+        }
+    }
+}
+
+fn handle_serve_command(command: Option<ServeCommand>, endpoint: &Endpoint, server: &ServerConfig) -> Result<()> {
     match command.unwrap_or(ServeCommand::Start { daemon: false }) {
         ServeCommand::Start { daemon } => {
             if daemon {
-                run_server_foreground(port)
+                run_server_foreground(endpoint.clone(), server)
             } else {
-                handle_serve_start(port)
+                handle_serve_start(endpoint)
             }
         }
-        ServeCommand::Stop => handle_serve_stop(),
-        ServeCommand::Restart => handle_serve_restart(port),
-        ServeCommand::Status => handle_serve_status(port),
+        ServeCommand::Stop => handle_serve_stop(endpoint),
+        ServeCommand::Restart => handle_serve_restart(endpoint),
+        ServeCommand::Status => handle_serve_status(endpoint),
     }
 }
 
-fn handle_serve_start(port: u16) -> Result<()> {
-    if is_server_running(port) {
-        println!("Server is already running on port {port}");
+fn handle_serve_start(endpoint: &Endpoint) -> Result<()> {
+    if is_server_running(endpoint) {
+        println!("Server is already running on {endpoint}");
         return Ok(());
     }
 
-    start_server_background(port)?;
-    println!("Server started on port {port}");
+    start_server_background(endpoint)?;
+    match endpoint {
+        Endpoint::Tcp { tls: Some(_), .. } => println!("Server started on {endpoint} (HTTPS)"),
+        _ => println!("Server started on {endpoint}"),
+    }
     Ok(())
 }
 
-fn handle_serve_stop() -> Result<()> {
+/// How long `serve stop` waits for the server to finish up before it is
+/// killed
+const STOP_TIMEOUT: Duration = Duration::from_secs(10);
+
+/// Ask the server to stop over the API, then with SIGTERM, and only kill it
+/// when it hasn't exited by then.
+fn handle_serve_stop(endpoint: &Endpoint) -> Result<()> {
     let pid = read_pid_file()?;
+    let asked = request_shutdown(endpoint);
 
-    if let Some(pid) = pid {
-        kill_process(pid)?;
+    let Some(pid) = pid else {
+        if asked && wait_until(|| !is_server_running(endpoint)) {
+            println!("Server stopped");
+        } else {
+            println!("Server is not running (no PID file found)");
+        }
+        return Ok(());
+    };
+
+    if !asked && !process_exists(pid) {
         remove_pid_file()?;
+        println!("Server is not running (removed stale PID file for {pid})");
+        return Ok(());
+    }
+
+    let stopped = (asked && wait_until(|| !process_exists(pid)))
+        || (terminate_process(pid) && wait_until(|| !process_exists(pid)));
+    if stopped {
         println!("Server stopped (PID: {pid})");
     } else {
-        println!("Server is not running (no PID file found)");
+        kill_process(pid)?;
+        let _ = fs::remove_file(get_server_token_path()?);
+        println!("Server did not stop in time and was killed (PID: {pid})");
     }
+    remove_pid_file()?;
 
     Ok(())
 }
 
-fn handle_serve_restart(port: u16) -> Result<()> {
-    let _ = handle_serve_stop();
-    std::thread::sleep(Duration::from_millis(500));
-    handle_serve_start(port)
+fn wait_until(mut done: impl FnMut() -> bool) -> bool {
+    let deadline = std::time::Instant::now() + STOP_TIMEOUT;
+    while std::time::Instant::now() < deadline {
+        if done() {
+            return true;
+        }
+        std::thread::sleep(Duration::from_millis(100));
+    }
+    done()
+}
+
+/// `POST /api/admin/shutdown` with the token the server left behind.
+fn request_shutdown(endpoint: &Endpoint) -> bool {
+    let Ok(token) = get_server_token_path().and_then(|path| Ok(fs::read_to_string(path)?)) else {
+        return false;
+    };
+    let token = token.trim();
+
+    let request = |host: &str| {
+        format!(
+            "POST /api/admin/shutdown HTTP/1.1\r\nHost: {host}\r\nAuthorization: Bearer {token}\r\nContent-Length: 0\r\nConnection: close\r\n\r\n"
+        )
+    };
+    let accepted = |response: Option<String>| response.is_some_and(|r| r.starts_with("HTTP/1.1 202"));
+
+    match endpoint {
+        Endpoint::Tcp { bind, port, tls: None } => {
+            let addr = local_addr(*bind, *port);
+            match TcpStream::connect_timeout(&addr, Duration::from_millis(500)) {
+                Ok(stream) => accepted(send_request(stream, &request(&addr.to_string()))),
+                Err(_) => false,
+            }
+        }
+        Endpoint::Tcp { bind, port, tls: Some(_) } => {
+            let client = reqwest::blocking::Client::builder()
+                .danger_accept_invalid_certs(true)
+                .timeout(Duration::from_secs(2))
+                .build();
+            client
+                .and_then(|client| {
+                    client
+                        .post(format!("https://{}/api/admin/shutdown", local_addr(*bind, *port)))
+                        .bearer_auth(token)
+                        .send()
+                })
+                .is_ok_and(|response| response.status() == reqwest::StatusCode::ACCEPTED)
+        }
+        #[cfg(unix)]
+        Endpoint::Socket(path) => match std::os::unix::net::UnixStream::connect(path) {
+            Ok(stream) => {
+                let _ = stream.set_read_timeout(Some(Duration::from_secs(2)));
+                accepted(send_request(stream, &request("localhost")))
+            }
+            Err(_) => false,
+        },
+        #[cfg(not(unix))]
+        Endpoint::Socket(_) => false,
+    }
+}
+
+/// The server reads this to authorize `POST /api/admin/shutdown`; only the
+/// user may read it.
+fn write_server_token(token: &str) -> Result<()> {
+    let path = get_server_token_path()?;
+    // The mode only applies to new files
+    let _ = fs::remove_file(&path);
+    let mut options = fs::OpenOptions::new();
+    options.write(true).create_new(true);
+    #[cfg(unix)]
+    {
+        use std::os::unix::fs::OpenOptionsExt;
+        options.mode(0o600);
+    }
+    options
+        .open(&path)
+        .and_then(|mut file| file.write_all(token.as_bytes()))
+        .with_context(|| format!("Failed to write {}", path.display()))
 }
 
-fn handle_serve_status(port: u16) -> Result<()> {
+fn handle_serve_restart(endpoint: &Endpoint) -> Result<()> {
+    let _ = handle_serve_stop(endpoint);
+    handle_serve_start(endpoint)
+}
+
+fn handle_serve_status(endpoint: &Endpoint) -> Result<()> {
     let pid = read_pid_file()?;
-    let running = is_server_running(port);
+    let running = is_server_running(endpoint);
 
     match (pid, running) {
         (Some(pid), true) => {
-            println!("Server is running on port {port} (PID: {pid})");
+            println!("Server is running on {endpoint} (PID: {pid})");
         }
         (Some(pid), false) => {
-            println!("Server PID file exists ({pid}) but server is not responding on port {port}");
+            println!("Server PID file exists ({pid}) but server is not responding on {endpoint}");
             println!("Consider running 'todo serve stop' to clean up");
         }
         (None, true) => {
-            println!("Server is running on port {port} but no PID file found");
+            println!("Server is running on {endpoint} but no PID file found");
         }
         (None, false) => {
             println!("Server is not running");
@@ -409,30 +922,82 @@ fn handle_serve_status(port: u16) -> Result<()> {
     Ok(())
 }
 
-fn is_server_running(port: u16) -> bool {
-    let addr = format!("127.0.0.1:{port}");
-    match TcpStream::connect_timeout(&addr.parse().unwrap(), Duration::from_millis(500)) {
-        Ok(mut stream) => {
-            let request = format!(
-                "GET /api/health HTTP/1.1\r\nHost: 127.0.0.1:{port}\r\nConnection: close\r\n\r\n"
-            );
-            if stream.write_all(request.as_bytes()).is_ok() {
-                let mut response = String::new();
-                let _ = stream.read_to_string(&mut response);
-                response.contains("200") || response.contains("ok")
-            } else {
-                false
+fn is_server_running(endpoint: &Endpoint) -> bool {
+    match endpoint {
+        Endpoint::Tcp { bind, port, tls: None } => {
+            let addr = local_addr(*bind, *port);
+            match TcpStream::connect_timeout(&addr, Duration::from_millis(500)) {
+                Ok(stream) => answers_health_check(stream, &addr.to_string()),
+                Err(_) => false,
             }
         }
+        Endpoint::Tcp { bind, port, tls: Some(_) } => is_tls_server_running(local_addr(*bind, *port)),
+        Endpoint::Socket(path) => is_socket_server_running(path),
+    }
+}
+
+/// Ask for `/api/health` over a connection to the server.
+fn answers_health_check(stream: impl Read + Write, host: &str) -> bool {
+    let request = format!("GET /api/health HTTP/1.1\r\nHost: {host}\r\nConnection: close\r\n\r\n");
+    send_request(stream, &request).is_some_and(|response| response.contains("200") || response.contains("ok"))
+}
+
+/// Write a raw HTTP/1.1 request and read the response until the server
+/// closes the connection.
+fn send_request(mut stream: impl Read + Write, request: &str) -> Option<String> {
+    stream.write_all(request.as_bytes()).ok()?;
+    let mut response = String::new();
+    let _ = stream.read_to_string(&mut response);
+    Some(response)
+}
+
+/// Health check over HTTPS. The certificate is likely self-signed or made
+/// out to another name than the address, so it isn't verified; this only
+/// asks whether something answers.
+fn is_tls_server_running(addr: SocketAddr) -> bool {
+    let client = reqwest::blocking::Client::builder()
+        .danger_accept_invalid_certs(true)
+        .timeout(Duration::from_millis(500))
+        .build();
+    client
+        .and_then(|client| client.get(format!("https://{addr}/api/health")).send())
+        .is_ok_and(|response| response.status().is_success())
+}
+
+#[cfg(unix)]
+fn is_socket_server_running(path: &Path) -> bool {
+    use std::os::unix::net::UnixStream;
+    match UnixStream::connect(path) {
+        Ok(stream) => {
+            let _ = stream.set_read_timeout(Some(Duration::from_millis(500)));
+            answers_health_check(stream, "localhost")
+        }
         Err(_) => false,
     }
 }
 
-fn start_server_background(port: u16) -> Result<()> {
+#[cfg(not(unix))]
+fn is_socket_server_running(_path: &Path) -> bool {
+    false
+}
+
+fn start_server_background(endpoint: &Endpoint) -> Result<()> {
     let current_exe = env::current_exe()?;
 
-    let child = Command::new(&current_exe)
-        .args(["serve", "start", "--port", &port.to_string(), "--daemon"])
+    let mut command = Command::new(&current_exe);
+    command.args(["serve", "start", "--daemon"]);
+    match endpoint {
+        Endpoint::Tcp { bind, port, tls } => {
+            command.args(["--bind", &bind.to_string(), "--port", &port.to_string()]);
+            if let Some(tls) = tls {
+                command.arg("--tls-cert").arg(&tls.cert).arg("--tls-key").arg(&tls.key);
+            }
+        }
+        Endpoint::Socket(path) => {
+            command.arg("--socket").arg(path);
+        }
+    }
+    let child = command
         .stdin(Stdio::null())
         .stdout(Stdio::null())
         .stderr(Stdio::null())
@@ -442,19 +1007,19 @@ fn start_server_background(port: u16) -> Result<()> {
 
     std::thread::sleep(Duration::from_millis(500));
 
-    if !is_server_running(port) {
+    if !is_server_running(endpoint) {
         return Err(anyhow!(
-            "Failed to start server - not responding on port {port}"
+            "Failed to start server - not responding on {endpoint}"
         ));
     }
 
     Ok(())
 }
 
-fn ensure_server_running(port: u16) -> Result<()> {
-    if !is_server_running(port) {
-        println!("Starting API server on port {port}...");
-        start_server_background(port)?;
+fn ensure_server_running(endpoint: &Endpoint) -> Result<()> {
+    if !is_server_running(endpoint) {
+        println!("Starting API server on {endpoint}...");
+        start_server_background(endpoint)?;
     }
     Ok(())
 }
@@ -491,6 +1056,39 @@ fn remove_pid_file() -> Result<()> {
     Ok(())
 }
 
+#[cfg(unix)]
+fn process_exists(pid: u32) -> bool {
+    Command::new("kill")
+        .args(["-0", &pid.to_string()])
+        .stderr(Stdio::null())
+        .status()
+        .is_ok_and(|status| status.success())
+}
+
+#[cfg(windows)]
+fn process_exists(pid: u32) -> bool {
+    Command::new("tasklist")
+        .args(["/FI", &format!("PID eq {pid}"), "/NH"])
+        .output()
+        .is_ok_and(|output| String::from_utf8_lossy(&output.stdout).contains(&pid.to_string()))
+}
+
+/// SIGTERM, which the server answers with a graceful shutdown.
+#[cfg(unix)]
+fn terminate_process(pid: u32) -> bool {
+    Command::new("kill")
+        .args(["-TERM", &pid.to_string()])
+        .stderr(Stdio::null())
+        .status()
+        .is_ok_and(|status| status.success())
+}
+
+/// Windows has no SIGTERM for a console process to handle.
+#[cfg(windows)]
+fn terminate_process(_pid: u32) -> bool {
+    false
+}
+
 #[cfg(unix)]
 fn kill_process(pid: u32) -> Result<()> {
     use std::process::Command;
@@ -509,38 +1107,205 @@ fn kill_process(pid: u32) -> Result<()> {
     Ok(())
 }
 
+/// stdout carries the protocol, so logs go to stderr.
 #[tokio::main]
-async fn run_server_foreground(port: u16) -> Result<()> {
+async fn run_mcp_stdio() -> Result<()> {
     tracing_subscriber::fmt()
         .with_env_filter(
-            tracing_subscriber::EnvFilter::try_from_default_env()
-                .unwrap_or_else(|_| "info,tower_http=debug".into()),
+            tracing_subscriber::EnvFilter::try_from_default_env().unwrap_or_else(|_| "warn".into()),
         )
+        .with_writer(std::io::stderr)
         .init();
 
-    let app = api::create_router();
-    let addr = format!("0.0.0.0:{port}");
+    to_tui::mcp::serve(tokio::io::stdin(), tokio::io::stdout()).await
+}
+
+#[tokio::main]
+async fn run_server_foreground(endpoint: Endpoint, server: &ServerConfig) -> Result<()> {
+    let filter = match tracing_subscriber::EnvFilter::try_from_default_env() {
+        Ok(filter) => filter,
+        Err(_) => {
+            let level = server.log_level.as_deref().unwrap_or("info,tower_http=debug");
+            tracing_subscriber::EnvFilter::try_new(level)
+                .with_context(|| format!("Invalid log_level '{level}' in [server]"))?
+        }
+    };
+    let log = api::logs::ServerLog::init(filter)?;
+
+    let admin = api::admin::Admin::new();
+    admin.stop_on_signals()?;
+    let app = api::create_router(api::live::LiveUpdates::start(), admin.clone(), log, server)?;
+    write_server_token(admin.token())?;
+
+    let result = match endpoint {
+        Endpoint::Tcp { bind, port, tls } => serve_tcp(SocketAddr::new(bind, port), tls, app, &admin).await,
+        Endpoint::Socket(path) => serve_socket(&path, app, &admin).await,
+    };
+    let _ = fs::remove_file(get_server_token_path()?);
+    result
+}
+
+/// How long requests in flight get to finish once the server is stopping.
+/// Live feeds never finish on their own, so this is also how long they hold
+/// up the exit.
+const SHUTDOWN_GRACE: Duration = Duration::from_secs(5);
+
+async fn serve_tcp(addr: SocketAddr, tls: Option<TlsFiles>, app: axum::Router, admin: &api::admin::Admin) -> Result<()> {
+    let app = app.into_make_service_with_connect_info::<SocketAddr>();
+
+    if let Some(tls) = tls {
+        // rustls is built with ring only, so there is no default to fall back on
+        let _ = rustls::crypto::ring::default_provider().install_default();
+        let rustls_config = axum_server::tls_rustls::RustlsConfig::from_pem_file(&tls.cert, &tls.key)
+            .await
+            .with_context(|| {
+                format!(
+                    "Failed to load TLS certificate {} and key {}",
+                    tls.cert.display(),
+                    tls.key.display()
+                )
+            })?;
+        let handle = axum_server::Handle::new();
+        let stopping = handle.clone();
+        let admin = admin.clone();
+        tokio::spawn(async move {
+            admin.stopped().await;
+            stopping.graceful_shutdown(Some(SHUTDOWN_GRACE));
+        });
+        tracing::info!("Starting HTTPS server on {}", addr);
+        axum_server::bind_rustls(addr, rustls_config)
+            .handle(handle)
+            .serve(app)
+            .await?;
+        return Ok(());
+    }
 
     tracing::info!("Starting server on {}", addr);
 
-    let listener = tokio::net::TcpListener::bind(&addr).await?;
-    axum::serve(listener, app).await?;
+    let listener = tokio::net::TcpListener::bind(addr)
+        .await
+        .with_context(|| format!("Failed to listen on {addr}"))?;
+    let stopping = admin.clone();
+    let server = axum::serve(listener, app).with_graceful_shutdown(async move { stopping.stopped().await });
+    serve_gracefully(server, admin).await
+}
 
+/// Run `server`, which shuts down gracefully once stopped, but give open
+/// connections no more than [`SHUTDOWN_GRACE`] to finish.
+async fn serve_gracefully(
+    server: impl std::future::IntoFuture<Output = std::io::Result<()>>,
+    admin: &api::admin::Admin,
+) -> Result<()> {
+    tokio::select! {
+        result = server.into_future() => result?,
+        _ = async {
+            admin.stopped().await;
+            tokio::time::sleep(SHUTDOWN_GRACE).await;
+        } => tracing::warn!("Connections still open after {}s, stopping anyway", SHUTDOWN_GRACE.as_secs()),
+    }
     Ok(())
 }
 
-fn handle_add(task: String) -> Result<()> {
-    let mut list = load_today_list_for_project(DEFAULT_PROJECT_NAME)?;
+#[cfg(unix)]
+async fn serve_socket(path: &Path, app: axum::Router, admin: &api::admin::Admin) -> Result<()> {
+    let listener = api::socket::bind(path)?;
+
+    tracing::info!("Starting server on {}", path.display());
+    let stopping = admin.clone();
+    let server = axum::serve(listener, app).with_graceful_shutdown(async move { stopping.stopped().await });
+    let result = serve_gracefully(server, admin).await;
+    let _ = fs::remove_file(path);
+    result
+}
+
+#[cfg(not(unix))]
+async fn serve_socket(_path: &Path, _app: axum::Router, _admin: &api::admin::Admin) -> Result<()> {
+    Err(anyhow!("--socket needs a Unix domain socket, which this platform lacks"))
+}
+
+fn handle_add(task: String, due: Option<String>) -> Result<()> {
+    let due_date = due.as_deref().map(utils::dateparse::parse_date).transpose()?;
 
-    list.add_item(task);
-    save_todo_list_for_project(&list, DEFAULT_PROJECT_NAME)?;
+    let mut item = todo::TodoItem::new(task, 0);
+    item.due_date = due_date;
+    add_items_to_today(vec![item])?;
 
     println!("✓ Todo added successfully!");
 
     Ok(())
 }
 
-fn handle_show(date: Option<String>, project: Option<String>) -> Result<()> {
+fn handle_search(query: &str, project: Option<&str>, limit: usize) -> Result<()> {
+    let hits = storage::database::search_todos(query, project, limit)?;
+    if hits.is_empty() {
+        println!("No todos match '{query}'.");
+        return Ok(());
+    }
+
+    for hit in hits {
+        let project_label = if hit.project != DEFAULT_PROJECT_NAME {
+            format!(" [{}]", hit.project)
+        } else {
+            String::new()
+        };
+        let archived = if hit.archived { " (archived)" } else { "" };
+        println!("{}{} {} {}{}", hit.date, project_label, hit.state, hit.content, archived);
+    }
+    Ok(())
+}
+
+fn handle_history(
+    project: Option<String>,
+    item: Option<String>,
+    kind: Option<String>,
+    limit: usize,
+    json: bool,
+) -> Result<()> {
+    let todo_id = item
+        .map(|id| to_tui::editor::full_id_today(&id, project.as_deref()))
+        .transpose()?;
+    let query = HistoryQuery {
+        project,
+        todo_id,
+        kind: kind.as_deref().map(str::parse).transpose()?,
+        since: None,
+        after: None,
+        limit,
+    };
+    let events = history::load_events(&query)?;
+    if events.is_empty() && !json {
+        println!("No changes recorded.");
+        return Ok(());
+    }
+
+    for event in events {
+        if json {
+            println!("{}", serde_json::to_string(&event)?);
+            continue;
+        }
+        let at = event.at.with_timezone(&Local).format("%Y-%m-%d %H:%M:%S");
+        let project_label = if event.project != DEFAULT_PROJECT_NAME {
+            format!(" [{}]", event.project)
+        } else {
+            String::new()
+        };
+        let detail = event.detail.map(|d| format!(" ({d})")).unwrap_or_default();
+        println!(
+            "{at} {:<16} {:<8}{project_label} {}{detail}",
+            event.origin,
+            event.kind,
+            event.content.unwrap_or_default()
+        );
+    }
+    Ok(())
+}
+
+fn handle_show(
+    date: Option<String>,
+    project: Option<String>,
+    waiting: bool,
+    show_ids: bool,
+) -> Result<()> {
     let project_name = project.as_deref().unwrap_or(DEFAULT_PROJECT_NAME);
 
     // Validate project exists
@@ -550,15 +1315,19 @@ fn handle_show(date: Option<String>, project: Option<String>) -> Result<()> {
         anyhow::bail!("Project '{}' not found", project_name);
     }
 
+    let mut short_ids = HashMap::new();
     let (items, display_date, is_archived): (Vec<todo::TodoItem>, chrono::NaiveDate, bool) =
         if let Some(date_str) = date {
-            let parsed_date = chrono::NaiveDate::parse_from_str(&date_str, "%Y-%m-%d")
-                .map_err(|_| anyhow!("Invalid date format. Use YYYY-MM-DD"))?;
+            let parsed_date = utils::dateparse::parse_date(&date_str)?;
 
             let today = Local::now().date_naive();
             if parsed_date == today {
-                let list = load_today_list_for_project(project_name)?;
-                (list.items, today, false)
+                // Include items already archived today so nothing goes missing
+                let mut items = load_today_list_for_project(project_name)?.items;
+                // Short ids come from the live list, as `totui toggle` sees it
+                short_ids = todo::short_id::assign_short_ids(&items);
+                items.extend(storage::load_archived_todos_for_date_and_project(today, project_name)?);
+                (items, today, false)
             } else {
                 let items = storage::load_archived_todos_for_date_and_project(parsed_date, project_name)?;
                 (items, parsed_date, true)
@@ -569,6 +1338,21 @@ fn handle_show(date: Option<String>, project: Option<String>) -> Result<()> {
             (list.items, date, false)
         };
 
+    if short_ids.is_empty() {
+        short_ids = todo::short_id::assign_short_ids(&items);
+    }
+
+    let items: Vec<todo::TodoItem> = if waiting {
+        items.into_iter().filter(|item| item.is_waiting()).collect()
+    } else {
+        items
+    };
+
+    if items.is_empty() && waiting {
+        println!("Nothing is waiting on anyone.");
+        return Ok(());
+    }
+
     if items.is_empty() {
         if is_archived {
             println!(
@@ -595,7 +1379,16 @@ fn handle_show(date: Option<String>, project: Option<String>) -> Result<()> {
 
     for (idx, item) in items.iter().enumerate() {
         let indent = "  ".repeat(item.indent_level);
-        println!("{}{}. {} {}", indent, idx + 1, item.state, item.content);
+        let waiting_on = item
+            .waiting_on
+            .as_ref()
+            .map(|who| format!(" (waiting on {who})"))
+            .unwrap_or_default();
+        let number = match short_ids.get(&item.id) {
+            Some(short_id) if show_ids => short_id.clone(),
+            _ => format!("{}.", idx + 1),
+        };
+        println!("{}{} {} {}{}", indent, number, item.state, item.content, waiting_on);
     }
 
     println!();
@@ -661,6 +1454,9 @@ fn handle_generate(
         ));
     }
 
+    // Whatever gets added came from the plugin
+    history::set_origin(Origin::Plugin(generator_name.clone()));
+
     println!("Fetching data from {generator_name}...");
     let items = plugin_loader
         .call_generate(&generator_name, &input_value)
@@ -720,14 +1516,10 @@ fn handle_generate(
 }
 
 fn add_items_to_today(items: Vec<todo::TodoItem>) -> Result<()> {
-    let mut list = load_today_list_for_project(DEFAULT_PROJECT_NAME)?;
-
-    for item in items {
-        list.items.push(item);
-    }
-
-    save_todo_list_for_project(&list, DEFAULT_PROJECT_NAME)?;
-    Ok(())
+    update_todo_list_for_project(DEFAULT_PROJECT_NAME, Local::now().date_naive(), |list| {
+        list.items.extend(items.iter().cloned());
+        Ok(())
+    })
 }
 
 fn select_items_interactive(items: &[todo::TodoItem]) -> Result<Vec<todo::TodoItem>> {
@@ -752,52 +1544,292 @@ fn select_items_interactive(items: &[todo::TodoItem]) -> Result<Vec<todo::TodoIt
         .collect())
 }
 
+fn handle_pick(print_id: bool, project: Option<String>) -> Result<()> {
+    use dialoguer::Select;
+
+    let items = to_tui::editor::list_today(project.as_deref())?;
+    if items.is_empty() {
+        anyhow::bail!("No todos for today");
+    }
+
+    let labels: Vec<String> = items
+        .iter()
+        .map(|item| {
+            format!(
+                "{:>3} {}[{}] {}",
+                item.short_id,
+                "  ".repeat(item.indent_level),
+                item.state,
+                item.content
+            )
+        })
+        .collect();
+
+    // Prompt on stderr so stdout only carries the selection
+    let Some(index) = Select::new()
+        .with_prompt("Pick a todo")
+        .items(&labels)
+        .default(0)
+        .interact_on_opt(&dialoguer::console::Term::stderr())?
+    else {
+        anyhow::bail!("No todo picked");
+    };
+
+    let item = &items[index];
+    if print_id {
+        println!("{}", item.id);
+    } else {
+        println!("{}", item.content);
+    }
+    Ok(())
+}
+
+fn handle_import_todoist(
+    token: Option<String>,
+    csv: Option<PathBuf>,
+    project: Option<String>,
+    dry_run: bool,
+) -> Result<()> {
+    use storage::importers::todoist;
+
+    let projects = match (token, csv) {
+        (_, Some(path)) => {
+            let content = fs::read_to_string(&path)
+                .with_context(|| format!("Failed to read {}", path.display()))?;
+            let name = match project {
+                Some(name) => name,
+                None => path
+                    .file_stem()
+                    .map(|stem| stem.to_string_lossy().into_owned())
+                    .context("Pass --project to name the project")?,
+            };
+            vec![todoist::from_csv(&content, &name)?]
+        }
+        (Some(token), None) => todoist::fetch(&token)?,
+        (None, None) => anyhow::bail!("Pass --token or --csv"),
+    };
+
+    finish_import(&projects, dry_run)
+}
+
+fn handle_import_github(
+    repo: &str,
+    assignee: Option<String>,
+    project: Option<String>,
+    token: Option<String>,
+    dry_run: bool,
+) -> Result<()> {
+    use storage::importers::github;
+
+    let token = token
+        .or_else(|| env::var("GITHUB_TOKEN").ok())
+        .or_else(|| env::var("GH_TOKEN").ok())
+        .filter(|token| !token.is_empty());
+    let issues = github::fetch(repo, assignee.as_deref(), token.as_deref())?;
+    let project_name = project.unwrap_or_else(|| DEFAULT_PROJECT_NAME.to_string());
+    let mut imported = github::from_issues(repo, issues, &project_name);
+    let skipped = imported.skip_imported()?;
+    if skipped > 0 {
+        println!("Skipping {skipped} issue(s) imported before");
+    }
+    finish_import(&[imported], dry_run)
+}
+
+fn handle_import_ics(path: &Path, project: Option<String>, skip_completed: bool, dry_run: bool) -> Result<()> {
+    let content =
+        fs::read_to_string(path).with_context(|| format!("Failed to read {}", path.display()))?;
+    let project_name = project.unwrap_or_else(|| DEFAULT_PROJECT_NAME.to_string());
+    let mut imported = storage::importers::ics::from_ics(&content, &project_name, skip_completed)?;
+    let skipped = imported.skip_imported()?;
+    if skipped > 0 {
+        println!("Skipping {skipped} to-do(s) imported before");
+    }
+    finish_import(&[imported], dry_run)
+}
+
+fn handle_import_reminders(
+    store: Option<PathBuf>,
+    project: Option<String>,
+    skip_completed: bool,
+    dry_run: bool,
+) -> Result<()> {
+    use storage::importers::reminders;
+
+    let stores = match store {
+        Some(store) => vec![store],
+        None => reminders::default_stores()?,
+    };
+    let project_name = project.unwrap_or_else(|| DEFAULT_PROJECT_NAME.to_string());
+    let mut imported = reminders::from_stores(&stores, &project_name, skip_completed)?;
+    let skipped = imported.skip_imported()?;
+    if skipped > 0 {
+        println!("Skipping {skipped} reminder(s) imported before");
+    }
+    finish_import(&[imported], dry_run)
+}
+
+fn handle_import_jira(
+    config: &Config,
+    jql: Option<String>,
+    project: Option<String>,
+    dry_run: bool,
+) -> Result<()> {
+    use storage::importers::jira;
+
+    let mut jira_config = config
+        .importers
+        .jira
+        .clone()
+        .context("Jira isn't set up: add an [importers.jira] section to the config")?;
+    if project.is_some() {
+        jira_config.project = project;
+    }
+    let jql = jql.unwrap_or_else(|| jira_config.jql.clone());
+    let issues = jira::fetch(&jira_config, &jql)?;
+    let mut imported = jira::from_issues(issues, &jira_config)?;
+    let skipped = imported.skip_imported()?;
+    if skipped > 0 {
+        println!("Skipping {skipped} issue(s) imported before");
+    }
+    finish_import(&[imported], dry_run)
+}
+
+/// Preview the imported items for a dry run, or add them to today's lists.
+fn finish_import(projects: &[storage::importers::ImportedProject], dry_run: bool) -> Result<()> {
+    let total: usize = projects.iter().map(|project| project.items.len()).sum();
+    if total == 0 {
+        println!("Nothing to import");
+        return Ok(());
+    }
+
+    if dry_run {
+        for project in projects {
+            println!("{} ({} items)", project.name, project.items.len());
+            print!("{}", project.preview());
+            println!();
+        }
+        println!("Dry run: {total} item(s) would be added to today's lists");
+        return Ok(());
+    }
+
+    let added = storage::importers::import(projects, Local::now().date_naive())?;
+    println!("✓ Imported {added} item(s) into {} project(s)", projects.len());
+    Ok(())
+}
+
 fn handle_import_archive() -> Result<()> {
     use storage::database::{archive_todos_for_date_and_project, init_database};
-    use storage::markdown::parse_todo_list;
-    use utils::paths::get_dailies_dir_for_project;
+    use storage::markdown::dialect_for_path;
+    use utils::paths::find_daily_files_for_project;
 
     init_database()?;
 
-    let dailies_dir = get_dailies_dir_for_project(DEFAULT_PROJECT_NAME)?;
-    if !dailies_dir.exists() {
-        println!("No dailies directory found at {dailies_dir:?}");
+    // Both the configured layout and the default one written before it
+    let files = find_daily_files_for_project(DEFAULT_PROJECT_NAME)?;
+    if files.is_empty() {
+        println!("No daily files found");
         return Ok(());
     }
 
     let today = Local::now().date_naive();
     let mut imported = 0;
 
-    for entry in std::fs::read_dir(&dailies_dir)? {
-        let entry = entry?;
-        let path = entry.path();
+    for (date, path) in files {
+        let Some(dialect) = dialect_for_path(&path) else {
+            continue;
+        };
+        let filename = path.file_name().and_then(|s| s.to_str()).unwrap_or("");
 
-        if path.extension().map(|e| e == "md").unwrap_or(false) {
-            let filename = path.file_stem().and_then(|s| s.to_str()).unwrap_or("");
+        if date >= today {
+            println!("Skipping {filename} (today or future)");
+            continue;
+        }
 
-            if let Ok(date) = chrono::NaiveDate::parse_from_str(filename, "%Y-%m-%d") {
-                if date >= today {
-                    println!("Skipping {filename} (today or future)");
-                    continue;
-                }
+        let content = storage::encryption::read_to_string(&path)?;
+        let list = dialect.parse(&content, date, path.clone())?;
 
-                let content = std::fs::read_to_string(&path)?;
-                let list = parse_todo_list(&content, date, path.clone())?;
+        if list.items.is_empty() {
+            println!("Skipping {filename} (empty)");
+            continue;
+        }
 
-                if list.items.is_empty() {
-                    println!("Skipping {filename} (empty)");
-                    continue;
-                }
+        storage::database::save_todo_list_for_project(&list, DEFAULT_PROJECT_NAME)?;
+        let count = archive_todos_for_date_and_project(date, DEFAULT_PROJECT_NAME)?;
+        println!("Imported {count} items from {filename}");
+        imported += count;
+    }
 
-                storage::database::save_todo_list_for_project(&list, DEFAULT_PROJECT_NAME)?;
-                let count = archive_todos_for_date_and_project(date, DEFAULT_PROJECT_NAME)?;
-                println!("Imported {count} items from {filename}");
-                imported += count;
-            }
+    println!("\nTotal: {imported} items imported to archive");
+    Ok(())
+}
+
+fn handle_export_archive(path: Option<PathBuf>, project: Option<String>, json: bool) -> Result<()> {
+    use storage::archive_export;
+
+    let days = storage::database::load_archived_days(project.as_deref())?;
+    let day_count = days.len();
+    let summaries = storage::archive_compaction::load_archive_summaries(project.as_deref())?;
+    let projects = archive_export::group(days, summaries);
+    let document = if json {
+        archive_export::to_json(&projects)? + "\n"
+    } else {
+        archive_export::to_markdown(&projects)
+    };
+
+    match path {
+        Some(path) => {
+            std::fs::write(&path, document)
+                .with_context(|| format!("Failed to write {}", path.display()))?;
+            println!(
+                "Exported {day_count} archived day(s) from {} project(s) to {}",
+                projects.len(),
+                path.display()
+            );
         }
+        None => print!("{document}"),
     }
+    Ok(())
+}
 
-    println!("\nTotal: {imported} items imported to archive");
+fn handle_export_all(path: Option<PathBuf>) -> Result<()> {
+    let path = path.unwrap_or_else(|| {
+        PathBuf::from(format!(
+            "totui-export-{}.tar.gz",
+            Local::now().date_naive().format("%Y-%m-%d")
+        ))
+    });
+
+    let manifest = storage::snapshot::export_all(&path)?;
+    println!(
+        "Exported {} project(s) and {} plugin(s) to {}",
+        manifest.projects.len(),
+        manifest.plugins.len(),
+        path.display()
+    );
+    Ok(())
+}
+
+fn handle_import_all(path: &Path, force: bool) -> Result<()> {
+    let manifest = storage::snapshot::import_all(path, force)?;
+    println!(
+        "Imported {} project(s) exported by totui {} on {}",
+        manifest.projects.len(),
+        manifest.app_version,
+        manifest.exported_at.with_timezone(&Local).format("%Y-%m-%d %H:%M")
+    );
+
+    if !manifest.plugins.is_empty() {
+        println!("\nPlugin binaries are not included. Reinstall them with:");
+        for plugin in &manifest.plugins {
+            match plugin.install_command() {
+                Some(command) => println!("  {command}"),
+                None => println!(
+                    "  {} v{} (installed from {} source, reinstall manually)",
+                    plugin.name, plugin.version, plugin.source
+                ),
+            }
+        }
+    }
     Ok(())
 }
 
@@ -891,6 +1923,7 @@ fn handle_plugin_command(command: PluginCommand) -> Result<()> {
             let mut config = Config::load()?;
             config.plugins.enable(&name);
             config.save()?;
+            plugin::quarantine::release(&name)?;
             println!("Plugin '{}' enabled", name);
             Ok(())
         }
@@ -922,6 +1955,12 @@ fn handle_plugin_command(command: PluginCommand) -> Result<()> {
                     println!("Description: {}", info.manifest.description);
                     println!("Path: {:?}", info.path);
                     println!("Enabled: {}", info.enabled);
+                    if !info.enabled
+                        && plugin::quarantine::CrashState::load()
+                            .is_ok_and(|s| s.is_quarantined(&info.manifest.name))
+                    {
+                        println!("Quarantined: disabled automatically after repeated startup crashes");
+                    }
                     println!("Available: {}", info.available);
 
                     if let Some(ref reason) = info.availability_reason {
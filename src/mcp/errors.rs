@@ -50,6 +50,15 @@ impl McpErrorDetail {
         }
     }
 
+    pub fn conflict(message: impl Into<String>) -> Self {
+        Self {
+            code: "CONFLICT".to_string(),
+            message: message.into(),
+            retryable: true,
+            suggestion: Some("Call the tool again to apply it to the list as it is now".to_string()),
+        }
+    }
+
     pub fn storage_error(message: impl Into<String>) -> Self {
         Self {
             code: "STORAGE_ERROR".to_string(),
@@ -58,6 +67,17 @@ impl McpErrorDetail {
             suggestion: None,
         }
     }
+
+    /// As a JSON-RPC error, for requests that aren't tool calls and so can't
+    /// report failures in their result.
+    pub fn into_protocol_error(self) -> rmcp::ErrorData {
+        let data = serde_json::to_value(&self).ok();
+        match self.code.as_str() {
+            "NOT_FOUND" => rmcp::ErrorData::resource_not_found(self.message, data),
+            "INVALID_INPUT" | "VALIDATION_ERROR" => rmcp::ErrorData::invalid_params(self.message, data),
+            _ => rmcp::ErrorData::internal_error(self.message, data),
+        }
+    }
 }
 
 /// Extension trait to simplify converting anyhow::Result to McpErrorDetail
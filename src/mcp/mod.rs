@@ -1,5 +1,74 @@
 pub mod errors;
+pub mod prompts;
+pub mod resources;
 pub mod schemas;
 pub mod server;
 
 pub use server::TodoMcpServer;
+
+use anyhow::Result;
+use rmcp::ServiceExt;
+use tokio::io::{AsyncRead, AsyncWrite};
+use tracing::info;
+
+/// Run the MCP server on a pair of streams, usually stdin and stdout, until
+/// the client hangs up. Messages are newline-delimited JSON-RPC, each
+/// flushed as it is written, so clients that spawn the server as a child
+/// process get the answer to `initialize` right away. Storage locations and
+/// the history origin must be set up first.
+pub async fn serve<R, W>(reader: R, writer: W) -> Result<()>
+where
+    R: AsyncRead + Send + Unpin + 'static,
+    W: AsyncWrite + Send + Unpin + 'static,
+{
+    let service = TodoMcpServer::new()
+        .serve((reader, writer))
+        .await
+        .map_err(|e| anyhow::anyhow!("Failed to create MCP service: {e}"))?;
+    info!("MCP server ready");
+
+    service
+        .waiting()
+        .await
+        .map_err(|e| anyhow::anyhow!("Service error: {e}"))?;
+    info!("MCP client disconnected");
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::Value;
+    use tokio::io::{AsyncBufReadExt, AsyncWriteExt, BufReader};
+
+    #[tokio::test]
+    async fn test_initialize_handshake() {
+        let (client, server) = tokio::io::duplex(64 * 1024);
+        let (server_read, server_write) = tokio::io::split(server);
+        let server = tokio::spawn(serve(server_read, server_write));
+
+        let (client_read, mut client_write) = tokio::io::split(client);
+        let mut lines = BufReader::new(client_read).lines();
+        let initialize = r#"{"jsonrpc":"2.0","id":1,"method":"initialize","params":{"protocolVersion":"2025-03-26","capabilities":{},"clientInfo":{"name":"test","version":"0"}}}"#;
+        client_write.write_all(format!("{initialize}\n").as_bytes()).await.unwrap();
+
+        let response: Value = serde_json::from_str(&lines.next_line().await.unwrap().unwrap()).unwrap();
+        assert_eq!(response["id"], 1);
+        assert!(response["result"]["protocolVersion"].is_string());
+        assert!(response["result"]["capabilities"]["tools"].is_object());
+        assert_eq!(response["result"]["serverInfo"]["name"], "totui");
+
+        client_write
+            .write_all(b"{\"jsonrpc\":\"2.0\",\"method\":\"notifications/initialized\"}\n{\"jsonrpc\":\"2.0\",\"id\":2,\"method\":\"tools/list\"}\n")
+            .await
+            .unwrap();
+        let tools: Value = serde_json::from_str(&lines.next_line().await.unwrap().unwrap()).unwrap();
+        assert_eq!(tools["id"], 2);
+        assert!(!tools["result"]["tools"].as_array().unwrap().is_empty());
+
+        // Hanging up ends the server
+        drop(client_write);
+        drop(lines);
+        server.await.unwrap().unwrap();
+    }
+}
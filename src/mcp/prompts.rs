@@ -0,0 +1,261 @@
+//! MCP prompts for common workflows. Each one embeds the current list or
+//! archive, so a client gets useful context from a single `prompts/get`.
+
+use std::collections::BTreeMap;
+
+use chrono::{Days, Local, NaiveDate};
+use rmcp::model::{GetPromptResult, JsonObject, Prompt, PromptArgument, PromptMessage, PromptMessageRole};
+
+use crate::project::{ProjectRegistry, DEFAULT_PROJECT_NAME};
+use crate::storage::database::{self, ArchiveFilter};
+use crate::storage::file::load_todo_list_for_project;
+use crate::storage::markdown::{serialize_items, serialize_todo_list_clean};
+use crate::todo::{TodoItem, TodoState};
+
+use super::errors::{IntoMcpError, McpErrorDetail};
+
+const PLAN_DAY: &str = "plan_day";
+const WEEKLY_SUMMARY: &str = "weekly_summary";
+const DEFAULT_SUMMARY_DAYS: u64 = 7;
+
+pub fn list() -> Vec<Prompt> {
+    vec![
+        prompt(
+            PLAN_DAY,
+            "Plan my day from these notes",
+            "Turn rough notes into todos for today, next to what is already on the list.",
+            vec![
+                argument("notes", "Meeting notes, a brain dump or anything else to plan from", true),
+                argument("project", "Project to plan in. Defaults to 'default'.", false),
+            ],
+        ),
+        prompt(
+            WEEKLY_SUMMARY,
+            "Summarize what I completed this week",
+            "Summarize the todos completed over the last days, archived days included.",
+            vec![
+                argument("project", "Only this project. Defaults to every project.", false),
+                argument("days", "How many days back to look, today included. Defaults to 7.", false),
+            ],
+        ),
+    ]
+}
+
+pub fn get(name: &str, arguments: Option<&JsonObject>) -> Result<GetPromptResult, McpErrorDetail> {
+    let arg = |key: &str| {
+        arguments
+            .and_then(|args| args.get(key))
+            .and_then(|value| value.as_str())
+            .map(str::trim)
+            .filter(|value| !value.is_empty())
+    };
+
+    let (description, text) = match name {
+        PLAN_DAY => {
+            let notes = arg("notes").ok_or_else(|| {
+                McpErrorDetail::validation_error("The notes argument is required", "Pass the notes to plan from")
+            })?;
+            let project = validated_project(arg("project").unwrap_or(DEFAULT_PROJECT_NAME))?;
+            ("Plan today from notes".to_string(), plan_day(notes, &project)?)
+        }
+        WEEKLY_SUMMARY => {
+            let project = arg("project").map(validated_project).transpose()?;
+            let days = match arg("days") {
+                Some(days) => days.parse::<u64>().ok().filter(|d| (1..=366).contains(d)).ok_or_else(|| {
+                    McpErrorDetail::invalid_input(format!("Invalid days '{days}'"), "Use a whole number from 1 to 366")
+                })?,
+                None => DEFAULT_SUMMARY_DAYS,
+            };
+            (
+                format!("Todos completed in the last {days} days"),
+                weekly_summary(project.as_deref(), days)?,
+            )
+        }
+        _ => {
+            return Err(McpErrorDetail::not_found(
+                format!("Unknown prompt '{name}'"),
+                format!("Available prompts: {PLAN_DAY}, {WEEKLY_SUMMARY}"),
+            ));
+        }
+    };
+
+    Ok(GetPromptResult {
+        description: Some(description),
+        messages: vec![PromptMessage::new_text(PromptMessageRole::User, text)],
+    })
+}
+
+fn plan_day(notes: &str, project: &str) -> Result<String, McpErrorDetail> {
+    let today = Local::now().date_naive();
+    let list = load_todo_list_for_project(project, today).into_mcp_storage_error()?;
+
+    Ok(format!(
+        "Plan my day in the '{project}' project from the notes below.\n\n\
+         Pull out concrete, actionable tasks. Skip anything already on today's list, \
+         nest steps under a parent task where it helps, and set a due date only when \
+         the notes give one. Show me the plan first, then add it with the add_todo tool \
+         (project '{project}') once I agree.\n\n\
+         Today's list as it stands:\n\n{}\n\
+         Notes:\n\n{notes}\n",
+        serialize_todo_list_clean(&list)
+    ))
+}
+
+fn weekly_summary(project: Option<&str>, days: u64) -> Result<String, McpErrorDetail> {
+    let today = Local::now().date_naive();
+    let from = today.checked_sub_days(Days::new(days - 1)).unwrap_or(today);
+    let done = completed_between(project, from, today)?;
+
+    let scope = project.map(|p| format!("the '{p}' project")).unwrap_or_else(|| "all projects".to_string());
+    let mut text = format!(
+        "Summarize what I completed from {from} to {today} in {scope}. \
+         Group related work into themes, call out anything notable, and keep it short \
+         enough to paste into a status update.\n\n"
+    );
+
+    if done.is_empty() {
+        text.push_str("Nothing was marked done in this period.\n");
+        return Ok(text);
+    }
+
+    text.push_str("Completed todos:\n");
+    for ((date, project_name), items) in &done {
+        text.push_str(&format!("\n## {date} ({project_name})\n\n"));
+        text.push_str(&serialize_items(items));
+    }
+    Ok(text)
+}
+
+/// Done todos on live and archived days in the range, by date and project.
+fn completed_between(
+    project: Option<&str>,
+    from: NaiveDate,
+    to: NaiveDate,
+) -> Result<BTreeMap<(NaiveDate, String), Vec<TodoItem>>, McpErrorDetail> {
+    let mut done: BTreeMap<(NaiveDate, String), Vec<TodoItem>> = BTreeMap::new();
+
+    let archived = database::query_archived_days(&ArchiveFilter {
+        project: project.map(str::to_string),
+        from: Some(from),
+        to: Some(to),
+        state: Some(TodoState::Checked),
+        text: None,
+    })
+    .into_mcp_storage_error()?;
+    for day in archived {
+        done.entry((day.date, day.project)).or_default().extend(day.items);
+    }
+
+    let projects: Vec<String> = match project {
+        Some(project) => vec![project.to_string()],
+        None => {
+            let registry = ProjectRegistry::load().into_mcp_storage_error()?;
+            registry.list_sorted().iter().map(|p| p.name.clone()).collect()
+        }
+    };
+    for project_name in projects {
+        for date in from.iter_days().take_while(|date| *date <= to) {
+            let items = database::load_todos_for_date_and_project(date, &project_name).into_mcp_storage_error()?;
+            let items: Vec<TodoItem> = items.into_iter().filter(|item| item.state == TodoState::Checked).collect();
+            if !items.is_empty() {
+                done.entry((date, project_name.clone())).or_default().extend(items);
+            }
+        }
+    }
+
+    // Nesting is lost once items are filtered, so list them flat
+    for items in done.values_mut() {
+        for item in items.iter_mut() {
+            item.indent_level = 0;
+        }
+    }
+    Ok(done)
+}
+
+fn validated_project(name: &str) -> Result<String, McpErrorDetail> {
+    let registry = ProjectRegistry::load().into_mcp_storage_error()?;
+    if registry.get_by_name(name).is_none() {
+        return Err(McpErrorDetail::not_found(
+            format!("Project '{name}' not found"),
+            "Use list_projects to see available projects",
+        ));
+    }
+    Ok(name.to_string())
+}
+
+fn prompt(name: &str, title: &str, description: &str, arguments: Vec<PromptArgument>) -> Prompt {
+    let mut prompt = Prompt::new(name, Some(description), Some(arguments));
+    prompt.title = Some(title.to_string());
+    prompt
+}
+
+fn argument(name: &str, description: &str, required: bool) -> PromptArgument {
+    PromptArgument {
+        name: name.to_string(),
+        title: None,
+        description: Some(description.to_string()),
+        required: Some(required),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::storage::database::{archive_todos_for_date_and_project, ensure_default_project_exists, init_database};
+    use crate::storage::file::save_todo_list_for_project;
+    use serial_test::serial;
+    use tempfile::TempDir;
+
+    fn args(pairs: &[(&str, &str)]) -> JsonObject {
+        pairs
+            .iter()
+            .map(|(k, v)| (k.to_string(), serde_json::Value::String(v.to_string())))
+            .collect()
+    }
+
+    fn text(result: &GetPromptResult) -> String {
+        match &result.messages[0].content {
+            rmcp::model::PromptMessageContent::Text { text } => text.clone(),
+            other => panic!("expected text, got {other:?}"),
+        }
+    }
+
+    #[test]
+    #[serial]
+    fn test_prompts_embed_list_and_archive() {
+        let temp_dir = TempDir::new().unwrap();
+        std::fs::create_dir_all(temp_dir.path().join(".to-tui")).unwrap();
+        unsafe {
+            std::env::set_var("HOME", temp_dir.path());
+        }
+        init_database().unwrap();
+        ensure_default_project_exists().unwrap();
+
+        let today = Local::now().date_naive();
+        let yesterday = today.pred_opt().unwrap();
+        let mut list = load_todo_list_for_project(DEFAULT_PROJECT_NAME, yesterday).unwrap();
+        list.add_item("Ship the release".to_string());
+        list.add_item("Write the blog post".to_string());
+        list.items[0].set_state(TodoState::Checked);
+        save_todo_list_for_project(&list, DEFAULT_PROJECT_NAME).unwrap();
+        archive_todos_for_date_and_project(yesterday, DEFAULT_PROJECT_NAME).unwrap();
+
+        let mut list = load_todo_list_for_project(DEFAULT_PROJECT_NAME, today).unwrap();
+        list.add_item("Review PRs".to_string());
+        list.items[0].set_state(TodoState::Checked);
+        save_todo_list_for_project(&list, DEFAULT_PROJECT_NAME).unwrap();
+
+        let summary = text(&get(WEEKLY_SUMMARY, None).unwrap());
+        assert_eq!(summary.matches("- [x] Ship the release").count(), 1, "{summary}");
+        assert!(summary.contains("- [x] Review PRs"), "{summary}");
+        assert!(!summary.contains("blog post"), "{summary}");
+
+        let plan = text(&get(PLAN_DAY, Some(&args(&[("notes", "call the bank")]))).unwrap());
+        assert!(plan.contains("- [x] Review PRs"), "{plan}");
+        assert!(plan.contains("call the bank"), "{plan}");
+
+        assert_eq!(get(PLAN_DAY, None).unwrap_err().code, "VALIDATION_ERROR");
+        assert_eq!(get(WEEKLY_SUMMARY, Some(&args(&[("days", "0")]))).unwrap_err().code, "INVALID_INPUT");
+        assert_eq!(get("nope", None).unwrap_err().code, "NOT_FOUND");
+    }
+}
@@ -0,0 +1,221 @@
+//! MCP resources: each project's daily list as markdown, plus an index of
+//! projects, so clients can pull context without calling tools.
+//!
+//! - `totui://projects` lists every project with the URI of today's list.
+//! - `totui://{project}/{date}` is a project's list for a day. The date is
+//!   `YYYY-MM-DD` or a phrase like `today` or `yesterday`; the project name
+//!   is percent-encoded.
+
+use chrono::{Local, NaiveDate};
+use rmcp::model::{AnnotateAble, RawResource, RawResourceTemplate, Resource, ResourceTemplate};
+
+use crate::project::ProjectRegistry;
+use crate::storage::file::load_todo_list_for_project;
+use crate::storage::markdown::serialize_todo_list_clean;
+
+use super::errors::{IntoMcpError, McpErrorDetail};
+use super::schemas::parse_date;
+
+const SCHEME: &str = "totui://";
+pub const PROJECTS_URI: &str = "totui://projects";
+const MARKDOWN: &str = "text/markdown";
+
+/// What a `totui://` URI points at.
+#[derive(Debug, PartialEq, Eq)]
+pub enum ResourceUri {
+    Projects,
+    Day { project: String, date: NaiveDate },
+}
+
+impl ResourceUri {
+    pub fn parse(uri: &str) -> Result<Self, McpErrorDetail> {
+        let path = uri.strip_prefix(SCHEME).ok_or_else(|| unknown_uri(uri))?;
+        if uri == PROJECTS_URI {
+            return Ok(Self::Projects);
+        }
+
+        // Project names can't hold a slash, so the last one splits off the date
+        let (project, date) = path.rsplit_once('/').ok_or_else(|| unknown_uri(uri))?;
+        let project = decode_segment(project).ok_or_else(|| unknown_uri(uri))?;
+        if project.is_empty() {
+            return Err(unknown_uri(uri));
+        }
+        let date = parse_date(Some(&decode_segment(date).ok_or_else(|| unknown_uri(uri))?))
+            .map_err(|msg| McpErrorDetail::invalid_input(msg, "Use YYYY-MM-DD or a phrase like 'today'"))?;
+
+        Ok(Self::Day { project, date })
+    }
+}
+
+pub fn day_uri(project: &str, date: NaiveDate) -> String {
+    format!("{SCHEME}{}/{}", encode_segment(project), date.format("%Y-%m-%d"))
+}
+
+/// The project index and today's list for every project.
+pub fn list() -> Result<Vec<Resource>, McpErrorDetail> {
+    let registry = ProjectRegistry::load().into_mcp_storage_error()?;
+    let today = Local::now().date_naive();
+
+    let mut resources = vec![markdown_resource(
+        RawResource::new(PROJECTS_URI, "projects"),
+        "Projects",
+        "Every project, with a link to its list for today",
+    )];
+    for project in registry.list_sorted() {
+        resources.push(markdown_resource(
+            RawResource::new(day_uri(&project.name, today), format!("{}/today", project.name)),
+            &format!("{} - today", project.name),
+            "Today's todo list as markdown",
+        ));
+    }
+    Ok(resources)
+}
+
+pub fn templates() -> Vec<ResourceTemplate> {
+    vec![
+        RawResourceTemplate {
+            uri_template: format!("{SCHEME}{{project}}/{{date}}"),
+            name: "day".to_string(),
+            title: Some("Todo list for a day".to_string()),
+            description: Some(
+                "A project's todo list as markdown. date is YYYY-MM-DD or a phrase like 'today' or 'yesterday'."
+                    .to_string(),
+            ),
+            mime_type: Some(MARKDOWN.to_string()),
+        }
+        .no_annotation(),
+    ]
+}
+
+/// The markdown behind a URI. Reading never rolls a list over.
+pub fn read(uri: &str) -> Result<String, McpErrorDetail> {
+    let registry = ProjectRegistry::load().into_mcp_storage_error()?;
+
+    match ResourceUri::parse(uri)? {
+        ResourceUri::Projects => {
+            let today = Local::now().date_naive();
+            let mut output = String::from("# Projects\n\n");
+            for project in registry.list_sorted() {
+                output.push_str(&format!("- {} ({})\n", project.name, day_uri(&project.name, today)));
+            }
+            Ok(output)
+        }
+        ResourceUri::Day { project, date } => {
+            if registry.get_by_name(&project).is_none() {
+                return Err(McpErrorDetail::not_found(
+                    format!("Project '{project}' not found"),
+                    format!("Read {PROJECTS_URI} to see available projects"),
+                ));
+            }
+            let list = load_todo_list_for_project(&project, date).into_mcp_storage_error()?;
+            Ok(serialize_todo_list_clean(&list))
+        }
+    }
+}
+
+fn markdown_resource(mut raw: RawResource, title: &str, description: &str) -> Resource {
+    raw.title = Some(title.to_string());
+    raw.description = Some(description.to_string());
+    raw.mime_type = Some(MARKDOWN.to_string());
+    raw.no_annotation()
+}
+
+fn unknown_uri(uri: &str) -> McpErrorDetail {
+    McpErrorDetail::not_found(
+        format!("Unknown resource '{uri}'"),
+        format!("Use {PROJECTS_URI} or {SCHEME}{{project}}/{{date}}"),
+    )
+}
+
+fn encode_segment(segment: &str) -> String {
+    segment
+        .bytes()
+        .map(|b| match b {
+            b'A'..=b'Z' | b'a'..=b'z' | b'0'..=b'9' | b'-' | b'.' | b'_' | b'~' => (b as char).to_string(),
+            _ => format!("%{b:02X}"),
+        })
+        .collect()
+}
+
+/// None when an escape is malformed or the result isn't UTF-8.
+fn decode_segment(segment: &str) -> Option<String> {
+    let bytes = segment.as_bytes();
+    let mut decoded = Vec::with_capacity(bytes.len());
+    let mut i = 0;
+    while i < bytes.len() {
+        if bytes[i] == b'%' {
+            let hex = segment.get(i + 1..i + 3)?;
+            decoded.push(u8::from_str_radix(hex, 16).ok()?);
+            i += 3;
+        } else {
+            decoded.push(bytes[i]);
+            i += 1;
+        }
+    }
+    String::from_utf8(decoded).ok()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::project::DEFAULT_PROJECT_NAME;
+    use crate::storage::database::{ensure_default_project_exists, init_database};
+    use crate::storage::file::save_todo_list_for_project;
+    use serial_test::serial;
+    use tempfile::TempDir;
+
+    #[test]
+    fn test_parse_resource_uri() {
+        assert_eq!(ResourceUri::parse("totui://projects").unwrap(), ResourceUri::Projects);
+
+        let date = NaiveDate::from_ymd_opt(2026, 3, 14).unwrap();
+        let uri = day_uri("Side work", date);
+        assert_eq!(uri, "totui://Side%20work/2026-03-14");
+        assert_eq!(
+            ResourceUri::parse(&uri).unwrap(),
+            ResourceUri::Day {
+                project: "Side work".to_string(),
+                date
+            }
+        );
+        assert_eq!(
+            ResourceUri::parse("totui://default/today").unwrap(),
+            ResourceUri::Day {
+                project: "default".to_string(),
+                date: Local::now().date_naive()
+            }
+        );
+
+        assert!(ResourceUri::parse("file:///etc/passwd").is_err());
+        assert!(ResourceUri::parse("totui://default").is_err());
+        assert!(ResourceUri::parse("totui:///2026-03-14").is_err());
+        assert!(ResourceUri::parse("totui://default/someday").is_err());
+        assert!(ResourceUri::parse("totui://bad%2/today").is_err());
+    }
+
+    #[test]
+    #[serial]
+    fn test_read_day_and_project_index() {
+        let temp_dir = TempDir::new().unwrap();
+        std::fs::create_dir_all(temp_dir.path().join(".to-tui")).unwrap();
+        unsafe {
+            std::env::set_var("HOME", temp_dir.path());
+        }
+        init_database().unwrap();
+        ensure_default_project_exists().unwrap();
+
+        let date = NaiveDate::from_ymd_opt(2026, 3, 14).unwrap();
+        let mut list = load_todo_list_for_project(DEFAULT_PROJECT_NAME, date).unwrap();
+        list.add_item("Buy pi".to_string());
+        save_todo_list_for_project(&list, DEFAULT_PROJECT_NAME).unwrap();
+
+        let day = read(&day_uri(DEFAULT_PROJECT_NAME, date)).unwrap();
+        assert!(day.contains("- [ ] Buy pi"), "{day}");
+
+        let index = read(PROJECTS_URI).unwrap();
+        assert!(index.contains("- default (totui://default/"), "{index}");
+
+        let missing = read("totui://nope/today").unwrap_err();
+        assert_eq!(missing.code, "NOT_FOUND");
+    }
+}
@@ -4,11 +4,12 @@ use serde::{Deserialize, Serialize};
 use uuid::Uuid;
 
 use crate::project::Project;
+use crate::storage::database::SearchHit;
 use crate::todo::{TodoItem, TodoState};
 
 #[derive(Debug, Deserialize, JsonSchema)]
 pub struct ListTodosRequest {
-    #[schemars(description = "Date in YYYY-MM-DD format. Defaults to today if not provided.")]
+    #[schemars(description = "Date in YYYY-MM-DD format or a phrase like 'yesterday'. Defaults to today if not provided.")]
     pub date: Option<String>,
     #[schemars(description = "Project name. Defaults to 'default' if not provided.")]
     pub project: Option<String>,
@@ -41,16 +42,16 @@ pub struct ProjectListResponse {
 }
 
 #[derive(Debug, Deserialize, JsonSchema)]
-pub struct CreateTodoRequest {
+pub struct AddTodoRequest {
     #[schemars(description = "The todo content text. Cannot be empty.")]
     pub content: String,
-    #[schemars(description = "Date in YYYY-MM-DD format. Defaults to today if not provided.")]
+    #[schemars(description = "Date in YYYY-MM-DD format or a phrase like 'yesterday'. Defaults to today if not provided.")]
     pub date: Option<String>,
     #[schemars(
         description = "UUID of parent todo to nest under. Use list_todos to get valid IDs."
     )]
     pub parent_id: Option<String>,
-    #[schemars(description = "Due date in YYYY-MM-DD format.")]
+    #[schemars(description = "Due date in YYYY-MM-DD format or a phrase like 'tomorrow', 'next friday', 'in 3 days'.")]
     pub due_date: Option<String>,
     #[schemars(description = "Additional notes or description for the todo.")]
     pub description: Option<String>,
@@ -62,7 +63,7 @@ pub struct CreateTodoRequest {
 pub struct UpdateTodoRequest {
     #[schemars(description = "UUID of the todo to update. Use list_todos to get valid IDs.")]
     pub id: String,
-    #[schemars(description = "Date in YYYY-MM-DD format. Defaults to today if not provided.")]
+    #[schemars(description = "Date in YYYY-MM-DD format or a phrase like 'yesterday'. Defaults to today if not provided.")]
     pub date: Option<String>,
     #[schemars(description = "New content text for the todo.")]
     pub content: Option<String>,
@@ -70,7 +71,7 @@ pub struct UpdateTodoRequest {
         description = "New state: ' ' (empty/pending), 'x' (done), '?' (question), '!' (important)"
     )]
     pub state: Option<String>,
-    #[schemars(description = "New due date in YYYY-MM-DD format.")]
+    #[schemars(description = "New due date in YYYY-MM-DD format or a phrase like 'tomorrow', 'next friday', 'in 3 days'.")]
     pub due_date: Option<String>,
     #[schemars(description = "New description. Empty string clears the description.")]
     pub description: Option<String>,
@@ -82,19 +83,19 @@ pub struct UpdateTodoRequest {
 pub struct DeleteTodoRequest {
     #[schemars(description = "UUID of the todo to delete. This also deletes all child todos.")]
     pub id: String,
-    #[schemars(description = "Date in YYYY-MM-DD format. Defaults to today if not provided.")]
+    #[schemars(description = "Date in YYYY-MM-DD format or a phrase like 'yesterday'. Defaults to today if not provided.")]
     pub date: Option<String>,
     #[schemars(description = "Project name. Defaults to 'default' if not provided.")]
     pub project: Option<String>,
 }
 
 #[derive(Debug, Deserialize, JsonSchema)]
-pub struct MarkCompleteRequest {
-    #[schemars(
-        description = "UUID of the todo to toggle completion. Use list_todos to get valid IDs."
-    )]
+pub struct CompleteTodoRequest {
+    #[schemars(description = "UUID of the todo to complete. Use list_todos to get valid IDs.")]
     pub id: String,
-    #[schemars(description = "Date in YYYY-MM-DD format. Defaults to today if not provided.")]
+    #[schemars(description = "Set to false to reopen a done todo. Defaults to true.")]
+    pub done: Option<bool>,
+    #[schemars(description = "Date in YYYY-MM-DD format or a phrase like 'yesterday'. Defaults to today if not provided.")]
     pub date: Option<String>,
     #[schemars(description = "Project name. Defaults to 'default' if not provided.")]
     pub project: Option<String>,
@@ -217,6 +218,62 @@ impl TodoListResponse {
     }
 }
 
+#[derive(Debug, Deserialize, JsonSchema)]
+pub struct SearchTodosRequest {
+    #[schemars(description = "Words to look for in todo content and descriptions. Every word must match, prefixes count.")]
+    pub query: String,
+    #[schemars(description = "Project name. Searches every project if not provided.")]
+    pub project: Option<String>,
+    #[schemars(description = "Maximum number of results. Defaults to 20.")]
+    pub limit: Option<usize>,
+}
+
+#[derive(Debug, Serialize, JsonSchema)]
+pub struct SearchHitResponse {
+    pub id: String,
+    pub date: String,
+    pub project: String,
+    pub content: String,
+    pub state: String,
+    #[schemars(description = "True when the todo was rolled over or archived and is no longer on a live list.")]
+    pub archived: bool,
+}
+
+impl From<&SearchHit> for SearchHitResponse {
+    fn from(hit: &SearchHit) -> Self {
+        Self {
+            id: hit.id.to_string(),
+            date: hit.date.format("%Y-%m-%d").to_string(),
+            project: hit.project.clone(),
+            content: hit.content.clone(),
+            state: hit.state.to_char().to_string(),
+            archived: hit.archived,
+        }
+    }
+}
+
+#[derive(Debug, Serialize, JsonSchema)]
+pub struct SearchTodosResponse {
+    pub count: usize,
+    pub hits: Vec<SearchHitResponse>,
+}
+
+#[derive(Debug, Deserialize, JsonSchema)]
+pub struct RolloverRequest {
+    #[schemars(description = "Project name. Defaults to 'default' if not provided.")]
+    pub project: Option<String>,
+}
+
+#[derive(Debug, Serialize, JsonSchema)]
+pub struct RolloverResponse {
+    #[schemars(description = "Number of unfinished todos carried over to today. 0 when there was nothing to roll over.")]
+    pub rolled_over: usize,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    #[schemars(description = "The day the todos came from. That day is archived.")]
+    pub from_date: Option<String>,
+    pub message: String,
+}
+
 #[derive(Debug, Serialize, JsonSchema)]
 pub struct DeleteTodoResponse {
     pub deleted_count: usize,
@@ -225,8 +282,7 @@ pub struct DeleteTodoResponse {
 
 pub fn parse_date(date_str: Option<&str>) -> Result<NaiveDate, String> {
     match date_str {
-        Some(s) => NaiveDate::parse_from_str(s, "%Y-%m-%d")
-            .map_err(|_| format!("Invalid date format '{s}'. Use YYYY-MM-DD format.")),
+        Some(s) => crate::utils::dateparse::parse_date(s).map_err(|e| e.to_string()),
         None => Ok(chrono::Local::now().date_naive()),
     }
 }
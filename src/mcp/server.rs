@@ -2,21 +2,32 @@ use chrono::Local;
 use rmcp::{
     Json,
     handler::server::{router::tool::ToolRouter, wrapper::Parameters},
-    model::{ServerCapabilities, ServerInfo},
+    RoleServer,
+    model::{
+        GetPromptRequestParam, GetPromptResult, Implementation, ListPromptsResult, ListResourceTemplatesResult,
+        ListResourcesResult, PaginatedRequestParam, ReadResourceRequestParam, ReadResourceResult, ResourceContents,
+        ServerCapabilities, ServerInfo,
+    },
+    service::RequestContext,
     tool, tool_handler, tool_router,
 };
 use tracing::{debug, error, info, warn};
 
 use crate::project::{ProjectRegistry, DEFAULT_PROJECT_NAME};
-use crate::storage::database::soft_delete_todos_for_project;
-use crate::storage::file::{file_exists_for_project, load_todo_list_for_project, save_todo_list_for_project};
-use crate::storage::rollover::create_rolled_over_list_for_project;
-use crate::todo::{TodoItem, TodoList};
+use crate::storage::database;
+use crate::storage::file::{load_todo_list_with_revision, save_todo_list_if_current, trash_todos_if_current};
+use crate::storage::revision::{day_revision, stale_day};
+use crate::storage::rollover::{
+    create_rolled_over_list_for_project, execute_rollover_for_project, find_rollover_candidates_for_project,
+};
+use crate::todo::{TodoItem, TodoList, TodoState};
 
 use super::errors::{IntoMcpError, McpErrorDetail};
+use super::{prompts, resources};
 use super::schemas::{
-    CreateTodoRequest, DeleteTodoRequest, DeleteTodoResponse, ListProjectsRequest, ListTodosRequest,
-    MarkCompleteRequest, ProjectItemResponse, ProjectListResponse, TodoItemResponse, TodoListResponse,
+    AddTodoRequest, CompleteTodoRequest, DeleteTodoRequest, DeleteTodoResponse, ListProjectsRequest,
+    ListTodosRequest, ProjectItemResponse, ProjectListResponse, RolloverRequest, RolloverResponse,
+    SearchHitResponse, SearchTodosRequest, SearchTodosResponse, TodoItemResponse, TodoListResponse,
     UpdateTodoRequest, parse_date, parse_state, parse_uuid,
 };
 
@@ -39,36 +50,45 @@ impl Default for TodoMcpServer {
     }
 }
 
-fn load_list_with_rollover(project: &str, date: chrono::NaiveDate) -> Result<TodoList, McpErrorDetail> {
+/// The day's list, rolling over unfinished todos first when it is today, and
+/// the revision to save it back against.
+fn load_list_with_rollover(project: &str, date: chrono::NaiveDate) -> Result<(TodoList, u64), McpErrorDetail> {
     let today = Local::now().date_naive();
 
-    if date == today && !file_exists_for_project(project, date).into_mcp_storage_error()? {
-        debug!(date = %date, project = %project, "No todos for today, checking for rollover candidates");
-        for days_back in 1..=30 {
-            if let Some(check_date) = today.checked_sub_days(chrono::Days::new(days_back))
-                && file_exists_for_project(project, check_date).into_mcp_storage_error()? {
-                    let list = load_todo_list_for_project(project, check_date).into_mcp_storage_error()?;
-                    let incomplete = list.get_incomplete_items();
-
-                    if !incomplete.is_empty() {
-                        info!(
-                            from_date = %check_date,
-                            to_date = %today,
-                            project = %project,
-                            count = incomplete.len(),
-                            "Rolling over incomplete todos"
-                        );
-                        let rolled_list =
-                            create_rolled_over_list_for_project(project, today, incomplete).into_mcp_storage_error()?;
-                        save_todo_list_for_project(&rolled_list, project).into_mcp_storage_error()?;
-                        return Ok(rolled_list);
-                    }
-                    break;
-                }
+    if date == today {
+        debug!(date = %date, project = %project, "Checking for rollover candidates");
+        if let Some((from_date, incomplete)) =
+            find_rollover_candidates_for_project(project).into_mcp_storage_error()?
+        {
+            info!(
+                from_date = %from_date,
+                to_date = %today,
+                project = %project,
+                count = incomplete.len(),
+                "Rolling over incomplete todos"
+            );
+            let revision = day_revision(today, project).into_mcp_storage_error()?;
+            let rolled_list =
+                create_rolled_over_list_for_project(project, today, incomplete).into_mcp_storage_error()?;
+            let revision = save_if_current(&rolled_list, project, revision)?;
+            return Ok((rolled_list, revision));
         }
     }
 
-    load_todo_list_for_project(project, date).into_mcp_storage_error()
+    load_todo_list_with_revision(project, date).into_mcp_storage_error()
+}
+
+/// Save the list unless the day was saved elsewhere since it was loaded at
+/// `revision`, and return its new revision.
+fn save_if_current(list: &TodoList, project: &str, revision: u64) -> Result<u64, McpErrorDetail> {
+    save_todo_list_if_current(list, project, revision).map_err(save_error)
+}
+
+fn save_error(e: anyhow::Error) -> McpErrorDetail {
+    match stale_day(&e) {
+        Some(_) => McpErrorDetail::conflict("The list was changed elsewhere while this call ran; nothing was saved"),
+        None => McpErrorDetail::storage_error(e.to_string()),
+    }
 }
 
 fn get_validated_project(project: Option<&str>) -> Result<String, McpErrorDetail> {
@@ -97,7 +117,7 @@ fn format_error(detail: McpErrorDetail) -> String {
 
 fn parse_date_or_err(date_str: Option<&str>) -> Result<chrono::NaiveDate, String> {
     parse_date(date_str).map_err(|msg| {
-        format_error(McpErrorDetail::invalid_input(&msg, "Use YYYY-MM-DD format"))
+        format_error(McpErrorDetail::invalid_input(&msg, "Use YYYY-MM-DD or a phrase like 'tomorrow'"))
     })
 }
 
@@ -122,7 +142,7 @@ impl TodoMcpServer {
         let project = get_validated_project(params.0.project.as_deref()).map_err(format_error)?;
         let date = parse_date_or_err(params.0.date.as_deref())?;
 
-        let list = load_list_with_rollover(&project, date).map_err(format_error)?;
+        let (list, _) = load_list_with_rollover(&project, date).map_err(format_error)?;
 
         let items: Vec<TodoItemResponse> = list.items.iter().map(TodoItemResponse::from).collect();
         let response = TodoListResponse::new(list.date.format("%Y-%m-%d").to_string(), items);
@@ -159,12 +179,12 @@ impl TodoMcpServer {
     }
 
     #[tool(
-        name = "create_todo",
-        description = "Create a new todo item in a project. Optionally nest under a parent todo by providing parent_id."
+        name = "add_todo",
+        description = "Add a new todo item to a project. Optionally nest under a parent todo by providing parent_id."
     )]
-    async fn create_todo(
+    async fn add_todo(
         &self,
-        params: Parameters<CreateTodoRequest>,
+        params: Parameters<AddTodoRequest>,
     ) -> Result<Json<TodoItemResponse>, String> {
         let req = params.0;
         info!(
@@ -172,7 +192,7 @@ impl TodoMcpServer {
             date = ?req.date,
             project = ?req.project,
             parent_id = ?req.parent_id,
-            "create_todo called"
+            "add_todo called"
         );
 
         if req.content.trim().is_empty() {
@@ -185,7 +205,7 @@ impl TodoMcpServer {
         let project = get_validated_project(req.project.as_deref()).map_err(format_error)?;
         let date = parse_date_or_err(req.date.as_deref())?;
 
-        let mut list = load_list_with_rollover(&project, date).map_err(format_error)?;
+        let (mut list, revision) = load_list_with_rollover(&project, date).map_err(format_error)?;
 
         let due_date = req
             .due_date
@@ -217,10 +237,9 @@ impl TodoMcpServer {
         let response = TodoItemResponse::from(&item);
         list.items.insert(insert_index, item);
 
-        save_todo_list_for_project(&list, &project)
-            .into_mcp_storage_error().map_err(format_error)?;
+        save_if_current(&list, &project, revision).map_err(format_error)?;
 
-        info!(id = %response.id, content = %response.content, project = %project, "create_todo completed");
+        info!(id = %response.id, content = %response.content, project = %project, "add_todo completed");
         Ok(Json(response))
     }
 
@@ -246,7 +265,7 @@ impl TodoMcpServer {
         let id = parse_uuid_or_err(&req.id)?;
         let date = parse_date_or_err(req.date.as_deref())?;
 
-        let mut list = load_list_with_rollover(&project, date).map_err(format_error)?;
+        let (mut list, revision) = load_list_with_rollover(&project, date).map_err(format_error)?;
 
         let item = list
             .items
@@ -293,8 +312,7 @@ impl TodoMcpServer {
 
         let response = TodoItemResponse::from(&*item);
 
-        save_todo_list_for_project(&list, &project)
-            .into_mcp_storage_error().map_err(format_error)?;
+        save_if_current(&list, &project, revision).map_err(format_error)?;
 
         info!(id = %response.id, state = %response.state, project = %project, "update_todo completed");
         Ok(Json(response))
@@ -315,7 +333,7 @@ impl TodoMcpServer {
         let id = parse_uuid_or_err(&req.id)?;
         let date = parse_date_or_err(req.date.as_deref())?;
 
-        let mut list = load_list_with_rollover(&project, date).map_err(format_error)?;
+        let (mut list, revision) = load_list_with_rollover(&project, date).map_err(format_error)?;
 
         let idx = list
             .items
@@ -334,15 +352,12 @@ impl TodoMcpServer {
 
         let deleted_count = end - start;
 
-        let ids: Vec<_> = list.items[start..end].iter().map(|item| item.id).collect();
-        soft_delete_todos_for_project(&ids, date, &project)
-            .into_mcp_storage_error().map_err(format_error)?;
-
-        list.items.drain(start..end);
+        let ids: Vec<_> = list.items.drain(start..end).map(|item| item.id).collect();
         list.recalculate_parent_ids();
 
-        save_todo_list_for_project(&list, &project)
-            .into_mcp_storage_error().map_err(format_error)?;
+        trash_todos_if_current(&list, &ids, &project, revision)
+            .map_err(save_error)
+            .map_err(format_error)?;
 
         info!(deleted_count = deleted_count, project = %project, "delete_todo completed");
         Ok(Json(DeleteTodoResponse {
@@ -352,21 +367,21 @@ impl TodoMcpServer {
     }
 
     #[tool(
-        name = "mark_complete",
-        description = "Toggle completion status: marks a todo as done [x] if pending, or pending [ ] if already done."
+        name = "complete_todo",
+        description = "Mark a todo as done [x]. Completing a done todo changes nothing; pass done=false to reopen it as pending [ ]."
     )]
-    async fn mark_complete(
+    async fn complete_todo(
         &self,
-        params: Parameters<MarkCompleteRequest>,
+        params: Parameters<CompleteTodoRequest>,
     ) -> Result<Json<TodoItemResponse>, String> {
         let req = params.0;
-        info!(id = %req.id, date = ?req.date, project = ?req.project, "mark_complete called");
+        info!(id = %req.id, done = ?req.done, date = ?req.date, project = ?req.project, "complete_todo called");
 
         let project = get_validated_project(req.project.as_deref()).map_err(format_error)?;
         let id = parse_uuid_or_err(&req.id)?;
         let date = parse_date_or_err(req.date.as_deref())?;
 
-        let mut list = load_list_with_rollover(&project, date).map_err(format_error)?;
+        let (mut list, revision) = load_list_with_rollover(&project, date).map_err(format_error)?;
 
         let item = list
             .items
@@ -379,15 +394,93 @@ impl TodoMcpServer {
                 ))
             })?;
 
-        item.toggle_state();
+        let state = if req.done.unwrap_or(true) {
+            TodoState::Checked
+        } else {
+            TodoState::Empty
+        };
+        if item.state != state {
+            item.set_state(state);
+        }
         let response = TodoItemResponse::from(&*item);
 
-        save_todo_list_for_project(&list, &project)
-            .into_mcp_storage_error().map_err(format_error)?;
+        save_if_current(&list, &project, revision).map_err(format_error)?;
+
+        info!(id = %response.id, new_state = %response.state, project = %project, "complete_todo completed");
+        Ok(Json(response))
+    }
 
-        info!(id = %response.id, new_state = %response.state, project = %project, "mark_complete completed");
+    #[tool(
+        name = "search_todos",
+        description = "Search todo content and descriptions across every date, including archived days. Returns newest matches first."
+    )]
+    async fn search_todos(
+        &self,
+        params: Parameters<SearchTodosRequest>,
+    ) -> Result<Json<SearchTodosResponse>, String> {
+        let req = params.0;
+        info!(query = %req.query, project = ?req.project, limit = ?req.limit, "search_todos called");
+
+        if req.query.trim().is_empty() {
+            return Err(format_error(McpErrorDetail::validation_error(
+                "Query cannot be empty",
+                "Provide one or more words to search for",
+            )));
+        }
+
+        let project = req
+            .project
+            .as_deref()
+            .map(|p| get_validated_project(Some(p)))
+            .transpose()
+            .map_err(format_error)?;
+
+        let hits = database::search_todos(&req.query, project.as_deref(), req.limit.unwrap_or(20))
+            .into_mcp_storage_error()
+            .map_err(format_error)?;
+
+        let hits: Vec<SearchHitResponse> = hits.iter().map(SearchHitResponse::from).collect();
+        let response = SearchTodosResponse { count: hits.len(), hits };
+
+        info!(count = response.count, "search_todos returning hits");
         Ok(Json(response))
     }
+
+    #[tool(
+        name = "rollover",
+        description = "Carry unfinished todos from the most recent previous day over to today and archive that day. Does nothing if today's list already exists."
+    )]
+    async fn rollover(
+        &self,
+        params: Parameters<RolloverRequest>,
+    ) -> Result<Json<RolloverResponse>, String> {
+        info!(project = ?params.0.project, "rollover called");
+
+        let project = get_validated_project(params.0.project.as_deref()).map_err(format_error)?;
+
+        let Some((from_date, items)) = find_rollover_candidates_for_project(&project)
+            .into_mcp_storage_error()
+            .map_err(format_error)?
+        else {
+            return Ok(Json(RolloverResponse {
+                rolled_over: 0,
+                from_date: None,
+                message: "Nothing to roll over".to_string(),
+            }));
+        };
+
+        let list = execute_rollover_for_project(&project, from_date, items)
+            .into_mcp_storage_error()
+            .map_err(format_error)?;
+
+        let rolled_over = list.items.len();
+        info!(from_date = %from_date, count = rolled_over, project = %project, "rollover completed");
+        Ok(Json(RolloverResponse {
+            rolled_over,
+            from_date: Some(from_date.format("%Y-%m-%d").to_string()),
+            message: format!("Rolled over {rolled_over} item(s) from {from_date}"),
+        }))
+    }
 }
 
 #[tool_handler(router = self.tool_router)]
@@ -398,21 +491,182 @@ impl rmcp::ServerHandler for TodoMcpServer {
                 "Todo list management server.\n\n\
                 TOOLS:\n\
                 - list_todos: List todos. Response has 'formatted' field - display it directly as markdown.\n\
-                - create_todo: Create new todo. Can nest under parent via parent_id.\n\
+                - add_todo: Add a new todo. Can nest under parent via parent_id.\n\
                 - update_todo: Update content/state/due_date. States: ' '=pending, 'x'=done, '?'=question, '!'=important\n\
                 - delete_todo: Delete todo and children.\n\
-                - mark_complete: Toggle done/pending.\n\
+                - complete_todo: Mark done, or pending again with done=false.\n\
+                - search_todos: Find todos by words across all dates, archived days included.\n\
+                - rollover: Move unfinished todos from the last day to today.\n\
                 - list_projects: List all available projects.\n\n\
                 DISPLAY GUIDELINES:\n\
                 - For list_todos: Display the 'formatted' field directly as markdown. Do NOT create tables.\n\
                 - For single items: Show as '[ ] content' or '[x] content' format.\n\
-                - Dates use YYYY-MM-DD format.\n\
+                - Dates use YYYY-MM-DD format; phrases like 'tomorrow' or 'next friday' are also accepted.\n\
                 - IDs are UUIDs - use list_todos to get valid IDs.\n\
-                - All tools accept optional 'project' parameter. Defaults to 'default' if not provided."
+                - All tools accept optional 'project' parameter. Defaults to 'default' if not provided.\n\n\
+                RESOURCES:\n\
+                - totui://projects: Every project, with the URI of its list for today.\n\
+                - totui://{project}/{date}: A day's list as markdown. date is YYYY-MM-DD or 'today', 'yesterday', ...\n\n\
+                PROMPTS:\n\
+                - plan_day: Plan today from notes, with today's list embedded.\n\
+                - weekly_summary: Summarize todos completed over the last days."
                     .into(),
             ),
-            capabilities: ServerCapabilities::builder().enable_tools().build(),
+            capabilities: ServerCapabilities::builder().enable_tools().enable_resources().enable_prompts().build(),
+            server_info: Implementation {
+                name: "totui".to_string(),
+                title: Some("to-tui".to_string()),
+                version: env!("CARGO_PKG_VERSION").to_string(),
+                ..Implementation::default()
+            },
             ..Default::default()
         }
     }
+
+    async fn list_resources(
+        &self,
+        _request: Option<PaginatedRequestParam>,
+        _context: RequestContext<RoleServer>,
+    ) -> Result<ListResourcesResult, rmcp::ErrorData> {
+        info!("list_resources called");
+        let resources = resources::list().map_err(McpErrorDetail::into_protocol_error)?;
+        Ok(ListResourcesResult {
+            resources,
+            ..Default::default()
+        })
+    }
+
+    async fn list_resource_templates(
+        &self,
+        _request: Option<PaginatedRequestParam>,
+        _context: RequestContext<RoleServer>,
+    ) -> Result<ListResourceTemplatesResult, rmcp::ErrorData> {
+        Ok(ListResourceTemplatesResult {
+            resource_templates: resources::templates(),
+            ..Default::default()
+        })
+    }
+
+    async fn read_resource(
+        &self,
+        request: ReadResourceRequestParam,
+        _context: RequestContext<RoleServer>,
+    ) -> Result<ReadResourceResult, rmcp::ErrorData> {
+        info!(uri = %request.uri, "read_resource called");
+        let text = resources::read(&request.uri).map_err(|detail| {
+            warn!(code = %detail.code, message = %detail.message, "read_resource failed");
+            detail.into_protocol_error()
+        })?;
+        Ok(ReadResourceResult {
+            contents: vec![ResourceContents::TextResourceContents {
+                uri: request.uri,
+                mime_type: Some("text/markdown".to_string()),
+                text,
+                meta: None,
+            }],
+        })
+    }
+
+    async fn list_prompts(
+        &self,
+        _request: Option<PaginatedRequestParam>,
+        _context: RequestContext<RoleServer>,
+    ) -> Result<ListPromptsResult, rmcp::ErrorData> {
+        Ok(ListPromptsResult {
+            prompts: prompts::list(),
+            ..Default::default()
+        })
+    }
+
+    async fn get_prompt(
+        &self,
+        request: GetPromptRequestParam,
+        _context: RequestContext<RoleServer>,
+    ) -> Result<GetPromptResult, rmcp::ErrorData> {
+        info!(name = %request.name, "get_prompt called");
+        prompts::get(&request.name, request.arguments.as_ref()).map_err(|detail| {
+            warn!(code = %detail.code, message = %detail.message, "get_prompt failed");
+            detail.into_protocol_error()
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::storage::database::{ensure_default_project_exists, init_database};
+    use serial_test::serial;
+    use tempfile::TempDir;
+
+    fn setup_test_env() -> TempDir {
+        let temp_dir = TempDir::new().unwrap();
+        std::fs::create_dir_all(temp_dir.path().join(".to-tui")).unwrap();
+        unsafe {
+            std::env::set_var("HOME", temp_dir.path());
+        }
+        init_database().unwrap();
+        ensure_default_project_exists().unwrap();
+        temp_dir
+    }
+
+    #[test]
+    fn test_agent_tools_are_listed() {
+        let server = TodoMcpServer::new();
+        let names: Vec<_> = server.tool_router.list_all().into_iter().map(|t| t.name).collect();
+        for name in ["add_todo", "list_todos", "complete_todo", "search_todos", "rollover"] {
+            assert!(names.iter().any(|n| n == name), "missing tool {name}");
+        }
+    }
+
+    #[tokio::test]
+    #[serial]
+    async fn test_add_complete_and_search() {
+        let _temp_dir = setup_test_env();
+        let server = TodoMcpServer::new();
+
+        let added = server
+            .add_todo(Parameters(AddTodoRequest {
+                content: "Renew passport".to_string(),
+                date: None,
+                parent_id: None,
+                due_date: None,
+                description: None,
+                project: None,
+            }))
+            .await
+            .unwrap()
+            .0;
+
+        let complete = |done| CompleteTodoRequest {
+            id: added.id.clone(),
+            done,
+            date: None,
+            project: None,
+        };
+        let done = server.complete_todo(Parameters(complete(None))).await.unwrap().0;
+        assert_eq!(done.state, "x");
+        let still_done = server.complete_todo(Parameters(complete(None))).await.unwrap().0;
+        assert_eq!(still_done.state, "x");
+        let reopened = server.complete_todo(Parameters(complete(Some(false)))).await.unwrap().0;
+        assert_eq!(reopened.state, " ");
+
+        let found = server
+            .search_todos(Parameters(SearchTodosRequest {
+                query: "passp".to_string(),
+                project: None,
+                limit: None,
+            }))
+            .await
+            .unwrap()
+            .0;
+        assert_eq!(found.count, 1);
+        assert_eq!(found.hits[0].id, added.id);
+
+        let rollover = server
+            .rollover(Parameters(RolloverRequest { project: None }))
+            .await
+            .unwrap()
+            .0;
+        assert_eq!(rollover.rolled_over, 0);
+    }
 }
@@ -61,12 +61,14 @@ impl From<FfiTodoState> for TodoState {
 // Priority <-> FfiPriority
 // ============================================================================
 
+/// Plugins only know three levels. Configured levels below P2 are all
+/// reported as P2, the least urgent level plugins can express.
 impl From<Priority> for FfiPriority {
     fn from(priority: Priority) -> Self {
         match priority {
             Priority::P0 => FfiPriority::P0,
             Priority::P1 => FfiPriority::P1,
-            Priority::P2 => FfiPriority::P2,
+            _ => FfiPriority::P2,
         }
     }
 }
@@ -104,6 +106,7 @@ impl From<&TodoItem> for FfiTodoItem {
             completed_at: item.completed_at.map(|dt| dt.timestamp_millis()).into(),
             // Position is set by host during query, default to 0
             position: 0,
+            link: item.link.clone().map(Into::into).into(),
         }
     }
 }
@@ -164,6 +167,13 @@ impl TryFrom<FfiTodoItem> for TodoItem {
             created_at,
             modified_at,
             completed_at,
+            // Estimates are exposed to plugins via FfiTodoMetadata instead
+            estimate: None,
+            waiting_on: None,
+            pinned: false,
+            link: Option::<RString>::from(ffi.link).map(Into::into),
+            location: None,
+            short_id: None,
             // UI-only field, default to false
             collapsed: false,
             // Host never passes deleted items to plugins
@@ -210,6 +220,12 @@ mod tests {
         }
     }
 
+    #[test]
+    fn test_extra_priority_levels_map_to_p2() {
+        let ffi: FfiPriority = Priority::new(5).unwrap().into();
+        assert_eq!(ffi, FfiPriority::P2);
+    }
+
     #[test]
     fn test_todo_item_roundtrip() {
         let item = TodoItem::new("Test task".to_string(), 2);
@@ -242,6 +258,7 @@ mod tests {
         item.priority = Some(Priority::P0);
         item.due_date = Some(NaiveDate::from_ymd_opt(2026, 12, 31).unwrap());
         item.description = Some("A longer description".to_string());
+        item.link = Some("https://example.com/PROJ-1".to_string());
         item.parent_id = Some(Uuid::new_v4());
         item.state = TodoState::Checked;
         item.completed_at = Some(Utc::now());
@@ -255,6 +272,7 @@ mod tests {
         assert_eq!(item.priority, back.priority);
         assert_eq!(item.due_date, back.due_date);
         assert_eq!(item.description, back.description);
+        assert_eq!(item.link, back.link);
         assert_eq!(item.parent_id, back.parent_id);
         assert!(back.completed_at.is_some());
     }
@@ -274,6 +292,7 @@ mod tests {
             modified_at: Utc::now().timestamp_millis(),
             completed_at: ROption::RNone,
             position: 0,
+            link: ROption::RNone,
         };
 
         let result: Result<TodoItem> = ffi.try_into();
@@ -296,6 +315,7 @@ mod tests {
             modified_at: Utc::now().timestamp_millis(),
             completed_at: ROption::RNone,
             position: 0,
+            link: ROption::RNone,
         };
 
         let result: Result<TodoItem> = ffi.try_into();
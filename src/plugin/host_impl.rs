@@ -6,12 +6,14 @@
 use abi_stable::std_types::{ROption, RString, RVec};
 use std::collections::HashSet;
 use totui_plugin_interface::{
-    FfiProjectContext, FfiStateFilter, FfiTodoItem, FfiTodoMetadata, FfiTodoNode, FfiTodoQuery,
+    FfiChange, FfiChangeSet, FfiHistoryEntry, FfiProjectContext, FfiStateFilter, FfiTodoItem, FfiTodoMetadata, FfiTodoNode, FfiTodoQuery,
     HostApi,
 };
 use uuid::Uuid;
 
+use crate::storage::history::{self, HistoryQuery};
 use crate::storage::metadata;
+use crate::storage::sync::{self, Change};
 
 use crate::project::Project;
 use crate::todo::{TodoList, TodoState};
@@ -253,9 +255,17 @@ impl HostApi for PluginHostApiImpl<'_> {
         let mut results = RVec::new();
         for todo_id in todo_ids.iter() {
             let data = self.get_todo_metadata(todo_id.clone());
+            let estimate_minutes = self
+                .todo_list
+                .items
+                .iter()
+                .find(|item| item.id.to_string() == todo_id.as_str())
+                .and_then(|item| item.estimate)
+                .map(|e| e.minutes());
             results.push(FfiTodoMetadata {
                 todo_id: todo_id.clone(),
                 data,
+                estimate_minutes: estimate_minutes.into(),
             });
         }
         results
@@ -324,6 +334,81 @@ impl HostApi for PluginHostApiImpl<'_> {
 
         results
     }
+
+    fn query_history(&self, todo_id: ROption<RString>, limit: u32) -> RVec<FfiHistoryEntry> {
+        if !self.can_access_project(&self.current_project.name) {
+            return RVec::new();
+        }
+
+        let query = HistoryQuery {
+            project: Some(self.current_project.name.clone()),
+            todo_id: todo_id.into_option().map(|id| id.to_string()),
+            limit: limit as usize,
+            ..HistoryQuery::default()
+        };
+        let events = match history::load_events(&query) {
+            Ok(events) => events,
+            Err(e) => {
+                tracing::warn!(plugin = %self.plugin_name, error = %e, "Failed to load history");
+                return RVec::new();
+            }
+        };
+
+        events
+            .into_iter()
+            .map(|event| FfiHistoryEntry {
+                at: event.at.to_rfc3339().into(),
+                origin: event.origin.into(),
+                kind: event.kind.into(),
+                project: event.project.into(),
+                date: event.date.map(|d| d.format("%Y-%m-%d").to_string().into()).into(),
+                todo_id: event.todo_id.map(RString::from).into(),
+                content: event.content.map(RString::from).into(),
+                detail: event.detail.map(RString::from).into(),
+            })
+            .collect()
+    }
+
+    fn query_changes(&self, since: i64, limit: u32) -> FfiChangeSet {
+        let empty = FfiChangeSet {
+            changes: RVec::new(),
+            cursor: since,
+            has_more: false,
+        };
+        if !self.can_access_project(&self.current_project.name) {
+            return empty;
+        }
+
+        let limit = if limit == 0 { u32::MAX } else { limit };
+        let set = match sync::changes_since(since, Some(&self.current_project.name), limit as usize) {
+            Ok(set) => set,
+            Err(e) => {
+                tracing::warn!(plugin = %self.plugin_name, error = %e, "Failed to load changes");
+                return empty;
+            }
+        };
+
+        FfiChangeSet {
+            changes: set
+                .changes
+                .iter()
+                .map(|change| {
+                    let (date, todo) = match change {
+                        Change::Updated { date, item, .. } => (date, ROption::RSome(FfiTodoItem::from(item.as_ref()))),
+                        Change::Removed { date, .. } => (date, ROption::RNone),
+                    };
+                    FfiChange {
+                        change_seq: change.change_seq(),
+                        todo_id: change.id().to_string().into(),
+                        date: date.format("%Y-%m-%d").to_string().into(),
+                        todo,
+                    }
+                })
+                .collect(),
+            cursor: set.cursor,
+            has_more: set.has_more,
+        }
+    }
 }
 
 #[cfg(test)]
@@ -62,6 +62,8 @@ pub enum PluginErrorKind {
     SessionDisabled,
     /// Plugin panicked during execution.
     Panicked { message: String },
+    /// Plugin was disabled automatically after repeated startup crashes.
+    Quarantined,
     /// Other error (catch-all).
     Other(String),
 }
@@ -433,6 +435,11 @@ impl PluginLoader {
         self.plugins.get_mut(&name.to_lowercase())
     }
 
+    /// Add a plugin that was loaded some other way, e.g. built into a test.
+    pub fn insert(&mut self, plugin: LoadedPlugin) {
+        self.plugins.insert(plugin.name.to_lowercase(), plugin);
+    }
+
     /// Iterate over all loaded plugins.
     pub fn loaded_plugins(&self) -> impl Iterator<Item = &LoadedPlugin> {
         self.plugins.values()
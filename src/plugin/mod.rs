@@ -9,6 +9,7 @@ pub mod loader;
 pub mod manager;
 pub mod manifest;
 pub mod marketplace;
+pub mod quarantine;
 pub mod subprocess;
 
 pub use actions::{PluginAction, PluginActionRegistry};
@@ -0,0 +1,267 @@
+//! Crash-loop detection and plugin quarantine.
+//!
+//! The panic hook records crashes that happen shortly after the TUI starts in
+//! `~/.to-tui/crash_state.json`. When enough of those pile up in a short
+//! period, the next launch disables every plugin installed or updated since the
+//! last clean exit, so a bad plugin can't leave the user stuck in a crash loop.
+
+use anyhow::{Context, Result};
+use chrono::{DateTime, Duration, Utc};
+use serde::{Deserialize, Serialize};
+use std::fs;
+use std::path::Path;
+use std::sync::OnceLock;
+
+use crate::config::PluginsConfig;
+use crate::plugin::PluginManager;
+use crate::utils::paths::get_crash_state_path;
+
+/// A crash within this many seconds of startup counts as a startup crash.
+pub const STARTUP_CRASH_WINDOW_SECS: i64 = 30;
+
+/// Number of startup crashes that constitutes a crash loop.
+pub const CRASH_LOOP_THRESHOLD: usize = 2;
+
+/// Startup crashes older than this are forgotten.
+pub const CRASH_LOOP_PERIOD_MINS: i64 = 10;
+
+/// Without a recorded clean exit, plugins changed within this many hours are suspects.
+const FALLBACK_SUSPECT_HOURS: i64 = 24;
+
+/// When the current TUI session started. Only set in TUI mode, so CLI
+/// subcommands never count towards a crash loop.
+static SESSION_START: OnceLock<DateTime<Utc>> = OnceLock::new();
+
+/// Persistent crash-loop bookkeeping.
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct CrashState {
+    /// Recent crashes that happened shortly after startup
+    #[serde(default)]
+    pub startup_crashes: Vec<DateTime<Utc>>,
+    /// When the TUI last exited cleanly
+    #[serde(default)]
+    pub last_clean_exit: Option<DateTime<Utc>>,
+    /// Plugins disabled automatically by crash-loop detection
+    #[serde(default)]
+    pub quarantined: Vec<String>,
+}
+
+impl CrashState {
+    pub fn load() -> Result<Self> {
+        let path = get_crash_state_path()?;
+        if !path.exists() {
+            return Ok(Self::default());
+        }
+
+        let content = fs::read_to_string(&path)
+            .with_context(|| format!("Failed to read {}", path.display()))?;
+        Ok(serde_json::from_str(&content).unwrap_or_default())
+    }
+
+    pub fn save(&self) -> Result<()> {
+        let path = get_crash_state_path()?;
+        let content = serde_json::to_string_pretty(self)?;
+        fs::write(&path, content).with_context(|| format!("Failed to write {}", path.display()))?;
+        Ok(())
+    }
+
+    /// Record a crash at `now` if it happened within the startup window.
+    /// Returns true if the crash was recorded.
+    pub fn record_crash(&mut self, started_at: DateTime<Utc>, now: DateTime<Utc>) -> bool {
+        if now - started_at > Duration::seconds(STARTUP_CRASH_WINDOW_SECS) {
+            return false;
+        }
+        self.prune(now);
+        self.startup_crashes.push(now);
+        true
+    }
+
+    /// Whether the recent startup crashes amount to a crash loop.
+    pub fn is_crash_loop(&self, now: DateTime<Utc>) -> bool {
+        let cutoff = now - Duration::minutes(CRASH_LOOP_PERIOD_MINS);
+        self.startup_crashes.iter().filter(|t| **t >= cutoff).count() >= CRASH_LOOP_THRESHOLD
+    }
+
+    /// Record a clean exit, resetting the crash counter.
+    pub fn record_clean_exit(&mut self, now: DateTime<Utc>) {
+        self.startup_crashes.clear();
+        self.last_clean_exit = Some(now);
+    }
+
+    /// Plugins are suspects if they changed after the last clean exit.
+    pub fn suspect_cutoff(&self, now: DateTime<Utc>) -> DateTime<Utc> {
+        self.last_clean_exit
+            .unwrap_or_else(|| now - Duration::hours(FALLBACK_SUSPECT_HOURS))
+    }
+
+    pub fn is_quarantined(&self, name: &str) -> bool {
+        self.quarantined.iter().any(|q| q.eq_ignore_ascii_case(name))
+    }
+
+    fn prune(&mut self, now: DateTime<Utc>) {
+        let cutoff = now - Duration::minutes(CRASH_LOOP_PERIOD_MINS);
+        self.startup_crashes.retain(|t| *t >= cutoff);
+    }
+}
+
+/// Mark the start of a TUI session so the panic hook can spot startup crashes.
+pub fn mark_session_start() {
+    let _ = SESSION_START.set(Utc::now());
+}
+
+/// Called from the panic hook. Records the crash if it happened during startup.
+pub fn record_crash() {
+    let Some(started_at) = SESSION_START.get() else {
+        return;
+    };
+    let Ok(mut state) = CrashState::load() else {
+        return;
+    };
+    if state.record_crash(*started_at, Utc::now()) {
+        let _ = state.save();
+    }
+}
+
+/// Called after the TUI exits normally.
+pub fn record_clean_exit() -> Result<()> {
+    let mut state = CrashState::load()?;
+    state.record_clean_exit(Utc::now());
+    state.save()
+}
+
+/// Remove a plugin from the quarantine list, e.g. when the user re-enables it.
+pub fn release(name: &str) -> Result<()> {
+    let mut state = CrashState::load()?;
+    if state.is_quarantined(name) {
+        state.quarantined.retain(|q| !q.eq_ignore_ascii_case(name));
+        state.save()?;
+    }
+    Ok(())
+}
+
+/// When a plugin was installed or last updated, judged by its manifest mtime.
+fn plugin_changed_at(plugin_dir: &Path) -> Option<DateTime<Utc>> {
+    let modified = fs::metadata(plugin_dir.join("plugin.toml"))
+        .and_then(|m| m.modified())
+        .ok()?;
+    Some(DateTime::<Utc>::from(modified))
+}
+
+/// Enabled plugins whose install or update time is after `cutoff`.
+pub fn find_suspects(
+    plugins: impl IntoIterator<Item = (String, Option<DateTime<Utc>>)>,
+    cutoff: DateTime<Utc>,
+) -> Vec<String> {
+    let mut suspects: Vec<String> = plugins
+        .into_iter()
+        .filter(|(_, changed_at)| changed_at.is_some_and(|t| t >= cutoff))
+        .map(|(name, _)| name)
+        .collect();
+    suspects.sort();
+    suspects
+}
+
+/// If the previous launches crash-looped, disable recently changed plugins.
+///
+/// Updates `plugins_config` in place (the caller persists it) and returns the
+/// names of the plugins that were quarantined.
+pub fn quarantine_if_crash_looping(
+    manager: &PluginManager,
+    plugins_config: &mut PluginsConfig,
+) -> Result<Vec<String>> {
+    let mut state = CrashState::load()?;
+    let now = Utc::now();
+    if !state.is_crash_loop(now) {
+        return Ok(Vec::new());
+    }
+
+    let candidates = manager
+        .list()
+        .into_iter()
+        .filter(|info| plugins_config.is_enabled(&info.manifest.name))
+        .map(|info| (info.manifest.name.clone(), plugin_changed_at(&info.path)));
+    let suspects = find_suspects(candidates, state.suspect_cutoff(now));
+
+    for name in &suspects {
+        plugins_config.disable(name);
+        if !state.is_quarantined(name) {
+            state.quarantined.push(name.clone());
+        }
+    }
+
+    // Give the next launch a fresh start either way
+    state.startup_crashes.clear();
+    state.save()?;
+
+    Ok(suspects)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn at(secs: i64) -> DateTime<Utc> {
+        DateTime::from_timestamp(1_768_000_000 + secs, 0).unwrap()
+    }
+
+    #[test]
+    fn test_only_startup_crashes_are_recorded() {
+        let mut state = CrashState::default();
+        assert!(state.record_crash(at(0), at(5)));
+        assert!(!state.record_crash(at(0), at(STARTUP_CRASH_WINDOW_SECS + 1)));
+        assert_eq!(state.startup_crashes.len(), 1);
+    }
+
+    #[test]
+    fn test_crash_loop_threshold() {
+        let mut state = CrashState::default();
+        state.record_crash(at(0), at(2));
+        assert!(!state.is_crash_loop(at(10)));
+
+        state.record_crash(at(20), at(22));
+        assert!(state.is_crash_loop(at(30)));
+    }
+
+    #[test]
+    fn test_old_crashes_expire() {
+        let mut state = CrashState::default();
+        state.record_crash(at(0), at(2));
+        state.record_crash(at(20), at(22));
+
+        let later = at(22 + CRASH_LOOP_PERIOD_MINS * 60 + 60);
+        assert!(!state.is_crash_loop(later));
+    }
+
+    #[test]
+    fn test_clean_exit_resets_counter() {
+        let mut state = CrashState::default();
+        state.record_crash(at(0), at(2));
+        state.record_crash(at(20), at(22));
+        state.record_clean_exit(at(100));
+
+        assert!(!state.is_crash_loop(at(110)));
+        assert_eq!(state.suspect_cutoff(at(200)), at(100));
+    }
+
+    #[test]
+    fn test_find_suspects() {
+        let plugins = vec![
+            ("old".to_string(), Some(at(0))),
+            ("updated".to_string(), Some(at(500))),
+            ("fresh".to_string(), Some(at(1000))),
+            ("unknown".to_string(), None),
+        ];
+
+        assert_eq!(find_suspects(plugins, at(400)), vec!["fresh", "updated"]);
+    }
+
+    #[test]
+    fn test_quarantine_list_is_case_insensitive() {
+        let state = CrashState {
+            quarantined: vec!["Jira".to_string()],
+            ..Default::default()
+        };
+        assert!(state.is_quarantined("jira"));
+        assert!(!state.is_quarantined("github"));
+    }
+}
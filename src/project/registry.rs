@@ -4,6 +4,8 @@ use serde::{Deserialize, Serialize};
 use uuid::Uuid;
 
 use crate::storage::database;
+use crate::utils::paths::get_project_dir;
+use std::fs;
 
 pub const DEFAULT_PROJECT_NAME: &str = "default";
 
@@ -28,6 +30,21 @@ impl Project {
     }
 }
 
+/// Project names become directory names, so they can't be empty or hold a
+/// path.
+pub fn validate_project_name(name: &str) -> Result<()> {
+    if name.trim().is_empty() {
+        anyhow::bail!("Project name can't be empty");
+    }
+    if name != name.trim() {
+        anyhow::bail!("Project name can't start or end with spaces");
+    }
+    if name == "." || name == ".." || name.contains(['/', '\\']) {
+        anyhow::bail!("Project name can't contain a path: '{}'", name);
+    }
+    Ok(())
+}
+
 #[derive(Debug, Clone, Default)]
 pub struct ProjectRegistry {
     pub projects: Vec<Project>,
@@ -56,6 +73,7 @@ impl ProjectRegistry {
 
     pub fn create(&mut self, name: impl Into<String>) -> Result<&Project> {
         let name = name.into();
+        validate_project_name(&name)?;
 
         if self.get_by_name(&name).is_some() {
             anyhow::bail!("Project '{}' already exists", name);
@@ -63,6 +81,7 @@ impl ProjectRegistry {
 
         let project = Project::new(name);
         database::create_project(&project)?;
+        fs::create_dir_all(get_project_dir(&project.name)?)?;
         self.projects.push(project);
 
         Ok(self.projects.last().expect("Just pushed a project"))
@@ -74,19 +93,24 @@ impl ProjectRegistry {
         if old_name == DEFAULT_PROJECT_NAME {
             anyhow::bail!("Cannot rename the default project");
         }
+        validate_project_name(&new_name)?;
 
         if self.get_by_name(&new_name).is_some() {
             anyhow::bail!("Project '{}' already exists", new_name);
         }
 
-        database::rename_project(old_name, &new_name)?;
-
         let project = self
             .projects
             .iter_mut()
             .find(|p| p.name == old_name)
             .ok_or_else(|| anyhow::anyhow!("Project '{}' not found", old_name))?;
 
+        database::rename_project(old_name, &new_name)?;
+        let old_dir = get_project_dir(old_name)?;
+        if old_dir.exists() {
+            fs::rename(&old_dir, get_project_dir(&new_name)?)?;
+        }
+
         project.name = new_name;
 
         Ok(())
@@ -104,6 +128,10 @@ impl ProjectRegistry {
             .ok_or_else(|| anyhow::anyhow!("Project '{}' not found", name))?;
 
         database::delete_project(name)?;
+        let project_dir = get_project_dir(name)?;
+        if project_dir.exists() {
+            fs::remove_dir_all(&project_dir)?;
+        }
         self.projects.remove(index);
 
         Ok(())
@@ -153,6 +181,15 @@ mod tests {
         assert!(registry.get_by_name("NotFound").is_none());
     }
 
+    #[test]
+    fn test_validate_project_name() {
+        assert!(validate_project_name("Side work").is_ok());
+        assert!(validate_project_name("").is_err());
+        assert!(validate_project_name(" Work").is_err());
+        assert!(validate_project_name("..").is_err());
+        assert!(validate_project_name("a/b").is_err());
+    }
+
     #[test]
     fn test_registry_list_sorted() {
         let mut registry = ProjectRegistry::default();
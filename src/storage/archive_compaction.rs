@@ -0,0 +1,218 @@
+//! Compacting old archived days.
+//!
+//! Years of history add up to a lot of archived rows nobody opens one by
+//! one. Past `archive_retention_months`, the archived items of each month
+//! are folded into one summary row per project (how many items there were,
+//! how many were done, and the titles of the done ones) and the items
+//! themselves are deleted. Items in the trash are left to trash retention.
+
+use super::database::{cleanup_orphaned_metadata, get_connection};
+use crate::todo::TodoState;
+use anyhow::Result;
+use chrono::{Datelike, NaiveDate};
+use rusqlite::{params, OptionalExtension};
+use serde::Serialize;
+use std::collections::BTreeMap;
+use tracing::debug;
+
+/// The `archive_summaries` table.
+pub(crate) const ARCHIVE_SUMMARIES_SCHEMA: &str = "
+    CREATE TABLE IF NOT EXISTS archive_summaries (
+        project TEXT NOT NULL,
+        month TEXT NOT NULL,
+        total INTEGER NOT NULL,
+        done INTEGER NOT NULL,
+        completed TEXT NOT NULL DEFAULT '[]',
+        PRIMARY KEY (project, month)
+    );
+";
+
+/// What is left of a compacted month of a project's archive.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct ArchiveSummary {
+    #[serde(skip)]
+    pub project: String,
+    /// `YYYY-MM`
+    #[serde(skip)]
+    pub month: String,
+    /// Items archived that month
+    pub total: usize,
+    /// Of those, how many were done
+    pub done: usize,
+    /// Contents of the done items, oldest day first
+    pub completed: Vec<String>,
+}
+
+/// The first day of the month `months` months before the month of `today`.
+/// Days before it are compacted.
+pub fn retention_cutoff(today: NaiveDate, months: u32) -> NaiveDate {
+    let month = today.year() * 12 + today.month0() as i32 - months as i32;
+    NaiveDate::from_ymd_opt(month.div_euclid(12), month.rem_euclid(12) as u32 + 1, 1)
+        .unwrap_or(NaiveDate::MIN)
+}
+
+/// Fold the archived items of days before `cutoff` into monthly summaries
+/// and delete them. Returns how many items were folded in.
+pub fn compact_archive_before(cutoff: NaiveDate) -> Result<usize> {
+    let mut conn = get_connection()?;
+    let tx = conn.transaction()?;
+    let cutoff = cutoff.format("%Y-%m-%d").to_string();
+    let done_state = TodoState::Checked.to_char().to_string();
+
+    let mut months: BTreeMap<(String, String), ArchiveSummary> = BTreeMap::new();
+    let mut count = 0;
+    {
+        let mut stmt = tx.prepare(
+            "SELECT project, substr(original_date, 1, 7), content, state = ?2
+             FROM archived_todos
+             WHERE original_date < ?1 AND deleted_at IS NULL
+             ORDER BY project, original_date, position",
+        )?;
+        let rows = stmt.query_map(params![cutoff, done_state], |row| {
+            Ok((
+                row.get::<_, String>(0)?,
+                row.get::<_, String>(1)?,
+                row.get::<_, String>(2)?,
+                row.get::<_, bool>(3)?,
+            ))
+        })?;
+        for row in rows {
+            let (project, month, content, done) = row?;
+            let summary = months
+                .entry((project.clone(), month.clone()))
+                .or_insert_with(|| ArchiveSummary {
+                    project,
+                    month,
+                    total: 0,
+                    done: 0,
+                    completed: Vec::new(),
+                });
+            summary.total += 1;
+            if done {
+                summary.done += 1;
+                summary.completed.push(content);
+            }
+            count += 1;
+        }
+    }
+
+    for summary in months.values_mut() {
+        // A month compacted before, with items archived into it since
+        let earlier: Option<(i64, i64, String)> = tx
+            .query_row(
+                "SELECT total, done, completed FROM archive_summaries WHERE project = ?1 AND month = ?2",
+                params![summary.project, summary.month],
+                |row| Ok((row.get(0)?, row.get(1)?, row.get(2)?)),
+            )
+            .optional()?;
+        if let Some((total, done, completed)) = earlier {
+            let mut titles: Vec<String> = serde_json::from_str(&completed).unwrap_or_default();
+            titles.append(&mut summary.completed);
+            summary.completed = titles;
+            summary.total += total as usize;
+            summary.done += done as usize;
+        }
+        tx.execute(
+            "INSERT OR REPLACE INTO archive_summaries (project, month, total, done, completed) VALUES (?1, ?2, ?3, ?4, ?5)",
+            params![
+                summary.project,
+                summary.month,
+                summary.total as i64,
+                summary.done as i64,
+                serde_json::to_string(&summary.completed)?,
+            ],
+        )?;
+    }
+    tx.execute(
+        "DELETE FROM archived_todos WHERE original_date < ?1 AND deleted_at IS NULL",
+        params![cutoff],
+    )?;
+
+    tx.commit()?;
+    drop(conn);
+    cleanup_orphaned_metadata()?;
+
+    debug!(count, cutoff, "Compacted old archived items");
+    Ok(count)
+}
+
+/// Summaries of compacted months, ordered by project and month.
+pub fn load_archive_summaries(project_name: Option<&str>) -> Result<Vec<ArchiveSummary>> {
+    let conn = get_connection()?;
+    let mut stmt = conn.prepare(
+        "SELECT project, month, total, done, completed FROM archive_summaries
+         WHERE ?1 IS NULL OR project = ?1
+         ORDER BY project, month",
+    )?;
+    let rows = stmt.query_map(params![project_name], |row| {
+        let completed: String = row.get(4)?;
+        Ok(ArchiveSummary {
+            project: row.get(0)?,
+            month: row.get(1)?,
+            total: row.get::<_, i64>(2)? as usize,
+            done: row.get::<_, i64>(3)? as usize,
+            completed: serde_json::from_str(&completed).unwrap_or_default(),
+        })
+    })?;
+    Ok(rows.collect::<rusqlite::Result<Vec<_>>>()?)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::storage::database::{
+        archive_todos_for_date_and_project, init_database, load_archived_days, save_todo_list_for_project,
+    };
+    use crate::todo::TodoList;
+    use serial_test::serial;
+    use std::path::PathBuf;
+    use tempfile::TempDir;
+
+    fn archive_day(date: NaiveDate, items: &[(&str, TodoState)]) {
+        let mut list = TodoList::new(date, PathBuf::from("/tmp/day.md"));
+        for (content, state) in items {
+            list.add_item(content.to_string());
+            list.items.last_mut().unwrap().state = *state;
+        }
+        save_todo_list_for_project(&list, "default").unwrap();
+        archive_todos_for_date_and_project(date, "default").unwrap();
+    }
+
+    #[test]
+    fn test_retention_cutoff() {
+        let today = NaiveDate::from_ymd_opt(2026, 3, 18).unwrap();
+        assert_eq!(retention_cutoff(today, 2), NaiveDate::from_ymd_opt(2026, 1, 1).unwrap());
+        assert_eq!(retention_cutoff(today, 14), NaiveDate::from_ymd_opt(2025, 1, 1).unwrap());
+    }
+
+    #[test]
+    #[serial]
+    fn test_old_months_are_folded_into_summaries() {
+        let temp_dir = TempDir::new().unwrap();
+        std::fs::create_dir_all(temp_dir.path().join(".to-tui")).unwrap();
+        unsafe {
+            std::env::set_var("HOME", temp_dir.path());
+        }
+        init_database().unwrap();
+        let date = |m, d| NaiveDate::from_ymd_opt(2024, m, d).unwrap();
+
+        archive_day(date(1, 5), &[("Milk", TodoState::Checked), ("Eggs", TodoState::Empty)]);
+        archive_day(date(1, 6), &[("Bread", TodoState::Checked)]);
+        archive_day(date(3, 1), &[("Report", TodoState::Checked)]);
+
+        assert_eq!(compact_archive_before(date(2, 1)).unwrap(), 3);
+        let days = load_archived_days(None).unwrap();
+        assert_eq!(days.len(), 1);
+        assert_eq!(days[0].date, date(3, 1));
+
+        // Items archived into a compacted month later are added to its summary
+        archive_day(date(1, 20), &[("Jam", TodoState::Checked)]);
+        assert_eq!(compact_archive_before(date(2, 1)).unwrap(), 1);
+
+        let summaries = load_archive_summaries(Some("default")).unwrap();
+        assert_eq!(summaries.len(), 1);
+        assert_eq!(summaries[0].month, "2024-01");
+        assert_eq!((summaries[0].total, summaries[0].done), (4, 3));
+        assert_eq!(summaries[0].completed, vec!["Milk", "Bread", "Jam"]);
+    }
+}
@@ -0,0 +1,170 @@
+//! The whole archive as one document, for `totui export-archive`.
+//!
+//! Days are grouped by project and then by month, oldest first, as markdown
+//! to read or as JSON for other tools. Compacted months come with their
+//! summary instead of their days.
+
+use super::archive_compaction::ArchiveSummary;
+use super::database::ArchivedDay;
+use super::markdown::serialize_items;
+use crate::todo::{TodoItem, TodoState};
+use chrono::NaiveDate;
+use serde::Serialize;
+use std::collections::BTreeMap;
+
+#[derive(Debug, Serialize)]
+pub struct ExportedProject {
+    pub name: String,
+    pub months: Vec<ExportedMonth>,
+}
+
+#[derive(Debug, Serialize)]
+pub struct ExportedMonth {
+    /// `YYYY-MM`
+    pub month: String,
+    /// What is left of the month's days compacted by archive retention
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub summary: Option<ArchiveSummary>,
+    pub days: Vec<ExportedDay>,
+}
+
+#[derive(Debug, Serialize)]
+pub struct ExportedDay {
+    pub date: NaiveDate,
+    pub items: Vec<TodoItem>,
+}
+
+impl ExportedMonth {
+    fn new(month: String) -> Self {
+        Self {
+            month,
+            summary: None,
+            days: Vec::new(),
+        }
+    }
+
+    fn items(&self) -> impl Iterator<Item = &TodoItem> {
+        self.days.iter().flat_map(|day| &day.items)
+    }
+}
+
+/// Group archived days and compacted months by project and month.
+pub fn group(days: Vec<ArchivedDay>, summaries: Vec<ArchiveSummary>) -> Vec<ExportedProject> {
+    let mut projects: BTreeMap<String, BTreeMap<String, ExportedMonth>> = BTreeMap::new();
+    for summary in summaries {
+        let months = projects.entry(summary.project.clone()).or_default();
+        let month = months
+            .entry(summary.month.clone())
+            .or_insert_with(|| ExportedMonth::new(summary.month.clone()));
+        month.summary = Some(summary);
+    }
+    for day in days {
+        let months = projects.entry(day.project.clone()).or_default();
+        let month = day.date.format("%Y-%m").to_string();
+        months
+            .entry(month.clone())
+            .or_insert_with(|| ExportedMonth::new(month))
+            .days
+            .push(ExportedDay {
+                date: day.date,
+                items: day.items,
+            });
+    }
+    projects
+        .into_iter()
+        .map(|(name, months)| ExportedProject {
+            name,
+            months: months.into_values().collect(),
+        })
+        .collect()
+}
+
+pub fn to_markdown(projects: &[ExportedProject]) -> String {
+    let mut output = String::from("# Archive\n");
+    for project in projects {
+        output.push_str(&format!("\n## {}\n", project.name));
+        for month in &project.months {
+            let summary = month.summary.as_ref();
+            let total = month.items().count() + summary.map_or(0, |s| s.total);
+            let done = month
+                .items()
+                .filter(|item| item.state == TodoState::Checked)
+                .count()
+                + summary.map_or(0, |s| s.done);
+            let title = NaiveDate::parse_from_str(&format!("{}-01", month.month), "%Y-%m-%d")
+                .map_or(month.month.clone(), |date| date.format("%B %Y").to_string());
+            output.push_str(&format!("\n### {title} ({total} items, {done} done)\n"));
+            if let Some(summary) = summary {
+                output.push_str("\nCompacted, done items only:\n\n");
+                for content in &summary.completed {
+                    output.push_str(&format!("- [x] {content}\n"));
+                }
+            }
+            for day in &month.days {
+                output.push_str(&format!("\n#### {}\n\n", day.date.format("%A, %B %-d")));
+                output.push_str(&serialize_items(&day.items));
+            }
+        }
+    }
+    output
+}
+
+pub fn to_json(projects: &[ExportedProject]) -> serde_json::Result<String> {
+    serde_json::to_string_pretty(projects)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn day(project: &str, date: &str, items: &[(&str, TodoState)]) -> ArchivedDay {
+        ArchivedDay {
+            project: project.to_string(),
+            date: date.parse().unwrap(),
+            items: items
+                .iter()
+                .map(|(content, state)| {
+                    let mut item = TodoItem::new(content.to_string(), 0);
+                    item.state = *state;
+                    item
+                })
+                .collect(),
+        }
+    }
+
+    #[test]
+    fn test_days_are_grouped_by_project_and_month() {
+        let summary = ArchiveSummary {
+            project: "default".to_string(),
+            month: "2025-12".to_string(),
+            total: 3,
+            done: 1,
+            completed: vec!["Tree".to_string()],
+        };
+        let projects = group(
+            vec![
+                day("default", "2026-01-18", &[("Milk", TodoState::Checked), ("Eggs", TodoState::Empty)]),
+                day("default", "2026-01-19", &[("Bread", TodoState::Checked)]),
+                day("default", "2026-02-01", &[("Jam", TodoState::Empty)]),
+                day("work", "2026-01-18", &[("Report", TodoState::Checked)]),
+            ],
+            vec![summary],
+        );
+        assert_eq!(projects.len(), 2);
+        assert_eq!(projects[0].months.len(), 3);
+        assert_eq!(projects[0].months[0].month, "2025-12");
+        assert_eq!(projects[0].months[1].days.len(), 2);
+
+        let markdown = to_markdown(&projects);
+        assert!(markdown.contains("### December 2025 (3 items, 1 done)\n\nCompacted, done items only:\n\n- [x] Tree\n"));
+        assert!(markdown.contains("### January 2026 (3 items, 2 done)\n"));
+        assert!(markdown.contains("#### Sunday, January 18\n\n- [x] Milk\n- [ ] Eggs\n"));
+        assert!(markdown.contains("## work\n"));
+
+        let json: serde_json::Value = serde_json::from_str(&to_json(&projects).unwrap()).unwrap();
+        assert_eq!(json[0]["months"][0]["summary"]["completed"][0], "Tree");
+        assert_eq!(json[0]["months"][2]["month"], "2026-02");
+        assert!(json[0]["months"][2].get("summary").is_none());
+        assert_eq!(json[1]["months"][0]["days"][0]["items"][0]["content"], "Report");
+    }
+}
@@ -0,0 +1,348 @@
+//! Files attached to items.
+//!
+//! Attached files are copied into a content-addressed store in the data
+//! directory, named by the SHA-256 of their bytes, so the original can move
+//! or go away and the same file attached twice is stored once. The
+//! `attachments` table links each item to its files under their original
+//! names. Items copied under new ids (rollover, moves) get their own rows
+//! for the same files, and rows and files no item refers to any more are
+//! removed along with orphaned metadata. With encryption on, stored files
+//! are encrypted and opened from a decrypted copy.
+
+use super::database::get_connection;
+use super::encryption;
+use crate::utils::paths::get_attachments_dir;
+use anyhow::{Context, Result};
+use chrono::{DateTime, Utc};
+use rusqlite::{params, Connection};
+use sha2::{Digest, Sha256};
+use std::collections::HashSet;
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::sync::Mutex;
+use tempfile::TempDir;
+use uuid::Uuid;
+
+/// The `attachments` table.
+pub(crate) const ATTACHMENTS_SCHEMA: &str = "
+    CREATE TABLE IF NOT EXISTS attachments (
+        id TEXT PRIMARY KEY,
+        todo_id TEXT NOT NULL,
+        name TEXT NOT NULL,
+        hash TEXT NOT NULL,
+        size INTEGER NOT NULL,
+        created_at TEXT NOT NULL
+    );
+    CREATE INDEX IF NOT EXISTS idx_attachments_todo ON attachments(todo_id);
+";
+
+/// The directories decrypted copies were opened from
+static OPENED: Mutex<Vec<TempDir>> = Mutex::new(Vec::new());
+
+/// A file attached to an item.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Attachment {
+    pub id: Uuid,
+    pub todo_id: Uuid,
+    /// File name it was attached under
+    pub name: String,
+    /// SHA-256 of the contents, hex
+    pub hash: String,
+    pub size: u64,
+    pub created_at: DateTime<Utc>,
+}
+
+impl Attachment {
+    /// Where the stored copy is. It keeps the original's extension, so the
+    /// system opener picks the right app.
+    pub fn path(&self) -> Result<PathBuf> {
+        Ok(blob_path(&get_attachments_dir()?, &self.hash, &self.name))
+    }
+
+    /// The file to hand to the system opener: the stored copy, or with
+    /// encryption on a decrypted copy under its own name in a private temp
+    /// directory. `remove_opened_copies` removes those.
+    pub fn openable_path(&self) -> Result<PathBuf> {
+        let path = self.path()?;
+        if !encryption::is_enabled() {
+            return Ok(path);
+        }
+        let dir = tempfile::Builder::new()
+            .prefix("totui-")
+            .tempdir()
+            .context("Failed to make a temp directory")?;
+        let copy = dir.path().join(&self.name);
+        fs::write(&copy, encryption::read(&path)?).with_context(|| format!("Failed to write {}", copy.display()))?;
+        OPENED.lock().unwrap_or_else(|e| e.into_inner()).push(dir);
+        Ok(copy)
+    }
+}
+
+/// Remove the decrypted copies attachments were opened from. Called at exit.
+pub fn remove_opened_copies() {
+    OPENED.lock().unwrap_or_else(|e| e.into_inner()).clear();
+}
+
+fn blob_path(dir: &Path, hash: &str, name: &str) -> PathBuf {
+    let file = match Path::new(name).extension().and_then(|ext| ext.to_str()) {
+        Some(ext) => format!("{hash}.{}", ext.to_lowercase()),
+        None => hash.to_string(),
+    };
+    dir.join(&hash[..2]).join(file)
+}
+
+/// Copy the file at `source` into the store and attach it to the item.
+pub fn attach(todo_id: &Uuid, source: &Path) -> Result<Attachment> {
+    let bytes = fs::read(source).with_context(|| format!("Failed to read {}", source.display()))?;
+    let name = source
+        .file_name()
+        .and_then(|name| name.to_str())
+        .with_context(|| format!("Not a file: {}", source.display()))?
+        .to_string();
+    let hash = format!("{:x}", Sha256::digest(&bytes));
+
+    let attachment = Attachment {
+        id: Uuid::new_v4(),
+        todo_id: *todo_id,
+        name,
+        hash,
+        size: bytes.len() as u64,
+        created_at: Utc::now(),
+    };
+    // The row goes in first, so removing orphaned files never takes one
+    // that is being attached
+    let conn = get_connection()?;
+    insert(&conn, &attachment)?;
+
+    let path = blob_path(&get_attachments_dir()?, &attachment.hash, &attachment.name);
+    if !path.exists()
+        && let Err(e) = encryption::seal(&bytes).and_then(|sealed| write_blob(&path, &sealed))
+    {
+        conn.execute("DELETE FROM attachments WHERE id = ?1", [attachment.id.to_string()])?;
+        return Err(e);
+    }
+    Ok(attachment)
+}
+
+fn insert(conn: &Connection, attachment: &Attachment) -> Result<()> {
+    conn.execute(
+        "INSERT INTO attachments (id, todo_id, name, hash, size, created_at) VALUES (?1, ?2, ?3, ?4, ?5, ?6)",
+        params![
+            attachment.id.to_string(),
+            attachment.todo_id.to_string(),
+            attachment.name,
+            attachment.hash,
+            attachment.size as i64,
+            attachment.created_at.to_rfc3339(),
+        ],
+    )?;
+    Ok(())
+}
+
+fn write_blob(path: &Path, bytes: &[u8]) -> Result<()> {
+    if let Some(dir) = path.parent() {
+        fs::create_dir_all(dir).with_context(|| format!("Failed to create {}", dir.display()))?;
+    }
+    // Write next to it and rename, so a crash never leaves half a file
+    let partial = path.with_extension("partial");
+    fs::write(&partial, bytes).with_context(|| format!("Failed to write {}", partial.display()))?;
+    fs::rename(&partial, path).with_context(|| format!("Failed to write {}", path.display()))?;
+    Ok(())
+}
+
+/// Attach the files of each `(from, to)` item to `to` as well, for items
+/// copied under a new id. Files `to` already has aren't attached twice.
+pub fn copy_attachments(ids: impl IntoIterator<Item = (Uuid, Uuid)>) -> Result<()> {
+    let mut conn = get_connection()?;
+    let tx = conn.transaction()?;
+    for (from, to) in ids {
+        let existing: HashSet<(String, String)> =
+            list_on(&tx, &to)?.into_iter().map(|a| (a.hash, a.name)).collect();
+        for attachment in list_on(&tx, &from)? {
+            if existing.contains(&(attachment.hash.clone(), attachment.name.clone())) {
+                continue;
+            }
+            insert(
+                &tx,
+                &Attachment {
+                    id: Uuid::new_v4(),
+                    todo_id: to,
+                    ..attachment
+                },
+            )?;
+        }
+    }
+    tx.commit()?;
+    Ok(())
+}
+
+/// Delete the rows of items that are gone from both the live and archived
+/// todos (the trash counts as live), then the stored files no row refers to.
+pub(crate) fn remove_orphaned_attachments(conn: &Connection) -> Result<()> {
+    conn.execute(
+        "DELETE FROM attachments
+         WHERE todo_id NOT IN (SELECT id FROM todos) AND todo_id NOT IN (SELECT id FROM archived_todos)",
+        [],
+    )?;
+
+    let dir = get_attachments_dir()?;
+    let mut stmt = conn.prepare("SELECT DISTINCT hash, name FROM attachments")?;
+    let kept: HashSet<PathBuf> = stmt
+        .query_map([], |row| Ok(blob_path(&dir, &row.get::<_, String>(0)?, &row.get::<_, String>(1)?)))?
+        .collect::<rusqlite::Result<_>>()?;
+    for path in stored_files()? {
+        if !kept.contains(&path) {
+            fs::remove_file(&path).with_context(|| format!("Failed to remove {}", path.display()))?;
+        }
+    }
+    Ok(())
+}
+
+/// The files in the store. Partial files are attachments still being
+/// written, and left out.
+pub(crate) fn stored_files() -> Result<Vec<PathBuf>> {
+    let dir = get_attachments_dir()?;
+    let mut files = Vec::new();
+    if !dir.exists() {
+        return Ok(files);
+    }
+    for prefix in fs::read_dir(&dir)? {
+        let prefix = prefix?.path();
+        if !prefix.is_dir() {
+            continue;
+        }
+        for file in fs::read_dir(&prefix)? {
+            let path = file?.path();
+            if path.extension().is_none_or(|ext| ext != "partial") {
+                files.push(path);
+            }
+        }
+    }
+    Ok(files)
+}
+
+/// The files attached to an item, in the order they were attached.
+pub fn list_attachments(todo_id: &Uuid) -> Result<Vec<Attachment>> {
+    let conn = get_connection()?;
+    list_on(&conn, todo_id)
+}
+
+fn list_on(conn: &Connection, todo_id: &Uuid) -> Result<Vec<Attachment>> {
+    let mut stmt = conn.prepare(
+        "SELECT id, name, hash, size, created_at FROM attachments WHERE todo_id = ?1 ORDER BY created_at, rowid",
+    )?;
+    let rows = stmt.query_map([todo_id.to_string()], |row| {
+        let id: String = row.get(0)?;
+        let created_at: String = row.get(4)?;
+        Ok(Attachment {
+            id: Uuid::parse_str(&id).unwrap_or_default(),
+            todo_id: *todo_id,
+            name: row.get(1)?,
+            hash: row.get(2)?,
+            size: row.get::<_, i64>(3)? as u64,
+            created_at: DateTime::parse_from_rfc3339(&created_at)
+                .map(|at| at.with_timezone(&Utc))
+                .unwrap_or_default(),
+        })
+    })?;
+    Ok(rows.collect::<rusqlite::Result<Vec<_>>>()?)
+}
+
+/// `1.2 MB`, `340 KB` or `12 B`.
+pub fn format_size(size: u64) -> String {
+    match size {
+        0..1_000 => format!("{size} B"),
+        1_000..1_000_000 => format!("{} KB", size / 1_000),
+        _ => format!("{:.1} MB", size as f64 / 1_000_000.0),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::storage::database::init_database;
+    use serial_test::serial;
+    use tempfile::TempDir;
+
+    #[test]
+    #[serial]
+    fn test_attached_files_are_stored_once_by_content() {
+        let temp_dir = TempDir::new().unwrap();
+        std::fs::create_dir_all(temp_dir.path().join(".to-tui")).unwrap();
+        unsafe {
+            std::env::set_var("HOME", temp_dir.path());
+        }
+        init_database().unwrap();
+
+        let receipt = temp_dir.path().join("Receipt.PDF");
+        fs::write(&receipt, b"%PDF-1.7 receipt").unwrap();
+        let copy = temp_dir.path().join("copy.pdf");
+        fs::write(&copy, b"%PDF-1.7 receipt").unwrap();
+
+        let todo_id = Uuid::new_v4();
+        let first = attach(&todo_id, &receipt).unwrap();
+        let second = attach(&todo_id, &copy).unwrap();
+        fs::remove_file(&receipt).unwrap();
+
+        assert_eq!(first.hash, second.hash);
+        assert_eq!(first.path().unwrap(), second.path().unwrap());
+        assert!(first.path().unwrap().to_string_lossy().ends_with(".pdf"));
+        assert_eq!(fs::read(first.path().unwrap()).unwrap(), b"%PDF-1.7 receipt");
+        // Without encryption the stored copy is opened as it is
+        assert_eq!(first.openable_path().unwrap(), first.path().unwrap());
+
+        let names: Vec<_> = list_attachments(&todo_id).unwrap().into_iter().map(|a| a.name).collect();
+        assert_eq!(names, vec!["Receipt.PDF", "copy.pdf"]);
+        assert!(list_attachments(&Uuid::new_v4()).unwrap().is_empty());
+        assert!(attach(&todo_id, &temp_dir.path().join("missing.txt")).is_err());
+    }
+
+    #[test]
+    #[serial]
+    fn test_attachments_go_when_their_item_is_purged() {
+        use crate::storage::database::{
+            cleanup_orphaned_metadata, purge_deleted_todos_for_project, save_todo_list_for_project,
+            soft_delete_todos_for_project,
+        };
+        use crate::todo::TodoList;
+        use chrono::NaiveDate;
+
+        let temp_dir = TempDir::new().unwrap();
+        std::fs::create_dir_all(temp_dir.path().join(".to-tui")).unwrap();
+        unsafe {
+            std::env::set_var("HOME", temp_dir.path());
+        }
+        init_database().unwrap();
+
+        let date = NaiveDate::from_ymd_opt(2026, 3, 1).unwrap();
+        let mut list = TodoList::new(date, temp_dir.path().join("list.md"));
+        list.add_item("Expenses".to_string());
+        list.add_item("Taxes".to_string());
+        save_todo_list_for_project(&list, "default").unwrap();
+        let (expenses, taxes) = (list.items[0].id, list.items[1].id);
+
+        let receipt = temp_dir.path().join("receipt.pdf");
+        fs::write(&receipt, b"receipt").unwrap();
+        let shared = attach(&expenses, &receipt).unwrap();
+        attach(&taxes, &receipt).unwrap();
+        let scan = temp_dir.path().join("scan.png");
+        fs::write(&scan, b"scan").unwrap();
+        let own = attach(&expenses, &scan).unwrap();
+        // Rows of items that never existed go on the next cleanup
+        attach(&Uuid::new_v4(), &scan).unwrap();
+        cleanup_orphaned_metadata().unwrap();
+        assert_eq!(list_attachments(&expenses).unwrap().len(), 2);
+
+        // Items in the trash keep theirs
+        soft_delete_todos_for_project(&[expenses], date, "default").unwrap();
+        assert_eq!(list_attachments(&expenses).unwrap().len(), 2);
+        assert!(own.path().unwrap().exists());
+
+        purge_deleted_todos_for_project(&[expenses], "default").unwrap();
+        assert!(list_attachments(&expenses).unwrap().is_empty());
+        assert!(!own.path().unwrap().exists());
+        // Files another item still has stay
+        assert!(shared.path().unwrap().exists());
+        assert_eq!(list_attachments(&taxes).unwrap().len(), 1);
+    }
+}
@@ -0,0 +1,181 @@
+//! Automatic backups of the database.
+//!
+//! A backup is a full copy made with SQLite's online backup API, so it is
+//! consistent even while the server is writing. Backups are named after the
+//! time they were taken (`todos-20260118-093000.db`), which makes listing the
+//! backups directory all the bookkeeping there is.
+
+use anyhow::{Context, Result};
+use chrono::{Duration, Local, NaiveDateTime};
+use rusqlite::Connection;
+use rusqlite::backup::Backup;
+use std::fs;
+use std::path::{Path, PathBuf};
+use tracing::debug;
+
+use crate::config::BackupConfig;
+use crate::storage::encryption;
+use crate::utils::paths::{get_backups_dir, get_database_path};
+
+const FILE_PREFIX: &str = "todos-";
+const FILE_SUFFIX: &str = ".db";
+const TIMESTAMP_FORMAT: &str = "%Y%m%d-%H%M%S";
+/// Pages copied at a time, pausing in between so writers aren't held up
+const PAGES_PER_STEP: std::ffi::c_int = 5;
+const STEP_PAUSE: std::time::Duration = std::time::Duration::from_millis(250);
+
+/// A backup in the backups directory.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct BackupFile {
+    pub path: PathBuf,
+    pub created: NaiveDateTime,
+}
+
+/// Backups in `dir`, newest first. A missing directory has none.
+pub fn list_backups(dir: &Path) -> Result<Vec<BackupFile>> {
+    if !dir.exists() {
+        return Ok(Vec::new());
+    }
+    let mut backups = Vec::new();
+    for entry in fs::read_dir(dir).with_context(|| format!("Failed to read {}", dir.display()))? {
+        let path = entry?.path();
+        let Some(name) = path.file_name().and_then(|name| name.to_str()) else {
+            continue;
+        };
+        let Some(stamp) = name
+            .strip_prefix(FILE_PREFIX)
+            .and_then(|rest| rest.strip_suffix(FILE_SUFFIX))
+        else {
+            continue;
+        };
+        if let Ok(created) = NaiveDateTime::parse_from_str(stamp, TIMESTAMP_FORMAT) {
+            backups.push(BackupFile { path, created });
+        }
+    }
+    backups.sort_by_key(|backup| std::cmp::Reverse(backup.created));
+    Ok(backups)
+}
+
+/// When the newest backup in the default backups directory was taken.
+pub fn last_backup_time() -> Result<Option<NaiveDateTime>> {
+    Ok(list_backups(&get_backups_dir()?)?.first().map(|backup| backup.created))
+}
+
+/// Whether a timed backup is due at `now`, given when the last one was taken.
+pub fn is_due(config: &BackupConfig, last: Option<NaiveDateTime>, now: NaiveDateTime) -> bool {
+    if !config.enabled || config.interval_hours == 0 {
+        return false;
+    }
+    let interval = Duration::hours(config.interval_hours as i64);
+    last.is_none_or(|last| now - last >= interval)
+}
+
+/// Copy the database at `db` into `dir`, named after `now`.
+pub fn create_backup(db: &Path, dir: &Path, now: NaiveDateTime) -> Result<PathBuf> {
+    fs::create_dir_all(dir).with_context(|| format!("Failed to create {}", dir.display()))?;
+    let path = dir.join(format!("{FILE_PREFIX}{}{FILE_SUFFIX}", now.format(TIMESTAMP_FORMAT)));
+    copy_database(db, &path)?;
+    debug!(path = %path.display(), "Database backed up");
+    Ok(path)
+}
+
+/// Copy the database at `db` to `to` with the online backup API, replacing
+/// whatever is there. Unlike copying the file, this takes in changes still
+/// in the write-ahead log and is consistent while others write.
+pub fn copy_database(db: &Path, to: &Path) -> Result<()> {
+    // The backup API writes into an existing database; start from nothing
+    if to.exists() {
+        fs::remove_file(to)?;
+    }
+    let conn = Connection::open(db).with_context(|| format!("Failed to open database at {db:?}"))?;
+    encryption::key_connection(&conn)?;
+    let mut copy = Connection::open(to)
+        .with_context(|| format!("Failed to create {}", to.display()))?;
+    // SQLCipher only copies into a database under the same key
+    encryption::key_connection(&copy)?;
+    Backup::new(&conn, &mut copy)
+        .and_then(|backup| backup.run_to_completion(PAGES_PER_STEP, STEP_PAUSE, None))
+        .with_context(|| format!("Failed to back up the database to {}", to.display()))
+}
+
+/// Delete all but the newest `keep` backups (at least one is kept).
+/// Returns how many were deleted.
+pub fn prune_backups(dir: &Path, keep: usize) -> Result<usize> {
+    let backups = list_backups(dir)?;
+    let mut deleted = 0;
+    for backup in backups.iter().skip(keep.max(1)) {
+        fs::remove_file(&backup.path)
+            .with_context(|| format!("Failed to delete {}", backup.path.display()))?;
+        deleted += 1;
+    }
+    Ok(deleted)
+}
+
+/// Back up the database to the backups directory now and apply the
+/// retention policy. Returns the new backup's path.
+pub fn backup_now(config: &BackupConfig) -> Result<PathBuf> {
+    let dir = get_backups_dir()?;
+    let path = create_backup(&get_database_path()?, &dir, Local::now().naive_local())?;
+    prune_backups(&dir, config.keep)?;
+    Ok(path)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use chrono::NaiveDate;
+    use tempfile::TempDir;
+
+    fn at(hour: u32) -> NaiveDateTime {
+        NaiveDate::from_ymd_opt(2026, 1, 18)
+            .unwrap()
+            .and_hms_opt(hour, 0, 0)
+            .unwrap()
+    }
+
+    #[test]
+    fn test_backups_are_listed_newest_first_and_pruned() {
+        let temp = TempDir::new().unwrap();
+        let db = temp.path().join("todos.db");
+        let conn = Connection::open(&db).unwrap();
+        conn.execute("CREATE TABLE todos (content TEXT)", []).unwrap();
+        conn.execute("INSERT INTO todos VALUES ('Buy milk')", []).unwrap();
+
+        let dir = temp.path().join("backups");
+        for hour in [9, 11, 10] {
+            create_backup(&db, &dir, at(hour)).unwrap();
+        }
+        fs::write(dir.join("notes.txt"), "not a backup").unwrap();
+
+        let created: Vec<_> = list_backups(&dir).unwrap().iter().map(|b| b.created).collect();
+        assert_eq!(created, vec![at(11), at(10), at(9)]);
+
+        assert_eq!(prune_backups(&dir, 2).unwrap(), 1);
+        let backups = list_backups(&dir).unwrap();
+        assert_eq!(backups.len(), 2);
+        assert!(dir.join("notes.txt").exists());
+
+        let copy = Connection::open(&backups[0].path).unwrap();
+        let content: String = copy.query_row("SELECT content FROM todos", [], |row| row.get(0)).unwrap();
+        assert_eq!(content, "Buy milk");
+    }
+
+    #[test]
+    fn test_is_due() {
+        let config = BackupConfig::default();
+        assert!(is_due(&config, None, at(9)));
+        assert!(!is_due(&config, Some(at(9)), at(23)));
+        assert!(is_due(&config, Some(at(0)), at(0) + Duration::hours(24)));
+
+        let exit_only = BackupConfig {
+            interval_hours: 0,
+            ..BackupConfig::default()
+        };
+        assert!(!is_due(&exit_only, None, at(9)));
+        let disabled = BackupConfig {
+            enabled: false,
+            ..BackupConfig::default()
+        };
+        assert!(!is_due(&disabled, None, at(9)));
+    }
+}
@@ -0,0 +1,471 @@
+//! Daily files edited outside totui.
+//!
+//! Whenever totui writes a daily file it records the file's modification
+//! time, a hash of its contents and the contents themselves. A file whose
+//! hash no longer matches was changed by something else, so saves leave it
+//! alone instead of overwriting the edit. The TUI then settles the two
+//! versions: it takes the file's edits in by itself when the database hasn't
+//! changed since, and asks otherwise. The recorded contents are what both
+//! versions started from, which is what makes merging them possible.
+
+use super::database::{self, DailyFileRecord};
+use super::encryption;
+use super::markdown::{daily_file_format, dialect_for_path};
+use crate::todo::{TodoItem, TodoList};
+use crate::utils::paths::get_daily_file_path_for_project;
+use anyhow::{Context, Result};
+use chrono::{DateTime, NaiveDate, Utc};
+use sha2::{Digest, Sha256};
+use std::collections::{HashMap, HashSet};
+use std::fs;
+use std::path::Path;
+
+pub fn content_hash(content: &str) -> String {
+    format!("{:x}", Sha256::digest(content.as_bytes()))
+}
+
+fn modified_millis(path: &Path) -> Option<i64> {
+    let modified = fs::metadata(path).ok()?.modified().ok()?;
+    Some(DateTime::<Utc>::from(modified).timestamp_millis())
+}
+
+/// Write `list` over its daily file in the configured format and record
+/// what was written.
+pub fn write_daily_file(list: &TodoList, project_name: &str) -> Result<()> {
+    let content = daily_file_format().dialect().serialize(list);
+
+    // The template can put each day in a directory of its own
+    if let Some(dir) = list.file_path.parent() {
+        fs::create_dir_all(dir).with_context(|| format!("Failed to create {}", dir.display()))?;
+    }
+    let temp_path = list.file_path.with_extension("tmp");
+
+    fs::write(&temp_path, encryption::seal(&content)?)
+        .with_context(|| format!("Failed to write to temp file: {}", temp_path.display()))?;
+
+    fs::rename(&temp_path, &list.file_path).with_context(|| {
+        format!(
+            "Failed to rename temp file to: {}",
+            list.file_path.display()
+        )
+    })?;
+
+    record_daily_file(project_name, list.date, &list.file_path, content)
+}
+
+/// Record `content` as what the daily file at `path` holds, so later edits
+/// to it are noticed.
+pub fn record_daily_file(project_name: &str, date: NaiveDate, path: &Path, content: String) -> Result<()> {
+    let record = DailyFileRecord {
+        mtime: modified_millis(path).unwrap_or_default(),
+        hash: content_hash(&content),
+        content,
+    };
+    database::save_daily_file_record(date, project_name, &record)
+}
+
+/// The contents of the daily file at `path`, and the record of what totui
+/// wrote there, when the file changed since. Files totui has no record of
+/// count as unchanged, and so do missing ones: the next save writes them.
+fn external_edit(
+    project_name: &str,
+    date: NaiveDate,
+    path: &Path,
+) -> Result<Option<(String, DailyFileRecord)>> {
+    let Some(record) = database::load_daily_file_record(date, project_name)? else {
+        return Ok(None);
+    };
+    let Some(mtime) = modified_millis(path) else {
+        return Ok(None);
+    };
+    if mtime == record.mtime {
+        return Ok(None);
+    }
+
+    let content = encryption::read_to_string(path)?;
+    if content_hash(&content) == record.hash {
+        // Touched but not changed
+        database::save_daily_file_record(date, project_name, &DailyFileRecord { mtime, ..record })?;
+        return Ok(None);
+    }
+
+    Ok(Some((content, record)))
+}
+
+/// Whether the daily file at `path` changed since totui last wrote it.
+pub fn is_edited_externally(project_name: &str, date: NaiveDate, path: &Path) -> Result<bool> {
+    Ok(external_edit(project_name, date, path)?.is_some())
+}
+
+/// Look for edits made outside totui to the daily file of `date`.
+pub fn find_conflict(project_name: &str, date: NaiveDate) -> Result<Option<DailyFileConflict>> {
+    let path = get_daily_file_path_for_project(project_name, date)?;
+    let Some((content, record)) = external_edit(project_name, date, &path)? else {
+        return Ok(None);
+    };
+
+    let writer = daily_file_format().dialect();
+    let reader = dialect_for_path(&path).unwrap_or(writer);
+    let items = database::load_todos_for_date_and_project(date, project_name)?;
+    let stored = TodoList::with_items(date, path.clone(), items);
+    let written = writer.serialize(&stored);
+
+    Ok(Some(DailyFileConflict {
+        project_name: project_name.to_string(),
+        date,
+        file: reader
+            .parse(&content, date, path.clone())
+            .with_context(|| format!("Failed to parse {}", path.display()))?,
+        base: reader.parse(&record.content, date, path.clone())?,
+        written: reader.parse(&written, date, path.clone())?,
+        database_changed: written != record.content,
+        file_hash: content_hash(&content),
+        database: stored,
+    }))
+}
+
+/// A daily file changed outside totui, and the day as totui has it.
+#[derive(Debug, Clone)]
+pub struct DailyFileConflict {
+    pub project_name: String,
+    pub date: NaiveDate,
+    /// The day as the file has it now
+    pub file: TodoList,
+    /// The day as the database has it
+    pub database: TodoList,
+    /// Whether the database changed too since the file was last written
+    pub database_changed: bool,
+    /// Hash of the file's contents, which tells this edit from later ones
+    pub file_hash: String,
+    /// The day as totui last wrote it, which both versions started from
+    base: TodoList,
+    /// The database read back through the file format, to compare the
+    /// file's items with: fields the format can't hold are left out of both
+    written: TodoList,
+}
+
+/// One item the two versions disagree on, by its text.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Difference {
+    OnlyInFile(String),
+    OnlyInDatabase(String),
+    Changed(String),
+}
+
+/// What matches items across versions: their text, numbered when the same
+/// text appears more than once.
+type Key = (String, usize);
+
+fn keys(items: &[TodoItem]) -> Vec<Key> {
+    let mut seen: HashMap<&str, usize> = HashMap::new();
+    items
+        .iter()
+        .map(|item| {
+            let count = seen.entry(item.content.as_str()).or_default();
+            *count += 1;
+            (item.content.clone(), *count)
+        })
+        .collect()
+}
+
+fn by_key(items: &[TodoItem]) -> HashMap<Key, &TodoItem> {
+    keys(items).into_iter().zip(items).collect()
+}
+
+/// `stored` with the fields that differ between `before` and `after` taken
+/// from `after`, and whether any did.
+fn edited(stored: &TodoItem, before: &TodoItem, after: &TodoItem) -> (TodoItem, bool) {
+    let mut item = stored.clone();
+    let mut changed = false;
+    if after.state != before.state {
+        item.set_state(after.state);
+        changed = true;
+    }
+    macro_rules! take {
+        ($($field:ident),*) => {
+            $(if after.$field != before.$field {
+                item.$field = after.$field.clone();
+                changed = true;
+            })*
+        };
+    }
+    take!(indent_level, due_date, description, priority, estimate, waiting_on, pinned, link, location);
+    if changed {
+        item.modified_at = Utc::now();
+    }
+    (item, changed)
+}
+
+impl DailyFileConflict {
+    /// Keys of the file's items. An item carrying the id of an item in the
+    /// database matches that item whatever its text.
+    fn file_keys(&self) -> Vec<Key> {
+        let stored: HashMap<_, _> = self
+            .database
+            .items
+            .iter()
+            .zip(keys(&self.database.items))
+            .map(|(item, key)| (item.id, key))
+            .collect();
+        self.file
+            .items
+            .iter()
+            .zip(keys(&self.file.items))
+            .map(|(item, key)| stored.get(&item.id).cloned().unwrap_or(key))
+            .collect()
+    }
+
+    /// The database's item for each key.
+    fn stored(&self) -> HashMap<Key, &TodoItem> {
+        by_key(&self.database.items)
+    }
+
+    fn list(&self, mut items: Vec<TodoItem>) -> TodoList {
+        // Items the other side removed can leave subtasks a level too deep
+        let mut max_indent = 0;
+        for item in &mut items {
+            item.indent_level = item.indent_level.min(max_indent);
+            max_indent = item.indent_level + 1;
+        }
+        let mut list = TodoList::with_items(self.date, self.database.file_path.clone(), items);
+        list.recalculate_parent_ids();
+        list
+    }
+
+    /// The file's version. Items that match one in the database keep its id
+    /// and history, and whatever the file format has no room for.
+    pub fn keep_file(&self) -> TodoList {
+        let stored = self.stored();
+        let written = by_key(&self.written.items);
+        let items = self
+            .file
+            .items
+            .iter()
+            .zip(self.file_keys())
+            .map(|(item, key)| match (stored.get(&key), written.get(&key)) {
+                (Some(stored), Some(written)) => edited(stored, written, item).0,
+                (Some(stored), None) => edited(stored, stored, item).0,
+                _ => item.clone(),
+            })
+            .collect();
+        self.list(items)
+    }
+
+    /// The database's version, unchanged.
+    pub fn keep_database(&self) -> TodoList {
+        self.database.clone()
+    }
+
+    /// Both versions' edits since the file was last written. Items either
+    /// side removed are gone, items the file added go after the item above
+    /// them in the file, and fields edited in the file win over the database.
+    pub fn merge(&self) -> TodoList {
+        let base = by_key(&self.base.items);
+        let file_keys = self.file_keys();
+        let file: HashMap<&Key, &TodoItem> = file_keys.iter().zip(&self.file.items).collect();
+
+        let mut items = Vec::new();
+        let mut ids: HashMap<Key, uuid::Uuid> = HashMap::new();
+        for (item, key) in self.database.items.iter().zip(keys(&self.database.items)) {
+            match (base.get(&key), file.get(&key)) {
+                // Removed from the file
+                (Some(_), None) => continue,
+                (Some(before), Some(after)) => items.push(edited(item, before, after).0),
+                _ => items.push(item.clone()),
+            }
+            ids.insert(key, item.id);
+        }
+
+        let stored: HashSet<Key> = self.stored().into_keys().collect();
+        let mut anchor: Option<uuid::Uuid> = None;
+        for (item, key) in self.file.items.iter().zip(&file_keys) {
+            if base.contains_key(key) || stored.contains(key) {
+                if let Some(&id) = ids.get(key) {
+                    anchor = Some(id);
+                }
+                continue;
+            }
+            let mut position = anchor
+                .and_then(|id| items.iter().position(|i: &TodoItem| i.id == id))
+                .map_or(0, |index| index + 1);
+            // Past the anchor's subtasks unless the new item is one of them
+            while position < items.len() && items[position].indent_level > item.indent_level {
+                position += 1;
+            }
+            items.insert(position, item.clone());
+            anchor = Some(item.id);
+        }
+
+        self.list(items)
+    }
+
+    /// The items the file and the database disagree on, in file order and
+    /// then database order.
+    pub fn differences(&self) -> Vec<Difference> {
+        let stored = self.stored();
+        let written = by_key(&self.written.items);
+        let file_keys = self.file_keys();
+        let mut differences = Vec::new();
+
+        for (item, key) in self.file.items.iter().zip(&file_keys) {
+            match stored.get(key) {
+                None => differences.push(Difference::OnlyInFile(item.content.clone())),
+                Some(stored) => {
+                    let before = written.get(key).copied().unwrap_or(stored);
+                    if edited(stored, before, item).1 {
+                        differences.push(Difference::Changed(item.content.clone()));
+                    }
+                }
+            }
+        }
+
+        let in_file: HashSet<&Key> = file_keys.iter().collect();
+        for (item, key) in self.database.items.iter().zip(keys(&self.database.items)) {
+            if !in_file.contains(&key) {
+                differences.push(Difference::OnlyInDatabase(item.content.clone()));
+            }
+        }
+
+        differences
+    }
+
+    /// Save `list` as the day and write it over the daily file.
+    pub fn resolve(&self, list: &TodoList) -> Result<()> {
+        database::save_todo_list_for_project(list, &self.project_name)?;
+        write_daily_file(list, &self.project_name)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::storage::file::save_todo_list_for_project;
+    use crate::storage::markdown::{parse_todo_list, serialize_todo_list_clean};
+    use crate::todo::{Priority, TodoState};
+    use serial_test::serial;
+    use std::path::PathBuf;
+    use std::time::{Duration, SystemTime};
+    use tempfile::TempDir;
+
+    fn date() -> NaiveDate {
+        NaiveDate::from_ymd_opt(2026, 1, 18).unwrap()
+    }
+
+    fn parse(content: &str) -> TodoList {
+        parse_todo_list(content, date(), PathBuf::from("/tmp/2026-01-18.md")).unwrap()
+    }
+
+    /// Milk, Eggs and Bread were written; totui then finished Milk, dropped
+    /// Bread and added Butter, while the file bumped Milk's priority,
+    /// dropped Eggs and added Jam.
+    fn conflict() -> DailyFileConflict {
+        let base = parse("- [ ] Milk\n- [ ] Eggs\n- [ ] Bread\n");
+        let mut database = base.clone();
+        database.items[0].set_state(TodoState::Checked);
+        database.items[0].description = Some("Two liters".to_string());
+        database.items.remove(2);
+        database.add_item("Butter".to_string());
+        let written = parse(&serialize_todo_list_clean(&database));
+
+        DailyFileConflict {
+            project_name: "default".to_string(),
+            date: date(),
+            file: parse("- [ ] Milk @priority(P0)\n- [ ] Jam\n- [ ] Bread\n"),
+            database,
+            database_changed: true,
+            file_hash: String::new(),
+            base,
+            written,
+        }
+    }
+
+    fn contents(list: &TodoList) -> Vec<&str> {
+        list.items.iter().map(|item| item.content.as_str()).collect()
+    }
+
+    #[test]
+    fn test_merge_keeps_both_sides_edits() {
+        let conflict = conflict();
+        let merged = conflict.merge();
+
+        assert_eq!(contents(&merged), vec!["Milk", "Jam", "Butter"]);
+        let milk = &merged.items[0];
+        assert_eq!(milk.id, conflict.database.items[0].id);
+        assert_eq!(milk.state, TodoState::Checked);
+        assert_eq!(milk.priority, Some(Priority::P0));
+        assert_eq!(milk.description.as_deref(), Some("Two liters"));
+    }
+
+    #[test]
+    fn test_keep_file_matches_items_to_the_database() {
+        let conflict = conflict();
+        let kept = conflict.keep_file();
+
+        assert_eq!(contents(&kept), vec!["Milk", "Jam", "Bread"]);
+        let milk = &kept.items[0];
+        assert_eq!(milk.id, conflict.database.items[0].id);
+        assert_eq!(milk.state, TodoState::Empty);
+        assert_eq!(milk.completed_at, None);
+        assert_eq!(milk.description, None);
+    }
+
+    #[test]
+    fn test_differences() {
+        assert_eq!(
+            conflict().differences(),
+            vec![
+                Difference::Changed("Milk".to_string()),
+                Difference::OnlyInFile("Jam".to_string()),
+                Difference::OnlyInFile("Bread".to_string()),
+                Difference::OnlyInDatabase("Eggs".to_string()),
+                Difference::OnlyInDatabase("Butter".to_string()),
+            ]
+        );
+    }
+
+    #[test]
+    fn test_merge_nests_new_subtasks_under_their_parent() {
+        let mut conflict = conflict();
+        conflict.file = parse("- [ ] Milk\n  - [ ] Oat\n- [ ] Eggs\n- [ ] Bread\n");
+        let merged = conflict.merge();
+
+        assert_eq!(contents(&merged), vec!["Milk", "Oat", "Eggs", "Butter"]);
+        assert_eq!(merged.items[1].parent_id, Some(merged.items[0].id));
+    }
+
+    #[test]
+    #[serial]
+    fn test_saves_leave_edited_files_alone() {
+        let temp_dir = TempDir::new().unwrap();
+        std::fs::create_dir_all(temp_dir.path().join(".to-tui")).unwrap();
+        unsafe {
+            std::env::set_var("HOME", temp_dir.path());
+        }
+        database::init_database().unwrap();
+
+        let path = get_daily_file_path_for_project("default", date()).unwrap();
+        let mut list = TodoList::new(date(), path.clone());
+        list.add_item("Milk".to_string());
+        save_todo_list_for_project(&list, "default").unwrap();
+        assert!(find_conflict("default", date()).unwrap().is_none());
+
+        let edit = "# Groceries\n\n- [x] Milk\n";
+        std::fs::write(&path, edit).unwrap();
+        let later = SystemTime::now() + Duration::from_secs(5);
+        std::fs::File::options().write(true).open(&path).unwrap().set_modified(later).unwrap();
+
+        list.add_item("Eggs".to_string());
+        save_todo_list_for_project(&list, "default").unwrap();
+        assert_eq!(std::fs::read_to_string(&path).unwrap(), edit);
+
+        let conflict = find_conflict("default", date()).unwrap().unwrap();
+        assert!(conflict.database_changed);
+        let merged = conflict.merge();
+        assert_eq!(contents(&merged), vec!["Milk", "Eggs"]);
+        assert_eq!(merged.items[0].state, TodoState::Checked);
+
+        conflict.resolve(&merged).unwrap();
+        assert!(find_conflict("default", date()).unwrap().is_none());
+        assert!(std::fs::read_to_string(&path).unwrap().contains("- [x] Milk"));
+    }
+}
@@ -1,10 +1,23 @@
+use super::archive_compaction::ARCHIVE_SUMMARIES_SCHEMA;
+use super::attachments::{remove_orphaned_attachments, ATTACHMENTS_SCHEMA};
+use super::encryption;
+use super::history::{record_on, EventKind, NewEvent, HISTORY_SCHEMA};
+use super::idempotency::IDEMPOTENCY_SCHEMA;
+use super::revision::{revision_on, StaleDay, REVISION_SCHEMA};
+use super::sync::SYNC_SCHEMA;
 use crate::project::DEFAULT_PROJECT_NAME;
-use crate::todo::{Priority, TodoItem, TodoList, TodoState};
+use crate::todo::{Estimate, Location, Priority, TodoItem, TodoList, TodoState};
 use crate::utils::paths::get_to_tui_dir;
 use anyhow::{Context, Result};
 use chrono::{DateTime, NaiveDate, Utc};
-use rusqlite::{params, Connection};
-use std::path::PathBuf;
+use rusqlite::types::Value;
+use rusqlite::{params, Connection, OptionalExtension, TransactionBehavior};
+use std::collections::{HashMap, HashSet};
+use std::mem::ManuallyDrop;
+use std::ops::{Deref, DerefMut};
+use std::path::{Path, PathBuf};
+use std::sync::Mutex;
+use std::time::Duration;
 use tracing::{debug, trace};
 use uuid::Uuid;
 
@@ -21,7 +34,7 @@ fn get_db_path() -> Result<PathBuf> {
 }
 
 /// Raw data extracted from a database row before conversion to TodoItem
-struct TodoRowData {
+pub(super) struct TodoRowData {
     id_str: String,
     content: String,
     state_str: String,
@@ -35,10 +48,15 @@ struct TodoRowData {
     updated_at_str: Option<String>,
     completed_at_str: Option<String>,
     deleted_at_str: Option<String>,
+    estimate_minutes: Option<i64>,
+    waiting_on: Option<String>,
+    pinned: bool,
+    link: Option<String>,
+    location: Option<String>,
 }
 
 impl TodoRowData {
-    fn from_row(row: &rusqlite::Row) -> rusqlite::Result<Self> {
+    pub(super) fn from_row(row: &rusqlite::Row) -> rusqlite::Result<Self> {
         let indent_level: i64 = row.get(3)?;
         Ok(Self {
             id_str: row.get(0)?,
@@ -54,10 +72,15 @@ impl TodoRowData {
             updated_at_str: row.get(10).ok(),
             completed_at_str: row.get(11).ok().flatten(),
             deleted_at_str: row.get(12).ok().flatten(),
+            estimate_minutes: row.get(13).ok().flatten(),
+            waiting_on: row.get(14).ok().flatten(),
+            pinned: row.get::<_, Option<bool>>(15).ok().flatten().unwrap_or(false),
+            link: row.get(16).ok().flatten(),
+            location: row.get(17).ok().flatten(),
         })
     }
 
-    fn into_todo_item(self) -> TodoItem {
+    pub(super) fn into_todo_item(self) -> TodoItem {
         let id = Uuid::parse_str(&self.id_str).unwrap_or_else(|_| Uuid::new_v4());
         let state = TodoState::from_char(self.state_str.chars().next().unwrap_or(' '))
             .unwrap_or(TodoState::Empty);
@@ -75,6 +98,14 @@ impl TodoRowData {
         todo.description = self.description;
         todo.priority = priority;
         todo.collapsed = self.collapsed != 0;
+        todo.estimate = self
+            .estimate_minutes
+            .and_then(|m| u32::try_from(m).ok())
+            .map(Estimate::from_minutes);
+        todo.waiting_on = self.waiting_on;
+        todo.pinned = self.pinned;
+        todo.link = self.link;
+        todo.location = self.location.as_deref().and_then(Location::parse);
 
         if let Some(s) = self.created_at_str
             && let Some(dt) = parse_rfc3339(&s) {
@@ -95,15 +126,105 @@ impl TodoRowData {
     }
 }
 
-pub fn get_connection() -> Result<Connection> {
-    let db_path = get_db_path()?;
-    let conn = Connection::open(&db_path)
+/// How long a statement waits for another connection's write lock (the TUI,
+/// the API server and the MCP server share the database) before failing.
+const BUSY_TIMEOUT: Duration = Duration::from_secs(5);
+
+/// Idle connections kept open for reuse; more can be open while in use.
+const MAX_IDLE_CONNECTIONS: usize = 4;
+
+/// Open connections to one database file, waiting to be reused.
+struct ConnectionPool {
+    path: PathBuf,
+    idle: Vec<Connection>,
+}
+
+static POOL: Mutex<Option<ConnectionPool>> = Mutex::new(None);
+
+/// A connection from the pool. It goes back to the pool when dropped.
+pub struct PooledConnection {
+    conn: ManuallyDrop<Connection>,
+    path: PathBuf,
+}
+
+impl Deref for PooledConnection {
+    type Target = Connection;
+
+    fn deref(&self) -> &Connection {
+        &self.conn
+    }
+}
+
+impl DerefMut for PooledConnection {
+    fn deref_mut(&mut self) -> &mut Connection {
+        &mut self.conn
+    }
+}
+
+impl Drop for PooledConnection {
+    fn drop(&mut self) {
+        // SAFETY: `conn` is not touched again after this; `self` is going away
+        let conn = unsafe { ManuallyDrop::take(&mut self.conn) };
+        let mut pool = POOL.lock().unwrap_or_else(|e| e.into_inner());
+        // A connection to a database the pool no longer serves is just closed
+        if let Some(pool) = pool.as_mut()
+            && pool.path == self.path
+            && pool.idle.len() < MAX_IDLE_CONNECTIONS
+        {
+            pool.idle.push(conn);
+        }
+    }
+}
+
+fn open_connection(db_path: &Path) -> Result<Connection> {
+    encryption::unlock()?;
+    let conn = Connection::open(db_path)
         .with_context(|| format!("Failed to open database at {db_path:?}"))?;
+    encryption::key_connection(&conn)?;
+    conn.busy_timeout(BUSY_TIMEOUT)?;
+    // WAL lets readers carry on while another process writes
+    conn.pragma_update_and_check(None, "journal_mode", "WAL", |row| row.get::<_, String>(0))
+        .context("Failed to enable WAL mode")?;
+    conn.pragma_update(None, "synchronous", "NORMAL")?;
     Ok(conn)
 }
 
+/// Get a connection to the database, reusing an idle one when there is one.
+/// Connections run in WAL mode with a busy timeout, so concurrent access
+/// from the TUI, the API server and the file watcher waits instead of failing.
+pub fn get_connection() -> Result<PooledConnection> {
+    let db_path = get_db_path()?;
+    let idle = {
+        let mut pool = POOL.lock().unwrap_or_else(|e| e.into_inner());
+        match pool.as_mut() {
+            Some(pool) if pool.path == db_path => pool.idle.pop(),
+            _ => {
+                *pool = Some(ConnectionPool {
+                    path: db_path.clone(),
+                    idle: Vec::new(),
+                });
+                None
+            }
+        }
+    };
+    let conn = match idle {
+        Some(conn) => conn,
+        None => open_connection(&db_path)?,
+    };
+    Ok(PooledConnection {
+        conn: ManuallyDrop::new(conn),
+        path: db_path,
+    })
+}
+
+/// Close the idle connections, e.g. before the database file is replaced.
+/// Connections in use are closed when they are dropped.
+pub fn close_connections() {
+    *POOL.lock().unwrap_or_else(|e| e.into_inner()) = None;
+}
+
 pub fn init_database() -> Result<()> {
-    let conn = get_connection()?;
+    let mut conn = get_connection()?;
 
     conn.execute(
         "CREATE TABLE IF NOT EXISTS todos (
@@ -142,6 +263,24 @@ pub fn init_database() -> Result<()> {
     conn.execute("ALTER TABLE todos ADD COLUMN priority TEXT", [])
         .ok();
 
+    conn.execute("ALTER TABLE todos ADD COLUMN estimate_minutes INTEGER", [])
+        .ok();
+
+    conn.execute("ALTER TABLE todos ADD COLUMN waiting_on TEXT", [])
+        .ok();
+
+    conn.execute("ALTER TABLE todos ADD COLUMN pinned INTEGER NOT NULL DEFAULT 0", [])
+        .ok();
+
+    conn.execute("ALTER TABLE todos ADD COLUMN link TEXT", [])
+        .ok();
+
+    conn.execute("ALTER TABLE todos ADD COLUMN location TEXT", [])
+        .ok();
+
+    conn.execute("ALTER TABLE todos ADD COLUMN short_id TEXT", [])
+        .ok();
+
     // Add project column for existing databases
     conn.execute(
         "ALTER TABLE todos ADD COLUMN project TEXT NOT NULL DEFAULT 'default'",
@@ -209,6 +348,21 @@ pub fn init_database() -> Result<()> {
     conn.execute("ALTER TABLE archived_todos ADD COLUMN priority TEXT", [])
         .ok();
 
+    conn.execute("ALTER TABLE archived_todos ADD COLUMN estimate_minutes INTEGER", [])
+        .ok();
+
+    conn.execute("ALTER TABLE archived_todos ADD COLUMN waiting_on TEXT", [])
+        .ok();
+
+    conn.execute("ALTER TABLE archived_todos ADD COLUMN pinned INTEGER NOT NULL DEFAULT 0", [])
+        .ok();
+
+    conn.execute("ALTER TABLE archived_todos ADD COLUMN link TEXT", [])
+        .ok();
+
+    conn.execute("ALTER TABLE archived_todos ADD COLUMN location TEXT", [])
+        .ok();
+
     // Add project column for existing databases
     conn.execute(
         "ALTER TABLE archived_todos ADD COLUMN project TEXT NOT NULL DEFAULT 'default'",
@@ -226,6 +380,11 @@ pub fn init_database() -> Result<()> {
         [],
     )?;
 
+    // Full-text indexes over content and descriptions. Each row's rowid is
+    // the rowid of its todo; triggers keep them in step with every write.
+    conn.execute_batch(SEARCH_INDEX_SCHEMA)?;
+    rebuild_stale_search_index(&mut conn)?;
+
     // Projects table
     conn.execute(
         "CREATE TABLE IF NOT EXISTS projects (
@@ -293,9 +452,171 @@ pub fn init_database() -> Result<()> {
         [],
     )?;
 
+    conn.execute_batch(HISTORY_SCHEMA)?;
+    conn.execute_batch(ATTACHMENTS_SCHEMA)?;
+    conn.execute_batch(ARCHIVE_SUMMARIES_SCHEMA)?;
+
+    // Change counter for sync consumers. Rows that exist before the column
+    // does are numbered in insertion order, before the triggers take over.
+    if conn
+        .execute("ALTER TABLE todos ADD COLUMN change_seq INTEGER NOT NULL DEFAULT 0", [])
+        .is_ok()
+    {
+        conn.execute("UPDATE todos SET change_seq = rowid", [])?;
+    }
+    conn.execute(
+        "CREATE INDEX IF NOT EXISTS idx_todos_change_seq ON todos(change_seq)",
+        [],
+    )?;
+    conn.execute_batch(SYNC_SCHEMA)?;
+    conn.execute_batch(IDEMPOTENCY_SCHEMA)?;
+    conn.execute_batch(REVISION_SCHEMA)?;
+
+    // Daily files as last written, to notice edits made outside totui
+    conn.execute(
+        "CREATE TABLE IF NOT EXISTS daily_files (
+            project TEXT NOT NULL,
+            date TEXT NOT NULL,
+            mtime INTEGER NOT NULL,
+            hash TEXT NOT NULL,
+            content TEXT NOT NULL,
+            PRIMARY KEY (project, date)
+        )",
+        [],
+    )?;
+
     Ok(())
 }
 
+/// FTS5 tables for `todos` and `archived_todos` and the triggers that
+/// maintain them. Inserts delete first: `INSERT OR REPLACE` removes the old
+/// row without firing delete triggers, so its index row is only dropped when
+/// the rowid is reused, and searches join back to the table to skip it.
+const SEARCH_INDEX_SCHEMA: &str = "
+    CREATE VIRTUAL TABLE IF NOT EXISTS todos_fts USING fts5(content, description);
+    CREATE VIRTUAL TABLE IF NOT EXISTS archived_todos_fts USING fts5(content, description);
+
+    CREATE TRIGGER IF NOT EXISTS todos_fts_insert AFTER INSERT ON todos BEGIN
+        DELETE FROM todos_fts WHERE rowid = new.rowid;
+        INSERT INTO todos_fts(rowid, content, description) VALUES (new.rowid, new.content, new.description);
+    END;
+    CREATE TRIGGER IF NOT EXISTS todos_fts_update AFTER UPDATE OF content, description ON todos BEGIN
+        DELETE FROM todos_fts WHERE rowid = old.rowid;
+        INSERT INTO todos_fts(rowid, content, description) VALUES (new.rowid, new.content, new.description);
+    END;
+    CREATE TRIGGER IF NOT EXISTS todos_fts_delete AFTER DELETE ON todos BEGIN
+        DELETE FROM todos_fts WHERE rowid = old.rowid;
+    END;
+
+    CREATE TRIGGER IF NOT EXISTS archived_todos_fts_insert AFTER INSERT ON archived_todos BEGIN
+        DELETE FROM archived_todos_fts WHERE rowid = new.rowid;
+        INSERT INTO archived_todos_fts(rowid, content, description) VALUES (new.rowid, new.content, new.description);
+    END;
+    CREATE TRIGGER IF NOT EXISTS archived_todos_fts_update AFTER UPDATE OF content, description ON archived_todos BEGIN
+        DELETE FROM archived_todos_fts WHERE rowid = old.rowid;
+        INSERT INTO archived_todos_fts(rowid, content, description) VALUES (new.rowid, new.content, new.description);
+    END;
+    CREATE TRIGGER IF NOT EXISTS archived_todos_fts_delete AFTER DELETE ON archived_todos BEGIN
+        DELETE FROM archived_todos_fts WHERE rowid = old.rowid;
+    END;
+";
+
+/// Fill the search indexes from their tables when the row counts disagree:
+/// on the first run after upgrading, or after rows were left behind.
+fn rebuild_stale_search_index(conn: &mut Connection) -> Result<()> {
+    for (table, index) in [("todos", "todos_fts"), ("archived_todos", "archived_todos_fts")] {
+        let count = |name: &str| -> rusqlite::Result<i64> {
+            conn.query_row(&format!("SELECT COUNT(*) FROM {name}"), [], |row| row.get(0))
+        };
+        if count(table)? == count(index)? {
+            continue;
+        }
+        debug!(table, "Rebuilding full-text search index");
+        // Dropping the transaction on an error rolls it back, leaving the
+        // old index rather than a half-filled one
+        let tx = conn.transaction()?;
+        tx.execute(&format!("DELETE FROM {index}"), [])?;
+        tx.execute(
+            &format!("INSERT INTO {index}(rowid, content, description) SELECT rowid, content, description FROM {table}"),
+            [],
+        )?;
+        tx.commit()?;
+    }
+    Ok(())
+}
+
+/// A todo found by [`search_todos`].
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct SearchHit {
+    pub id: Uuid,
+    pub date: NaiveDate,
+    pub project: String,
+    pub content: String,
+    pub state: TodoState,
+    pub archived: bool,
+}
+
+/// Turn what a user typed into an FTS5 query: every word must appear, as a
+/// word or the start of one. Quoting keeps FTS syntax like `-` or `OR` literal.
+/// None when there are no words.
+fn fts_query(text: &str) -> Option<String> {
+    let terms: Vec<String> = text
+        .split_whitespace()
+        .map(|word| format!("\"{}\"*", word.replace('"', "\"\"")))
+        .collect();
+    (!terms.is_empty()).then(|| terms.join(" "))
+}
+
+/// Search the content and descriptions of live and archived todos on every
+/// date, newest first. `project` limits the search to one project.
+pub fn search_todos(text: &str, project: Option<&str>, limit: usize) -> Result<Vec<SearchHit>> {
+    let Some(query) = fts_query(text) else {
+        return Ok(Vec::new());
+    };
+    let conn = get_connection()?;
+
+    let mut stmt = conn.prepare(
+        "SELECT t.id, t.date, t.project, t.content, t.state, 0
+         FROM todos_fts JOIN todos t ON t.rowid = todos_fts.rowid
+         WHERE todos_fts MATCH ?1 AND t.deleted_at IS NULL AND (?2 IS NULL OR t.project = ?2)
+         UNION ALL
+         SELECT a.id, a.original_date, a.project, a.content, a.state, 1
+         FROM archived_todos_fts JOIN archived_todos a ON a.rowid = archived_todos_fts.rowid
+         WHERE archived_todos_fts MATCH ?1 AND a.deleted_at IS NULL AND (?2 IS NULL OR a.project = ?2)
+         ORDER BY 2 DESC
+         LIMIT ?3",
+    )?;
+
+    let rows = stmt.query_map(params![query, project, limit as i64], |row| {
+        Ok((
+            row.get::<_, String>(0)?,
+            row.get::<_, String>(1)?,
+            row.get::<_, String>(2)?,
+            row.get::<_, String>(3)?,
+            row.get::<_, String>(4)?,
+            row.get::<_, bool>(5)?,
+        ))
+    })?;
+
+    let mut hits = Vec::new();
+    for row in rows {
+        let (id, date, project, content, state, archived) = row?;
+        let (Ok(id), Ok(date)) = (Uuid::parse_str(&id), NaiveDate::parse_from_str(&date, "%Y-%m-%d")) else {
+            continue;
+        };
+        let state = TodoState::from_char(state.chars().next().unwrap_or(' ')).unwrap_or(TodoState::Empty);
+        hits.push(SearchHit {
+            id,
+            date,
+            project,
+            content,
+            state,
+            archived,
+        });
+    }
+    Ok(hits)
+}
+
 pub fn load_todos_for_date_and_project(
     date: NaiveDate,
     project_name: &str,
@@ -304,17 +625,22 @@ pub fn load_todos_for_date_and_project(
     let date_str = date.format("%Y-%m-%d").to_string();
 
     let mut stmt = conn.prepare(
-        "SELECT id, content, state, indent_level, parent_id, due_date, description, priority, collapsed, created_at, updated_at, completed_at, deleted_at
+        "SELECT id, content, state, indent_level, parent_id, due_date, description, priority, collapsed, created_at, updated_at, completed_at, deleted_at, estimate_minutes, waiting_on, pinned, link, location, short_id
          FROM todos
          WHERE date = ?1 AND project = ?2 AND deleted_at IS NULL
          ORDER BY position ASC",
     )?;
 
-    let items = stmt.query_map(params![&date_str, project_name], TodoRowData::from_row)?;
+    let items = stmt.query_map(params![&date_str, project_name], |row| {
+        Ok((TodoRowData::from_row(row)?, row.get::<_, Option<String>>(18)?))
+    })?;
 
     let mut result = Vec::new();
     for item in items {
-        result.push(item?.into_todo_item());
+        let (data, short_id) = item?;
+        let mut item = data.into_todo_item();
+        item.short_id = short_id;
+        result.push(item);
     }
 
     Ok(result)
@@ -339,139 +665,336 @@ pub fn soft_delete_todos_for_project(
     );
 
     let conn = get_connection()?;
+    soft_delete_on(&conn, ids, date, project_name)?;
+
+    // Clean up metadata for soft-deleted todos
+    cleanup_orphaned_metadata()?;
+
+    debug!(count = ids.len(), "soft_delete completed");
+    Ok(())
+}
+
+fn soft_delete_on(conn: &Connection, ids: &[Uuid], date: NaiveDate, project_name: &str) -> Result<()> {
     let date_str = date.format("%Y-%m-%d").to_string();
     let now = chrono::Utc::now().to_rfc3339();
 
     for id in ids {
         let id_str = id.to_string();
         trace!(id = %id_str, "Setting deleted_at on todo");
-        conn.execute(
-            "UPDATE todos SET deleted_at = ?1, updated_at = ?1 WHERE id = ?2 AND date = ?3 AND project = ?4",
-            params![now, id_str, date_str, project_name],
-        )?;
+        let content: Option<String> = conn
+            .query_row(
+                "UPDATE todos SET deleted_at = ?1, updated_at = ?1 WHERE id = ?2 AND date = ?3 AND project = ?4
+                 RETURNING content",
+                params![now, id_str, date_str, project_name],
+                |row| row.get(0),
+            )
+            .optional()?;
+        if let Some(content) = content {
+            record_on(
+                conn,
+                &NewEvent {
+                    date: Some(date),
+                    todo_id: Some(&id_str),
+                    content: Some(&content),
+                    ..NewEvent::new(EventKind::Delete, project_name)
+                },
+            )?;
+        }
     }
+    Ok(())
+}
 
-    // Clean up metadata for soft-deleted todos
-    cleanup_orphaned_metadata()?;
+/// Columns written for each item, in the order of `todo_row_values`.
+const TODO_ROW_COLUMNS: [&str; 22] = [
+    "id",
+    "date",
+    "content",
+    "state",
+    "indent_level",
+    "parent_id",
+    "due_date",
+    "description",
+    "priority",
+    "collapsed",
+    "position",
+    "created_at",
+    "updated_at",
+    "completed_at",
+    "deleted_at",
+    "project",
+    "estimate_minutes",
+    "waiting_on",
+    "pinned",
+    "link",
+    "location",
+    "short_id",
+];
+
+const ROW_ID: usize = row_column("id");
+const ROW_CONTENT: usize = row_column("content");
+const ROW_DELETED_AT: usize = row_column("deleted_at");
+
+/// Where `name` is in [`TODO_ROW_COLUMNS`], checked when compiling.
+const fn row_column(name: &str) -> usize {
+    let mut i = 0;
+    while i < TODO_ROW_COLUMNS.len() {
+        if TODO_ROW_COLUMNS[i].eq_ignore_ascii_case(name) {
+            return i;
+        }
+        i += 1;
+    }
+    panic!("not a todo row column")
+}
 
-    debug!(count = ids.len(), "soft_delete completed");
-    Ok(())
+/// Columns that change along with others or only affect how the list is
+/// shown, so changes to them alone aren't logged.
+const UNLOGGED_COLUMNS: [&str; 7] =
+    ["position", "parent_id", "collapsed", "updated_at", "completed_at", "deleted_at", "short_id"];
+
+/// The events to log for writing `values` over the `stored` row.
+fn change_events(stored: Option<&Vec<Value>>, values: &[Value]) -> Vec<(EventKind, Option<String>)> {
+    let Some(stored) = stored else {
+        return vec![(EventKind::Add, None)];
+    };
+    let text = |value: &Value| match value {
+        Value::Text(text) => text.clone(),
+        _ => String::new(),
+    };
+
+    let mut events = Vec::new();
+    if stored[ROW_DELETED_AT] != Value::Null {
+        events.push((EventKind::Restore, None));
+    }
+    let mut fields = Vec::new();
+    for ((column, old), new) in TODO_ROW_COLUMNS.iter().zip(stored).zip(values) {
+        if old == new || UNLOGGED_COLUMNS.contains(column) {
+            continue;
+        }
+        if *column == "state" {
+            events.push((EventKind::State, Some(format!("[{}] -> [{}]", text(old), text(new)))));
+        } else {
+            fields.push(*column);
+        }
+    }
+    if !fields.is_empty() {
+        events.push((EventKind::Edit, Some(fields.join(", "))));
+    }
+    events
 }
 
+/// The row an item is stored as, as values in `TODO_ROW_COLUMNS` order.
+fn todo_row_values(
+    item: &TodoItem,
+    short_id: Option<&String>,
+    position: usize,
+    date_str: &str,
+    project_name: &str,
+) -> Vec<Value> {
+    vec![
+        item.id.to_string().into(),
+        date_str.to_string().into(),
+        item.content.clone().into(),
+        item.state.to_char().to_string().into(),
+        (item.indent_level as i64).into(),
+        item.parent_id.map(|id| id.to_string()).into(),
+        item.due_date.map(|d| d.format("%Y-%m-%d").to_string()).into(),
+        item.description.clone().into(),
+        item.priority.and_then(|p| p.to_db_str()).into(),
+        item.collapsed.into(),
+        (position as i64).into(),
+        item.created_at.to_rfc3339().into(),
+        item.modified_at.to_rfc3339().into(),
+        item.completed_at.map(|dt| dt.to_rfc3339()).into(),
+        item.deleted_at.map(|dt| dt.to_rfc3339()).into(),
+        project_name.to_string().into(),
+        item.estimate.map(|e| e.minutes() as i64).into(),
+        item.waiting_on.clone().into(),
+        item.pinned.into(),
+        item.link.clone().into(),
+        item.location.as_ref().map(|l| l.to_string()).into(),
+        short_id.cloned().into(),
+    ]
+}
+
+/// Save a day's list in one transaction, writing only the rows that changed
+/// since the last save. Rows for items no longer in the list are deleted,
+/// unless they were soft-deleted (those stay for the trash).
 pub fn save_todo_list_for_project(list: &TodoList, project_name: &str) -> Result<()> {
-    let conn = get_connection()?;
+    let mut conn = get_connection()?;
+    let tx = conn.transaction()?;
+    save_on(&tx, list, project_name)?;
+    tx.commit()?;
+    Ok(())
+}
+
+/// Save a day's list only if the day is still at `revision`, the revision it
+/// was loaded at, and return the day's new revision. Fails with `StaleDay`
+/// when someone else saved the day in between.
+pub fn save_todo_list_if_current(list: &TodoList, project_name: &str, revision: u64) -> Result<u64> {
+    let mut conn = get_connection()?;
+    // Taking the write lock up front keeps other writers out between the
+    // check and the save
+    let tx = conn.transaction_with_behavior(TransactionBehavior::Immediate)?;
+    let current = revision_on(&tx, list.date, project_name)?;
+    if current != revision {
+        return Err(StaleDay { revision: current }.into());
+    }
+    save_on(&tx, list, project_name)?;
+    let saved = revision_on(&tx, list.date, project_name)?;
+    tx.commit()?;
+    Ok(saved)
+}
+
+/// Move `trashed` to the trash and save `list`, which no longer has them,
+/// in one transaction, only if the day is still at `revision`. Returns the
+/// day's new revision; fails with `StaleDay` like `save_todo_list_if_current`.
+pub fn trash_todos_if_current(list: &TodoList, trashed: &[Uuid], project_name: &str, revision: u64) -> Result<u64> {
+    let mut conn = get_connection()?;
+    let tx = conn.transaction_with_behavior(TransactionBehavior::Immediate)?;
+    let current = revision_on(&tx, list.date, project_name)?;
+    if current != revision {
+        return Err(StaleDay { revision: current }.into());
+    }
+    soft_delete_on(&tx, trashed, list.date, project_name)?;
+    save_on(&tx, list, project_name)?;
+    let saved = revision_on(&tx, list.date, project_name)?;
+    tx.commit()?;
+    drop(conn);
+
+    cleanup_orphaned_metadata()?;
+    Ok(saved)
+}
+
+fn save_on(tx: &Connection, list: &TodoList, project_name: &str) -> Result<()> {
     let date_str = list.date.format("%Y-%m-%d").to_string();
 
     debug!(
         project = %project_name,
         date = %date_str,
         item_count = list.items.len(),
-        ids = ?list.items.iter().map(|i| i.id.to_string()).collect::<Vec<_>>(),
         "save_todo_list_for_project: starting save"
     );
 
-    // Collect IDs of items being saved (for cleanup of removed items later)
-    let item_ids: Vec<String> = list.items.iter().map(|i| i.id.to_string()).collect();
-
-    // Use UPSERT to handle both new items and existing items (including soft-deleted ones).
-    // This handles the undo case cleanly: when a soft-deleted item is restored via undo,
-    // we UPDATE the existing row to clear deleted_at rather than trying to INSERT.
-    let mut stmt = conn.prepare(
-        "INSERT INTO todos (id, date, content, state, indent_level, parent_id, due_date, description, priority, collapsed, position, created_at, updated_at, completed_at, deleted_at, project)
-         VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7, ?8, ?9, ?10, ?11, ?12, ?13, ?14, ?15, ?16)
-         ON CONFLICT(id) DO UPDATE SET
-             date = excluded.date,
-             content = excluded.content,
-             state = excluded.state,
-             indent_level = excluded.indent_level,
-             parent_id = excluded.parent_id,
-             due_date = excluded.due_date,
-             description = excluded.description,
-             priority = excluded.priority,
-             collapsed = excluded.collapsed,
-             position = excluded.position,
-             updated_at = excluded.updated_at,
-             completed_at = excluded.completed_at,
-             deleted_at = NULL,
-             project = excluded.project"
-    )?;
-
-    let mut inserted_count = 0;
-    let mut updated_count = 0;
-
-    for (position, item) in list.items.iter().enumerate() {
-        let id_str = item.id.to_string();
-        let state_str = item.state.to_char().to_string();
-        let parent_id_str = item.parent_id.map(|id| id.to_string());
-        let due_date_str = item.due_date.map(|d| d.format("%Y-%m-%d").to_string());
-        let priority_str = item.priority.and_then(|p| p.to_db_str());
-        let collapsed_int: i32 = if item.collapsed { 1 } else { 0 };
-        let created_at_str = item.created_at.to_rfc3339();
-        let modified_at_str = item.modified_at.to_rfc3339();
-        let completed_at_str = item.completed_at.map(|dt| dt.to_rfc3339());
-        let deleted_at_str = item.deleted_at.map(|dt| dt.to_rfc3339());
-
-        // Check if this is an update (row exists) or insert (new row)
-        let exists: bool = conn.query_row(
-            "SELECT EXISTS(SELECT 1 FROM todos WHERE id = ?1)",
-            params![&id_str],
-            |row| row.get(0),
-        ).unwrap_or(false);
-
-        if exists {
-            trace!(id = %id_str, content = %item.content, "Updating existing todo (possibly restoring from soft-delete)");
-            updated_count += 1;
-        } else {
-            trace!(id = %id_str, content = %item.content, "Inserting new todo");
-            inserted_count += 1;
+    let columns = TODO_ROW_COLUMNS.join(", ");
+    let placeholders: Vec<String> = (1..=TODO_ROW_COLUMNS.len()).map(|i| format!("?{i}")).collect();
+
+    // The day's rows as stored, to compare against
+    let mut stored: HashMap<String, Vec<Value>> = HashMap::new();
+    {
+        let mut stmt = tx.prepare(&format!(
+            "SELECT {columns} FROM todos WHERE date = ?1 AND project = ?2"
+        ))?;
+        let rows = stmt.query_map(params![date_str, project_name], |row| {
+            (0..TODO_ROW_COLUMNS.len())
+                .map(|i| row.get::<_, Value>(i))
+                .collect::<rusqlite::Result<Vec<_>>>()
+        })?;
+        for row in rows {
+            let row = row?;
+            if let Value::Text(id) = &row[ROW_ID] {
+                stored.insert(id.clone(), row);
+            }
         }
+    }
 
-        stmt.execute(params![
-            id_str,
-            date_str,
-            item.content,
-            state_str,
-            item.indent_level as i64,
-            parent_id_str,
-            due_date_str,
-            item.description,
-            priority_str,
-            collapsed_int,
-            position as i64,
-            created_at_str,
-            modified_at_str,
-            completed_at_str,
-            deleted_at_str,
-            project_name,
-        ])?;
+    let mut written = 0;
+    {
+        // UPSERT handles new items and existing ones alike, including
+        // soft-deleted rows restored by undo: their deleted_at is cleared.
+        let mut stmt = tx.prepare(&format!(
+            "INSERT INTO todos ({columns})
+             VALUES ({})
+             ON CONFLICT(id) DO UPDATE SET
+                 date = excluded.date,
+                 content = excluded.content,
+                 state = excluded.state,
+                 indent_level = excluded.indent_level,
+                 parent_id = excluded.parent_id,
+                 due_date = excluded.due_date,
+                 description = excluded.description,
+                 priority = excluded.priority,
+                 collapsed = excluded.collapsed,
+                 position = excluded.position,
+                 updated_at = excluded.updated_at,
+                 completed_at = excluded.completed_at,
+                 deleted_at = NULL,
+                 project = excluded.project,
+                 estimate_minutes = excluded.estimate_minutes,
+                 waiting_on = excluded.waiting_on,
+                 pinned = excluded.pinned,
+                 link = excluded.link,
+                 location = excluded.location,
+                 short_id = excluded.short_id",
+            placeholders.join(", ")
+        ))?;
+
+        let short_ids = list.short_ids();
+        for (position, item) in list.items.iter().enumerate() {
+            let values = todo_row_values(item, short_ids.get(&item.id), position, &date_str, project_name);
+            let id_str = item.id.to_string();
+            if stored.get(&id_str) == Some(&values) {
+                continue;
+            }
+            trace!(id = %id_str, content = %item.content, "Writing changed todo");
+            stmt.execute(rusqlite::params_from_iter(values.iter()))?;
+            if !stored.contains_key(&id_str) {
+                // Back from the archive, e.g. by undoing the archiving; an
+                // item is live or archived, never both
+                tx.execute(
+                    "DELETE FROM archived_todos WHERE id = ?1 AND project = ?2",
+                    params![id_str, project_name],
+                )?;
+            }
+            written += 1;
+            for (kind, detail) in change_events(stored.get(&id_str), &values) {
+                record_on(
+                    tx,
+                    &NewEvent {
+                        date: Some(list.date),
+                        todo_id: Some(&id_str),
+                        content: Some(&item.content),
+                        detail,
+                        ..NewEvent::new(kind, project_name)
+                    },
+                )?;
+            }
+        }
     }
 
     // Delete items that are no longer in the list (but weren't soft-deleted).
     // These are items that were removed from the in-memory list but not via soft_delete.
-    // Build a NOT IN clause for items being saved.
-    if !item_ids.is_empty() {
-        let placeholders: String = item_ids.iter().map(|_| "?").collect::<Vec<_>>().join(",");
-        let sql = format!(
-            "DELETE FROM todos WHERE date = ?1 AND project = ?2 AND deleted_at IS NULL AND id NOT IN ({})",
-            placeholders
-        );
-        
-        let mut delete_params: Vec<&dyn rusqlite::ToSql> = vec![&date_str, &project_name];
-        for id in &item_ids {
-            delete_params.push(id);
-        }
-        
-        let removed_count = conn.execute(&sql, rusqlite::params_from_iter(delete_params))?;
-        if removed_count > 0 {
-            debug!(removed_count = removed_count, "Removed items no longer in list");
+    let item_ids: HashSet<String> = list.items.iter().map(|i| i.id.to_string()).collect();
+    let mut removed = 0;
+    for (id, row) in &stored {
+        if !item_ids.is_empty() && !item_ids.contains(id) && row[ROW_DELETED_AT] == Value::Null {
+            let deleted = tx.execute(
+                "DELETE FROM todos WHERE id = ?1 AND deleted_at IS NULL",
+                params![id],
+            )?;
+            if deleted > 0 {
+                let content = match &row[ROW_CONTENT] {
+                    Value::Text(content) => Some(content.as_str()),
+                    _ => None,
+                };
+                record_on(
+                    tx,
+                    &NewEvent {
+                        date: Some(list.date),
+                        todo_id: Some(id),
+                        content,
+                        ..NewEvent::new(EventKind::Delete, project_name)
+                    },
+                )?;
+            }
+            removed += deleted;
         }
     }
 
     debug!(
         project = %project_name,
         date = %date_str,
-        inserted = inserted_count,
-        updated = updated_count,
+        written = written,
+        removed = removed,
         total = list.items.len(),
         "save_todo_list_for_project: completed successfully"
     );
@@ -479,6 +1002,217 @@ pub fn save_todo_list_for_project(list: &TodoList, project_name: &str) -> Result
     Ok(())
 }
 
+/// A soft-deleted todo in the trash. Its `deleted_at` is always set.
+#[derive(Debug, Clone)]
+pub struct DeletedTodo {
+    pub item: TodoItem,
+    /// Day of the list the item was deleted from
+    pub date: NaiveDate,
+    /// Whether the day has since been archived
+    pub archived: bool,
+}
+
+/// Soft-deleted todos of a project, live and archived, most recently deleted
+/// first. Items deleted together keep their list order.
+pub fn load_deleted_todos_for_project(project_name: &str) -> Result<Vec<DeletedTodo>> {
+    let conn = get_connection()?;
+
+    let mut stmt = conn.prepare(
+        "SELECT id, content, state, indent_level, parent_id, due_date, description, priority, collapsed, created_at, updated_at, completed_at, deleted_at, estimate_minutes, waiting_on, pinned, link, location, date, 0, position
+         FROM todos WHERE project = ?1 AND deleted_at IS NOT NULL
+         UNION ALL
+         SELECT id, content, state, indent_level, parent_id, due_date, description, priority, collapsed, created_at, updated_at, completed_at, deleted_at, estimate_minutes, waiting_on, pinned, link, location, original_date, 1, position
+         FROM archived_todos WHERE project = ?1 AND deleted_at IS NOT NULL
+         ORDER BY 13 DESC, 19, 21",
+    )?;
+
+    let rows = stmt.query_map(params![project_name], |row| {
+        Ok((
+            TodoRowData::from_row(row)?,
+            row.get::<_, String>(18)?,
+            row.get::<_, bool>(19)?,
+        ))
+    })?;
+
+    let mut deleted = Vec::new();
+    for row in rows {
+        let (data, date, archived) = row?;
+        let Ok(date) = NaiveDate::parse_from_str(&date, "%Y-%m-%d") else {
+            continue;
+        };
+        deleted.push(DeletedTodo {
+            item: data.into_todo_item(),
+            date,
+            archived,
+        });
+    }
+    Ok(deleted)
+}
+
+/// Put soft-deleted todos back on their lists. Returns how many came back.
+pub fn restore_deleted_todos_for_project(ids: &[Uuid], project_name: &str) -> Result<usize> {
+    let conn = get_connection()?;
+    let now = chrono::Utc::now().to_rfc3339();
+
+    let mut count = 0;
+    for id in ids {
+        let id_str = id.to_string();
+        for table in ["todos", "archived_todos"] {
+            let content: Option<String> = conn
+                .query_row(
+                    &format!(
+                        "UPDATE {table} SET deleted_at = NULL, updated_at = ?1
+                         WHERE id = ?2 AND project = ?3 AND deleted_at IS NOT NULL
+                         RETURNING content"
+                    ),
+                    params![now, id_str, project_name],
+                    |row| row.get(0),
+                )
+                .optional()?;
+            if let Some(content) = content {
+                record_on(
+                    &conn,
+                    &NewEvent {
+                        todo_id: Some(&id_str),
+                        content: Some(&content),
+                        ..NewEvent::new(EventKind::Restore, project_name)
+                    },
+                )?;
+                count += 1;
+            }
+        }
+    }
+
+    debug!(project = %project_name, count, "Restored deleted todos");
+    Ok(count)
+}
+
+/// Delete live todos of `date` outright, without going through the trash,
+/// for changes being taken back. Their attachments are left for the next
+/// cleanup, as the caller may be handing them to other items.
+pub fn delete_todos_for_project(ids: &[Uuid], date: NaiveDate, project_name: &str) -> Result<usize> {
+    let mut conn = get_connection()?;
+    let tx = conn.transaction()?;
+    let date_str = date.format("%Y-%m-%d").to_string();
+
+    let mut count = 0;
+    for id in ids {
+        let id_str = id.to_string();
+        let content: Option<String> = tx
+            .query_row(
+                "DELETE FROM todos WHERE id = ?1 AND date = ?2 AND project = ?3 AND deleted_at IS NULL
+                 RETURNING content",
+                params![id_str, date_str, project_name],
+                |row| row.get(0),
+            )
+            .optional()?;
+        if let Some(content) = content {
+            record_on(
+                &tx,
+                &NewEvent {
+                    date: Some(date),
+                    todo_id: Some(&id_str),
+                    content: Some(&content),
+                    ..NewEvent::new(EventKind::Delete, project_name)
+                },
+            )?;
+            count += 1;
+        }
+    }
+
+    tx.commit()?;
+    debug!(project = %project_name, count, "Deleted todos");
+    Ok(count)
+}
+
+/// Remove soft-deleted todos for good. Live todos are never touched.
+pub fn purge_deleted_todos_for_project(ids: &[Uuid], project_name: &str) -> Result<usize> {
+    let conn = get_connection()?;
+
+    let mut count = 0;
+    for id in ids {
+        let id_str = id.to_string();
+        for table in ["todos", "archived_todos"] {
+            let content: Option<String> = conn
+                .query_row(
+                    &format!(
+                        "DELETE FROM {table} WHERE id = ?1 AND project = ?2 AND deleted_at IS NOT NULL
+                         RETURNING content"
+                    ),
+                    params![id_str, project_name],
+                    |row| row.get(0),
+                )
+                .optional()?;
+            if let Some(content) = content {
+                record_on(
+                    &conn,
+                    &NewEvent {
+                        todo_id: Some(&id_str),
+                        content: Some(&content),
+                        ..NewEvent::new(EventKind::Purge, project_name)
+                    },
+                )?;
+                count += 1;
+            }
+        }
+    }
+
+    drop(conn);
+    cleanup_orphaned_metadata()?;
+
+    debug!(project = %project_name, count, "Purged deleted todos");
+    Ok(count)
+}
+
+/// Delete for good the todos, live and archived, that went to the trash
+/// before `cutoff`. Returns how many were deleted.
+pub fn purge_deleted_todos_before(cutoff: DateTime<Utc>) -> Result<usize> {
+    let mut conn = get_connection()?;
+    let tx = conn.transaction()?;
+
+    let mut count = 0;
+    for table in ["todos", "archived_todos"] {
+        let expired: Vec<(String, String, String)> = {
+            let mut stmt = tx.prepare(&format!(
+                "SELECT id, project, content, deleted_at FROM {table} WHERE deleted_at IS NOT NULL"
+            ))?;
+            let rows = stmt.query_map([], |row| {
+                Ok((row.get(0)?, row.get(1)?, row.get(2)?, row.get::<_, String>(3)?))
+            })?;
+            let mut expired = Vec::new();
+            for row in rows {
+                let (id, project, content, deleted_at) = row?;
+                // Timestamps are compared parsed; their fractions vary in length
+                if parse_rfc3339(&deleted_at).is_some_and(|at| at < cutoff) {
+                    expired.push((id, project, content));
+                }
+            }
+            expired
+        };
+
+        for (id, project, content) in &expired {
+            tx.execute(&format!("DELETE FROM {table} WHERE id = ?1"), params![id])?;
+            record_on(
+                &tx,
+                &NewEvent {
+                    todo_id: Some(id),
+                    content: Some(content),
+                    detail: Some("trash retention".to_string()),
+                    ..NewEvent::new(EventKind::Purge, project)
+                },
+            )?;
+        }
+        count += expired.len();
+    }
+
+    tx.commit()?;
+    drop(conn);
+    cleanup_orphaned_metadata()?;
+
+    debug!(count, cutoff = %cutoff, "Purged expired trash");
+    Ok(count)
+}
+
 pub fn has_todos_for_date_and_project(date: NaiveDate, project_name: &str) -> Result<bool> {
     let conn = get_connection()?;
     let date_str = date.format("%Y-%m-%d").to_string();
@@ -492,14 +1226,129 @@ pub fn has_todos_for_date_and_project(date: NaiveDate, project_name: &str) -> Re
     Ok(count > 0)
 }
 
+/// A daily file as totui last wrote it.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct DailyFileRecord {
+    /// Modification time in milliseconds since the epoch
+    pub mtime: i64,
+    pub hash: String,
+    pub content: String,
+}
+
+pub fn load_daily_file_record(date: NaiveDate, project_name: &str) -> Result<Option<DailyFileRecord>> {
+    let conn = get_connection()?;
+    let date_str = date.format("%Y-%m-%d").to_string();
+
+    let mut stmt =
+        conn.prepare("SELECT mtime, hash, content FROM daily_files WHERE date = ?1 AND project = ?2")?;
+    let mut rows = stmt.query_map(params![&date_str, project_name], |row| {
+        Ok(DailyFileRecord {
+            mtime: row.get(0)?,
+            hash: row.get(1)?,
+            content: row.get(2)?,
+        })
+    })?;
+
+    Ok(rows.next().transpose()?)
+}
+
+pub fn save_daily_file_record(date: NaiveDate, project_name: &str, record: &DailyFileRecord) -> Result<()> {
+    let conn = get_connection()?;
+    let date_str = date.format("%Y-%m-%d").to_string();
+
+    conn.execute(
+        "INSERT OR REPLACE INTO daily_files (project, date, mtime, hash, content)
+         VALUES (?1, ?2, ?3, ?4, ?5)",
+        params![project_name, &date_str, record.mtime, &record.hash, &record.content],
+    )?;
+
+    Ok(())
+}
+
+/// Item counts for one day, live and archived together.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct DaySummary {
+    pub total: usize,
+    pub done: usize,
+}
+
+/// Per-day item counts for `from..=to`, for days that have any items.
+pub fn day_summaries_for_project(
+    from: NaiveDate,
+    to: NaiveDate,
+    project_name: &str,
+) -> Result<HashMap<NaiveDate, DaySummary>> {
+    let conn = get_connection()?;
+    let from_str = from.format("%Y-%m-%d").to_string();
+    let to_str = to.format("%Y-%m-%d").to_string();
+
+    let mut stmt = conn.prepare(
+        "SELECT day, COUNT(*), SUM(state IN ('x', '-'))
+         FROM (
+             SELECT date AS day, state FROM todos
+             WHERE project = ?1 AND deleted_at IS NULL AND date BETWEEN ?2 AND ?3
+             UNION ALL
+             SELECT original_date AS day, state FROM archived_todos
+             WHERE project = ?1 AND deleted_at IS NULL AND original_date BETWEEN ?2 AND ?3
+         )
+         GROUP BY day",
+    )?;
+    let rows = stmt.query_map(params![project_name, &from_str, &to_str], |row| {
+        Ok((
+            row.get::<_, String>(0)?,
+            row.get::<_, i64>(1)?,
+            row.get::<_, i64>(2)?,
+        ))
+    })?;
+
+    let mut summaries = HashMap::new();
+    for row in rows {
+        let (day, total, done) = row?;
+        if let Ok(date) = NaiveDate::parse_from_str(&day, "%Y-%m-%d") {
+            summaries.insert(
+                date,
+                DaySummary {
+                    total: total as usize,
+                    done: done as usize,
+                },
+            );
+        }
+    }
+    Ok(summaries)
+}
+
+/// Problems SQLite finds in the database file; empty when it's healthy.
+pub fn quick_check() -> Result<Vec<String>> {
+    let conn = get_connection()?;
+    let mut stmt = conn.prepare("PRAGMA quick_check")?;
+    let messages = stmt
+        .query_map([], |row| row.get::<_, String>(0))?
+        .collect::<rusqlite::Result<Vec<_>>>()?;
+    Ok(messages.into_iter().filter(|message| message != "ok").collect())
+}
+
+/// Whether the database holds any todos, live or archived.
+pub fn has_any_todos() -> Result<bool> {
+    let conn = get_connection()?;
+
+    let count: i64 = conn.query_row(
+        "SELECT (SELECT COUNT(*) FROM todos) + (SELECT COUNT(*) FROM archived_todos)",
+        [],
+        |row| row.get(0),
+    )?;
+
+    Ok(count > 0)
+}
+
 pub fn archive_todos_for_date_and_project(date: NaiveDate, project_name: &str) -> Result<usize> {
     let conn = get_connection()?;
     let date_str = date.format("%Y-%m-%d").to_string();
     let now = chrono::Utc::now().to_rfc3339();
 
+    // OR REPLACE: an item archived mid-day may have been restored to the list by undo
     let count = conn.execute(
-        "INSERT INTO archived_todos (id, original_date, archived_at, content, state, indent_level, parent_id, due_date, description, priority, collapsed, position, created_at, updated_at, completed_at, deleted_at, project)
-         SELECT id, date, ?1, content, state, indent_level, parent_id, due_date, description, priority, collapsed, position, created_at, updated_at, completed_at, deleted_at, project
+        "INSERT OR REPLACE INTO archived_todos (id, original_date, archived_at, content, state, indent_level, parent_id, due_date, description, priority, collapsed, position, created_at, updated_at, completed_at, deleted_at, project, estimate_minutes, waiting_on, pinned, link, location)
+         SELECT id, date, ?1, content, state, indent_level, parent_id, due_date, description, priority, collapsed, position, created_at, updated_at, completed_at, deleted_at, project, estimate_minutes, waiting_on, pinned, link, location
          FROM todos WHERE date = ?2 AND project = ?3",
         params![now, date_str, project_name],
     )?;
@@ -509,12 +1358,76 @@ pub fn archive_todos_for_date_and_project(date: NaiveDate, project_name: &str) -
         params![&date_str, project_name],
     )?;
 
+    if count > 0 {
+        record_on(
+            &conn,
+            &NewEvent {
+                date: Some(date),
+                detail: Some(format!("{count} items")),
+                ..NewEvent::new(EventKind::Archive, project_name)
+            },
+        )?;
+    }
+
     // Clean up orphaned metadata for deleted todos
     cleanup_orphaned_metadata()?;
 
     Ok(count)
 }
 
+/// Archive specific todos right away, leaving the rest of the day's list live.
+///
+/// Copies the rows into `archived_todos` under their original date and removes
+/// them from `todos`. Used for archiving finished items mid-day.
+pub fn archive_todos_by_id_for_project(
+    ids: &[Uuid],
+    date: NaiveDate,
+    project_name: &str,
+) -> Result<usize> {
+    let mut conn = get_connection()?;
+    let tx = conn.transaction()?;
+    let date_str = date.format("%Y-%m-%d").to_string();
+    let now = chrono::Utc::now().to_rfc3339();
+
+    let mut count = 0;
+    for id in ids {
+        let id_str = id.to_string();
+        let content: Option<String> = tx
+            .query_row(
+                "INSERT OR REPLACE INTO archived_todos (id, original_date, archived_at, content, state, indent_level, parent_id, due_date, description, priority, collapsed, position, created_at, updated_at, completed_at, deleted_at, project, estimate_minutes, waiting_on, pinned, link, location)
+                 SELECT id, date, ?1, content, state, indent_level, parent_id, due_date, description, priority, collapsed, position, created_at, updated_at, completed_at, deleted_at, project, estimate_minutes, waiting_on, pinned, link, location
+                 FROM todos WHERE id = ?2 AND date = ?3 AND project = ?4
+                 RETURNING content",
+                params![now, id_str, date_str, project_name],
+                |row| row.get(0),
+            )
+            .optional()?;
+        if let Some(content) = content {
+            record_on(
+                &tx,
+                &NewEvent {
+                    date: Some(date),
+                    todo_id: Some(&id_str),
+                    content: Some(&content),
+                    ..NewEvent::new(EventKind::Archive, project_name)
+                },
+            )?;
+            count += 1;
+        }
+        tx.execute(
+            "DELETE FROM todos WHERE id = ?1 AND date = ?2 AND project = ?3",
+            params![id_str, date_str, project_name],
+        )?;
+    }
+    tx.commit()?;
+    drop(conn);
+
+    cleanup_orphaned_metadata()?;
+
+    debug!(project = %project_name, date = %date_str, count, "Archived todos by id");
+    Ok(count)
+}
+
 /// Clean up orphaned metadata entries.
 ///
 /// Deletes todo_metadata rows where the referenced todo_id either:
@@ -523,6 +1436,9 @@ pub fn archive_todos_for_date_and_project(date: NaiveDate, project_name: &str) -
 ///
 /// This prevents UNIQUE constraint violations when plugins try to
 /// recreate todos with the same external_id.
+///
+/// Attachments are kept while their item is anywhere, trash and archive
+/// included; those of items gone for good are deleted with their files.
 pub fn cleanup_orphaned_metadata() -> Result<()> {
     let conn = get_connection()?;
 
@@ -530,6 +1446,7 @@ pub fn cleanup_orphaned_metadata() -> Result<()> {
         "DELETE FROM todo_metadata WHERE todo_id NOT IN (SELECT id FROM todos WHERE deleted_at IS NULL)",
         [],
     )?;
+    remove_orphaned_attachments(&conn)?;
 
     Ok(())
 }
@@ -542,7 +1459,7 @@ pub fn load_archived_todos_for_date_and_project(
     let date_str = date.format("%Y-%m-%d").to_string();
 
     let mut stmt = conn.prepare(
-        "SELECT id, content, state, indent_level, parent_id, due_date, description, priority, collapsed, created_at, updated_at, completed_at, deleted_at
+        "SELECT id, content, state, indent_level, parent_id, due_date, description, priority, collapsed, created_at, updated_at, completed_at, deleted_at, estimate_minutes, waiting_on, pinned, link, location
          FROM archived_todos
          WHERE original_date = ?1 AND project = ?2 AND deleted_at IS NULL
          ORDER BY position ASC",
@@ -558,6 +1475,104 @@ pub fn load_archived_todos_for_date_and_project(
     Ok(result)
 }
 
+/// The archived todos of one day.
+#[derive(Debug, Clone)]
+pub struct ArchivedDay {
+    pub project: String,
+    pub date: NaiveDate,
+    pub items: Vec<TodoItem>,
+}
+
+/// Every archived day, of one project or all of them, ordered by project and
+/// date.
+pub fn load_archived_days(project_name: Option<&str>) -> Result<Vec<ArchivedDay>> {
+    query_archived_days(&ArchiveFilter {
+        project: project_name.map(str::to_string),
+        ..Default::default()
+    })
+}
+
+/// Which archived items to load. Unset fields match everything.
+#[derive(Debug, Clone, Default)]
+pub struct ArchiveFilter {
+    pub project: Option<String>,
+    /// First day, inclusive
+    pub from: Option<NaiveDate>,
+    /// Last day, inclusive
+    pub to: Option<NaiveDate>,
+    pub state: Option<TodoState>,
+    /// Words in the content or description, matched as in [`search_todos`]
+    pub text: Option<String>,
+}
+
+/// Archived days with the items matching `filter`, ordered by project and
+/// date. Days with no matching items are left out.
+pub fn query_archived_days(filter: &ArchiveFilter) -> Result<Vec<ArchivedDay>> {
+    let conn = get_connection()?;
+
+    let mut conditions = vec!["deleted_at IS NULL".to_string()];
+    let mut values: Vec<String> = Vec::new();
+    if let Some(project) = &filter.project {
+        values.push(project.clone());
+        conditions.push(format!("project = ?{}", values.len()));
+    }
+    if let Some(from) = filter.from {
+        values.push(from.format("%Y-%m-%d").to_string());
+        conditions.push(format!("original_date >= ?{}", values.len()));
+    }
+    if let Some(to) = filter.to {
+        values.push(to.format("%Y-%m-%d").to_string());
+        conditions.push(format!("original_date <= ?{}", values.len()));
+    }
+    if let Some(state) = filter.state {
+        values.push(state.to_char().to_string());
+        conditions.push(format!("state = ?{}", values.len()));
+    }
+    if let Some(query) = filter.text.as_deref().and_then(fts_query) {
+        values.push(query);
+        conditions.push(format!(
+            "rowid IN (SELECT rowid FROM archived_todos_fts WHERE archived_todos_fts MATCH ?{})",
+            values.len()
+        ));
+    }
+
+    let sql = format!(
+        "SELECT id, content, state, indent_level, parent_id, due_date, description, priority, collapsed, created_at, updated_at, completed_at, deleted_at, estimate_minutes, waiting_on, pinned, link, location, project, original_date
+         FROM archived_todos
+         WHERE {}
+         ORDER BY project, original_date, position",
+        conditions.join(" AND ")
+    );
+    let mut stmt = conn.prepare(&sql)?;
+
+    let rows = stmt.query_map(rusqlite::params_from_iter(values.iter()), |row| {
+        Ok((
+            TodoRowData::from_row(row)?,
+            row.get::<_, String>(18)?,
+            row.get::<_, String>(19)?,
+        ))
+    })?;
+
+    let mut days: Vec<ArchivedDay> = Vec::new();
+    for row in rows {
+        let (data, project, date) = row?;
+        let Ok(date) = NaiveDate::parse_from_str(&date, "%Y-%m-%d") else {
+            continue;
+        };
+        match days.last_mut() {
+            Some(day) if day.project == project && day.date == date => {
+                day.items.push(data.into_todo_item());
+            }
+            _ => days.push(ArchivedDay {
+                project,
+                date,
+                items: vec![data.into_todo_item()],
+            }),
+        }
+    }
+    Ok(days)
+}
+
 // ============================================================================
 // Project database functions
 // ============================================================================
@@ -663,6 +1678,11 @@ pub fn rename_project(old_name: &str, new_name: &str) -> Result<()> {
         params![new_name, old_name],
     )?;
 
+    conn.execute(
+        "UPDATE daily_files SET project = ?1 WHERE project = ?2",
+        params![new_name, old_name],
+    )?;
+
     Ok(())
 }
 
@@ -1414,6 +2434,206 @@ mod tests {
         assert_eq!(found, None, "Orphaned metadata should be deleted");
     }
 
+    #[test]
+    #[serial]
+    fn test_day_summaries_count_live_and_archived_items() {
+        let temp_dir = TempDir::new().unwrap();
+        let to_tui_dir = temp_dir.path().join(".to-tui");
+        std::fs::create_dir_all(&to_tui_dir).unwrap();
+        unsafe {
+            std::env::set_var("HOME", temp_dir.path());
+        }
+        init_database().unwrap();
+
+        let archived_day = NaiveDate::from_ymd_opt(2025, 12, 30).unwrap();
+        let mut list = create_test_list(archived_day);
+        list.add_item("Done".to_string());
+        list.add_item("Open".to_string());
+        list.items[0].state = TodoState::Checked;
+        save_todo_list_for_project(&list, DEFAULT_PROJECT_NAME).unwrap();
+        archive_todos_for_date_and_project(archived_day, DEFAULT_PROJECT_NAME).unwrap();
+
+        let live_day = NaiveDate::from_ymd_opt(2025, 12, 31).unwrap();
+        let mut list = create_test_list(live_day);
+        list.add_item("Cancelled".to_string());
+        list.items[0].state = TodoState::Cancelled;
+        save_todo_list_for_project(&list, DEFAULT_PROJECT_NAME).unwrap();
+
+        let summaries =
+            day_summaries_for_project(archived_day, live_day, DEFAULT_PROJECT_NAME).unwrap();
+        assert_eq!(summaries[&archived_day], DaySummary { total: 2, done: 1 });
+        assert_eq!(summaries[&live_day], DaySummary { total: 1, done: 1 });
+
+        let other = day_summaries_for_project(archived_day, live_day, "other").unwrap();
+        assert!(other.is_empty());
+    }
+
+    #[test]
+    #[serial]
+    fn test_search_todos_across_dates_and_archive() {
+        let temp_dir = TempDir::new().unwrap();
+        let to_tui_dir = temp_dir.path().join(".to-tui");
+        std::fs::create_dir_all(&to_tui_dir).unwrap();
+        unsafe {
+            std::env::set_var("HOME", temp_dir.path());
+        }
+        init_database().unwrap();
+
+        let archived_day = NaiveDate::from_ymd_opt(2025, 12, 30).unwrap();
+        let mut list = create_test_list(archived_day);
+        list.add_item("Deploy login service".to_string());
+        save_todo_list_for_project(&list, DEFAULT_PROJECT_NAME).unwrap();
+        archive_todos_for_date_and_project(archived_day, DEFAULT_PROJECT_NAME).unwrap();
+
+        let live_day = NaiveDate::from_ymd_opt(2025, 12, 31).unwrap();
+        let mut list = create_test_list(live_day);
+        list.add_item("Fix the login bug".to_string());
+        list.add_item("Groceries".to_string());
+        list.items[1].description = Some("milk, eggs".to_string());
+        list.add_item("Old login idea".to_string());
+        save_todo_list_for_project(&list, DEFAULT_PROJECT_NAME).unwrap();
+        soft_delete_todos_for_project(&[list.items[2].id], live_day, DEFAULT_PROJECT_NAME).unwrap();
+
+        let hits = search_todos("logi", None, 10).unwrap();
+        let found: Vec<_> = hits.iter().map(|h| (h.content.as_str(), h.date, h.archived)).collect();
+        assert_eq!(
+            found,
+            vec![
+                ("Fix the login bug", live_day, false),
+                ("Deploy login service", archived_day, true),
+            ]
+        );
+        assert_eq!(search_todos("eggs", None, 10).unwrap()[0].content, "Groceries");
+        assert!(search_todos("login", Some("other"), 10).unwrap().is_empty());
+        assert!(search_todos("\"bug OR -", None, 10).unwrap().is_empty());
+        assert!(search_todos("  ", None, 10).unwrap().is_empty());
+
+        // Edits are picked up, and an index that fell behind is rebuilt
+        list.items.truncate(2);
+        list.items[0].content = "Fix the signup bug".to_string();
+        save_todo_list_for_project(&list, DEFAULT_PROJECT_NAME).unwrap();
+        get_connection().unwrap().execute("DELETE FROM todos_fts", []).unwrap();
+        init_database().unwrap();
+        assert_eq!(search_todos("signup", None, 10).unwrap().len(), 1);
+        assert_eq!(search_todos("login", None, 10).unwrap().len(), 1);
+    }
+
+    #[test]
+    #[serial]
+    fn test_query_archived_days() {
+        let temp_dir = TempDir::new().unwrap();
+        let to_tui_dir = temp_dir.path().join(".to-tui");
+        std::fs::create_dir_all(&to_tui_dir).unwrap();
+        unsafe {
+            std::env::set_var("HOME", temp_dir.path());
+        }
+        init_database().unwrap();
+
+        let day = |d| NaiveDate::from_ymd_opt(2025, 12, d).unwrap();
+        for (date, content, state) in [
+            (day(1), "Deploy login service", TodoState::Checked),
+            (day(2), "Login bug", TodoState::Empty),
+            (day(3), "Deploy docs", TodoState::Checked),
+        ] {
+            let mut list = create_test_list(date);
+            list.add_item(content.to_string());
+            list.items[0].state = state;
+            save_todo_list_for_project(&list, DEFAULT_PROJECT_NAME).unwrap();
+            archive_todos_for_date_and_project(date, DEFAULT_PROJECT_NAME).unwrap();
+        }
+
+        let dates = |filter: ArchiveFilter| -> Vec<NaiveDate> {
+            query_archived_days(&filter).unwrap().iter().map(|d| d.date).collect()
+        };
+        assert_eq!(dates(ArchiveFilter::default()).len(), 3);
+        assert_eq!(
+            dates(ArchiveFilter { from: Some(day(2)), to: Some(day(3)), ..Default::default() }),
+            vec![day(2), day(3)]
+        );
+        assert_eq!(
+            dates(ArchiveFilter { state: Some(TodoState::Checked), text: Some("deploy".into()), ..Default::default() }),
+            vec![day(1), day(3)]
+        );
+        assert_eq!(dates(ArchiveFilter { text: Some("logi".into()), ..Default::default() }), vec![day(1), day(2)]);
+        assert!(dates(ArchiveFilter { project: Some("other".into()), ..Default::default() }).is_empty());
+    }
+
+    #[test]
+    #[serial]
+    fn test_trash_restore_and_purge() {
+        let temp_dir = TempDir::new().unwrap();
+        let to_tui_dir = temp_dir.path().join(".to-tui");
+        std::fs::create_dir_all(&to_tui_dir).unwrap();
+        unsafe {
+            std::env::set_var("HOME", temp_dir.path());
+        }
+        init_database().unwrap();
+
+        let archived_day = NaiveDate::from_ymd_opt(2025, 12, 30).unwrap();
+        let mut list = create_test_list(archived_day);
+        list.add_item("Old".to_string());
+        save_todo_list_for_project(&list, DEFAULT_PROJECT_NAME).unwrap();
+        soft_delete_todos_for_project(&[list.items[0].id], archived_day, DEFAULT_PROJECT_NAME).unwrap();
+        archive_todos_for_date_and_project(archived_day, DEFAULT_PROJECT_NAME).unwrap();
+        let old_id = list.items[0].id;
+
+        let day = NaiveDate::from_ymd_opt(2025, 12, 31).unwrap();
+        let mut list = create_test_list(day);
+        list.add_item("Keep".to_string());
+        list.add_item("Gone".to_string());
+        save_todo_list_for_project(&list, DEFAULT_PROJECT_NAME).unwrap();
+        let gone_id = list.items[1].id;
+        soft_delete_todos_for_project(&[gone_id], day, DEFAULT_PROJECT_NAME).unwrap();
+
+        let trash = load_deleted_todos_for_project(DEFAULT_PROJECT_NAME).unwrap();
+        let found: Vec<_> = trash.iter().map(|d| (d.item.content.as_str(), d.date, d.archived)).collect();
+        assert_eq!(found, vec![("Gone", day, false), ("Old", archived_day, true)]);
+        assert!(trash.iter().all(|d| d.item.deleted_at.is_some()));
+        assert!(load_deleted_todos_for_project("other").unwrap().is_empty());
+
+        // Live items are never purged
+        assert_eq!(purge_deleted_todos_for_project(&[list.items[0].id], DEFAULT_PROJECT_NAME).unwrap(), 0);
+
+        assert_eq!(restore_deleted_todos_for_project(&[gone_id], DEFAULT_PROJECT_NAME).unwrap(), 1);
+        let items = load_todos_for_date_and_project(day, DEFAULT_PROJECT_NAME).unwrap();
+        assert_eq!(items.iter().map(|i| i.content.as_str()).collect::<Vec<_>>(), vec!["Keep", "Gone"]);
+
+        assert_eq!(purge_deleted_todos_for_project(&[old_id], DEFAULT_PROJECT_NAME).unwrap(), 1);
+        assert!(load_deleted_todos_for_project(DEFAULT_PROJECT_NAME).unwrap().is_empty());
+    }
+
+    #[test]
+    #[serial]
+    fn test_expired_trash_is_purged() {
+        let temp_dir = TempDir::new().unwrap();
+        std::fs::create_dir_all(temp_dir.path().join(".to-tui")).unwrap();
+        unsafe {
+            std::env::set_var("HOME", temp_dir.path());
+        }
+        init_database().unwrap();
+
+        let day = NaiveDate::from_ymd_opt(2025, 12, 31).unwrap();
+        let mut list = create_test_list(day);
+        list.add_item("Keep".to_string());
+        list.add_item("Old".to_string());
+        list.add_item("Recent".to_string());
+        save_todo_list_for_project(&list, DEFAULT_PROJECT_NAME).unwrap();
+        soft_delete_todos_for_project(&[list.items[1].id, list.items[2].id], day, DEFAULT_PROJECT_NAME).unwrap();
+        let conn = get_connection().unwrap();
+        conn.execute(
+            "UPDATE todos SET deleted_at = '2025-12-01T10:00:00.5+00:00' WHERE id = ?1",
+            [list.items[1].id.to_string()],
+        )
+        .unwrap();
+        drop(conn);
+
+        let cutoff = "2025-12-15T00:00:00Z".parse().unwrap();
+        assert_eq!(purge_deleted_todos_before(cutoff).unwrap(), 1);
+        let trash = load_deleted_todos_for_project(DEFAULT_PROJECT_NAME).unwrap();
+        assert_eq!(trash.iter().map(|d| d.item.content.as_str()).collect::<Vec<_>>(), vec!["Recent"]);
+        assert_eq!(load_todos_for_date_and_project(day, DEFAULT_PROJECT_NAME).unwrap().len(), 1);
+    }
+
     /// Regression test for undo crash after deleting non-last item.
     /// 
     /// Bug: Deleting an item soft-deletes it in DB. On undo, the restored
@@ -1538,4 +2758,177 @@ mod tests {
         
         assert_eq!(count, 1, "Soft-deleted item B should still be in DB for audit trail");
     }
+
+    #[test]
+    #[serial]
+    fn test_connections_use_wal_and_are_reused() {
+        let temp_dir = TempDir::new().unwrap();
+        std::fs::create_dir_all(temp_dir.path().join(".to-tui")).unwrap();
+        unsafe {
+            std::env::set_var("HOME", temp_dir.path());
+        }
+
+        let conn = get_connection().unwrap();
+        let mode: String = conn.query_row("PRAGMA journal_mode", [], |row| row.get(0)).unwrap();
+        assert_eq!(mode, "wal");
+        let timeout: i64 = conn.query_row("PRAGMA busy_timeout", [], |row| row.get(0)).unwrap();
+        assert_eq!(timeout, 5000);
+        // TEMP tables only exist on the connection that made them
+        conn.execute("CREATE TEMP TABLE marker (x INTEGER)", []).unwrap();
+        drop(conn);
+
+        let reused = get_connection().unwrap();
+        assert!(reused.prepare("SELECT x FROM marker").is_ok());
+        let second = get_connection().unwrap();
+        assert!(second.prepare("SELECT x FROM marker").is_err());
+        drop(reused);
+        drop(second);
+
+        close_connections();
+        let fresh = get_connection().unwrap();
+        assert!(fresh.prepare("SELECT x FROM marker").is_err());
+    }
+
+    #[test]
+    #[serial]
+    fn test_save_writes_only_changed_rows() {
+        let temp_dir = TempDir::new().unwrap();
+        std::fs::create_dir_all(temp_dir.path().join(".to-tui")).unwrap();
+        unsafe {
+            std::env::set_var("HOME", temp_dir.path());
+        }
+        init_database().unwrap();
+        let conn = get_connection().unwrap();
+        conn.execute_batch(
+            "CREATE TABLE writes (id TEXT);
+             CREATE TRIGGER count_inserts AFTER INSERT ON todos BEGIN INSERT INTO writes VALUES (new.id); END;
+             -- Not the sync triggers stamping the row's change_seq
+             CREATE TRIGGER count_updates AFTER UPDATE ON todos WHEN new.change_seq = old.change_seq
+                 BEGIN INSERT INTO writes VALUES (new.id); END;",
+        )
+        .unwrap();
+        let writes = |conn: &Connection| -> i64 {
+            conn.query_row("SELECT COUNT(*) FROM writes", [], |row| row.get(0)).unwrap()
+        };
+
+        let date = NaiveDate::from_ymd_opt(2026, 1, 18).unwrap();
+        let mut list = create_test_list(date);
+        list.add_item("One".to_string());
+        list.add_item("Two".to_string());
+        list.add_item("Three".to_string());
+        save_todo_list_for_project(&list, DEFAULT_PROJECT_NAME).unwrap();
+        assert_eq!(writes(&conn), 3);
+
+        // Nothing changed, nothing written
+        save_todo_list_for_project(&list, DEFAULT_PROJECT_NAME).unwrap();
+        assert_eq!(writes(&conn), 3);
+
+        list.items[1].content = "Two!".to_string();
+        list.items.remove(2);
+        save_todo_list_for_project(&list, DEFAULT_PROJECT_NAME).unwrap();
+        assert_eq!(writes(&conn), 4);
+
+        let loaded = load_todos_for_date_and_project(date, DEFAULT_PROJECT_NAME).unwrap();
+        let contents: Vec<_> = loaded.iter().map(|i| i.content.as_str()).collect();
+        assert_eq!(contents, vec!["One", "Two!"]);
+    }
+
+    #[test]
+    #[serial]
+    fn test_short_ids_are_stored_and_survive_removals() {
+        let temp_dir = TempDir::new().unwrap();
+        let to_tui_dir = temp_dir.path().join(".to-tui");
+        std::fs::create_dir_all(&to_tui_dir).unwrap();
+        unsafe {
+            std::env::set_var("HOME", temp_dir.path());
+        }
+        init_database().unwrap();
+
+        let date = NaiveDate::from_ymd_opt(2025, 12, 31).unwrap();
+        let mut list = create_test_list(date);
+        for i in 0..40 {
+            list.add_item(format!("Item {i}"));
+        }
+        save_todo_list_for_project(&list, DEFAULT_PROJECT_NAME).unwrap();
+        let mut loaded = create_test_list(date);
+        loaded.items = load_todos_for_date_and_project(date, DEFAULT_PROJECT_NAME).unwrap();
+        let before = list.short_ids();
+        assert!(loaded.items.iter().all(|item| item.short_id.as_ref() == before.get(&item.id)));
+
+        loaded.items.retain(|item| item.content.ends_with('7'));
+        save_todo_list_for_project(&loaded, DEFAULT_PROJECT_NAME).unwrap();
+        let after = load_todos_for_date_and_project(date, DEFAULT_PROJECT_NAME).unwrap();
+
+        assert_eq!(after.len(), 4);
+        for item in &after {
+            assert_eq!(item.short_id.as_ref(), before.get(&item.id));
+        }
+    }
+
+    #[test]
+    #[serial]
+    fn test_location_is_stored_and_archived() {
+        let temp_dir = TempDir::new().unwrap();
+        std::fs::create_dir_all(temp_dir.path().join(".to-tui")).unwrap();
+        unsafe {
+            std::env::set_var("HOME", temp_dir.path());
+        }
+        init_database().unwrap();
+
+        let date = NaiveDate::from_ymd_opt(2025, 12, 31).unwrap();
+        let mut list = create_test_list(date);
+        list.add_item("Fix the parser".to_string());
+        let location = Location {
+            file: "/src/lib.rs".to_string(),
+            line: 7,
+        };
+        list.items[0].location = Some(location.clone());
+        save_todo_list_for_project(&list, DEFAULT_PROJECT_NAME).unwrap();
+
+        let loaded = load_todos_for_date_and_project(date, DEFAULT_PROJECT_NAME).unwrap();
+        assert_eq!(loaded[0].location.as_ref(), Some(&location));
+        assert_eq!(loaded[0].description, None);
+
+        archive_todos_for_date_and_project(date, DEFAULT_PROJECT_NAME).unwrap();
+        let archived = load_archived_todos_for_date_and_project(date, DEFAULT_PROJECT_NAME).unwrap();
+        assert_eq!(archived[0].location, Some(location));
+    }
+
+    #[test]
+    #[serial]
+    fn test_undone_archive_leaves_each_item_once() {
+        let temp_dir = TempDir::new().unwrap();
+        let to_tui_dir = temp_dir.path().join(".to-tui");
+        std::fs::create_dir_all(&to_tui_dir).unwrap();
+        unsafe {
+            std::env::set_var("HOME", temp_dir.path());
+        }
+        init_database().unwrap();
+
+        let date = NaiveDate::from_ymd_opt(2025, 12, 31).unwrap();
+        let mut list = create_test_list(date);
+        list.add_item("Done".to_string());
+        list.add_item("Open".to_string());
+        list.items[0].state = TodoState::Checked;
+        save_todo_list_for_project(&list, DEFAULT_PROJECT_NAME).unwrap();
+
+        let ids: Vec<Uuid> = list.items.iter().map(|item| item.id).collect();
+        assert_eq!(archive_todos_by_id_for_project(&ids[..1], date, DEFAULT_PROJECT_NAME).unwrap(), 1);
+
+        // Undo puts the archived item back into the list that is saved next
+        save_todo_list_for_project(&list, DEFAULT_PROJECT_NAME).unwrap();
+
+        let conn = get_connection().unwrap();
+        for id in &ids {
+            let count: i64 = conn
+                .query_row(
+                    "SELECT (SELECT COUNT(*) FROM todos WHERE id = ?1 AND deleted_at IS NULL)
+                          + (SELECT COUNT(*) FROM archived_todos WHERE id = ?1)",
+                    params![id.to_string()],
+                    |row| row.get(0),
+                )
+                .unwrap();
+            assert_eq!(count, 1);
+        }
+    }
 }
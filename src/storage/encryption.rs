@@ -0,0 +1,495 @@
+//! Encryption at rest, for to-dos kept on shared machines.
+//!
+//! With `[encryption] enabled = true` (in builds with the `encryption`
+//! feature) the database is opened with SQLCipher under a passphrase, and
+//! daily files, the undo history and attachments are written age-encrypted.
+//! Their age key is kept in the database, so the passphrase is the only
+//! secret.
+//!
+//! The passphrase comes from TOTUI_PASSPHRASE or the system keyring, or is
+//! asked for on the terminal and then kept in the keyring. Turning
+//! encryption on encrypts the existing database, its backups, the daily
+//! files and attachments on the next start.
+
+use anyhow::{Context, Result, bail};
+use rusqlite::Connection;
+use std::fs;
+use std::io::Read;
+use std::path::Path;
+use std::sync::{Mutex, RwLock};
+
+use crate::config::EncryptionConfig;
+use crate::utils::paths::get_database_path;
+
+/// Set to the passphrase to skip the keyring and the prompt
+pub const PASSPHRASE_ENV: &str = "TOTUI_PASSPHRASE";
+
+/// How an age-encrypted file starts; anything else is read as text
+const AGE_ARMOR_BEGIN: &[u8] = b"-----BEGIN AGE ENCRYPTED FILE-----";
+/// How an unencrypted SQLite database starts
+const SQLITE_HEADER: [u8; 16] = *b"SQLite format 3\0";
+
+static CONFIG: RwLock<EncryptionConfig> = RwLock::new(EncryptionConfig {
+    enabled: false,
+    keyring: true,
+});
+
+/// The passphrase the database was opened with, once `unlock` succeeded
+static PASSPHRASE: Mutex<Option<String>> = Mutex::new(None);
+
+/// Apply the `[encryption]` settings. Called once at startup.
+pub fn configure(config: &EncryptionConfig) -> Result<()> {
+    if config.enabled && !cfg!(feature = "encryption") {
+        bail!(
+            "[encryption] is enabled in config.toml, but this totui was built without the \
+             `encryption` feature"
+        );
+    }
+    *CONFIG.write().unwrap_or_else(|e| e.into_inner()) = config.clone();
+    *PASSPHRASE.lock().unwrap_or_else(|e| e.into_inner()) = None;
+    #[cfg(feature = "encryption")]
+    {
+        *imp::IDENTITY.lock().unwrap_or_else(|e| e.into_inner()) = None;
+    }
+    Ok(())
+}
+
+pub fn is_enabled() -> bool {
+    CONFIG.read().unwrap_or_else(|e| e.into_inner()).enabled
+}
+
+/// Get the passphrase and check it opens the database, encrypting what was
+/// written before encryption was turned on. With encryption off, refuse a
+/// database that is encrypted. Call on startup, before the terminal is taken
+/// over, so a prompt can be answered.
+pub fn unlock() -> Result<()> {
+    if !is_enabled() {
+        let db_path = get_database_path()?;
+        if database_header(&db_path).is_some_and(|header| header != SQLITE_HEADER) {
+            bail!(
+                "{} is encrypted or not a database; set [encryption] enabled = true if totui encrypted it",
+                db_path.display()
+            );
+        }
+        return Ok(());
+    }
+    if PASSPHRASE.lock().unwrap_or_else(|e| e.into_inner()).is_some() {
+        return Ok(());
+    }
+    imp::unlock()
+}
+
+/// The first bytes of the database file at `path`, if it has them.
+fn database_header(path: &Path) -> Option<[u8; 16]> {
+    let mut header = [0; SQLITE_HEADER.len()];
+    fs::File::open(path).ok()?.read_exact(&mut header).ok()?;
+    Some(header)
+}
+
+/// Give a newly opened connection the database's key. Comes before
+/// anything else is done with the connection, and after `unlock`: that can
+/// replace the database file.
+pub fn key_connection(conn: &Connection) -> Result<()> {
+    if !is_enabled() {
+        return Ok(());
+    }
+    let passphrase = PASSPHRASE
+        .lock()
+        .unwrap_or_else(|e| e.into_inner())
+        .clone()
+        .context("The database is still locked")?;
+    conn.pragma_update(None, "key", passphrase)?;
+    Ok(())
+}
+
+/// The bytes to write for a file holding `content`: the content itself, or
+/// age-encrypted when encryption is on.
+pub fn seal(content: impl AsRef<[u8]>) -> Result<Vec<u8>> {
+    if !is_enabled() {
+        return Ok(content.as_ref().to_vec());
+    }
+    imp::encrypt(content.as_ref())
+}
+
+/// Read a file written with `seal`. Files that aren't encrypted are read as
+/// they are, so files from before encryption was turned on still load.
+pub fn read(path: &Path) -> Result<Vec<u8>> {
+    let bytes = fs::read(path).with_context(|| format!("Failed to read file: {}", path.display()))?;
+    if !bytes.starts_with(AGE_ARMOR_BEGIN) {
+        return Ok(bytes);
+    }
+    if !is_enabled() {
+        bail!("{} is encrypted; set [encryption] enabled = true to read it", path.display());
+    }
+    imp::decrypt(&bytes).with_context(|| format!("Failed to decrypt {}", path.display()))
+}
+
+/// `read` for a text file.
+pub fn read_to_string(path: &Path) -> Result<String> {
+    String::from_utf8(read(path)?).with_context(|| format!("{} is not UTF-8 text", path.display()))
+}
+
+#[cfg(feature = "encryption")]
+mod imp {
+    use super::*;
+    use crate::storage::attachments::stored_files;
+    use crate::storage::backup::list_backups;
+    use crate::storage::database::{self, close_connections, get_connection};
+    use crate::utils::paths::{find_daily_files, get_backups_dir, get_undo_history_path};
+    use age::secrecy::ExposeSecret;
+    use age::x25519::Identity;
+    use rusqlite::{OptionalExtension, params};
+    use std::io::Write;
+    use std::str::FromStr;
+    use tracing::{info, warn};
+
+    const KEYRING_SERVICE: &str = "to-tui";
+    const KEYRING_USER: &str = "database";
+
+    /// The `encryption_keys` table: the age identity files are encrypted to.
+    const KEYS_SCHEMA: &str = "
+        CREATE TABLE IF NOT EXISTS encryption_keys (
+            name TEXT PRIMARY KEY,
+            value TEXT NOT NULL
+        );
+    ";
+    const FILES_KEY: &str = "files";
+
+    /// The identity in the database unlocked last
+    pub static IDENTITY: Mutex<Option<Identity>> = Mutex::new(None);
+
+    /// Where the passphrase came from
+    #[derive(PartialEq, Eq)]
+    enum Source {
+        Environment,
+        Keyring,
+        Prompt,
+    }
+
+    fn keyring_enabled() -> bool {
+        CONFIG.read().unwrap_or_else(|e| e.into_inner()).keyring
+    }
+
+    pub fn unlock() -> Result<()> {
+        let db_path = get_database_path()?;
+        let plaintext = database_header(&db_path) == Some(SQLITE_HEADER);
+        let (passphrase, source) = find_passphrase(plaintext || !db_path.exists())?;
+
+        if plaintext {
+            close_connections();
+            encrypt_database(&db_path, &passphrase)?;
+            info!(path = %db_path.display(), "Encrypted the database");
+        }
+        check_passphrase(&db_path, &passphrase).map_err(|e| match source {
+            Source::Environment => e.context(format!("{PASSPHRASE_ENV} doesn't open the database")),
+            Source::Keyring => e.context("The passphrase in the system keyring doesn't open the database"),
+            Source::Prompt => e.context("Wrong passphrase"),
+        })?;
+        *PASSPHRASE.lock().unwrap_or_else(|e| e.into_inner()) = Some(passphrase.clone());
+
+        if source == Source::Prompt
+            && keyring_enabled()
+            && let Err(e) = keyring::Entry::new(KEYRING_SERVICE, KEYRING_USER)
+                .and_then(|entry| entry.set_password(&passphrase))
+        {
+            warn!("Failed to keep the passphrase in the system keyring: {e}");
+        }
+
+        if plaintext {
+            encrypt_backups(&passphrase)?;
+        }
+        database::init_database()?;
+        encrypt_files()
+    }
+
+    fn find_passphrase(new: bool) -> Result<(String, Source)> {
+        if let Ok(passphrase) = std::env::var(PASSPHRASE_ENV)
+            && !passphrase.is_empty()
+        {
+            return Ok((passphrase, Source::Environment));
+        }
+        if keyring_enabled() {
+            match keyring::Entry::new(KEYRING_SERVICE, KEYRING_USER).and_then(|entry| entry.get_password()) {
+                Ok(passphrase) => return Ok((passphrase, Source::Keyring)),
+                Err(keyring::Error::NoEntry) => {}
+                Err(e) => warn!("Failed to read the passphrase from the system keyring: {e}"),
+            }
+        }
+
+        let read_error = || format!("Failed to read the passphrase; set {PASSPHRASE_ENV} when there is no terminal");
+        let passphrase = rpassword::prompt_password("Passphrase for the totui database: ").with_context(read_error)?;
+        if passphrase.is_empty() {
+            bail!("The passphrase can't be empty");
+        }
+        if new {
+            let again = rpassword::prompt_password("Repeat the passphrase: ").with_context(read_error)?;
+            if again != passphrase {
+                bail!("The passphrases don't match");
+            }
+        }
+        Ok((passphrase, Source::Prompt))
+    }
+
+    fn check_passphrase(db_path: &Path, passphrase: &str) -> Result<()> {
+        let conn = Connection::open(db_path)
+            .with_context(|| format!("Failed to open database at {db_path:?}"))?;
+        // A wrong passphrase is reported below, not logged over the terminal
+        conn.pragma_update(None, "cipher_log_level", "NONE")?;
+        conn.pragma_update(None, "key", passphrase)?;
+        conn.query_row("SELECT count(*) FROM sqlite_master", [], |_| Ok(()))?;
+        Ok(())
+    }
+
+    /// Replace the unencrypted database at `path` with an encrypted copy.
+    fn encrypt_database(path: &Path, passphrase: &str) -> Result<()> {
+        let encrypted = path.with_extension("encrypting");
+        if encrypted.exists() {
+            fs::remove_file(&encrypted)?;
+        }
+        {
+            let conn = Connection::open(path)
+                .with_context(|| format!("Failed to open database at {path:?}"))?;
+            // Everything in the write-ahead log goes into the copy
+            conn.query_row("PRAGMA wal_checkpoint(TRUNCATE)", [], |_| Ok(()))?;
+            conn.execute(
+                "ATTACH DATABASE ?1 AS encrypted KEY ?2",
+                params![encrypted.to_string_lossy(), passphrase],
+            )?;
+            conn.query_row("SELECT sqlcipher_export('encrypted')", [], |_| Ok(()))
+                .with_context(|| format!("Failed to encrypt {}", path.display()))?;
+            conn.execute("DETACH DATABASE encrypted", [])?;
+        }
+        // What's left of the old log must not be applied to the new file
+        for suffix in ["-wal", "-shm"] {
+            let mut side_file = path.as_os_str().to_owned();
+            side_file.push(suffix);
+            let side_file = Path::new(&side_file);
+            if side_file.exists() {
+                fs::remove_file(side_file)?;
+            }
+        }
+        fs::rename(&encrypted, path)
+            .with_context(|| format!("Failed to replace {} with its encrypted copy", path.display()))
+    }
+
+    fn encrypt_backups(passphrase: &str) -> Result<()> {
+        for backup in list_backups(&get_backups_dir()?)? {
+            if database_header(&backup.path) == Some(SQLITE_HEADER) {
+                encrypt_database(&backup.path, passphrase)?;
+            }
+        }
+        Ok(())
+    }
+
+    fn is_encrypted_file(path: &Path) -> Result<bool> {
+        let bytes = fs::read(path).with_context(|| format!("Failed to read file: {}", path.display()))?;
+        Ok(bytes.starts_with(AGE_ARMOR_BEGIN))
+    }
+
+    /// Encrypt the daily files, undo history and attachments written before
+    /// encryption was turned on.
+    fn encrypt_files() -> Result<()> {
+        let mut files = find_daily_files()?;
+        files.push(get_undo_history_path()?);
+        files.extend(stored_files()?);
+        for path in files {
+            if !path.exists() || is_encrypted_file(&path)? {
+                continue;
+            }
+            let content = read(&path)?;
+            let temp_path = path.with_extension("tmp");
+            fs::write(&temp_path, encrypt(&content)?)
+                .with_context(|| format!("Failed to write to temp file: {}", temp_path.display()))?;
+            fs::rename(&temp_path, &path)
+                .with_context(|| format!("Failed to rename temp file to: {}", path.display()))?;
+        }
+        Ok(())
+    }
+
+    /// The identity files are encrypted to, made the first time it's needed.
+    fn identity() -> Result<Identity> {
+        let mut cached = IDENTITY.lock().unwrap_or_else(|e| e.into_inner());
+        if let Some(identity) = cached.as_ref() {
+            return Ok(identity.clone());
+        }
+
+        let conn = get_connection()?;
+        conn.execute_batch(KEYS_SCHEMA)?;
+        conn.execute(
+            "INSERT OR IGNORE INTO encryption_keys (name, value) VALUES (?1, ?2)",
+            params![FILES_KEY, Identity::generate().to_string().expose_secret()],
+        )?;
+        let stored: Option<String> = conn
+            .query_row("SELECT value FROM encryption_keys WHERE name = ?1", [FILES_KEY], |row| row.get(0))
+            .optional()?;
+        let identity = Identity::from_str(&stored.context("The file encryption key is missing")?)
+            .map_err(|e| anyhow::anyhow!("The file encryption key is invalid: {e}"))?;
+        *cached = Some(identity.clone());
+        Ok(identity)
+    }
+
+    pub fn encrypt(content: &[u8]) -> Result<Vec<u8>> {
+        let recipient = identity()?.to_public();
+        let encryptor = age::Encryptor::with_recipients(std::iter::once(&recipient as &dyn age::Recipient))?;
+        let mut output = Vec::new();
+        let armor = age::armor::ArmoredWriter::wrap_output(&mut output, age::armor::Format::AsciiArmor)?;
+        let mut writer = encryptor.wrap_output(armor)?;
+        writer.write_all(content)?;
+        writer.finish()?.finish()?;
+        Ok(output)
+    }
+
+    pub fn decrypt(bytes: &[u8]) -> Result<Vec<u8>> {
+        let identity = identity()?;
+        let decryptor = age::Decryptor::new(age::armor::ArmoredReader::new(bytes))?;
+        let mut reader = decryptor.decrypt(std::iter::once(&identity as &dyn age::Identity))?;
+        let mut content = Vec::new();
+        reader.read_to_end(&mut content)?;
+        Ok(content)
+    }
+}
+
+/// `configure` refuses to turn encryption on in builds without it
+#[cfg(not(feature = "encryption"))]
+mod imp {
+    use super::*;
+
+    const NOT_BUILT: &str = "This totui was built without the `encryption` feature";
+
+    pub fn unlock() -> Result<()> {
+        bail!(NOT_BUILT)
+    }
+
+    pub fn encrypt(_content: &[u8]) -> Result<Vec<u8>> {
+        bail!(NOT_BUILT)
+    }
+
+    pub fn decrypt(_bytes: &[u8]) -> Result<Vec<u8>> {
+        bail!(NOT_BUILT)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serial_test::serial;
+    use tempfile::TempDir;
+
+    #[test]
+    #[serial]
+    fn test_files_are_read_as_text_unless_encrypted() {
+        configure(&EncryptionConfig::default()).unwrap();
+        let temp_dir = TempDir::new().unwrap();
+        let plain = temp_dir.path().join("2026-01-18.md");
+        fs::write(&plain, seal("- [ ] Milk\n").unwrap()).unwrap();
+        assert_eq!(read_to_string(&plain).unwrap(), "- [ ] Milk\n");
+
+        let encrypted = temp_dir.path().join("2026-01-19.md");
+        fs::write(&encrypted, "-----BEGIN AGE ENCRYPTED FILE-----\nYWdl\n-----END AGE ENCRYPTED FILE-----\n").unwrap();
+        let error = read_to_string(&encrypted).unwrap_err();
+        assert!(error.to_string().contains("is encrypted"), "{error}");
+    }
+
+    #[cfg(not(feature = "encryption"))]
+    #[test]
+    #[serial]
+    fn test_encryption_needs_the_feature() {
+        let config = EncryptionConfig {
+            enabled: true,
+            ..EncryptionConfig::default()
+        };
+        assert!(configure(&config).is_err());
+        assert!(!is_enabled());
+    }
+
+    #[cfg(feature = "encryption")]
+    #[test]
+    #[serial]
+    fn test_turning_encryption_on_encrypts_what_is_there() {
+        use crate::storage::database::close_connections;
+        use crate::storage::file::{load_todo_list_for_project, save_todo_list_for_project};
+        use crate::todo::TodoList;
+        use crate::utils::paths::{get_daily_file_path_for_project, get_database_path};
+        use chrono::NaiveDate;
+
+        let temp_dir = TempDir::new().unwrap();
+        fs::create_dir_all(temp_dir.path().join(".to-tui")).unwrap();
+        unsafe {
+            std::env::set_var("HOME", temp_dir.path());
+            std::env::set_var(PASSPHRASE_ENV, "correct horse");
+        }
+        configure(&EncryptionConfig::default()).unwrap();
+        let date = NaiveDate::from_ymd_opt(2026, 1, 18).unwrap();
+        let mut list = TodoList::new(date, get_daily_file_path_for_project("default", date).unwrap());
+        list.add_item("Milk".to_string());
+        save_todo_list_for_project(&list, "default").unwrap();
+        let notes = temp_dir.path().join("notes.txt");
+        fs::write(&notes, "Whole milk").unwrap();
+        let attached = crate::storage::attachments::attach(&list.items[0].id, &notes).unwrap();
+        close_connections();
+
+        let enabled = EncryptionConfig {
+            enabled: true,
+            keyring: false,
+        };
+        configure(&enabled).unwrap();
+        unlock().unwrap();
+
+        let db_path = get_database_path().unwrap();
+        assert!(!fs::read(&db_path).unwrap().starts_with(b"SQLite format 3"));
+        let unkeyed = Connection::open(&db_path).unwrap();
+        assert!(unkeyed.query_row("SELECT count(*) FROM todos", [], |_| Ok(())).is_err());
+
+        let file = fs::read(&list.file_path).unwrap();
+        assert!(file.starts_with(AGE_ARMOR_BEGIN));
+        assert!(read_to_string(&list.file_path).unwrap().contains("Milk"));
+
+        // Attachments are encrypted in the store and opened from a decrypted
+        // copy, which goes at exit
+        assert!(fs::read(attached.path().unwrap()).unwrap().starts_with(AGE_ARMOR_BEGIN));
+        let opened = attached.openable_path().unwrap();
+        assert!(opened.ends_with("notes.txt"));
+        assert_eq!(fs::read(&opened).unwrap(), b"Whole milk");
+        crate::storage::attachments::remove_opened_copies();
+        assert!(!opened.exists());
+        let photo = temp_dir.path().join("photo.jpg");
+        fs::write(&photo, [0xff, 0xd8, 0xff, 0x00]).unwrap();
+        let photo = crate::storage::attachments::attach(&list.items[0].id, &photo).unwrap();
+        assert!(fs::read(photo.path().unwrap()).unwrap().starts_with(AGE_ARMOR_BEGIN));
+        assert_eq!(fs::read(photo.openable_path().unwrap()).unwrap(), [0xff, 0xd8, 0xff, 0x00]);
+        crate::storage::attachments::remove_opened_copies();
+
+        // Saves keep the file encrypted, and what was there still loads
+        let mut loaded = load_todo_list_for_project("default", date).unwrap();
+        assert_eq!(loaded.items[0].content, "Milk");
+        loaded.add_item("Eggs".to_string());
+        save_todo_list_for_project(&loaded, "default").unwrap();
+        assert!(fs::read(&list.file_path).unwrap().starts_with(AGE_ARMOR_BEGIN));
+        assert!(read_to_string(&list.file_path).unwrap().contains("Eggs"));
+
+        // Backups are copies under the same key
+        let backup = crate::storage::backup::create_backup(
+            &db_path,
+            &temp_dir.path().join("backups"),
+            chrono::Local::now().naive_local(),
+        )
+        .unwrap();
+        let copy = Connection::open(&backup).unwrap();
+        key_connection(&copy).unwrap();
+        let count: i64 = copy.query_row("SELECT count(*) FROM todos", [], |row| row.get(0)).unwrap();
+        assert_eq!(count, 2);
+
+        // Another passphrase doesn't open it
+        close_connections();
+        configure(&enabled).unwrap();
+        unsafe {
+            std::env::set_var(PASSPHRASE_ENV, "wrong");
+        }
+        assert!(unlock().is_err());
+
+        configure(&EncryptionConfig::default()).unwrap();
+        unsafe {
+            std::env::remove_var(PASSPHRASE_ENV);
+        }
+    }
+}
@@ -1,10 +1,22 @@
-use super::database;
-use super::markdown::{parse_todo_list, serialize_todo_list_clean};
-use crate::todo::TodoList;
-use crate::utils::paths::{ensure_project_directories_exist, get_daily_file_path_for_project};
+use super::conflict::{is_edited_externally, record_daily_file, write_daily_file};
+use super::database::{self, DeletedTodo};
+use super::encryption;
+use super::markdown::{daily_file_format, dialect_for_path};
+use super::revision::{day_revision, stale_day};
+use super::shared;
+use crate::todo::{TodoItem, TodoList};
+use crate::utils::paths::{
+    ensure_project_directories_exist, find_daily_file_for_project, get_daily_file_path_for_project,
+};
 use anyhow::{Context, Result};
-use chrono::{Local, NaiveDate};
-use std::fs;
+use chrono::{DateTime, Local, NaiveDate, Utc};
+use std::collections::HashSet;
+use tracing::warn;
+use uuid::Uuid;
+
+/// How many times `update_todo_list_for_project` redoes an edit the day
+/// changed under before giving up.
+const UPDATE_ATTEMPTS: usize = 3;
 
 pub fn load_todo_list_for_project(project_name: &str, date: NaiveDate) -> Result<TodoList> {
     ensure_project_directories_exist(project_name)?;
@@ -12,21 +24,29 @@ pub fn load_todo_list_for_project(project_name: &str, date: NaiveDate) -> Result
 
     let file_path = get_daily_file_path_for_project(project_name, date)?;
 
+    if let Some(items) = pull_day(project_name, date)? {
+        return Ok(TodoList::with_items(date, file_path, items));
+    }
     if database::has_todos_for_date_and_project(date, project_name)? {
         let items = database::load_todos_for_date_and_project(date, project_name)?;
         return Ok(TodoList::with_items(date, file_path, items));
     }
 
-    if file_path.exists() {
-        let content = fs::read_to_string(&file_path)
-            .with_context(|| format!("Failed to read file: {}", file_path.display()))?;
+    // A file in the old layout is read, and the next save writes the new one
+    if let Some(found_path) = find_daily_file_for_project(project_name, date)? {
+        let content = encryption::read_to_string(&found_path)?;
 
-        let list = parse_todo_list(&content, date, file_path.clone())
+        let dialect = dialect_for_path(&found_path).unwrap_or(daily_file_format().dialect());
+        let list = dialect
+            .parse(&content, date, file_path.clone())
             .with_context(|| "Failed to parse todo list")?;
 
         if !list.items.is_empty() {
             database::save_todo_list_for_project(&list, project_name)?;
         }
+        if found_path == file_path {
+            record_daily_file(project_name, date, &file_path, content)?;
+        }
 
         return Ok(list);
     }
@@ -38,34 +58,107 @@ pub fn save_todo_list_for_project(list: &TodoList, project_name: &str) -> Result
     ensure_project_directories_exist(project_name)?;
     database::init_database()?;
 
+    if let Some(store) = shared::store() {
+        store.save_day(list, &[], project_name, None)?;
+    }
     database::save_todo_list_for_project(list, project_name)?;
+    write_unless_edited(list, project_name)
+}
 
-    let content = serialize_todo_list_clean(list);
+/// The day's list and the revision it was loaded at, to save it back with
+/// `save_todo_list_if_current`.
+pub fn load_todo_list_with_revision(project_name: &str, date: NaiveDate) -> Result<(TodoList, u64)> {
+    ensure_project_directories_exist(project_name)?;
+    database::init_database()?;
 
-    let temp_path = list.file_path.with_extension("tmp");
+    // Read first, so a save landing in between makes the revision look old
+    // rather than new
+    let revision = day_revision(date, project_name)?;
+    let list = load_todo_list_for_project(project_name, date)?;
+    Ok((list, revision))
+}
 
-    fs::write(&temp_path, content)
-        .with_context(|| format!("Failed to write to temp file: {}", temp_path.display()))?;
+/// Save the list unless the day changed since it was loaded at `revision`,
+/// and return its new revision. See `database::save_todo_list_if_current`.
+pub fn save_todo_list_if_current(list: &TodoList, project_name: &str, revision: u64) -> Result<u64> {
+    ensure_project_directories_exist(project_name)?;
+    database::init_database()?;
 
-    fs::rename(&temp_path, &list.file_path).with_context(|| {
-        format!(
-            "Failed to rename temp file to: {}",
-            list.file_path.display()
-        )
-    })?;
+    let saved = match shared::store() {
+        Some(store) => {
+            let saved = store.save_day(list, &[], project_name, Some(revision))?;
+            database::save_todo_list_for_project(list, project_name)?;
+            saved
+        }
+        None => database::save_todo_list_if_current(list, project_name, revision)?,
+    };
+    write_unless_edited(list, project_name)?;
+    Ok(saved)
+}
+
+/// Move `trashed` to the trash and save the rest of the list, unless the
+/// day changed since it was loaded at `revision`. See
+/// `database::trash_todos_if_current`.
+pub fn trash_todos_if_current(list: &TodoList, trashed: &[Uuid], project_name: &str, revision: u64) -> Result<u64> {
+    ensure_project_directories_exist(project_name)?;
+    database::init_database()?;
+
+    let saved = match shared::store() {
+        Some(store) => {
+            let saved = store.save_day(list, trashed, project_name, Some(revision))?;
+            database::soft_delete_todos_for_project(trashed, list.date, project_name)?;
+            database::save_todo_list_for_project(list, project_name)?;
+            saved
+        }
+        None => database::trash_todos_if_current(list, trashed, project_name, revision)?,
+    };
+    write_unless_edited(list, project_name)?;
+    Ok(saved)
+}
+
+/// Load the day's list, apply `edit` and save it, for writers that don't
+/// hold the list between commands (the CLI, editor integrations). When the
+/// day is saved elsewhere in between, the edit is made again on the new
+/// version rather than overwriting it.
+pub fn update_todo_list_for_project<T>(
+    project_name: &str,
+    date: NaiveDate,
+    mut edit: impl FnMut(&mut TodoList) -> Result<T>,
+) -> Result<T> {
+    let mut attempts = 0;
+    loop {
+        let (mut list, revision) = load_todo_list_with_revision(project_name, date)?;
+        let value = edit(&mut list)?;
+        match save_todo_list_if_current(&list, project_name, revision) {
+            Ok(_) => return Ok(value),
+            Err(e) if stale_day(&e).is_some() && attempts < UPDATE_ATTEMPTS => attempts += 1,
+            Err(e) => return Err(e),
+        }
+    }
+}
+
+fn write_unless_edited(list: &TodoList, project_name: &str) -> Result<()> {
+    // Edits made to the file outside totui are left for the TUI to settle
+    if is_edited_externally(project_name, list.date, &list.file_path)? {
+        warn!(
+            path = %list.file_path.display(),
+            "Daily file changed outside totui, saved to the database only"
+        );
+        return Ok(());
+    }
 
-    Ok(())
+    write_daily_file(list, project_name)
 }
 
 pub fn file_exists_for_project(project_name: &str, date: NaiveDate) -> Result<bool> {
     database::init_database()?;
 
+    pull_day(project_name, date)?;
     if database::has_todos_for_date_and_project(date, project_name)? {
         return Ok(true);
     }
 
-    let file_path = get_daily_file_path_for_project(project_name, date)?;
-    Ok(file_path.exists())
+    Ok(find_daily_file_for_project(project_name, date)?.is_some())
 }
 
 pub fn load_todos_for_viewing_in_project(project_name: &str, date: NaiveDate) -> Result<TodoList> {
@@ -78,25 +171,128 @@ pub fn load_todos_for_viewing_in_project(project_name: &str, date: NaiveDate) ->
     if date == today {
         return load_todo_list_for_project(project_name, date);
     }
+    pull_day(project_name, date)?;
 
-    let items = database::load_archived_todos_for_date_and_project(date, project_name)?;
-    if !items.is_empty() {
-        return Ok(TodoList::with_items(date, file_path, items));
-    }
+    let mut items = database::load_archived_todos_for_date_and_project(date, project_name)?;
 
+    // Items archived mid-day sit next to live rows until rollover archives the rest
     if database::has_todos_for_date_and_project(date, project_name)? {
-        let items = database::load_todos_for_date_and_project(date, project_name)?;
-        return Ok(TodoList::with_items(date, file_path, items));
+        items.extend(database::load_todos_for_date_and_project(date, project_name)?);
     }
 
-    Ok(TodoList::new(date, file_path))
+    Ok(TodoList::with_items(date, file_path, items))
+}
+
+/// With a shared database, copy the day from it into the local one and
+/// return its items. `None` without one, or when the day was never saved
+/// there: the local day goes up with its next save.
+fn pull_day(project_name: &str, date: NaiveDate) -> Result<Option<Vec<TodoItem>>> {
+    let Some(store) = shared::store() else {
+        return Ok(None);
+    };
+    let day = store.load_day(project_name, date)?;
+    if day.revision == 0 {
+        return Ok(None);
+    }
+
+    // Local items the shared day no longer has went the way they went there
+    let live: HashSet<Uuid> = day.items.iter().map(|item| item.id).collect();
+    let (mut archived, mut trashed, mut deleted) = (Vec::new(), Vec::new(), Vec::new());
+    for item in database::load_todos_for_date_and_project(date, project_name)? {
+        if live.contains(&item.id) {
+            continue;
+        }
+        if day.archived.contains(&item.id) {
+            archived.push(item.id);
+        } else if day.trashed.contains(&item.id) {
+            trashed.push(item.id);
+        } else {
+            deleted.push(item.id);
+        }
+    }
+    if !archived.is_empty() {
+        database::archive_todos_by_id_for_project(&archived, date, project_name)?;
+    }
+    database::soft_delete_todos_for_project(&trashed, date, project_name)?;
+    database::delete_todos_for_project(&deleted, date, project_name)?;
+
+    let list = TodoList::with_items(date, get_daily_file_path_for_project(project_name, date)?, day.items);
+    database::save_todo_list_for_project(&list, project_name)?;
+    Ok(Some(list.items))
+}
+
+/// Move items to the trash. See `database::soft_delete_todos_for_project`.
+pub fn soft_delete_todos_for_project(ids: &[Uuid], date: NaiveDate, project_name: &str) -> Result<()> {
+    if let Some(store) = shared::store() {
+        store.soft_delete(ids, date, project_name)?;
+    }
+    database::soft_delete_todos_for_project(ids, date, project_name)
+}
+
+/// Delete live items outright. See `database::delete_todos_for_project`.
+pub fn delete_todos_for_project(ids: &[Uuid], date: NaiveDate, project_name: &str) -> Result<usize> {
+    let shared = shared_count(|store| store.delete(ids, date, project_name))?;
+    let local = database::delete_todos_for_project(ids, date, project_name)?;
+    Ok(shared.unwrap_or(local))
+}
+
+/// Archive the day's items, at rollover.
+pub fn archive_todos_for_date_and_project(date: NaiveDate, project_name: &str) -> Result<usize> {
+    let shared = shared_count(|store| store.archive(None, date, project_name))?;
+    let local = database::archive_todos_for_date_and_project(date, project_name)?;
+    Ok(shared.unwrap_or(local))
+}
+
+/// Archive some of the day's items. See
+/// `database::archive_todos_by_id_for_project`.
+pub fn archive_todos_by_id_for_project(ids: &[Uuid], date: NaiveDate, project_name: &str) -> Result<usize> {
+    let shared = shared_count(|store| store.archive(Some(ids), date, project_name))?;
+    let local = database::archive_todos_by_id_for_project(ids, date, project_name)?;
+    Ok(shared.unwrap_or(local))
+}
+
+/// The project's trash, the shared one when there is a shared database.
+pub fn load_deleted_todos_for_project(project_name: &str) -> Result<Vec<DeletedTodo>> {
+    match shared::store() {
+        Some(store) => store.load_deleted(project_name),
+        None => database::load_deleted_todos_for_project(project_name),
+    }
+}
+
+/// Put items in the trash back. Returns how many came back.
+pub fn restore_deleted_todos_for_project(ids: &[Uuid], project_name: &str) -> Result<usize> {
+    let shared = shared_count(|store| store.restore_deleted(ids, project_name))?;
+    let local = database::restore_deleted_todos_for_project(ids, project_name)?;
+    Ok(shared.unwrap_or(local))
+}
+
+/// Empty items from the trash for good. Returns how many went.
+pub fn purge_deleted_todos_for_project(ids: &[Uuid], project_name: &str) -> Result<usize> {
+    let shared = shared_count(|store| store.purge_deleted(ids, project_name))?;
+    let local = database::purge_deleted_todos_for_project(ids, project_name)?;
+    Ok(shared.unwrap_or(local))
+}
+
+/// Empty what went to the trash before `cutoff`, locally and in the shared
+/// projects this user owns.
+pub fn purge_deleted_todos_before(cutoff: DateTime<Utc>) -> Result<usize> {
+    let shared = shared_count(|store| store.purge_deleted_before(cutoff))?;
+    let local = database::purge_deleted_todos_before(cutoff)?;
+    Ok(shared.unwrap_or(local))
+}
+
+/// How many items `op` changed in the shared database, when there is one.
+/// Changes go there first, and its count is the one reported.
+fn shared_count(op: impl FnOnce(&dyn shared::SharedStore) -> Result<usize>) -> Result<Option<usize>> {
+    shared::store().map(|store| op(store.as_ref())).transpose()
 }
 
 #[cfg(test)]
 mod tests {
-    use super::super::markdown::serialize_todo_list_clean;
+    use super::super::markdown::{parse_todo_list, serialize_todo_list_clean};
     use super::*;
     use chrono::NaiveDate;
+    use std::fs;
     use tempfile::TempDir;
 
     fn setup_test_dir() -> TempDir {
@@ -143,4 +339,146 @@ mod tests {
         assert_eq!(parsed.items[1].content, "Child");
         assert_eq!(parsed.items[1].state, crate::todo::TodoState::Checked);
     }
+
+    #[test]
+    #[serial_test::serial]
+    fn test_update_redoes_the_edit_when_the_day_changed_meanwhile() {
+        let temp_dir = setup_test_dir();
+        std::fs::create_dir_all(temp_dir.path().join(".to-tui")).unwrap();
+        unsafe {
+            std::env::set_var("HOME", temp_dir.path());
+        }
+        let date = NaiveDate::from_ymd_opt(2026, 1, 18).unwrap();
+
+        let mut attempts = 0;
+        update_todo_list_for_project("default", date, |list| {
+            attempts += 1;
+            if attempts == 1 {
+                let mut elsewhere = load_todo_list_for_project("default", date)?;
+                elsewhere.add_item("From the TUI".to_string());
+                save_todo_list_for_project(&elsewhere, "default")?;
+            }
+            list.add_item("From the CLI".to_string());
+            Ok(())
+        })
+        .unwrap();
+
+        let list = load_todo_list_for_project("default", date).unwrap();
+        let contents: Vec<_> = list.items.iter().map(|item| item.content.as_str()).collect();
+        assert_eq!(attempts, 2);
+        assert_eq!(contents, vec!["From the TUI", "From the CLI"]);
+    }
+
+    /// A shared database in memory, for one project.
+    #[derive(Default)]
+    struct MemoryStore {
+        days: std::sync::Mutex<std::collections::HashMap<NaiveDate, shared::SharedDay>>,
+    }
+
+    impl shared::SharedStore for MemoryStore {
+        fn load_day(&self, _project_name: &str, date: NaiveDate) -> Result<shared::SharedDay> {
+            Ok(self.days.lock().unwrap().get(&date).cloned().unwrap_or_default())
+        }
+
+        fn day_revision(&self, project_name: &str, date: NaiveDate) -> Result<u64> {
+            Ok(self.load_day(project_name, date)?.revision)
+        }
+
+        fn save_day(&self, list: &TodoList, trashed: &[Uuid], _project_name: &str, revision: Option<u64>) -> Result<u64> {
+            let mut days = self.days.lock().unwrap();
+            let day = days.entry(list.date).or_default();
+            if revision.is_some_and(|revision| revision != day.revision) {
+                return Err(super::super::revision::StaleDay { revision: day.revision }.into());
+            }
+            day.items = list.items.clone();
+            day.trashed.extend(trashed);
+            day.revision += 1;
+            Ok(day.revision)
+        }
+
+        fn soft_delete(&self, ids: &[Uuid], date: NaiveDate, _project_name: &str) -> Result<usize> {
+            let mut days = self.days.lock().unwrap();
+            let day = days.entry(date).or_default();
+            day.items.retain(|item| !ids.contains(&item.id));
+            day.trashed.extend(ids);
+            day.revision += 1;
+            Ok(ids.len())
+        }
+
+        fn delete(&self, _ids: &[Uuid], _date: NaiveDate, _project_name: &str) -> Result<usize> {
+            Ok(0)
+        }
+
+        fn archive(&self, _ids: Option<&[Uuid]>, _date: NaiveDate, _project_name: &str) -> Result<usize> {
+            Ok(0)
+        }
+
+        fn load_deleted(&self, _project_name: &str) -> Result<Vec<DeletedTodo>> {
+            Ok(Vec::new())
+        }
+
+        fn restore_deleted(&self, _ids: &[Uuid], _project_name: &str) -> Result<usize> {
+            Ok(0)
+        }
+
+        fn purge_deleted(&self, _ids: &[Uuid], _project_name: &str) -> Result<usize> {
+            Ok(0)
+        }
+
+        fn purge_deleted_before(&self, _cutoff: DateTime<Utc>) -> Result<usize> {
+            Ok(0)
+        }
+
+        fn project_access(&self, _project_name: &str) -> Result<Option<shared::ProjectAccess>> {
+            Ok(None)
+        }
+
+        fn share_project(&self, _project_name: &str, _member: &str, _shared: bool) -> Result<()> {
+            Ok(())
+        }
+    }
+
+    #[test]
+    #[serial_test::serial]
+    fn test_days_are_loaded_from_the_shared_database() {
+        let temp_dir = setup_test_dir();
+        std::fs::create_dir_all(temp_dir.path().join(".to-tui")).unwrap();
+        unsafe {
+            std::env::set_var("HOME", temp_dir.path());
+        }
+        let store = std::sync::Arc::new(MemoryStore::default());
+        shared::set_store(Some(store.clone()));
+        let date = NaiveDate::from_ymd_opt(2026, 3, 2).unwrap();
+
+        let mut mine = load_todo_list_for_project("default", date).unwrap();
+        mine.add_item("Milk".to_string());
+        save_todo_list_for_project(&mine, "default").unwrap();
+        let (_, revision) = load_todo_list_with_revision("default", date).unwrap();
+        assert_eq!(revision, 1);
+
+        // A teammate adds an item
+        let mut theirs = mine.clone();
+        theirs.add_item("Eggs".to_string());
+        shared::SharedStore::save_day(store.as_ref(), &theirs, &[], "default", Some(1)).unwrap();
+        mine.add_item("Bread".to_string());
+        let error = save_todo_list_if_current(&mine, "default", revision).unwrap_err();
+        assert_eq!(stale_day(&error).map(|stale| stale.revision), Some(2));
+
+        let (list, revision) = load_todo_list_with_revision("default", date).unwrap();
+        assert_eq!(revision, 2);
+        let contents: Vec<_> = list.items.iter().map(|item| item.content.as_str()).collect();
+        assert_eq!(contents, vec!["Milk", "Eggs"]);
+        // The local copy has it too, for search and history
+        assert_eq!(database::load_todos_for_date_and_project(date, "default").unwrap().len(), 2);
+
+        // And deletes it; the local copy keeps it in the trash
+        shared::SharedStore::soft_delete(store.as_ref(), &[theirs.items[1].id], date, "default").unwrap();
+        let list = load_todo_list_for_project("default", date).unwrap();
+        assert_eq!(list.items.len(), 1);
+        let trash = database::load_deleted_todos_for_project("default").unwrap();
+        assert_eq!(trash.len(), 1);
+        assert_eq!(trash[0].item.content, "Eggs");
+
+        shared::set_store(None);
+    }
 }
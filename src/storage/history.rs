@@ -0,0 +1,377 @@
+//! Append-only log of changes to todos.
+//!
+//! Every write to a project's todos records what changed, when, and where
+//! the change came from: the TUI, the command line, the API server, the MCP
+//! server, an editor integration or a plugin. Rows are never updated or
+//! deleted (triggers refuse to), so the log shows how a list got to where it
+//! is, which is what a data-loss report needs. `totui history` prints it and
+//! plugins can query it through the host API.
+
+use super::database::get_connection;
+use anyhow::{anyhow, Result};
+use chrono::{DateTime, NaiveDate, Utc};
+use rusqlite::{params_from_iter, Connection};
+use serde::Serialize;
+use std::cell::RefCell;
+use std::fmt;
+use std::str::FromStr;
+use std::sync::RwLock;
+
+/// Where a change came from.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Origin {
+    Tui,
+    Cli,
+    Api,
+    Mcp,
+    /// `totui rpc` and the commands editor integrations call
+    Editor,
+    Plugin(String),
+}
+
+impl fmt::Display for Origin {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Origin::Tui => write!(f, "tui"),
+            Origin::Cli => write!(f, "cli"),
+            Origin::Api => write!(f, "api"),
+            Origin::Mcp => write!(f, "mcp"),
+            Origin::Editor => write!(f, "editor"),
+            Origin::Plugin(name) => write!(f, "plugin:{name}"),
+        }
+    }
+}
+
+static ORIGIN: RwLock<Origin> = RwLock::new(Origin::Cli);
+
+thread_local! {
+    /// Plugin whose changes this thread is writing, see `with_plugin_origin`
+    static PLUGIN: RefCell<Option<String>> = const { RefCell::new(None) };
+}
+
+/// Set where this process's changes come from. Called once on startup.
+pub fn set_origin(origin: Origin) {
+    *ORIGIN.write().unwrap_or_else(|e| e.into_inner()) = origin;
+}
+
+pub fn origin() -> Origin {
+    if let Some(name) = PLUGIN.with_borrow(|plugin| plugin.clone()) {
+        return Origin::Plugin(name);
+    }
+    ORIGIN.read().unwrap_or_else(|e| e.into_inner()).clone()
+}
+
+/// Run `f` with the changes it writes credited to the plugin `plugin_name`.
+pub fn with_plugin_origin<T>(plugin_name: &str, f: impl FnOnce() -> T) -> T {
+    let previous = PLUGIN.with_borrow_mut(|plugin| plugin.replace(plugin_name.to_string()));
+    let result = f();
+    PLUGIN.with_borrow_mut(|plugin| *plugin = previous);
+    result
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "lowercase")]
+pub enum EventKind {
+    Add,
+    Edit,
+    /// The item's state changed, e.g. it was checked off
+    State,
+    Delete,
+    /// Brought back from the trash, or by undoing a delete
+    Restore,
+    /// Deleted from the trash for good
+    Purge,
+    Archive,
+    Rollover,
+}
+
+impl EventKind {
+    pub const ALL: [EventKind; 8] = [
+        EventKind::Add,
+        EventKind::Edit,
+        EventKind::State,
+        EventKind::Delete,
+        EventKind::Restore,
+        EventKind::Purge,
+        EventKind::Archive,
+        EventKind::Rollover,
+    ];
+
+    pub fn as_str(self) -> &'static str {
+        match self {
+            EventKind::Add => "add",
+            EventKind::Edit => "edit",
+            EventKind::State => "state",
+            EventKind::Delete => "delete",
+            EventKind::Restore => "restore",
+            EventKind::Purge => "purge",
+            EventKind::Archive => "archive",
+            EventKind::Rollover => "rollover",
+        }
+    }
+}
+
+impl fmt::Display for EventKind {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(self.as_str())
+    }
+}
+
+impl FromStr for EventKind {
+    type Err = anyhow::Error;
+
+    fn from_str(s: &str) -> Result<Self> {
+        EventKind::ALL
+            .into_iter()
+            .find(|kind| kind.as_str() == s)
+            .ok_or_else(|| anyhow!("Unknown event kind '{s}'"))
+    }
+}
+
+/// The `events` table. Triggers make it append-only.
+pub(crate) const HISTORY_SCHEMA: &str = "
+    CREATE TABLE IF NOT EXISTS events (
+        id INTEGER PRIMARY KEY AUTOINCREMENT,
+        at TEXT NOT NULL,
+        origin TEXT NOT NULL,
+        kind TEXT NOT NULL,
+        project TEXT NOT NULL,
+        date TEXT,
+        todo_id TEXT,
+        content TEXT,
+        detail TEXT
+    );
+    CREATE INDEX IF NOT EXISTS idx_events_todo ON events(todo_id);
+    CREATE INDEX IF NOT EXISTS idx_events_project ON events(project, id);
+
+    CREATE TRIGGER IF NOT EXISTS events_no_update BEFORE UPDATE ON events BEGIN
+        SELECT RAISE(ABORT, 'the event log is append-only');
+    END;
+    CREATE TRIGGER IF NOT EXISTS events_no_delete BEFORE DELETE ON events BEGIN
+        SELECT RAISE(ABORT, 'the event log is append-only');
+    END;
+";
+
+/// A change to record. The time and origin are filled in when it's written.
+#[derive(Debug, Clone)]
+pub struct NewEvent<'a> {
+    pub kind: EventKind,
+    pub project: &'a str,
+    pub date: Option<NaiveDate>,
+    pub todo_id: Option<&'a str>,
+    pub content: Option<&'a str>,
+    pub detail: Option<String>,
+}
+
+impl<'a> NewEvent<'a> {
+    pub fn new(kind: EventKind, project: &'a str) -> Self {
+        Self {
+            kind,
+            project,
+            date: None,
+            todo_id: None,
+            content: None,
+            detail: None,
+        }
+    }
+}
+
+/// Record `event` on `conn`, inside whatever transaction it is in.
+pub(crate) fn record_on(conn: &Connection, event: &NewEvent) -> Result<()> {
+    conn.execute(
+        "INSERT INTO events (at, origin, kind, project, date, todo_id, content, detail)
+         VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7, ?8)",
+        rusqlite::params![
+            Utc::now().to_rfc3339(),
+            origin().to_string(),
+            event.kind.as_str(),
+            event.project,
+            event.date.map(|d| d.format("%Y-%m-%d").to_string()),
+            event.todo_id,
+            event.content,
+            event.detail,
+        ],
+    )?;
+    Ok(())
+}
+
+pub fn record(event: &NewEvent) -> Result<()> {
+    let conn = get_connection()?;
+    record_on(&conn, event)
+}
+
+/// A recorded change.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct Event {
+    pub id: i64,
+    pub at: DateTime<Utc>,
+    pub origin: String,
+    pub kind: String,
+    pub project: String,
+    pub date: Option<NaiveDate>,
+    pub todo_id: Option<String>,
+    /// The item's text at the time
+    pub content: Option<String>,
+    /// What changed: the fields of an edit, the states of a state change
+    pub detail: Option<String>,
+}
+
+/// Which events to load. Unset fields match everything.
+#[derive(Debug, Clone, Default)]
+pub struct HistoryQuery {
+    pub project: Option<String>,
+    /// An item's id, or the start of one
+    pub todo_id: Option<String>,
+    pub kind: Option<EventKind>,
+    pub since: Option<DateTime<Utc>>,
+    /// Only events recorded after the one with this id
+    pub after: Option<i64>,
+    /// Most events to load; 0 loads them all
+    pub limit: usize,
+}
+
+/// Events matching `query`, newest first.
+pub fn load_events(query: &HistoryQuery) -> Result<Vec<Event>> {
+    let conn = get_connection()?;
+
+    let mut conditions = Vec::new();
+    let mut values: Vec<String> = Vec::new();
+    if let Some(project) = &query.project {
+        values.push(project.clone());
+        conditions.push(format!("project = ?{}", values.len()));
+    }
+    if let Some(todo_id) = &query.todo_id {
+        values.push(format!("{}%", todo_id.to_lowercase()));
+        conditions.push(format!("todo_id LIKE ?{}", values.len()));
+    }
+    if let Some(kind) = query.kind {
+        values.push(kind.as_str().to_string());
+        conditions.push(format!("kind = ?{}", values.len()));
+    }
+    if let Some(since) = query.since {
+        values.push(since.to_rfc3339());
+        conditions.push(format!("at >= ?{}", values.len()));
+    }
+    if let Some(after) = query.after {
+        values.push(after.to_string());
+        conditions.push(format!("id > CAST(?{} AS INTEGER)", values.len()));
+    }
+
+    let mut sql = "SELECT id, at, origin, kind, project, date, todo_id, content, detail FROM events".to_string();
+    if !conditions.is_empty() {
+        sql.push_str(" WHERE ");
+        sql.push_str(&conditions.join(" AND "));
+    }
+    sql.push_str(" ORDER BY id DESC");
+    if query.limit > 0 {
+        sql.push_str(&format!(" LIMIT {}", query.limit));
+    }
+
+    let mut stmt = conn.prepare(&sql)?;
+    let rows = stmt.query_map(params_from_iter(values.iter()), |row| {
+        let at: String = row.get(1)?;
+        let date: Option<String> = row.get(5)?;
+        Ok(Event {
+            id: row.get(0)?,
+            at: DateTime::parse_from_rfc3339(&at)
+                .map(|at| at.with_timezone(&Utc))
+                .unwrap_or_default(),
+            origin: row.get(2)?,
+            kind: row.get(3)?,
+            project: row.get(4)?,
+            date: date.and_then(|d| NaiveDate::parse_from_str(&d, "%Y-%m-%d").ok()),
+            todo_id: row.get(6)?,
+            content: row.get(7)?,
+            detail: row.get(8)?,
+        })
+    })?;
+
+    Ok(rows.collect::<rusqlite::Result<Vec<_>>>()?)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::storage::database::{
+        init_database, save_todo_list_for_project, soft_delete_todos_for_project,
+    };
+    use crate::todo::{TodoList, TodoState};
+    use serial_test::serial;
+    use std::path::PathBuf;
+    use tempfile::TempDir;
+
+    fn setup() -> TempDir {
+        let temp_dir = TempDir::new().unwrap();
+        std::fs::create_dir_all(temp_dir.path().join(".to-tui")).unwrap();
+        unsafe {
+            std::env::set_var("HOME", temp_dir.path());
+        }
+        init_database().unwrap();
+        temp_dir
+    }
+
+    fn kinds(events: &[Event]) -> Vec<&str> {
+        events.iter().map(|event| event.kind.as_str()).collect()
+    }
+
+    #[test]
+    #[serial]
+    fn test_saves_log_each_change() {
+        let _temp = setup();
+        let date = NaiveDate::from_ymd_opt(2026, 1, 18).unwrap();
+        let mut list = TodoList::new(date, PathBuf::from("/tmp/2026-01-18.md"));
+        list.add_item("Milk".to_string());
+        list.add_item("Eggs".to_string());
+        save_todo_list_for_project(&list, "default").unwrap();
+
+        list.items[0].set_state(TodoState::Checked);
+        list.items[1].content = "Free range eggs".to_string();
+        with_plugin_origin("jira", || save_todo_list_for_project(&list, "default")).unwrap();
+
+        // Saving again without changes logs nothing
+        save_todo_list_for_project(&list, "default").unwrap();
+        soft_delete_todos_for_project(&[list.items[1].id], date, "default").unwrap();
+
+        let events = load_events(&HistoryQuery::default()).unwrap();
+        assert_eq!(kinds(&events), vec!["delete", "edit", "state", "add", "add"]);
+        assert_eq!(events[0].content.as_deref(), Some("Free range eggs"));
+        assert_eq!(events[0].origin, "cli");
+        assert_eq!(events[1].detail.as_deref(), Some("content"));
+        assert_eq!(events[1].origin, "plugin:jira");
+        assert_eq!(events[2].detail.as_deref(), Some("[ ] -> [x]"));
+
+        let milk = list.items[0].id.to_string();
+        let query = HistoryQuery {
+            todo_id: Some(milk[..8].to_string()),
+            ..HistoryQuery::default()
+        };
+        assert_eq!(kinds(&load_events(&query).unwrap()), vec!["state", "add"]);
+
+        let query = HistoryQuery {
+            kind: Some(EventKind::Add),
+            limit: 1,
+            ..HistoryQuery::default()
+        };
+        assert_eq!(load_events(&query).unwrap().len(), 1);
+    }
+
+    #[test]
+    #[serial]
+    fn test_log_is_append_only() {
+        let _temp = setup();
+        record(&NewEvent::new(EventKind::Rollover, "default")).unwrap();
+
+        let conn = get_connection().unwrap();
+        assert!(conn.execute("UPDATE events SET origin = 'api'", []).is_err());
+        assert!(conn.execute("DELETE FROM events", []).is_err());
+        assert_eq!(load_events(&HistoryQuery::default()).unwrap().len(), 1);
+    }
+
+    #[test]
+    fn test_event_kind_round_trip() {
+        for kind in EventKind::ALL {
+            assert_eq!(kind.as_str().parse::<EventKind>().unwrap(), kind);
+        }
+        assert!("moved".parse::<EventKind>().is_err());
+    }
+}
@@ -0,0 +1,121 @@
+//! Responses to API writes sent with an `Idempotency-Key`, kept for a day so
+//! a client retrying after a dropped connection gets the first answer back
+//! instead of writing twice.
+
+use super::database::get_connection;
+use anyhow::Result;
+use chrono::Utc;
+use rusqlite::{params, OptionalExtension};
+
+/// The `idempotency_keys` table.
+pub(crate) const IDEMPOTENCY_SCHEMA: &str = "
+    CREATE TABLE IF NOT EXISTS idempotency_keys (
+        key TEXT PRIMARY KEY,
+        request_hash TEXT NOT NULL,
+        status INTEGER NOT NULL,
+        content_type TEXT,
+        body BLOB NOT NULL,
+        created_at INTEGER NOT NULL
+    );
+    CREATE INDEX IF NOT EXISTS idx_idempotency_keys_created ON idempotency_keys(created_at);
+";
+
+/// How long a key is remembered, in seconds
+pub const KEY_LIFETIME_SECS: i64 = 24 * 60 * 60;
+
+/// The response first given to a request with a key.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct StoredResponse {
+    /// Identifies the request, so a key reused for another one is caught
+    pub request_hash: String,
+    pub status: u16,
+    pub content_type: Option<String>,
+    pub body: Vec<u8>,
+}
+
+/// The response recorded under `key`, unless it has expired.
+pub fn load_response(key: &str) -> Result<Option<StoredResponse>> {
+    let conn = get_connection()?;
+    let response = conn
+        .query_row(
+            "SELECT request_hash, status, content_type, body FROM idempotency_keys
+             WHERE key = ?1 AND created_at > ?2",
+            params![key, Utc::now().timestamp() - KEY_LIFETIME_SECS],
+            |row| {
+                Ok(StoredResponse {
+                    request_hash: row.get(0)?,
+                    status: row.get(1)?,
+                    content_type: row.get(2)?,
+                    body: row.get(3)?,
+                })
+            },
+        )
+        .optional()?;
+    Ok(response)
+}
+
+/// Record the response to `key`, forgetting keys that have expired.
+pub fn save_response(key: &str, response: &StoredResponse) -> Result<()> {
+    let conn = get_connection()?;
+    let now = Utc::now().timestamp();
+    conn.execute(
+        "DELETE FROM idempotency_keys WHERE created_at <= ?1",
+        params![now - KEY_LIFETIME_SECS],
+    )?;
+    conn.execute(
+        "INSERT OR REPLACE INTO idempotency_keys (key, request_hash, status, content_type, body, created_at)
+         VALUES (?1, ?2, ?3, ?4, ?5, ?6)",
+        params![
+            key,
+            response.request_hash,
+            response.status,
+            response.content_type,
+            response.body,
+            now
+        ],
+    )?;
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::storage::database::init_database;
+    use serial_test::serial;
+    use tempfile::TempDir;
+
+    #[test]
+    #[serial]
+    fn test_responses_are_kept_until_they_expire() {
+        let temp_dir = TempDir::new().unwrap();
+        std::fs::create_dir_all(temp_dir.path().join(".to-tui")).unwrap();
+        unsafe {
+            std::env::set_var("HOME", temp_dir.path());
+        }
+        init_database().unwrap();
+
+        let response = StoredResponse {
+            request_hash: "abc".to_string(),
+            status: 201,
+            content_type: Some("application/json".to_string()),
+            body: br#"{"id":1}"#.to_vec(),
+        };
+        assert_eq!(load_response("key-1").unwrap(), None);
+        save_response("key-1", &response).unwrap();
+        assert_eq!(load_response("key-1").unwrap(), Some(response.clone()));
+
+        let conn = get_connection().unwrap();
+        conn.execute(
+            "UPDATE idempotency_keys SET created_at = created_at - ?1",
+            params![KEY_LIFETIME_SECS],
+        )
+        .unwrap();
+        assert_eq!(load_response("key-1").unwrap(), None);
+
+        save_response("key-2", &response).unwrap();
+        let kept: i64 = conn
+            .query_row("SELECT COUNT(*) FROM idempotency_keys", [], |row| row.get(0))
+            .unwrap();
+        assert_eq!(kept, 1);
+    }
+}
@@ -0,0 +1,183 @@
+//! Open GitHub issues of a repository, through the REST API.
+//!
+//! Each issue becomes an item linking to it, with the issue's number and URL
+//! kept in the item's `github` metadata. The issue's id is the item's
+//! external id, which is how issues imported before are recognized.
+
+use super::{ImportedProject, ItemSource};
+use crate::todo::TodoItem;
+use anyhow::{Context, Result};
+use chrono::{DateTime, Local, Utc};
+use serde::Deserialize;
+use serde_json::json;
+use tracing::debug;
+
+pub const IMPORTER: &str = "github";
+
+const API_URL: &str = "https://api.github.com";
+const PER_PAGE: usize = 100;
+
+#[derive(Debug, Clone, Deserialize)]
+pub struct Issue {
+    pub id: u64,
+    pub number: u64,
+    pub title: String,
+    pub html_url: String,
+    /// Set when the "issue" is a pull request
+    #[serde(default)]
+    pub pull_request: Option<serde_json::Value>,
+    #[serde(default)]
+    pub milestone: Option<Milestone>,
+}
+
+#[derive(Debug, Clone, Deserialize)]
+pub struct Milestone {
+    #[serde(default)]
+    pub due_on: Option<DateTime<Utc>>,
+}
+
+#[derive(Deserialize)]
+struct User {
+    login: String,
+}
+
+fn client() -> Result<reqwest::blocking::Client> {
+    Ok(reqwest::blocking::Client::builder()
+        .user_agent("to-tui")
+        .timeout(std::time::Duration::from_secs(30))
+        .build()?)
+}
+
+fn get(client: &reqwest::blocking::Client, url: &str, token: Option<&str>) -> reqwest::blocking::RequestBuilder {
+    let request = client
+        .get(url)
+        .header("Accept", "application/vnd.github+json");
+    match token {
+        Some(token) => request.bearer_auth(token),
+        None => request,
+    }
+}
+
+/// Fetch the open issues of `repo` (`owner/name`), only those assigned to
+/// `assignee` if given. `me` stands for the owner of `token`.
+pub fn fetch(repo: &str, assignee: Option<&str>, token: Option<&str>) -> Result<Vec<Issue>> {
+    if repo.split('/').filter(|part| !part.is_empty()).count() != 2 {
+        anyhow::bail!("Expected a repository as owner/name, got '{repo}'");
+    }
+    let client = client()?;
+
+    let assignee = match assignee {
+        Some("me") => {
+            let token = token.context("--assignee me needs a token to know who you are")?;
+            let user: User = get(&client, &format!("{API_URL}/user"), Some(token))
+                .send()?
+                .error_for_status()
+                .context("GitHub didn't accept the token")?
+                .json()?;
+            Some(user.login)
+        }
+        other => other.map(str::to_string),
+    };
+
+    let url = format!("{API_URL}/repos/{repo}/issues");
+    let mut issues = Vec::new();
+    for page in 1.. {
+        let mut query = vec![
+            ("state", "open".to_string()),
+            ("per_page", PER_PAGE.to_string()),
+            ("page", page.to_string()),
+        ];
+        if let Some(assignee) = &assignee {
+            query.push(("assignee", assignee.clone()));
+        }
+        let response = get(&client, &url, token)
+            .query(&query)
+            .send()
+            .with_context(|| format!("Failed to reach GitHub at {url}"))?;
+        if !response.status().is_success() {
+            anyhow::bail!("GitHub returned HTTP {} for {repo}", response.status());
+        }
+        let batch: Vec<Issue> = response
+            .json()
+            .with_context(|| format!("Unexpected response from GitHub for {repo}"))?;
+        let done = batch.len() < PER_PAGE;
+        issues.extend(batch);
+        if done {
+            break;
+        }
+    }
+    debug!(repo = %repo, count = issues.len(), "Fetched GitHub issues");
+    Ok(issues)
+}
+
+/// Items for the issues, pull requests left out, bound for `project_name`.
+pub fn from_issues(repo: &str, issues: Vec<Issue>, project_name: &str) -> ImportedProject {
+    let mut project = ImportedProject::new(project_name, Vec::new());
+    for issue in issues.into_iter().filter(|issue| issue.pull_request.is_none()) {
+        let mut item = TodoItem::new(format!("{} (#{})", issue.title.trim(), issue.number), 0);
+        item.link = Some(issue.html_url.clone());
+        item.due_date = issue
+            .milestone
+            .and_then(|milestone| milestone.due_on)
+            .map(|due| due.with_timezone(&Local).date_naive());
+
+        project.sources.push(ItemSource {
+            todo_id: item.id,
+            importer: IMPORTER,
+            external_id: issue.id.to_string(),
+            data: json!({
+                "repo": repo,
+                "number": issue.number,
+                "url": issue.html_url,
+            }),
+        });
+        project.items.push(item);
+    }
+    project
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::storage::database::init_database;
+    use crate::storage::importers::import;
+    use crate::storage::metadata::get_todo_metadata;
+    use serial_test::serial;
+    use tempfile::TempDir;
+
+    fn issues() -> Vec<Issue> {
+        serde_json::from_str(
+            r#"[{"id": 11, "number": 1, "title": "Crash on start", "html_url": "https://github.com/o/r/issues/1"},
+                {"id": 12, "number": 2, "title": "Add export", "html_url": "https://github.com/o/r/pull/2",
+                 "pull_request": {"url": "https://api.github.com/repos/o/r/pulls/2"}},
+                {"id": 13, "number": 3, "title": "Docs ", "html_url": "https://github.com/o/r/issues/3",
+                 "milestone": {"due_on": "2026-02-01T08:00:00Z"}}]"#,
+        )
+        .unwrap()
+    }
+
+    #[test]
+    #[serial]
+    fn test_issues_are_imported_once_with_metadata() {
+        let temp_dir = TempDir::new().unwrap();
+        std::fs::create_dir_all(temp_dir.path().join(".to-tui")).unwrap();
+        unsafe {
+            std::env::set_var("HOME", temp_dir.path());
+        }
+        init_database().unwrap();
+        let date = chrono::NaiveDate::from_ymd_opt(2026, 1, 18).unwrap();
+
+        let project = from_issues("o/r", issues(), "default");
+        assert_eq!(project.preview(), "- [ ] Crash on start (#1) @link(https://github.com/o/r/issues/1)\n- [ ] Docs (#3) @link(https://github.com/o/r/issues/3) @due(2026-02-01)\n");
+        assert_eq!(import(std::slice::from_ref(&project), date).unwrap(), 2);
+
+        let metadata = get_todo_metadata(&project.items[0].id, IMPORTER).unwrap();
+        let metadata: serde_json::Value = serde_json::from_str(&metadata).unwrap();
+        assert_eq!(metadata["number"], 1);
+        assert_eq!(metadata["url"], "https://github.com/o/r/issues/1");
+
+        let mut again = from_issues("o/r", issues(), "default");
+        assert_eq!(again.skip_imported().unwrap(), 2);
+        assert!(again.items.is_empty());
+    }
+}
@@ -0,0 +1,232 @@
+//! To-dos (`VTODO`) of an iCalendar file, as exported by Reminders,
+//! Thunderbird or Nextcloud Tasks.
+//!
+//! Due dates, completion and priority carry over, and a to-do related to
+//! another one by `RELATED-TO` is nested under it. The `UID` is the item's
+//! external id, so a file can be imported again as it grows.
+
+use super::{ImportedProject, ItemSource};
+use crate::todo::{Priority, TodoItem, TodoState};
+use anyhow::Result;
+use chrono::{DateTime, Local, NaiveDate, NaiveDateTime, Utc};
+use serde_json::json;
+use std::collections::HashMap;
+
+pub const IMPORTER: &str = "ics";
+
+#[derive(Debug, Default, Clone)]
+struct Todo {
+    uid: Option<String>,
+    summary: String,
+    description: Option<String>,
+    due: Option<NaiveDate>,
+    status: Option<String>,
+    completed: bool,
+    priority: Option<Priority>,
+    parent: Option<String>,
+}
+
+/// Read the to-dos of `content`, leaving out completed ones if asked.
+pub fn from_ics(content: &str, project_name: &str, skip_completed: bool) -> Result<ImportedProject> {
+    let todos: Vec<Todo> = parse(content)
+        .into_iter()
+        .filter(|todo| !(skip_completed && todo.completed))
+        .collect();
+    if todos.is_empty() && !content.contains("BEGIN:VCALENDAR") {
+        anyhow::bail!("Not an iCalendar file");
+    }
+
+    let mut children: HashMap<Option<&str>, Vec<&Todo>> = HashMap::new();
+    let uids: Vec<&str> = todos.iter().filter_map(|todo| todo.uid.as_deref()).collect();
+    for todo in &todos {
+        // To-dos whose parent isn't in the file stay at the top
+        let parent = todo.parent.as_deref().filter(|parent| uids.contains(parent));
+        children.entry(parent).or_default().push(todo);
+    }
+
+    let mut project = ImportedProject::new(project_name, Vec::new());
+    for todo in children.get(&None).into_iter().flatten() {
+        push_todo(todo, 0, &children, &mut project);
+    }
+    Ok(project)
+}
+
+fn push_todo(
+    todo: &Todo,
+    indent_level: usize,
+    children: &HashMap<Option<&str>, Vec<&Todo>>,
+    project: &mut ImportedProject,
+) {
+    let mut item = TodoItem::new(todo.summary.clone(), indent_level);
+    let state = match todo.status.as_deref() {
+        _ if todo.completed => TodoState::Checked,
+        Some("IN-PROCESS") => TodoState::InProgress,
+        Some("CANCELLED") => TodoState::Cancelled,
+        _ => TodoState::Empty,
+    };
+    item.set_state(state);
+    item.due_date = todo.due;
+    item.priority = todo.priority;
+    item.description = todo.description.clone();
+    if let Some(uid) = &todo.uid {
+        project.sources.push(ItemSource {
+            todo_id: item.id,
+            importer: IMPORTER,
+            external_id: uid.clone(),
+            data: json!({ "uid": uid }),
+        });
+    }
+    project.items.push(item);
+
+    if let Some(uid) = todo.uid.as_deref() {
+        for child in children.get(&Some(uid)).into_iter().flatten() {
+            push_todo(child, indent_level + 1, children, project);
+        }
+    }
+}
+
+fn parse(content: &str) -> Vec<Todo> {
+    let mut todos = Vec::new();
+    let mut current: Option<Todo> = None;
+    for line in unfold(content) {
+        let Some((name_and_params, value)) = line.split_once(':') else {
+            continue;
+        };
+        let mut params = name_and_params.split(';');
+        let name = params.next().unwrap_or_default().to_ascii_uppercase();
+        let value = value.trim();
+
+        match (name.as_str(), current.as_mut()) {
+            ("BEGIN", None) if value.eq_ignore_ascii_case("VTODO") => current = Some(Todo::default()),
+            ("END", Some(_)) if value.eq_ignore_ascii_case("VTODO") => {
+                if let Some(todo) = current.take()
+                    && !todo.summary.is_empty()
+                {
+                    todos.push(todo);
+                }
+            }
+            ("UID", Some(todo)) => todo.uid = Some(value.to_string()),
+            ("SUMMARY", Some(todo)) => todo.summary = unescape(value),
+            ("DESCRIPTION", Some(todo)) => {
+                let description = unescape(value);
+                todo.description = (!description.trim().is_empty()).then_some(description);
+            }
+            ("DUE", Some(todo)) => todo.due = parse_date(value),
+            ("STATUS", Some(todo)) => {
+                let status = value.to_ascii_uppercase();
+                todo.completed |= status == "COMPLETED";
+                todo.status = Some(status);
+            }
+            ("COMPLETED", Some(todo)) => todo.completed = true,
+            ("PRIORITY", Some(todo)) => {
+                todo.priority = match value.parse::<u8>() {
+                    Ok(1..=4) => Some(Priority::P0),
+                    Ok(5) => Some(Priority::P1),
+                    Ok(6..=9) => Some(Priority::P2),
+                    _ => None,
+                }
+            }
+            ("RELATED-TO", Some(todo)) => {
+                // Only the parent relation, which is also the default
+                let is_parent = params.all(|param| {
+                    param
+                        .split_once('=')
+                        .is_none_or(|(key, kind)| !key.eq_ignore_ascii_case("RELTYPE") || kind.eq_ignore_ascii_case("PARENT"))
+                });
+                if is_parent {
+                    todo.parent = Some(value.to_string());
+                }
+            }
+            _ => {}
+        }
+    }
+    todos
+}
+
+/// Join lines continued on the next line by a leading space or tab.
+fn unfold(content: &str) -> Vec<String> {
+    let mut lines: Vec<String> = Vec::new();
+    for line in content.lines() {
+        match (line.strip_prefix([' ', '\t']), lines.last_mut()) {
+            (Some(rest), Some(last)) => last.push_str(rest),
+            _ => lines.push(line.trim_end_matches('\r').to_string()),
+        }
+    }
+    lines
+}
+
+fn unescape(value: &str) -> String {
+    let mut output = String::with_capacity(value.len());
+    let mut chars = value.chars();
+    while let Some(c) = chars.next() {
+        if c != '\\' {
+            output.push(c);
+            continue;
+        }
+        match chars.next() {
+            Some('n' | 'N') => output.push('\n'),
+            Some(other) => output.push(other),
+            None => output.push('\\'),
+        }
+    }
+    output.trim().to_string()
+}
+
+/// `20260118`, `20260118T090000` (floating) or `20260118T090000Z` (UTC).
+fn parse_date(value: &str) -> Option<NaiveDate> {
+    if let Some(utc) = value.strip_suffix('Z') {
+        let at = NaiveDateTime::parse_from_str(utc, "%Y%m%dT%H%M%S").ok()?;
+        return Some(DateTime::<Utc>::from_naive_utc_and_offset(at, Utc).with_timezone(&Local).date_naive());
+    }
+    NaiveDate::parse_from_str(value.get(..8)?, "%Y%m%d").ok()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const CALENDAR: &str = "BEGIN:VCALENDAR\r\n\
+        VERSION:2.0\r\n\
+        BEGIN:VTODO\r\n\
+        UID:trip\r\n\
+        SUMMARY:Plan the trip\r\n\
+        DUE;VALUE=DATE:20260201\r\n\
+        PRIORITY:1\r\n\
+        DESCRIPTION:Flights\\, hotel\\nand a car\r\n\
+        END:VTODO\r\n\
+        BEGIN:VTODO\r\n\
+        UID:passport\r\n\
+        RELATED-TO;RELTYPE=PARENT:trip\r\n\
+        SUMMARY:Renew the pass\r\n \
+        port\r\n\
+        STATUS:COMPLETED\r\n\
+        COMPLETED:20260110T120000Z\r\n\
+        END:VTODO\r\n\
+        BEGIN:VTODO\r\n\
+        UID:bike\r\n\
+        SUMMARY:Fix the bike\r\n\
+        STATUS:IN-PROCESS\r\n\
+        DUE;TZID=Europe/Oslo:20260120T090000\r\n\
+        END:VTODO\r\n\
+        END:VCALENDAR\r\n";
+
+    #[test]
+    fn test_from_ics_keeps_due_dates_state_and_nesting() {
+        let project = from_ics(CALENDAR, "default", false).unwrap();
+        assert_eq!(
+            project.preview(),
+            "- [ ] Plan the trip @priority(P0) @due(2026-02-01)\n\
+             \x20 > Flights, hotel\n\
+             \x20 > and a car\n\
+             \x20 - [x] Renew the passport\n\
+             - [*] Fix the bike @due(2026-01-20)\n"
+        );
+        assert_eq!(project.sources.len(), 3);
+        assert_eq!(project.sources[1].external_id, "passport");
+
+        let open = from_ics(CALENDAR, "default", true).unwrap();
+        assert_eq!(open.items.len(), 2);
+
+        assert!(from_ics("TYPE,CONTENT\n", "default", false).is_err());
+    }
+}
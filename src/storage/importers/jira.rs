@@ -0,0 +1,242 @@
+//! Jira issues matching a JQL query, through the Cloud REST API.
+//!
+//! Subtasks become children of their parent issue, and each issue's status
+//! picks its state through `status_map` or else its status category. Issues
+//! link to their page and keep their key in the item's `jira` metadata;
+//! issues imported before are recognized by their id.
+
+use super::{ImportedProject, ItemSource};
+use crate::config::JiraConfig;
+use crate::plugin::subprocess::run_command;
+use crate::project::DEFAULT_PROJECT_NAME;
+use crate::todo::{TodoItem, TodoState};
+use anyhow::{Context, Result};
+use chrono::NaiveDate;
+use serde::Deserialize;
+use serde_json::json;
+use std::collections::HashSet;
+use tracing::debug;
+
+pub const IMPORTER: &str = "jira";
+
+const FIELDS: &str = "summary,status,subtasks,duedate";
+const PAGE_SIZE: &str = "100";
+
+#[derive(Debug, Clone, Deserialize)]
+pub struct Issue {
+    pub id: String,
+    pub key: String,
+    pub fields: Fields,
+}
+
+#[derive(Debug, Clone, Deserialize)]
+pub struct Fields {
+    #[serde(default)]
+    pub summary: String,
+    pub status: Option<Status>,
+    /// Subtasks carry only their summary and status
+    #[serde(default)]
+    pub subtasks: Vec<Issue>,
+    #[serde(default)]
+    pub duedate: Option<NaiveDate>,
+}
+
+#[derive(Debug, Clone, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct Status {
+    pub name: String,
+    pub status_category: Option<StatusCategory>,
+}
+
+#[derive(Debug, Clone, Deserialize)]
+pub struct StatusCategory {
+    /// `new`, `indeterminate` or `done`
+    pub key: String,
+}
+
+#[derive(Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct SearchPage {
+    issues: Vec<Issue>,
+    #[serde(default)]
+    next_page_token: Option<String>,
+}
+
+/// The API token from the configured environment variable, or else from
+/// `token_command`.
+fn token(config: &JiraConfig) -> Result<String> {
+    if let Ok(token) = std::env::var(&config.token_env)
+        && !token.trim().is_empty()
+    {
+        return Ok(token.trim().to_string());
+    }
+    let command = config.token_command.as_deref().with_context(|| {
+        format!(
+            "No Jira API token: set ${} or token_command in [importers.jira]",
+            config.token_env
+        )
+    })?;
+    let output = if cfg!(windows) {
+        run_command("cmd", &["/C", command])
+    } else {
+        run_command("sh", &["-c", command])
+    }?;
+    let token = output.trim();
+    if token.is_empty() {
+        anyhow::bail!("token_command printed no token");
+    }
+    Ok(token.to_string())
+}
+
+/// Fetch the issues matching `jql`.
+pub fn fetch(config: &JiraConfig, jql: &str) -> Result<Vec<Issue>> {
+    let email = config
+        .email
+        .clone()
+        .or_else(|| std::env::var("JIRA_EMAIL").ok())
+        .context("No Jira account email: set email in [importers.jira] or $JIRA_EMAIL")?;
+    let token = token(config)?;
+    let client = reqwest::blocking::Client::builder()
+        .user_agent("to-tui")
+        .timeout(std::time::Duration::from_secs(30))
+        .build()?;
+
+    let url = format!("{}/rest/api/3/search/jql", config.url.trim_end_matches('/'));
+    let mut issues = Vec::new();
+    let mut page_token: Option<String> = None;
+    loop {
+        let mut query = vec![("jql", jql), ("fields", FIELDS), ("maxResults", PAGE_SIZE)];
+        if let Some(page_token) = &page_token {
+            query.push(("nextPageToken", page_token));
+        }
+        let response = client
+            .get(&url)
+            .basic_auth(&email, Some(&token))
+            .header("Accept", "application/json")
+            .query(&query)
+            .send()
+            .with_context(|| format!("Failed to reach Jira at {url}"))?;
+        if !response.status().is_success() {
+            let status = response.status();
+            let body = response.text().unwrap_or_default();
+            anyhow::bail!("Jira returned HTTP {status}: {}", body.trim());
+        }
+        let page: SearchPage = response.json().context("Unexpected response from Jira")?;
+        issues.extend(page.issues);
+        page_token = page.next_page_token;
+        if page_token.is_none() {
+            break;
+        }
+    }
+    debug!(count = issues.len(), "Fetched Jira issues");
+    Ok(issues)
+}
+
+fn state_for(status: Option<&Status>, config: &JiraConfig) -> Result<TodoState> {
+    let Some(status) = status else {
+        return Ok(TodoState::Empty);
+    };
+    if let Some(state) = config
+        .status_map
+        .iter()
+        .find(|(name, _)| name.eq_ignore_ascii_case(&status.name))
+        .map(|(_, state)| state)
+    {
+        return TodoState::parse(state).with_context(|| {
+            format!("Unknown state '{state}' for status '{}' in status_map", status.name)
+        });
+    }
+    Ok(
+        match status.status_category.as_ref().map(|category| category.key.as_str()) {
+            Some("done") => TodoState::Checked,
+            Some("indeterminate") => TodoState::InProgress,
+            _ => TodoState::Empty,
+        },
+    )
+}
+
+/// Items for the issues, each followed by its subtasks, bound for the
+/// configured project.
+pub fn from_issues(issues: Vec<Issue>, config: &JiraConfig) -> Result<ImportedProject> {
+    let project_name = config.project.as_deref().unwrap_or(DEFAULT_PROJECT_NAME);
+    let mut project = ImportedProject::new(project_name, Vec::new());
+    // Subtasks come along with their parent
+    let nested: HashSet<String> = issues
+        .iter()
+        .flat_map(|issue| issue.fields.subtasks.iter().map(|subtask| subtask.key.clone()))
+        .collect();
+
+    let mut add = |issue: &Issue, indent_level: usize| -> Result<()> {
+        let url = format!("{}/browse/{}", config.url.trim_end_matches('/'), issue.key);
+        let mut item = TodoItem::new(format!("{} {}", issue.key, issue.fields.summary.trim()), indent_level);
+        item.set_state(state_for(issue.fields.status.as_ref(), config)?);
+        item.due_date = issue.fields.duedate;
+        item.link = Some(url.clone());
+        project.sources.push(ItemSource {
+            todo_id: item.id,
+            importer: IMPORTER,
+            external_id: issue.id.clone(),
+            data: json!({ "key": issue.key, "url": url }),
+        });
+        project.items.push(item);
+        Ok(())
+    };
+
+    for issue in issues.iter().filter(|issue| !nested.contains(&issue.key)) {
+        add(issue, 0)?;
+        for subtask in &issue.fields.subtasks {
+            add(subtask, 1)?;
+        }
+    }
+    Ok(project)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn config() -> JiraConfig {
+        toml::from_str(
+            r#"
+            url = "https://example.atlassian.net/"
+            project = "work"
+            [status_map]
+            "In Review" = "?"
+            "#,
+        )
+        .unwrap()
+    }
+
+    #[test]
+    fn test_subtasks_are_nested_and_statuses_mapped() {
+        let issues: Vec<Issue> = serde_json::from_str(
+            r#"[{"id": "1", "key": "APP-1", "fields": {
+                    "summary": "Checkout flow",
+                    "status": {"name": "In Progress", "statusCategory": {"key": "indeterminate"}},
+                    "duedate": "2026-02-01",
+                    "subtasks": [
+                        {"id": "2", "key": "APP-2", "fields": {"summary": "Button",
+                         "status": {"name": "Done", "statusCategory": {"key": "done"}}}},
+                        {"id": "3", "key": "APP-3", "fields": {"summary": "Copy",
+                         "status": {"name": "In Review", "statusCategory": {"key": "indeterminate"}}}}
+                    ]}},
+                {"id": "3", "key": "APP-3", "fields": {"summary": "Copy", "parent": {"key": "APP-1"},
+                    "status": {"name": "In Review", "statusCategory": {"key": "indeterminate"}}}},
+                {"id": "4", "key": "APP-4", "fields": {"summary": "Logo",
+                    "status": {"name": "To Do", "statusCategory": {"key": "new"}}}}]"#,
+        )
+        .unwrap();
+
+        let project = from_issues(issues, &config()).unwrap();
+        assert_eq!(project.name, "work");
+        assert_eq!(
+            project.preview(),
+            "- [*] APP-1 Checkout flow @link(https://example.atlassian.net/browse/APP-1) @due(2026-02-01)\n\
+             \x20 - [x] APP-2 Button @link(https://example.atlassian.net/browse/APP-2)\n\
+             \x20 - [?] APP-3 Copy @link(https://example.atlassian.net/browse/APP-3)\n\
+             - [ ] APP-4 Logo @link(https://example.atlassian.net/browse/APP-4)\n"
+        );
+        assert_eq!(project.sources[0].external_id, "1");
+        assert_eq!(project.sources[0].data["key"], "APP-1");
+    }
+}
@@ -0,0 +1,120 @@
+//! Importing todos from other apps.
+//!
+//! Each importer turns what it reads into [`ImportedProject`]s, which
+//! [`import`] then adds to today's lists.
+
+pub mod github;
+pub mod ics;
+pub mod jira;
+pub mod reminders;
+pub mod todoist;
+
+use super::file::{load_todo_list_for_project, save_todo_list_for_project};
+use super::markdown::serialize_items;
+use super::metadata::{get_todo_id_by_external_id, set_external_id, set_todo_metadata};
+use crate::project::ProjectRegistry;
+use crate::todo::TodoItem;
+use anyhow::Result;
+use chrono::NaiveDate;
+use std::collections::HashSet;
+use uuid::Uuid;
+
+/// Todos read from another app, bound for one totui project.
+#[derive(Debug, Clone)]
+pub struct ImportedProject {
+    pub name: String,
+    /// In list order, nesting given by `indent_level`
+    pub items: Vec<TodoItem>,
+    /// Where items came from, for importers that can tell
+    pub sources: Vec<ItemSource>,
+}
+
+/// The item in the other app an imported item came from. It is stored as
+/// the item's metadata under the importer's name, so the same item isn't
+/// imported twice.
+#[derive(Debug, Clone)]
+pub struct ItemSource {
+    pub todo_id: Uuid,
+    pub importer: &'static str,
+    pub external_id: String,
+    /// JSON object
+    pub data: serde_json::Value,
+}
+
+impl ImportedProject {
+    pub fn new(name: impl Into<String>, items: Vec<TodoItem>) -> Self {
+        Self {
+            name: name.into(),
+            items,
+            sources: Vec::new(),
+        }
+    }
+
+    /// The items as they would appear in a daily file.
+    pub fn preview(&self) -> String {
+        serialize_items(&self.items)
+    }
+
+    /// Leave out items imported before. Returns how many were left out.
+    pub fn skip_imported(&mut self) -> Result<usize> {
+        let mut skipped = Vec::new();
+        for source in &self.sources {
+            if get_todo_id_by_external_id(source.importer, &source.external_id)?.is_some() {
+                skipped.push(source.todo_id);
+            }
+        }
+        self.items.retain(|item| !skipped.contains(&item.id));
+        self.sources.retain(|source| !skipped.contains(&source.todo_id));
+
+        // Grouping items, such as a list's name, go when nothing is left in them
+        if !skipped.is_empty() {
+            let sourced: HashSet<Uuid> = self.sources.iter().map(|source| source.todo_id).collect();
+            let empty: HashSet<Uuid> = self
+                .items
+                .iter()
+                .enumerate()
+                .filter(|(i, item)| {
+                    !sourced.contains(&item.id)
+                        && self
+                            .items
+                            .get(i + 1)
+                            .is_none_or(|next| next.indent_level <= item.indent_level)
+                })
+                .map(|(_, item)| item.id)
+                .collect();
+            self.items.retain(|item| !empty.contains(&item.id));
+        }
+
+        // Children of items left out move out to where their parent was
+        let mut max_indent = 0;
+        for item in &mut self.items {
+            item.indent_level = item.indent_level.min(max_indent);
+            max_indent = item.indent_level + 1;
+        }
+        Ok(skipped.len())
+    }
+}
+
+/// Add the items to `date`'s list of each project, creating projects that
+/// don't exist yet. Returns how many items were added.
+pub fn import(projects: &[ImportedProject], date: NaiveDate) -> Result<usize> {
+    let mut registry = ProjectRegistry::load()?;
+    let mut added = 0;
+    for project in projects.iter().filter(|project| !project.items.is_empty()) {
+        if registry.get_by_name(&project.name).is_none() {
+            registry.create(project.name.as_str())?;
+        }
+
+        let mut list = load_todo_list_for_project(&project.name, date)?;
+        list.items.extend(project.items.iter().cloned());
+        list.recalculate_parent_ids();
+        save_todo_list_for_project(&list, &project.name)?;
+        added += project.items.len();
+
+        for source in &project.sources {
+            set_todo_metadata(&source.todo_id, source.importer, &source.data.to_string(), false)?;
+            set_external_id(&source.todo_id, source.importer, &source.external_id)?;
+        }
+    }
+    Ok(added)
+}
@@ -0,0 +1,202 @@
+//! Reminders on macOS, read straight from the app's SQLite stores.
+//!
+//! The stores are Core Data databases under the Reminders group container.
+//! They are opened read-only, so Reminders itself is never touched. Each
+//! list becomes a parent item with its reminders nested beneath, and
+//! subtasks stay under their reminder.
+
+use super::{ImportedProject, ItemSource};
+use crate::todo::{Priority, TodoItem, TodoState};
+use anyhow::{Context, Result};
+use chrono::{DateTime, Local};
+use rusqlite::{Connection, OpenFlags};
+use serde_json::json;
+use std::collections::HashMap;
+use std::fs;
+use std::path::{Path, PathBuf};
+
+pub const IMPORTER: &str = "reminders";
+
+/// Seconds from the Unix epoch to Core Data's, 2001-01-01 UTC
+const CORE_DATA_EPOCH: i64 = 978_307_200;
+
+#[derive(Debug, Clone)]
+struct Reminder {
+    pk: i64,
+    id: String,
+    list: String,
+    title: String,
+    notes: Option<String>,
+    completed: bool,
+    due: Option<f64>,
+    priority: i64,
+    parent: Option<i64>,
+}
+
+/// The Reminders stores of the current user.
+pub fn default_stores() -> Result<Vec<PathBuf>> {
+    let home = dirs::home_dir().context("Could not determine home directory")?;
+    let dir = home.join("Library/Group Containers/group.com.apple.reminders/Container_v1/Stores");
+    let entries = fs::read_dir(&dir)
+        .with_context(|| format!("No Reminders data in {} (Reminders is only on macOS)", dir.display()))?;
+    let mut stores: Vec<PathBuf> = entries
+        .filter_map(|entry| entry.ok().map(|entry| entry.path()))
+        .filter(|path| {
+            let name = path.file_name().and_then(|name| name.to_str()).unwrap_or_default();
+            name.starts_with("Data-") && name.ends_with(".sqlite")
+        })
+        .collect();
+    stores.sort();
+    Ok(stores)
+}
+
+/// Read the reminders of the stores at `paths`, leaving out completed ones
+/// if asked. Stores without reminders tables are passed over.
+pub fn from_stores(paths: &[PathBuf], project_name: &str, skip_completed: bool) -> Result<ImportedProject> {
+    let mut project = ImportedProject::new(project_name, Vec::new());
+    for path in paths {
+        let reminders = match read_store(path) {
+            Ok(reminders) => reminders,
+            Err(e) => {
+                tracing::debug!(path = %path.display(), "Skipping Reminders store: {e:#}");
+                continue;
+            }
+        };
+        let reminders: Vec<Reminder> = reminders
+            .into_iter()
+            .filter(|reminder| !(skip_completed && reminder.completed))
+            .collect();
+        add_reminders(&reminders, &mut project);
+    }
+    Ok(project)
+}
+
+fn read_store(path: &Path) -> Result<Vec<Reminder>> {
+    let conn = Connection::open_with_flags(path, OpenFlags::SQLITE_OPEN_READ_ONLY | OpenFlags::SQLITE_OPEN_NO_MUTEX)
+        .with_context(|| format!("Failed to open {}", path.display()))?;
+    let mut stmt = conn.prepare(
+        "SELECT r.Z_PK, r.ZCKIDENTIFIER, l.ZNAME, r.ZTITLE, r.ZNOTES, r.ZCOMPLETED, r.ZDUEDATE, r.ZPRIORITY, r.ZPARENTREMINDER
+         FROM ZREMCDREMINDER r JOIN ZREMCDBASELIST l ON l.Z_PK = r.ZLIST
+         WHERE COALESCE(r.ZMARKEDFORDELETION, 0) = 0 AND COALESCE(l.ZMARKEDFORDELETION, 0) = 0
+           AND r.ZTITLE IS NOT NULL AND r.ZTITLE != ''
+         ORDER BY l.ZNAME, r.Z_PK",
+    )?;
+    let reminders = stmt
+        .query_map([], |row| {
+            let pk: i64 = row.get(0)?;
+            Ok(Reminder {
+                pk,
+                id: row.get::<_, Option<String>>(1)?.unwrap_or_else(|| pk.to_string()),
+                list: row.get::<_, Option<String>>(2)?.unwrap_or_default(),
+                title: row.get(3)?,
+                notes: row.get(4)?,
+                completed: row.get::<_, Option<i64>>(5)?.unwrap_or(0) != 0,
+                due: row.get(6)?,
+                priority: row.get::<_, Option<i64>>(7)?.unwrap_or(0),
+                parent: row.get(8)?,
+            })
+        })?
+        .collect::<rusqlite::Result<Vec<_>>>()?;
+    Ok(reminders)
+}
+
+fn add_reminders(reminders: &[Reminder], project: &mut ImportedProject) {
+    let pks: Vec<i64> = reminders.iter().map(|reminder| reminder.pk).collect();
+    let mut children: HashMap<Option<i64>, Vec<&Reminder>> = HashMap::new();
+    for reminder in reminders {
+        let parent = reminder.parent.filter(|parent| pks.contains(parent));
+        children.entry(parent).or_default().push(reminder);
+    }
+
+    let mut lists: Vec<&str> = Vec::new();
+    for reminder in children.get(&None).into_iter().flatten() {
+        if !lists.contains(&reminder.list.as_str()) {
+            lists.push(&reminder.list);
+        }
+    }
+    for list in lists {
+        project.items.push(TodoItem::new(list.to_string(), 0));
+        for reminder in children.get(&None).into_iter().flatten().filter(|reminder| reminder.list == list) {
+            push_reminder(reminder, 1, &children, project);
+        }
+    }
+}
+
+fn push_reminder(
+    reminder: &Reminder,
+    indent_level: usize,
+    children: &HashMap<Option<i64>, Vec<&Reminder>>,
+    project: &mut ImportedProject,
+) {
+    let mut item = TodoItem::new(reminder.title.trim().to_string(), indent_level);
+    if reminder.completed {
+        item.set_state(TodoState::Checked);
+    }
+    item.due_date = reminder
+        .due
+        .and_then(|seconds| DateTime::from_timestamp(CORE_DATA_EPOCH + seconds as i64, 0))
+        .map(|due| due.with_timezone(&Local).date_naive());
+    item.priority = match reminder.priority {
+        1 => Some(Priority::P0),
+        5 => Some(Priority::P1),
+        9 => Some(Priority::P2),
+        _ => None,
+    };
+    item.description = reminder
+        .notes
+        .as_deref()
+        .map(str::trim)
+        .filter(|notes| !notes.is_empty())
+        .map(str::to_string);
+    project.sources.push(ItemSource {
+        todo_id: item.id,
+        importer: IMPORTER,
+        external_id: reminder.id.clone(),
+        data: json!({ "list": reminder.list }),
+    });
+    project.items.push(item);
+
+    for child in children.get(&Some(reminder.pk)).into_iter().flatten() {
+        push_reminder(child, indent_level + 1, children, project);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tempfile::TempDir;
+
+    #[test]
+    fn test_from_stores_nests_reminders_under_their_list() {
+        let temp = TempDir::new().unwrap();
+        let path = temp.path().join("Data-1.sqlite");
+        let conn = Connection::open(&path).unwrap();
+        conn.execute_batch(
+            "CREATE TABLE ZREMCDBASELIST (Z_PK INTEGER PRIMARY KEY, ZNAME TEXT, ZMARKEDFORDELETION INTEGER);
+             CREATE TABLE ZREMCDREMINDER (Z_PK INTEGER PRIMARY KEY, ZCKIDENTIFIER TEXT, ZLIST INTEGER,
+                 ZTITLE TEXT, ZNOTES TEXT, ZCOMPLETED INTEGER, ZDUEDATE REAL, ZPRIORITY INTEGER,
+                 ZPARENTREMINDER INTEGER, ZMARKEDFORDELETION INTEGER);
+             INSERT INTO ZREMCDBASELIST VALUES (1, 'Groceries', 0), (2, 'Old', 1);
+             -- 790_000_000 seconds after 2001-01-01 is 2026-01-14
+             INSERT INTO ZREMCDREMINDER VALUES
+                 (1, 'A', 1, 'Milk', 'Oat', 0, 790000000.0, 1, NULL, 0),
+                 (2, 'B', 1, 'Check the fridge', NULL, 1, NULL, 0, 1, 0),
+                 (3, 'C', 1, 'Deleted', NULL, 0, NULL, 0, NULL, 1),
+                 (4, 'D', 2, 'In a deleted list', NULL, 0, NULL, 0, NULL, 0);",
+        )
+        .unwrap();
+        drop(conn);
+
+        let project = from_stores(&[path.clone(), temp.path().join("missing.sqlite")], "default", false).unwrap();
+        let due = project.items[1].due_date.unwrap();
+        assert!((13..=14).contains(&chrono::Datelike::day(&due)), "{due}");
+        assert_eq!(
+            project.preview().replace(&due.format("%Y-%m-%d").to_string(), "DUE"),
+            "- [ ] Groceries\n  - [ ] Milk @priority(P0) @due(DUE)\n    > Oat\n    - [x] Check the fridge\n"
+        );
+        assert_eq!(project.sources[0].external_id, "A");
+
+        let open = from_stores(&[path], "default", true).unwrap();
+        assert_eq!(open.items.len(), 2);
+    }
+}
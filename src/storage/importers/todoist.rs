@@ -0,0 +1,364 @@
+//! Todoist, read through its API or from a project's CSV export.
+//!
+//! Sections become parent items with their tasks nested beneath, subtasks
+//! stay nested under their task, and Todoist's p1-p3 become P0-P2 (p4, the
+//! default, means no priority). Comments in a CSV export are added to their
+//! task's description.
+
+use super::ImportedProject;
+use crate::todo::{Priority, TodoItem};
+use crate::utils::dateparse::parse_date;
+use anyhow::{Context, Result};
+use chrono::NaiveDate;
+use serde::Deserialize;
+use serde::de::DeserializeOwned;
+use std::collections::HashMap;
+use tracing::debug;
+
+const API_URL: &str = "https://api.todoist.com/api/v1";
+
+#[derive(Debug, Clone, Deserialize)]
+pub struct ApiProject {
+    pub id: String,
+    pub name: String,
+    #[serde(default)]
+    pub child_order: i64,
+}
+
+#[derive(Debug, Clone, Deserialize)]
+pub struct ApiSection {
+    pub id: String,
+    pub project_id: String,
+    pub name: String,
+    #[serde(default)]
+    pub section_order: i64,
+}
+
+#[derive(Debug, Clone, Deserialize)]
+pub struct ApiTask {
+    pub id: String,
+    pub project_id: String,
+    #[serde(default)]
+    pub section_id: Option<String>,
+    #[serde(default)]
+    pub parent_id: Option<String>,
+    pub content: String,
+    #[serde(default)]
+    pub description: String,
+    /// 4 is p1, the most urgent, and 1 is p4
+    #[serde(default = "default_api_priority")]
+    pub priority: u8,
+    #[serde(default)]
+    pub due: Option<ApiDue>,
+    #[serde(default)]
+    pub child_order: i64,
+}
+
+#[derive(Debug, Clone, Deserialize)]
+pub struct ApiDue {
+    /// `YYYY-MM-DD`, with a time after it for tasks due at a time
+    pub date: String,
+}
+
+fn default_api_priority() -> u8 {
+    1
+}
+
+#[derive(Deserialize)]
+struct Page<T> {
+    results: Vec<T>,
+    next_cursor: Option<String>,
+}
+
+/// Fetch all active tasks of the account `token` belongs to.
+pub fn fetch(token: &str) -> Result<Vec<ImportedProject>> {
+    let client = reqwest::blocking::Client::builder()
+        .user_agent("to-tui")
+        .timeout(std::time::Duration::from_secs(30))
+        .build()?;
+
+    let projects = fetch_all(&client, token, "projects")?;
+    let sections = fetch_all(&client, token, "sections")?;
+    let tasks = fetch_all(&client, token, "tasks")?;
+    debug!(
+        projects = projects.len(),
+        sections = sections.len(),
+        tasks = tasks.len(),
+        "Fetched from Todoist"
+    );
+    Ok(from_api(projects, sections, tasks))
+}
+
+fn fetch_all<T: DeserializeOwned>(
+    client: &reqwest::blocking::Client,
+    token: &str,
+    resource: &str,
+) -> Result<Vec<T>> {
+    let url = format!("{API_URL}/{resource}");
+    let mut results = Vec::new();
+    let mut cursor: Option<String> = None;
+    loop {
+        let mut request = client.get(&url).bearer_auth(token).query(&[("limit", "200")]);
+        if let Some(cursor) = &cursor {
+            request = request.query(&[("cursor", cursor)]);
+        }
+        let response = request
+            .send()
+            .with_context(|| format!("Failed to reach Todoist at {url}"))?;
+        if !response.status().is_success() {
+            anyhow::bail!("Todoist returned HTTP {} for {resource}", response.status());
+        }
+        let page: Page<T> = response
+            .json()
+            .with_context(|| format!("Unexpected response from Todoist for {resource}"))?;
+        results.extend(page.results);
+        cursor = page.next_cursor;
+        if cursor.is_none() {
+            return Ok(results);
+        }
+    }
+}
+
+/// Arrange tasks by project: tasks outside any section first, then each
+/// section as a parent of its tasks. Projects without tasks are left out.
+pub fn from_api(
+    mut projects: Vec<ApiProject>,
+    mut sections: Vec<ApiSection>,
+    mut tasks: Vec<ApiTask>,
+) -> Vec<ImportedProject> {
+    projects.sort_by_key(|project| project.child_order);
+    sections.sort_by_key(|section| section.section_order);
+    tasks.sort_by_key(|task| task.child_order);
+
+    let mut children: HashMap<Option<&str>, Vec<&ApiTask>> = HashMap::new();
+    for task in &tasks {
+        children.entry(task.parent_id.as_deref()).or_default().push(task);
+    }
+    let top_level = children.get(&None).cloned().unwrap_or_default();
+
+    projects
+        .iter()
+        .map(|project| {
+            let mut items = Vec::new();
+            let in_section = |section: Option<&str>| -> Vec<&ApiTask> {
+                top_level
+                    .iter()
+                    .filter(|task| task.project_id == project.id && task.section_id.as_deref() == section)
+                    .copied()
+                    .collect()
+            };
+            for task in in_section(None) {
+                push_task(task, 0, &children, &mut items);
+            }
+            for section in sections.iter().filter(|section| section.project_id == project.id) {
+                let tasks = in_section(Some(&section.id));
+                if tasks.is_empty() {
+                    continue;
+                }
+                items.push(TodoItem::new(section.name.clone(), 0));
+                for task in tasks {
+                    push_task(task, 1, &children, &mut items);
+                }
+            }
+            ImportedProject::new(project.name.clone(), items)
+        })
+        .filter(|project| !project.items.is_empty())
+        .collect()
+}
+
+fn push_task(
+    task: &ApiTask,
+    indent_level: usize,
+    children: &HashMap<Option<&str>, Vec<&ApiTask>>,
+    items: &mut Vec<TodoItem>,
+) {
+    let mut item = TodoItem::new(task.content.clone(), indent_level);
+    item.priority = priority_from_api(task.priority);
+    item.due_date = task
+        .due
+        .as_ref()
+        .and_then(|due| NaiveDate::parse_from_str(due.date.get(..10)?, "%Y-%m-%d").ok());
+    if !task.description.trim().is_empty() {
+        item.description = Some(task.description.trim().to_string());
+    }
+    items.push(item);
+
+    for child in children.get(&Some(task.id.as_str())).into_iter().flatten() {
+        push_task(child, indent_level + 1, children, items);
+    }
+}
+
+fn priority_from_api(priority: u8) -> Option<Priority> {
+    match priority {
+        4 => Some(Priority::P0),
+        3 => Some(Priority::P1),
+        2 => Some(Priority::P2),
+        _ => None,
+    }
+}
+
+/// Read a project's CSV export (Todoist's "Export as a template").
+pub fn from_csv(content: &str, project_name: &str) -> Result<ImportedProject> {
+    let mut records = parse_csv(content.trim_start_matches('\u{feff}')).into_iter();
+    let header = records.next().context("The CSV file is empty")?;
+    let column = |name: &str| header.iter().position(|field| field.eq_ignore_ascii_case(name));
+    let kind_column = column("TYPE").context("Not a Todoist CSV export: no TYPE column")?;
+    let content_column = column("CONTENT").context("Not a Todoist CSV export: no CONTENT column")?;
+    let description_column = column("DESCRIPTION");
+    let priority_column = column("PRIORITY");
+    let indent_column = column("INDENT");
+    let date_column = column("DATE");
+
+    let field = |record: &[String], index: Option<usize>| -> String {
+        index
+            .and_then(|index| record.get(index))
+            .map(|value| value.trim().to_string())
+            .unwrap_or_default()
+    };
+
+    let mut items: Vec<TodoItem> = Vec::new();
+    let mut in_section = false;
+    for record in records {
+        let content = field(&record, Some(content_column));
+        match field(&record, Some(kind_column)).to_lowercase().as_str() {
+            "section" if !content.is_empty() => {
+                items.push(TodoItem::new(content, 0));
+                in_section = true;
+            }
+            "task" if !content.is_empty() => {
+                // INDENT counts from 1 for top-level tasks
+                let indent = field(&record, indent_column).parse::<usize>().unwrap_or(1).max(1) - 1;
+                let mut item = TodoItem::new(content, indent + usize::from(in_section));
+                item.priority = priority_from_csv(&field(&record, priority_column));
+                let date = field(&record, date_column);
+                item.due_date = (!date.is_empty()).then(|| parse_date(&date).ok()).flatten();
+                let description = field(&record, description_column);
+                if !description.is_empty() {
+                    item.description = Some(description);
+                }
+                items.push(item);
+            }
+            "note" if !content.is_empty() => {
+                if let Some(task) = items.last_mut() {
+                    task.description = Some(match task.description.take() {
+                        Some(description) => format!("{description}\n{content}"),
+                        None => content,
+                    });
+                }
+            }
+            _ => {}
+        }
+    }
+
+    Ok(ImportedProject::new(project_name, items))
+}
+
+/// CSV exports number priorities as Todoist shows them: 1 is p1.
+fn priority_from_csv(priority: &str) -> Option<Priority> {
+    match priority {
+        "1" => Some(Priority::P0),
+        "2" => Some(Priority::P1),
+        "3" => Some(Priority::P2),
+        _ => None,
+    }
+}
+
+/// Split CSV into records of fields, with quoted fields that may hold
+/// commas, doubled quotes and line breaks.
+fn parse_csv(content: &str) -> Vec<Vec<String>> {
+    let mut records = Vec::new();
+    let mut record = Vec::new();
+    let mut field = String::new();
+    let mut quoted = false;
+    let mut chars = content.chars().peekable();
+    while let Some(c) = chars.next() {
+        match c {
+            '"' if quoted && chars.peek() == Some(&'"') => {
+                field.push('"');
+                chars.next();
+            }
+            '"' => quoted = !quoted,
+            ',' if !quoted => record.push(std::mem::take(&mut field)),
+            '\r' if !quoted => {}
+            '\n' if !quoted => {
+                record.push(std::mem::take(&mut field));
+                records.push(std::mem::take(&mut record));
+            }
+            _ => field.push(c),
+        }
+    }
+    if !field.is_empty() || !record.is_empty() {
+        record.push(field);
+        records.push(record);
+    }
+    records
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_from_csv_maps_sections_priorities_and_notes() {
+        let csv = "\u{feff}TYPE,CONTENT,DESCRIPTION,PRIORITY,INDENT,AUTHOR,RESPONSIBLE,DATE,DATE_LANG,TIMEZONE\r\n\
+            task,Call the bank,,1,1,Ann,,2026-01-20,en,UTC\r\n\
+            ,,,,,,,,,\r\n\
+            section,Groceries,,,,,,,,\r\n\
+            task,\"Milk, oat\",\"Two \"\"big\"\" ones\",4,1,Ann,,,en,UTC\r\n\
+            task,Check the fridge first,,2,2,Ann,,,en,UTC\r\n\
+            note,\"Ask Bob\nabout cheese\",,,,Ann,,,,\r\n";
+
+        let project = from_csv(csv, "Home").unwrap();
+        assert_eq!(project.name, "Home");
+        let items: Vec<(&str, usize)> = project
+            .items
+            .iter()
+            .map(|item| (item.content.as_str(), item.indent_level))
+            .collect();
+        assert_eq!(
+            items,
+            vec![
+                ("Call the bank", 0),
+                ("Groceries", 0),
+                ("Milk, oat", 1),
+                ("Check the fridge first", 2),
+            ]
+        );
+        assert_eq!(project.items[0].priority, Some(Priority::P0));
+        assert_eq!(project.items[0].due_date, NaiveDate::from_ymd_opt(2026, 1, 20));
+        assert_eq!(project.items[2].priority, None);
+        assert_eq!(project.items[2].description.as_deref(), Some("Two \"big\" ones"));
+        assert_eq!(project.items[3].priority, Some(Priority::P1));
+        assert_eq!(project.items[3].description.as_deref(), Some("Ask Bob\nabout cheese"));
+
+        assert!(from_csv("a,b\n", "Home").is_err());
+    }
+
+    #[test]
+    fn test_from_api_nests_sections_and_subtasks() {
+        let projects: Vec<ApiProject> = serde_json::from_str(
+            r#"[{"id": "p2", "name": "Work", "child_order": 2},
+                {"id": "p1", "name": "Inbox", "child_order": 1},
+                {"id": "p3", "name": "Empty", "child_order": 3}]"#,
+        )
+        .unwrap();
+        let sections: Vec<ApiSection> = serde_json::from_str(
+            r#"[{"id": "s1", "project_id": "p2", "name": "Reports", "section_order": 1}]"#,
+        )
+        .unwrap();
+        let tasks: Vec<ApiTask> = serde_json::from_str(
+            r#"[{"id": "t1", "project_id": "p1", "content": "Buy milk", "priority": 1, "child_order": 1},
+                {"id": "t2", "project_id": "p2", "section_id": "s1", "content": "Q1 report",
+                 "priority": 4, "due": {"date": "2026-01-20T09:00:00"}, "child_order": 1},
+                {"id": "t3", "project_id": "p2", "section_id": "s1", "parent_id": "t2",
+                 "content": "Gather numbers", "priority": 3, "child_order": 1},
+                {"id": "t4", "project_id": "p2", "content": "Email Ann", "child_order": 5}]"#,
+        )
+        .unwrap();
+
+        let imported = from_api(projects, sections, tasks);
+        assert_eq!(imported.len(), 2);
+        assert_eq!(imported[0].name, "Inbox");
+        assert_eq!(imported[1].preview(), "- [ ] Email Ann\n- [ ] Reports\n  - [ ] Q1 report @priority(P0) @due(2026-01-20)\n    - [ ] Gather numbers @priority(P1)\n");
+    }
+}
@@ -0,0 +1,313 @@
+//! Consistency checks behind `totui db check`.
+//!
+//! Besides SQLite's own integrity check this looks for what SQLite can't
+//! know is wrong: children whose parent is gone, ids used by both a todo and
+//! an archived todo, and daily files that don't match the database.
+
+use super::conflict::{find_conflict, write_daily_file};
+use super::database::{self, get_connection};
+use super::encryption;
+use super::markdown::daily_file_format;
+use crate::todo::TodoList;
+use crate::utils::paths::get_daily_file_path_for_project;
+use anyhow::Result;
+use chrono::NaiveDate;
+use rusqlite::params;
+use std::fmt;
+use std::path::PathBuf;
+use uuid::Uuid;
+
+/// How a daily file differs from the database.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum FileMismatch {
+    /// The day has todos but no file
+    Missing,
+    /// The file was edited outside totui since it was written
+    EditedOutside,
+    /// The file is out of date, such as after a crash between the two writes
+    Stale,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Problem {
+    /// Reported by `PRAGMA integrity_check`
+    Corrupt(String),
+    /// A todo whose parent isn't a todo of the same day
+    OrphanedChild {
+        project: String,
+        date: NaiveDate,
+        id: String,
+        content: String,
+    },
+    /// An id used by a todo, live or deleted, and by an archived todo
+    DuplicateId {
+        project: String,
+        id: String,
+        content: String,
+    },
+    FileMismatch {
+        project: String,
+        date: NaiveDate,
+        path: PathBuf,
+        mismatch: FileMismatch,
+    },
+}
+
+impl fmt::Display for Problem {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Problem::Corrupt(message) => write!(f, "database corruption: {message}"),
+            Problem::OrphanedChild { project, date, id, content } => write!(
+                f,
+                "{project} {date}: \"{content}\" ({}) has a parent that no longer exists",
+                short(id)
+            ),
+            Problem::DuplicateId { project, id, content } => write!(
+                f,
+                "{project}: \"{content}\" ({}) is both a todo and an archived todo",
+                short(id)
+            ),
+            Problem::FileMismatch { project, date, path, mismatch } => {
+                let what = match mismatch {
+                    FileMismatch::Missing => "is missing",
+                    FileMismatch::EditedOutside => "was edited outside totui",
+                    FileMismatch::Stale => "doesn't match the database",
+                };
+                write!(f, "{project} {date}: {} {what}", path.display())
+            }
+        }
+    }
+}
+
+fn short(id: &str) -> &str {
+    id.get(..8).unwrap_or(id)
+}
+
+impl Problem {
+    /// What `repair` would do, or `None` when it can't be repaired here.
+    pub fn repair_description(&self) -> Option<&'static str> {
+        match self {
+            Problem::Corrupt(_) => None,
+            Problem::OrphanedChild { .. } => Some("reattach it by its indentation"),
+            Problem::DuplicateId { .. } => Some("give the archived todo a new id"),
+            Problem::FileMismatch { mismatch, .. } => Some(match mismatch {
+                FileMismatch::Missing | FileMismatch::Stale => "rewrite the file from the database",
+                FileMismatch::EditedOutside => "merge the file's edits into the database",
+            }),
+        }
+    }
+
+    pub fn repair(&self) -> Result<()> {
+        match self {
+            Problem::Corrupt(message) => {
+                anyhow::bail!("Corruption can't be repaired in place, restore a backup: {message}")
+            }
+            Problem::OrphanedChild { project, date, .. } => {
+                let mut list = load_list(project, *date)?;
+                list.recalculate_parent_ids();
+                database::save_todo_list_for_project(&list, project)
+            }
+            Problem::DuplicateId { id, .. } => {
+                let conn = get_connection()?;
+                conn.execute(
+                    "UPDATE archived_todos SET id = ?1 WHERE id = ?2",
+                    params![Uuid::new_v4().to_string(), id],
+                )?;
+                Ok(())
+            }
+            Problem::FileMismatch { project, date, mismatch, .. } => match mismatch {
+                FileMismatch::EditedOutside => match find_conflict(project, *date)? {
+                    Some(conflict) => conflict.resolve(&conflict.merge()),
+                    None => write_daily_file(&load_list(project, *date)?, project),
+                },
+                FileMismatch::Missing | FileMismatch::Stale => {
+                    write_daily_file(&load_list(project, *date)?, project)
+                }
+            },
+        }
+    }
+}
+
+fn load_list(project: &str, date: NaiveDate) -> Result<TodoList> {
+    let path = get_daily_file_path_for_project(project, date)?;
+    let items = database::load_todos_for_date_and_project(date, project)?;
+    Ok(TodoList::with_items(date, path, items))
+}
+
+/// Run every check, database corruption first.
+pub fn check() -> Result<Vec<Problem>> {
+    let corruption = integrity_check()?;
+    if !corruption.is_empty() {
+        // The rest reads tables that can't be trusted now
+        return Ok(corruption);
+    }
+
+    let mut problems = orphaned_children()?;
+    problems.extend(duplicate_ids()?);
+    problems.extend(file_mismatches()?);
+    Ok(problems)
+}
+
+fn integrity_check() -> Result<Vec<Problem>> {
+    let conn = get_connection()?;
+    let mut stmt = conn.prepare("PRAGMA integrity_check")?;
+    let messages = stmt
+        .query_map([], |row| row.get::<_, String>(0))?
+        .collect::<rusqlite::Result<Vec<_>>>()?;
+    Ok(messages
+        .into_iter()
+        .filter(|message| message != "ok")
+        .map(Problem::Corrupt)
+        .collect())
+}
+
+fn parse_date(date: &str) -> rusqlite::Result<NaiveDate> {
+    NaiveDate::parse_from_str(date, "%Y-%m-%d").map_err(|e| {
+        rusqlite::Error::FromSqlConversionFailure(0, rusqlite::types::Type::Text, Box::new(e))
+    })
+}
+
+fn orphaned_children() -> Result<Vec<Problem>> {
+    let conn = get_connection()?;
+    let mut stmt = conn.prepare(
+        "SELECT child.project, child.date, child.id, child.content
+         FROM todos child
+         WHERE child.deleted_at IS NULL AND child.parent_id IS NOT NULL
+           AND NOT EXISTS (
+               SELECT 1 FROM todos parent
+               WHERE parent.id = child.parent_id AND parent.deleted_at IS NULL
+                 AND parent.project = child.project AND parent.date = child.date
+           )
+         ORDER BY child.project, child.date, child.position",
+    )?;
+    let problems = stmt
+        .query_map([], |row| {
+            Ok(Problem::OrphanedChild {
+                project: row.get(0)?,
+                date: parse_date(&row.get::<_, String>(1)?)?,
+                id: row.get(2)?,
+                content: row.get(3)?,
+            })
+        })?
+        .collect::<rusqlite::Result<Vec<_>>>()?;
+    Ok(problems)
+}
+
+fn duplicate_ids() -> Result<Vec<Problem>> {
+    let conn = get_connection()?;
+    let mut stmt = conn.prepare(
+        "SELECT todos.project, todos.id, todos.content
+         FROM todos JOIN archived_todos ON archived_todos.id = todos.id
+         ORDER BY todos.project, todos.date",
+    )?;
+    let problems = stmt
+        .query_map([], |row| {
+            Ok(Problem::DuplicateId {
+                project: row.get(0)?,
+                id: row.get(1)?,
+                content: row.get(2)?,
+            })
+        })?
+        .collect::<rusqlite::Result<Vec<_>>>()?;
+    Ok(problems)
+}
+
+fn file_mismatches() -> Result<Vec<Problem>> {
+    let days = {
+        let conn = get_connection()?;
+        let mut stmt = conn.prepare(
+            "SELECT DISTINCT project, date FROM todos WHERE deleted_at IS NULL ORDER BY project, date",
+        )?;
+        stmt.query_map([], |row| Ok((row.get::<_, String>(0)?, parse_date(&row.get::<_, String>(1)?)?)))?
+            .collect::<rusqlite::Result<Vec<_>>>()?
+    };
+
+    let writer = daily_file_format().dialect();
+    let mut problems = Vec::new();
+    for (project, date) in days {
+        let list = load_list(&project, date)?;
+        let mismatch = if !list.file_path.exists() {
+            Some(FileMismatch::Missing)
+        } else if find_conflict(&project, date)?.is_some() {
+            Some(FileMismatch::EditedOutside)
+        } else {
+            let content = encryption::read_to_string(&list.file_path)?;
+            (content != writer.serialize(&list)).then_some(FileMismatch::Stale)
+        };
+        if let Some(mismatch) = mismatch {
+            problems.push(Problem::FileMismatch {
+                project,
+                date,
+                path: list.file_path,
+                mismatch,
+            });
+        }
+    }
+    Ok(problems)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::storage::database::init_database;
+    use crate::storage::file::save_todo_list_for_project;
+    use crate::todo::TodoItem;
+    use serial_test::serial;
+    use tempfile::TempDir;
+
+    fn setup() -> TempDir {
+        let temp_dir = TempDir::new().unwrap();
+        std::fs::create_dir_all(temp_dir.path().join(".to-tui")).unwrap();
+        unsafe {
+            std::env::set_var("HOME", temp_dir.path());
+        }
+        init_database().unwrap();
+        temp_dir
+    }
+
+    #[test]
+    #[serial]
+    fn test_check_finds_and_repairs_problems() {
+        let _temp = setup();
+        let date = NaiveDate::from_ymd_opt(2026, 1, 18).unwrap();
+        let path = get_daily_file_path_for_project("default", date).unwrap();
+        let mut list = TodoList::new(date, path.clone());
+        list.add_item("Groceries".to_string());
+        list.items.push(TodoItem::new("Milk".to_string(), 1));
+        list.recalculate_parent_ids();
+        save_todo_list_for_project(&list, "default").unwrap();
+        assert!(check().unwrap().is_empty());
+
+        let conn = get_connection().unwrap();
+        let milk = list.items[1].id.to_string();
+        conn.execute(
+            "UPDATE todos SET parent_id = ?1 WHERE id = ?2",
+            params![Uuid::new_v4().to_string(), milk],
+        )
+        .unwrap();
+        conn.execute(
+            "INSERT INTO archived_todos (id, original_date, archived_at, content, state, indent_level, position, created_at, updated_at, project)
+             VALUES (?1, '2026-01-17', '2026-01-17', 'Milk', ' ', 0, 0, '2026-01-17', '2026-01-17', 'default')",
+            params![milk],
+        )
+        .unwrap();
+        drop(conn);
+        std::fs::remove_file(&path).unwrap();
+
+        let problems = check().unwrap();
+        assert_eq!(problems.len(), 3);
+        assert!(matches!(problems[0], Problem::OrphanedChild { ref content, .. } if content == "Milk"));
+        assert!(matches!(problems[1], Problem::DuplicateId { .. }));
+        assert!(matches!(
+            problems[2],
+            Problem::FileMismatch { mismatch: FileMismatch::Missing, .. }
+        ));
+
+        for problem in &problems {
+            problem.repair().unwrap();
+        }
+        assert!(check().unwrap().is_empty());
+        let items = database::load_todos_for_date_and_project(date, "default").unwrap();
+        assert_eq!(items[1].parent_id, Some(items[0].id));
+    }
+}
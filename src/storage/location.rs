@@ -0,0 +1,237 @@
+//! Moving data when storage locations change.
+//!
+//! The data directory comes from `data_dir` in config.toml or
+//! $XDG_DATA_HOME, falling back to ~/.to-tui (see `utils::paths`). The data
+//! directory in use is remembered in the config directory, so when the
+//! setting changes the data follows it instead of totui starting over empty.
+//! TOTUI_DATA_DIR is taken as-is and never moves anything: it is meant for
+//! pointing a single run somewhere else.
+
+use anyhow::{Context, Result};
+use std::fs;
+use std::path::{Path, PathBuf};
+use tracing::{debug, info, warn};
+
+use crate::config::Config;
+use crate::storage::encryption;
+use crate::storage::markdown::set_daily_file_format;
+use crate::storage::shared;
+use crate::utils::paths::{
+    get_config_dir, get_legacy_to_tui_dir, get_to_tui_dir, set_overrides, DATA_DIR_ENV,
+};
+
+const CONFIG_FILE: &str = "config.toml";
+const DATABASE_FILE: &str = "todos.db";
+/// Records the data directory used by the last run, in the config directory.
+const LAST_DATA_DIR_FILE: &str = ".data_dir";
+
+/// Resolve where this run keeps its files: move a config file left in
+/// ~/.to-tui into the config directory, load the config, apply its storage
+/// locations, daily file format, encryption and shared database settings,
+/// and move the data to the configured data directory.
+///
+/// Call on startup, before anything reads the config or the data directory.
+pub fn init_storage_locations() -> Result<Config> {
+    let config_dir = get_config_dir()?;
+    if move_config_file(&get_legacy_to_tui_dir()?, &config_dir)? {
+        info!(to = %config_dir.display(), "Moved config.toml to the config directory");
+    }
+
+    let config = Config::load()?;
+    set_overrides(config.path_overrides()?);
+    set_daily_file_format(config.daily_file_format);
+    encryption::configure(&config.encryption)?;
+    shared::configure(&config.shared)?;
+
+    if std::env::var_os(DATA_DIR_ENV).is_none() {
+        relocate_data_dir(&config_dir)?;
+    }
+    let data_dir = get_to_tui_dir()?;
+    fs::create_dir_all(&data_dir)
+        .with_context(|| format!("Failed to create data directory {}", data_dir.display()))?;
+    Ok(config)
+}
+
+/// Move the data from the directory the last run used (~/.to-tui for
+/// installations that predate this) to the current data directory.
+fn relocate_data_dir(config_dir: &Path) -> Result<()> {
+    let current = get_to_tui_dir()?;
+    let previous = match last_data_dir(config_dir) {
+        Some(dir) => dir,
+        None => get_legacy_to_tui_dir()?,
+    };
+    if move_data(&previous, &current, config_dir)? {
+        info!(from = %previous.display(), to = %current.display(), "Moved data directory");
+    }
+    if last_data_dir(config_dir).as_deref() != Some(current.as_path())
+        && let Err(e) = remember_data_dir(config_dir, &current)
+    {
+        warn!(error = %e, "Failed to record the data directory");
+    }
+    Ok(())
+}
+
+fn last_data_dir(config_dir: &Path) -> Option<PathBuf> {
+    let recorded = fs::read_to_string(config_dir.join(LAST_DATA_DIR_FILE)).ok()?;
+    let recorded = recorded.trim();
+    (!recorded.is_empty()).then(|| PathBuf::from(recorded))
+}
+
+fn remember_data_dir(config_dir: &Path, data_dir: &Path) -> Result<()> {
+    fs::create_dir_all(config_dir)?;
+    fs::write(
+        config_dir.join(LAST_DATA_DIR_FILE),
+        data_dir.to_string_lossy().as_bytes(),
+    )?;
+    Ok(())
+}
+
+/// Move config.toml from `from` into `to` when `to` has none yet.
+/// Returns whether it moved.
+fn move_config_file(from: &Path, to: &Path) -> Result<bool> {
+    let source = from.join(CONFIG_FILE);
+    let target = to.join(CONFIG_FILE);
+    if same_dir(from, to) || !source.exists() || target.exists() {
+        return Ok(false);
+    }
+    fs::create_dir_all(to).with_context(|| format!("Failed to create {}", to.display()))?;
+    move_path(&source, &target)?;
+    Ok(true)
+}
+
+/// Move everything in `from` into `to`, as long as `from` holds a database
+/// and `to` doesn't: two populated directories are never merged. The config
+/// file stays put when `from` is also the config directory. Returns whether
+/// anything moved.
+fn move_data(from: &Path, to: &Path, config_dir: &Path) -> Result<bool> {
+    if same_dir(from, to) || !from.join(DATABASE_FILE).exists() || to.join(DATABASE_FILE).exists() {
+        return Ok(false);
+    }
+    fs::create_dir_all(to).with_context(|| format!("Failed to create {}", to.display()))?;
+    let keep_config = same_dir(from, config_dir);
+
+    let entries = fs::read_dir(from).with_context(|| format!("Failed to read {}", from.display()))?;
+    for entry in entries {
+        let entry = entry?;
+        let name = entry.file_name();
+        let source = entry.path();
+        if keep_config && (name == CONFIG_FILE || name == LAST_DATA_DIR_FILE) {
+            continue;
+        }
+        // The new directory may live inside the old one
+        if to.starts_with(&source) {
+            continue;
+        }
+        let target = to.join(&name);
+        if target.exists() {
+            debug!(path = %source.display(), "Skipping, already exists in the new data directory");
+            continue;
+        }
+        move_path(&source, &target)?;
+    }
+
+    if fs::read_dir(from).is_ok_and(|mut entries| entries.next().is_none()) {
+        fs::remove_dir(from).ok();
+    }
+    Ok(true)
+}
+
+fn same_dir(a: &Path, b: &Path) -> bool {
+    a == b
+        || matches!(
+            (fs::canonicalize(a), fs::canonicalize(b)),
+            (Ok(a), Ok(b)) if a == b
+        )
+}
+
+/// Rename, or copy and delete when the two paths are on different filesystems.
+fn move_path(from: &Path, to: &Path) -> Result<()> {
+    if fs::rename(from, to).is_ok() {
+        return Ok(());
+    }
+    copy_recursive(from, to)
+        .with_context(|| format!("Failed to move {} to {}", from.display(), to.display()))?;
+    if from.is_dir() {
+        fs::remove_dir_all(from)?;
+    } else {
+        fs::remove_file(from)?;
+    }
+    Ok(())
+}
+
+fn copy_recursive(from: &Path, to: &Path) -> Result<()> {
+    if from.is_dir() {
+        fs::create_dir_all(to)?;
+        for entry in fs::read_dir(from)? {
+            let entry = entry?;
+            copy_recursive(&entry.path(), &to.join(entry.file_name()))?;
+        }
+    } else {
+        fs::copy(from, to)?;
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tempfile::TempDir;
+
+    #[test]
+    fn test_move_data_keeps_config_and_skips_populated_target() {
+        let temp = TempDir::new().unwrap();
+        let old = temp.path().join(".to-tui");
+        let new = temp.path().join("data").join("to-tui");
+        fs::create_dir_all(old.join("projects").join("default").join("dailies")).unwrap();
+        fs::write(old.join(DATABASE_FILE), "db").unwrap();
+        fs::write(old.join(CONFIG_FILE), "theme = \"dark\"").unwrap();
+        fs::write(old.join("projects/default/dailies/2026-01-18.md"), "- [ ] Milk").unwrap();
+
+        assert!(move_data(&old, &new, &old).unwrap());
+        assert_eq!(fs::read_to_string(new.join(DATABASE_FILE)).unwrap(), "db");
+        assert!(new.join("projects/default/dailies/2026-01-18.md").exists());
+        assert!(!new.join(CONFIG_FILE).exists());
+        assert!(old.join(CONFIG_FILE).exists());
+        assert!(!old.join(DATABASE_FILE).exists());
+
+        // Moving back onto a directory that has a database does nothing
+        fs::write(old.join(DATABASE_FILE), "other").unwrap();
+        assert!(!move_data(&old, &new, &old).unwrap());
+        assert_eq!(fs::read_to_string(new.join(DATABASE_FILE)).unwrap(), "db");
+    }
+
+    #[test]
+    fn test_move_data_into_subdirectory() {
+        let temp = TempDir::new().unwrap();
+        let old = temp.path().join(".to-tui");
+        let new = old.join("data");
+        fs::create_dir_all(&old).unwrap();
+        fs::write(old.join(DATABASE_FILE), "db").unwrap();
+
+        assert!(move_data(&old, &new, temp.path()).unwrap());
+        assert!(new.join(DATABASE_FILE).exists());
+        assert!(!old.join(DATABASE_FILE).exists());
+    }
+
+    #[test]
+    fn test_move_config_file() {
+        let temp = TempDir::new().unwrap();
+        let old = temp.path().join(".to-tui");
+        let new = temp.path().join("config").join("to-tui");
+        fs::create_dir_all(&old).unwrap();
+        fs::write(old.join(CONFIG_FILE), "theme = \"dark\"").unwrap();
+
+        assert!(move_config_file(&old, &new).unwrap());
+        assert!(new.join(CONFIG_FILE).exists());
+        assert!(!old.join(CONFIG_FILE).exists());
+        assert!(!move_config_file(&old, &new).unwrap());
+    }
+
+    #[test]
+    fn test_last_data_dir_round_trip() {
+        let temp = TempDir::new().unwrap();
+        assert_eq!(last_data_dir(temp.path()), None);
+        remember_data_dir(temp.path(), Path::new("/srv/todos")).unwrap();
+        assert_eq!(last_data_dir(temp.path()), Some(PathBuf::from("/srv/todos")));
+    }
+}
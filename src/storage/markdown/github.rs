@@ -0,0 +1,77 @@
+//! GitHub task lists: only `[ ]` and `[x]`, which GitHub, Obsidian and most
+//! markdown tools render as checkboxes. Cancelled items are done and struck
+//! through; in-progress, question and important items are written as open.
+
+use super::{parse_todo_list, tag_suffix, title, Dialect, Tags};
+use crate::todo::{TodoList, TodoState};
+use anyhow::Result;
+use chrono::NaiveDate;
+use std::path::PathBuf;
+
+pub struct GithubMarkdown;
+
+impl Dialect for GithubMarkdown {
+    fn extension(&self) -> &'static str {
+        "md"
+    }
+
+    fn serialize(&self, list: &TodoList) -> String {
+        let mut output = format!("# {}\n\n", title(list));
+
+        for item in &list.items {
+            let indent = "  ".repeat(item.indent_level);
+            let (checkbox, content) = match item.state {
+                TodoState::Checked => ('x', item.content.clone()),
+                TodoState::Cancelled => ('x', format!("~~{}~~", item.content)),
+                _ => (' ', item.content.clone()),
+            };
+            output.push_str(&format!(
+                "{indent}- [{checkbox}] {content}{}\n",
+                tag_suffix(item, Tags::ALL)
+            ));
+
+            if let Some(ref desc) = item.description {
+                for line in desc.lines() {
+                    output.push_str(&format!("{indent}  > {line}\n"));
+                }
+            }
+        }
+
+        output
+    }
+
+    fn parse(&self, content: &str, date: NaiveDate, file_path: PathBuf) -> Result<TodoList> {
+        parse_todo_list(content, date, file_path)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_round_trip_keeps_done_and_cancelled() {
+        let date = NaiveDate::from_ymd_opt(2026, 1, 18).unwrap();
+        let mut list = TodoList::new(date, PathBuf::from("/tmp/2026-01-18.md"));
+        list.add_item("Open".to_string());
+        list.add_item_with_indent("Doing".to_string(), 1);
+        list.add_item("Done".to_string());
+        list.add_item("Dropped".to_string());
+        list.items[1].state = TodoState::InProgress;
+        list.items[2].state = TodoState::Checked;
+        list.items[3].state = TodoState::Cancelled;
+
+        let text = GithubMarkdown.serialize(&list);
+        assert!(text.contains("  - [ ] Doing\n"));
+        assert!(text.contains("- [x] ~~Dropped~~\n"));
+
+        let parsed = GithubMarkdown.parse(&text, date, list.file_path.clone()).unwrap();
+        let states: Vec<_> = parsed.items.iter().map(|i| i.state).collect();
+        assert_eq!(
+            states,
+            vec![TodoState::Empty, TodoState::Empty, TodoState::Checked, TodoState::Cancelled]
+        );
+        assert_eq!(parsed.items[3].content, "Dropped");
+        assert_eq!(parsed.items[1].parent_id, Some(parsed.items[0].id));
+    }
+}
@@ -1,36 +1,170 @@
-use crate::todo::{Priority, TodoItem, TodoList, TodoState};
+//! Daily files: each day's list written out in one of several dialects.
+//!
+//! The database is the source of truth; daily files are a readable copy,
+//! read back only for days the database doesn't have. The dialect is set
+//! with `daily_file_format` in the config: to-tui's own markdown (the
+//! default), GitHub task lists, org-mode or todo.txt.
+
+mod github;
+mod org;
+mod todo_txt;
+
+use crate::todo::estimate::extract_estimate_tag;
+use crate::todo::tag::take_tag;
+use crate::todo::{Location, Priority, TodoItem, TodoList, TodoState};
 use anyhow::{anyhow, Result};
 use chrono::NaiveDate;
-use std::path::PathBuf;
+use serde::{Deserialize, Serialize};
+use std::path::{Path, PathBuf};
+use std::sync::RwLock;
+
+pub use github::GithubMarkdown;
+pub use org::OrgMode;
+pub use todo_txt::TodoTxt;
+
+/// The dialect daily files are written in.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "kebab-case")]
+pub enum DailyFileFormat {
+    /// `- [ ]`, `- [x]`, `- [*]`, `- [?]`, `- [!]`, `- [-]` with `@tags`
+    #[default]
+    ToTui,
+    /// Only `[ ]` and `[x]`, which GitHub and most markdown tools render
+    Github,
+    /// Org-mode headings with TODO keywords and DEADLINE lines
+    Org,
+    /// One todo.txt line per item
+    TodoTxt,
+}
 
-pub fn serialize_todo_list_clean(list: &TodoList) -> String {
-    let mut output = String::new();
+impl DailyFileFormat {
+    pub fn dialect(self) -> &'static dyn Dialect {
+        match self {
+            Self::ToTui => &ToTuiMarkdown,
+            Self::Github => &GithubMarkdown,
+            Self::Org => &OrgMode,
+            Self::TodoTxt => &TodoTxt,
+        }
+    }
+}
 
-    output.push_str(&format!(
-        "# Todo List - {}\n\n",
-        list.date.format("%B %d, %Y")
-    ));
+/// How a day's list is written to a daily file and read back from one.
+pub trait Dialect: Sync {
+    /// Extension of daily files in this dialect, without the dot.
+    fn extension(&self) -> &'static str;
 
-    for item in &list.items {
-        let indent = "  ".repeat(item.indent_level);
+    fn serialize(&self, list: &TodoList) -> String;
+
+    fn parse(&self, content: &str, date: NaiveDate, file_path: PathBuf) -> Result<TodoList>;
+}
+
+static DAILY_FILE_FORMAT: RwLock<DailyFileFormat> = RwLock::new(DailyFileFormat::ToTui);
+
+/// Set the dialect new daily files are written in. Called once at startup.
+pub fn set_daily_file_format(format: DailyFileFormat) {
+    *DAILY_FILE_FORMAT.write().unwrap_or_else(|e| e.into_inner()) = format;
+}
+
+pub fn daily_file_format() -> DailyFileFormat {
+    *DAILY_FILE_FORMAT.read().unwrap_or_else(|e| e.into_inner())
+}
+
+/// The dialect to read a daily file with, from its extension. Markdown files
+/// go to the to-tui parser, which reads GitHub task lists as well.
+pub fn dialect_for_path(path: &Path) -> Option<&'static dyn Dialect> {
+    match path.extension()?.to_str()? {
+        "md" => Some(&ToTuiMarkdown),
+        "org" => Some(&OrgMode),
+        "txt" => Some(&TodoTxt),
+        _ => None,
+    }
+}
+
+/// to-tui's own markdown.
+pub struct ToTuiMarkdown;
 
-        let priority_suffix = item
-            .priority
-            .map(|p| format!(" @priority({})", p))
-            .unwrap_or_default();
+impl Dialect for ToTuiMarkdown {
+    fn extension(&self) -> &'static str {
+        "md"
+    }
+
+    fn serialize(&self, list: &TodoList) -> String {
+        serialize_todo_list_clean(list)
+    }
 
-        let due_suffix = item
-            .due_date
-            .map(|d| format!(" @due({})", d.format("%Y-%m-%d")))
-            .unwrap_or_default();
+    fn parse(&self, content: &str, date: NaiveDate, file_path: PathBuf) -> Result<TodoList> {
+        parse_todo_list(content, date, file_path)
+    }
+}
+
+/// Which `@tags` `tag_suffix` writes; dialects with their own syntax for a
+/// field leave its tag out.
+#[derive(Clone, Copy)]
+struct Tags {
+    priority: bool,
+    due: bool,
+}
+
+impl Tags {
+    const ALL: Tags = Tags {
+        priority: true,
+        due: true,
+    };
+}
 
+/// An item's fields as ` @tag(...)` suffixes, in the order to-tui writes them.
+fn tag_suffix(item: &TodoItem, tags: Tags) -> String {
+    let mut suffix = String::new();
+    if item.pinned {
+        suffix.push_str(" @pinned");
+    }
+    if let Some(url) = &item.link {
+        suffix.push_str(&format!(" @link({url})"));
+    }
+    if let Some(location) = &item.location {
+        suffix.push_str(&format!(" @at({location})"));
+    }
+    if tags.priority
+        && let Some(priority) = item.priority
+    {
+        suffix.push_str(&format!(" @priority({priority})"));
+    }
+    if let Some(estimate) = item.estimate {
+        suffix.push_str(&format!(" @est({})", estimate.to_tag_str()));
+    }
+    if let Some(who) = &item.waiting_on {
+        suffix.push_str(&format!(" @waiting({who})"));
+    }
+    if tags.due
+        && let Some(due) = item.due_date
+    {
+        suffix.push_str(&format!(" @due({})", due.format("%Y-%m-%d")));
+    }
+    suffix
+}
+
+fn title(list: &TodoList) -> String {
+    format!("Todo List - {}", list.date.format("%B %d, %Y"))
+}
+
+pub fn serialize_todo_list_clean(list: &TodoList) -> String {
+    let mut output = format!("# {}\n\n", title(list));
+    output.push_str(&serialize_items(&list.items));
+    output
+}
+
+/// Items as to-tui markdown task lines, without a title.
+pub fn serialize_items(items: &[TodoItem]) -> String {
+    let mut output = String::new();
+
+    for item in items {
+        let indent = "  ".repeat(item.indent_level);
         output.push_str(&format!(
-            "{}- [{}] {}{}{}\n",
+            "{}- [{}] {}{}\n",
             indent,
             item.state.to_char(),
             item.content,
-            priority_suffix,
-            due_suffix
+            tag_suffix(item, Tags::ALL)
         ));
 
         if let Some(ref desc) = item.description {
@@ -69,9 +203,7 @@ pub fn parse_todo_list(content: &str, date: NaiveDate, file_path: PathBuf) -> Re
                 last_item.description = Some(desc);
             }
 
-        if let Some(mut item) = parse_todo_line(line)? {
-            let parent_id = find_parent_id(&items, item.indent_level);
-            item.parent_id = parent_id;
+        if let Some(item) = parse_todo_line(line)? {
             items.push(item);
         }
     }
@@ -81,9 +213,18 @@ pub fn parse_todo_list(content: &str, date: NaiveDate, file_path: PathBuf) -> Re
             last_item.description = Some(desc);
         }
 
+    attach_parents(&mut items);
     Ok(TodoList::with_items(date, file_path, items))
 }
 
+/// Set each item's parent from the indent levels.
+fn attach_parents(items: &mut [TodoItem]) {
+    for index in 0..items.len() {
+        let parent_id = find_parent_id(&items[..index], items[index].indent_level);
+        items[index].parent_id = parent_id;
+    }
+}
+
 fn find_parent_id(items: &[TodoItem], indent_level: usize) -> Option<uuid::Uuid> {
     if indent_level == 0 {
         return None;
@@ -103,7 +244,8 @@ fn parse_todo_line(line: &str) -> Result<Option<TodoItem>> {
 
     let trimmed = line.trim_start();
 
-    if !trimmed.starts_with("- [") {
+    // `*` and `+` bullets are markdown too, as other tools write them
+    if !["- [", "* [", "+ ["].iter().any(|bullet| trimmed.starts_with(bullet)) {
         return Ok(None);
     }
 
@@ -124,17 +266,41 @@ fn parse_todo_line(line: &str) -> Result<Option<TodoItem>> {
         ""
     };
 
-    let (content, id) = parse_id(raw_content);
+    // GitHub flavor writes cancelled items as struck-through done ones
+    if state == TodoState::Checked
+        && let Some(inner) = raw_content.strip_prefix("~~")
+        && let Some(end) = inner.find("~~")
+    {
+        let text = format!("{}{}", &inner[..end], &inner[end + 2..]);
+        return Ok(Some(item_from_text(&text, TodoState::Cancelled, indent_level)));
+    }
+
+    Ok(Some(item_from_text(raw_content, state, indent_level)))
+}
+
+/// Build an item from its text, taking its fields from the `@tags` in it.
+fn item_from_text(text: &str, state: TodoState, indent_level: usize) -> TodoItem {
+    let (content, id) = parse_id(text.trim());
+    let (content, link) = parse_link(&content);
+    let (content, location) = parse_location(&content);
     let (content, due_date) = parse_due_date(&content);
     let (content, priority) = parse_priority(&content);
+    let (content, estimate) = extract_estimate_tag(&content);
+    let (content, waiting_on) = parse_waiting(&content);
+    let (content, pinned) = parse_pinned(&content);
 
     let mut item = TodoItem::full(content, state, indent_level, None, due_date, None, priority, false);
+    item.estimate = estimate;
+    item.waiting_on = waiting_on;
+    item.pinned = pinned;
+    item.link = link;
+    item.location = location;
 
     if let Some(parsed_id) = id {
         item.id = parsed_id;
     }
 
-    Ok(Some(item))
+    item
 }
 
 fn parse_id(content: &str) -> (String, Option<uuid::Uuid>) {
@@ -203,9 +369,67 @@ fn parse_priority(content: &str) -> (String, Option<Priority>) {
     (content.to_string(), None)
 }
 
+/// Strip a `@waiting(name)` tag, returning who the item is delegated to.
+pub fn parse_waiting(content: &str) -> (String, Option<String>) {
+    match take_tag(content, "waiting") {
+        Some((cleaned, who)) => {
+            let who = who.trim();
+            (cleaned, (!who.is_empty()).then(|| who.to_string()))
+        }
+        None => (content.to_string(), None),
+    }
+}
+
+/// Strip a `@link(url)` tag, returning the URL. Parentheses inside the URL
+/// are matched, so links like `.../Rust_(programming_language)` survive.
+fn parse_link(content: &str) -> (String, Option<String>) {
+    match take_tag(content, "link") {
+        Some((cleaned, url)) => {
+            let url = url.trim();
+            (cleaned, (!url.is_empty()).then(|| url.to_string()))
+        }
+        None => (content.to_string(), None),
+    }
+}
+
+/// Strip an `@at(path:line)` tag, returning the location. A tag that isn't
+/// a location, like `@at(noon)`, is left in the text as written.
+fn parse_location(content: &str) -> (String, Option<Location>) {
+    match take_tag(content, "at") {
+        Some((cleaned, value)) => match Location::parse(value) {
+            Some(location) => (cleaned, Some(location)),
+            None => (content.to_string(), None),
+        },
+        None => (content.to_string(), None),
+    }
+}
+
+/// Strip a standalone `@pinned` tag, returning whether it was present.
+fn parse_pinned(content: &str) -> (String, bool) {
+    const TAG: &str = "@pinned";
+    let found = content.match_indices(TAG).find(|(start, _)| {
+        let after = &content[start + TAG.len()..];
+        after.is_empty() || after.starts_with(' ')
+    });
+    if let Some((start, _)) = found {
+        let mut cleaned = String::new();
+        cleaned.push_str(content[..start].trim());
+        let suffix = content[start + TAG.len()..].trim();
+        if !suffix.is_empty() {
+            if !cleaned.is_empty() {
+                cleaned.push(' ');
+            }
+            cleaned.push_str(suffix);
+        }
+        return (cleaned, true);
+    }
+    (content.to_string(), false)
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::todo::Estimate;
     use chrono::NaiveDate;
 
     fn create_test_date() -> NaiveDate {
@@ -465,4 +689,135 @@ Empty line above
         assert_eq!(parsed.items[0].priority, Some(Priority::P0));
         assert_eq!(parsed.items[0].due_date, Some(NaiveDate::from_ymd_opt(2026, 1, 15).unwrap()));
     }
+
+    #[test]
+    fn test_estimate_round_trip() {
+        let date = create_test_date();
+        let path = create_test_path();
+        let mut list = TodoList::new(date, path.clone());
+
+        list.add_item("Write report".to_string());
+        list.add_item("No estimate".to_string());
+        list.items[0].priority = Some(Priority::P1);
+        list.items[0].estimate = Some(Estimate::from_minutes(90));
+        list.items[0].due_date = Some(NaiveDate::from_ymd_opt(2026, 1, 15).unwrap());
+
+        let markdown = serialize_todo_list_clean(&list);
+        assert!(markdown.contains("- [ ] Write report @priority(P1) @est(1h30m) @due(2026-01-15)\n"));
+        assert!(markdown.contains("- [ ] No estimate\n"));
+
+        let parsed = parse_todo_list(&markdown, date, path).unwrap();
+        assert_eq!(parsed.items[0].content, "Write report");
+        assert_eq!(parsed.items[0].estimate, Some(Estimate::from_minutes(90)));
+        assert_eq!(parsed.items[1].estimate, None);
+    }
+
+    #[test]
+    fn test_unparseable_estimate_stays_in_the_text() {
+        let markdown = "# Todo List - 2025-12-31\n\n- [ ] Write report @est(soon)\n";
+        let parsed = parse_todo_list(markdown, create_test_date(), create_test_path()).unwrap();
+        assert_eq!(parsed.items[0].content, "Write report @est(soon)");
+        assert_eq!(parsed.items[0].estimate, None);
+    }
+
+    #[test]
+    fn test_waiting_round_trip() {
+        let date = create_test_date();
+        let path = create_test_path();
+        let mut list = TodoList::new(date, path.clone());
+
+        list.add_item("Review contract".to_string());
+        list.items[0].waiting_on = Some("alice".to_string());
+        list.items[0].due_date = Some(NaiveDate::from_ymd_opt(2026, 1, 15).unwrap());
+
+        let markdown = serialize_todo_list_clean(&list);
+        assert!(markdown.contains("- [ ] Review contract @waiting(alice) @due(2026-01-15)\n"));
+
+        let parsed = parse_todo_list(&markdown, date, path).unwrap();
+        assert_eq!(parsed.items[0].content, "Review contract");
+        assert_eq!(parsed.items[0].waiting_on.as_deref(), Some("alice"));
+    }
+
+    #[test]
+    fn test_pinned_round_trip() {
+        let date = create_test_date();
+        let path = create_test_path();
+        let mut list = TodoList::new(date, path.clone());
+
+        list.add_item("Ship release".to_string());
+        list.add_item("Read @pinnedtweets".to_string());
+        list.items[0].pinned = true;
+        list.items[0].priority = Some(Priority::P1);
+
+        let markdown = serialize_todo_list_clean(&list);
+        assert!(markdown.contains("- [ ] Ship release @pinned @priority(P1)\n"));
+
+        let parsed = parse_todo_list(&markdown, date, path).unwrap();
+        assert_eq!(parsed.items[0].content, "Ship release");
+        assert!(parsed.items[0].pinned);
+        assert_eq!(parsed.items[1].content, "Read @pinnedtweets");
+        assert!(!parsed.items[1].pinned);
+    }
+
+    #[test]
+    fn test_location_round_trip() {
+        let date = create_test_date();
+        let path = create_test_path();
+        let mut list = TodoList::new(date, path.clone());
+
+        list.add_item("Fix the parser".to_string());
+        list.items[0].location = Location::parse("/home/me/src (old)/lib.rs:7");
+
+        let markdown = serialize_todo_list_clean(&list);
+        assert!(markdown.contains("- [ ] Fix the parser @at(/home/me/src (old)/lib.rs:7)\n"));
+
+        let parsed = parse_todo_list(&markdown, date, path).unwrap();
+        assert_eq!(parsed.items[0].content, "Fix the parser");
+        assert_eq!(parsed.items[0].location, list.items[0].location);
+    }
+
+    #[test]
+    fn test_unparseable_location_stays_in_the_text() {
+        let date = create_test_date();
+        let path = create_test_path();
+        let mut list = TodoList::new(date, path.clone());
+        list.add_item("Lunch @at(noon)".to_string());
+
+        let markdown = serialize_todo_list_clean(&list);
+        let parsed = parse_todo_list(&markdown, date, path).unwrap();
+        assert_eq!(parsed.items[0].content, "Lunch @at(noon)");
+        assert_eq!(parsed.items[0].location, None);
+        assert_eq!(serialize_todo_list_clean(&parsed), markdown);
+    }
+
+    #[test]
+    fn test_link_round_trip() {
+        let date = create_test_date();
+        let path = create_test_path();
+        let mut list = TodoList::new(date, path.clone());
+
+        list.add_item("Review PR".to_string());
+        list.items[0].link = Some("https://github.com/org/repo/pull/7?tab=files".to_string());
+        list.items[0].priority = Some(Priority::P0);
+        list.add_item("Read up".to_string());
+        list.items[1].link = Some("https://en.wikipedia.org/wiki/Rust_(programming_language)".to_string());
+
+        let markdown = serialize_todo_list_clean(&list);
+        assert!(markdown.contains(
+            "- [ ] Review PR @link(https://github.com/org/repo/pull/7?tab=files) @priority(P0)\n"
+        ));
+
+        let parsed = parse_todo_list(&markdown, date, path).unwrap();
+        assert_eq!(parsed.items[0].content, "Review PR");
+        assert_eq!(parsed.items[0].priority, Some(Priority::P0));
+        assert_eq!(
+            parsed.items[0].link.as_deref(),
+            Some("https://github.com/org/repo/pull/7?tab=files")
+        );
+        assert_eq!(parsed.items[1].content, "Read up");
+        assert_eq!(
+            parsed.items[1].link.as_deref(),
+            Some("https://en.wikipedia.org/wiki/Rust_(programming_language)")
+        );
+    }
 }
@@ -0,0 +1,138 @@
+//! Org-mode: one heading per item, nested by level, with TODO keywords for
+//! the states and a DEADLINE line for the due date. The file declares the
+//! keywords it uses, so Emacs cycles through them like to-tui does.
+
+use super::{attach_parents, item_from_text, tag_suffix, title, Dialect, Tags};
+use crate::todo::{TodoItem, TodoList, TodoState};
+use anyhow::Result;
+use chrono::NaiveDate;
+use std::path::PathBuf;
+
+const KEYWORDS: [(TodoState, &str); 6] = [
+    (TodoState::Empty, "TODO"),
+    (TodoState::InProgress, "STARTED"),
+    (TodoState::Question, "QUESTION"),
+    (TodoState::Exclamation, "IMPORTANT"),
+    (TodoState::Checked, "DONE"),
+    (TodoState::Cancelled, "CANCELLED"),
+];
+
+fn keyword(state: TodoState) -> &'static str {
+    KEYWORDS
+        .iter()
+        .find(|(s, _)| *s == state)
+        .map(|(_, keyword)| *keyword)
+        .unwrap_or("TODO")
+}
+
+pub struct OrgMode;
+
+impl Dialect for OrgMode {
+    fn extension(&self) -> &'static str {
+        "org"
+    }
+
+    fn serialize(&self, list: &TodoList) -> String {
+        let mut output = format!(
+            "#+TITLE: {}\n#+TODO: TODO STARTED QUESTION IMPORTANT | DONE CANCELLED\n\n",
+            title(list)
+        );
+
+        for item in &list.items {
+            let stars = "*".repeat(item.indent_level + 1);
+            let body_indent = " ".repeat(item.indent_level + 2);
+            output.push_str(&format!(
+                "{stars} {} {}{}\n",
+                keyword(item.state),
+                item.content,
+                tag_suffix(item, Tags { priority: true, due: false })
+            ));
+            if let Some(due) = item.due_date {
+                output.push_str(&format!("{body_indent}DEADLINE: <{}>\n", due.format("%Y-%m-%d %a")));
+            }
+            if let Some(ref desc) = item.description {
+                for line in desc.lines() {
+                    output.push_str(&format!("{body_indent}{line}\n"));
+                }
+            }
+        }
+
+        output
+    }
+
+    fn parse(&self, content: &str, date: NaiveDate, file_path: PathBuf) -> Result<TodoList> {
+        let mut items: Vec<TodoItem> = Vec::new();
+
+        for line in content.lines() {
+            if line.trim().is_empty() || line.starts_with("#+") {
+                continue;
+            }
+
+            let stars = line.len() - line.trim_start_matches('*').len();
+            if stars > 0 && line[stars..].starts_with(' ') {
+                let text = line[stars..].trim();
+                let (word, rest) = text.split_once(' ').unwrap_or((text, ""));
+                let (state, text) = match KEYWORDS.iter().find(|(_, keyword)| *keyword == word) {
+                    Some((state, _)) => (*state, rest),
+                    None => (TodoState::Empty, text),
+                };
+                items.push(item_from_text(text, state, stars - 1));
+                continue;
+            }
+
+            // Body lines belong to the heading above them
+            let Some(item) = items.last_mut() else {
+                continue;
+            };
+            let body = line.trim();
+            if let Some(at) = body.find("DEADLINE: <") {
+                let date = body.get(at + 11..at + 21).unwrap_or_default();
+                item.due_date = NaiveDate::parse_from_str(date, "%Y-%m-%d").ok();
+            } else if !body.starts_with("SCHEDULED:") && !body.starts_with("CLOSED:") {
+                match item.description.as_mut() {
+                    Some(desc) => {
+                        desc.push('\n');
+                        desc.push_str(body);
+                    }
+                    None => item.description = Some(body.to_string()),
+                }
+            }
+        }
+
+        attach_parents(&mut items);
+        Ok(TodoList::with_items(date, file_path, items))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::todo::Priority;
+
+    #[test]
+    fn test_round_trip() {
+        let date = NaiveDate::from_ymd_opt(2026, 1, 18).unwrap();
+        let mut list = TodoList::new(date, PathBuf::from("/tmp/2026-01-18.org"));
+        list.add_item("Plan trip".to_string());
+        list.add_item_with_indent("Book hotel".to_string(), 1);
+        list.items[0].state = TodoState::InProgress;
+        list.items[0].priority = Some(Priority::P1);
+        list.items[0].description = Some("Two nights\nNear the station".to_string());
+        list.items[1].state = TodoState::Checked;
+        list.items[1].due_date = NaiveDate::from_ymd_opt(2026, 1, 20);
+
+        let text = OrgMode.serialize(&list);
+        assert!(text.contains("* STARTED Plan trip @priority(P1)\n"));
+        assert!(text.contains("** DONE Book hotel\n   DEADLINE: <2026-01-20 Tue>\n"));
+
+        let parsed = OrgMode.parse(&text, date, list.file_path.clone()).unwrap();
+        assert_eq!(parsed.items.len(), 2);
+        assert_eq!(parsed.items[0].state, TodoState::InProgress);
+        assert_eq!(parsed.items[0].priority, Some(Priority::P1));
+        assert_eq!(parsed.items[0].description.as_deref(), Some("Two nights\nNear the station"));
+        assert_eq!(parsed.items[1].content, "Book hotel");
+        assert_eq!(parsed.items[1].indent_level, 1);
+        assert_eq!(parsed.items[1].due_date, NaiveDate::from_ymd_opt(2026, 1, 20));
+        assert_eq!(parsed.items[1].parent_id, Some(parsed.items[0].id));
+    }
+}
@@ -0,0 +1,177 @@
+//! todo.txt: one line per item, `x` and a completion date for finished ones,
+//! `(A)` for the most urgent priority level, `(B)` for the next, and so on.
+//! The format has no nesting or notes, so subtasks carry an `indent:` key and
+//! descriptions stay in the database only.
+
+use super::{attach_parents, item_from_text, tag_suffix, Dialect, Tags};
+use crate::todo::{Priority, TodoItem, TodoList, TodoState};
+use anyhow::Result;
+use chrono::{Local, NaiveDate};
+use std::path::PathBuf;
+
+/// `state:` values for the states todo.txt has no syntax for.
+const STATE_WORDS: [(TodoState, &str); 4] = [
+    (TodoState::InProgress, "started"),
+    (TodoState::Question, "question"),
+    (TodoState::Exclamation, "important"),
+    (TodoState::Cancelled, "cancelled"),
+];
+
+fn priority_letter(priority: Priority) -> char {
+    (b'A' + priority.level()) as char
+}
+
+fn letter_priority(letter: char) -> Option<Priority> {
+    letter
+        .is_ascii_uppercase()
+        .then(|| Priority::new(letter as u8 - b'A'))
+        .flatten()
+}
+
+fn is_date(word: &str) -> bool {
+    NaiveDate::parse_from_str(word, "%Y-%m-%d").is_ok()
+}
+
+pub struct TodoTxt;
+
+impl Dialect for TodoTxt {
+    fn extension(&self) -> &'static str {
+        "txt"
+    }
+
+    fn serialize(&self, list: &TodoList) -> String {
+        let mut output = String::new();
+
+        for item in &list.items {
+            let created = item.created_at.with_timezone(&Local).date_naive();
+            let done = item.is_complete();
+            let mut line = if done {
+                let completed = item
+                    .completed_at
+                    .map(|at| at.with_timezone(&Local).date_naive())
+                    .unwrap_or(list.date);
+                format!("x {} {} ", completed.format("%Y-%m-%d"), created.format("%Y-%m-%d"))
+            } else {
+                let priority = item
+                    .priority
+                    .map(|p| format!("({}) ", priority_letter(p)))
+                    .unwrap_or_default();
+                format!("{priority}{} ", created.format("%Y-%m-%d"))
+            };
+
+            line.push_str(&item.content);
+            line.push_str(&tag_suffix(item, Tags { priority: false, due: false }));
+            if let Some(due) = item.due_date {
+                line.push_str(&format!(" due:{}", due.format("%Y-%m-%d")));
+            }
+            // Finished lines start with `x`, so their priority goes in a key
+            if done && let Some(priority) = item.priority {
+                line.push_str(&format!(" pri:{}", priority_letter(priority)));
+            }
+            if item.indent_level > 0 {
+                line.push_str(&format!(" indent:{}", item.indent_level));
+            }
+            if let Some((_, word)) = STATE_WORDS.iter().find(|(state, _)| *state == item.state) {
+                line.push_str(&format!(" state:{word}"));
+            }
+
+            output.push_str(&line);
+            output.push('\n');
+        }
+
+        output
+    }
+
+    fn parse(&self, content: &str, date: NaiveDate, file_path: PathBuf) -> Result<TodoList> {
+        let mut items: Vec<TodoItem> = content.lines().filter_map(parse_line).collect();
+        attach_parents(&mut items);
+        Ok(TodoList::with_items(date, file_path, items))
+    }
+}
+
+fn parse_line(line: &str) -> Option<TodoItem> {
+    let mut words = line.split_whitespace().peekable();
+    words.peek()?;
+
+    let done = words.next_if_eq(&"x").is_some();
+    let mut priority = None;
+    if !done
+        && let Some(word) = words.peek()
+        && let [b'(', letter, b')'] = word.as_bytes()
+    {
+        priority = letter_priority(*letter as char);
+        words.next();
+    }
+    // Completion and creation dates
+    for _ in 0..if done { 2 } else { 1 } {
+        words.next_if(|word| is_date(word));
+    }
+
+    let mut text = Vec::new();
+    let mut due_date = None;
+    let mut indent_level = 0;
+    let mut state = if done { TodoState::Checked } else { TodoState::Empty };
+    for word in words {
+        match word.split_once(':') {
+            Some(("due", value)) if is_date(value) => {
+                due_date = NaiveDate::parse_from_str(value, "%Y-%m-%d").ok();
+            }
+            Some(("pri", value)) if value.len() == 1 => {
+                priority = value.chars().next().and_then(letter_priority);
+            }
+            Some(("indent", value)) => match value.parse() {
+                Ok(level) => indent_level = level,
+                Err(_) => text.push(word),
+            },
+            Some(("state", value)) => match STATE_WORDS.iter().find(|(_, w)| *w == value) {
+                Some((named, _)) => state = *named,
+                None => text.push(word),
+            },
+            _ => text.push(word),
+        }
+    }
+
+    let mut item = item_from_text(&text.join(" "), state, indent_level);
+    item.priority = item.priority.or(priority);
+    item.due_date = item.due_date.or(due_date);
+    Some(item)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_round_trip() {
+        let date = NaiveDate::from_ymd_opt(2026, 1, 18).unwrap();
+        let mut list = TodoList::new(date, PathBuf::from("/tmp/2026-01-18.txt"));
+        list.add_item("Call the bank".to_string());
+        list.add_item_with_indent("Find the account number".to_string(), 1);
+        list.add_item("Send invoice".to_string());
+        list.items[0].waiting_on = Some("Sam".to_string());
+        list.items[0].priority = Some(Priority::P1);
+        list.items[0].due_date = NaiveDate::from_ymd_opt(2026, 1, 20);
+        list.items[1].state = TodoState::Question;
+        list.items[2].state = TodoState::Checked;
+        list.items[2].priority = Some(Priority::P0);
+
+        let text = TodoTxt.serialize(&list);
+        let lines: Vec<_> = text.lines().collect();
+        assert!(lines[0].starts_with("(B) "));
+        assert!(lines[0].ends_with("Call the bank @waiting(Sam) due:2026-01-20"));
+        assert!(lines[1].ends_with("Find the account number indent:1 state:question"));
+        assert!(lines[2].starts_with("x "));
+        assert!(lines[2].ends_with("Send invoice pri:A"));
+
+        let parsed = TodoTxt.parse(&text, date, list.file_path.clone()).unwrap();
+        let first = &parsed.items[0];
+        assert_eq!(first.content, "Call the bank");
+        assert_eq!(first.priority, Some(Priority::P1));
+        assert_eq!(first.waiting_on.as_deref(), Some("Sam"));
+        assert_eq!(first.due_date, NaiveDate::from_ymd_opt(2026, 1, 20));
+        assert_eq!(parsed.items[1].state, TodoState::Question);
+        assert_eq!(parsed.items[1].parent_id, Some(first.id));
+        assert_eq!(parsed.items[2].state, TodoState::Checked);
+        assert_eq!(parsed.items[2].priority, Some(Priority::P0));
+    }
+}
@@ -126,6 +126,33 @@ pub fn get_todo_metadata(todo_id: &Uuid, plugin_name: &str) -> Result<String> {
     }
 }
 
+/// One plugin's metadata on a todo item.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct PluginMetadata {
+    pub plugin_name: String,
+    /// JSON object as stored by the plugin
+    pub data: String,
+    pub external_id: Option<String>,
+}
+
+/// Get the metadata every plugin has stored for a todo item, sorted by plugin name.
+pub fn get_all_todo_metadata(todo_id: &Uuid) -> Result<Vec<PluginMetadata>> {
+    let conn = get_connection()?;
+    let mut stmt = conn.prepare(
+        "SELECT plugin_name, data, external_id FROM todo_metadata
+         WHERE todo_id = ?1 ORDER BY plugin_name",
+    )?;
+    let rows = stmt.query_map(params![todo_id.to_string()], |row| {
+        Ok(PluginMetadata {
+            plugin_name: row.get(0)?,
+            data: row.get(1)?,
+            external_id: row.get(2)?,
+        })
+    })?;
+    rows.collect::<rusqlite::Result<Vec<_>>>()
+        .context("Failed to read todo metadata")
+}
+
 /// Delete metadata for a todo item.
 ///
 /// Returns true if metadata was deleted, false if it didn't exist.
@@ -380,6 +407,23 @@ mod tests {
         assert_eq!(result, data);
     }
 
+    #[test]
+    #[serial]
+    fn test_get_all_todo_metadata() {
+        let _temp = setup_test_env();
+        let todo_id = Uuid::new_v4();
+
+        set_todo_metadata(&todo_id, "jira", r#"{"status": "open"}"#, false).unwrap();
+        set_external_id(&todo_id, "github", "issue-42").unwrap();
+        set_todo_metadata(&Uuid::new_v4(), "jira", r#"{"other": 1}"#, false).unwrap();
+
+        let all = get_all_todo_metadata(&todo_id).unwrap();
+        assert_eq!(all.len(), 2);
+        assert_eq!(all[0].plugin_name, "github");
+        assert_eq!(all[0].external_id.as_deref(), Some("issue-42"));
+        assert_eq!(all[1].data, r#"{"status": "open"}"#);
+    }
+
     #[test]
     #[serial]
     fn test_get_todo_metadata_returns_empty_for_nonexistent() {
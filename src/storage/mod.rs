@@ -1,12 +1,30 @@
+pub mod archive_compaction;
+pub mod archive_export;
+pub mod attachments;
+pub mod backup;
+pub mod conflict;
 pub mod database;
+pub mod encryption;
 pub mod file;
+pub mod history;
+pub mod idempotency;
+pub mod importers;
+pub mod integrity;
+pub mod location;
 pub mod markdown;
 pub mod metadata;
 pub mod migration;
+pub mod revision;
 pub mod rollover;
+pub mod shared;
+pub mod snapshot;
+pub mod sync;
 pub mod ui_cache;
+pub mod undo_history;
+pub mod watcher;
 
-pub use database::{load_archived_todos_for_date_and_project, soft_delete_todos_for_project};
+pub use database::load_archived_todos_for_date_and_project;
+pub use file::soft_delete_todos_for_project;
 pub use migration::ensure_installation_ready;
 pub use rollover::{execute_rollover_for_project, find_rollover_candidates_for_project};
-pub use ui_cache::UiCache;
+pub use ui_cache::{CachedView, UiCache};
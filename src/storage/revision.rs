@@ -0,0 +1,365 @@
+//! Per-day revision numbers, so writers that hold a day's list for a while
+//! (the TUI between saves, an API client between reading and writing) can
+//! tell whether someone else saved the day in the meantime.
+//!
+//! Every write to a day's rows in `todos` bumps its revision. Triggers do the
+//! bumping, so writes from any process are covered. A save made against the
+//! revision the list was loaded at fails with `StaleDay` if the day has moved
+//! on, and the writer settles the two versions with `StaleSave` instead of
+//! overwriting the other one.
+
+use super::database::get_connection;
+use super::shared;
+use crate::todo::{TodoItem, TodoList};
+use anyhow::Result;
+use chrono::{NaiveDate, Utc};
+use rusqlite::{params, Connection, OptionalExtension};
+use std::collections::{HashMap, HashSet};
+use std::fmt;
+use uuid::Uuid;
+
+/// The revisions and the triggers keeping them. A row moved to another day
+/// bumps both days.
+pub(crate) const REVISION_SCHEMA: &str = "
+    CREATE TABLE IF NOT EXISTS day_revisions (
+        project TEXT NOT NULL,
+        date TEXT NOT NULL,
+        revision INTEGER NOT NULL,
+        PRIMARY KEY (project, date)
+    );
+
+    CREATE TRIGGER IF NOT EXISTS todos_revision_insert AFTER INSERT ON todos BEGIN
+        INSERT INTO day_revisions (project, date, revision) VALUES (new.project, new.date, 1)
+            ON CONFLICT (project, date) DO UPDATE SET revision = revision + 1;
+    END;
+    CREATE TRIGGER IF NOT EXISTS todos_revision_update AFTER UPDATE ON todos BEGIN
+        INSERT INTO day_revisions (project, date, revision) VALUES (new.project, new.date, 1)
+            ON CONFLICT (project, date) DO UPDATE SET revision = revision + 1;
+        INSERT INTO day_revisions (project, date, revision)
+            SELECT old.project, old.date, 1
+            WHERE old.project IS NOT new.project OR old.date IS NOT new.date
+            ON CONFLICT (project, date) DO UPDATE SET revision = revision + 1;
+    END;
+    CREATE TRIGGER IF NOT EXISTS todos_revision_delete AFTER DELETE ON todos BEGIN
+        INSERT INTO day_revisions (project, date, revision) VALUES (old.project, old.date, 1)
+            ON CONFLICT (project, date) DO UPDATE SET revision = revision + 1;
+    END;
+";
+
+/// The day's revision, in the shared database when there is one. Days
+/// never written are at 0.
+pub fn day_revision(date: NaiveDate, project_name: &str) -> Result<u64> {
+    if let Some(store) = shared::store() {
+        return store.day_revision(project_name, date);
+    }
+    let conn = get_connection()?;
+    revision_on(&conn, date, project_name)
+}
+
+pub(crate) fn revision_on(conn: &Connection, date: NaiveDate, project_name: &str) -> Result<u64> {
+    let revision: Option<i64> = conn
+        .query_row(
+            "SELECT revision FROM day_revisions WHERE project = ?1 AND date = ?2",
+            params![project_name, date.format("%Y-%m-%d").to_string()],
+            |row| row.get(0),
+        )
+        .optional()?;
+    Ok(revision.unwrap_or(0) as u64)
+}
+
+/// A save refused because the day changed since the list was loaded.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct StaleDay {
+    /// The day's revision now
+    pub revision: u64,
+}
+
+impl fmt::Display for StaleDay {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "The list was changed elsewhere (now at revision {})", self.revision)
+    }
+}
+
+impl std::error::Error for StaleDay {}
+
+/// Whether `error` is a save refused by `StaleDay`.
+pub fn stale_day(error: &anyhow::Error) -> Option<&StaleDay> {
+    error.downcast_ref::<StaleDay>()
+}
+
+/// What someone else did to the day since the list was loaded, by item text.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Change {
+    Added(String),
+    Edited(String),
+    Removed(String),
+}
+
+/// Unsaved changes to a day that was saved elsewhere in the meantime. Items
+/// are matched by id, which every version shares.
+#[derive(Debug, Clone)]
+pub struct StaleSave {
+    pub project_name: String,
+    pub date: NaiveDate,
+    /// The list with the unsaved changes
+    pub mine: TodoList,
+    /// The day as saved elsewhere
+    pub theirs: TodoList,
+    /// The revision `theirs` is at
+    pub revision: u64,
+    /// The list as loaded, which both versions started from
+    base: TodoList,
+}
+
+/// Whether two versions of an item differ in anything the user can change.
+fn same(a: &TodoItem, b: &TodoItem) -> bool {
+    a.content == b.content
+        && a.state == b.state
+        && a.indent_level == b.indent_level
+        && a.due_date == b.due_date
+        && a.description == b.description
+        && a.priority == b.priority
+        && a.estimate == b.estimate
+        && a.waiting_on == b.waiting_on
+        && a.pinned == b.pinned
+        && a.link == b.link
+        && a.location == b.location
+        && a.collapsed == b.collapsed
+}
+
+/// `theirs` with the fields `mine` changed since `base` taken from `mine`.
+fn edited(theirs: &TodoItem, base: &TodoItem, mine: &TodoItem) -> TodoItem {
+    if same(mine, base) {
+        return theirs.clone();
+    }
+    if same(theirs, base) {
+        return mine.clone();
+    }
+    let mut item = theirs.clone();
+    if mine.state != base.state {
+        item.set_state(mine.state);
+    }
+    macro_rules! take {
+        ($($field:ident),*) => {
+            $(if mine.$field != base.$field {
+                item.$field = mine.$field.clone();
+            })*
+        };
+    }
+    take!(content, indent_level, due_date, description, priority, estimate, waiting_on, pinned, link, location, collapsed);
+    item.modified_at = Utc::now();
+    item
+}
+
+fn by_id(items: &[TodoItem]) -> HashMap<Uuid, &TodoItem> {
+    items.iter().map(|item| (item.id, item)).collect()
+}
+
+impl StaleSave {
+    /// `mine` was loaded as `base`; the day has since been saved as `theirs`,
+    /// now at `revision`.
+    pub fn new(project_name: &str, mine: TodoList, base: TodoList, theirs: TodoList, revision: u64) -> Self {
+        Self {
+            project_name: project_name.to_string(),
+            date: mine.date,
+            mine,
+            theirs,
+            revision,
+            base,
+        }
+    }
+
+    /// What was done elsewhere since the list was loaded, in their order and
+    /// then the order of the items they removed.
+    pub fn changes(&self) -> Vec<Change> {
+        let base = by_id(&self.base.items);
+        let mut changes = Vec::new();
+        for item in &self.theirs.items {
+            match base.get(&item.id) {
+                None => changes.push(Change::Added(item.content.clone())),
+                Some(before) if !same(before, item) => changes.push(Change::Edited(item.content.clone())),
+                Some(_) => {}
+            }
+        }
+        let theirs = by_id(&self.theirs.items);
+        for item in &self.base.items {
+            if !theirs.contains_key(&item.id) {
+                changes.push(Change::Removed(item.content.clone()));
+            }
+        }
+        changes
+    }
+
+    /// Whether the user has to choose. When all that happened elsewhere is
+    /// removing items the list no longer has either (the trash does that, for
+    /// one), saving the list loses nothing.
+    pub fn needs_decision(&self) -> bool {
+        let mine = by_id(&self.mine.items);
+        let theirs = by_id(&self.theirs.items);
+        self.changes().iter().any(|change| !matches!(change, Change::Removed(_)))
+            || self
+                .base
+                .items
+                .iter()
+                .any(|item| mine.contains_key(&item.id) && !theirs.contains_key(&item.id))
+    }
+
+    /// The list with the unsaved changes, saved over the other version.
+    pub fn keep_mine(&self) -> TodoList {
+        self.mine.clone()
+    }
+
+    /// The day as saved elsewhere, dropping the unsaved changes.
+    pub fn take_theirs(&self) -> TodoList {
+        self.theirs.clone()
+    }
+
+    /// Both versions' changes since the list was loaded, in their order.
+    /// Items either side removed are gone, items added here go after the item
+    /// above them in this list, and fields edited here win.
+    pub fn merge(&self) -> TodoList {
+        let base = by_id(&self.base.items);
+        let mine = by_id(&self.mine.items);
+
+        let mut items: Vec<TodoItem> = Vec::new();
+        for item in &self.theirs.items {
+            match (base.get(&item.id), mine.get(&item.id)) {
+                // Removed here
+                (Some(_), None) => continue,
+                (Some(before), Some(after)) => items.push(edited(item, before, after)),
+                _ => items.push(item.clone()),
+            }
+        }
+
+        let kept: HashSet<Uuid> = base.keys().chain(self.theirs.items.iter().map(|item| &item.id)).copied().collect();
+        let mut anchor: Option<Uuid> = None;
+        for item in &self.mine.items {
+            if kept.contains(&item.id) {
+                if items.iter().any(|i| i.id == item.id) {
+                    anchor = Some(item.id);
+                }
+                continue;
+            }
+            let mut position = anchor
+                .and_then(|id| items.iter().position(|i| i.id == id))
+                .map_or(0, |index| index + 1);
+            // Past the anchor's subtasks unless the new item is one of them
+            while position < items.len() && items[position].indent_level > item.indent_level {
+                position += 1;
+            }
+            items.insert(position, item.clone());
+            anchor = Some(item.id);
+        }
+
+        // Items the other side removed can leave subtasks a level too deep
+        let mut max_indent = 0;
+        for item in &mut items {
+            item.indent_level = item.indent_level.min(max_indent);
+            max_indent = item.indent_level + 1;
+        }
+        let mut list = TodoList::with_items(self.date, self.mine.file_path.clone(), items);
+        list.recalculate_parent_ids();
+        list
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::storage::database::{init_database, save_todo_list_for_project, save_todo_list_if_current};
+    use crate::todo::TodoState;
+    use serial_test::serial;
+    use std::path::PathBuf;
+    use tempfile::TempDir;
+
+    fn date() -> NaiveDate {
+        NaiveDate::from_ymd_opt(2026, 1, 18).unwrap()
+    }
+
+    fn list(contents: &[&str]) -> TodoList {
+        let mut list = TodoList::new(date(), PathBuf::from("/tmp/2026-01-18.md"));
+        for content in contents {
+            list.add_item(content.to_string());
+        }
+        list
+    }
+
+    fn contents(list: &TodoList) -> Vec<&str> {
+        list.items.iter().map(|item| item.content.as_str()).collect()
+    }
+
+    #[test]
+    #[serial]
+    fn test_saves_against_an_old_revision_are_refused() {
+        let temp_dir = TempDir::new().unwrap();
+        std::fs::create_dir_all(temp_dir.path().join(".to-tui")).unwrap();
+        unsafe {
+            std::env::set_var("HOME", temp_dir.path());
+        }
+        init_database().unwrap();
+
+        assert_eq!(day_revision(date(), "default").unwrap(), 0);
+        let mut mine = list(&["Milk"]);
+        let revision = save_todo_list_if_current(&mine, "default", 0).unwrap();
+        assert!(revision > 0);
+        assert_eq!(day_revision(date(), "default").unwrap(), revision);
+
+        let mut theirs = mine.clone();
+        theirs.add_item("Eggs".to_string());
+        save_todo_list_for_project(&theirs, "default").unwrap();
+
+        mine.items[0].set_state(TodoState::Checked);
+        let error = save_todo_list_if_current(&mine, "default", revision).unwrap_err();
+        let stale = stale_day(&error).unwrap();
+        assert_eq!(stale.revision, day_revision(date(), "default").unwrap());
+        assert!(save_todo_list_if_current(&mine, "default", stale.revision).is_ok());
+
+        // Other days keep their own revisions
+        let tomorrow = date().succ_opt().unwrap();
+        assert_eq!(day_revision(tomorrow, "default").unwrap(), 0);
+    }
+
+    #[test]
+    fn test_merge_keeps_both_sides_changes() {
+        let base = list(&["Milk", "Eggs", "Bread"]);
+        let mut mine = base.clone();
+        mine.items[0].set_state(TodoState::Checked);
+        mine.items.remove(2);
+        mine.add_item("Butter".to_string());
+        let mut theirs = base.clone();
+        theirs.items[0].description = Some("Two liters".to_string());
+        theirs.items[1].content = "Free range eggs".to_string();
+        theirs.add_item("Jam".to_string());
+
+        let stale = StaleSave::new("default", mine, base.clone(), theirs, 7);
+        assert!(stale.needs_decision());
+        assert_eq!(
+            stale.changes(),
+            vec![
+                Change::Edited("Milk".to_string()),
+                Change::Edited("Free range eggs".to_string()),
+                Change::Added("Jam".to_string()),
+            ]
+        );
+
+        let merged = stale.merge();
+        assert_eq!(contents(&merged), vec!["Milk", "Free range eggs", "Butter", "Jam"]);
+        let milk = &merged.items[0];
+        assert_eq!(milk.id, base.items[0].id);
+        assert_eq!(milk.state, TodoState::Checked);
+        assert_eq!(milk.description.as_deref(), Some("Two liters"));
+    }
+
+    #[test]
+    fn test_removals_made_here_too_need_no_decision() {
+        let base = list(&["Milk", "Eggs"]);
+        let mut mine = base.clone();
+        mine.items.remove(1);
+        mine.add_item("Butter".to_string());
+        let mut theirs = base.clone();
+        theirs.items.remove(1);
+
+        assert!(!StaleSave::new("default", mine.clone(), base.clone(), theirs, 3).needs_decision());
+        assert!(StaleSave::new("default", base.clone(), base, mine, 3).needs_decision());
+    }
+}
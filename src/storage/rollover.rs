@@ -1,6 +1,8 @@
-use super::database::archive_todos_for_date_and_project;
+use super::attachments::copy_attachments;
+use super::history::{self, EventKind, NewEvent};
 use super::file::{
-    file_exists_for_project, load_todo_list_for_project, save_todo_list_for_project,
+    archive_todos_for_date_and_project, file_exists_for_project, load_todo_list_for_project,
+    save_todo_list_for_project,
 };
 use crate::todo::TodoList;
 use crate::utils::paths::get_daily_file_path_for_project;
@@ -48,6 +50,11 @@ pub fn execute_rollover_for_project(
 ) -> Result<TodoList> {
     let today = Local::now().date_naive();
     archive_todos_for_date_and_project(source_date, project_name)?;
+    history::record(&NewEvent {
+        date: Some(today),
+        detail: Some(format!("{} items from {source_date}", items.len())),
+        ..NewEvent::new(EventKind::Rollover, project_name)
+    })?;
     let list = create_rolled_over_list_for_project(project_name, today, items)?;
     save_todo_list_for_project(&list, project_name)?;
     Ok(list)
@@ -74,17 +81,38 @@ pub fn create_rolled_over_list_for_project(
         }
     }
 
-    Ok(TodoList::with_items(date, file_path, items))
+    copy_attachments(old_to_new_id)?;
+
+    let mut list = TodoList::with_items(date, file_path, items);
+    list.float_pinned();
+    Ok(list)
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
     use crate::project::DEFAULT_PROJECT_NAME;
+    use crate::storage::attachments::{attach, list_attachments};
+    use crate::storage::database::init_database;
     use crate::todo::{TodoItem, TodoState};
+    use serial_test::serial;
+    use tempfile::TempDir;
+
+    /// A fresh data directory; rolling over copies attachment rows.
+    fn temp_home() -> TempDir {
+        let temp_dir = TempDir::new().unwrap();
+        std::fs::create_dir_all(temp_dir.path().join(".to-tui")).unwrap();
+        unsafe {
+            std::env::set_var("HOME", temp_dir.path());
+        }
+        init_database().unwrap();
+        temp_dir
+    }
 
     #[test]
+    #[serial]
     fn test_create_rolled_over_list() {
+        let _home = temp_home();
         let today = Local::now().date_naive();
         let items = vec![
             TodoItem::with_state("Task 1".to_string(), TodoState::Empty, 0),
@@ -98,4 +126,67 @@ mod tests {
         assert_eq!(list.items[0].content, "Task 1");
         assert_eq!(list.items[1].content, "Task 2");
     }
+
+    #[test]
+    #[serial]
+    fn test_rollover_keeps_waiting_on() {
+        let _home = temp_home();
+        let today = Local::now().date_naive();
+        let mut delegated = TodoItem::with_state("Contract review".to_string(), TodoState::Empty, 0);
+        delegated.waiting_on = Some("alice".to_string());
+        let source = TodoList::with_items(
+            today,
+            std::path::PathBuf::from("/tmp/test.md"),
+            vec![
+                delegated,
+                TodoItem::with_state("Done".to_string(), TodoState::Checked, 0),
+            ],
+        );
+
+        let items = source.get_incomplete_items();
+        let list = create_rolled_over_list_for_project(DEFAULT_PROJECT_NAME, today, items).unwrap();
+
+        assert_eq!(list.items.len(), 1);
+        assert_eq!(list.items[0].waiting_on.as_deref(), Some("alice"));
+        assert!(list.items[0].is_waiting());
+    }
+
+    #[test]
+    #[serial]
+    fn test_rollover_keeps_pinned_items_on_top() {
+        let _home = temp_home();
+        let today = Local::now().date_naive();
+        let mut pinned = TodoItem::with_state("Pinned".to_string(), TodoState::Empty, 0);
+        pinned.pinned = true;
+        let items = vec![
+            TodoItem::with_state("Regular".to_string(), TodoState::Empty, 0),
+            pinned,
+        ];
+
+        let list = create_rolled_over_list_for_project(DEFAULT_PROJECT_NAME, today, items).unwrap();
+
+        assert_eq!(list.items[0].content, "Pinned");
+        assert!(list.items[0].pinned);
+    }
+
+    #[test]
+    #[serial]
+    fn test_rollover_keeps_attachments() {
+        let home = temp_home();
+        let today = Local::now().date_naive();
+        let notes = home.path().join("notes.txt");
+        std::fs::write(&notes, "agenda").unwrap();
+        let item = TodoItem::with_state("Prepare meeting".to_string(), TodoState::Empty, 0);
+        let old_id = item.id;
+        attach(&old_id, &notes).unwrap();
+
+        let list = create_rolled_over_list_for_project(DEFAULT_PROJECT_NAME, today, vec![item]).unwrap();
+
+        let rolled = list_attachments(&list.items[0].id).unwrap();
+        assert_eq!(rolled.len(), 1);
+        assert_eq!(rolled[0].name, "notes.txt");
+        assert_eq!(std::fs::read_to_string(rolled[0].path().unwrap()).unwrap(), "agenda");
+        // The archived item keeps its own
+        assert_eq!(list_attachments(&old_id).unwrap().len(), 1);
+    }
 }
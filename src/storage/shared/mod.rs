@@ -0,0 +1,165 @@
+//! A database shared by a team.
+//!
+//! With `[shared] url` set (in builds with the `postgres` feature) the lists
+//! and the trash are kept in a Postgres database several clients point at.
+//! The local database stays each client's working copy: a day is copied in
+//! from the shared database whenever it's loaded, and every save, delete,
+//! restore and archive goes to the shared database first. History, search,
+//! attachments and the archive view keep working on the local copy, and
+//! cover what this client has seen.
+//!
+//! Each project in the shared database has an owner, the first user to save
+//! to it, who can share it with others. Only the owner and the users it is
+//! shared with can read and change it, and only the owner can empty its
+//! trash.
+
+#[cfg(feature = "postgres")]
+mod postgres;
+
+use anyhow::{Context, Result, bail};
+use chrono::{DateTime, NaiveDate, Utc};
+use std::sync::{Arc, RwLock};
+use uuid::Uuid;
+
+use super::database::DeletedTodo;
+use crate::config::SharedConfig;
+use crate::todo::{TodoItem, TodoList};
+
+/// A day as the shared database has it.
+#[derive(Debug, Clone, Default)]
+pub struct SharedDay {
+    /// The live items, in list order
+    pub items: Vec<TodoItem>,
+    /// Items of the day that were archived
+    pub archived: Vec<Uuid>,
+    /// Items of the day in the trash
+    pub trashed: Vec<Uuid>,
+    /// Bumped by every change to the day; 0 if it was never saved
+    pub revision: u64,
+}
+
+/// Who can use a project in the shared database.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ProjectAccess {
+    pub owner: String,
+    /// The users the owner shared it with
+    pub members: Vec<String>,
+}
+
+/// A database lists are shared through. The operations match those of the
+/// local database of the same names, soft deletes included; writes fail for
+/// users the project isn't shared with.
+pub trait SharedStore: Send + Sync {
+    /// The day's items and revision.
+    fn load_day(&self, project_name: &str, date: NaiveDate) -> Result<SharedDay>;
+
+    /// The day's revision. Days never saved are at 0.
+    fn day_revision(&self, project_name: &str, date: NaiveDate) -> Result<u64>;
+
+    /// Move `trashed` to the trash and save `list`, which no longer has
+    /// them, and return the day's new revision. With a `revision`, fails
+    /// with `StaleDay` if the day has moved on from it. Items no longer on
+    /// the list are deleted, unless the list is empty.
+    fn save_day(&self, list: &TodoList, trashed: &[Uuid], project_name: &str, revision: Option<u64>) -> Result<u64>;
+
+    /// Move live items of `date` to the trash.
+    fn soft_delete(&self, ids: &[Uuid], date: NaiveDate, project_name: &str) -> Result<usize>;
+
+    /// Delete live items of `date` outright, for changes being taken back.
+    fn delete(&self, ids: &[Uuid], date: NaiveDate, project_name: &str) -> Result<usize>;
+
+    /// Archive the items of `date`, or only `ids` among them.
+    fn archive(&self, ids: Option<&[Uuid]>, date: NaiveDate, project_name: &str) -> Result<usize>;
+
+    /// The project's trash, most recently deleted first.
+    fn load_deleted(&self, project_name: &str) -> Result<Vec<DeletedTodo>>;
+
+    /// Put items in the trash back on their lists.
+    fn restore_deleted(&self, ids: &[Uuid], project_name: &str) -> Result<usize>;
+
+    /// Delete items in the trash for good. Only the owner can.
+    fn purge_deleted(&self, ids: &[Uuid], project_name: &str) -> Result<usize>;
+
+    /// Delete for good what went to the trash before `cutoff`, in the
+    /// projects this user owns.
+    fn purge_deleted_before(&self, cutoff: DateTime<Utc>) -> Result<usize>;
+
+    /// Who can use the project, `None` if nobody saved to it yet.
+    fn project_access(&self, project_name: &str) -> Result<Option<ProjectAccess>>;
+
+    /// Let `member` read and change the project, or stop letting them.
+    /// Only the owner can; a project nobody saved to yet becomes theirs.
+    fn share_project(&self, project_name: &str, member: &str, shared: bool) -> Result<()>;
+}
+
+static STORE: RwLock<Option<Arc<dyn SharedStore>>> = RwLock::new(None);
+
+/// Apply the `[shared]` settings. Called once at startup; nothing is
+/// connected to until the first list is loaded.
+pub fn configure(config: &SharedConfig) -> Result<()> {
+    let store = match &config.url {
+        Some(url) if !url.is_empty() => Some(open(url, &user_name(config)?)?),
+        _ => None,
+    };
+    set_store(store);
+    Ok(())
+}
+
+/// Keep lists in `store` from now on, or only locally with `None`.
+pub fn set_store(store: Option<Arc<dyn SharedStore>>) {
+    *STORE.write().unwrap_or_else(|e| e.into_inner()) = store;
+}
+
+/// The shared database, if one is configured.
+pub fn store() -> Option<Arc<dyn SharedStore>> {
+    STORE.read().unwrap_or_else(|e| e.into_inner()).clone()
+}
+
+/// Who this client writes as: `[shared] user`, or the login name.
+fn user_name(config: &SharedConfig) -> Result<String> {
+    config
+        .user
+        .clone()
+        .or_else(|| std::env::var("USER").ok())
+        .or_else(|| std::env::var("USERNAME").ok())
+        .filter(|user| !user.is_empty())
+        .context("Set [shared] user in config.toml to the name to share projects under")
+}
+
+#[cfg(feature = "postgres")]
+fn open(url: &str, user: &str) -> Result<Arc<dyn SharedStore>> {
+    if !url.starts_with("postgres://") && !url.starts_with("postgresql://") {
+        bail!("[shared] url must be a postgres:// connection string");
+    }
+    Ok(Arc::new(postgres::PostgresStore::new(url, user)?))
+}
+
+#[cfg(not(feature = "postgres"))]
+fn open(_url: &str, _user: &str) -> Result<Arc<dyn SharedStore>> {
+    bail!("[shared] url is set in config.toml, but this totui was built without the `postgres` feature")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serial_test::serial;
+
+    #[test]
+    #[serial]
+    fn test_nothing_is_shared_without_a_url() {
+        configure(&SharedConfig::default()).unwrap();
+        assert!(store().is_none());
+    }
+
+    #[cfg(not(feature = "postgres"))]
+    #[test]
+    #[serial]
+    fn test_a_shared_database_needs_the_feature() {
+        let config = SharedConfig {
+            url: Some("postgres://db.example.com/totui".to_string()),
+            user: Some("alice".to_string()),
+        };
+        assert!(configure(&config).is_err());
+        assert!(store().is_none());
+    }
+}
@@ -0,0 +1,572 @@
+//! The shared database on Postgres.
+//!
+//! Items are kept whole as JSON next to the columns they are looked up by.
+//! Soft-deleted and archived items keep their rows, with `deleted_at` or
+//! `archived_at` set, like the local `todos` and `archived_todos` tables.
+
+use super::{ProjectAccess, SharedDay, SharedStore};
+use crate::storage::database::DeletedTodo;
+use crate::storage::revision::StaleDay;
+use crate::todo::{TodoItem, TodoList};
+use ::postgres::{Client, Config, GenericClient, Transaction};
+use anyhow::{Context, Result, bail};
+use chrono::{DateTime, NaiveDate, Utc};
+use std::collections::{HashMap, HashSet};
+use std::str::FromStr;
+use std::sync::{Arc, Mutex};
+use tokio_postgres_rustls::MakeRustlsConnect;
+use tracing::debug;
+use uuid::Uuid;
+
+const SCHEMA: &str = "
+    CREATE TABLE IF NOT EXISTS projects (
+        name TEXT PRIMARY KEY,
+        owner TEXT NOT NULL,
+        created_at TIMESTAMPTZ NOT NULL DEFAULT now()
+    );
+    CREATE TABLE IF NOT EXISTS project_members (
+        project TEXT NOT NULL REFERENCES projects (name) ON DELETE CASCADE,
+        member TEXT NOT NULL,
+        PRIMARY KEY (project, member)
+    );
+    CREATE TABLE IF NOT EXISTS todos (
+        id UUID PRIMARY KEY,
+        project TEXT NOT NULL REFERENCES projects (name),
+        date DATE NOT NULL,
+        position INTEGER NOT NULL,
+        item JSONB NOT NULL,
+        deleted_at TIMESTAMPTZ,
+        archived_at TIMESTAMPTZ
+    );
+    CREATE INDEX IF NOT EXISTS idx_todos_day ON todos (project, date);
+    CREATE TABLE IF NOT EXISTS day_revisions (
+        project TEXT NOT NULL,
+        date DATE NOT NULL,
+        revision BIGINT NOT NULL,
+        PRIMARY KEY (project, date)
+    );
+";
+
+pub struct PostgresStore {
+    config: Config,
+    user: String,
+    /// Connected on first use, and again after the connection drops
+    client: Mutex<Option<Client>>,
+}
+
+impl PostgresStore {
+    pub fn new(url: &str, user: &str) -> Result<Self> {
+        let config = Config::from_str(url).context("Invalid [shared] url")?;
+        Ok(Self {
+            config,
+            user: user.to_string(),
+            client: Mutex::new(None),
+        })
+    }
+
+    /// Run `f` on the connection, connecting first if needed.
+    fn with_client<T>(&self, f: impl FnOnce(&mut Client) -> Result<T>) -> Result<T> {
+        let mut client = self.client.lock().unwrap_or_else(|e| e.into_inner());
+        if client.as_ref().is_none_or(|client| client.is_closed()) {
+            *client = Some(self.connect()?);
+        }
+        match client.as_mut() {
+            Some(client) => f(client),
+            None => bail!("Not connected to the shared database"),
+        }
+    }
+
+    fn connect(&self) -> Result<Client> {
+        let tls = rustls::ClientConfig::builder_with_provider(Arc::new(rustls::crypto::ring::default_provider()))
+            .with_safe_default_protocol_versions()?
+            .with_root_certificates(rustls::RootCertStore {
+                roots: webpki_roots::TLS_SERVER_ROOTS.to_vec(),
+            })
+            .with_no_client_auth();
+        let mut client = self
+            .config
+            .connect(MakeRustlsConnect::new(tls))
+            .context("Failed to connect to the shared database")?;
+        client.batch_execute(SCHEMA)?;
+        debug!(user = %self.user, "Connected to the shared database");
+        Ok(client)
+    }
+
+    /// Fail unless this user can use the project. With `claim`, a project
+    /// nobody saved to yet becomes this user's.
+    fn check_access(&self, tx: &mut impl GenericClient, project_name: &str, claim: bool) -> Result<()> {
+        if claim {
+            tx.execute(
+                "INSERT INTO projects (name, owner) VALUES ($1, $2) ON CONFLICT (name) DO NOTHING",
+                &[&project_name, &self.user],
+            )?;
+        }
+        let Some(access) = access_on(tx, project_name)? else {
+            return Ok(());
+        };
+        if access.owner != self.user && !access.members.contains(&self.user) {
+            bail!(
+                "Project '{project_name}' in the shared database belongs to {}; they can share it with `totui share {project_name} {}`",
+                access.owner,
+                self.user
+            );
+        }
+        Ok(())
+    }
+
+    fn check_owner(&self, tx: &mut impl GenericClient, project_name: &str) -> Result<()> {
+        match access_on(tx, project_name)? {
+            Some(access) if access.owner != self.user => {
+                bail!("Only {}, who owns '{project_name}', can do that", access.owner)
+            }
+            _ => Ok(()),
+        }
+    }
+}
+
+fn access_on(client: &mut impl GenericClient, project_name: &str) -> Result<Option<ProjectAccess>> {
+    let Some(row) = client.query_opt("SELECT owner FROM projects WHERE name = $1", &[&project_name])? else {
+        return Ok(None);
+    };
+    let members = client
+        .query(
+            "SELECT member FROM project_members WHERE project = $1 ORDER BY member",
+            &[&project_name],
+        )?
+        .iter()
+        .map(|row| row.get(0))
+        .collect();
+    Ok(Some(ProjectAccess {
+        owner: row.get(0),
+        members,
+    }))
+}
+
+/// The day's revision, locking it until the transaction ends so saves to
+/// the day take turns.
+fn lock_revision(tx: &mut Transaction, project_name: &str, date: NaiveDate) -> Result<u64> {
+    tx.execute(
+        "INSERT INTO day_revisions (project, date, revision) VALUES ($1, $2, 0) ON CONFLICT DO NOTHING",
+        &[&project_name, &date],
+    )?;
+    let revision: i64 = tx
+        .query_one(
+            "SELECT revision FROM day_revisions WHERE project = $1 AND date = $2 FOR UPDATE",
+            &[&project_name, &date],
+        )?
+        .get(0);
+    Ok(revision as u64)
+}
+
+fn bump(tx: &mut impl GenericClient, project_name: &str, date: NaiveDate) -> Result<u64> {
+    let revision: i64 = tx
+        .query_one(
+            "INSERT INTO day_revisions (project, date, revision) VALUES ($1, $2, 1)
+             ON CONFLICT (project, date) DO UPDATE SET revision = day_revisions.revision + 1
+             RETURNING revision",
+            &[&project_name, &date],
+        )?
+        .get(0);
+    Ok(revision as u64)
+}
+
+fn revision_on(client: &mut impl GenericClient, project_name: &str, date: NaiveDate) -> Result<u64> {
+    let revision = client
+        .query_opt(
+            "SELECT revision FROM day_revisions WHERE project = $1 AND date = $2",
+            &[&project_name, &date],
+        )?
+        .map(|row| row.get::<_, i64>(0))
+        .unwrap_or(0);
+    Ok(revision as u64)
+}
+
+/// The item stored in a row, with `deleted_at` from its column.
+fn item_from(value: serde_json::Value, deleted_at: Option<DateTime<Utc>>) -> Result<TodoItem> {
+    let mut item: TodoItem = serde_json::from_value(value).context("Invalid item in the shared database")?;
+    item.deleted_at = deleted_at;
+    Ok(item)
+}
+
+impl SharedStore for PostgresStore {
+    fn load_day(&self, project_name: &str, date: NaiveDate) -> Result<SharedDay> {
+        self.with_client(|client| {
+            let mut tx = client.transaction()?;
+            self.check_access(&mut tx, project_name, false)?;
+            let mut day = SharedDay {
+                revision: revision_on(&mut tx, project_name, date)?,
+                ..SharedDay::default()
+            };
+            let rows = tx.query(
+                "SELECT id, item, deleted_at, archived_at IS NOT NULL FROM todos
+                 WHERE project = $1 AND date = $2 ORDER BY position",
+                &[&project_name, &date],
+            )?;
+            for row in rows {
+                let deleted_at: Option<DateTime<Utc>> = row.get(2);
+                if row.get(3) {
+                    day.archived.push(row.get(0));
+                } else if deleted_at.is_some() {
+                    day.trashed.push(row.get(0));
+                } else {
+                    day.items.push(item_from(row.get(1), None)?);
+                }
+            }
+            tx.commit()?;
+            Ok(day)
+        })
+    }
+
+    fn day_revision(&self, project_name: &str, date: NaiveDate) -> Result<u64> {
+        self.with_client(|client| {
+            self.check_access(client, project_name, false)?;
+            revision_on(client, project_name, date)
+        })
+    }
+
+    fn save_day(&self, list: &TodoList, trashed: &[Uuid], project_name: &str, revision: Option<u64>) -> Result<u64> {
+        self.with_client(|client| {
+            let mut tx = client.transaction()?;
+            self.check_access(&mut tx, project_name, true)?;
+            let current = lock_revision(&mut tx, project_name, list.date)?;
+            if revision.is_some_and(|revision| revision != current) {
+                return Err(StaleDay { revision: current }.into());
+            }
+
+            let mut changed = soft_delete_on(&mut tx, trashed, list.date, project_name)? > 0;
+
+            // The day's rows as stored, to compare against
+            let mut stored: HashMap<Uuid, (i32, serde_json::Value, bool)> = HashMap::new();
+            for row in tx.query(
+                "SELECT id, position, item, deleted_at IS NULL AND archived_at IS NULL FROM todos
+                 WHERE project = $1 AND date = $2",
+                &[&project_name, &list.date],
+            )? {
+                stored.insert(row.get(0), (row.get(1), row.get(2), row.get(3)));
+            }
+
+            // Items coming from another day or project leave that day too
+            let ids: Vec<Uuid> = list.items.iter().map(|item| item.id).collect();
+            for row in tx.query(
+                "SELECT DISTINCT project, date FROM todos
+                 WHERE id = ANY($1) AND NOT (project = $2 AND date = $3)",
+                &[&ids, &project_name, &list.date],
+            )? {
+                let (other_project, other_date): (String, NaiveDate) = (row.get(0), row.get(1));
+                self.check_access(&mut tx, &other_project, false)?;
+                bump(&mut tx, &other_project, other_date)?;
+            }
+
+            let short_ids = list.short_ids();
+            for (position, item) in list.items.iter().enumerate() {
+                let position = position as i32;
+                let mut item = item.clone();
+                item.short_id = short_ids.get(&item.id).cloned();
+                item.deleted_at = None;
+                let value = serde_json::to_value(&item)?;
+                if stored.get(&item.id) == Some(&(position, value.clone(), true)) {
+                    continue;
+                }
+                tx.execute(
+                    "INSERT INTO todos (id, project, date, position, item) VALUES ($1, $2, $3, $4, $5)
+                     ON CONFLICT (id) DO UPDATE SET
+                         project = excluded.project,
+                         date = excluded.date,
+                         position = excluded.position,
+                         item = excluded.item,
+                         deleted_at = NULL,
+                         archived_at = NULL",
+                    &[&item.id, &project_name, &list.date, &position, &value],
+                )?;
+                changed = true;
+            }
+
+            // Like the local save, an empty list never deletes anything
+            if !ids.is_empty() {
+                let kept: HashSet<Uuid> = ids.iter().copied().collect();
+                let removed: Vec<Uuid> = stored
+                    .iter()
+                    .filter(|(id, (_, _, live))| *live && !kept.contains(id))
+                    .map(|(id, _)| *id)
+                    .collect();
+                changed |= tx.execute(
+                    "DELETE FROM todos WHERE id = ANY($1) AND deleted_at IS NULL AND archived_at IS NULL",
+                    &[&removed],
+                )? > 0;
+            }
+
+            let saved = if changed { bump(&mut tx, project_name, list.date)? } else { current };
+            tx.commit()?;
+            Ok(saved)
+        })
+    }
+
+    fn soft_delete(&self, ids: &[Uuid], date: NaiveDate, project_name: &str) -> Result<usize> {
+        self.with_client(|client| {
+            let mut tx = client.transaction()?;
+            self.check_access(&mut tx, project_name, true)?;
+            let count = soft_delete_on(&mut tx, ids, date, project_name)?;
+            if count > 0 {
+                bump(&mut tx, project_name, date)?;
+            }
+            tx.commit()?;
+            Ok(count)
+        })
+    }
+
+    fn delete(&self, ids: &[Uuid], date: NaiveDate, project_name: &str) -> Result<usize> {
+        self.with_client(|client| {
+            let mut tx = client.transaction()?;
+            self.check_access(&mut tx, project_name, true)?;
+            let count = tx.execute(
+                "DELETE FROM todos WHERE id = ANY($1) AND project = $2 AND date = $3
+                 AND deleted_at IS NULL AND archived_at IS NULL",
+                &[&ids, &project_name, &date],
+            )?;
+            if count > 0 {
+                bump(&mut tx, project_name, date)?;
+            }
+            tx.commit()?;
+            Ok(count as usize)
+        })
+    }
+
+    fn archive(&self, ids: Option<&[Uuid]>, date: NaiveDate, project_name: &str) -> Result<usize> {
+        self.with_client(|client| {
+            let mut tx = client.transaction()?;
+            self.check_access(&mut tx, project_name, true)?;
+            let count = match ids {
+                Some(ids) => tx.execute(
+                    "UPDATE todos SET archived_at = now()
+                     WHERE id = ANY($1) AND project = $2 AND date = $3 AND archived_at IS NULL",
+                    &[&ids, &project_name, &date],
+                )?,
+                None => tx.execute(
+                    "UPDATE todos SET archived_at = now() WHERE project = $1 AND date = $2 AND archived_at IS NULL",
+                    &[&project_name, &date],
+                )?,
+            };
+            if count > 0 {
+                bump(&mut tx, project_name, date)?;
+            }
+            tx.commit()?;
+            Ok(count as usize)
+        })
+    }
+
+    fn load_deleted(&self, project_name: &str) -> Result<Vec<DeletedTodo>> {
+        self.with_client(|client| {
+            self.check_access(client, project_name, false)?;
+            let rows = client.query(
+                "SELECT item, deleted_at, date, archived_at IS NOT NULL FROM todos
+                 WHERE project = $1 AND deleted_at IS NOT NULL
+                 ORDER BY deleted_at DESC, date, position",
+                &[&project_name],
+            )?;
+            rows.into_iter()
+                .map(|row| {
+                    Ok(DeletedTodo {
+                        item: item_from(row.get(0), row.get(1))?,
+                        date: row.get(2),
+                        archived: row.get(3),
+                    })
+                })
+                .collect()
+        })
+    }
+
+    fn restore_deleted(&self, ids: &[Uuid], project_name: &str) -> Result<usize> {
+        self.with_client(|client| {
+            let mut tx = client.transaction()?;
+            self.check_access(&mut tx, project_name, true)?;
+            let days: Vec<(NaiveDate, bool)> = tx
+                .query(
+                    "UPDATE todos SET deleted_at = NULL
+                     WHERE id = ANY($1) AND project = $2 AND deleted_at IS NOT NULL
+                     RETURNING date, archived_at IS NOT NULL",
+                    &[&ids, &project_name],
+                )?
+                .iter()
+                .map(|row| (row.get(0), row.get(1)))
+                .collect();
+            let live: HashSet<NaiveDate> =
+                days.iter().filter(|(_, archived)| !archived).map(|(date, _)| *date).collect();
+            for date in live {
+                bump(&mut tx, project_name, date)?;
+            }
+            tx.commit()?;
+            Ok(days.len())
+        })
+    }
+
+    fn purge_deleted(&self, ids: &[Uuid], project_name: &str) -> Result<usize> {
+        self.with_client(|client| {
+            let mut tx = client.transaction()?;
+            self.check_owner(&mut tx, project_name)?;
+            let count = tx.execute(
+                "DELETE FROM todos WHERE id = ANY($1) AND project = $2 AND deleted_at IS NOT NULL",
+                &[&ids, &project_name],
+            )?;
+            tx.commit()?;
+            Ok(count as usize)
+        })
+    }
+
+    fn purge_deleted_before(&self, cutoff: DateTime<Utc>) -> Result<usize> {
+        self.with_client(|client| {
+            let count = client.execute(
+                "DELETE FROM todos USING projects
+                 WHERE todos.project = projects.name AND projects.owner = $1 AND todos.deleted_at < $2",
+                &[&self.user, &cutoff],
+            )?;
+            Ok(count as usize)
+        })
+    }
+
+    fn project_access(&self, project_name: &str) -> Result<Option<ProjectAccess>> {
+        self.with_client(|client| access_on(client, project_name))
+    }
+
+    fn share_project(&self, project_name: &str, member: &str, shared: bool) -> Result<()> {
+        self.with_client(|client| {
+            let mut tx = client.transaction()?;
+            self.check_access(&mut tx, project_name, true)?;
+            self.check_owner(&mut tx, project_name)?;
+            if shared {
+                tx.execute(
+                    "INSERT INTO project_members (project, member) VALUES ($1, $2) ON CONFLICT DO NOTHING",
+                    &[&project_name, &member],
+                )?;
+            } else {
+                tx.execute(
+                    "DELETE FROM project_members WHERE project = $1 AND member = $2",
+                    &[&project_name, &member],
+                )?;
+            }
+            tx.commit()?;
+            Ok(())
+        })
+    }
+}
+
+fn soft_delete_on(tx: &mut impl GenericClient, ids: &[Uuid], date: NaiveDate, project_name: &str) -> Result<usize> {
+    if ids.is_empty() {
+        return Ok(0);
+    }
+    let count = tx.execute(
+        "UPDATE todos SET deleted_at = now()
+         WHERE id = ANY($1) AND project = $2 AND date = $3 AND deleted_at IS NULL AND archived_at IS NULL",
+        &[&ids, &project_name, &date],
+    )?;
+    Ok(count as usize)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serial_test::serial;
+
+    /// A store on the server in TOTUI_TEST_POSTGRES_URL, emptied first.
+    fn store(user: &str) -> PostgresStore {
+        let url = std::env::var("TOTUI_TEST_POSTGRES_URL").unwrap();
+        let store = PostgresStore::new(&url, user).unwrap();
+        store
+            .with_client(|client| {
+                client.batch_execute("TRUNCATE todos, day_revisions, project_members, projects")?;
+                Ok(())
+            })
+            .unwrap();
+        store
+    }
+
+    fn date() -> NaiveDate {
+        NaiveDate::from_ymd_opt(2026, 3, 2).unwrap()
+    }
+
+    fn list(items: &[&str]) -> TodoList {
+        let mut list = TodoList::new(date(), "2026-03-02.md".into());
+        for item in items {
+            list.add_item(item.to_string());
+        }
+        list
+    }
+
+    #[test]
+    #[serial]
+    #[ignore] // Needs a Postgres server in TOTUI_TEST_POSTGRES_URL
+    fn test_saves_are_checked_against_the_revision() {
+        let alice = store("alice");
+        let mut mine = list(&["Milk", "Eggs"]);
+        let revision = alice.save_day(&mine, &[], "default", Some(0)).unwrap();
+        assert_eq!(revision, 1);
+        // Nothing changed, nothing bumped
+        assert_eq!(alice.save_day(&mine, &[], "default", Some(1)).unwrap(), 1);
+
+        let day = alice.load_day("default", date()).unwrap();
+        let contents: Vec<_> = day.items.iter().map(|item| item.content.as_str()).collect();
+        assert_eq!(contents, vec!["Milk", "Eggs"]);
+        assert_eq!(day.items[0].short_id, mine.short_ids().get(&mine.items[0].id).cloned());
+
+        mine.items[0].content = "Oat milk".to_string();
+        assert_eq!(alice.save_day(&mine, &[], "default", Some(1)).unwrap(), 2);
+        let error = alice.save_day(&mine, &[], "default", Some(1)).unwrap_err();
+        assert_eq!(crate::storage::revision::stale_day(&error).map(|stale| stale.revision), Some(2));
+
+        // Dropped items are deleted, trashed ones wait in the trash
+        let eggs = mine.items.remove(1);
+        mine.add_item("Bread".to_string());
+        alice.save_day(&mine, &[], "default", None).unwrap();
+        let bread = mine.items.pop().unwrap();
+        alice.save_day(&mine, &[bread.id], "default", None).unwrap();
+        let day = alice.load_day("default", date()).unwrap();
+        assert_eq!(day.items.len(), 1);
+        assert_eq!(day.trashed, vec![bread.id]);
+        let trash = alice.load_deleted("default").unwrap();
+        assert_eq!(trash.len(), 1);
+        assert!(trash[0].item.deleted_at.is_some());
+        assert!(!trash.iter().any(|deleted| deleted.item.id == eggs.id));
+
+        assert_eq!(alice.restore_deleted(&[bread.id], "default").unwrap(), 1);
+        assert_eq!(alice.load_day("default", date()).unwrap().items.len(), 2);
+        alice.soft_delete(&[bread.id], date(), "default").unwrap();
+        assert_eq!(alice.purge_deleted(&[bread.id], "default").unwrap(), 1);
+        assert!(alice.load_deleted("default").unwrap().is_empty());
+
+        // Archived items leave the list
+        assert_eq!(alice.archive(None, date(), "default").unwrap(), 1);
+        let day = alice.load_day("default", date()).unwrap();
+        assert!(day.items.is_empty());
+        assert_eq!(day.archived, vec![mine.items[0].id]);
+    }
+
+    #[test]
+    #[serial]
+    #[ignore] // Needs a Postgres server in TOTUI_TEST_POSTGRES_URL
+    fn test_projects_belong_to_whoever_saved_first() {
+        let alice = store("alice");
+        let url = std::env::var("TOTUI_TEST_POSTGRES_URL").unwrap();
+        let bob = PostgresStore::new(&url, "bob").unwrap();
+
+        let list = list(&["Release notes"]);
+        alice.save_day(&list, &[], "work", None).unwrap();
+        assert!(bob.load_day("work", date()).is_err());
+        assert!(bob.save_day(&list, &[], "work", None).is_err());
+        assert!(bob.share_project("work", "bob", true).is_err());
+        // Projects nobody saved to can be read by anyone, and are empty
+        assert_eq!(bob.load_day("home", date()).unwrap().revision, 0);
+
+        alice.share_project("work", "bob", true).unwrap();
+        let access = bob.project_access("work").unwrap().unwrap();
+        assert_eq!(access.owner, "alice");
+        assert_eq!(access.members, vec!["bob"]);
+        assert_eq!(bob.load_day("work", date()).unwrap().items.len(), 1);
+        bob.soft_delete(&[list.items[0].id], date(), "work").unwrap();
+        // Only the owner empties the trash
+        assert!(bob.purge_deleted(&[list.items[0].id], "work").is_err());
+        assert_eq!(bob.purge_deleted_before(Utc::now()).unwrap(), 0);
+        assert_eq!(alice.purge_deleted_before(Utc::now()).unwrap(), 1);
+
+        alice.share_project("work", "bob", false).unwrap();
+        assert!(bob.load_day("work", date()).is_err());
+    }
+}
@@ -0,0 +1,388 @@
+//! Export and import of the full application state.
+//!
+//! `totui export-all` bundles the database (which also holds the project
+//! registry), every project's dailies, the config file, plugin configs and a
+//! list of installed plugins into a single `.tar.gz`. Plugin binaries are not
+//! included; `totui import-all` prints how to reinstall them instead.
+
+use anyhow::{Context, Result, anyhow, bail};
+use chrono::{DateTime, Utc};
+use flate2::Compression;
+use flate2::read::GzDecoder;
+use flate2::write::GzEncoder;
+use rusqlite::Connection;
+use serde::{Deserialize, Serialize};
+use std::fs::{self, File};
+use std::path::{Path, PathBuf};
+use tar::{Archive, Builder};
+use tempfile::tempdir;
+use tracing::debug;
+
+use crate::plugin::PluginManager;
+use crate::project::Project;
+use crate::storage::backup::copy_database;
+use crate::storage::database;
+use crate::storage::encryption;
+use crate::utils::paths::{
+    get_config_path, get_database_path, get_plugins_config_dir, get_projects_dir,
+};
+
+/// Bumped when the archive layout changes incompatibly.
+pub const EXPORT_FORMAT_VERSION: u32 = 1;
+
+const MANIFEST_FILE: &str = "manifest.json";
+const DATABASE_FILE: &str = "todos.db";
+const CONFIG_FILE: &str = "config.toml";
+const PROJECTS_DIR: &str = "projects";
+const PLUGIN_CONFIG_DIR: &str = "plugin-config";
+
+/// Describes the contents of an export archive.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ExportManifest {
+    pub format_version: u32,
+    /// totui version that produced the archive
+    pub app_version: String,
+    pub exported_at: DateTime<Utc>,
+    pub projects: Vec<Project>,
+    pub plugins: Vec<ExportedPlugin>,
+}
+
+/// An installed plugin, recorded so it can be reinstalled after import.
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq)]
+pub struct ExportedPlugin {
+    pub name: String,
+    pub version: String,
+    /// `owner/repo` for marketplace installs, otherwise `local` or `unknown`
+    pub source: String,
+}
+
+impl ExportedPlugin {
+    /// Command that reinstalls this plugin, if it came from a marketplace.
+    pub fn install_command(&self) -> Option<String> {
+        let (owner, repo) = self.source.split_once('/')?;
+        Some(format!(
+            "totui plugin install {owner}/{repo}/{} --version {}",
+            self.name, self.version
+        ))
+    }
+}
+
+/// Locations of everything that goes into an export.
+#[derive(Debug, Clone)]
+struct StatePaths {
+    database: PathBuf,
+    config: PathBuf,
+    projects_dir: PathBuf,
+    plugin_configs_dir: PathBuf,
+}
+
+impl StatePaths {
+    fn current() -> Result<Self> {
+        Ok(Self {
+            database: get_database_path()?,
+            config: get_config_path()?,
+            projects_dir: get_projects_dir()?,
+            plugin_configs_dir: get_plugins_config_dir()?,
+        })
+    }
+}
+
+/// Write the full application state to a `.tar.gz` archive at `output`.
+pub fn export_all(output: &Path) -> Result<ExportManifest> {
+    database::init_database()?;
+
+    let plugins = PluginManager::discover()?
+        .list()
+        .into_iter()
+        .map(|info| ExportedPlugin {
+            name: info.manifest.name.clone(),
+            version: info.manifest.version.clone(),
+            source: info.source.to_string(),
+        })
+        .collect();
+
+    let manifest = ExportManifest {
+        format_version: EXPORT_FORMAT_VERSION,
+        app_version: env!("CARGO_PKG_VERSION").to_string(),
+        exported_at: Utc::now(),
+        projects: database::load_projects()?,
+        plugins,
+    };
+
+    write_archive(output, &StatePaths::current()?, &manifest)?;
+    Ok(manifest)
+}
+
+/// Restore the application state from an archive written by [`export_all`].
+///
+/// Refuses to overwrite existing todos unless `force` is set. The previous
+/// database is kept next to the new one as `todos.db.before-import`.
+pub fn import_all(archive: &Path, force: bool) -> Result<ExportManifest> {
+    database::init_database()?;
+    if !force && database::has_any_todos()? {
+        bail!("This machine already has todos. Re-run with --force to replace them");
+    }
+
+    // The database file is about to be replaced underneath any open connection
+    database::close_connections();
+    let manifest = restore_archive(archive, &StatePaths::current()?)?;
+
+    // Bring an export from an older version up to the current schema
+    database::init_database()?;
+    Ok(manifest)
+}
+
+fn write_archive(output: &Path, paths: &StatePaths, manifest: &ExportManifest) -> Result<()> {
+    let file = File::create(output)
+        .with_context(|| format!("Failed to create {}", output.display()))?;
+    let mut builder = Builder::new(GzEncoder::new(file, Compression::default()));
+
+    let staging = tempdir().context("Failed to create temporary directory")?;
+
+    let manifest_path = staging.path().join(MANIFEST_FILE);
+    fs::write(&manifest_path, serde_json::to_string_pretty(manifest)?)?;
+    builder.append_path_with_name(&manifest_path, MANIFEST_FILE)?;
+
+    // VACUUM INTO gives a consistent copy even while the TUI or server is running
+    if paths.database.exists() {
+        let db_copy = staging.path().join(DATABASE_FILE);
+        let conn = Connection::open(&paths.database).with_context(|| {
+            format!("Failed to open database at {}", paths.database.display())
+        })?;
+        // An encrypted database is copied under its own key
+        encryption::key_connection(&conn)?;
+        conn.execute("VACUUM INTO ?1", [db_copy.to_string_lossy()])
+            .context("Failed to snapshot database")?;
+        builder.append_path_with_name(&db_copy, DATABASE_FILE)?;
+    }
+
+    if paths.config.exists() {
+        builder.append_path_with_name(&paths.config, CONFIG_FILE)?;
+    }
+
+    if paths.projects_dir.exists() {
+        builder.append_dir_all(PROJECTS_DIR, &paths.projects_dir)?;
+    }
+
+    for plugin in &manifest.plugins {
+        let config = paths.plugin_configs_dir.join(&plugin.name).join("config.toml");
+        if config.exists() {
+            let name = format!("{PLUGIN_CONFIG_DIR}/{}/config.toml", plugin.name);
+            builder.append_path_with_name(&config, name)?;
+        }
+    }
+
+    builder
+        .into_inner()?
+        .finish()
+        .with_context(|| format!("Failed to write {}", output.display()))?;
+
+    debug!("Exported application state to {}", output.display());
+    Ok(())
+}
+
+fn restore_archive(archive: &Path, paths: &StatePaths) -> Result<ExportManifest> {
+    let file =
+        File::open(archive).with_context(|| format!("Failed to open {}", archive.display()))?;
+
+    // Unpack into a scratch directory first so a broken archive changes nothing
+    let staging = tempdir().context("Failed to create temporary directory")?;
+    Archive::new(GzDecoder::new(file))
+        .unpack(staging.path())
+        .with_context(|| format!("Failed to unpack {}", archive.display()))?;
+
+    let manifest_path = staging.path().join(MANIFEST_FILE);
+    let content = fs::read_to_string(&manifest_path)
+        .map_err(|_| anyhow!("{} is not a totui export", archive.display()))?;
+    let manifest: ExportManifest =
+        serde_json::from_str(&content).context("Failed to parse export manifest")?;
+    if manifest.format_version > EXPORT_FORMAT_VERSION {
+        bail!(
+            "Export was made by totui {} and uses a newer format; upgrade totui first",
+            manifest.app_version
+        );
+    }
+
+    let db = staging.path().join(DATABASE_FILE);
+    if db.exists() {
+        if paths.database.exists() {
+            copy_database(&paths.database, &paths.database.with_extension("db.before-import"))
+                .context("Failed to back up existing database")?;
+        }
+        for suffix in ["-wal", "-shm"] {
+            let mut side_file = paths.database.clone().into_os_string();
+            side_file.push(suffix);
+            let _ = fs::remove_file(side_file);
+        }
+        copy_file(&db, &paths.database)?;
+    }
+
+    let config = staging.path().join(CONFIG_FILE);
+    if config.exists() {
+        copy_file(&config, &paths.config)?;
+    }
+
+    copy_dir_all(&staging.path().join(PROJECTS_DIR), &paths.projects_dir)?;
+    copy_dir_all(
+        &staging.path().join(PLUGIN_CONFIG_DIR),
+        &paths.plugin_configs_dir,
+    )?;
+
+    debug!("Imported application state from {}", archive.display());
+    Ok(manifest)
+}
+
+fn copy_file(from: &Path, to: &Path) -> Result<()> {
+    if let Some(parent) = to.parent() {
+        fs::create_dir_all(parent)
+            .with_context(|| format!("Failed to create {}", parent.display()))?;
+    }
+    fs::copy(from, to).with_context(|| format!("Failed to write {}", to.display()))?;
+    Ok(())
+}
+
+fn copy_dir_all(from: &Path, to: &Path) -> Result<()> {
+    if !from.exists() {
+        return Ok(());
+    }
+
+    for entry in fs::read_dir(from)? {
+        let entry = entry?;
+        let target = to.join(entry.file_name());
+        if entry.file_type()?.is_dir() {
+            copy_dir_all(&entry.path(), &target)?;
+        } else {
+            copy_file(&entry.path(), &target)?;
+        }
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn state_paths(root: &Path) -> StatePaths {
+        StatePaths {
+            database: root.join("todos.db"),
+            config: root.join("config.toml"),
+            projects_dir: root.join("projects"),
+            plugin_configs_dir: root.join("plugin-configs"),
+        }
+    }
+
+    fn manifest(plugins: Vec<ExportedPlugin>) -> ExportManifest {
+        ExportManifest {
+            format_version: EXPORT_FORMAT_VERSION,
+            app_version: "0.0.0".to_string(),
+            exported_at: Utc::now(),
+            projects: vec![Project::default_project()],
+            plugins,
+        }
+    }
+
+    #[test]
+    fn test_round_trip() {
+        let source = tempdir().unwrap();
+        let paths = state_paths(source.path());
+
+        let conn = Connection::open(&paths.database).unwrap();
+        conn.execute_batch("CREATE TABLE t (v TEXT); INSERT INTO t VALUES ('kept');")
+            .unwrap();
+        drop(conn);
+        fs::write(&paths.config, "theme = \"dark\"\n").unwrap();
+        let dailies = paths.projects_dir.join("default").join("dailies");
+        fs::create_dir_all(&dailies).unwrap();
+        fs::write(dailies.join("2026-01-05.md"), "- [ ] Task\n").unwrap();
+        let jira_config = paths.plugin_configs_dir.join("jira");
+        fs::create_dir_all(&jira_config).unwrap();
+        fs::write(jira_config.join("config.toml"), "url = \"x\"\n").unwrap();
+
+        let plugins = vec![ExportedPlugin {
+            name: "jira".to_string(),
+            version: "1.2.0".to_string(),
+            source: "acme/totui-plugins".to_string(),
+        }];
+        let archive = source.path().join("export.tar.gz");
+        write_archive(&archive, &paths, &manifest(plugins.clone())).unwrap();
+
+        let target = tempdir().unwrap();
+        let target_paths = state_paths(target.path());
+        let restored = restore_archive(&archive, &target_paths).unwrap();
+
+        assert_eq!(restored.plugins, plugins);
+        assert_eq!(
+            fs::read_to_string(&target_paths.config).unwrap(),
+            "theme = \"dark\"\n"
+        );
+        assert!(target_paths
+            .projects_dir
+            .join("default/dailies/2026-01-05.md")
+            .exists());
+        assert!(target_paths.plugin_configs_dir.join("jira/config.toml").exists());
+
+        let conn = Connection::open(&target_paths.database).unwrap();
+        let value: String = conn.query_row("SELECT v FROM t", [], |r| r.get(0)).unwrap();
+        assert_eq!(value, "kept");
+    }
+
+    #[test]
+    fn test_restore_keeps_previous_database() {
+        let source = tempdir().unwrap();
+        let paths = state_paths(source.path());
+        Connection::open(&paths.database).unwrap();
+        let archive = source.path().join("export.tar.gz");
+        write_archive(&archive, &paths, &manifest(vec![])).unwrap();
+
+        let target = tempdir().unwrap();
+        let target_paths = state_paths(target.path());
+        // A write still in the write-ahead log, as while the TUI runs
+        let running = Connection::open(&target_paths.database).unwrap();
+        running
+            .pragma_update_and_check(None, "journal_mode", "WAL", |row| row.get::<_, String>(0))
+            .unwrap();
+        running
+            .execute_batch("PRAGMA wal_autocheckpoint = 0; CREATE TABLE t (v TEXT); INSERT INTO t VALUES ('old');")
+            .unwrap();
+        restore_archive(&archive, &target_paths).unwrap();
+
+        let backup = Connection::open(target.path().join("todos.db.before-import")).unwrap();
+        let value: String = backup.query_row("SELECT v FROM t", [], |r| r.get(0)).unwrap();
+        assert_eq!(value, "old");
+        drop(running);
+    }
+
+    #[test]
+    fn test_rejects_non_export_archive() {
+        let dir = tempdir().unwrap();
+        let archive = dir.path().join("other.tar.gz");
+        let file = File::create(&archive).unwrap();
+        let mut builder = Builder::new(GzEncoder::new(file, Compression::default()));
+        let readme = dir.path().join("README");
+        fs::write(&readme, "hi").unwrap();
+        builder.append_path_with_name(&readme, "README").unwrap();
+        builder.into_inner().unwrap().finish().unwrap();
+
+        let err = restore_archive(&archive, &state_paths(dir.path())).unwrap_err();
+        assert!(err.to_string().contains("is not a totui export"));
+    }
+
+    #[test]
+    fn test_install_command() {
+        let remote = ExportedPlugin {
+            name: "jira".to_string(),
+            version: "1.2.0".to_string(),
+            source: "acme/plugins".to_string(),
+        };
+        assert_eq!(
+            remote.install_command().unwrap(),
+            "totui plugin install acme/plugins/jira --version 1.2.0"
+        );
+
+        let local = ExportedPlugin {
+            source: "local".to_string(),
+            ..remote
+        };
+        assert!(local.install_command().is_none());
+    }
+}
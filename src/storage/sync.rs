@@ -0,0 +1,234 @@
+//! Change feed for external sync tools.
+//!
+//! Every write to the `todos` table bumps a database-wide counter and stamps
+//! the row with it, and rows deleted outright (archived, purged, dropped from
+//! a list) leave a tombstone with theirs. A consumer keeps the cursor of its
+//! last poll and asks for what changed since, instead of reading every list
+//! again. Triggers do the stamping, so writes from any process are covered.
+
+use super::database::{get_connection, TodoRowData};
+use crate::todo::TodoItem;
+use anyhow::Result;
+use chrono::NaiveDate;
+use rusqlite::params;
+use uuid::Uuid;
+
+/// The counter, the tombstones and the triggers keeping them. Runs after
+/// `todos` has its `change_seq` column; the counter starts past the highest
+/// number already handed out.
+pub(crate) const SYNC_SCHEMA: &str = "
+    CREATE TABLE IF NOT EXISTS sync_state (
+        id INTEGER PRIMARY KEY CHECK (id = 1),
+        change_seq INTEGER NOT NULL
+    );
+    INSERT OR IGNORE INTO sync_state (id, change_seq)
+        VALUES (1, (SELECT COALESCE(MAX(change_seq), 0) FROM todos));
+
+    CREATE TABLE IF NOT EXISTS todo_removals (
+        change_seq INTEGER PRIMARY KEY,
+        id TEXT NOT NULL,
+        project TEXT NOT NULL,
+        date TEXT NOT NULL
+    );
+    CREATE INDEX IF NOT EXISTS idx_todo_removals_project ON todo_removals(project, change_seq);
+
+    CREATE TRIGGER IF NOT EXISTS todos_sync_insert AFTER INSERT ON todos BEGIN
+        UPDATE sync_state SET change_seq = change_seq + 1 WHERE id = 1;
+        UPDATE todos SET change_seq = (SELECT change_seq FROM sync_state WHERE id = 1)
+            WHERE rowid = new.rowid;
+    END;
+    CREATE TRIGGER IF NOT EXISTS todos_sync_update AFTER UPDATE ON todos
+        WHEN new.change_seq = old.change_seq BEGIN
+        UPDATE sync_state SET change_seq = change_seq + 1 WHERE id = 1;
+        UPDATE todos SET change_seq = (SELECT change_seq FROM sync_state WHERE id = 1)
+            WHERE rowid = new.rowid;
+    END;
+    CREATE TRIGGER IF NOT EXISTS todos_sync_delete AFTER DELETE ON todos BEGIN
+        UPDATE sync_state SET change_seq = change_seq + 1 WHERE id = 1;
+        INSERT INTO todo_removals (change_seq, id, project, date)
+            SELECT change_seq, old.id, old.project, old.date FROM sync_state WHERE id = 1;
+    END;
+";
+
+/// One change to a todo, numbered by when it happened.
+#[derive(Debug, Clone)]
+pub enum Change {
+    /// Added or changed; the item as it is now. Soft-deleted items come
+    /// through here with `deleted_at` set.
+    Updated {
+        change_seq: i64,
+        project: String,
+        date: NaiveDate,
+        item: Box<TodoItem>,
+    },
+    /// Gone from the live lists: archived, purged or removed from its list
+    Removed {
+        change_seq: i64,
+        project: String,
+        date: NaiveDate,
+        id: Uuid,
+    },
+}
+
+impl Change {
+    pub fn change_seq(&self) -> i64 {
+        match self {
+            Change::Updated { change_seq, .. } | Change::Removed { change_seq, .. } => *change_seq,
+        }
+    }
+
+    pub fn id(&self) -> Uuid {
+        match self {
+            Change::Updated { item, .. } => item.id,
+            Change::Removed { id, .. } => *id,
+        }
+    }
+}
+
+/// Changes after a cursor, oldest first.
+#[derive(Debug, Clone)]
+pub struct ChangeSet {
+    pub changes: Vec<Change>,
+    /// Where the next poll picks up
+    pub cursor: i64,
+    /// Whether there were more than `limit` changes
+    pub has_more: bool,
+}
+
+/// The newest change number, to start following changes from now on.
+pub fn current_cursor() -> Result<i64> {
+    let conn = get_connection()?;
+    Ok(conn.query_row("SELECT change_seq FROM sync_state WHERE id = 1", [], |row| row.get(0))?)
+}
+
+fn parse_date(date: &str) -> NaiveDate {
+    NaiveDate::parse_from_str(date, "%Y-%m-%d").unwrap_or_default()
+}
+
+/// Up to `limit` changes after `cursor`, only those of `project` if given.
+/// An item changed several times since shows up once, as it is now.
+pub fn changes_since(cursor: i64, project: Option<&str>, limit: usize) -> Result<ChangeSet> {
+    let mut conn = get_connection()?;
+    // One read transaction, so the cursor matches the rows read
+    let tx = conn.transaction()?;
+    let fetch = limit as i64 + 1;
+
+    let mut changes = Vec::new();
+    {
+        let mut stmt = tx.prepare(
+            "SELECT id, content, state, indent_level, parent_id, due_date, description, priority, collapsed, created_at, updated_at, completed_at, deleted_at, estimate_minutes, waiting_on, pinned, link, location, change_seq, project, date
+             FROM todos
+             WHERE change_seq > ?1 AND (?2 IS NULL OR project = ?2)
+             ORDER BY change_seq
+             LIMIT ?3",
+        )?;
+        let rows = stmt.query_map(params![cursor, project, fetch], |row| {
+            let date: String = row.get(20)?;
+            Ok(Change::Updated {
+                change_seq: row.get(18)?,
+                project: row.get(19)?,
+                date: parse_date(&date),
+                item: Box::new(TodoRowData::from_row(row)?.into_todo_item()),
+            })
+        })?;
+        for row in rows {
+            changes.push(row?);
+        }
+
+        let mut stmt = tx.prepare(
+            "SELECT change_seq, id, project, date FROM todo_removals
+             WHERE change_seq > ?1 AND (?2 IS NULL OR project = ?2)
+             ORDER BY change_seq
+             LIMIT ?3",
+        )?;
+        let rows = stmt.query_map(params![cursor, project, fetch], |row| {
+            let id: String = row.get(1)?;
+            let date: String = row.get(3)?;
+            Ok((row.get(0)?, id, row.get(2)?, date))
+        })?;
+        for row in rows {
+            let (change_seq, id, project, date) = row?;
+            let Ok(id) = Uuid::parse_str(&id) else {
+                continue;
+            };
+            changes.push(Change::Removed {
+                change_seq,
+                project,
+                date: parse_date(&date),
+                id,
+            });
+        }
+    }
+
+    changes.sort_by_key(Change::change_seq);
+    let has_more = changes.len() > limit;
+    changes.truncate(limit);
+    let cursor = match changes.last() {
+        Some(change) if has_more => change.change_seq(),
+        // Nothing left to read: skip past changes to other projects too
+        _ => tx.query_row("SELECT change_seq FROM sync_state WHERE id = 1", [], |row| row.get(0))?,
+    };
+    tx.commit()?;
+
+    Ok(ChangeSet {
+        changes,
+        cursor,
+        has_more,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::storage::database::{
+        archive_todos_for_date_and_project, init_database, save_todo_list_for_project,
+        soft_delete_todos_for_project,
+    };
+    use crate::todo::{TodoList, TodoState};
+    use serial_test::serial;
+    use std::path::PathBuf;
+    use tempfile::TempDir;
+
+    #[test]
+    #[serial]
+    fn test_changes_since_follows_writes() {
+        let temp_dir = TempDir::new().unwrap();
+        std::fs::create_dir_all(temp_dir.path().join(".to-tui")).unwrap();
+        unsafe {
+            std::env::set_var("HOME", temp_dir.path());
+        }
+        init_database().unwrap();
+        let date = NaiveDate::from_ymd_opt(2026, 1, 18).unwrap();
+
+        let mut list = TodoList::new(date, PathBuf::from("/tmp/2026-01-18.md"));
+        list.add_item("Milk".to_string());
+        list.add_item("Eggs".to_string());
+        save_todo_list_for_project(&list, "default").unwrap();
+        let mut work = TodoList::new(date, PathBuf::from("/tmp/work.md"));
+        work.add_item("Report".to_string());
+        save_todo_list_for_project(&work, "work").unwrap();
+
+        let first = changes_since(0, Some("default"), 1).unwrap();
+        assert!(first.has_more);
+        assert_eq!(first.changes[0].id(), list.items[0].id);
+        let rest = changes_since(first.cursor, Some("default"), 10).unwrap();
+        assert!(!rest.has_more);
+        assert_eq!(rest.changes.len(), 1);
+        assert_eq!(rest.cursor, current_cursor().unwrap());
+
+        // Saving without changes leaves the feed alone
+        save_todo_list_for_project(&list, "default").unwrap();
+        assert!(changes_since(rest.cursor, None, 10).unwrap().changes.is_empty());
+
+        list.items[0].set_state(TodoState::Checked);
+        save_todo_list_for_project(&list, "default").unwrap();
+        soft_delete_todos_for_project(&[list.items[1].id], date, "default").unwrap();
+        archive_todos_for_date_and_project(date, "work").unwrap();
+
+        let changes = changes_since(rest.cursor, None, 10).unwrap().changes;
+        assert_eq!(changes.len(), 3);
+        assert!(matches!(&changes[0], Change::Updated { item, .. } if item.state == TodoState::Checked));
+        assert!(matches!(&changes[1], Change::Updated { item, .. } if item.deleted_at.is_some()));
+        assert!(matches!(&changes[2], Change::Removed { id, project, .. } if *id == work.items[0].id && project == "work"));
+    }
+}
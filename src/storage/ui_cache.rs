@@ -1,14 +1,38 @@
 use anyhow::Result;
+use chrono::{Duration, NaiveDate};
 use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
 use std::fs;
 use uuid::Uuid;
 
+use crate::todo::Filter;
 use crate::utils::paths::get_ui_cache_path;
 
+/// Views older than this many days before the one being saved are dropped
+const VIEW_RETENTION_DAYS: i64 = 30;
+
+/// How the list looked for one project and day.
+#[derive(Debug, Clone, Serialize, Deserialize, Default, PartialEq)]
+pub struct CachedView {
+    pub selected_todo_id: Option<Uuid>,
+    /// First visible row of the list
+    #[serde(default)]
+    pub scroll_offset: usize,
+    #[serde(default)]
+    pub active_filters: Vec<Filter>,
+    #[serde(default)]
+    pub show_waiting_only: bool,
+}
+
 #[derive(Debug, Clone, Serialize, Deserialize, Default)]
 pub struct UiCache {
-    /// The ID of the currently selected todo item
+    /// The ID of the currently selected todo item. Only read from caches
+    /// written before `views` existed.
+    #[serde(default)]
     pub selected_todo_id: Option<Uuid>,
+    /// Views keyed by "project/date"
+    #[serde(default)]
+    pub views: HashMap<String, CachedView>,
 }
 
 impl UiCache {
@@ -29,6 +53,34 @@ impl UiCache {
         fs::write(&path, content)?;
         Ok(())
     }
+
+    fn key(project: &str, date: NaiveDate) -> String {
+        format!("{project}/{date}")
+    }
+
+    /// The saved view for a project and day, falling back to the selection
+    /// stored by older versions.
+    pub fn view(&self, project: &str, date: NaiveDate) -> Option<CachedView> {
+        self.views.get(&Self::key(project, date)).cloned().or_else(|| {
+            self.selected_todo_id.map(|id| CachedView {
+                selected_todo_id: Some(id),
+                ..CachedView::default()
+            })
+        })
+    }
+
+    /// Remember the view for a project and day, forgetting views more than
+    /// a month older than it.
+    pub fn set_view(&mut self, project: &str, date: NaiveDate, view: CachedView) {
+        let cutoff = date - Duration::days(VIEW_RETENTION_DAYS);
+        self.views.retain(|key, _| {
+            key.rsplit_once('/')
+                .and_then(|(_, day)| day.parse::<NaiveDate>().ok())
+                .is_some_and(|day| day >= cutoff)
+        });
+        self.views.insert(Self::key(project, date), view);
+        self.selected_todo_id = None;
+    }
 }
 
 #[cfg(test)]
@@ -46,6 +98,7 @@ mod tests {
         let todo_id = Uuid::new_v4();
         let cache = UiCache {
             selected_todo_id: Some(todo_id),
+            ..UiCache::default()
         };
 
         let json = serde_json::to_string(&cache).unwrap();
@@ -58,6 +111,7 @@ mod tests {
     fn test_serialize_none() {
         let cache = UiCache {
             selected_todo_id: None,
+            ..UiCache::default()
         };
 
         let json = serde_json::to_string(&cache).unwrap();
@@ -65,4 +119,35 @@ mod tests {
 
         assert!(loaded.selected_todo_id.is_none());
     }
+
+    #[test]
+    fn test_views_keyed_by_project_and_date() {
+        let day = NaiveDate::from_ymd_opt(2026, 3, 10).unwrap();
+        let view = CachedView {
+            selected_todo_id: Some(Uuid::new_v4()),
+            scroll_offset: 7,
+            active_filters: vec![Filter::HideCompleted, Filter::Tag("work".to_string())],
+            show_waiting_only: true,
+        };
+        let mut cache = UiCache::default();
+        cache.set_view("work", day, view.clone());
+
+        let json = serde_json::to_string(&cache).unwrap();
+        let loaded: UiCache = serde_json::from_str(&json).unwrap();
+        assert_eq!(loaded.view("work", day), Some(view));
+        assert_eq!(loaded.view("home", day), None);
+
+        // Saving a much later day forgets the old one
+        cache.set_view("work", day + Duration::days(40), CachedView::default());
+        assert_eq!(cache.views.len(), 1);
+    }
+
+    #[test]
+    fn test_old_cache_selection_still_restored() {
+        let id = Uuid::new_v4();
+        let json = format!(r#"{{"selected_todo_id": "{id}"}}"#);
+        let loaded: UiCache = serde_json::from_str(&json).unwrap();
+        let day = NaiveDate::from_ymd_opt(2026, 3, 10).unwrap();
+        assert_eq!(loaded.view("default", day).and_then(|v| v.selected_todo_id), Some(id));
+    }
 }
@@ -0,0 +1,138 @@
+use anyhow::Result;
+use chrono::{DateTime, Duration, Local, NaiveDate};
+use serde::{Deserialize, Serialize};
+use std::fs;
+
+use crate::storage::encryption;
+use crate::todo::TodoList;
+use crate::utils::paths::get_undo_history_path;
+use uuid::Uuid;
+
+/// Snapshots older than this are dropped when the history is saved
+const RETENTION_HOURS: i64 = 24;
+/// Oldest snapshots are dropped until the file fits in this many bytes
+const MAX_FILE_BYTES: usize = 4 * 1024 * 1024;
+
+/// A list as it was before a change.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct UndoSnapshot {
+    pub list: TodoList,
+    pub cursor: usize,
+    pub saved_at: DateTime<Local>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub moved: Option<MovedItems>,
+}
+
+/// Items a change put on another day of the same project, taken off it
+/// again when the change is undone.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct MovedItems {
+    pub date: NaiveDate,
+    /// Each item's id before the move and its id on `date`
+    pub ids: Vec<(Uuid, Uuid)>,
+}
+
+/// The undo stack of the day the TUI last showed, kept on disk so `u` still
+/// works after a restart or a crash.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct UndoHistory {
+    pub project: String,
+    pub date: NaiveDate,
+    /// Oldest first, like the undo stack
+    pub snapshots: Vec<UndoSnapshot>,
+}
+
+impl UndoHistory {
+    pub fn load() -> Result<Option<Self>> {
+        let path = get_undo_history_path()?;
+        if !path.exists() {
+            return Ok(None);
+        }
+
+        let content = encryption::read_to_string(&path)?;
+        Ok(Some(serde_json::from_str(&content)?))
+    }
+
+    /// Write the history, leaving out snapshots older than a day and the
+    /// oldest ones beyond the size limit.
+    pub fn save(mut self) -> Result<()> {
+        let cutoff = Local::now() - Duration::hours(RETENTION_HOURS);
+        self.snapshots.retain(|snapshot| snapshot.saved_at >= cutoff);
+
+        let mut content = serde_json::to_string(&self)?;
+        while content.len() > MAX_FILE_BYTES && !self.snapshots.is_empty() {
+            let excess = content.len() - MAX_FILE_BYTES;
+            // Snapshots are of one day's list, so they're about the same size
+            let per_snapshot = content.len() / self.snapshots.len();
+            let drop = excess.div_ceil(per_snapshot.max(1)).clamp(1, self.snapshots.len());
+            self.snapshots.drain(..drop);
+            content = serde_json::to_string(&self)?;
+        }
+
+        let path = get_undo_history_path()?;
+        let temp_path = path.with_extension("tmp");
+        fs::write(&temp_path, encryption::seal(&content)?)?;
+        fs::rename(&temp_path, &path)?;
+        Ok(())
+    }
+
+    /// The snapshots, if they are of `project`'s list for `date`.
+    pub fn into_snapshots_for(self, project: &str, date: NaiveDate) -> Vec<UndoSnapshot> {
+        if self.project == project && self.date == date {
+            self.snapshots
+        } else {
+            Vec::new()
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serial_test::serial;
+    use std::path::PathBuf;
+    use tempfile::TempDir;
+
+    fn snapshot(date: NaiveDate, saved_at: DateTime<Local>) -> UndoSnapshot {
+        let mut list = TodoList::new(date, PathBuf::from("/tmp/list.md"));
+        list.add_item("Milk".to_string());
+        UndoSnapshot {
+            list,
+            cursor: 0,
+            saved_at,
+            moved: None,
+        }
+    }
+
+    #[test]
+    #[serial]
+    fn test_save_drops_old_snapshots_and_load_round_trips() {
+        let temp_dir = TempDir::new().unwrap();
+        std::fs::create_dir_all(temp_dir.path().join(".to-tui")).unwrap();
+        unsafe {
+            std::env::set_var("HOME", temp_dir.path());
+        }
+        let date = NaiveDate::from_ymd_opt(2026, 1, 18).unwrap();
+        let now = Local::now();
+
+        UndoHistory {
+            project: "default".to_string(),
+            date,
+            snapshots: vec![
+                snapshot(date, now - Duration::hours(30)),
+                snapshot(date, now - Duration::minutes(5)),
+                snapshot(date, now),
+            ],
+        }
+        .save()
+        .unwrap();
+
+        let loaded = UndoHistory::load().unwrap().unwrap();
+        assert_eq!(loaded.snapshots.len(), 2);
+        let milk = &loaded.snapshots[0].list.items[0];
+        assert_eq!(milk.content, "Milk");
+
+        assert!(loaded.clone().into_snapshots_for("work", date).is_empty());
+        assert_eq!(loaded.into_snapshots_for("default", date).len(), 2);
+    }
+}
@@ -0,0 +1,36 @@
+//! Noticing writes to the database from other processes.
+//!
+//! The TUI, the API server, the MCP server and the command line all write
+//! the same SQLite file. Watching it is how each of them learns that another
+//! one changed something.
+
+use crate::utils::paths::get_database_path;
+use notify::{Config, RecommendedWatcher, RecursiveMode, Watcher};
+
+/// Call `on_change` whenever the database is written to. Writes come in
+/// bursts, so callers should expect several calls per change. Watching
+/// stops when the returned watcher is dropped; None if it couldn't start.
+pub fn watch_database(on_change: impl Fn() + Send + 'static) -> Option<RecommendedWatcher> {
+    let db_path = get_database_path().ok()?;
+    let db_dir = db_path.parent()?.to_path_buf();
+    // In WAL mode other processes' writes land in todos.db-wal until a
+    // checkpoint, so watch the directory for both files
+    let mut wal_path = db_path.clone().into_os_string();
+    wal_path.push("-wal");
+    let watched = [db_path, wal_path.into()];
+
+    let mut watcher = RecommendedWatcher::new(
+        move |res: Result<notify::Event, notify::Error>| {
+            if let Ok(event) = res
+                && (event.kind.is_modify() || event.kind.is_create())
+                && event.paths.iter().any(|path| watched.contains(path))
+            {
+                on_change();
+            }
+        },
+        Config::default(),
+    )
+    .ok()?;
+    watcher.watch(&db_dir, RecursiveMode::NonRecursive).ok()?;
+    Some(watcher)
+}
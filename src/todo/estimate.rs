@@ -0,0 +1,159 @@
+use serde::{Deserialize, Serialize};
+use std::fmt;
+use std::str::FromStr;
+
+use super::tag::take_tag;
+
+/// Effort estimate for a todo item, stored as whole minutes.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash, Serialize, Deserialize)]
+pub struct Estimate(u32);
+
+impl Estimate {
+    pub fn from_minutes(minutes: u32) -> Self {
+        Self(minutes)
+    }
+
+    pub fn minutes(self) -> u32 {
+        self.0
+    }
+
+    /// Compact form used in markdown tags, e.g. `30m`, `2h`, `1h30m`
+    pub fn to_tag_str(self) -> String {
+        let (hours, minutes) = (self.0 / 60, self.0 % 60);
+        match (hours, minutes) {
+            (0, m) => format!("{m}m"),
+            (h, 0) => format!("{h}h"),
+            (h, m) => format!("{h}h{m}m"),
+        }
+    }
+}
+
+impl std::ops::Add for Estimate {
+    type Output = Estimate;
+
+    fn add(self, other: Estimate) -> Estimate {
+        Estimate(self.0.saturating_add(other.0))
+    }
+}
+
+impl std::iter::Sum for Estimate {
+    fn sum<I: Iterator<Item = Estimate>>(iter: I) -> Estimate {
+        iter.fold(Estimate(0), |acc, e| acc + e)
+    }
+}
+
+impl fmt::Display for Estimate {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let (hours, minutes) = (self.0 / 60, self.0 % 60);
+        match (hours, minutes) {
+            (0, m) => write!(f, "{m}m"),
+            (h, 0) => write!(f, "{h}h"),
+            (h, m) => write!(f, "{h}h {m}m"),
+        }
+    }
+}
+
+impl FromStr for Estimate {
+    type Err = String;
+
+    /// Accepts `30m`, `2h`, `1h30m`, `1h 30m`, `1.5h` and bare minutes (`45`).
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let invalid = || format!("Invalid estimate: {s} (use e.g. 30m, 2h, 1h30m)");
+        let normalized: String = s.trim().to_lowercase().split_whitespace().collect();
+        if normalized.is_empty() {
+            return Err(invalid());
+        }
+
+        if let Ok(minutes) = normalized.parse::<u32>() {
+            return Ok(Estimate(minutes));
+        }
+
+        let mut total = 0.0_f64;
+        let mut number = String::new();
+        for c in normalized.chars() {
+            match c {
+                '0'..='9' | '.' => number.push(c),
+                'h' | 'm' => {
+                    let value: f64 = number.parse().map_err(|_| invalid())?;
+                    total += if c == 'h' { value * 60.0 } else { value };
+                    number.clear();
+                }
+                _ => return Err(invalid()),
+            }
+        }
+        if !number.is_empty() || total > u32::MAX as f64 {
+            return Err(invalid());
+        }
+
+        Ok(Estimate(total.round() as u32))
+    }
+}
+
+/// Strip an `@est(...)` tag from free text, returning the text and the estimate.
+///
+/// A tag with an unparseable estimate is left in place so the user can fix it.
+pub fn extract_estimate_tag(text: &str) -> (String, Option<Estimate>) {
+    match take_tag(text, "est") {
+        Some((cleaned, value)) => match value.parse::<Estimate>() {
+            Ok(estimate) => (cleaned, Some(estimate)),
+            Err(_) => (text.to_string(), None),
+        },
+        None => (text.to_string(), None),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse() {
+        assert_eq!("30m".parse::<Estimate>().unwrap().minutes(), 30);
+        assert_eq!("2h".parse::<Estimate>().unwrap().minutes(), 120);
+        assert_eq!("1h30m".parse::<Estimate>().unwrap().minutes(), 90);
+        assert_eq!("1h 30m".parse::<Estimate>().unwrap().minutes(), 90);
+        assert_eq!("1.5h".parse::<Estimate>().unwrap().minutes(), 90);
+        assert_eq!(" 45 ".parse::<Estimate>().unwrap().minutes(), 45);
+        assert_eq!("2H".parse::<Estimate>().unwrap().minutes(), 120);
+    }
+
+    #[test]
+    fn test_parse_invalid() {
+        assert!("".parse::<Estimate>().is_err());
+        assert!("soon".parse::<Estimate>().is_err());
+        assert!("1h30".parse::<Estimate>().is_err());
+        assert!("h".parse::<Estimate>().is_err());
+    }
+
+    #[test]
+    fn test_display() {
+        assert_eq!(Estimate::from_minutes(30).to_string(), "30m");
+        assert_eq!(Estimate::from_minutes(120).to_string(), "2h");
+        assert_eq!(Estimate::from_minutes(270).to_string(), "4h 30m");
+    }
+
+    #[test]
+    fn test_tag_round_trip() {
+        for minutes in [5, 60, 90, 270] {
+            let estimate = Estimate::from_minutes(minutes);
+            assert_eq!(estimate.to_tag_str().parse::<Estimate>().unwrap(), estimate);
+        }
+    }
+
+    #[test]
+    fn test_sum() {
+        let total: Estimate = [30, 90, 150].into_iter().map(Estimate::from_minutes).sum();
+        assert_eq!(total.to_string(), "4h 30m");
+    }
+
+    #[test]
+    fn test_extract_estimate_tag() {
+        let (text, estimate) = extract_estimate_tag("Write report @est(1h30m) today");
+        assert_eq!(text, "Write report today");
+        assert_eq!(estimate, Some(Estimate::from_minutes(90)));
+
+        let (text, estimate) = extract_estimate_tag("Write report @est(later)");
+        assert_eq!(text, "Write report @est(later)");
+        assert_eq!(estimate, None);
+    }
+}
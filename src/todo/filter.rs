@@ -0,0 +1,167 @@
+//! View filters that narrow the list without changing it.
+//!
+//! Filters of the same kind are alternatives (`[!]` or `[?]`), filters of
+//! different kinds must all match (`[!]` and P0).
+
+use serde::{Deserialize, Serialize};
+use std::fmt;
+use std::mem;
+use std::str::FromStr;
+
+use super::{Priority, TodoItem, TodoState};
+
+#[derive(Debug, Clone, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[serde(into = "String", try_from = "String")]
+pub enum Filter {
+    /// Hide done and cancelled items
+    HideCompleted,
+    /// Only items in this state
+    State(TodoState),
+    /// Only items with this priority
+    Priority(Priority),
+    /// Only items mentioning `#tag` (stored without the `#`, lowercase)
+    Tag(String),
+}
+
+impl Filter {
+    /// Tag filter from user input; a leading `#` is optional.
+    pub fn tag(input: &str) -> Option<Filter> {
+        let tag = input.trim().trim_start_matches('#');
+        (!tag.is_empty() && !tag.contains(char::is_whitespace))
+            .then(|| Filter::Tag(tag.to_lowercase()))
+    }
+
+    pub fn matches(&self, item: &TodoItem) -> bool {
+        match self {
+            Filter::HideCompleted => !item.state.is_complete(),
+            Filter::State(state) => item.state == *state,
+            Filter::Priority(priority) => item.priority == Some(*priority),
+            Filter::Tag(tag) => tags(&item.content).any(|t| t.eq_ignore_ascii_case(tag)),
+        }
+    }
+}
+
+impl fmt::Display for Filter {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Filter::HideCompleted => write!(f, "hide done"),
+            Filter::State(state) => write!(f, "[{}]", state.to_char()),
+            Filter::Priority(priority) => write!(f, "{priority}"),
+            Filter::Tag(tag) => write!(f, "#{tag}"),
+        }
+    }
+}
+
+/// Parses the `Display` form: `hide done`, `[x]`, `P1` or `#tag`.
+impl FromStr for Filter {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let invalid = || format!("Invalid filter: {s}");
+        if s == "hide done" {
+            return Ok(Filter::HideCompleted);
+        }
+        if s.starts_with('#') {
+            return Filter::tag(s).ok_or_else(invalid);
+        }
+        if let Some(c) = s.strip_prefix('[').and_then(|rest| rest.strip_suffix(']')) {
+            let mut chars = c.chars();
+            return match (chars.next(), chars.next()) {
+                (Some(c), None) => TodoState::from_char(c).map(Filter::State).ok_or_else(invalid),
+                _ => Err(invalid()),
+            };
+        }
+        s.parse().map(Filter::Priority).map_err(|_| invalid())
+    }
+}
+
+impl From<Filter> for String {
+    fn from(filter: Filter) -> Self {
+        filter.to_string()
+    }
+}
+
+impl TryFrom<String> for Filter {
+    type Error = String;
+
+    fn try_from(s: String) -> Result<Self, Self::Error> {
+        s.parse()
+    }
+}
+
+/// Whether `item` passes every kind of filter in `filters`.
+pub fn passes(filters: &[Filter], item: &TodoItem) -> bool {
+    filters.iter().map(mem::discriminant).all(|kind| {
+        filters
+            .iter()
+            .filter(|filter| mem::discriminant(*filter) == kind)
+            .any(|filter| filter.matches(item))
+    })
+}
+
+/// `#tag` words in `text`, without the `#`.
+pub fn tags(text: &str) -> impl Iterator<Item = &str> {
+    text.split_whitespace().filter_map(|word| {
+        let tag = word
+            .trim_start_matches(['(', '['])
+            .strip_prefix('#')?
+            .trim_end_matches(|c: char| !c.is_alphanumeric());
+        (!tag.is_empty()).then_some(tag)
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn item(content: &str, state: TodoState, priority: Option<Priority>) -> TodoItem {
+        let mut item = TodoItem::new(content.to_string(), 0);
+        item.state = state;
+        item.priority = priority;
+        item
+    }
+
+    #[test]
+    fn test_tags() {
+        let found: Vec<_> = tags("Fix #login bug (#auth-v2), not a#tag or #").collect();
+        assert_eq!(found, vec!["login", "auth-v2"]);
+    }
+
+    #[test]
+    fn test_same_kind_is_or_different_kinds_are_and() {
+        let filters = vec![
+            Filter::State(TodoState::Exclamation),
+            Filter::State(TodoState::Question),
+            Filter::Priority(Priority::P0),
+        ];
+        assert!(passes(&filters, &item("a", TodoState::Question, Some(Priority::P0))));
+        assert!(!passes(&filters, &item("b", TodoState::Question, Some(Priority::P1))));
+        assert!(!passes(&filters, &item("c", TodoState::Empty, Some(Priority::P0))));
+        assert!(passes(&[], &item("d", TodoState::Checked, None)));
+    }
+
+    #[test]
+    fn test_hide_completed_and_tag() {
+        let filters = vec![Filter::HideCompleted, Filter::tag("#Work").unwrap()];
+        assert!(passes(&filters, &item("Deploy #work", TodoState::InProgress, None)));
+        assert!(!passes(&filters, &item("Deploy #work", TodoState::Cancelled, None)));
+        assert!(!passes(&filters, &item("Laundry #home", TodoState::Empty, None)));
+        assert_eq!(Filter::tag("two words"), None);
+    }
+
+    #[test]
+    fn test_filters_round_trip_through_strings() {
+        let filters = vec![
+            Filter::HideCompleted,
+            Filter::State(TodoState::Empty),
+            Filter::State(TodoState::Question),
+            Filter::Priority(Priority::P1),
+            Filter::Tag("work".to_string()),
+        ];
+        for filter in filters {
+            assert_eq!(filter.to_string().parse::<Filter>(), Ok(filter));
+        }
+        assert!("[xx]".parse::<Filter>().is_err());
+        assert!("P".parse::<Filter>().is_err());
+    }
+}
@@ -1,9 +1,12 @@
+use super::estimate::Estimate;
+use super::location::Location;
 use super::priority::Priority;
 use super::state::TodoState;
 use chrono::{DateTime, NaiveDate, Utc};
+use serde::{Deserialize, Serialize};
 use uuid::Uuid;
 
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct TodoItem {
     pub id: Uuid,
     pub content: String,
@@ -13,6 +16,18 @@ pub struct TodoItem {
     pub due_date: Option<NaiveDate>,
     pub description: Option<String>,
     pub priority: Option<Priority>,
+    pub estimate: Option<Estimate>,
+    pub waiting_on: Option<String>,
+    /// Pinned items are kept above their unpinned siblings
+    pub pinned: bool,
+    /// Source URL (ticket, PR, meeting), kept apart from the content
+    pub link: Option<String>,
+    /// File and line the item was pushed from by an editor
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub location: Option<Location>,
+    /// Per-day short id (`a3`), assigned when the item is first saved
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub short_id: Option<String>,
     pub collapsed: bool,
     pub created_at: DateTime<Utc>,
     pub modified_at: DateTime<Utc>,
@@ -32,6 +47,12 @@ impl TodoItem {
             due_date: None,
             description: None,
             priority: None,
+            estimate: None,
+            waiting_on: None,
+            pinned: false,
+            link: None,
+            location: None,
+            short_id: None,
             collapsed: false,
             created_at: now,
             modified_at: now,
@@ -57,6 +78,12 @@ impl TodoItem {
             due_date: None,
             description: None,
             priority: None,
+            estimate: None,
+            waiting_on: None,
+            pinned: false,
+            link: None,
+            location: None,
+            short_id: None,
             collapsed: false,
             created_at: now,
             modified_at: now,
@@ -91,6 +118,12 @@ impl TodoItem {
             due_date,
             description,
             priority,
+            estimate: None,
+            waiting_on: None,
+            pinned: false,
+            link: None,
+            location: None,
+            short_id: None,
             collapsed,
             created_at: now,
             modified_at: now,
@@ -111,6 +144,12 @@ impl TodoItem {
         self.update_completed_at(was_complete);
     }
 
+    pub fn set_state(&mut self, state: TodoState) {
+        let was_complete = self.state.is_complete();
+        self.state = state;
+        self.update_completed_at(was_complete);
+    }
+
     fn update_completed_at(&mut self, was_complete: bool) {
         let is_complete = self.state.is_complete();
         self.modified_at = Utc::now();
@@ -125,6 +164,23 @@ impl TodoItem {
         self.state.is_complete()
     }
 
+    /// Due before `today` and not yet done.
+    pub fn is_overdue(&self, today: NaiveDate) -> bool {
+        self.due_date.is_some_and(|due| due < today) && !self.is_complete()
+    }
+
+    /// Delegated to someone else and not yet done.
+    pub fn is_waiting(&self) -> bool {
+        self.waiting_on.is_some() && !self.is_complete()
+    }
+
+    /// Set the link from user input; blank input clears it.
+    pub fn set_link(&mut self, input: &str) -> Result<(), String> {
+        self.link = normalize_link(input)?;
+        self.modified_at = Utc::now();
+        Ok(())
+    }
+
     #[cfg(test)]
     pub fn can_indent(&self, prev_indent: Option<usize>) -> bool {
         match prev_indent {
@@ -151,6 +207,50 @@ impl TodoItem {
     }
 }
 
+/// Validate a link, adding `https://` to bare hosts like `github.com/org/repo`.
+/// Only http, https and mailto links are accepted, since they are handed to
+/// the system opener.
+pub fn normalize_link(input: &str) -> Result<Option<String>, String> {
+    let link = input.trim();
+    if link.is_empty() {
+        return Ok(None);
+    }
+    if link.chars().any(char::is_whitespace) || !balanced_parens(link) {
+        return Err(format!("Invalid link: {link}"));
+    }
+    let lower = link.to_ascii_lowercase();
+    if ["http://", "https://", "mailto:"].iter().any(|scheme| lower.starts_with(scheme)) {
+        return Ok(Some(link.to_string()));
+    }
+    let has_scheme = link.split_once(':').is_some_and(|(scheme, _)| {
+        !scheme.is_empty() && scheme.chars().all(|c| c.is_ascii_alphanumeric() || "+-".contains(c))
+    });
+    if has_scheme {
+        return Err(format!("Invalid link: {link} (only http, https and mailto links are supported)"));
+    }
+    if link.split('/').next().is_some_and(|host| host.contains('.')) {
+        return Ok(Some(format!("https://{link}")));
+    }
+    Err(format!("Invalid link: {link} (expected a URL)"))
+}
+
+/// Whether every `)` closes an earlier `(`, so the link can be written
+/// inside `@link(...)` and read back whole.
+fn balanced_parens(link: &str) -> bool {
+    let mut depth = 0usize;
+    for c in link.chars() {
+        match c {
+            '(' => depth += 1,
+            ')' => match depth.checked_sub(1) {
+                Some(d) => depth = d,
+                None => return false,
+            },
+            _ => {}
+        }
+    }
+    depth == 0
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -257,4 +357,51 @@ mod tests {
         item.outdent(); // Should not go negative
         assert_eq!(item.indent_level, 0);
     }
+
+    #[test]
+    fn test_normalize_link() {
+        assert_eq!(
+            normalize_link("https://example.com/a?b=1").unwrap().as_deref(),
+            Some("https://example.com/a?b=1")
+        );
+        assert_eq!(
+            normalize_link(" github.com/org/repo/pull/7 ").unwrap().as_deref(),
+            Some("https://github.com/org/repo/pull/7")
+        );
+        assert_eq!(normalize_link("").unwrap(), None);
+        assert!(normalize_link("not a link").is_err());
+        assert!(normalize_link("PROJ-123").is_err());
+        assert_eq!(
+            normalize_link("https://en.wikipedia.org/wiki/Rust_(programming_language)").unwrap().as_deref(),
+            Some("https://en.wikipedia.org/wiki/Rust_(programming_language)")
+        );
+        assert!(normalize_link("https://example.com/a)b").is_err());
+        assert_eq!(
+            normalize_link("mailto:me@example.com").unwrap().as_deref(),
+            Some("mailto:me@example.com")
+        );
+        assert!(normalize_link("file:///etc/passwd").is_err());
+        assert!(normalize_link("javascript:alert(1)").is_err());
+        assert!(normalize_link("ssh://host.example.com").is_err());
+        assert_eq!(
+            normalize_link("example.com:8080/status").unwrap().as_deref(),
+            Some("https://example.com:8080/status")
+        );
+    }
+
+    #[test]
+    fn test_is_overdue() {
+        let today = NaiveDate::from_ymd_opt(2026, 3, 10).unwrap();
+        let mut item = TodoItem::new("Invoice".to_string(), 0);
+        assert!(!item.is_overdue(today));
+
+        item.due_date = NaiveDate::from_ymd_opt(2026, 3, 9);
+        assert!(item.is_overdue(today));
+        item.due_date = Some(today);
+        assert!(!item.is_overdue(today));
+
+        item.due_date = NaiveDate::from_ymd_opt(2026, 3, 1);
+        item.set_state(TodoState::Checked);
+        assert!(!item.is_overdue(today));
+    }
 }
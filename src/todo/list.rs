@@ -1,12 +1,17 @@
+use super::estimate::Estimate;
+use super::filter::{self, Filter};
 use super::priority::Priority;
+use super::short_id::assign_short_ids;
+use super::state::TodoState;
 use super::TodoItem;
 use anyhow::{anyhow, Result};
 use chrono::NaiveDate;
-use std::collections::HashSet;
+use serde::{Deserialize, Serialize};
+use std::collections::{HashMap, HashSet};
 use std::path::PathBuf;
 use uuid::Uuid;
 
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct TodoList {
     pub date: NaiveDate,
     pub items: Vec<TodoItem>,
@@ -79,6 +84,59 @@ impl TodoList {
         }
     }
 
+    /// Sum of estimates for the day, ignoring cancelled items.
+    /// Returns None when no item carries an estimate.
+    pub fn total_estimate(&self) -> Option<Estimate> {
+        let mut estimates = self
+            .items
+            .iter()
+            .filter(|item| item.state != TodoState::Cancelled)
+            .filter_map(|item| item.estimate)
+            .peekable();
+        estimates.peek()?;
+        Some(estimates.sum())
+    }
+
+    /// Done and total work for the day's progress bar, ignoring cancelled
+    /// items. Counted in estimate minutes when any item has an estimate,
+    /// otherwise in items.
+    pub fn progress(&self) -> (u32, u32) {
+        let active = self.items.iter().filter(|item| item.state != TodoState::Cancelled);
+        let weight: fn(&TodoItem) -> u32 = if self.total_estimate().is_some() {
+            |item| item.estimate.map_or(0, Estimate::minutes)
+        } else {
+            |_| 1
+        };
+        active.fold((0, 0), |(done, total), item| {
+            let w = weight(item);
+            let done = if item.state == TodoState::Checked { done + w } else { done };
+            (done, total + w)
+        })
+    }
+
+    /// Short ids (`a3`, `k7`, ...) for every item, see [`super::short_id`].
+    pub fn short_ids(&self) -> HashMap<Uuid, String> {
+        assign_short_ids(&self.items)
+    }
+
+    /// Give every item the short id it is saved with, so ids shown before
+    /// the next load stay the same however the list changes.
+    pub fn fill_short_ids(&mut self) {
+        let mut ids = self.short_ids();
+        for item in &mut self.items {
+            item.short_id = ids.remove(&item.id);
+        }
+    }
+
+    /// Index of the item with the given short id (case-insensitive).
+    pub fn find_by_short_id(&self, short_id: &str) -> Option<usize> {
+        let short_id = short_id.to_lowercase();
+        let ids = self.short_ids();
+        self.items
+            .iter()
+            .position(|item| ids.get(&item.id) == Some(&short_id))
+    }
+
     #[cfg(test)]
     pub fn toggle_item_state(&mut self, index: usize) -> Result<()> {
         if index >= self.items.len() {
@@ -116,6 +174,37 @@ impl TodoList {
         hidden
     }
 
+    /// Indices of items waiting on someone else, plus their ancestors for context
+    pub fn waiting_visible_indices(&self) -> HashSet<usize> {
+        self.matching_with_ancestors(TodoItem::is_waiting)
+    }
+
+    /// Indices of items passing all view filters, plus their ancestors for context
+    pub fn filter_visible_indices(&self, filters: &[Filter]) -> HashSet<usize> {
+        self.matching_with_ancestors(|item| filter::passes(filters, item))
+    }
+
+    fn matching_with_ancestors(&self, matches: impl Fn(&TodoItem) -> bool) -> HashSet<usize> {
+        let mut visible = HashSet::new();
+        for (i, item) in self.items.iter().enumerate() {
+            if !matches(item) {
+                continue;
+            }
+            visible.insert(i);
+            let mut indent = item.indent_level;
+            for j in (0..i).rev() {
+                if indent == 0 {
+                    break;
+                }
+                if self.items[j].indent_level < indent {
+                    visible.insert(j);
+                    indent = self.items[j].indent_level;
+                }
+            }
+        }
+        visible
+    }
+
     pub fn remove_item_range(&mut self, start: usize, end: usize) -> Result<Vec<TodoItem>> {
         if start >= self.items.len() || end > self.items.len() || start >= end {
             return Err(anyhow!("Invalid range"));
@@ -138,32 +227,49 @@ impl TodoList {
     }
 
     /// Sort todos by priority at every level, keeping children grouped with their parents.
-    /// Sort order: P0 (highest) -> P1 -> P2 -> None (lowest)
+    /// Sort order: pinned first, then P0 (highest) -> P1 -> P2 -> None (lowest)
     /// Within same priority, maintains original relative order (stable sort).
     pub fn sort_by_priority(&mut self) {
+        self.sort_siblings_by_key(|item| {
+            (!item.pinned, item.priority.map_or(u8::MAX, Priority::level))
+        });
+    }
+
+    /// Sort siblings by due date, soonest first and undated last, keeping pinned items on top.
+    pub fn sort_by_due(&mut self) {
+        self.sort_siblings_by_key(|item| (!item.pinned, item.due_date.is_none(), item.due_date));
+    }
+
+    /// Move pinned items above their unpinned siblings, keeping order otherwise.
+    pub fn float_pinned(&mut self) {
+        self.sort_siblings_by_key(|item| !item.pinned);
+    }
+
+    /// Move done and cancelled items below their open siblings, keeping
+    /// pinned items on top and order otherwise.
+    pub fn sink_completed(&mut self) {
+        self.sort_siblings_by_key(|item| (!item.pinned, item.state.is_complete()));
+    }
+
+    /// Stable-sort each group of siblings (with their subtrees) by `key`.
+    fn sort_siblings_by_key<K: Ord>(&mut self, key: impl Fn(&TodoItem) -> K) {
         if self.items.is_empty() {
             return;
         }
 
-        // Helper function to get sort key for priority
-        fn priority_sort_key(priority: Option<Priority>) -> u8 {
-            match priority {
-                Some(Priority::P0) => 0,
-                Some(Priority::P1) => 1,
-                Some(Priority::P2) => 2,
-                None => 3,
-            }
-        }
-
         // Recursively sort items at a given indent level
         // Returns sorted items with their subtrees
-        fn sort_at_level(items: &[TodoItem], target_level: usize) -> Vec<TodoItem> {
+        fn sort_at_level<K: Ord>(
+            items: &[TodoItem],
+            target_level: usize,
+            key: &impl Fn(&TodoItem) -> K,
+        ) -> Vec<TodoItem> {
             if items.is_empty() {
                 return Vec::new();
             }
 
             // Group items at target_level with their children
-            let mut groups: Vec<(u8, Vec<TodoItem>)> = Vec::new();
+            let mut groups: Vec<(K, Vec<TodoItem>)> = Vec::new();
             let mut i = 0;
 
             while i < items.len() {
@@ -180,29 +286,27 @@ impl TodoList {
                     if end > i + 1 {
                         // Has children - recursively sort them
                         let children = &items[i + 1..end];
-                        subtree.extend(sort_at_level(children, target_level + 1));
+                        subtree.extend(sort_at_level(children, target_level + 1, key));
                     }
 
-                    let sort_key = priority_sort_key(item.priority);
-                    groups.push((sort_key, subtree));
+                    groups.push((key(item), subtree));
                     i = end;
                 } else {
                     // Item at different level - shouldn't happen at top call, handle gracefully
-                    let sort_key = priority_sort_key(item.priority);
-                    groups.push((sort_key, vec![item.clone()]));
+                    groups.push((key(item), vec![item.clone()]));
                     i += 1;
                 }
             }
 
-            // Stable sort groups by priority key
-            groups.sort_by_key(|(key, _)| *key);
+            // Stable sort groups by key
+            groups.sort_by(|(a, _), (b, _)| a.cmp(b));
 
             // Flatten back to vec
             groups.into_iter().flat_map(|(_, items)| items).collect()
         }
 
         // Sort starting from root level (0)
-        self.items = sort_at_level(&self.items, 0);
+        self.items = sort_at_level(&self.items, 0, &key);
 
         // Recalculate parent IDs after reordering
         self.recalculate_parent_ids();
@@ -222,7 +326,6 @@ impl TodoList {
 #[cfg(test)]
 mod tests {
     use super::*;
-    use crate::todo::TodoState;
     use chrono::{Datelike, NaiveDate};
 
     fn create_test_list() -> TodoList {
@@ -407,6 +510,23 @@ mod tests {
         assert_eq!(list.items[3].content, "No priority");
     }
 
+    #[test]
+    fn test_sort_by_priority_extra_levels() {
+        let mut list = create_test_list();
+        list.add_item("No priority".to_string());
+        list.add_item("P4 task".to_string());
+        list.add_item("P2 task".to_string());
+
+        list.items[1].priority = Priority::new(4);
+        list.items[2].priority = Some(Priority::P2);
+
+        list.sort_by_priority();
+
+        assert_eq!(list.items[0].content, "P2 task");
+        assert_eq!(list.items[1].content, "P4 task");
+        assert_eq!(list.items[2].content, "No priority");
+    }
+
     #[test]
     fn test_sort_by_priority_preserves_hierarchy() {
         let mut list = create_test_list();
@@ -496,4 +616,100 @@ mod tests {
         // P1 child should have P1 parent as its parent
         assert_eq!(list.items[3].parent_id, Some(list.items[2].id));
     }
+
+    #[test]
+    fn test_float_pinned_keeps_subtrees_and_order() {
+        let mut list = create_test_list();
+        list.add_item("First".to_string());
+        list.add_item("Second".to_string());
+        list.add_item_with_indent("Second child".to_string(), 1);
+        list.add_item("Third".to_string());
+        list.items[3].pinned = true;
+        list.items[1].pinned = true;
+
+        list.float_pinned();
+
+        let contents: Vec<_> = list.items.iter().map(|i| i.content.as_str()).collect();
+        assert_eq!(contents, vec!["Second", "Second child", "Third", "First"]);
+        assert_eq!(list.items[1].parent_id, Some(list.items[0].id));
+    }
+
+    #[test]
+    fn test_sort_by_priority_keeps_pinned_on_top() {
+        let mut list = create_test_list();
+        list.add_item("Urgent".to_string());
+        list.add_item("Pinned".to_string());
+        list.items[0].priority = Some(Priority::P0);
+        list.items[1].pinned = true;
+
+        list.sort_by_priority();
+
+        assert_eq!(list.items[0].content, "Pinned");
+        assert_eq!(list.items[1].content, "Urgent");
+    }
+
+    #[test]
+    fn test_sort_by_due_puts_undated_last() {
+        let mut list = create_test_list();
+        list.add_item("Someday".to_string());
+        list.add_item("Friday".to_string());
+        list.add_item("Tomorrow".to_string());
+        list.items[1].due_date = NaiveDate::from_ymd_opt(2025, 1, 17);
+        list.items[2].due_date = NaiveDate::from_ymd_opt(2025, 1, 14);
+
+        list.sort_by_due();
+
+        let order: Vec<_> = list.items.iter().map(|i| i.content.as_str()).collect();
+        assert_eq!(order, ["Tomorrow", "Friday", "Someday"]);
+    }
+
+    #[test]
+    fn test_total_estimate() {
+        let mut list = create_test_list();
+        assert_eq!(list.total_estimate(), None);
+
+        list.add_item("Write report".to_string());
+        list.add_item("Review PR".to_string());
+        list.add_item("Dropped".to_string());
+        list.add_item("Unestimated".to_string());
+        list.items[0].estimate = Some(Estimate::from_minutes(180));
+        list.items[1].estimate = Some(Estimate::from_minutes(90));
+        list.items[2].estimate = Some(Estimate::from_minutes(60));
+        list.items[2].state = TodoState::Cancelled;
+
+        assert_eq!(list.total_estimate(), Some(Estimate::from_minutes(270)));
+    }
+
+    #[test]
+    fn test_progress_counts_items_then_estimates() {
+        let mut list = create_test_list();
+        assert_eq!(list.progress(), (0, 0));
+
+        list.add_item("Write report".to_string());
+        list.add_item("Review PR".to_string());
+        list.add_item("Dropped".to_string());
+        list.items[0].state = TodoState::Checked;
+        list.items[2].state = TodoState::Cancelled;
+        assert_eq!(list.progress(), (1, 2));
+
+        list.items[0].estimate = Some(Estimate::from_minutes(30));
+        list.items[1].estimate = Some(Estimate::from_minutes(90));
+        assert_eq!(list.progress(), (30, 120));
+    }
+
+    #[test]
+    fn test_waiting_visible_indices_include_ancestors() {
+        let mut list = create_test_list();
+        list.add_item_with_indent("Project".to_string(), 0);
+        list.add_item_with_indent("Mine".to_string(), 1);
+        list.add_item_with_indent("Delegated".to_string(), 1);
+        list.add_item_with_indent("Other".to_string(), 0);
+        list.add_item_with_indent("Done delegated".to_string(), 0);
+        list.items[2].waiting_on = Some("alice".to_string());
+        list.items[4].waiting_on = Some("bob".to_string());
+        list.items[4].state = TodoState::Checked;
+
+        let visible = list.waiting_visible_indices();
+        assert_eq!(visible, HashSet::from([0, 2]));
+    }
 }
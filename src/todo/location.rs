@@ -0,0 +1,46 @@
+use serde::{Deserialize, Serialize};
+use std::fmt;
+
+/// A file and line an item points at, e.g. one pushed from an editor.
+#[derive(Debug, Clone, PartialEq, Eq, Hash, Serialize, Deserialize)]
+pub struct Location {
+    pub file: String,
+    pub line: u32,
+}
+
+impl Location {
+    /// Parse `path:line`. The line is taken after the last colon, so Windows
+    /// paths like `C:/code/a.rs:3` work.
+    pub fn parse(text: &str) -> Option<Location> {
+        let (file, line) = text.trim().rsplit_once(':')?;
+        let line = line.parse().ok()?;
+        (!file.is_empty()).then(|| Location {
+            file: file.to_string(),
+            line,
+        })
+    }
+}
+
+impl fmt::Display for Location {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}:{}", self.file, self.line)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse() {
+        let location = Location::parse("/src/app/main.rs:42").unwrap();
+        assert_eq!(location.file, "/src/app/main.rs");
+        assert_eq!(location.line, 42);
+        assert_eq!(location.to_string(), "/src/app/main.rs:42");
+
+        assert_eq!(Location::parse("C:/code/a.rs:3").unwrap().file, "C:/code/a.rs");
+        assert_eq!(Location::parse("Just some notes"), None);
+        assert_eq!(Location::parse(":3"), None);
+        assert_eq!(Location::parse("a.rs:x"), None);
+    }
+}
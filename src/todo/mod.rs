@@ -1,10 +1,19 @@
+pub mod estimate;
+pub mod filter;
 pub mod hierarchy;
 pub mod item;
 pub mod list;
+pub mod location;
+pub mod plan;
 pub mod priority;
+pub mod short_id;
 pub mod state;
+pub mod tag;
 
+pub use estimate::Estimate;
+pub use filter::Filter;
 pub use item::TodoItem;
 pub use list::TodoList;
+pub use location::Location;
 pub use priority::{Priority, PriorityCycle};
 pub use state::TodoState;
@@ -0,0 +1,373 @@
+//! Review of generated plans before they are added to a list.
+//!
+//! A plan is a tree of items (from a generator plugin or pasted text). Each
+//! node can be accepted, rejected or edited; rejecting a node drops its whole
+//! subtree. Only the accepted items are inserted.
+
+use uuid::Uuid;
+
+use super::{TodoItem, TodoState};
+
+/// One item of a plan under review.
+#[derive(Debug, Clone)]
+pub struct PlanNode {
+    pub item: TodoItem,
+    pub rejected: bool,
+}
+
+/// A plan being reviewed, flattened depth-first with relative indent levels.
+#[derive(Debug, Clone)]
+pub struct PlanReview {
+    pub nodes: Vec<PlanNode>,
+    pub cursor: usize,
+    /// Where the plan came from, shown in the title (e.g. "jira", "paste")
+    pub source: String,
+    /// Insert accepted items as subtasks of this item, or at the end of the list
+    pub parent_id: Option<Uuid>,
+    /// Whether the selected node's text is being edited
+    pub editing: bool,
+}
+
+impl PlanReview {
+    pub fn new(items: Vec<TodoItem>, source: impl Into<String>, parent_id: Option<Uuid>) -> Self {
+        Self {
+            nodes: normalize_indents(items)
+                .into_iter()
+                .map(|item| PlanNode {
+                    item,
+                    rejected: false,
+                })
+                .collect(),
+            cursor: 0,
+            source: source.into(),
+            parent_id,
+            editing: false,
+        }
+    }
+
+    pub fn move_up(&mut self) {
+        self.cursor = self.cursor.saturating_sub(1);
+    }
+
+    pub fn move_down(&mut self) {
+        if self.cursor + 1 < self.nodes.len() {
+            self.cursor += 1;
+        }
+    }
+
+    /// End (exclusive) of the subtree rooted at `index`.
+    pub fn subtree_end(&self, index: usize) -> usize {
+        let level = self.nodes[index].item.indent_level;
+        self.nodes[index + 1..]
+            .iter()
+            .position(|node| node.item.indent_level <= level)
+            .map_or(self.nodes.len(), |offset| index + 1 + offset)
+    }
+
+    /// Reject the selected node and its subtree, or accept them again.
+    pub fn toggle_selected(&mut self) {
+        if self.nodes.is_empty() {
+            return;
+        }
+        let rejected = !self.is_rejected(self.cursor);
+        let end = self.subtree_end(self.cursor);
+        for node in &mut self.nodes[self.cursor..end] {
+            node.rejected = rejected;
+        }
+        // Accepting a child brings back the ancestors it hangs from
+        if !rejected {
+            let mut level = self.nodes[self.cursor].item.indent_level;
+            for node in self.nodes[..self.cursor].iter_mut().rev() {
+                if node.item.indent_level < level {
+                    node.rejected = false;
+                    level = node.item.indent_level;
+                }
+            }
+        }
+    }
+
+    pub fn set_all(&mut self, rejected: bool) {
+        for node in &mut self.nodes {
+            node.rejected = rejected;
+        }
+    }
+
+    /// Whether the node at `index` or any of its ancestors is rejected.
+    pub fn is_rejected(&self, index: usize) -> bool {
+        let mut level = self.nodes[index].item.indent_level;
+        if self.nodes[index].rejected {
+            return true;
+        }
+        for node in self.nodes[..index].iter().rev() {
+            if node.item.indent_level < level {
+                if node.rejected {
+                    return true;
+                }
+                level = node.item.indent_level;
+            }
+        }
+        false
+    }
+
+    pub fn selected_content(&self) -> Option<&str> {
+        self.nodes.get(self.cursor).map(|node| node.item.content.as_str())
+    }
+
+    /// Replace the selected node's text; blank text is ignored.
+    pub fn set_selected_content(&mut self, content: &str) {
+        let content = content.trim();
+        if let Some(node) = self.nodes.get_mut(self.cursor)
+            && !content.is_empty()
+        {
+            node.item.content = content.to_string();
+        }
+    }
+
+    /// Indent the selected node and its subtree one level, as long as it
+    /// stays at most one level below the node above it.
+    pub fn indent_selected(&mut self) {
+        if self.cursor == 0 || self.cursor >= self.nodes.len() {
+            return;
+        }
+        if self.nodes[self.cursor].item.indent_level > self.nodes[self.cursor - 1].item.indent_level {
+            return;
+        }
+        let end = self.subtree_end(self.cursor);
+        for node in &mut self.nodes[self.cursor..end] {
+            node.item.indent_level += 1;
+        }
+    }
+
+    /// Outdent the selected node and its subtree one level.
+    pub fn outdent_selected(&mut self) {
+        match self.nodes.get(self.cursor) {
+            Some(node) if node.item.indent_level > 0 => {}
+            _ => return,
+        }
+        let end = self.subtree_end(self.cursor);
+        for node in &mut self.nodes[self.cursor..end] {
+            node.item.indent_level -= 1;
+        }
+    }
+
+    pub fn accepted_count(&self) -> usize {
+        (0..self.nodes.len()).filter(|&i| !self.is_rejected(i)).count()
+    }
+
+    /// The accepted items, indented to sit at `base_indent`.
+    pub fn accepted_items(&self, base_indent: usize) -> Vec<TodoItem> {
+        (0..self.nodes.len())
+            .filter(|&i| !self.is_rejected(i))
+            .map(|i| {
+                let mut item = self.nodes[i].item.clone();
+                item.indent_level += base_indent;
+                item.parent_id = None;
+                item
+            })
+            .collect()
+    }
+}
+
+/// Shift levels so the shallowest item is at 0 and no item is more than one
+/// level deeper than the item above it.
+fn normalize_indents(mut items: Vec<TodoItem>) -> Vec<TodoItem> {
+    let min = items.iter().map(|i| i.indent_level).min().unwrap_or(0);
+    let mut previous: Option<usize> = None;
+    for item in &mut items {
+        let level = item.indent_level - min;
+        item.indent_level = match previous {
+            Some(prev) => level.min(prev + 1),
+            None => 0,
+        };
+        previous = Some(item.indent_level);
+    }
+    items
+}
+
+/// Parse a plan written as an outline: bullets (`-`, `*`, `+`), numbered
+/// steps (`1.`, `2)`), checkboxes (`- [ ]`) or headings (`## Phase 1`).
+/// Headings become parents of the items below them. Blank lines and prose
+/// without a marker are skipped.
+pub fn parse_plan(text: &str) -> Vec<TodoItem> {
+    let mut items = Vec::new();
+    let mut under_heading = false;
+
+    for line in text.lines() {
+        let trimmed = line.trim_start();
+        if trimmed.trim().is_empty() {
+            continue;
+        }
+        let indent = line[..line.len() - trimmed.len()]
+            .chars()
+            .map(|c| if c == '\t' { 4 } else { 1 })
+            .sum::<usize>()
+            / 2;
+
+        if let Some(title) = heading(trimmed) {
+            items.push(TodoItem::new(title.to_string(), 0));
+            under_heading = true;
+            continue;
+        }
+
+        let Some((content, state)) = list_entry(trimmed) else {
+            continue;
+        };
+        let mut item = TodoItem::new(content.to_string(), indent + usize::from(under_heading));
+        if state != TodoState::Empty {
+            item.state = state;
+        }
+        items.push(item);
+    }
+
+    normalize_indents(items)
+}
+
+fn heading(line: &str) -> Option<&str> {
+    let title = line.trim_start_matches('#');
+    (title.len() < line.len() && title.starts_with(' '))
+        .then(|| title.trim())
+        .filter(|t| !t.is_empty())
+}
+
+fn list_entry(line: &str) -> Option<(&str, TodoState)> {
+    let rest = if let Some(rest) = line
+        .strip_prefix("- ")
+        .or_else(|| line.strip_prefix("* "))
+        .or_else(|| line.strip_prefix("+ "))
+    {
+        rest
+    } else {
+        let digits = line.chars().take_while(char::is_ascii_digit).count();
+        if digits == 0 {
+            return None;
+        }
+        line[digits..]
+            .strip_prefix(". ")
+            .or_else(|| line[digits..].strip_prefix(") "))?
+    };
+
+    let rest = rest.trim();
+    let mut chars = rest.chars();
+    if chars.next() == Some('[')
+        && let Some(c) = chars.next()
+        && chars.next() == Some(']')
+    {
+        let state = TodoState::from_char(c).unwrap_or(TodoState::Empty);
+        let content = rest[c.len_utf8() + 2..].trim();
+        return (!content.is_empty()).then_some((content, state));
+    }
+    (!rest.is_empty()).then_some((rest, TodoState::Empty))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const PLAN: &str = "\
+Here is a plan:
+
+## Backend
+- Add endpoint
+  - Write handler
+  - Add tests
+- Migrate schema
+
+## Frontend
+1. Build form
+2. [x] Wire up API
+";
+
+    fn contents(items: &[TodoItem]) -> Vec<(usize, &str)> {
+        items.iter().map(|i| (i.indent_level, i.content.as_str())).collect()
+    }
+
+    #[test]
+    fn test_parse_plan_outline() {
+        let items = parse_plan(PLAN);
+        assert_eq!(
+            contents(&items),
+            vec![
+                (0, "Backend"),
+                (1, "Add endpoint"),
+                (2, "Write handler"),
+                (2, "Add tests"),
+                (1, "Migrate schema"),
+                (0, "Frontend"),
+                (1, "Build form"),
+                (1, "Wire up API"),
+            ]
+        );
+        assert_eq!(items[7].state, TodoState::Checked);
+    }
+
+    #[test]
+    fn test_parse_plan_clamps_indent_jumps() {
+        let items = parse_plan("      - deep\n- top\n      - deep again");
+        assert_eq!(contents(&items), vec![(0, "deep"), (0, "top"), (1, "deep again")]);
+    }
+
+    #[test]
+    fn test_rejecting_a_node_drops_its_subtree() {
+        let mut review = PlanReview::new(parse_plan(PLAN), "paste", None);
+        review.cursor = 1; // Add endpoint
+        review.toggle_selected();
+
+        let accepted = review.accepted_items(0);
+        assert_eq!(
+            contents(&accepted),
+            vec![
+                (0, "Backend"),
+                (1, "Migrate schema"),
+                (0, "Frontend"),
+                (1, "Build form"),
+                (1, "Wire up API"),
+            ]
+        );
+    }
+
+    #[test]
+    fn test_accepting_a_child_restores_its_ancestors() {
+        let mut review = PlanReview::new(parse_plan(PLAN), "paste", None);
+        review.set_all(true);
+        review.cursor = 2; // Write handler
+        review.toggle_selected();
+
+        let accepted = review.accepted_items(1);
+        assert_eq!(
+            contents(&accepted),
+            vec![(1, "Backend"), (2, "Add endpoint"), (3, "Write handler")]
+        );
+        assert_eq!(review.accepted_count(), 3);
+    }
+
+    #[test]
+    fn test_edit_selected_content() {
+        let mut review = PlanReview::new(parse_plan("- one\n- two"), "paste", None);
+        review.move_down();
+        review.set_selected_content("  second  ");
+        review.set_selected_content("   ");
+        assert_eq!(review.selected_content(), Some("second"));
+    }
+
+    #[test]
+    fn test_indent_moves_the_subtree() {
+        let mut review = PlanReview::new(parse_plan(PLAN), "paste", None);
+        review.cursor = 5; // Frontend
+        review.indent_selected();
+        // Already one level below the node above
+        review.cursor = 6; // Build form
+        review.indent_selected();
+        assert_eq!(
+            contents(&review.accepted_items(0))[5..],
+            [(1, "Frontend"), (2, "Build form"), (2, "Wire up API")]
+        );
+
+        review.cursor = 1; // Add endpoint
+        review.outdent_selected();
+        review.outdent_selected();
+        assert_eq!(
+            contents(&review.accepted_items(0))[..3],
+            [(0, "Backend"), (0, "Add endpoint"), (1, "Write handler")]
+        );
+    }
+}
@@ -2,25 +2,39 @@ use serde::{Deserialize, Serialize};
 use std::fmt;
 use std::str::FromStr;
 
-/// Priority levels for todo items.
-/// P0 = critical, P1 = high, P2 = medium
-#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, Serialize, Deserialize)]
-pub enum Priority {
-    P0, // Critical
-    P1, // High
-    #[default]
-    P2, // Medium
-}
+/// Priority level of a todo item. `P0` is the most urgent.
+///
+/// The number of levels in use is configurable (see `PrioritiesConfig`);
+/// three (P0 critical, P1 high, P2 medium) unless the user configures more.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash, Serialize, Deserialize)]
+#[serde(into = "String", try_from = "String")]
+pub struct Priority(u8);
 
 impl Priority {
-    /// Cycle to next priority: None -> P0 -> P1 -> P2 -> None
+    pub const P0: Priority = Priority(0); // Critical
+    pub const P1: Priority = Priority(1); // High
+    pub const P2: Priority = Priority(2); // Medium
+
+    /// Upper bound on configurable levels (P0..=P9)
+    pub const MAX_LEVELS: u8 = 10;
+
+    /// Number of levels when none are configured
+    pub const DEFAULT_LEVELS: u8 = 3;
+
+    pub fn new(level: u8) -> Option<Priority> {
+        (level < Self::MAX_LEVELS).then_some(Priority(level))
+    }
+
+    /// Zero-based level; lower is more urgent.
+    pub fn level(self) -> u8 {
+        self.0
+    }
+
+    /// Next lower priority, or None after the last of `levels` levels.
     /// This is called on Option<Priority>, see the impl below for that.
-    pub fn next(self) -> Option<Priority> {
-        match self {
-            Priority::P0 => Some(Priority::P1),
-            Priority::P1 => Some(Priority::P2),
-            Priority::P2 => None,
-        }
+    pub fn next(self, levels: u8) -> Option<Priority> {
+        let next = self.0 + 1;
+        (next < levels.min(Self::MAX_LEVELS)).then_some(Priority(next))
     }
 
     /// Convert to string for database storage
@@ -34,13 +48,15 @@ impl Priority {
     }
 }
 
+impl Default for Priority {
+    fn default() -> Self {
+        Priority::P2
+    }
+}
+
 impl fmt::Display for Priority {
     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
-        match self {
-            Priority::P0 => write!(f, "P0"),
-            Priority::P1 => write!(f, "P1"),
-            Priority::P2 => write!(f, "P2"),
-        }
+        write!(f, "P{}", self.0)
     }
 }
 
@@ -48,26 +64,41 @@ impl FromStr for Priority {
     type Err = String;
 
     fn from_str(s: &str) -> Result<Self, Self::Err> {
-        match s.trim().to_uppercase().as_str() {
-            "P0" => Ok(Priority::P0),
-            "P1" => Ok(Priority::P1),
-            "P2" => Ok(Priority::P2),
-            _ => Err(format!("Invalid priority: {}", s)),
-        }
+        let invalid = || format!("Invalid priority: {}", s);
+        let level = s
+            .trim()
+            .strip_prefix(['P', 'p'])
+            .and_then(|n| n.parse::<u8>().ok())
+            .ok_or_else(invalid)?;
+        Priority::new(level).ok_or_else(invalid)
+    }
+}
+
+impl From<Priority> for String {
+    fn from(priority: Priority) -> Self {
+        priority.to_string()
+    }
+}
+
+impl TryFrom<String> for Priority {
+    type Error = String;
+
+    fn try_from(s: String) -> Result<Self, Self::Error> {
+        s.parse()
     }
 }
 
 /// Extension trait for Option<Priority> to enable cycling through None
 pub trait PriorityCycle {
-    /// Cycle through priorities: None -> P0 -> P1 -> P2 -> None
-    fn cycle_priority(self) -> Option<Priority>;
+    /// Cycle through `levels` priorities: None -> P0 -> P1 -> ... -> None
+    fn cycle_priority(self, levels: u8) -> Option<Priority>;
 }
 
 impl PriorityCycle for Option<Priority> {
-    fn cycle_priority(self) -> Option<Priority> {
+    fn cycle_priority(self, levels: u8) -> Option<Priority> {
         match self {
             None => Some(Priority::P0),
-            Some(p) => p.next(),
+            Some(p) => p.next(levels),
         }
     }
 }
@@ -97,19 +128,45 @@ mod tests {
     #[test]
     fn test_cycle_priority() {
         let none: Option<Priority> = None;
-        let p0 = none.cycle_priority();
+        let p0 = none.cycle_priority(3);
         assert_eq!(p0, Some(Priority::P0));
 
-        let p1 = p0.cycle_priority();
+        let p1 = p0.cycle_priority(3);
         assert_eq!(p1, Some(Priority::P1));
 
-        let p2 = p1.cycle_priority();
+        let p2 = p1.cycle_priority(3);
         assert_eq!(p2, Some(Priority::P2));
 
-        let back_to_none = p2.cycle_priority();
+        let back_to_none = p2.cycle_priority(3);
         assert_eq!(back_to_none, None);
     }
 
+    #[test]
+    fn test_cycle_priority_with_custom_levels() {
+        assert_eq!(Some(Priority::P0).cycle_priority(1), None);
+        assert_eq!(Some(Priority::P2).cycle_priority(5), Priority::new(3));
+        assert_eq!(Priority::new(4).cycle_priority(5), None);
+        // Levels left over from a larger configuration cycle back to none
+        assert_eq!(Priority::new(6).cycle_priority(3), None);
+    }
+
+    #[test]
+    fn test_extra_levels_parse_and_display() {
+        let p4 = "P4".parse::<Priority>().unwrap();
+        assert_eq!(p4.level(), 4);
+        assert_eq!(p4.to_string(), "P4");
+        assert!("P10".parse::<Priority>().is_err());
+        assert!("P".parse::<Priority>().is_err());
+        assert!(Priority::P0 < p4);
+    }
+
+    #[test]
+    fn test_serde_as_string() {
+        let json = serde_json::to_string(&Priority::P1).unwrap();
+        assert_eq!(json, "\"P1\"");
+        assert_eq!(serde_json::from_str::<Priority>(&json).unwrap(), Priority::P1);
+    }
+
     #[test]
     fn test_to_db_str() {
         assert_eq!(Priority::P0.to_db_str(), Some("P0".to_string()));
@@ -132,6 +189,7 @@ mod tests {
     }
 
     #[test]
+    #[allow(clippy::clone_on_copy)]
     fn test_copy_clone() {
         let p = Priority::P0;
         let p_copy = p;
@@ -0,0 +1,180 @@
+//! Short per-day item identifiers such as `a3` or `k7`.
+//!
+//! An item's id is assigned once and stored with it, so it keeps its id
+//! when other items are added, moved or removed. New items get the slot
+//! their UUID hashes to in the `a0`..`z9` space, or the next free one after
+//! it in creation order. Once those 260 are taken, new items get a letter
+//! and two digits (`a00`..`z99`), then three, and so on.
+
+use std::collections::hash_map::Entry;
+use std::collections::{HashMap, HashSet};
+use uuid::Uuid;
+
+use super::TodoItem;
+
+const LETTERS: u128 = 26;
+
+/// Short ids for every item in the list: the stored one when it has one,
+/// otherwise the one it will be stored with.
+pub fn assign_short_ids(items: &[TodoItem]) -> HashMap<Uuid, String> {
+    let mut order: Vec<&TodoItem> = items.iter().collect();
+    order.sort_by_key(|item| (item.created_at, item.id));
+
+    let mut taken = HashSet::new();
+    let mut ids = HashMap::with_capacity(items.len());
+    // An id stored twice (an item moved in from another day) stays with the
+    // older item; the other gets a new one.
+    for item in &order {
+        if let Some(short_id) = item.short_id.as_deref().filter(|id| is_assignable(id))
+            && taken.insert(short_id.to_string())
+        {
+            ids.insert(item.id, short_id.to_string());
+        }
+    }
+    for item in order {
+        if let Entry::Vacant(entry) = ids.entry(item.id) {
+            let short_id = free_short_id(item.id, &taken);
+            taken.insert(short_id.clone());
+            entry.insert(short_id);
+        }
+    }
+    ids
+}
+
+fn is_assignable(id: &str) -> bool {
+    looks_like_short_id(id) && id.starts_with(|c: char| c.is_ascii_lowercase())
+}
+
+/// The first free id at or after `id`'s slot, in the narrowest space that
+/// still has room.
+fn free_short_id(id: Uuid, taken: &HashSet<String>) -> String {
+    let mut digits = 1;
+    loop {
+        let slots = LETTERS * 10u128.pow(digits);
+        let used = taken.iter().filter(|short_id| short_id.len() == digits as usize + 1).count();
+        if (used as u128) < slots {
+            let mut slot = id.as_u128() % slots;
+            while taken.contains(&format_slot(slot, digits)) {
+                slot = (slot + 1) % slots;
+            }
+            return format_slot(slot, digits);
+        }
+        digits += 1;
+    }
+}
+
+fn format_slot(slot: u128, digits: u32) -> String {
+    let per_letter = 10u128.pow(digits);
+    let letter = (b'a' + (slot / per_letter) as u8) as char;
+    format!("{letter}{:0width$}", slot % per_letter, width = digits as usize)
+}
+
+/// Whether `s` has the shape of a short id (not whether it exists).
+pub fn looks_like_short_id(s: &str) -> bool {
+    let mut chars = s.chars();
+    matches!(chars.next(), Some(c) if c.is_ascii_alphabetic())
+        && s.len() >= 2
+        && chars.all(|c| c.is_ascii_digit())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use chrono::{Duration, Utc};
+
+    fn items(count: usize) -> Vec<TodoItem> {
+        let start = Utc::now();
+        (0..count)
+            .map(|i| {
+                let mut item = TodoItem::new(format!("Item {i}"), 0);
+                item.created_at = start + Duration::seconds(i as i64);
+                item
+            })
+            .collect()
+    }
+
+    /// The items as stored: each with the id it was assigned.
+    fn stored(mut items: Vec<TodoItem>) -> Vec<TodoItem> {
+        let ids = assign_short_ids(&items);
+        for item in &mut items {
+            item.short_id = ids.get(&item.id).cloned();
+        }
+        items
+    }
+
+    #[test]
+    fn test_ids_are_unique_and_short() {
+        let items = items(200);
+        let ids = assign_short_ids(&items);
+        let unique: HashSet<_> = ids.values().collect();
+        assert_eq!(unique.len(), 200);
+        assert!(ids.values().all(|id| id.len() == 2 && looks_like_short_id(id)));
+    }
+
+    #[test]
+    fn test_ids_are_stable_when_items_are_added_or_moved() {
+        let mut list = stored(items(30));
+        let before = assign_short_ids(&list);
+
+        list.reverse();
+        let mut later = TodoItem::new("Later".to_string(), 0);
+        later.created_at = Utc::now() + Duration::hours(1);
+        list.push(later);
+        let after = assign_short_ids(&list);
+
+        for (id, short) in &before {
+            assert_eq!(after.get(id), Some(short));
+        }
+    }
+
+    #[test]
+    fn test_ids_are_stable_when_items_are_removed() {
+        let mut list = stored(items(250));
+        let before = assign_short_ids(&list);
+
+        list.retain(|item| item.content.ends_with('0'));
+        let after = assign_short_ids(&list);
+
+        for (id, short) in &after {
+            assert_eq!(before.get(id), Some(short));
+        }
+    }
+
+    #[test]
+    fn test_large_lists_keep_their_short_ids() {
+        let mut list = stored(items(260));
+        list.extend(items(40));
+        let ids = assign_short_ids(&list);
+
+        assert!(list[..260].iter().all(|item| ids[&item.id].len() == 2));
+        assert!(list[260..].iter().all(|item| ids[&item.id].len() == 3));
+        assert_eq!(ids.values().collect::<HashSet<_>>().len(), 300);
+    }
+
+    #[test]
+    fn test_lists_past_every_two_and_three_character_id_get_longer_ids() {
+        let ids = assign_short_ids(&items(3000));
+        assert_eq!(ids.values().collect::<HashSet<_>>().len(), 3000);
+        assert_eq!(ids.values().filter(|id| id.len() == 4).count(), 3000 - 260 - 2600);
+    }
+
+    #[test]
+    fn test_duplicate_stored_id_goes_to_the_older_item() {
+        let mut list = stored(items(2));
+        list[1].short_id = list[0].short_id.clone();
+        let ids = assign_short_ids(&list);
+
+        assert_eq!(ids[&list[0].id], list[0].short_id.clone().unwrap());
+        assert_ne!(ids[&list[1].id], ids[&list[0].id]);
+    }
+
+    #[test]
+    fn test_looks_like_short_id() {
+        assert!(looks_like_short_id("a3"));
+        assert!(looks_like_short_id("Z99"));
+        assert!(looks_like_short_id("k100"));
+        assert!(!looks_like_short_id("a"));
+        assert!(!looks_like_short_id("3a"));
+        assert!(!looks_like_short_id("abcd"));
+    }
+}
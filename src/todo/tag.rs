@@ -0,0 +1,54 @@
+//! `@name(value)` tags in item text, as written to the daily files and typed
+//! while editing.
+
+/// Find the first `@name(...)` tag in `text`, returning the text without it
+/// and the tag's value. Parentheses inside the value are matched, so
+/// `@link(.../Rust_(programming_language))` is one tag.
+pub fn take_tag<'a>(text: &'a str, name: &str) -> Option<(String, &'a str)> {
+    let opening = format!("@{name}(");
+    let start = text.find(&opening)?;
+    let value_start = start + opening.len();
+    let value_end = value_start + closing_paren(&text[value_start..])?;
+
+    let before = text[..start].trim_end();
+    let after = text[value_end + 1..].trim_start();
+    let cleaned = match (before.is_empty(), after.is_empty()) {
+        (false, false) => format!("{before} {after}"),
+        _ => format!("{before}{after}"),
+    };
+    Some((cleaned, &text[value_start..value_end]))
+}
+
+/// Byte offset of the `)` closing a tag whose value starts `value`.
+fn closing_paren(value: &str) -> Option<usize> {
+    let mut depth = 0usize;
+    for (i, c) in value.char_indices() {
+        match c {
+            '(' => depth += 1,
+            ')' if depth == 0 => return Some(i),
+            ')' => depth -= 1,
+            _ => {}
+        }
+    }
+    None
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_take_tag() {
+        assert_eq!(
+            take_tag("Write report @est(1h30m) today", "est"),
+            Some(("Write report today".to_string(), "1h30m"))
+        );
+        assert_eq!(take_tag("@est(30m) Write report", "est"), Some(("Write report".to_string(), "30m")));
+        assert_eq!(
+            take_tag("Read @link(https://en.wikipedia.org/wiki/Rust_(lang)) first", "link"),
+            Some(("Read first".to_string(), "https://en.wikipedia.org/wiki/Rust_(lang)"))
+        );
+        assert_eq!(take_tag("Write report @est(30m", "est"), None);
+        assert_eq!(take_tag("Write report", "est"), None);
+    }
+}
@@ -0,0 +1,70 @@
+//! Accessibility mode: a pass over the finished frame that swaps box-drawing
+//! and symbol glyphs for ASCII and drops colors, so the screen reads the same
+//! in a screen reader, a braille display or a terminal without color.
+
+use ratatui::{buffer::Buffer, style::Color};
+
+/// Replace the UI's non-ASCII glyphs in `buf` with ASCII look-alikes.
+/// Every glyph here is one cell wide, as is its replacement.
+pub fn ascii_glyphs(buf: &mut Buffer) {
+    for cell in buf.content.iter_mut() {
+        let mut chars = cell.symbol().chars();
+        if let (Some(c), None) = (chars.next(), chars.next())
+            && let Some(ascii) = ascii_glyph(c)
+        {
+            cell.set_char(ascii);
+        }
+    }
+}
+
+/// Reset every foreground and background color in `buf`. Bold, underline,
+/// reverse and strikethrough stay, so emphasis still shows.
+pub fn strip_colors(buf: &mut Buffer) {
+    for cell in buf.content.iter_mut() {
+        cell.set_fg(Color::Reset);
+        cell.set_bg(Color::Reset);
+    }
+}
+
+fn ascii_glyph(c: char) -> Option<char> {
+    let ascii = match c {
+        '─' | '━' | '—' | '·' => '-',
+        '│' | '┃' | '║' => '|',
+        '╭' | '╮' | '╰' | '╯' | '┌' | '┐' | '└' | '┘' | '├' | '┤' | '┬' | '┴' | '┼' => '+',
+        '═' => '=',
+        '▶' | '▸' | '›' | '→' => '>',
+        '←' => '<',
+        '▼' | '▾' | '↓' => 'v',
+        '↑' | '▲' | '↗' => '^',
+        '★' | '•' | '●' => '*',
+        '█' | '▌' | '▐' => '#',
+        '░' => '.',
+        '…' => '.',
+        '✓' => 'x',
+        '✗' => 'X',
+        // Braille spinner frames
+        '\u{2800}'..='\u{28FF}' => '*',
+        _ => return None,
+    };
+    Some(ascii)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use ratatui::layout::Rect;
+    use ratatui::style::Style;
+
+    #[test]
+    fn test_frame_becomes_plain_ascii() {
+        let mut buf = Buffer::empty(Rect::new(0, 0, 12, 1));
+        buf.set_string(0, 0, "╭─▶ 日本★⠋", Style::default().fg(Color::Red).bg(Color::Blue));
+        ascii_glyphs(&mut buf);
+        strip_colors(&mut buf);
+
+        let text: String = buf.content.iter().map(|cell| cell.symbol()).collect();
+        // The wide characters are left alone, with their blank trailing cells
+        assert_eq!(text, "+-> 日 本 **  ");
+        assert!(buf.content.iter().all(|cell| cell.fg == Color::Reset && cell.bg == Color::Reset));
+    }
+}
@@ -0,0 +1,81 @@
+use crate::app::AppState;
+use chrono::{Datelike, Duration};
+use ratatui::{
+    layout::Rect,
+    style::{Color, Modifier, Style},
+    text::{Line, Span},
+    widgets::{Block, Borders, Clear, Paragraph},
+    Frame,
+};
+
+/// Seven cells of four columns plus borders
+const WIDTH: u16 = 7 * 4 + 3;
+/// Weekday header, six week rows, blank line, hint and borders
+const HEIGHT: u16 = 1 + 6 + 2 + 2;
+
+/// Render the month grid. A dot after a day means it has items: green when
+/// all are done, yellow while some are open.
+pub fn render_calendar_modal(f: &mut Frame, state: &AppState) {
+    let Some(calendar) = &state.calendar else {
+        return;
+    };
+    let screen = f.area();
+    let area = Rect {
+        x: screen.x + screen.width.saturating_sub(WIDTH) / 2,
+        y: screen.y + screen.height.saturating_sub(HEIGHT) / 2,
+        width: WIDTH.min(screen.width),
+        height: HEIGHT.min(screen.height),
+    };
+
+    let month_start = calendar.month_start();
+    let block = Block::default()
+        .borders(Borders::ALL)
+        .title(format!(" {} ", month_start.format("%B %Y")))
+        .style(Style::default().bg(state.theme.background));
+
+    let dim_style = Style::default().fg(Color::DarkGray);
+    let mut lines = vec![Line::from(Span::styled(" Mo  Tu  We  Th  Fr  Sa  Su", dim_style))];
+
+    // Start the grid on the Monday on or before the 1st
+    let offset = month_start.weekday().num_days_from_monday() as i64;
+    let grid_start = month_start - Duration::days(offset);
+    for week in 0..6 {
+        let mut spans = Vec::new();
+        for weekday in 0..7 {
+            let date = grid_start + Duration::days(week * 7 + weekday);
+            if date.month() != month_start.month() {
+                spans.push(Span::raw("    "));
+                continue;
+            }
+
+            let mut day_style = Style::default().fg(state.theme.foreground);
+            if date == state.today {
+                day_style = day_style.add_modifier(Modifier::BOLD | Modifier::UNDERLINED);
+            }
+            if date == calendar.selected {
+                day_style = day_style.bg(Color::Yellow).fg(Color::Black);
+            }
+            let dot = match calendar.days.get(&date) {
+                Some(summary) if summary.done == summary.total => {
+                    Span::styled("●", Style::default().fg(Color::Green))
+                }
+                Some(_) => Span::styled("●", Style::default().fg(Color::Yellow)),
+                None => Span::raw(" "),
+            };
+            spans.push(Span::raw(" "));
+            spans.push(Span::styled(format!("{:>2}", date.day()), day_style));
+            spans.push(dot);
+        }
+        lines.push(Line::from(spans));
+    }
+
+    lines.push(Line::from(""));
+    let hint = match calendar.days.get(&calendar.selected) {
+        Some(summary) => format!(" {}/{} done  [ ] month", summary.done, summary.total),
+        None => " [ ] month  Enter go".to_string(),
+    };
+    lines.push(Line::from(Span::styled(hint, dim_style)));
+
+    f.render_widget(Clear, area);
+    f.render_widget(Paragraph::new(lines).block(block), area);
+}
@@ -0,0 +1,57 @@
+use crate::app::command::MAX_COMPLETIONS;
+use crate::app::AppState;
+use ratatui::{
+    layout::Rect,
+    style::{Color, Modifier, Style},
+    text::{Line, Span},
+    widgets::{Block, Borders, Clear, Paragraph},
+    Frame,
+};
+
+/// Render the `:` prompt's completions in a box resting on the bottom of `area`.
+pub fn render_command_palette(f: &mut Frame, state: &AppState, area: Rect) {
+    let completions = state.command_completions();
+    if completions.is_empty() {
+        return;
+    }
+    let shown = &completions[..completions.len().min(MAX_COMPLETIONS)];
+
+    let text_width = shown.iter().map(|c| c.text.chars().count()).max().unwrap_or(0);
+    let height = (shown.len() as u16 + 2).min(area.height);
+    let width = 60.min(area.width);
+    let popup = Rect {
+        x: area.x,
+        y: area.y + area.height - height,
+        width,
+        height,
+    };
+
+    let hint_style = Style::default().fg(Color::DarkGray);
+    let lines: Vec<Line> = shown
+        .iter()
+        .enumerate()
+        .map(|(i, completion)| {
+            let text_style = if i == state.command_selected {
+                Style::default().fg(Color::Black).bg(Color::Yellow).add_modifier(Modifier::BOLD)
+            } else {
+                Style::default().fg(state.theme.foreground)
+            };
+            Line::from(vec![
+                Span::styled(format!(" {:<text_width$} ", completion.text), text_style),
+                Span::styled(format!(" {}", completion.hint), hint_style),
+            ])
+        })
+        .collect();
+
+    let more = completions.len() - shown.len();
+    let mut block = Block::default()
+        .borders(Borders::ALL)
+        .title_bottom(Line::from(" Tab complete • Enter run ").centered())
+        .style(Style::default().bg(state.theme.background));
+    if more > 0 {
+        block = block.title(Line::from(format!(" +{more} more ")).right_aligned());
+    }
+
+    f.render_widget(Clear, popup);
+    f.render_widget(Paragraph::new(lines).block(block), popup);
+}
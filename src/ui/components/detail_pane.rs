@@ -0,0 +1,176 @@
+use crate::app::AppState;
+use crate::storage::attachments::format_size;
+use crate::todo::filter::tags;
+use crate::todo::TodoState;
+use crate::ui::markdown;
+use chrono::{DateTime, Local, Utc};
+use ratatui::{
+    layout::Rect,
+    style::{Color, Modifier, Style},
+    text::{Line, Span},
+    widgets::{Block, Borders, Paragraph, Wrap},
+    Frame,
+};
+
+/// Width of the field labels, including the space after them
+const LABEL_WIDTH: usize = 11;
+
+/// Render everything known about the selected item: full content, fields,
+/// timestamps, description and plugin metadata.
+pub fn render(f: &mut Frame, state: &AppState, area: Rect) {
+    let block = Block::default()
+        .borders(Borders::ALL)
+        .title(" Details ")
+        .style(Style::default().bg(state.theme.background));
+
+    let Some(item) = state.selected_item() else {
+        let empty = Paragraph::new(Span::styled(" No item selected", Style::default().fg(Color::DarkGray)));
+        f.render_widget(empty.block(block), area);
+        return;
+    };
+
+    let label_style = Style::default().fg(Color::DarkGray);
+    let value_style = Style::default().fg(state.theme.foreground);
+    let section_style = Style::default().fg(Color::Cyan).add_modifier(Modifier::BOLD);
+    let field = |label: &str, value: String| {
+        Line::from(vec![
+            Span::styled(format!("{label:<LABEL_WIDTH$}"), label_style),
+            Span::styled(value, value_style),
+        ])
+    };
+
+    let mut lines = vec![
+        Line::from(Span::styled(
+            item.content.clone(),
+            value_style.add_modifier(Modifier::BOLD),
+        )),
+        Line::from(""),
+        field("State", state_label(item.state).to_string()),
+    ];
+    if let Some(priority) = item.priority {
+        let style = state.theme.priority_style(priority);
+        lines.push(Line::from(vec![
+            Span::styled(format!("{:<LABEL_WIDTH$}", "Priority"), label_style),
+            Span::styled(style.label, Style::default().fg(style.color)),
+        ]));
+    }
+    if let Some(due) = item.due_date {
+        lines.push(field("Due", due.format("%a %Y-%m-%d").to_string()));
+    }
+    if let Some(estimate) = &item.estimate {
+        lines.push(field("Estimate", estimate.to_string()));
+    }
+    if let Some(waiting_on) = &item.waiting_on {
+        lines.push(field("Waiting on", waiting_on.clone()));
+    }
+    let item_tags: Vec<String> = tags(&item.content).map(|tag| format!("#{tag}")).collect();
+    if !item_tags.is_empty() {
+        lines.push(field("Tags", item_tags.join(" ")));
+    }
+    if item.pinned {
+        lines.push(field("Pinned", "yes".to_string()));
+    }
+    if let Some(link) = &item.link {
+        lines.push(field("Link", link.clone()));
+    }
+    if let Some(location) = &item.location {
+        lines.push(field("Location", location.to_string()));
+    }
+
+    lines.push(Line::from(""));
+    lines.push(field("Created", timestamp(item.created_at)));
+    lines.push(field("Modified", timestamp(item.modified_at)));
+    if let Some(completed_at) = item.completed_at {
+        lines.push(field("Completed", timestamp(completed_at)));
+    }
+
+    if let Some(description) = &item.description {
+        lines.push(Line::from(""));
+        lines.push(Line::from(Span::styled("Description", section_style)));
+        for line in description.lines() {
+            if state.render_markdown {
+                lines.push(Line::from(
+                    markdown::parse_line(line)
+                        .into_iter()
+                        .map(|(text, md_style)| Span::styled(text, md_style.apply(value_style)))
+                        .collect::<Vec<_>>(),
+                ));
+            } else {
+                lines.push(Line::from(Span::styled(line.to_string(), value_style)));
+            }
+        }
+    }
+
+    let attachments = match &state.detail_attachments {
+        Some((id, attachments)) if *id == item.id => attachments.as_slice(),
+        _ => &[],
+    };
+    if !attachments.is_empty() {
+        lines.push(Line::from(""));
+        lines.push(Line::from(Span::styled("Attachments", section_style)));
+        for (i, attachment) in attachments.iter().enumerate() {
+            lines.push(Line::from(vec![
+                Span::styled(format!("{:>2} ", i + 1), label_style),
+                Span::styled(attachment.name.clone(), value_style),
+                Span::styled(format!("  {}", format_size(attachment.size)), label_style),
+            ]));
+        }
+    }
+
+    let metadata = match &state.detail_metadata {
+        Some((id, metadata)) if *id == item.id => metadata.as_slice(),
+        _ => &[],
+    };
+    if !metadata.is_empty() {
+        lines.push(Line::from(""));
+        lines.push(Line::from(Span::styled("Plugins", section_style)));
+        for entry in metadata {
+            let mut heading = vec![Span::styled(entry.plugin_name.clone(), value_style.add_modifier(Modifier::BOLD))];
+            if let Some(external_id) = &entry.external_id {
+                heading.push(Span::styled(format!(" ({external_id})"), label_style));
+            }
+            lines.push(Line::from(heading));
+            lines.extend(metadata_lines(&entry.data).into_iter().map(|(key, value)| {
+                Line::from(vec![
+                    Span::styled(format!("  {key}: "), label_style),
+                    Span::styled(value, value_style),
+                ])
+            }));
+        }
+    }
+
+    f.render_widget(
+        Paragraph::new(lines).block(block).wrap(Wrap { trim: false }),
+        area,
+    );
+}
+
+fn state_label(state: TodoState) -> &'static str {
+    match state {
+        TodoState::Empty => "open",
+        TodoState::Checked => "done",
+        TodoState::Question => "question",
+        TodoState::Exclamation => "important",
+        TodoState::InProgress => "in progress",
+        TodoState::Cancelled => "cancelled",
+    }
+}
+
+fn timestamp(at: DateTime<Utc>) -> String {
+    at.with_timezone(&Local).format("%Y-%m-%d %H:%M").to_string()
+}
+
+/// Top-level keys of a plugin's JSON metadata with their values; strings
+/// are shown without quotes.
+fn metadata_lines(data: &str) -> Vec<(String, String)> {
+    match serde_json::from_str::<serde_json::Value>(data) {
+        Ok(serde_json::Value::Object(map)) => map
+            .into_iter()
+            .map(|(key, value)| match value {
+                serde_json::Value::String(s) => (key, s),
+                other => (key, other.to_string()),
+            })
+            .collect(),
+        _ => Vec::new(),
+    }
+}
@@ -0,0 +1,141 @@
+use crate::app::AppState;
+use crate::todo::TodoItem;
+use crate::utils::dateparse::parse_date;
+use crate::utils::unicode::{after_first_char, first_char_as_str};
+use super::centered_rect_absolute_height;
+use chrono::Local;
+use ratatui::{
+    layout::Rect,
+    style::{Color, Modifier, Style},
+    text::{Line, Span},
+    widgets::{Block, Borders, Clear, Paragraph},
+    Frame,
+};
+
+/// Render the due date prompt with a live preview of the parsed date.
+pub fn render_due_modal(f: &mut Frame, state: &AppState) {
+    let area = centered_rect_absolute_height(50, 9, f.area());
+
+    let item_title = match state.due_targets.as_slice() {
+        [index] => state
+            .todo_list
+            .items
+            .get(*index)
+            .map(|item| item.content.clone())
+            .unwrap_or_default(),
+        targets => format!("{} items", targets.len()),
+    };
+
+    let block = Block::default()
+        .borders(Borders::ALL)
+        .title(" Due date (Enter to set, Esc to cancel) ")
+        .style(Style::default().bg(state.theme.background));
+
+    f.render_widget(Clear, area);
+    f.render_widget(block, area);
+
+    let inner_area = Rect {
+        x: area.x + 1,
+        y: area.y + 1,
+        width: area.width.saturating_sub(2),
+        height: area.height.saturating_sub(2),
+    };
+
+    let title_area = Rect {
+        height: 1,
+        ..inner_area
+    };
+    let title = Paragraph::new(item_title).style(Style::default().fg(state.theme.foreground));
+    f.render_widget(title, title_area);
+
+    let current_area = Rect {
+        y: inner_area.y + 1,
+        height: 1,
+        ..inner_area
+    };
+    if let [index] = state.due_targets.as_slice()
+        && let Some(item) = state.todo_list.items.get(*index)
+    {
+        f.render_widget(Paragraph::new(Line::from(current_due(state, item))), current_area);
+    }
+
+    let hint_area = Rect {
+        y: inner_area.y + 2,
+        height: 1,
+        ..inner_area
+    };
+    let hint = Paragraph::new("e.g. friday, in 2 weeks, 2026-03-01 (empty clears)")
+        .style(Style::default().fg(Color::DarkGray));
+    f.render_widget(hint, hint_area);
+
+    let input_area = Rect {
+        y: inner_area.y + 4,
+        height: 1,
+        ..inner_area
+    };
+
+    let before_cursor = &state.edit_buffer[..state.edit_cursor_pos];
+    let after_cursor = &state.edit_buffer[state.edit_cursor_pos..];
+    let cursor_char = if after_cursor.is_empty() {
+        "█"
+    } else {
+        first_char_as_str(after_cursor)
+    };
+
+    let input_line = Line::from(vec![
+        Span::raw(before_cursor),
+        Span::styled(
+            cursor_char,
+            Style::default().bg(Color::Yellow).fg(Color::Black),
+        ),
+        Span::raw(after_first_char(after_cursor)),
+    ]);
+    f.render_widget(Paragraph::new(input_line), input_area);
+
+    let preview_area = Rect {
+        y: inner_area.y + 5,
+        height: 1,
+        ..inner_area
+    };
+    let preview = if state.edit_buffer.trim().is_empty() {
+        Span::styled("No due date", Style::default().fg(Color::DarkGray))
+    } else {
+        match parse_date(&state.edit_buffer) {
+            Ok(date) => Span::styled(
+                format!("→ {}", date.format("%A, %Y-%m-%d")),
+                Style::default().fg(Color::Green),
+            ),
+            Err(_) => Span::styled("Unrecognised date", Style::default().fg(Color::Red)),
+        }
+    };
+    f.render_widget(Paragraph::new(Line::from(preview)), preview_area);
+
+    let shortcuts_area = Rect {
+        y: inner_area.y + 6,
+        height: 1,
+        ..inner_area
+    };
+    let shortcuts = Paragraph::new("↑/↓ ±1 day  PgUp/PgDn ±1 week  Ctrl+x clear")
+        .style(Style::default().fg(Color::DarkGray));
+    f.render_widget(shortcuts, shortcuts_area);
+}
+
+/// The item's due date before this edit, flagged when it has passed.
+fn current_due(state: &AppState, item: &TodoItem) -> Span<'static> {
+    let today = Local::now().date_naive();
+    match item.due_date {
+        None => Span::styled("Currently: no due date", Style::default().fg(Color::DarkGray)),
+        Some(due) if item.is_overdue(today) => Span::styled(
+            format!(
+                "Currently: {} ({} days overdue)",
+                due.format("%a %Y-%m-%d"),
+                (today - due).num_days()
+            ),
+            Style::default().fg(state.theme.exclamation).add_modifier(Modifier::BOLD),
+        ),
+        Some(due) => Span::styled(
+            format!("Currently: {}", due.format("%a %Y-%m-%d")),
+            Style::default().fg(state.theme.foreground),
+        ),
+    }
+}
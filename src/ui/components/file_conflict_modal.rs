@@ -0,0 +1,68 @@
+use crate::app::AppState;
+use crate::storage::conflict::Difference;
+use super::centered_rect;
+use ratatui::{
+    style::{Color, Style},
+    text::{Line, Span},
+    widgets::{Block, Borders, Clear, Paragraph, Wrap},
+    Frame,
+};
+
+/// Render the choice between a daily file edited outside totui and the
+/// database, listing the items the two disagree on.
+pub fn render_file_conflict_modal(f: &mut Frame, state: &AppState) {
+    let Some(conflict) = &state.file_conflict else {
+        return;
+    };
+    let area = centered_rect(70, 60, f.area());
+    let inner_height = area.height.saturating_sub(2) as usize;
+    let theme = &state.theme;
+
+    let block = Block::default()
+        .borders(Borders::ALL)
+        .title(" Daily file changed outside totui ")
+        .title_bottom(
+            Line::from(" f keep file • d keep database • m merge • Esc decide later ").centered(),
+        )
+        .style(Style::default().bg(theme.background).fg(theme.foreground));
+
+    let dim = Style::default().fg(Color::DarkGray);
+    let file_name = conflict
+        .file
+        .file_path
+        .file_name()
+        .map(|name| name.to_string_lossy().into_owned())
+        .unwrap_or_default();
+
+    let mut lines = vec![
+        Line::from(format!(
+            " {file_name} was edited while the day also changed in totui."
+        )),
+        Line::from(Span::styled(
+            " Merging keeps both sides' edits; items removed on either side stay removed.",
+            dim,
+        )),
+        Line::default(),
+    ];
+
+    let differences = conflict.differences();
+    if differences.is_empty() {
+        lines.push(Line::from(Span::styled(" The items are the same on both sides.", dim)));
+    }
+    for difference in differences {
+        let (marker, text, note, color) = match difference {
+            Difference::OnlyInFile(text) => ("+", text, "only in the file", Color::Green),
+            Difference::OnlyInDatabase(text) => ("-", text, "only in totui", Color::Red),
+            Difference::Changed(text) => ("~", text, "changed in the file", Color::Yellow),
+        };
+        lines.push(Line::from(vec![
+            Span::styled(format!("   {marker} "), Style::default().fg(color)),
+            Span::styled(text, Style::default().fg(theme.foreground)),
+            Span::styled(format!("  {note}"), dim),
+        ]));
+    }
+    lines.truncate(inner_height);
+
+    f.render_widget(Clear, area);
+    f.render_widget(Paragraph::new(lines).block(block).wrap(Wrap { trim: false }), area);
+}
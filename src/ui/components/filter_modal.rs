@@ -0,0 +1,120 @@
+use crate::app::AppState;
+use crate::todo::{Filter, Priority, TodoState};
+use crate::utils::unicode::{after_first_char, first_char_as_str};
+use super::centered_rect_absolute_height;
+use ratatui::{
+    layout::Rect,
+    style::{Color, Modifier, Style},
+    text::{Line, Span},
+    widgets::{Block, Borders, Clear, Paragraph},
+    Frame,
+};
+
+const STATE_KEYS: [(char, TodoState); 6] = [
+    ('o', TodoState::Empty),
+    ('x', TodoState::Checked),
+    ('*', TodoState::InProgress),
+    ('?', TodoState::Question),
+    ('!', TodoState::Exclamation),
+    ('-', TodoState::Cancelled),
+];
+
+/// Render the view filter menu. Keys toggle filters while it stays open.
+pub fn render_filter_modal(f: &mut Frame, state: &AppState) {
+    let area = centered_rect_absolute_height(50, 12, f.area());
+
+    let block = Block::default()
+        .borders(Borders::ALL)
+        .title(" Filter view (Esc to close) ")
+        .style(Style::default().bg(state.theme.background));
+
+    f.render_widget(Clear, area);
+    f.render_widget(block, area);
+
+    let inner_area = Rect {
+        x: area.x + 1,
+        y: area.y + 1,
+        width: area.width.saturating_sub(2),
+        height: area.height.saturating_sub(2),
+    };
+
+    let key_style = Style::default().fg(Color::Yellow).add_modifier(Modifier::BOLD);
+    let desc_style = Style::default().fg(state.theme.foreground);
+    let active_style = Style::default().fg(Color::Green).add_modifier(Modifier::BOLD);
+    let dim_style = Style::default().fg(Color::DarkGray);
+
+    let is_active = |filter: &Filter| state.active_filters.contains(filter);
+    let toggle = |key: String, label: &str, active: bool| {
+        Line::from(vec![
+            Span::styled(format!(" {key:<4}"), key_style),
+            Span::styled(format!("{label:<26}"), desc_style),
+            Span::styled(if active { "on" } else { "" }, active_style),
+        ])
+    };
+    // A row of keys, each highlighted when its filter is active
+    let choices = |label: &str, keys: Vec<(String, bool)>| {
+        let mut spans = vec![Span::styled(format!(" {label:<30}"), desc_style)];
+        for (key, active) in keys {
+            spans.push(Span::styled(key, if active { active_style } else { key_style }));
+            spans.push(Span::raw(" "));
+        }
+        Line::from(spans)
+    };
+
+    let mut lines = vec![
+        toggle("c".into(), "Hide done and cancelled", is_active(&Filter::HideCompleted)),
+        toggle("w".into(), "Only waiting on someone", state.show_waiting_only),
+        choices(
+            "Only state",
+            STATE_KEYS
+                .iter()
+                .map(|(key, s)| (key.to_string(), is_active(&Filter::State(*s))))
+                .collect(),
+        ),
+        choices(
+            "Only priority",
+            (0..state.theme.priority_levels())
+                .filter_map(Priority::new)
+                .map(|p| (p.level().to_string(), is_active(&Filter::Priority(p))))
+                .collect(),
+        ),
+    ];
+
+    let tags: Vec<String> = state
+        .active_filters
+        .iter()
+        .filter(|filter| matches!(filter, Filter::Tag(_)))
+        .map(Filter::to_string)
+        .collect();
+    if state.filter_tag_input {
+        let before_cursor = &state.edit_buffer[..state.edit_cursor_pos];
+        let after_cursor = &state.edit_buffer[state.edit_cursor_pos..];
+        let cursor_char = if after_cursor.is_empty() {
+            "█"
+        } else {
+            first_char_as_str(after_cursor)
+        };
+        lines.push(Line::from(vec![
+            Span::styled(" #   ", key_style),
+            Span::raw(before_cursor),
+            Span::styled(
+                cursor_char,
+                Style::default().bg(Color::Yellow).fg(Color::Black),
+            ),
+            Span::raw(after_first_char(after_cursor)),
+        ]));
+    } else {
+        lines.push(toggle("#".into(), "Only #tag (again removes)", false));
+    }
+    if !tags.is_empty() {
+        lines.push(Line::from(Span::styled(format!("      {}", tags.join(" ")), active_style)));
+    }
+    lines.push(Line::from(""));
+    lines.push(toggle("r".into(), "Clear filters", false));
+    lines.push(Line::from(Span::styled(
+        " Same kind: any matches; different kinds: all must",
+        dim_style,
+    )));
+
+    f.render_widget(Paragraph::new(lines), inner_area);
+}
@@ -0,0 +1,337 @@
+//! Content of the help overlay, built from the active keybindings so remapped
+//! keys show up as they are configured.
+
+use crate::app::AppState;
+use crate::keybindings::{Action, KeyBinding, KeySequence, KeybindingCache};
+use crossterm::event::{KeyCode, KeyModifiers};
+use ratatui::{
+    style::{Color, Modifier, Style},
+    text::{Line, Span},
+};
+
+/// Width of the key column, including the indent
+const KEY_COLUMN_WIDTH: usize = 20;
+
+/// A row in the help overlay.
+enum Entry {
+    Navigate(Action),
+    Search(Action),
+    Visual(Action),
+    Edit(Action),
+    /// Fixed key text and description, for things that are not bindable actions
+    Note(&'static str, &'static str),
+}
+
+use Entry::{Edit, Navigate, Note, Search, Visual};
+
+const SECTIONS: &[(&str, &[Entry])] = &[
+    (
+        "Navigation",
+        &[
+            Navigate(Action::MoveDown),
+            Navigate(Action::MoveUp),
+            Navigate(Action::GoToTop),
+            Navigate(Action::GoToBottom),
+            Navigate(Action::CollapseOrParent),
+            Navigate(Action::Expand),
+            Navigate(Action::ToggleCollapse),
+            Navigate(Action::CollapseAll),
+            Navigate(Action::ExpandAll),
+            Navigate(Action::ExpandOneLevel),
+            Navigate(Action::FocusSubtree),
+            Navigate(Action::StartSearch),
+            Search(Action::SearchNext),
+            Search(Action::SearchPrev),
+            Search(Action::ClearSearch),
+            Note("/ ... Tab", "Search every date, archive included"),
+            Note("5j / 3dd / 12G", "Count repeats moves, dd, indent"),
+        ],
+    ),
+    (
+        "Item State",
+        &[
+            Navigate(Action::ToggleState),
+            Navigate(Action::CycleState),
+            Navigate(Action::SetStateTodo),
+            Navigate(Action::SetStateDone),
+            Navigate(Action::SetStateInProgress),
+            Navigate(Action::SetStateQuestion),
+            Navigate(Action::SetStateImportant),
+            Navigate(Action::SetStateCancelled),
+        ],
+    ),
+    (
+        "Item Management",
+        &[
+            Navigate(Action::NewItem),
+            Navigate(Action::InsertItemAbove),
+            Navigate(Action::NewItemSameLevel),
+            Navigate(Action::EnterEditMode),
+            Navigate(Action::EditDescription),
+            Navigate(Action::Delete),
+            Navigate(Action::Yank),
+            Navigate(Action::PasteBelow),
+            Navigate(Action::PasteAbove),
+            Note("\"a-\"z", "Use a named register for yank / paste"),
+            Navigate(Action::Undo),
+            Navigate(Action::OpenUndoHistory),
+            Navigate(Action::OpenTrash),
+            Navigate(Action::RecordMacro),
+            Navigate(Action::PlayMacro),
+            Navigate(Action::RepeatChange),
+        ],
+    ),
+    (
+        "Indentation",
+        &[
+            Navigate(Action::Indent),
+            Navigate(Action::Outdent),
+            Navigate(Action::IndentWithChildren),
+            Navigate(Action::OutdentWithChildren),
+        ],
+    ),
+    (
+        "Move Items",
+        &[Navigate(Action::MoveItemUp), Navigate(Action::MoveItemDown)],
+    ),
+    (
+        "Priority & Planning",
+        &[
+            Navigate(Action::CyclePriority),
+            Navigate(Action::TogglePin),
+            Navigate(Action::SortByPriority),
+            Navigate(Action::EditEstimate),
+            Navigate(Action::SetDueDate),
+            Navigate(Action::EditLink),
+            Navigate(Action::OpenLink),
+            Navigate(Action::AttachFile),
+            Navigate(Action::OpenAttachment),
+            Navigate(Action::ToggleWaitingFilter),
+            Navigate(Action::OpenFilterMenu),
+            Navigate(Action::StartCommand),
+            Navigate(Action::ToggleShortIds),
+            Navigate(Action::ToggleLineNumbers),
+            Navigate(Action::ToggleSinkCompleted),
+            Navigate(Action::ToggleDetailPane),
+            Navigate(Action::OpenThemePicker),
+            Navigate(Action::ArchiveItem),
+        ],
+    ),
+    (
+        "Visual Mode",
+        &[
+            Navigate(Action::ToggleVisual),
+            Visual(Action::ExitVisual),
+            Visual(Action::MoveDown),
+            Visual(Action::MoveUp),
+            Visual(Action::Indent),
+            Visual(Action::Outdent),
+            Visual(Action::Delete),
+            Visual(Action::ToggleState),
+            Visual(Action::CyclePriority),
+            Visual(Action::Yank),
+            Visual(Action::SetDueDate),
+            Visual(Action::MoveToProject),
+            Visual(Action::RepeatChange),
+            Visual(Action::Undo),
+        ],
+    ),
+    (
+        "Day Navigation",
+        &[
+            Navigate(Action::PrevDay),
+            Navigate(Action::NextDay),
+            Navigate(Action::GoToToday),
+            Navigate(Action::OpenWeekView),
+            Navigate(Action::OpenCalendar),
+            Navigate(Action::OpenRolloverModal),
+            Navigate(Action::MoveToDate),
+            Navigate(Action::ToggleSplitView),
+            Navigate(Action::SwitchPane),
+        ],
+    ),
+    (
+        "Other",
+        &[
+            Navigate(Action::OpenProjectModal),
+            Navigate(Action::MoveToProject),
+            Navigate(Action::OpenPluginMenu),
+            Note("(paste outline)", "Review a pasted plan, then add it as subtasks"),
+            Navigate(Action::CopyLogPath),
+            Navigate(Action::ToggleHelp),
+            Navigate(Action::Quit),
+            Note("Ctrl+q", "Quit from anywhere, asking first if changes are unsaved"),
+        ],
+    ),
+    (
+        "Edit Mode",
+        &[
+            Edit(Action::EditCancel),
+            Edit(Action::EditConfirm),
+            Edit(Action::EditLeft),
+            Edit(Action::EditRight),
+            Edit(Action::EditWordLeft),
+            Edit(Action::EditWordRight),
+            Edit(Action::EditHome),
+            Edit(Action::EditEnd),
+            Edit(Action::EditIndent),
+            Edit(Action::EditOutdent),
+            Edit(Action::EditBackspace),
+        ],
+    ),
+];
+
+impl Entry {
+    /// Key column text and description, or None for an action with no keys.
+    fn row(&self, keybindings: &KeybindingCache) -> Option<(String, &'static str)> {
+        let (keys, action) = match *self {
+            Navigate(action) => (keybindings.navigate_keys(action), action),
+            Search(action) => (keybindings.search_keys(action), action),
+            Visual(action) => (keybindings.visual_keys(action), action),
+            Edit(action) => (keybindings.edit_keys(action), action),
+            Note(keys, description) => return Some((keys.to_string(), description)),
+        };
+        if keys.is_empty() {
+            return None;
+        }
+        let keys: Vec<String> = keys.iter().map(sequence_label).collect();
+        Some((keys.join(" / "), action.description()))
+    }
+}
+
+/// Every line of the help overlay, including the footer.
+pub fn help_lines(state: &AppState) -> Vec<Line<'static>> {
+    let key_style = Style::default().fg(Color::Yellow).add_modifier(Modifier::BOLD);
+    let desc_style = Style::default().fg(state.theme.foreground);
+    let section_style = Style::default().fg(Color::Cyan).add_modifier(Modifier::BOLD);
+    let dim_style = Style::default().fg(Color::DarkGray);
+
+    let mut lines = vec![
+        Line::from(Span::styled(
+            "  TO-TUI Help",
+            Style::default().fg(Color::Magenta).add_modifier(Modifier::BOLD),
+        )),
+        Line::from(""),
+    ];
+
+    for (i, (title, entries)) in SECTIONS.iter().enumerate() {
+        if i > 0 {
+            lines.push(Line::from(""));
+        }
+        lines.push(Line::from(Span::styled(format!("  ── {title} ──"), section_style)));
+        for (keys, description) in entries.iter().filter_map(|e| e.row(&state.keybindings)) {
+            lines.push(Line::from(vec![
+                Span::styled(key_column(&keys), key_style),
+                Span::styled(description, desc_style),
+            ]));
+        }
+    }
+
+    // Plugin Actions section (only if any enabled plugins have actions)
+    let actions_by_plugin = state.plugin_action_registry.actions_by_plugin();
+    if !actions_by_plugin.is_empty() {
+        lines.push(Line::from(""));
+        lines.push(Line::from(Span::styled("  ── Plugin Actions ──", section_style)));
+
+        // Sort plugin names for consistent display
+        let mut plugin_names: Vec<_> = actions_by_plugin.keys().collect();
+        plugin_names.sort();
+
+        for plugin_name in plugin_names {
+            lines.push(Line::from(Span::styled(
+                format!("  [{}]", plugin_name),
+                Style::default().fg(Color::Blue).add_modifier(Modifier::BOLD),
+            )));
+
+            for action in &actions_by_plugin[plugin_name] {
+                let keys = action
+                    .keybinding
+                    .as_ref()
+                    .map(sequence_label)
+                    .unwrap_or_else(|| "(no binding)".to_string());
+                lines.push(Line::from(vec![
+                    Span::styled(key_column(&keys), key_style),
+                    Span::styled(action.description.clone(), desc_style),
+                ]));
+            }
+        }
+    }
+
+    lines.push(Line::from(""));
+    lines.push(Line::from(Span::styled(
+        "  ↑/↓ or j/k to scroll • Esc or ? to close",
+        dim_style,
+    )));
+    lines
+}
+
+/// Keys indented and padded to the key column, with at least one space after.
+fn key_column(keys: &str) -> String {
+    let padded = format!("    {keys:<w$}", w = KEY_COLUMN_WIDTH - 5);
+    format!("{padded} ")
+}
+
+/// Readable form of a key sequence: `gg`, `Ctrl+p`, `Alt+Shift+↑`.
+pub fn sequence_label(sequence: &KeySequence) -> String {
+    sequence.0.iter().map(binding_label).collect()
+}
+
+fn binding_label(binding: &KeyBinding) -> String {
+    let mut label = String::new();
+    if binding.modifiers.contains(KeyModifiers::CONTROL) {
+        label.push_str("Ctrl+");
+    }
+    if binding.modifiers.contains(KeyModifiers::ALT) {
+        label.push_str("Alt+");
+    }
+    if binding.modifiers.contains(KeyModifiers::SHIFT) || binding.code == KeyCode::BackTab {
+        label.push_str("Shift+");
+    }
+    let key = match binding.code {
+        KeyCode::Char(' ') => "Space".to_string(),
+        KeyCode::Char(c) => c.to_string(),
+        KeyCode::Up => "↑".to_string(),
+        KeyCode::Down => "↓".to_string(),
+        KeyCode::Left => "←".to_string(),
+        KeyCode::Right => "→".to_string(),
+        KeyCode::Tab | KeyCode::BackTab => "Tab".to_string(),
+        KeyCode::Backspace => "Backspace".to_string(),
+        code => KeyBinding::new(code, KeyModifiers::NONE)
+            .to_string()
+            .trim_matches(['<', '>'])
+            .to_string(),
+    };
+    label.push_str(&key);
+    label
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::keybindings::KeybindingsConfig;
+
+    fn navigate_label(keybindings: &KeybindingCache, action: Action) -> Option<String> {
+        Navigate(action).row(keybindings).map(|(keys, _)| keys)
+    }
+
+    #[test]
+    fn test_rows_follow_configured_keys() {
+        let defaults = KeybindingCache::default();
+        assert_eq!(navigate_label(&defaults, Action::MoveDown).as_deref(), Some("j / ↓"));
+        assert_eq!(navigate_label(&defaults, Action::GoToTop).as_deref(), Some("gg"));
+        assert_eq!(navigate_label(&defaults, Action::OpenProjectModal).as_deref(), Some("Ctrl+p"));
+        assert_eq!(
+            navigate_label(&defaults, Action::MoveItemUp).as_deref(),
+            Some("Alt+Shift+↑")
+        );
+
+        let mut config = KeybindingsConfig::default();
+        config.navigate.retain(|_, action| action != "move_down");
+        config.navigate.insert("<C-n>".to_string(), "move_down".to_string());
+        let custom = KeybindingCache::from_config(&config);
+        assert_eq!(navigate_label(&custom, Action::MoveDown).as_deref(), Some("Ctrl+n"));
+
+        // Unbound actions are left out
+        assert_eq!(navigate_label(&defaults, Action::ToggleLineNumbers), None);
+    }
+}
@@ -1,12 +1,34 @@
+pub mod attach_modal;
+pub mod calendar_modal;
+pub mod command_palette;
 pub mod description_modal;
+pub mod detail_pane;
+pub mod due_modal;
+pub mod estimate_modal;
+pub mod file_conflict_modal;
+pub mod filter_modal;
+pub mod help;
+pub mod link_modal;
+pub mod move_date_modal;
+pub mod plan_review_modal;
 pub mod plugin_modal;
+pub mod search_results_modal;
+pub mod split_pane;
+pub mod stale_save_modal;
 pub mod status_bar;
+pub mod theme_picker_modal;
+pub mod timeline_modal;
+pub mod toasts;
+pub mod trash_modal;
 pub mod todo_list;
+pub mod undo_history_modal;
+pub mod week_view;
 
 use crate::app::mode::Mode;
-use crate::app::state::{MoveToProjectSubState, PluginSubState, ProjectSubState};
+use crate::app::state::{matching_projects, MoveToProjectSubState, PluginSubState, ProjectSubState};
 use crate::app::AppState;
 use crate::project::DEFAULT_PROJECT_NAME;
+use crate::utils::fuzzy::fuzzy_positions;
 use crate::utils::upgrade::{format_bytes, PluginUpgradeSubState, UpgradeSubState};
 use chrono::{Local, NaiveDate};
 
@@ -31,8 +53,45 @@ pub fn render(f: &mut Frame, state: &mut AppState) {
         ])
         .split(f.area());
 
-    // Render todo list
-    todo_list::render(f, state, chunks[0]);
+    // Breadcrumb line above the list when the selected item is deeply nested
+    let list_area = if state.breadcrumb_height() > 0 {
+        let rows = Layout::default()
+            .direction(Direction::Vertical)
+            .constraints([Constraint::Length(state.breadcrumb_height()), Constraint::Min(1)])
+            .split(chunks[0]);
+        todo_list::render_breadcrumb(f, state, rows[0]);
+        rows[1]
+    } else {
+        chunks[0]
+    };
+
+    // Render todo list, or the week in its place
+    if state.mode == Mode::WeekView {
+        week_view::render(f, state, chunks[0]);
+    } else if state.split_pane_width() > 0 {
+        let columns = Layout::default()
+            .direction(Direction::Horizontal)
+            .constraints([
+                Constraint::Min(1),
+                Constraint::Length(state.split_pane_width()),
+            ])
+            .split(list_area);
+        todo_list::render(f, state, columns[0]);
+        split_pane::render(f, state, columns[1]);
+    } else if state.detail_pane_width() > 0 {
+        let columns = Layout::default()
+            .direction(Direction::Horizontal)
+            .constraints([
+                Constraint::Min(1),
+                Constraint::Length(state.detail_pane_width()),
+            ])
+            .split(list_area);
+        todo_list::render(f, state, columns[0]);
+        state.refresh_detail_metadata();
+        detail_pane::render(f, state, columns[1]);
+    } else {
+        todo_list::render(f, state, list_area);
+    }
 
     // Render status bar
     status_bar::render(f, state, chunks[1]);
@@ -41,6 +100,10 @@ pub fn render(f: &mut Frame, state: &mut AppState) {
         render_help_overlay(f, state);
     }
 
+    if state.show_timeline {
+        timeline_modal::render_timeline_modal(f, state);
+    }
+
     // Render new plugins modal if active, otherwise fall back to old plugin overlay
     if state.plugins_modal_state.is_some() {
         plugin_modal::render_plugins_modal(f, state);
@@ -74,288 +137,74 @@ pub fn render(f: &mut Frame, state: &mut AppState) {
     if state.mode == Mode::EditDescription {
         description_modal::render_description_modal(f, state);
     }
-}
-
-#[allow(clippy::vec_init_then_push)]
-fn render_help_overlay(f: &mut Frame, state: &AppState) {
-    let key_style = Style::default().fg(Color::Yellow).add_modifier(Modifier::BOLD);
-    let desc_style = Style::default().fg(state.theme.foreground);
-    let section_style = Style::default().fg(Color::Cyan).add_modifier(Modifier::BOLD);
-    let dim_style = Style::default().fg(Color::DarkGray);
-
-    let mut lines: Vec<Line> = vec![];
-
-    // Title
-    lines.push(Line::from(vec![
-        Span::styled("  TO-TUI Help", Style::default().fg(Color::Magenta).add_modifier(Modifier::BOLD)),
-    ]));
-    lines.push(Line::from(""));
-
-    // Navigation section
-    lines.push(Line::from(Span::styled("  ── Navigation ──", section_style)));
-    lines.push(Line::from(vec![
-        Span::styled("    j / ↓           ", key_style),
-        Span::styled("Move cursor down", desc_style),
-    ]));
-    lines.push(Line::from(vec![
-        Span::styled("    k / ↑           ", key_style),
-        Span::styled("Move cursor up", desc_style),
-    ]));
-    lines.push(Line::from(vec![
-        Span::styled("    h / ←           ", key_style),
-        Span::styled("Collapse item or go to parent", desc_style),
-    ]));
-    lines.push(Line::from(vec![
-        Span::styled("    l / →           ", key_style),
-        Span::styled("Expand collapsed item", desc_style),
-    ]));
-    lines.push(Line::from(vec![
-        Span::styled("    c               ", key_style),
-        Span::styled("Toggle collapse/expand", desc_style),
-    ]));
-    lines.push(Line::from(""));
 
-    // Item State section
-    lines.push(Line::from(Span::styled("  ── Item State ──", section_style)));
-    lines.push(Line::from(vec![
-        Span::styled("    x               ", key_style),
-        Span::styled("Toggle done/undone", desc_style),
-    ]));
-    lines.push(Line::from(vec![
-        Span::styled("    Space           ", key_style),
-        Span::styled("Cycle: [ ]→[x]→[*]→[?]→[!]→[-]", desc_style),
-    ]));
-    lines.push(Line::from(""));
+    if state.mode == Mode::EditEstimate {
+        estimate_modal::render_estimate_modal(f, state);
+    }
 
-    // Item Management section
-    lines.push(Line::from(Span::styled("  ── Item Management ──", section_style)));
-    lines.push(Line::from(vec![
-        Span::styled("    n / o           ", key_style),
-        Span::styled("New item below", desc_style),
-    ]));
-    lines.push(Line::from(vec![
-        Span::styled("    O / Shift+Enter ", key_style),
-        Span::styled("New item above", desc_style),
-    ]));
-    lines.push(Line::from(vec![
-        Span::styled("    Enter           ", key_style),
-        Span::styled("New item at same indent level", desc_style),
-    ]));
-    lines.push(Line::from(vec![
-        Span::styled("    i               ", key_style),
-        Span::styled("Edit current item", desc_style),
-    ]));
-    lines.push(Line::from(vec![
-        Span::styled("    e               ", key_style),
-        Span::styled("Edit description", desc_style),
-    ]));
-    lines.push(Line::from(vec![
-        Span::styled("    dd              ", key_style),
-        Span::styled("Delete item (with children)", desc_style),
-    ]));
-    lines.push(Line::from(vec![
-        Span::styled("    y               ", key_style),
-        Span::styled("Yank (copy) item to clipboard", desc_style),
-    ]));
-    lines.push(Line::from(vec![
-        Span::styled("    u               ", key_style),
-        Span::styled("Undo last action", desc_style),
-    ]));
-    lines.push(Line::from(""));
+    if state.mode == Mode::MoveToDate {
+        move_date_modal::render_move_date_modal(f, state);
+    }
 
-    // Indentation section
-    lines.push(Line::from(Span::styled("  ── Indentation ──", section_style)));
-    lines.push(Line::from(vec![
-        Span::styled("    Tab             ", key_style),
-        Span::styled("Indent item", desc_style),
-    ]));
-    lines.push(Line::from(vec![
-        Span::styled("    Shift+Tab       ", key_style),
-        Span::styled("Outdent item", desc_style),
-    ]));
-    lines.push(Line::from(vec![
-        Span::styled("    Alt+Shift+→     ", key_style),
-        Span::styled("Indent with children", desc_style),
-    ]));
-    lines.push(Line::from(vec![
-        Span::styled("    Alt+Shift+←     ", key_style),
-        Span::styled("Outdent with children", desc_style),
-    ]));
-    lines.push(Line::from(""));
+    if state.mode == Mode::EditDue {
+        due_modal::render_due_modal(f, state);
+    }
 
-    // Move Items section
-    lines.push(Line::from(Span::styled("  ── Move Items ──", section_style)));
-    lines.push(Line::from(vec![
-        Span::styled("    Alt+Shift+↑     ", key_style),
-        Span::styled("Move item up (with children)", desc_style),
-    ]));
-    lines.push(Line::from(vec![
-        Span::styled("    Alt+Shift+↓     ", key_style),
-        Span::styled("Move item down (with children)", desc_style),
-    ]));
-    lines.push(Line::from(""));
+    if state.mode == Mode::EditLink {
+        link_modal::render_link_modal(f, state);
+    }
 
-    // Priority section
-    lines.push(Line::from(Span::styled("  ── Priority ──", section_style)));
-    lines.push(Line::from(vec![
-        Span::styled("    p               ", key_style),
-        Span::styled("Cycle priority: none→low→medium→high", desc_style),
-    ]));
-    lines.push(Line::from(vec![
-        Span::styled("    s               ", key_style),
-        Span::styled("Sort items by priority", desc_style),
-    ]));
-    lines.push(Line::from(""));
+    if state.mode == Mode::AttachFile {
+        attach_modal::render_attach_modal(f, state);
+    }
 
-    // Visual Mode section
-    lines.push(Line::from(Span::styled("  ── Visual Mode ──", section_style)));
-    lines.push(Line::from(vec![
-        Span::styled("    v               ", key_style),
-        Span::styled("Enter visual mode (select multiple)", desc_style),
-    ]));
-    lines.push(Line::from(vec![
-        Span::styled("    Esc / v / q     ", key_style),
-        Span::styled("Exit visual mode", desc_style),
-    ]));
-    lines.push(Line::from(vec![
-        Span::raw("    "),
-        Span::styled("In visual: ", dim_style),
-        Span::styled("j/k", key_style),
-        Span::styled(" extend selection, ", dim_style),
-        Span::styled("Tab/S-Tab", key_style),
-        Span::styled(" indent/outdent", dim_style),
-    ]));
-    lines.push(Line::from(""));
+    if state.mode == Mode::PlanReview {
+        plan_review_modal::render_plan_review_modal(f, state);
+    }
 
-    // Day Navigation section
-    lines.push(Line::from(Span::styled("  ── Day Navigation ──", section_style)));
-    lines.push(Line::from(vec![
-        Span::styled("    <               ", key_style),
-        Span::styled("Previous day (archived, readonly)", desc_style),
-    ]));
-    lines.push(Line::from(vec![
-        Span::styled("    >               ", key_style),
-        Span::styled("Next day", desc_style),
-    ]));
-    lines.push(Line::from(vec![
-        Span::styled("    T               ", key_style),
-        Span::styled("Go to today", desc_style),
-    ]));
-    lines.push(Line::from(vec![
-        Span::styled("    R               ", key_style),
-        Span::styled("Open rollover modal", desc_style),
-    ]));
-    lines.push(Line::from(""));
+    if state.mode == Mode::Filter {
+        filter_modal::render_filter_modal(f, state);
+    }
 
-    // Other section
-    lines.push(Line::from(Span::styled("  ── Other ──", section_style)));
-    lines.push(Line::from(vec![
-        Span::styled("    Ctrl+p          ", key_style),
-        Span::styled("Open project switcher", desc_style),
-    ]));
-    lines.push(Line::from(vec![
-        Span::styled("    P               ", key_style),
-        Span::styled("Open plugins menu", desc_style),
-    ]));
-    lines.push(Line::from(vec![
-        Span::styled("    L               ", key_style),
-        Span::styled("Copy log file path to clipboard", desc_style),
-    ]));
-    lines.push(Line::from(vec![
-        Span::styled("    ?               ", key_style),
-        Span::styled("Toggle this help", desc_style),
-    ]));
-    lines.push(Line::from(vec![
-        Span::styled("    q               ", key_style),
-        Span::styled("Quit", desc_style),
-    ]));
-    lines.push(Line::from(""));
+    if state.mode == Mode::Calendar {
+        calendar_modal::render_calendar_modal(f, state);
+    }
+    if state.mode == Mode::Command {
+        command_palette::render_command_palette(f, state, chunks[0]);
+    }
 
-    // Edit Mode section
-    lines.push(Line::from(Span::styled("  ── Edit Mode ──", section_style)));
-    lines.push(Line::from(vec![
-        Span::styled("    Esc             ", key_style),
-        Span::styled("Save and exit edit mode", desc_style),
-    ]));
-    lines.push(Line::from(vec![
-        Span::styled("    Enter           ", key_style),
-        Span::styled("Save and create new item below", desc_style),
-    ]));
-    lines.push(Line::from(vec![
-        Span::styled("    ← / →           ", key_style),
-        Span::styled("Move cursor", desc_style),
-    ]));
-    lines.push(Line::from(vec![
-        Span::styled("    Alt+b / Alt+←   ", key_style),
-        Span::styled("Move word left", desc_style),
-    ]));
-    lines.push(Line::from(vec![
-        Span::styled("    Alt+f / Alt+→   ", key_style),
-        Span::styled("Move word right", desc_style),
-    ]));
-    lines.push(Line::from(vec![
-        Span::styled("    Home / Ctrl+a   ", key_style),
-        Span::styled("Go to start of line", desc_style),
-    ]));
-    lines.push(Line::from(vec![
-        Span::styled("    End / Ctrl+e    ", key_style),
-        Span::styled("Go to end of line", desc_style),
-    ]));
-    lines.push(Line::from(vec![
-        Span::styled("    Tab / Shift+Tab ", key_style),
-        Span::styled("Indent/outdent while editing", desc_style),
-    ]));
-    lines.push(Line::from(vec![
-        Span::styled("    Backspace       ", key_style),
-        Span::styled("Delete character", desc_style),
-    ]));
+    if state.mode == Mode::ThemePicker {
+        theme_picker_modal::render_theme_picker_modal(f, state);
+    }
 
-    // Plugin Actions section (only if any enabled plugins have actions)
-    let actions_by_plugin = state.plugin_action_registry.actions_by_plugin();
-    if !actions_by_plugin.is_empty() {
-        lines.push(Line::from(""));
-        lines.push(Line::from(Span::styled(
-            "  ── Plugin Actions ──",
-            section_style,
-        )));
+    if state.mode == Mode::UndoHistory {
+        undo_history_modal::render_undo_history_modal(f, state);
+    }
 
-        // Sort plugin names for consistent display
-        let mut plugin_names: Vec<_> = actions_by_plugin.keys().collect();
-        plugin_names.sort();
+    if state.mode == Mode::SearchResults {
+        search_results_modal::render_search_results_modal(f, state);
+    }
 
-        for plugin_name in plugin_names {
-            let actions = &actions_by_plugin[plugin_name];
+    if state.mode == Mode::Trash {
+        trash_modal::render_trash_modal(f, state);
+    }
 
-            lines.push(Line::from(vec![Span::styled(
-                format!("  [{}]", plugin_name),
-                Style::default()
-                    .fg(Color::Blue)
-                    .add_modifier(Modifier::BOLD),
-            )]));
-
-            for action in actions {
-                // Format the KeyBinding directly using its Display impl
-                let key_text = action
-                    .keybinding
-                    .as_ref()
-                    .map(|kb| format!("{:<16}", kb))
-                    .unwrap_or_else(|| "(no binding)    ".to_string());
-
-                lines.push(Line::from(vec![
-                    Span::styled(format!("    {}  ", key_text), key_style),
-                    Span::styled(&action.description, desc_style),
-                ]));
-            }
-        }
+    if state.mode == Mode::FileConflict {
+        file_conflict_modal::render_file_conflict_modal(f, state);
     }
 
-    lines.push(Line::from(""));
+    if state.mode == Mode::StaleSave {
+        stale_save_modal::render_stale_save_modal(f, state);
+    }
 
-    // Footer hint
-    lines.push(Line::from(vec![
-        Span::styled("  ↑/↓ or j/k to scroll • Esc or ? to close", dim_style),
-    ]));
+    // Accessible mode puts messages in the status line instead
+    if !state.accessible_mode {
+        toasts::render(f, state, chunks[0]);
+    }
+}
 
+fn render_help_overlay(f: &mut Frame, state: &AppState) {
+    let lines = help::help_lines(state);
     let total_lines = lines.len() as u16;
 
     // Center the help popup
@@ -430,7 +279,7 @@ pub(crate) fn centered_rect(percent_x: u16, percent_y: u16, r: Rect) -> Rect {
 }
 
 /// Create a centered rect with percentage width and absolute height in lines
-fn centered_rect_absolute_height(percent_x: u16, height: u16, r: Rect) -> Rect {
+pub(crate) fn centered_rect_absolute_height(percent_x: u16, height: u16, r: Rect) -> Rect {
     let height = height.min(r.height); // Don't exceed available height
     let vertical_margin = (r.height.saturating_sub(height)) / 2;
 
@@ -466,7 +315,6 @@ fn render_plugin_overlay(f: &mut Frame, state: &AppState, plugin_state: &PluginS
         } => render_plugin_input(f, state, plugin_name, input_buffer, *cursor_pos),
         PluginSubState::Executing { plugin_name } => render_plugin_executing(f, state, plugin_name),
         PluginSubState::Error { message } => render_plugin_error(f, state, message),
-        PluginSubState::Preview { items } => render_plugin_preview(f, state, items),
     }
 }
 
@@ -628,36 +476,6 @@ fn render_plugin_error(f: &mut Frame, state: &AppState, message: &str) {
     f.render_widget(paragraph, area);
 }
 
-fn render_plugin_preview(f: &mut Frame, state: &AppState, items: &[crate::todo::TodoItem]) {
-    let area = centered_rect(70, 60, f.area());
-
-    let list_items: Vec<ListItem> = items
-        .iter()
-        .map(|item| {
-            let indent = "  ".repeat(item.indent_level);
-            let line = format!("{}[ ] {}", indent, item.content);
-            ListItem::new(Line::from(Span::styled(
-                line,
-                Style::default().fg(state.theme.foreground),
-            )))
-        })
-        .collect();
-
-    let title = format!(" Generated {} item(s) - Add to list? (Y/n) ", items.len());
-
-    let list = List::new(list_items)
-        .block(
-            Block::default()
-                .borders(Borders::ALL)
-                .title(title)
-                .style(Style::default().bg(state.theme.background)),
-        )
-        .style(Style::default().fg(state.theme.foreground));
-
-    f.render_widget(Clear, area);
-    f.render_widget(list, area);
-}
-
 fn format_date_description(source_date: NaiveDate) -> String {
     let today = Local::now().date_naive();
     let days_ago = (today - source_date).num_days();
@@ -718,7 +536,12 @@ fn render_rollover_overlay(f: &mut Frame, state: &AppState) {
     for item in &pending.items {
         let indent = "  ".repeat(item.indent_level);
         let state_char = item.state.to_char();
-        let line = format!("  {}[{}] {}", indent, state_char, item.content);
+        let waiting = item
+            .waiting_on
+            .as_ref()
+            .map(|who| format!(" @{who}"))
+            .unwrap_or_default();
+        let line = format!("  {}[{}] {}{}", indent, state_char, item.content, waiting);
         lines.push(ListItem::new(Line::from(Span::styled(
             line,
             Style::default().fg(state.theme.foreground),
@@ -1111,18 +934,19 @@ fn render_plugin_downloading(
 
     let title = format!(" Updating {} ", plugin_name);
 
-    let mut lines: Vec<Line> = vec![];
-    lines.push(Line::from(""));
-    lines.push(Line::from(vec![
-        Span::raw("  Version: "),
-        Span::styled(current_version, Style::default().fg(Color::Yellow)),
-        Span::raw(" → "),
-        Span::styled(
-            latest_version,
-            Style::default().fg(Color::Green).add_modifier(Modifier::BOLD),
-        ),
-    ]));
-    lines.push(Line::from(""));
+    let lines: Vec<Line> = vec![
+        Line::from(""),
+        Line::from(vec![
+            Span::raw("  Version: "),
+            Span::styled(current_version, Style::default().fg(Color::Yellow)),
+            Span::raw(" → "),
+            Span::styled(
+                latest_version,
+                Style::default().fg(Color::Green).add_modifier(Modifier::BOLD),
+            ),
+        ]),
+        Line::from(""),
+    ];
 
     let content = Paragraph::new(lines)
         .block(
@@ -1546,7 +1370,8 @@ fn render_project_overlay(f: &mut Frame, state: &AppState, project_state: &Proje
         ProjectSubState::Selecting {
             projects,
             selected_index,
-        } => render_project_selecting(f, state, projects, *selected_index),
+            query,
+        } => render_project_selecting(f, state, projects, *selected_index, query),
         ProjectSubState::CreateInput {
             input_buffer,
             cursor_pos,
@@ -1567,11 +1392,12 @@ fn render_project_selecting(
     state: &AppState,
     projects: &[crate::project::Project],
     selected_index: usize,
+    query: &str,
 ) {
     let area = centered_rect(50, 50, f.area());
 
-    let items: Vec<ListItem> = projects
-        .iter()
+    let items: Vec<ListItem> = matching_projects(projects, query)
+        .into_iter()
         .enumerate()
         .map(|(i, project)| {
             let is_current = project.name == state.current_project.name;
@@ -1590,23 +1416,32 @@ fn render_project_selecting(
             } else {
                 Style::default().fg(state.theme.foreground)
             };
+            let match_style = name_style.fg(Color::Cyan).add_modifier(Modifier::UNDERLINED);
+            let matched = fuzzy_positions(&project.name, query).unwrap_or_default();
+
+            let mut spans = vec![Span::styled(marker, Style::default().fg(Color::Green))];
+            spans.extend(project.name.chars().enumerate().map(|(pos, c)| {
+                let style = if matched.contains(&pos) { match_style } else { name_style };
+                Span::styled(c.to_string(), style)
+            }));
+            if is_default {
+                spans.push(Span::styled(" (default)", name_style));
+            }
 
-            let suffix = if is_default { " (default)" } else { "" };
-
-            let line = Line::from(vec![
-                Span::styled(marker, Style::default().fg(Color::Green)),
-                Span::styled(format!("{}{}", project.name, suffix), name_style),
-            ]);
-
-            ListItem::new(line)
+            ListItem::new(Line::from(spans))
         })
         .collect();
 
+    let title = if query.is_empty() {
+        " Projects (type to filter, Enter to switch, Esc to cancel) ".to_string()
+    } else {
+        format!(" Projects matching \"{query}\" (Esc to clear) ")
+    };
     let list = List::new(items)
         .block(
             Block::default()
                 .borders(Borders::ALL)
-                .title(" Projects (Enter to switch, Esc to cancel) ")
+                .title(title)
                 .style(Style::default().bg(state.theme.background)),
         )
         .style(Style::default().fg(state.theme.foreground));
@@ -1624,21 +1459,21 @@ fn render_project_selecting(
 
     let footer = Paragraph::new(Line::from(vec![
         Span::styled(
-            "[n]",
+            "[^n]",
             Style::default()
                 .fg(Color::Cyan)
                 .add_modifier(Modifier::BOLD),
         ),
         Span::raw("ew  "),
         Span::styled(
-            "[r]",
+            "[^r]",
             Style::default()
                 .fg(Color::Yellow)
                 .add_modifier(Modifier::BOLD),
         ),
         Span::raw("ename  "),
         Span::styled(
-            "[d]",
+            "[^d]",
             Style::default()
                 .fg(Color::Red)
                 .add_modifier(Modifier::BOLD),
@@ -1880,15 +1715,20 @@ pub fn render_move_to_project_modal(frame: &mut Frame, state: &AppState) {
         projects,
         selected_index,
         item_index,
+        item_end,
     } = move_state;
 
-    // Get the item being moved for display
-    let item_name = state
-        .todo_list
-        .items
-        .get(*item_index)
-        .map(|i| i.content.as_str())
-        .unwrap_or("(unknown)");
+    // Get the item being moved for display, or the count for a selection
+    let moved = state.todo_list.items.get(*item_index..*item_end).unwrap_or(&[]);
+    let roots = moved
+        .iter()
+        .filter(|i| !i.parent_id.is_some_and(|p| moved.iter().any(|m| m.id == p)))
+        .count();
+    let item_name = match moved.first() {
+        Some(_) if roots > 1 => format!("{roots} items"),
+        Some(item) => item.content.clone(),
+        None => "(unknown)".to_string(),
+    };
 
     // Build title with truncated item name
     let max_title_len = 40;
@@ -0,0 +1,94 @@
+use crate::app::AppState;
+use crate::utils::dateparse::parse_date;
+use crate::utils::unicode::{after_first_char, first_char_as_str};
+use super::centered_rect_absolute_height;
+use ratatui::{
+    layout::Rect,
+    style::{Color, Style},
+    text::{Line, Span},
+    widgets::{Block, Borders, Clear, Paragraph},
+    Frame,
+};
+
+/// Render the move-to-date popup with a live preview of the parsed date.
+pub fn render_move_date_modal(f: &mut Frame, state: &AppState) {
+    let area = centered_rect_absolute_height(50, 7, f.area());
+
+    let item_title = state
+        .selected_item()
+        .map(|item| item.content.as_str())
+        .unwrap_or("");
+
+    let block = Block::default()
+        .borders(Borders::ALL)
+        .title(" Move to date (Enter to move, Esc to cancel) ")
+        .style(Style::default().bg(state.theme.background));
+
+    f.render_widget(Clear, area);
+    f.render_widget(block, area);
+
+    let inner_area = Rect {
+        x: area.x + 1,
+        y: area.y + 1,
+        width: area.width.saturating_sub(2),
+        height: area.height.saturating_sub(2),
+    };
+
+    let title_area = Rect {
+        height: 1,
+        ..inner_area
+    };
+    let title = Paragraph::new(item_title).style(Style::default().fg(state.theme.foreground));
+    f.render_widget(title, title_area);
+
+    let hint_area = Rect {
+        y: inner_area.y + 1,
+        height: 1,
+        ..inner_area
+    };
+    let hint = Paragraph::new("e.g. tomorrow, next monday, 2026-03-01 (↑/↓ step a day)")
+        .style(Style::default().fg(Color::DarkGray));
+    f.render_widget(hint, hint_area);
+
+    let input_area = Rect {
+        y: inner_area.y + 3,
+        height: 1,
+        ..inner_area
+    };
+
+    let before_cursor = &state.edit_buffer[..state.edit_cursor_pos];
+    let after_cursor = &state.edit_buffer[state.edit_cursor_pos..];
+    let cursor_char = if after_cursor.is_empty() {
+        "█"
+    } else {
+        first_char_as_str(after_cursor)
+    };
+
+    let input_line = Line::from(vec![
+        Span::raw(before_cursor),
+        Span::styled(
+            cursor_char,
+            Style::default().bg(Color::Yellow).fg(Color::Black),
+        ),
+        Span::raw(after_first_char(after_cursor)),
+    ]);
+    f.render_widget(Paragraph::new(input_line), input_area);
+
+    let preview_area = Rect {
+        y: inner_area.y + 4,
+        height: 1,
+        ..inner_area
+    };
+    let preview = if state.edit_buffer.trim().is_empty() {
+        Span::raw("")
+    } else {
+        match parse_date(&state.edit_buffer) {
+            Ok(date) => Span::styled(
+                format!("→ {}", date.format("%A, %Y-%m-%d")),
+                Style::default().fg(Color::Green),
+            ),
+            Err(_) => Span::styled("Unrecognised date", Style::default().fg(Color::Red)),
+        }
+    };
+    f.render_widget(Paragraph::new(Line::from(preview)), preview_area);
+}
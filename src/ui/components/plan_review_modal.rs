@@ -0,0 +1,131 @@
+use crate::app::AppState;
+use crate::utils::unicode::{after_first_char, first_char_as_str};
+use super::centered_rect;
+use ratatui::{
+    layout::Rect,
+    style::{Color, Modifier, Style},
+    text::{Line, Span},
+    widgets::{Block, Borders, Clear, Paragraph},
+    Frame,
+};
+
+/// Render the review popup for a generated or pasted plan.
+pub fn render_plan_review_modal(f: &mut Frame, state: &AppState) {
+    let Some(review) = &state.plan_review else {
+        return;
+    };
+    let area = centered_rect(70, 70, f.area());
+
+    let block = Block::default()
+        .borders(Borders::ALL)
+        .title(format!(
+            " Review plan from {}: {}/{} accepted ",
+            review.source,
+            review.accepted_count(),
+            review.nodes.len()
+        ))
+        .style(Style::default().bg(state.theme.background));
+
+    f.render_widget(Clear, area);
+    f.render_widget(block, area);
+
+    let inner_area = Rect {
+        x: area.x + 1,
+        y: area.y + 1,
+        width: area.width.saturating_sub(2),
+        height: area.height.saturating_sub(2),
+    };
+    if inner_area.height < 4 {
+        return;
+    }
+
+    let parent = review
+        .parent_id
+        .and_then(|id| state.todo_list.items.iter().find(|item| item.id == id));
+    let target = match parent {
+        Some(item) => format!("Add as subtasks of: {}", item.content),
+        None => "Add at the end of the list".to_string(),
+    };
+    let target_area = Rect {
+        height: 1,
+        ..inner_area
+    };
+    f.render_widget(
+        Paragraph::new(target).style(Style::default().fg(state.theme.foreground)),
+        target_area,
+    );
+
+    let list_area = Rect {
+        y: inner_area.y + 2,
+        height: inner_area.height - 3,
+        ..inner_area
+    };
+    let visible = list_area.height as usize;
+    let offset = review.cursor.saturating_sub(visible.saturating_sub(1));
+
+    let accepted_style = Style::default().fg(state.theme.foreground);
+    let rejected_style = Style::default()
+        .fg(Color::DarkGray)
+        .add_modifier(Modifier::CROSSED_OUT);
+    let cursor_style = Style::default().bg(Color::DarkGray);
+
+    let lines: Vec<Line> = review
+        .nodes
+        .iter()
+        .enumerate()
+        .skip(offset)
+        .take(visible)
+        .map(|(i, node)| {
+            let rejected = review.is_rejected(i);
+            let marker = if rejected {
+                Span::styled("[✗] ", Style::default().fg(Color::Red))
+            } else {
+                Span::styled("[✓] ", Style::default().fg(Color::Green))
+            };
+            let indent = Span::raw("  ".repeat(node.item.indent_level));
+
+            let mut spans = vec![indent, marker];
+            if i == review.cursor && review.editing {
+                let before_cursor = &state.edit_buffer[..state.edit_cursor_pos];
+                let after_cursor = &state.edit_buffer[state.edit_cursor_pos..];
+                let cursor_char = if after_cursor.is_empty() {
+                    "█"
+                } else {
+                    first_char_as_str(after_cursor)
+                };
+                spans.push(Span::raw(before_cursor.to_string()));
+                spans.push(Span::styled(
+                    cursor_char.to_string(),
+                    Style::default().bg(Color::Yellow).fg(Color::Black),
+                ));
+                spans.push(Span::raw(after_first_char(after_cursor).to_string()));
+                return Line::from(spans);
+            }
+
+            let content_style = if rejected { rejected_style } else { accepted_style };
+            spans.push(Span::styled(node.item.content.clone(), content_style));
+            let line = Line::from(spans);
+            if i == review.cursor {
+                line.style(cursor_style)
+            } else {
+                line
+            }
+        })
+        .collect();
+    f.render_widget(Paragraph::new(lines), list_area);
+
+    let hint_area = Rect {
+        y: inner_area.y + inner_area.height - 1,
+        height: 1,
+        ..inner_area
+    };
+    let hint = if review.editing {
+        "Enter save  Esc cancel edit"
+    } else {
+        "Space accept/reject  e edit  Tab/Shift+Tab indent  a/r all  t target  Enter add  Esc discard"
+    };
+    f.render_widget(
+        Paragraph::new(hint).style(Style::default().fg(Color::DarkGray)),
+        hint_area,
+    );
+}
@@ -1,15 +1,16 @@
 //! Plugins modal component for the tabbed plugin browser.
 //!
-//! This module renders the P-key plugins modal with:
+//! This module renders the `gp` plugins modal with:
 //! - Installed tab showing loaded plugins
 //! - Marketplace tab showing available plugins from registry
 //! - Input view for plugin parameter entry
-//! - Preview view for generated items
 //! - Error view for displaying errors
 
-use crate::app::state::{PluginsModalState, PluginsTab};
+use super::help::sequence_label;
+use crate::app::state::{installed_rows, InstalledRow, PluginsModalState, PluginsTab};
 use crate::app::AppState;
 use crate::plugin::marketplace::PluginEntry;
+use crate::plugin::PluginAction;
 use ratatui::{
     layout::{Constraint, Direction, Layout, Rect},
     style::{Color, Modifier, Style},
@@ -58,7 +59,6 @@ pub fn render_plugins_modal(f: &mut Frame, state: &AppState) {
             selected_index,
         } => render_select_input_view(f, state, plugin_name, field_name, options, *selected_index),
         PluginsModalState::Executing { plugin_name } => render_executing_view(f, state, plugin_name),
-        PluginsModalState::Preview { items } => render_preview_view(f, state, items),
         PluginsModalState::Error { message } => render_error_view(f, state, message),
     }
 }
@@ -149,7 +149,7 @@ fn render_tabs_view(
 
     // Render footer
     let footer_text = match active_tab {
-        PluginsTab::Installed => "[Tab] switch | [j/k] navigate | [Enter] invoke | [Esc] close",
+        PluginsTab::Installed => "[Tab] switch | [j/k] navigate | [Enter] generate / run action | [Esc] close",
         PluginsTab::Marketplace => "[Tab] switch | [j/k] navigate | [Enter] details | [Esc] close",
     };
     let footer = Paragraph::new(Line::from(Span::styled(
@@ -198,11 +198,17 @@ fn render_installed_list(f: &mut Frame, state: &AppState, area: Rect, selected_i
         return;
     }
 
-    let items: Vec<ListItem> = plugins
-        .iter()
+    let items: Vec<ListItem> = installed_rows(&state.plugin_loader, &state.plugin_action_registry)
+        .into_iter()
         .enumerate()
-        .map(|(i, plugin)| {
+        .map(|(i, row)| {
             let is_selected = i == selected_index;
+            let plugin = match row {
+                InstalledRow::Plugin(plugin) => plugin,
+                InstalledRow::Action(action) => {
+                    return render_action_row(state, action, is_selected);
+                }
+            };
 
             // Status indicator
             let status = if plugin.session_disabled {
@@ -253,6 +259,30 @@ fn render_installed_list(f: &mut Frame, state: &AppState, area: Rect, selected_i
     f.render_widget(list, area);
 }
 
+/// A plugin action under its plugin in the Installed tab, with its key.
+fn render_action_row<'a>(state: &AppState, action: &'a PluginAction, is_selected: bool) -> ListItem<'a> {
+    let name_style = if is_selected {
+        Style::default()
+            .fg(Color::Yellow)
+            .add_modifier(Modifier::BOLD | Modifier::REVERSED)
+    } else {
+        Style::default().fg(state.theme.foreground)
+    };
+    let key = action
+        .keybinding
+        .as_ref()
+        .map(sequence_label)
+        .unwrap_or_else(|| "no key".to_string());
+
+    ListItem::new(Line::from(vec![
+        Span::styled("      ↳ ", Style::default().fg(Color::DarkGray)),
+        Span::styled(&action.action_name, name_style),
+        Span::styled(format!(" [{key}]"), Style::default().fg(Color::Cyan)),
+        Span::raw(" - "),
+        Span::styled(&action.description, Style::default().fg(Color::DarkGray)),
+    ]))
+}
+
 /// Render the marketplace plugins list
 #[allow(clippy::too_many_arguments)]
 fn render_marketplace_list(
@@ -619,38 +649,6 @@ fn render_executing_view(f: &mut Frame, state: &AppState, plugin_name: &str) {
     f.render_widget(paragraph, area);
 }
 
-/// Render the preview view for generated items
-fn render_preview_view(f: &mut Frame, state: &AppState, items: &[crate::todo::TodoItem]) {
-    let area = centered_rect(70, 60, f.area());
-
-    f.render_widget(Clear, area);
-
-    let list_items: Vec<ListItem> = items
-        .iter()
-        .map(|item| {
-            let indent = "  ".repeat(item.indent_level);
-            let line = format!("{}[ ] {}", indent, item.content);
-            ListItem::new(Line::from(Span::styled(
-                line,
-                Style::default().fg(state.theme.foreground),
-            )))
-        })
-        .collect();
-
-    let title = format!(" Generated {} item(s) - Add to list? (Y/n) ", items.len());
-
-    let list = List::new(list_items)
-        .block(
-            Block::default()
-                .borders(Borders::ALL)
-                .title(title)
-                .style(Style::default().bg(state.theme.background)),
-        )
-        .style(Style::default().fg(state.theme.foreground));
-
-    f.render_widget(list, area);
-}
-
 /// Render the error view
 fn render_error_view(f: &mut Frame, state: &AppState, message: &str) {
     let area = centered_rect(60, 30, f.area());
@@ -0,0 +1,62 @@
+use crate::app::AppState;
+use super::centered_rect;
+use ratatui::{
+    style::{Color, Modifier, Style},
+    text::{Line, Span},
+    widgets::{Block, Borders, Clear, Paragraph},
+    Frame,
+};
+
+/// Render the items on any date that matched the all-dates search, newest
+/// first. Enter goes to the highlighted item's day.
+pub fn render_search_results_modal(f: &mut Frame, state: &AppState) {
+    let area = centered_rect(70, 60, f.area());
+    let inner_height = area.height.saturating_sub(2) as usize;
+    let theme = &state.theme;
+    let selected = state.search_results_selected;
+
+    let block = Block::default()
+        .borders(Borders::ALL)
+        .title(format!(
+            " {} results for \"{}\" ",
+            state.search_results.len(),
+            state.search_results_query
+        ))
+        .title_bottom(Line::from(" ↑↓ select • Enter go to day • Esc close ").centered())
+        .style(Style::default().bg(theme.background).fg(theme.foreground));
+
+    let date_style = Style::default().fg(Color::DarkGray);
+    let offset = selected.saturating_sub(inner_height.saturating_sub(1));
+    let lines: Vec<Line> = state
+        .search_results
+        .iter()
+        .enumerate()
+        .skip(offset)
+        .take(inner_height)
+        .map(|(i, hit)| {
+            let date = format!(" {} ", hit.date.format("%Y-%m-%d"));
+            let text = format!("{} {}", hit.state, hit.content);
+            let archived = if hit.archived { " (archived)" } else { "" };
+            if i == selected {
+                let style = Style::default()
+                    .fg(theme.status_bar_fg)
+                    .bg(theme.status_bar_bg)
+                    .add_modifier(Modifier::BOLD);
+                Line::from(vec![
+                    Span::styled(date, style),
+                    Span::styled(text, style),
+                    Span::styled(archived, style),
+                ])
+            } else {
+                Line::from(vec![
+                    Span::styled(date, date_style),
+                    Span::styled(text, Style::default().fg(theme.foreground)),
+                    Span::styled(archived, date_style),
+                ])
+            }
+        })
+        .collect();
+
+    f.render_widget(Clear, area);
+    f.render_widget(Paragraph::new(lines).block(block), area);
+}
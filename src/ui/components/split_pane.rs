@@ -0,0 +1,73 @@
+use crate::app::AppState;
+use ratatui::{
+    layout::Rect,
+    style::{Color, Modifier, Style},
+    text::{Line, Span},
+    widgets::{Block, Borders, Paragraph},
+    Frame,
+};
+
+/// Render the split view's other day, read-only. Its selection only shows
+/// while the pane has focus.
+pub fn render(f: &mut Frame, state: &AppState, area: Rect) {
+    let Some(split) = &state.split_view else {
+        return;
+    };
+    let theme = &state.theme;
+    let list = &split.list;
+
+    let today_marker = if list.date == state.today { ", today" } else { "" };
+    let title = format!(" {} (read-only{today_marker}) ", list.date.format("%a %Y-%m-%d"));
+    let border_style = if split.focused {
+        Style::default().fg(Color::Yellow)
+    } else {
+        Style::default().fg(Color::DarkGray)
+    };
+    let hint = if split.focused { " y yank • Ctrl+w w back " } else { " Ctrl+w w to focus " };
+    let block = Block::default()
+        .borders(Borders::ALL)
+        .border_style(border_style)
+        .title(Span::styled(title, Style::default().fg(theme.foreground).add_modifier(Modifier::BOLD)))
+        .title_bottom(Line::from(hint).right_aligned())
+        .style(Style::default().bg(theme.background));
+
+    let visible = split.visible_indices();
+    if visible.is_empty() {
+        let empty = Paragraph::new(Span::styled(" Nothing on this day", Style::default().fg(Color::DarkGray)));
+        f.render_widget(empty.block(block), area);
+        return;
+    }
+
+    let inner_height = area.height.saturating_sub(2) as usize;
+    let offset = split.cursor.saturating_sub(inner_height.saturating_sub(1));
+    let lines: Vec<Line> = visible
+        .iter()
+        .enumerate()
+        .skip(offset)
+        .take(inner_height)
+        .map(|(row, &idx)| {
+            let item = &list.items[idx];
+            let collapsible = list.has_children(idx) || item.description.is_some();
+            let mut style = if item.state.is_complete() {
+                Style::default().fg(Color::DarkGray)
+            } else {
+                Style::default().fg(theme.foreground)
+            };
+            if split.focused && row == split.cursor {
+                style = style.add_modifier(Modifier::REVERSED);
+            }
+            Line::from(Span::styled(
+                format!(
+                    " {}{}{} {}",
+                    "  ".repeat(item.indent_level),
+                    theme.glyphs.fold_icon(collapsible, item.collapsed),
+                    theme.glyphs.checkbox(item.state, ' '),
+                    item.content
+                ),
+                style,
+            ))
+        })
+        .collect();
+
+    f.render_widget(Paragraph::new(lines).block(block), area);
+}
@@ -0,0 +1,56 @@
+use crate::app::AppState;
+use crate::storage::revision::Change;
+use super::centered_rect;
+use ratatui::{
+    style::{Color, Style},
+    text::{Line, Span},
+    widgets::{Block, Borders, Clear, Paragraph, Wrap},
+    Frame,
+};
+
+/// Render the choice between unsaved changes and the day as saved elsewhere
+/// (the API, the CLI, another TUI), listing what was done there.
+pub fn render_stale_save_modal(f: &mut Frame, state: &AppState) {
+    let Some(stale) = &state.stale_save else {
+        return;
+    };
+    let area = centered_rect(70, 60, f.area());
+    let inner_height = area.height.saturating_sub(2) as usize;
+    let theme = &state.theme;
+
+    let block = Block::default()
+        .borders(Borders::ALL)
+        .title(" List changed elsewhere ")
+        .title_bottom(Line::from(" m merge • k keep mine • t take theirs ").centered())
+        .style(Style::default().bg(theme.background).fg(theme.foreground));
+
+    let dim = Style::default().fg(Color::DarkGray);
+    let mut lines = vec![
+        Line::from(format!(
+            " {} was saved elsewhere while it had unsaved changes here.",
+            stale.date.format("%B %d, %Y")
+        )),
+        Line::from(Span::styled(
+            " Merging keeps both sides' changes; items removed on either side stay removed.",
+            dim,
+        )),
+        Line::default(),
+    ];
+
+    for change in stale.changes() {
+        let (marker, text, note, color) = match change {
+            Change::Added(text) => ("+", text, "added there", Color::Green),
+            Change::Edited(text) => ("~", text, "changed there", Color::Yellow),
+            Change::Removed(text) => ("-", text, "removed there", Color::Red),
+        };
+        lines.push(Line::from(vec![
+            Span::styled(format!("   {marker} "), Style::default().fg(color)),
+            Span::styled(text, Style::default().fg(theme.foreground)),
+            Span::styled(format!("  {note}"), dim),
+        ]));
+    }
+    lines.truncate(inner_height);
+
+    f.render_widget(Clear, area);
+    f.render_widget(Paragraph::new(lines).block(block).wrap(Wrap { trim: false }), area);
+}
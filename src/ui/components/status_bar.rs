@@ -1,5 +1,8 @@
 use crate::app::mode::Mode;
+use crate::app::toast::ToastLevel;
 use crate::app::AppState;
+use crate::utils::unicode::{after_first_char, display_width, first_char_as_str, prefix_fitting};
+use std::ops::Range;
 use ratatui::{
     layout::Rect,
     style::{Modifier, Style},
@@ -16,31 +19,27 @@ pub fn render(f: &mut Frame, state: &AppState, area: Rect) {
         return;
     }
 
-    if let Some((message, time)) = &state.status_message
-        && time.elapsed().as_secs() <= 3 {
-            render_status_message(f, message, area);
-            return;
-        }
+    if state.mode == Mode::ConfirmQuit {
+        render_confirm_quit(f, area);
+        return;
+    }
 
-    let mode_text = format!("{}", state.mode);
-    let readonly_indicator = if state.is_readonly() {
-        " [READONLY]"
-    } else {
-        ""
-    };
-    let save_indicator = if state.unsaved_changes {
-        " [unsaved]"
-    } else {
-        ""
-    };
+    if state.mode == Mode::Search {
+        let count = if state.edit_buffer.is_empty() {
+            String::new()
+        } else {
+            format!("  ({} matches, Tab searches all dates)", state.search_matches().len())
+        };
+        render_prompt(f, state, area, '/', count);
+        return;
+    }
 
-    let date_str = state.viewing_date.format("%Y-%m-%d").to_string();
-    let date_label = if state.viewing_date == state.today {
-        format!("{date_str} (today)")
-    } else {
-        format!("{date_str} (archived)")
-    };
+    if state.mode == Mode::Command {
+        render_prompt(f, state, area, ':', String::new());
+        return;
+    }
 
+    let (left_content, overdue_columns) = left_content(state);
     let nav_hint = if state.is_readonly() {
         "< prev  > next  T today"
     } else {
@@ -52,26 +51,10 @@ pub fn render(f: &mut Frame, state: &AppState, area: Rect) {
         None => format!("v{VERSION}"),
     };
 
-    let project_prefix = if state.current_project.name != crate::project::DEFAULT_PROJECT_NAME {
-        format!("[{}] ", state.current_project.name)
-    } else {
-        String::new()
-    };
-
-    let left_content = format!(
-        " {}{} | {} | {} items{}{}",
-        project_prefix,
-        mode_text,
-        date_label,
-        state.todo_list.items.len(),
-        readonly_indicator,
-        save_indicator
-    );
-
     // Format: "{left_content} {nav_hint} {padding} {github_link} {version_text} "
     // Spaces: 4 spaces between segments + 1 trailing space
     let padding = area.width.saturating_sub(
-        left_content.len() as u16 + nav_hint.len() as u16 + github_link.len() as u16 + version_text.len() as u16 + 5,
+        (display_width(&left_content) + nav_hint.len() + github_link.len() + display_width(&version_text) + 5) as u16,
     );
 
     let base_style = Style::default()
@@ -94,11 +77,135 @@ pub fn render(f: &mut Frame, state: &AppState, area: Rect) {
         padding = padding as usize
     );
 
-    let status = Paragraph::new(Line::from(vec![Span::styled(status_line, readonly_style)]));
+    // The overdue count stands out, as overdue dates do in the list
+    let spans = match overdue_columns {
+        Some(columns) => {
+            let start = prefix_fitting(&status_line, columns.start);
+            let end = prefix_fitting(&status_line, columns.end);
+            let overdue_style = readonly_style
+                .fg(state.theme.exclamation)
+                .add_modifier(Modifier::BOLD);
+            vec![
+                Span::styled(status_line[..start].to_string(), readonly_style),
+                Span::styled(status_line[start..end].to_string(), overdue_style),
+                Span::styled(status_line[end..].to_string(), readonly_style),
+            ]
+        }
+        None => vec![Span::styled(status_line, readonly_style)],
+    };
+    let status = Paragraph::new(Line::from(spans));
 
     f.render_widget(status, area);
 }
 
+/// Everything left of the navigation hint, and the columns of the overdue
+/// count in it, if there are overdue items.
+fn left_content(state: &AppState) -> (String, Option<Range<usize>>) {
+    let mode_text = format!("{}", state.mode);
+    let readonly_indicator = if state.is_readonly() {
+        " [READONLY]"
+    } else {
+        ""
+    };
+    let mut filter_indicator = if state.show_waiting_only {
+        " [waiting]".to_string()
+    } else {
+        String::new()
+    };
+    if let Some(summary) = state.filter_summary() {
+        filter_indicator.push_str(&format!(" [filter: {summary}]"));
+    }
+    let search_indicator = state
+        .search_query
+        .as_ref()
+        .map(|query| format!(" [/{query}: {} matches]", state.search_matches().len()))
+        .unwrap_or_default();
+    // Register and count typed so far, as in `"a3`
+    let register = state.pending_register.map(|r| format!("\"{r}")).unwrap_or_default();
+    let count = state.pending_count.map(|c| c.to_string()).unwrap_or_default();
+    let mut count_indicator = if register.is_empty() && count.is_empty() {
+        String::new()
+    } else {
+        format!(" [{register}{count}]")
+    };
+    if let Some(name) = state.macros.recording() {
+        count_indicator.push_str(&format!(" [recording @{name}]"));
+    }
+    let save_indicator = if state.unsaved_changes || state.has_pending_edit() {
+        " [unsaved]"
+    } else {
+        ""
+    };
+
+    let date_str = state.viewing_date.format("%Y-%m-%d").to_string();
+    let date_label = if state.viewing_date == state.today {
+        format!("{date_str} (today)")
+    } else {
+        format!("{date_str} (archived)")
+    };
+
+    let project_prefix = if state.current_project.name != crate::project::DEFAULT_PROJECT_NAME {
+        format!("[{}] ", state.current_project.name)
+    } else {
+        String::new()
+    };
+
+    let planned = state
+        .todo_list
+        .total_estimate()
+        .map(|total| format!(" | {total} planned"))
+        .unwrap_or_default();
+
+    // Screen readers start at the left, so accessible mode leads with the
+    // newest message instead of floating it over the list
+    let message = state
+        .toasts
+        .visible()
+        .last()
+        .filter(|_| state.accessible_mode)
+        .map(|toast| match toast.level {
+            ToastLevel::Info => format!("{} | ", toast.message),
+            ToastLevel::Warn => format!("Warning: {} | ", toast.message),
+            ToastLevel::Error => format!("Error: {} | ", toast.message),
+        })
+        .unwrap_or_default();
+
+    let (open, done, overdue) = state.item_counts();
+    let mut counts = format!("{open} open · {done} done");
+    let before_overdue = format!(
+        " {}{}{}{} | {} | {}",
+        message, project_prefix, mode_text, count_indicator, date_label, counts
+    );
+    let overdue_columns = (overdue > 0).then(|| {
+        let overdue_text = format!("{overdue} overdue");
+        let start = display_width(&before_overdue) + display_width(" · ");
+        counts.push_str(&format!(" · {overdue_text}"));
+        start..start + display_width(&overdue_text)
+    });
+
+    let left_content = format!(
+        " {}{}{}{} | {} | {}{}{}{}{}{}",
+        message,
+        project_prefix,
+        mode_text,
+        count_indicator,
+        date_label,
+        counts,
+        planned,
+        filter_indicator,
+        search_indicator,
+        readonly_indicator,
+        save_indicator
+    );
+    (left_content, overdue_columns)
+}
+
+/// Columns of the status bar's overdue count, which jumps to the first
+/// overdue item when clicked.
+pub fn overdue_columns(state: &AppState) -> Option<Range<usize>> {
+    left_content(state).1
+}
+
 fn render_confirm_delete(f: &mut Frame, state: &AppState, area: Rect) {
     let subtask_count = state.pending_delete_subtask_count.unwrap_or(0);
     let prompt = format!(
@@ -119,22 +226,37 @@ fn render_confirm_delete(f: &mut Frame, state: &AppState, area: Rect) {
     f.render_widget(status, area);
 }
 
-fn render_status_message(f: &mut Frame, message: &str, area: Rect) {
-    let display_message = format!(" {message} ");
-
+fn render_confirm_quit(f: &mut Frame, area: Rect) {
+    let prompt = " Unsaved changes: (s)ave and quit / (d)iscard and quit / (c)ancel ";
     let style = Style::default()
         .fg(ratatui::style::Color::White)
-        .bg(ratatui::style::Color::Rgb(0, 100, 0))
+        .bg(ratatui::style::Color::Rgb(180, 100, 0))
         .add_modifier(Modifier::BOLD);
 
-    let padding = area.width.saturating_sub(display_message.len() as u16);
-    let status_line = format!(
-        "{}{:padding$}",
-        display_message,
-        "",
-        padding = padding as usize
-    );
+    let padding = area.width.saturating_sub(display_width(prompt) as u16);
+    let status_line = format!("{}{:padding$}", prompt, "", padding = padding as usize);
+    f.render_widget(Paragraph::new(Line::from(Span::styled(status_line, style))), area);
+}
 
-    let status = Paragraph::new(Line::from(vec![Span::styled(status_line, style)]));
-    f.render_widget(status, area);
+/// Single-line prompt for search (`/`) and commands (`:`), with a dimmed hint after it.
+fn render_prompt(f: &mut Frame, state: &AppState, area: Rect, prefix: char, hint: String) {
+    let base_style = Style::default()
+        .fg(state.theme.status_bar_fg)
+        .bg(state.theme.status_bar_bg);
+
+    let before_cursor = &state.edit_buffer[..state.edit_cursor_pos];
+    let after_cursor = &state.edit_buffer[state.edit_cursor_pos..];
+    let cursor_char = if after_cursor.is_empty() {
+        " "
+    } else {
+        first_char_as_str(after_cursor)
+    };
+
+    let line = Line::from(vec![
+        Span::styled(format!(" {prefix}{before_cursor}"), base_style),
+        Span::styled(cursor_char, base_style.add_modifier(Modifier::REVERSED)),
+        Span::styled(after_first_char(after_cursor), base_style),
+        Span::styled(hint, base_style.add_modifier(Modifier::DIM)),
+    ]);
+    f.render_widget(Paragraph::new(line).style(base_style), area);
 }
@@ -0,0 +1,71 @@
+use crate::app::AppState;
+use crate::ui::theme::THEME_NAMES;
+use ratatui::{
+    layout::Rect,
+    style::{Modifier, Style},
+    text::{Line, Span},
+    widgets::{Block, Borders, Clear, Paragraph},
+    Frame,
+};
+
+const WIDTH: u16 = 34;
+
+/// Render the theme list. The highlighted theme is already applied, so the
+/// list and status bar behind the popup show what it looks like.
+pub fn render_theme_picker_modal(f: &mut Frame, state: &AppState) {
+    let Some(picker) = &state.theme_picker else {
+        return;
+    };
+    let theme = &state.theme;
+    // Theme names, a blank line, a sample line and borders
+    let height = THEME_NAMES.len() as u16 + 4;
+    let screen = f.area();
+    let area = Rect {
+        x: screen.x + screen.width.saturating_sub(WIDTH) / 2,
+        y: screen.y + screen.height.saturating_sub(height) / 2,
+        width: WIDTH.min(screen.width),
+        height: height.min(screen.height),
+    };
+
+    let mut lines: Vec<Line> = THEME_NAMES
+        .iter()
+        .enumerate()
+        .map(|(i, name)| {
+            if i == picker.selected {
+                Line::from(Span::styled(
+                    format!(" ▶ {name}"),
+                    Style::default()
+                        .fg(theme.status_bar_fg)
+                        .bg(theme.status_bar_bg)
+                        .add_modifier(Modifier::BOLD),
+                ))
+            } else {
+                Line::from(Span::styled(format!("   {name}"), Style::default().fg(theme.foreground)))
+            }
+        })
+        .collect();
+
+    lines.push(Line::from(""));
+    let mut sample = vec![
+        Span::styled(" [?]", Style::default().fg(theme.question)),
+        Span::styled(" [!]", Style::default().fg(theme.exclamation)),
+        Span::styled(" [*]", Style::default().fg(theme.in_progress)),
+        Span::styled(" [-]", Style::default().fg(theme.cancelled)),
+    ];
+    for priority in theme.priorities.iter().take(3) {
+        sample.push(Span::styled(
+            format!(" {}", priority.label),
+            Style::default().fg(priority.color).add_modifier(Modifier::BOLD),
+        ));
+    }
+    lines.push(Line::from(sample));
+
+    let block = Block::default()
+        .borders(Borders::ALL)
+        .title(" Theme ")
+        .title_bottom(Line::from(" Enter save • Esc cancel ").centered())
+        .style(Style::default().bg(theme.background).fg(theme.foreground));
+
+    f.render_widget(Clear, area);
+    f.render_widget(Paragraph::new(lines).block(block), area);
+}
@@ -0,0 +1,64 @@
+use crate::app::activity::Activity;
+use crate::app::AppState;
+use super::centered_rect;
+use ratatui::{
+    style::{Color, Modifier, Style},
+    text::{Line, Span},
+    widgets::{Block, Borders, Clear, Paragraph},
+    Frame,
+};
+
+/// Render the session activity timeline, newest first.
+pub fn render_timeline_modal(f: &mut Frame, state: &AppState) {
+    let area = centered_rect(60, 70, f.area());
+    let inner_height = area.height.saturating_sub(2) as usize;
+
+    let title = if state.activity.is_empty() {
+        " Timeline ".to_string()
+    } else {
+        format!(" Timeline: {} ", state.activity.summary())
+    };
+    let block = Block::default()
+        .borders(Borders::ALL)
+        .title(title)
+        .title_bottom(Line::from(" ↑↓ scroll • Esc to close ").centered())
+        .style(Style::default().bg(state.theme.background));
+
+    let time_style = Style::default().fg(Color::DarkGray);
+    let content_style = Style::default().fg(state.theme.foreground);
+
+    let lines: Vec<Line> = if state.activity.is_empty() {
+        vec![Line::from(Span::styled(
+            " Nothing yet. Items you add, complete or move show up here.",
+            time_style,
+        ))]
+    } else {
+        state
+            .activity
+            .entries()
+            .rev()
+            .skip(state.timeline_scroll as usize)
+            .take(inner_height)
+            .map(|entry| {
+                let color = match entry.activity {
+                    Activity::Added => Color::Cyan,
+                    Activity::Completed => Color::Green,
+                    Activity::Cancelled | Activity::Deleted => Color::Red,
+                    Activity::Reopened => Color::Yellow,
+                    Activity::Archived | Activity::Moved(_) => Color::Magenta,
+                };
+                Line::from(vec![
+                    Span::styled(format!(" {} ", entry.at.format("%H:%M")), time_style),
+                    Span::styled(
+                        format!("{:<10} ", entry.activity.to_string()),
+                        Style::default().fg(color).add_modifier(Modifier::BOLD),
+                    ),
+                    Span::styled(entry.content.clone(), content_style),
+                ])
+            })
+            .collect()
+    };
+
+    f.render_widget(Clear, area);
+    f.render_widget(Paragraph::new(lines).block(block), area);
+}
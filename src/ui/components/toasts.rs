@@ -0,0 +1,55 @@
+use crate::app::toast::ToastLevel;
+use crate::app::AppState;
+use ratatui::{
+    layout::Rect,
+    style::{Color, Modifier, Style},
+    text::{Line, Span},
+    widgets::{Clear, Paragraph},
+    Frame,
+};
+use unicode_width::{UnicodeWidthChar, UnicodeWidthStr};
+
+fn toast_style(level: ToastLevel) -> Style {
+    let bg = match level {
+        ToastLevel::Info => Color::Rgb(0, 100, 0),
+        ToastLevel::Warn => Color::Rgb(150, 100, 0),
+        ToastLevel::Error => Color::Rgb(150, 0, 0),
+    };
+    Style::default().fg(Color::White).bg(bg).add_modifier(Modifier::BOLD)
+}
+
+/// Stack the visible toasts in the bottom-right corner of `area`, newest at
+/// the bottom. Long messages are cut to half the width.
+pub fn render(f: &mut Frame, state: &AppState, area: Rect) {
+    let max_width = (area.width / 2).max(20).min(area.width) as usize;
+    let mut y = area.bottom();
+    for toast in state.toasts.visible().rev() {
+        if y <= area.top() {
+            break;
+        }
+        y -= 1;
+
+        let mut text = format!(" {} ", toast.message);
+        if text.width() > max_width {
+            text = truncate_to_width(&text, max_width.saturating_sub(2));
+            text.push_str("… ");
+        }
+        let width = text.width() as u16;
+        let rect = Rect::new(area.right().saturating_sub(width), y, width, 1);
+        f.render_widget(Clear, rect);
+        f.render_widget(
+            Paragraph::new(Line::from(Span::styled(text, toast_style(toast.level)))),
+            rect,
+        );
+    }
+}
+
+fn truncate_to_width(text: &str, width: usize) -> String {
+    let mut used = 0;
+    text.chars()
+        .take_while(|c| {
+            used += c.width().unwrap_or(0);
+            used <= width
+        })
+        .collect()
+}
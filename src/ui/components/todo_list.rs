@@ -1,26 +1,35 @@
 use crate::app::{AppState, Mode};
-use crate::todo::{Priority, TodoState};
+use crate::keybindings::Action;
+use crate::plugin::marketplace::DEFAULT_MARKETPLACE;
+use crate::todo::{Estimate, TodoItem, TodoState};
+use crate::ui::components::help;
+use crate::ui::highlight::{self, HighlightRule};
+use crate::ui::markdown::{self, MdStyle, Run};
 use crate::ui::theme::Theme;
-use crate::utils::unicode::{after_first_char, first_char_as_str};
+use crate::utils::search;
+use crate::utils::unicode::{after_first_char, first_char_as_str, wrap_lines, wrap_ranges};
+use chrono::Local;
 use ratatui::{
     layout::{Margin, Rect},
     style::{Color, Modifier, Style},
     text::{Line, Span},
-    widgets::{Block, Borders, List, ListItem, Scrollbar, ScrollbarOrientation, ScrollbarState},
+    widgets::{Block, Borders, List, ListItem, ListState, Paragraph, Scrollbar, ScrollbarOrientation, ScrollbarState},
     Frame,
 };
+use std::collections::{HashMap, HashSet};
 use unicode_width::UnicodeWidthStr;
 
-/// Get the priority badge text and color for a given priority level
-fn priority_badge(priority: Option<Priority>, theme: &Theme) -> Option<(String, Color)> {
-    priority.map(|p| {
-        let (text, color) = match p {
-            Priority::P0 => ("[P0]", theme.priority_p0),
-            Priority::P1 => ("[P1]", theme.priority_p1),
-            Priority::P2 => ("[P2]", theme.priority_p2),
-        };
-        (text.to_string(), color)
-    })
+/// Marker shown before the content of pinned items
+pub const PINNED_GLYPH: &str = "★ ";
+
+/// Marker shown after the content of items with a link
+pub const LINK_GLYPH: &str = " ↗";
+
+/// Get the priority badge text and color for an item
+fn priority_badge(state: &AppState, item: &TodoItem) -> Option<(String, Color)> {
+    let badge = state.item_priority_badge(item)?;
+    let color = state.theme.priority_style(item.priority?).color;
+    Some((badge, color))
 }
 
 /// Compute the base style for a todo item (color only, no strikethrough)
@@ -54,52 +63,100 @@ fn compute_content_style(state: TodoState, theme: &Theme, is_in_selection: bool)
 }
 
 pub fn render(f: &mut Frame, state: &mut AppState, area: Rect) {
+    // The welcome panel is for a fresh install; once there's an item it's done
+    if !state.todo_list.items.is_empty() {
+        state.onboarding = false;
+    }
+
     let mut items: Vec<ListItem> = Vec::new();
-    let hidden_indices = state.todo_list.build_hidden_indices();
-    let available_width = area.width.saturating_sub(2) as usize;
+    let hidden_indices = state.hidden_indices();
+    let id_width = state.short_id_gutter_width();
+    let line_width = state.line_number_width();
+    let gutter_width = line_width + id_width;
+    let short_ids = if id_width > 0 {
+        state.todo_list.short_ids()
+    } else {
+        HashMap::new()
+    };
+    let gutter_style = Style::default().fg(Color::DarkGray);
+    let today = Local::now().date_naive();
+    let overdue_style = Style::default()
+        .fg(state.theme.exclamation)
+        .add_modifier(Modifier::BOLD);
+    let search_pattern = state.search_pattern();
+    let search_matches: HashSet<usize> = state.search_matches().into_iter().collect();
+    let mut highlight_style = Style::default().bg(Color::Yellow).fg(Color::Black);
+    if state.no_color {
+        highlight_style = highlight_style.add_modifier(Modifier::UNDERLINED);
+    }
+    let available_width = (area.width.saturating_sub(2) as usize).saturating_sub(gutter_width);
     let viewport_height = area.height.saturating_sub(2) as usize; // minus borders
     let scroll_offset = state.list_state.offset();
     let mut list_item_index: usize = 0;
     let mut height_from_offset: usize = 0;
+    let mut line_number: usize = 0;
+    let is_creating = state.is_creating_new_item && state.mode == Mode::Edit;
+
+    // Only list items that can end up on screen are built. The list scrolls
+    // from the offset, or to the selection when that is out of view, and
+    // every item is at least a row tall.
+    let selected = state.list_state.selected().unwrap_or(scroll_offset);
+    let window_start = scroll_offset.min(selected);
+    let window_end = scroll_offset.max(selected) + viewport_height + 1;
+    let mut first_built: Option<usize> = None;
+    // Rows of the list items before and after the window, from the cache
+    let mut unbuilt_lines: usize = 0;
+    let item_heights = state.item_heights();
 
     for (idx, item) in state.todo_list.items.iter().enumerate() {
         if hidden_indices.contains(&idx) {
             continue;
         }
+        line_number += 1;
+
+        let height = item_heights[idx];
+        let new_item_rows = usize::from(is_creating && idx == state.cursor_position);
+        let list_items = 1 + usize::from(height.description > 0) + new_item_rows;
+        if list_item_index + list_items <= window_start || list_item_index >= window_end {
+            list_item_index += list_items;
+            unbuilt_lines += height.row + height.description + new_item_rows;
+            continue;
+        }
+        first_built.get_or_insert(list_item_index);
 
         let indent = "  ".repeat(item.indent_level);
         let has_children = state.todo_list.has_children(idx);
+        let gutter = gutter_label(
+            line_number,
+            short_ids.get(&item.id).map(String::as_str),
+            line_width,
+            id_width,
+        );
+        let gutter = gutter.as_deref();
+        let pattern = search_pattern.filter(|_| search_matches.contains(&idx));
 
         let has_description = item.description.is_some();
         let is_collapsible = has_children || has_description;
 
-        let fold_icon = if is_collapsible {
-            if item.collapsed {
-                "▶ "
-            } else {
-                "▼ "
-            }
-        } else {
-            "  "
-        };
-
-        let checkbox = if item.state == TodoState::InProgress {
-            format!("[{}]", state.get_spinner_char())
-        } else if item.state == TodoState::Empty && has_children {
-            let (completed, _) = state.todo_list.count_children_stats(idx);
-            if completed > 0 {
-                "[-]".to_string()
-            } else {
-                format!("{}", item.state)
-            }
-        } else {
-            format!("{}", item.state)
-        };
+        let fold_icon = state.theme.glyphs.fold_icon(is_collapsible, item.collapsed);
+        let checkbox = state.item_checkbox(idx);
 
+        let estimate_str = item
+            .estimate
+            .map(|e| format!(" ~{e}"))
+            .unwrap_or_default();
+        let waiting_str = item
+            .waiting_on
+            .as_ref()
+            .map(|who| format!(" @{who}"))
+            .unwrap_or_default();
         let due_date_str = item
             .due_date
             .map(|d| format!(" [{}]", d.format("%Y-%m-%d")))
             .unwrap_or_default();
+        let overdue_date = item
+            .is_overdue(today)
+            .then(|| due_date_str.trim_start().to_string());
 
         let collapse_indicator = if item.collapsed && has_children {
             let (completed, total) = state.todo_list.count_children_stats(idx);
@@ -112,10 +169,21 @@ pub fn render(f: &mut Frame, state: &mut AppState, area: Rect) {
         let prefix_width = prefix.width();
         let checkbox_with_space = format!("{checkbox} ");
         let checkbox_width = checkbox_with_space.width();
-        let content_with_extras = format!("{}{}{}", item.content, due_date_str, collapse_indicator);
+        let pinned_str = if item.pinned { PINNED_GLYPH } else { "" };
+        let link_str = if item.link.is_some() { LINK_GLYPH } else { "" };
+        let content_with_extras = format!(
+            "{}{}{}{}{}{}{}",
+            pinned_str,
+            item.content,
+            link_str,
+            estimate_str,
+            waiting_str,
+            due_date_str,
+            collapse_indicator
+        );
 
         // Get priority badge if item has priority
-        let badge = priority_badge(item.priority, &state.theme);
+        let badge = priority_badge(state, item);
         let badge_width = badge.as_ref().map(|(text, _)| text.width() + 1).unwrap_or(0); // +1 for space after badge
 
         let is_in_selection = state.is_selected(idx) && state.mode == Mode::Visual;
@@ -123,7 +191,10 @@ pub fn render(f: &mut Frame, state: &mut AppState, area: Rect) {
         // Base style for prefix elements (no strikethrough)
         let base_style = compute_base_style(item.state, &state.theme, is_in_selection);
         // Content style includes strikethrough for cancelled items
-        let text_style = compute_content_style(item.state, &state.theme, is_in_selection);
+        let mut text_style = compute_content_style(item.state, &state.theme, is_in_selection);
+        if state.sink_completed && item.state.is_complete() {
+            text_style = text_style.add_modifier(Modifier::DIM);
+        }
 
         let content_max_width = available_width.saturating_sub(prefix_width + badge_width + checkbox_width);
 
@@ -138,7 +209,7 @@ pub fn render(f: &mut Frame, state: &mut AppState, area: Rect) {
         if should_show_new_item_above {
             let new_item_lines = build_wrapped_edit_lines(state, available_width);
             let h = new_item_lines.len();
-            items.push(ListItem::new(new_item_lines));
+            items.push(ListItem::new(with_gutter(new_item_lines, None, gutter_width, gutter_style)));
             if list_item_index >= scroll_offset {
                 height_from_offset += h;
             }
@@ -149,7 +220,7 @@ pub fn render(f: &mut Frame, state: &mut AppState, area: Rect) {
             let edit_lines =
                 build_wrapped_edit_lines_for_existing(state, available_width, item.indent_level);
             let h = edit_lines.len();
-            items.push(ListItem::new(edit_lines));
+            items.push(ListItem::new(with_gutter(edit_lines, gutter, gutter_width, gutter_style)));
             if list_item_index >= scroll_offset {
                 height_from_offset += h;
             }
@@ -158,7 +229,10 @@ pub fn render(f: &mut Frame, state: &mut AppState, area: Rect) {
             let should_truncate = item.collapsed && has_description;
 
             if should_truncate {
-                let content_with_due = format!("{}{}", item.content, due_date_str);
+                let content_with_due = format!(
+                    "{}{}{}{}{}{}",
+                    pinned_str, item.content, link_str, estimate_str, waiting_str, due_date_str
+                );
                 let indicator_width = collapse_indicator.width();
                 let available_for_content = content_max_width.saturating_sub(indicator_width);
                 let truncated_content =
@@ -181,17 +255,27 @@ pub fn render(f: &mut Frame, state: &mut AppState, area: Rect) {
                 }
 
                 spans.push(Span::styled(checkbox_with_space.clone(), base_style));
-                spans.push(Span::styled(display_text, text_style));
+                spans.extend(mark_overdue(
+                    highlight_matches(
+                        display_text,
+                        pattern,
+                        &state.highlight_rules,
+                        text_style,
+                        highlight_style,
+                    ),
+                    overdue_date.as_deref(),
+                    overdue_style,
+                ));
                 spans.push(Span::styled(padding, base_style));
 
                 let lines = vec![Line::from(spans)];
-                items.push(ListItem::new(lines));
+                items.push(ListItem::new(with_gutter(lines, gutter, gutter_width, gutter_style)));
                 if list_item_index >= scroll_offset {
                     height_from_offset += 1;
                 }
                 list_item_index += 1;
             } else {
-                let wrapped_lines = wrap_text(&content_with_extras, content_max_width);
+                let wrapped_lines = wrap_lines(&content_with_extras, content_max_width);
                 let continuation_indent = " ".repeat(prefix_width + badge_width + checkbox_width);
 
                 let mut lines: Vec<Line> = Vec::new();
@@ -213,7 +297,17 @@ pub fn render(f: &mut Frame, state: &mut AppState, area: Rect) {
                         }
 
                         spans.push(Span::styled(checkbox_with_space.clone(), base_style));
-                        spans.push(Span::styled(line_text.clone(), text_style));
+                        spans.extend(mark_overdue(
+                            highlight_matches(
+                                line_text.to_string(),
+                                pattern,
+                                &state.highlight_rules,
+                                text_style,
+                                highlight_style,
+                            ),
+                            overdue_date.as_deref(),
+                            overdue_style,
+                        ));
                         spans.push(Span::styled(padding, base_style));
 
                         lines.push(Line::from(spans));
@@ -222,16 +316,25 @@ pub fn render(f: &mut Frame, state: &mut AppState, area: Rect) {
                         let current_width = continuation_indent.width() + line_text.width();
                         let padding = " ".repeat(available_width.saturating_sub(current_width));
 
-                        lines.push(Line::from(vec![
-                            Span::styled(continuation_indent.clone(), base_style),
-                            Span::styled(line_text.clone(), text_style),
-                            Span::styled(padding, base_style),
-                        ]));
+                        let mut spans = vec![Span::styled(continuation_indent.clone(), base_style)];
+                        spans.extend(mark_overdue(
+                            highlight_matches(
+                                line_text.to_string(),
+                                pattern,
+                                &state.highlight_rules,
+                                text_style,
+                                highlight_style,
+                            ),
+                            overdue_date.as_deref(),
+                            overdue_style,
+                        ));
+                        spans.push(Span::styled(padding, base_style));
+                        lines.push(Line::from(spans));
                     }
                 }
 
                 let h = lines.len();
-                items.push(ListItem::new(lines));
+                items.push(ListItem::new(with_gutter(lines, gutter, gutter_width, gutter_style)));
                 if list_item_index >= scroll_offset {
                     height_from_offset += h;
                 }
@@ -255,7 +358,14 @@ pub fn render(f: &mut Frame, state: &mut AppState, area: Rect) {
                 let box_indent = format!("{base_indent}    ");
                 let box_indent_width = box_indent.width();
                 let inner_width = available_width.saturating_sub(box_indent_width + 4);
-                let desc_wrapped = wrap_text(desc, inner_width);
+                let desc_wrapped: Vec<Vec<Run>> = if state.render_markdown {
+                    markdown::wrap(desc, inner_width)
+                } else {
+                    wrap_lines(desc, inner_width)
+                        .into_iter()
+                        .map(|line| vec![(line.to_string(), MdStyle::default())])
+                        .collect()
+                };
 
                 let border_width = inner_width + 2;
                 let top_border = format!(
@@ -301,18 +411,28 @@ pub fn render(f: &mut Frame, state: &mut AppState, area: Rect) {
                     let content_lines_budget = max_lines.saturating_sub(2); // reserve bottom border
                     let is_truncated = content_lines_budget < desc_wrapped.len();
 
-                    for (i, line_text) in desc_wrapped.iter().enumerate() {
+                    for (i, runs) in desc_wrapped.iter().enumerate() {
                         if i >= content_lines_budget {
                             break;
                         }
-                        let padding = inner_width.saturating_sub(line_text.width());
-                        let padded_text = format!("{}{}", line_text, " ".repeat(padding));
-                        desc_lines.push(Line::from(vec![
+                        let line_width: usize = runs.iter().map(|(text, _)| text.width()).sum();
+                        let padding = inner_width.saturating_sub(line_width);
+                        let mut spans = vec![
                             Span::styled(box_indent.clone(), Style::default()),
                             Span::styled(format!("{vertical} "), border_style),
-                            Span::styled(padded_text, text_style),
-                            Span::styled(format!(" {vertical}"), border_style),
-                        ]));
+                        ];
+                        for (text, md_style) in runs {
+                            spans.extend(highlight_matches(
+                                text.clone(),
+                                pattern,
+                                &[],
+                                md_style.apply(text_style),
+                                highlight_style,
+                            ));
+                        }
+                        spans.push(Span::raw(" ".repeat(padding)));
+                        spans.push(Span::styled(format!(" {vertical}"), border_style));
+                        desc_lines.push(Line::from(spans));
                     }
 
                     if is_truncated {
@@ -353,7 +473,12 @@ pub fn render(f: &mut Frame, state: &mut AppState, area: Rect) {
 
                 if !desc_lines.is_empty() {
                     let h = desc_lines.len();
-                    items.push(ListItem::new(desc_lines));
+                    items.push(ListItem::new(with_gutter(
+                        desc_lines,
+                        None,
+                        gutter_width,
+                        gutter_style,
+                    )));
                     if list_item_index >= scroll_offset {
                         height_from_offset += h;
                     }
@@ -369,7 +494,7 @@ pub fn render(f: &mut Frame, state: &mut AppState, area: Rect) {
         if should_show_new_item_below {
             let new_item_lines = build_wrapped_edit_lines(state, available_width);
             let h = new_item_lines.len();
-            items.push(ListItem::new(new_item_lines));
+            items.push(ListItem::new(with_gutter(new_item_lines, None, gutter_width, gutter_style)));
             if list_item_index >= scroll_offset {
                 height_from_offset += h;
             }
@@ -402,6 +527,8 @@ pub fn render(f: &mut Frame, state: &mut AppState, area: Rect) {
                 "  Press 'T' to go back to today",
                 Style::default().fg(state.theme.foreground),
             ))));
+        } else if state.onboarding {
+            items.extend(onboarding_lines(state));
         } else {
             items.push(ListItem::new(Line::from(Span::styled(
                 "",
@@ -437,7 +564,8 @@ pub fn render(f: &mut Frame, state: &mut AppState, area: Rect) {
     let viewport_height = area.height.saturating_sub(2) as usize; // minus borders
 
     // Count actual rendered lines (ListItems may have multiple lines each for wrapped text)
-    let total_rendered_lines: usize = items.iter().map(|item| item.height()).sum();
+    let total_rendered_lines: usize =
+        unbuilt_lines + items.iter().map(|item| item.height()).sum::<usize>();
 
     let scroll_info = if total_rendered_lines > viewport_height {
         let scroll_offset = state.list_state.offset();
@@ -447,20 +575,37 @@ pub fn render(f: &mut Frame, state: &mut AppState, area: Rect) {
         String::new()
     };
 
-    let title = format!(
+    let mut title = format!(
         " to-tui [{}] - {}{}{} ",
         state.current_project.name,
         state.viewing_date.format("%B %d, %Y"),
         title_suffix,
         scroll_info
     );
+    if let Some(breadcrumb) = state.focus_breadcrumb() {
+        title.push_str(&format!("› {breadcrumb} (Esc to exit) "));
+    }
+
+    let mut block = Block::default().borders(Borders::ALL).title(title);
+    if state.show_progress_bar
+        && let Some(gauge) = progress_gauge(state)
+    {
+        block = block.title_top(gauge.right_aligned());
+    }
 
     let list = List::new(items)
-        .block(Block::default().borders(Borders::ALL).title(title))
+        .block(block)
         .style(Style::default().fg(state.theme.foreground))
         .highlight_style(Style::default().add_modifier(Modifier::REVERSED));
 
-    f.render_stateful_widget(list, area, &mut state.list_state);
+    // The built items start at `first_built`; render them with the offset and
+    // selection shifted to match, then shift the scrolled offset back
+    let first_built = first_built.unwrap_or(0);
+    let mut window_state = ListState::default()
+        .with_offset(scroll_offset.saturating_sub(first_built))
+        .with_selected(state.list_state.selected().map(|s| s.saturating_sub(first_built)));
+    f.render_stateful_widget(list, area, &mut window_state);
+    *state.list_state.offset_mut() = first_built + window_state.offset();
 
     // Render scrollbar only when content exceeds viewport
     if total_rendered_lines > viewport_height {
@@ -485,6 +630,240 @@ pub fn render(f: &mut Frame, state: &mut AppState, area: Rect) {
     }
 }
 
+/// Welcome panel shown in place of the empty list until the first todo is
+/// saved. Keys are looked up in the keybindings, so remapped keys show up.
+fn onboarding_lines(state: &AppState) -> Vec<ListItem<'static>> {
+    let heading = Style::default()
+        .fg(state.theme.foreground)
+        .add_modifier(Modifier::BOLD);
+    let text = Style::default().fg(state.theme.foreground);
+    let key_style = Style::default().fg(Color::Cyan);
+    let key = |action: Action| {
+        state
+            .keybindings
+            .navigate_keys(action)
+            .first()
+            .map(help::sequence_label)
+            .unwrap_or_else(|| format!(":{action}"))
+    };
+    let hint = |action: Action, description: &str| {
+        ListItem::new(Line::from(vec![
+            Span::styled(format!("    {:<8}", key(action)), key_style),
+            Span::styled(description.to_string(), text),
+        ]))
+    };
+    let line = |content: String, style: Style| ListItem::new(Line::from(Span::styled(content, style)));
+
+    vec![
+        line(String::new(), text),
+        line("  Welcome to to-tui!".to_string(), heading),
+        line(String::new(), text),
+        line(
+            "  Each day gets its own list; unfinished items can roll over to the next.".to_string(),
+            text,
+        ),
+        line(String::new(), text),
+        line("  Adding items".to_string(), heading),
+        hint(Action::NewItem, "add an item, Enter saves it and starts the next"),
+        hint(Action::Indent, "make it a subtask of the item above"),
+        hint(Action::ToggleState, "mark it done"),
+        hint(Action::EnterEditMode, "edit it"),
+        line(String::new(), text),
+        line("  Projects".to_string(), heading),
+        line(
+            "  Every project keeps its own lists. Create and switch projects with".to_string(),
+            text,
+        ),
+        hint(Action::OpenProjectModal, "the project switcher"),
+        line(String::new(), text),
+        line("  Plugins".to_string(), heading),
+        line(
+            "  Plugins generate todos from other tools, like Jira. Browse them with".to_string(),
+            text,
+        ),
+        hint(Action::OpenPluginMenu, "the plugins menu"),
+        line(format!("  or at https://github.com/{DEFAULT_MARKETPLACE}"), text),
+        line(String::new(), text),
+        hint(Action::StartCommand, "run any action by name"),
+        hint(Action::ToggleHelp, "all keys"),
+        hint(Action::Quit, "quit"),
+    ]
+}
+
+/// Split `text` into spans, styling search matches with `highlight` and the
+/// parts matched by highlight `rules` with their styles patched onto `style`.
+fn highlight_matches(
+    text: String,
+    pattern: Option<&str>,
+    rules: &[HighlightRule],
+    style: Style,
+    highlight: Style,
+) -> Vec<Span<'static>> {
+    let ranges = pattern.map(|p| search::find_matches(&text, p)).unwrap_or_default();
+    let rule_ranges = highlight::rule_ranges(rules, &text);
+    if ranges.is_empty() && rule_ranges.is_empty() {
+        return vec![Span::styled(text, style)];
+    }
+
+    let mut bounds: Vec<usize> = ranges
+        .iter()
+        .chain(rule_ranges.iter().map(|(range, _)| range))
+        .flat_map(|range| [range.start, range.end])
+        .chain([0, text.len()])
+        .collect();
+    bounds.sort_unstable();
+    bounds.dedup();
+
+    let mut spans: Vec<Span<'static>> = Vec::new();
+    for pair in bounds.windows(2) {
+        let (start, end) = (pair[0], pair[1]);
+        let part_style = if ranges.iter().any(|range| range.start <= start && end <= range.end) {
+            highlight
+        } else {
+            rule_ranges
+                .iter()
+                .filter(|(range, _)| range.start <= start && end <= range.end)
+                .fold(style, |acc, (_, rule_style)| acc.patch(*rule_style))
+        };
+        match spans.last_mut() {
+            Some(last) if last.style == part_style => last.content.to_mut().push_str(&text[start..end]),
+            _ => spans.push(Span::styled(text[start..end].to_string(), part_style)),
+        }
+    }
+    spans
+}
+
+/// Restyle an overdue item's `[YYYY-MM-DD]` in a line's spans. The date is
+/// the last thing before the collapse count, so the last match is used.
+fn mark_overdue(spans: Vec<Span<'static>>, date: Option<&str>, style: Style) -> Vec<Span<'static>> {
+    let Some(date) = date else {
+        return spans;
+    };
+    let Some(index) = spans.iter().rposition(|span| span.content.contains(date)) else {
+        return spans;
+    };
+    let mut spans = spans;
+    let span = spans.remove(index);
+    let text = span.content.to_string();
+    let start = text.rfind(date).unwrap_or(0);
+    let end = start + date.len();
+    let parts = [
+        Span::styled(text[..start].to_string(), span.style),
+        Span::styled(text[start..end].to_string(), span.style.patch(style)),
+        Span::styled(text[end..].to_string(), span.style),
+    ];
+    spans.splice(index..index, parts.into_iter().filter(|part| !part.content.is_empty()));
+    spans
+}
+
+/// Path to the selected item, e.g. "Project Alpha ▸ Backend ▸ Auth refactor".
+/// Leading ancestors are dropped when the path is too wide.
+pub fn render_breadcrumb(f: &mut Frame, state: &AppState, area: Rect) {
+    let Some(path) = state.breadcrumb() else {
+        return;
+    };
+    let separator = " ▸ ";
+    let max_width = area.width.saturating_sub(2) as usize;
+    let mut skip = 0;
+    while skip + 1 < path.len()
+        && path[skip..].iter().map(|s| s.width()).sum::<usize>()
+            + separator.width() * (path.len() - skip)
+            > max_width
+    {
+        skip += 1;
+    }
+
+    let dim = Style::default().fg(Color::DarkGray);
+    let ancestor = Style::default().fg(state.theme.foreground);
+    let mut spans = vec![Span::raw(" ")];
+    if skip > 0 {
+        spans.push(Span::styled("…", dim));
+        spans.push(Span::styled(separator, dim));
+    }
+    for (i, segment) in path.iter().enumerate().skip(skip) {
+        if i + 1 == path.len() {
+            spans.push(Span::styled(*segment, ancestor.add_modifier(Modifier::BOLD)));
+        } else {
+            spans.push(Span::styled(*segment, ancestor));
+            spans.push(Span::styled(separator, dim));
+        }
+    }
+    f.render_widget(Paragraph::new(Line::from(spans)), area);
+}
+
+/// Width of the progress bar in the list's top border, in cells
+const PROGRESS_BAR_WIDTH: usize = 10;
+
+/// The day's progress as a bar plus "done/total", e.g. "██████░░░░ 3/5 ",
+/// in estimated time when the list has estimates. None for an empty day.
+fn progress_gauge(state: &AppState) -> Option<Line<'static>> {
+    let (done, total) = state.todo_list.progress();
+    if total == 0 {
+        return None;
+    }
+    // Rounds down, so the bar is only full once everything is done
+    let filled = done as usize * PROGRESS_BAR_WIDTH / total as usize;
+    let label = if state.todo_list.total_estimate().is_some() {
+        format!(
+            "{}/{}",
+            Estimate::from_minutes(done),
+            Estimate::from_minutes(total)
+        )
+    } else {
+        format!("{done}/{total}")
+    };
+    Some(Line::from(vec![
+        Span::styled("█".repeat(filled), Style::default().fg(Color::Green)),
+        Span::styled(
+            "░".repeat(PROGRESS_BAR_WIDTH - filled),
+            Style::default().fg(Color::DarkGray),
+        ),
+        Span::raw(format!(" {label} ")),
+    ]))
+}
+
+/// Gutter text for an item: its line number and short id, each right-aligned
+/// in its column. `None` when both columns are hidden.
+fn gutter_label(
+    line_number: usize,
+    short_id: Option<&str>,
+    line_width: usize,
+    id_width: usize,
+) -> Option<String> {
+    let mut label = String::new();
+    if line_width > 0 {
+        label.push_str(&format!("{line_number:>w$} ", w = line_width - 1));
+    }
+    if id_width > 0 {
+        label.push_str(&format!("{:>w$} ", short_id.unwrap_or(""), w = id_width - 1));
+    }
+    (!label.is_empty()).then_some(label)
+}
+
+/// Prefix each line with the gutter: the label on the first line, blanks below.
+fn with_gutter<'a>(
+    lines: Vec<Line<'a>>,
+    label: Option<&str>,
+    width: usize,
+    style: Style,
+) -> Vec<Line<'a>> {
+    if width == 0 {
+        return lines;
+    }
+    lines
+        .into_iter()
+        .enumerate()
+        .map(|(i, mut line)| {
+            let label = match label {
+                Some(label) if i == 0 => label.to_string(),
+                _ => " ".repeat(width),
+            };
+            line.spans.insert(0, Span::styled(label, style));
+            line
+        })
+        .collect()
+}
+
 fn build_wrapped_edit_lines(state: &AppState, available_width: usize) -> Vec<Line<'static>> {
     build_wrapped_edit_lines_with_indent(state, available_width, state.pending_indent_level)
 }
@@ -503,19 +882,30 @@ fn build_wrapped_edit_lines_with_indent(
     indent_level: usize,
 ) -> Vec<Line<'static>> {
     let indent = "  ".repeat(indent_level);
-    let fold_icon_space = "  ";
-    let prefix = format!("{indent}{fold_icon_space}[ ] ");
+    let fold_icon_space = state.theme.glyphs.fold_icon(false, false);
+    let prefix = format!("{indent}{fold_icon_space}{} ", state.theme.glyphs.empty);
     let prefix_width = prefix.width();
     let content_max_width = available_width.saturating_sub(prefix_width);
 
-    let edit_wrapped = wrap_text_preserving_trailing(&state.edit_buffer, content_max_width);
+    let text = state.edit_buffer.as_str();
+    let mut edit_wrapped = wrap_ranges(text, content_max_width);
+    // Keep trailing spaces on the last line so the cursor moves as they are typed
+    if let Some(last) = edit_wrapped.last_mut() {
+        last.end = text.len();
+    }
     let edit_row_count = edit_wrapped.len();
-    let cursor_line =
-        find_cursor_line(&state.edit_buffer, state.edit_cursor_pos, content_max_width);
+    // The cursor sits on the first line that reaches it; in the whitespace
+    // where a line breaks it shows at the end of the earlier line
+    let cursor = state.edit_cursor_pos.min(text.len());
+    let cursor_line = edit_wrapped
+        .iter()
+        .position(|range| cursor <= range.end)
+        .unwrap_or(edit_row_count - 1);
 
     let mut lines: Vec<Line<'static>> = Vec::new();
 
-    for (line_idx, line_text) in edit_wrapped.iter().enumerate() {
+    for (line_idx, range) in edit_wrapped.iter().enumerate() {
+        let line_text = &text[range.clone()];
         let line_prefix = if line_idx == 0 {
             prefix.clone()
         } else {
@@ -523,14 +913,9 @@ fn build_wrapped_edit_lines_with_indent(
         };
 
         if cursor_line == line_idx {
-            let cursor_pos_in_line = find_cursor_pos_in_wrapped_line_preserving(
-                &state.edit_buffer,
-                state.edit_cursor_pos,
-                content_max_width,
-                line_idx,
-            );
-            let before_cursor = &line_text[..cursor_pos_in_line.min(line_text.len())];
-            let after_cursor = &line_text[cursor_pos_in_line.min(line_text.len())..];
+            let split = cursor.clamp(range.start, range.end);
+            let before_cursor = &text[range.start..split];
+            let after_cursor = &text[split..range.end];
 
             let mut spans: Vec<Span<'static>> = vec![
                 Span::styled(line_prefix, Style::default()),
@@ -569,7 +954,7 @@ fn build_wrapped_edit_lines_with_indent(
         } else {
             let spans: Vec<Span<'static>> = vec![
                 Span::styled(line_prefix, Style::default()),
-                Span::styled(line_text.clone(), Style::default()),
+                Span::styled(line_text.to_string(), Style::default()),
             ];
             lines.push(Line::from(spans));
         }
@@ -578,51 +963,6 @@ fn build_wrapped_edit_lines_with_indent(
     lines
 }
 
-fn find_cursor_line(text: &str, cursor_pos: usize, max_width: usize) -> usize {
-    if max_width == 0 || text.is_empty() {
-        return 0;
-    }
-
-    let mut current_line = 0;
-    let mut line_start_byte = 0;
-    let mut current_width = 0;
-    let mut last_space_byte = None;
-
-    for (byte_idx, c) in text.char_indices() {
-        if byte_idx >= cursor_pos {
-            return current_line;
-        }
-
-        let char_width = c.to_string().width();
-
-        if c == ' ' {
-            last_space_byte = Some(byte_idx);
-        }
-
-        if current_width + char_width > max_width && current_width > 0 {
-            if let Some(space_byte) = last_space_byte
-                && space_byte > line_start_byte {
-                    if cursor_pos <= space_byte {
-                        return current_line;
-                    }
-                    current_line += 1;
-                    line_start_byte = space_byte + 1;
-                    current_width = text[line_start_byte..=byte_idx].width();
-                    last_space_byte = None;
-                    continue;
-                }
-            current_line += 1;
-            line_start_byte = byte_idx;
-            current_width = char_width;
-            last_space_byte = None;
-        } else {
-            current_width += char_width;
-        }
-    }
-
-    current_line
-}
-
 fn truncate_with_ellipsis(text: &str, max_width: usize) -> String {
     if max_width == 0 {
         return String::new();
@@ -649,98 +989,59 @@ fn truncate_with_ellipsis(text: &str, max_width: usize) -> String {
     result
 }
 
-fn wrap_text(text: &str, max_width: usize) -> Vec<String> {
-    if max_width == 0 {
-        return vec![text.to_string()];
-    }
-
-    let mut lines = Vec::new();
-
-    for paragraph in text.split('\n') {
-        if paragraph.is_empty() {
-            lines.push(String::new());
-            continue;
-        }
-
-        let mut current_line = String::new();
-        let mut current_width = 0;
-
-        for word in paragraph.split_whitespace() {
-            let word_width = word.width();
-
-            if current_line.is_empty() {
-                current_line = word.to_string();
-                current_width = word_width;
-            } else if current_width + 1 + word_width <= max_width {
-                current_line.push(' ');
-                current_line.push_str(word);
-                current_width += 1 + word_width;
-            } else {
-                lines.push(current_line);
-                current_line = word.to_string();
-                current_width = word_width;
-            }
-        }
-
-        if !current_line.is_empty() {
-            lines.push(current_line);
-        }
-    }
-
-    if lines.is_empty() {
-        lines.push(String::new());
-    }
-
-    lines
-}
-
-fn wrap_text_preserving_trailing(text: &str, max_width: usize) -> Vec<String> {
-    if max_width == 0 {
-        return vec![text.to_string()];
-    }
-
-    let trailing_spaces = text.len() - text.trim_end().len();
-    let mut lines = wrap_text(text, max_width);
-
-    if trailing_spaces > 0 && !lines.is_empty() {
-        let last_idx = lines.len() - 1;
-        lines[last_idx].push_str(&" ".repeat(trailing_spaces));
-    }
-
-    lines
-}
 
-fn find_cursor_pos_in_wrapped_line_preserving(
-    text: &str,
-    cursor_pos: usize,
-    max_width: usize,
-    target_line: usize,
-) -> usize {
-    let wrapped = wrap_text_preserving_trailing(text, max_width);
-    if target_line >= wrapped.len() {
-        return 0;
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::keybindings::KeybindingCache;
+    use crate::plugin::{PluginActionRegistry, PluginLoader};
+    use crate::project::Project;
+    use crate::todo::TodoList;
+    use ratatui::{backend::TestBackend, Terminal};
+    use std::path::PathBuf;
+
+    fn make_state() -> AppState {
+        let date = Local::now().date_naive();
+        AppState::new(
+            TodoList::new(date, PathBuf::from("/tmp/test.md")),
+            Theme::default(),
+            KeybindingCache::default(),
+            1000,
+            None,
+            None,
+            Project::default_project(),
+            PluginLoader::new(),
+            vec![],
+            PluginActionRegistry::new(),
+            crate::config::AutoRolloverPref::Ask,
+        )
     }
 
-    let mut byte_offset = 0;
-    for (line_idx, line) in wrapped.iter().enumerate() {
-        if line_idx == target_line {
-            break;
-        }
-        byte_offset += line.trim_end().len();
-        while byte_offset < text.len() {
-            let next_char = text[byte_offset..].chars().next();
-            if next_char == Some(' ') {
-                byte_offset += 1;
-            } else {
-                break;
-            }
-        }
+    fn rendered(state: &mut AppState) -> String {
+        let mut terminal = Terminal::new(TestBackend::new(100, 40)).unwrap();
+        terminal.draw(|f| render(f, state, f.area())).unwrap();
+        terminal.backend().buffer().content().iter().map(|cell| cell.symbol()).collect()
     }
 
-    if cursor_pos < byte_offset {
-        return 0;
+    #[test]
+    fn test_welcome_panel_until_the_first_item() {
+        let mut state = make_state();
+        assert!(!rendered(&mut state).contains("Welcome to to-tui!"));
+
+        state.onboarding = true;
+        let screen = rendered(&mut state);
+        assert!(screen.contains("Welcome to to-tui!"));
+        assert!(screen.contains("add an item, Enter saves it and starts the next"));
+        assert!(screen.contains("the plugins menu"));
+
+        state.todo_list.add_item("Milk".to_string());
+        let screen = rendered(&mut state);
+        assert!(!screen.contains("Welcome to to-tui!"));
+        assert!(screen.contains("Milk"));
+        assert!(!state.onboarding);
+
+        // Emptying the list again doesn't bring it back
+        state.todo_list.items.clear();
+        assert!(!rendered(&mut state).contains("Welcome to to-tui!"));
     }
-
-    let pos_in_line = cursor_pos - byte_offset;
-    pos_in_line.min(wrapped[target_line].len())
 }
@@ -0,0 +1,90 @@
+use crate::app::AppState;
+use super::centered_rect;
+use chrono::{Local, NaiveDate};
+use ratatui::{
+    style::{Color, Modifier, Style},
+    text::{Line, Span},
+    widgets::{Block, Borders, Clear, Paragraph},
+    Frame,
+};
+
+/// Render the deleted items of the project, grouped under the day they were
+/// deleted. Subtasks deleted with their parent count toward its row.
+pub fn render_trash_modal(f: &mut Frame, state: &AppState) {
+    let area = centered_rect(70, 60, f.area());
+    let inner_height = area.height.saturating_sub(2) as usize;
+    let theme = &state.theme;
+    let selected = state.trash_selected;
+    let today = Local::now().date_naive();
+
+    let block = Block::default()
+        .borders(Borders::ALL)
+        .title(" Trash ")
+        .title_bottom(Line::from(" ↑↓ select • r restore • d delete for good • Esc close ").centered())
+        .style(Style::default().bg(theme.background).fg(theme.foreground));
+
+    let dim = Style::default().fg(Color::DarkGray);
+    let heading = Style::default()
+        .fg(theme.foreground)
+        .add_modifier(Modifier::BOLD);
+
+    let mut lines: Vec<Line> = Vec::new();
+    let mut selected_line = 0;
+    let mut group: Option<NaiveDate> = None;
+    for (row, index) in state.trash_rows().into_iter().enumerate() {
+        let deleted = &state.trash[index];
+        let deleted_at = deleted.item.deleted_at.map(|at| at.with_timezone(&Local));
+        let day = deleted_at.map(|at| at.date_naive());
+        if group != day || lines.is_empty() {
+            group = day;
+            let label = match day {
+                Some(day) if day == today => "Deleted today".to_string(),
+                Some(day) if day.succ_opt() == Some(today) => "Deleted yesterday".to_string(),
+                Some(day) => format!("Deleted {}", day.format("%A, %B %d, %Y")),
+                None => "Deleted".to_string(),
+            };
+            if !lines.is_empty() {
+                lines.push(Line::default());
+            }
+            lines.push(Line::from(Span::styled(format!(" {label}"), heading)));
+        }
+
+        let time = deleted_at.map_or_else(String::new, |at| at.format("%H:%M").to_string());
+        let subtasks = state.trash_subtree(index).len() - 1;
+        let mut extra = match subtasks {
+            0 => String::new(),
+            1 => " (+1 subtask)".to_string(),
+            n => format!(" (+{n} subtasks)"),
+        };
+        extra.push_str(&format!("  from {}", deleted.date.format("%Y-%m-%d")));
+        if deleted.archived {
+            extra.push_str(" (archived)");
+        }
+
+        let text = format!("{} {}", deleted.item.state, deleted.item.content);
+        if row == selected {
+            selected_line = lines.len();
+            let style = Style::default()
+                .fg(theme.status_bar_fg)
+                .bg(theme.status_bar_bg)
+                .add_modifier(Modifier::BOLD);
+            lines.push(Line::from(vec![
+                Span::styled(format!("   {time} "), style),
+                Span::styled(text, style),
+                Span::styled(extra, style),
+            ]));
+        } else {
+            lines.push(Line::from(vec![
+                Span::styled(format!("   {time} "), dim),
+                Span::styled(text, Style::default().fg(theme.foreground)),
+                Span::styled(extra, dim),
+            ]));
+        }
+    }
+
+    let offset = selected_line.saturating_sub(inner_height.saturating_sub(1));
+    let lines: Vec<Line> = lines.into_iter().skip(offset).take(inner_height).collect();
+
+    f.render_widget(Clear, area);
+    f.render_widget(Paragraph::new(lines).block(block), area);
+}
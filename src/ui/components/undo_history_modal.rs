@@ -0,0 +1,61 @@
+use crate::app::AppState;
+use super::centered_rect;
+use ratatui::{
+    style::{Color, Modifier, Style},
+    text::{Line, Span},
+    widgets::{Block, Borders, Clear, Paragraph},
+    Frame,
+};
+
+/// Render the undo history, latest change first. Changes from the top down
+/// to the highlighted one are struck through: Enter undoes all of them.
+pub fn render_undo_history_modal(f: &mut Frame, state: &AppState) {
+    let area = centered_rect(60, 60, f.area());
+    let inner_height = area.height.saturating_sub(2) as usize;
+    let theme = &state.theme;
+    let selected = state.undo_history_selected;
+
+    let block = Block::default()
+        .borders(Borders::ALL)
+        .title(" Undo history ")
+        .title_bottom(Line::from(" ↑↓ select • Enter undo to here • Esc close ").centered())
+        .style(Style::default().bg(theme.background).fg(theme.foreground));
+
+    let time_style = Style::default().fg(Color::DarkGray);
+    let offset = selected.saturating_sub(inner_height.saturating_sub(1));
+    let lines: Vec<Line> = state
+        .undo_history()
+        .into_iter()
+        .enumerate()
+        .skip(offset)
+        .take(inner_height)
+        .map(|(i, (at, description))| {
+            let steps = format!("{:>3} ", i + 1);
+            let time = format!("{} ", at.format("%H:%M:%S"));
+            if i == selected {
+                let style = Style::default()
+                    .fg(theme.status_bar_fg)
+                    .bg(theme.status_bar_bg)
+                    .add_modifier(Modifier::BOLD);
+                Line::from(vec![
+                    Span::styled(steps, style),
+                    Span::styled(time, style),
+                    Span::styled(description, style.add_modifier(Modifier::CROSSED_OUT)),
+                ])
+            } else {
+                let mut style = Style::default().fg(theme.foreground);
+                if i < selected {
+                    style = style.add_modifier(Modifier::CROSSED_OUT);
+                }
+                Line::from(vec![
+                    Span::styled(steps, time_style),
+                    Span::styled(time, time_style),
+                    Span::styled(description, style),
+                ])
+            }
+        })
+        .collect();
+
+    f.render_widget(Clear, area);
+    f.render_widget(Paragraph::new(lines).block(block), area);
+}
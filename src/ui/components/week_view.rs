@@ -0,0 +1,128 @@
+use crate::app::AppState;
+use crate::todo::TodoList;
+use chrono::{Duration, NaiveDate};
+use ratatui::{
+    layout::{Constraint, Direction, Layout, Rect},
+    style::{Color, Modifier, Style},
+    text::{Line, Span},
+    widgets::{Block, Borders, Paragraph},
+    Frame,
+};
+
+/// Below this width per day the week is stacked vertically instead
+const MIN_COLUMN_WIDTH: u16 = 16;
+
+/// Render the week as seven columns, or as stacked day sections on narrow terminals.
+pub fn render(f: &mut Frame, state: &AppState, area: Rect) {
+    let Some(week) = &state.week_view else {
+        return;
+    };
+
+    if area.width >= MIN_COLUMN_WIDTH * 7 {
+        let columns = Layout::default()
+            .direction(Direction::Horizontal)
+            .constraints([Constraint::Ratio(1, 7); 7])
+            .split(area);
+        for (i, list) in week.days.iter().enumerate() {
+            let date = week.start + Duration::days(i as i64);
+            let selected = i == week.selected;
+            let border_style = if selected {
+                Style::default().fg(Color::Yellow)
+            } else {
+                Style::default().fg(Color::DarkGray)
+            };
+            let block = Block::default()
+                .borders(Borders::ALL)
+                .border_style(border_style)
+                .title(Span::styled(day_title(date, state.today), title_style(state, selected)))
+                .title_bottom(Line::from(format!(" {} ", progress(list))).right_aligned())
+                .style(Style::default().bg(state.theme.background));
+            let lines = item_lines(state, list, "");
+            f.render_widget(Paragraph::new(lines).block(block), columns[i]);
+        }
+        return;
+    }
+
+    let mut lines = Vec::new();
+    let mut selected_line = 0;
+    for (i, list) in week.days.iter().enumerate() {
+        let date = week.start + Duration::days(i as i64);
+        let selected = i == week.selected;
+        if selected {
+            selected_line = lines.len();
+        }
+        let marker = if selected { "▶" } else { " " };
+        lines.push(Line::from(vec![
+            Span::styled(format!("{marker}{}", day_title(date, state.today)), title_style(state, selected)),
+            Span::styled(progress(list), Style::default().fg(Color::DarkGray)),
+        ]));
+        lines.extend(item_lines(state, list, "   "));
+        lines.push(Line::from(""));
+    }
+
+    let block = Block::default()
+        .borders(Borders::ALL)
+        .title(format!(
+            " Week of {} ",
+            week.start.format("%Y-%m-%d")
+        ))
+        .style(Style::default().bg(state.theme.background));
+    // Keep the selected day's heading at the top once the week overflows
+    let inner_height = area.height.saturating_sub(2) as usize;
+    let scroll = if lines.len() > inner_height {
+        selected_line.min(lines.len() - inner_height)
+    } else {
+        0
+    };
+    f.render_widget(
+        Paragraph::new(lines).block(block).scroll((scroll as u16, 0)),
+        area,
+    );
+}
+
+fn day_title(date: NaiveDate, today: NaiveDate) -> String {
+    let today_marker = if date == today { " (today)" } else { "" };
+    format!(" {}{} ", date.format("%a %d"), today_marker)
+}
+
+fn title_style(state: &AppState, selected: bool) -> Style {
+    if selected {
+        Style::default().fg(Color::Yellow).add_modifier(Modifier::BOLD)
+    } else {
+        Style::default().fg(state.theme.foreground)
+    }
+}
+
+/// "done/total", or empty for a day without items.
+fn progress(list: &TodoList) -> String {
+    if list.items.is_empty() {
+        return String::new();
+    }
+    let done = list.items.iter().filter(|item| item.state.is_complete()).count();
+    format!("{}/{}", done, list.items.len())
+}
+
+fn item_lines<'a>(state: &AppState, list: &'a TodoList, prefix: &'a str) -> Vec<Line<'a>> {
+    let hidden = list.build_hidden_indices();
+    list.items
+        .iter()
+        .enumerate()
+        .filter(|(i, _)| !hidden.contains(i))
+        .map(|(_, item)| {
+            let style = if item.state.is_complete() {
+                Style::default().fg(Color::DarkGray)
+            } else {
+                Style::default().fg(state.theme.foreground)
+            };
+            Line::from(Span::styled(
+                format!(
+                    "{prefix}{}[{}] {}",
+                    "  ".repeat(item.indent_level),
+                    item.state.to_char(),
+                    item.content
+                ),
+                style,
+            ))
+        })
+        .collect()
+}
@@ -0,0 +1,111 @@
+//! Highlight rules: styles for parts of an item's text that match a regex,
+//! configured as `[[highlights]]` entries.
+
+use crate::config::HighlightRuleConfig;
+use anyhow::{Context, Result};
+use ratatui::style::{Color, Modifier, Style};
+use regex::Regex;
+use std::ops::Range;
+use std::str::FromStr;
+
+#[derive(Debug, Clone)]
+pub struct HighlightRule {
+    pub regex: Regex,
+    pub style: Style,
+}
+
+impl HighlightRule {
+    pub fn from_config(config: &HighlightRuleConfig) -> Result<Self> {
+        let regex = Regex::new(&config.pattern)
+            .with_context(|| format!("Invalid highlight pattern '{}'", config.pattern))?;
+        let mut style = Style::default();
+        if let Some(color) = config.color.as_deref() {
+            let color =
+                Color::from_str(color).map_err(|_| anyhow::anyhow!("Invalid highlight color '{color}'"))?;
+            style = style.fg(color);
+        }
+        if let Some(background) = config.background.as_deref() {
+            let color = Color::from_str(background)
+                .map_err(|_| anyhow::anyhow!("Invalid highlight color '{background}'"))?;
+            style = style.bg(color);
+        }
+        let modifiers = [
+            (config.bold, Modifier::BOLD),
+            (config.italic, Modifier::ITALIC),
+            (config.underline, Modifier::UNDERLINED),
+            (config.dim, Modifier::DIM),
+        ];
+        for (enabled, modifier) in modifiers {
+            if enabled {
+                style = style.add_modifier(modifier);
+            }
+        }
+        Ok(Self { regex, style })
+    }
+}
+
+/// Compile the configured rules. Rules that fail to compile are left out and
+/// their errors returned, so one typo doesn't turn off the rest.
+pub fn compile_rules(configs: &[HighlightRuleConfig]) -> (Vec<HighlightRule>, Vec<anyhow::Error>) {
+    let mut rules = Vec::new();
+    let mut errors = Vec::new();
+    for config in configs {
+        match HighlightRule::from_config(config) {
+            Ok(rule) => rules.push(rule),
+            Err(e) => errors.push(e),
+        }
+    }
+    (rules, errors)
+}
+
+/// Byte ranges in `text` matched by the rules, each with the rule's style.
+/// Empty matches are skipped.
+pub fn rule_ranges(rules: &[HighlightRule], text: &str) -> Vec<(Range<usize>, Style)> {
+    rules
+        .iter()
+        .flat_map(|rule| {
+            rule.regex
+                .find_iter(text)
+                .filter(|m| !m.is_empty())
+                .map(|m| (m.range(), rule.style))
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn config(pattern: &str) -> HighlightRuleConfig {
+        HighlightRuleConfig {
+            pattern: pattern.to_string(),
+            color: None,
+            background: None,
+            bold: false,
+            italic: false,
+            underline: false,
+            dim: false,
+        }
+    }
+
+    #[test]
+    fn test_compile_rules_skips_invalid_patterns() {
+        let urgent = HighlightRuleConfig {
+            color: Some("red".to_string()),
+            bold: true,
+            ..config("URGENT")
+        };
+        let (rules, errors) = compile_rules(&[config(r"JIRA-\d+"), config("("), urgent]);
+        assert_eq!(rules.len(), 2);
+        assert_eq!(errors.len(), 1);
+        assert_eq!(rules[1].style, Style::default().fg(Color::Red).add_modifier(Modifier::BOLD));
+    }
+
+    #[test]
+    fn test_rule_ranges() {
+        let (rules, _) = compile_rules(&[config(r"JIRA-\d+"), config("z*")]);
+        let ranges = rule_ranges(&rules, "Fix JIRA-12 and JIRA-7");
+        let ranges: Vec<_> = ranges.into_iter().map(|(range, _)| range).collect();
+        assert_eq!(ranges, vec![4..11, 16..22]);
+    }
+}
@@ -0,0 +1,297 @@
+//! Basic markdown for description boxes: `**bold**`, `*italics*`, `` `code` ``,
+//! `[links](url)` and `-`/`*` bullet lists.
+//!
+//! Text is split into styled runs and word-wrapped like plain descriptions,
+//! so box heights line up with the markers removed.
+
+use ratatui::style::{Color, Modifier, Style};
+use unicode_width::{UnicodeWidthChar, UnicodeWidthStr};
+
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct MdStyle {
+    pub bold: bool,
+    pub italic: bool,
+    pub code: bool,
+    pub link: bool,
+}
+
+impl MdStyle {
+    /// `base` with this run's emphasis applied.
+    pub fn apply(self, base: Style) -> Style {
+        let mut style = base;
+        if self.bold {
+            style = style.add_modifier(Modifier::BOLD);
+        }
+        if self.italic {
+            style = style.add_modifier(Modifier::ITALIC);
+        }
+        if self.code {
+            style = style.fg(Color::Rgb(230, 160, 120));
+        }
+        if self.link {
+            style = style.fg(Color::Rgb(120, 170, 255)).add_modifier(Modifier::UNDERLINED);
+        }
+        style
+    }
+}
+
+/// A piece of text sharing one style.
+pub type Run = (String, MdStyle);
+
+/// Styled runs for one line of markdown, markers removed.
+pub fn parse_line(line: &str) -> Vec<Run> {
+    let indent_len = line.len() - line.trim_start().len();
+    let (indent, rest) = line.split_at(indent_len);
+
+    let mut runs = Vec::new();
+    let mut heading = MdStyle::default();
+    let body = if let Some(item) = rest.strip_prefix("- ").or_else(|| rest.strip_prefix("* ")).or_else(|| rest.strip_prefix("+ ")) {
+        runs.push((format!("{indent}• "), MdStyle::default()));
+        item
+    } else if rest.starts_with('#') && rest.trim_start_matches('#').starts_with(' ') {
+        heading.bold = true;
+        if !indent.is_empty() {
+            runs.push((indent.to_string(), MdStyle::default()));
+        }
+        rest.trim_start_matches('#').trim_start()
+    } else {
+        if !indent.is_empty() {
+            runs.push((indent.to_string(), MdStyle::default()));
+        }
+        rest
+    };
+
+    parse_inline(body, heading, &mut runs);
+    runs
+}
+
+fn parse_inline(text: &str, base: MdStyle, runs: &mut Vec<Run>) {
+    let mut style = base;
+    let mut current = String::new();
+    let mut i = 0;
+
+    let flush = |current: &mut String, style: MdStyle, runs: &mut Vec<Run>| {
+        if !current.is_empty() {
+            push_run(runs, std::mem::take(current), style);
+        }
+    };
+
+    while i < text.len() {
+        let rest = &text[i..];
+        let prev = text[..i].chars().next_back();
+
+        // Inline code is taken literally
+        if let Some(after) = rest.strip_prefix('`')
+            && let Some(end) = after.find('`')
+            && end > 0
+        {
+            flush(&mut current, style, runs);
+            push_run(runs, after[..end].to_string(), MdStyle { code: true, ..style });
+            i += end + 2;
+            continue;
+        }
+
+        // [text](url) shows the text
+        if let Some(after) = rest.strip_prefix('[')
+            && let Some(close) = after.find("](")
+            && let Some(url_end) = after[close + 2..].find(')')
+            && close > 0
+        {
+            flush(&mut current, style, runs);
+            push_run(runs, after[..close].to_string(), MdStyle { link: true, ..style });
+            i += 1 + close + 2 + url_end + 1;
+            continue;
+        }
+
+        if rest.starts_with("**") || rest.starts_with("__") {
+            let marker = &rest[..2];
+            let closes = style.bold && !base.bold;
+            let opens = !style.bold && rest[2..].contains(marker);
+            if opens || closes {
+                flush(&mut current, style, runs);
+                style.bold = !style.bold;
+            } else {
+                current.push_str(marker);
+            }
+            i += 2;
+            continue;
+        }
+
+        let c = rest.chars().next().unwrap_or_default();
+        if c == '*' || c == '_' {
+            let next = rest[1..].chars().next();
+            let inside_word = |ch: Option<char>| ch.is_some_and(char::is_alphanumeric);
+            // `_` inside a word (snake_case) is not emphasis
+            let opens = !style.italic
+                && next.is_some_and(|n| !n.is_whitespace())
+                && (c == '*' || !inside_word(prev))
+                && rest[1..].contains(c);
+            let closes = style.italic
+                && prev.is_some_and(|p| !p.is_whitespace())
+                && (c == '*' || !inside_word(next));
+            if opens || closes {
+                flush(&mut current, style, runs);
+                style.italic = !style.italic;
+                i += 1;
+                continue;
+            }
+        }
+
+        current.push(c);
+        i += c.len_utf8();
+    }
+    flush(&mut current, style, runs);
+}
+
+/// Append `text`, merging with the previous run when the style matches.
+fn push_run(runs: &mut Vec<Run>, text: String, style: MdStyle) {
+    match runs.last_mut() {
+        Some((last, last_style)) if *last_style == style => last.push_str(&text),
+        _ => runs.push((text, style)),
+    }
+}
+
+/// Parse `text` and word-wrap it to `max_width`, one `Vec<Run>` per line.
+/// Wraps exactly like plain descriptions: whitespace collapses to single spaces.
+pub fn wrap(text: &str, max_width: usize) -> Vec<Vec<Run>> {
+    let mut lines = Vec::new();
+
+    for paragraph in text.split('\n') {
+        let runs = parse_line(paragraph);
+
+        // Split into words, each a list of runs
+        let mut words: Vec<Vec<Run>> = Vec::new();
+        let mut in_word = false;
+        for (run_text, style) in runs {
+            for (j, piece) in run_text.split(char::is_whitespace).enumerate() {
+                if j > 0 {
+                    in_word = false;
+                }
+                if piece.is_empty() {
+                    continue;
+                }
+                if !in_word {
+                    words.push(Vec::new());
+                    in_word = true;
+                }
+                if let Some(word) = words.last_mut() {
+                    push_run(word, piece.to_string(), style);
+                }
+            }
+        }
+
+        if words.is_empty() {
+            lines.push(Vec::new());
+            continue;
+        }
+
+        let mut current: Vec<Run> = Vec::new();
+        let mut current_width = 0;
+        for word in words {
+            let word_width = runs_width(&word);
+            if !current.is_empty() {
+                if max_width == 0 || current_width + 1 + word_width <= max_width {
+                    let style = current.last().map(|(_, s)| *s).unwrap_or_default();
+                    let next_style = word[0].1;
+                    // Keep the space inside a styled span so underlines stay continuous
+                    push_run(&mut current, " ".to_string(), if style == next_style { style } else { MdStyle::default() });
+                    current_width += 1 + word_width;
+                    for (t, s) in word {
+                        push_run(&mut current, t, s);
+                    }
+                    continue;
+                }
+                lines.push(std::mem::take(&mut current));
+            }
+            if max_width > 0 && word_width > max_width {
+                let mut pieces = split_wide_word(word, max_width);
+                current = pieces.pop().unwrap_or_default();
+                lines.extend(pieces);
+            } else {
+                current = word;
+            }
+            current_width = runs_width(&current);
+        }
+        lines.push(current);
+    }
+
+    if lines.is_empty() {
+        lines.push(Vec::new());
+    }
+    lines
+}
+
+fn runs_width(runs: &[Run]) -> usize {
+    runs.iter().map(|(t, _)| t.width()).sum()
+}
+
+/// Break a word wider than `max_width` between characters, as plain
+/// descriptions do, keeping each character's style.
+fn split_wide_word(word: Vec<Run>, max_width: usize) -> Vec<Vec<Run>> {
+    let mut pieces = vec![Vec::new()];
+    let mut width = 0;
+    for (text, style) in word {
+        for c in text.chars() {
+            let char_width = c.width().unwrap_or(0);
+            if width + char_width > max_width && width > 0 {
+                pieces.push(Vec::new());
+                width = 0;
+            }
+            width += char_width;
+            if let Some(piece) = pieces.last_mut() {
+                push_run(piece, c.to_string(), style);
+            }
+        }
+    }
+    pieces
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn plain(runs: &[Run]) -> String {
+        runs.iter().map(|(t, _)| t.as_str()).collect()
+    }
+
+    #[test]
+    fn test_parse_inline_styles() {
+        let runs = parse_line("Run **cargo** `test --all` and *then* [ship it](https://x.y)");
+        assert_eq!(plain(&runs), "Run cargo test --all and then ship it");
+        assert!(runs.contains(&("cargo".to_string(), MdStyle { bold: true, ..Default::default() })));
+        assert!(runs.contains(&("test --all".to_string(), MdStyle { code: true, ..Default::default() })));
+        assert!(runs.contains(&("then".to_string(), MdStyle { italic: true, ..Default::default() })));
+        assert!(runs.contains(&("ship it".to_string(), MdStyle { link: true, ..Default::default() })));
+    }
+
+    #[test]
+    fn test_stray_markers_stay_literal() {
+        assert_eq!(plain(&parse_line("snake_case_name")), "snake_case_name");
+        assert_eq!(plain(&parse_line("5 * 3 = 15")), "5 * 3 = 15");
+        assert_eq!(plain(&parse_line("**unclosed")), "**unclosed");
+        assert_eq!(plain(&parse_line("a `lone backtick")), "a `lone backtick");
+    }
+
+    #[test]
+    fn test_bullets_and_headings() {
+        assert_eq!(plain(&parse_line("  - nested item")), "  • nested item");
+        assert_eq!(plain(&parse_line("* item")), "• item");
+        let heading = parse_line("## Notes");
+        assert_eq!(heading, vec![("Notes".to_string(), MdStyle { bold: true, ..Default::default() })]);
+    }
+
+    #[test]
+    fn test_wrap_uses_rendered_width() {
+        let lines = wrap("**aaaa** bbbb\n\ncccc", 9);
+        let text: Vec<String> = lines.iter().map(|l| plain(l)).collect();
+        assert_eq!(text, vec!["aaaa bbbb", "", "cccc"]);
+    }
+
+    #[test]
+    fn test_wrap_splits_wide_words_like_plain_text() {
+        let text = "**日本語の**テキスト";
+        let lines: Vec<String> = wrap(text, 5).iter().map(|l| plain(l)).collect();
+        assert_eq!(lines, crate::utils::unicode::wrap_lines("日本語のテキスト", 5));
+        assert_eq!(wrap(text, 5)[1][0].1, MdStyle { bold: true, ..Default::default() });
+    }
+}
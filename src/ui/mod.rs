@@ -1,14 +1,20 @@
+pub mod accessible;
 pub mod components;
+pub mod highlight;
+pub mod markdown;
 pub mod theme;
 
-use crate::app::{event::handle_key_event, event::handle_mouse_event, AppState};
-use crate::storage::UiCache;
+use crate::app::{
+    event::handle_key_event, event::handle_mouse_event, event::handle_paste_event, AppState,
+};
 use crate::utils::cursor::set_mouse_cursor_default;
-use crate::utils::paths::get_database_path;
+use crate::storage::markdown::dialect_for_path;
+use crate::storage::watcher::watch_database;
+use crate::utils::paths::{get_daily_file_roots, parse_daily_file_path};
 use anyhow::Result;
 use crossterm::{
     event::{
-        DisableMouseCapture, EnableMouseCapture, Event, EventStream, KeyEventKind,
+        DisableBracketedPaste, DisableMouseCapture, EnableBracketedPaste, EnableMouseCapture, Event, EventStream, KeyEventKind,
         KeyboardEnhancementFlags, PopKeyboardEnhancementFlags, PushKeyboardEnhancementFlags,
     },
     execute,
@@ -23,6 +29,7 @@ use ratatui::{
     Terminal,
 };
 use std::io::{self, Write};
+use std::path::PathBuf;
 use std::time::Duration;
 use tokio::sync::mpsc;
 
@@ -37,7 +44,12 @@ impl Drop for TerminalGuard {
             let _ = execute!(stdout, PopKeyboardEnhancementFlags);
         }
         let _ = disable_raw_mode();
-        let _ = execute!(stdout, DisableMouseCapture, LeaveAlternateScreen);
+        let _ = execute!(
+            stdout,
+            DisableBracketedPaste,
+            DisableMouseCapture,
+            LeaveAlternateScreen
+        );
         // Reset mouse cursor to default in case it was changed to pointer
         set_mouse_cursor_default();
         let _ = stdout.flush();
@@ -47,7 +59,7 @@ impl Drop for TerminalGuard {
 pub fn run_tui(mut state: AppState) -> Result<AppState> {
     enable_raw_mode()?;
     let mut stdout = io::stdout();
-    execute!(stdout, EnterAlternateScreen, EnableMouseCapture)?;
+    execute!(stdout, EnterAlternateScreen, EnableMouseCapture, EnableBracketedPaste)?;
 
     let supports_keyboard_enhancement = execute!(
         stdout,
@@ -67,32 +79,41 @@ pub fn run_tui(mut state: AppState) -> Result<AppState> {
 
     // Set up database watcher with tokio channel
     let (db_tx, db_rx) = mpsc::unbounded_channel();
-    let _watcher = setup_database_watcher(db_tx);
+    let _watcher = watch_database(move || {
+        let _ = db_tx.send(());
+    });
+    let (file_tx, file_rx) = mpsc::unbounded_channel();
+    let _file_watcher = setup_daily_file_watcher(file_tx);
 
     // Create single-threaded runtime for the UI event loop
     let rt = tokio::runtime::Builder::new_current_thread()
         .enable_all()
         .build()?;
 
-    let result = rt.block_on(run_app(&mut terminal, &mut state, db_rx, plugin_rx));
+    let result = rt.block_on(run_app(&mut terminal, &mut state, db_rx, file_rx, plugin_rx));
     terminal.show_cursor()?;
 
     result?;
     Ok(state)
 }
 
-fn setup_database_watcher(tx: mpsc::UnboundedSender<()>) -> Option<RecommendedWatcher> {
-    let db_path = match get_database_path() {
-        Ok(path) => path,
-        Err(_) => return None,
-    };
+/// Watch the daily files of every project, sending the path of each one
+/// that changes, so edits made in another editor show up right away.
+fn setup_daily_file_watcher(tx: mpsc::UnboundedSender<PathBuf>) -> Option<RecommendedWatcher> {
+    let roots = get_daily_file_roots().ok()?;
 
     let watcher = RecommendedWatcher::new(
         move |res: Result<notify::Event, notify::Error>| {
-            if let Ok(event) = res
-                && event.kind.is_modify()
-            {
-                let _ = tx.send(());
+            let Ok(event) = res else {
+                return;
+            };
+            if !(event.kind.is_modify() || event.kind.is_create()) {
+                return;
+            }
+            for path in event.paths {
+                if dialect_for_path(&path).is_some() && parse_daily_file_path(&path).is_some() {
+                    let _ = tx.send(path);
+                }
             }
         },
         Config::default(),
@@ -100,11 +121,11 @@ fn setup_database_watcher(tx: mpsc::UnboundedSender<()>) -> Option<RecommendedWa
 
     match watcher {
         Ok(mut w) => {
-            if w.watch(&db_path, RecursiveMode::NonRecursive).is_ok() {
-                Some(w)
-            } else {
-                None
-            }
+            let watched = roots
+                .iter()
+                .filter(|root| w.watch(root, RecursiveMode::Recursive).is_ok())
+                .count();
+            (watched > 0).then_some(w)
         }
         Err(_) => None,
     }
@@ -114,6 +135,7 @@ async fn run_app(
     terminal: &mut Terminal<CrosstermBackend<io::Stdout>>,
     state: &mut AppState,
     mut db_rx: mpsc::UnboundedReceiver<()>,
+    mut file_rx: mpsc::UnboundedReceiver<PathBuf>,
     mut plugin_rx: mpsc::UnboundedReceiver<()>,
 ) -> Result<()> {
     let mut reader = EventStream::new();
@@ -121,7 +143,7 @@ async fn run_app(
 
     loop {
         // State maintenance
-        state.clear_expired_status_message();
+        state.clear_expired_toasts();
         state.check_plugin_result();
         state.check_marketplace_fetch();
         state.check_version_update();
@@ -134,6 +156,12 @@ async fn run_app(
         // Render
         terminal.draw(|f| {
             components::render(f, state);
+            if state.accessible_mode {
+                accessible::ascii_glyphs(f.buffer_mut());
+            }
+            if state.no_color {
+                accessible::strip_colors(f.buffer_mut());
+            }
 
             // Capture screen buffer content for mouse text selection
             {
@@ -181,7 +209,7 @@ async fn run_app(
         tokio::select! {
             biased;  // Check in priority order
 
-            // Terminal events (keyboard, mouse)
+            // Terminal events (keyboard, mouse, paste)
             maybe_event = reader.next() => {
                 if let Some(Ok(event)) = maybe_event {
                     match event {
@@ -196,6 +224,9 @@ async fn run_app(
                         Event::Mouse(mouse) => {
                             handle_mouse_event(mouse, state)?;
                         }
+                        Event::Paste(text) => {
+                            handle_paste_event(text, state)?;
+                        }
                         Event::Resize(_, _) => {
                             state.clear_mouse_selection();
                         }
@@ -216,23 +247,67 @@ async fn run_app(
                 let _ = state.reload_from_database();
             }
 
+            // Daily file changed, maybe by another editor
+            Some(path) = file_rx.recv() => {
+                state.daily_file_changed(&path);
+            }
+
             // Periodic tick for animations (spinner, status messages)
             _ = tick_interval.tick() => {
                 // Don't log ticks - too noisy
                 state.tick_spinner();
+                state.check_save_due();
+                state.check_daily_file();
                 state.check_midnight_rollover();
+                state.check_backup_due();
             }
         }
 
         if state.should_quit {
+            if let Err(e) = state.flush_save() {
+                tracing::error!("Failed to save before quitting: {e}");
+            }
+            state.fire_shutdown_event();
             // Save UI cache before quitting
-            let cache = UiCache {
-                selected_todo_id: state.get_selected_todo_id(),
-            };
-            let _ = cache.save(); // Ignore errors on save
+            let _ = state.save_ui_cache(); // Ignore errors on save
+            if let Err(e) = state.save_undo_history() {
+                tracing::warn!("Failed to save undo history: {e:#}");
+            }
             break;
         }
     }
 
     Ok(())
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::utils::paths::get_daily_file_path_for_project;
+    use chrono::NaiveDate;
+    use serial_test::serial;
+    use tempfile::TempDir;
+
+    #[tokio::test]
+    #[serial]
+    async fn test_daily_file_watcher_reports_daily_files_only() {
+        let temp_dir = TempDir::new().unwrap();
+        std::fs::create_dir_all(temp_dir.path().join(".to-tui")).unwrap();
+        unsafe {
+            std::env::set_var("HOME", temp_dir.path());
+        }
+        let date = NaiveDate::from_ymd_opt(2026, 1, 18).unwrap();
+        let path = get_daily_file_path_for_project("default", date).unwrap();
+        // Directories made after the watch starts are picked up a moment
+        // later, so the day's directory is there first
+        std::fs::create_dir_all(path.parent().unwrap()).unwrap();
+        let (tx, mut rx) = mpsc::unbounded_channel();
+        let _watcher = setup_daily_file_watcher(tx).unwrap();
+
+        std::fs::write(path.with_file_name("notes.txt"), "not a list").unwrap();
+        std::fs::write(&path, "# Todo List - 2026-01-18\n\n- [ ] Milk\n").unwrap();
+
+        let seen = tokio::time::timeout(Duration::from_secs(5), rx.recv()).await.unwrap().unwrap();
+        assert_eq!(seen, path);
+    }
+}
@@ -1,5 +1,19 @@
-use crate::config::Config;
+use crate::config::{Config, GlyphsConfig, PrioritiesConfig};
+use crate::todo::{Priority, TodoState};
+use crate::utils::unicode::display_width;
 use ratatui::style::Color;
+use std::str::FromStr;
+
+/// Names accepted by the `theme` config option, in picker order
+pub const THEME_NAMES: &[&str] = &[
+    "default",
+    "dark",
+    "light",
+    "catppuccin",
+    "gruvbox",
+    "solarized",
+    "high-contrast",
+];
 
 #[derive(Debug, Clone)]
 pub struct Theme {
@@ -11,9 +25,28 @@ pub struct Theme {
     pub cancelled: Color,
     pub status_bar_bg: Color,
     pub status_bar_fg: Color,
-    pub priority_p0: Color,
-    pub priority_p1: Color,
-    pub priority_p2: Color,
+    /// Badge label and color for each priority level, most urgent first
+    pub priorities: Vec<PriorityStyle>,
+    /// Checkbox, fold icon and badge characters
+    pub glyphs: Glyphs,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct PriorityStyle {
+    pub label: String,
+    pub color: Color,
+}
+
+/// Default P0, P1, P2 badges in the given colors.
+fn priority_styles(colors: [Color; 3]) -> Vec<PriorityStyle> {
+    colors
+        .into_iter()
+        .enumerate()
+        .map(|(level, color)| PriorityStyle {
+            label: format!("P{level}"),
+            color,
+        })
+        .collect()
 }
 
 impl Theme {
@@ -27,9 +60,12 @@ impl Theme {
             cancelled: Color::DarkGray,
             status_bar_bg: Color::Rgb(40, 40, 40),
             status_bar_fg: Color::White,
-            priority_p0: Color::Rgb(255, 100, 100), // Red for critical
-            priority_p1: Color::Rgb(255, 200, 100), // Yellow/orange for high
-            priority_p2: Color::Rgb(100, 150, 255), // Blue for medium
+            priorities: priority_styles([
+                Color::Rgb(255, 100, 100),
+                Color::Rgb(255, 200, 100),
+                Color::Rgb(100, 150, 255),
+            ]),
+            glyphs: Glyphs::default(),
         }
     }
 
@@ -43,9 +79,12 @@ impl Theme {
             cancelled: Color::DarkGray,
             status_bar_bg: Color::Rgb(40, 40, 40),
             status_bar_fg: Color::White,
-            priority_p0: Color::Rgb(255, 100, 100),
-            priority_p1: Color::Rgb(255, 200, 100),
-            priority_p2: Color::Rgb(100, 150, 255),
+            priorities: priority_styles([
+                Color::Rgb(255, 100, 100),
+                Color::Rgb(255, 200, 100),
+                Color::Rgb(100, 150, 255),
+            ]),
+            glyphs: Glyphs::default(),
         }
     }
 
@@ -59,19 +98,358 @@ impl Theme {
             cancelled: Color::Gray,
             status_bar_bg: Color::LightBlue,
             status_bar_fg: Color::Black,
-            priority_p0: Color::Rgb(200, 50, 50),   // Darker red for light theme
-            priority_p1: Color::Rgb(180, 130, 0),   // Darker yellow/brown for light theme
-            priority_p2: Color::Rgb(50, 100, 200),  // Darker blue for light theme
+            priorities: priority_styles([
+                Color::Rgb(200, 50, 50),
+                Color::Rgb(180, 130, 0),
+                Color::Rgb(50, 100, 200),
+            ]),
+            glyphs: Glyphs::default(),
+        }
+    }
+
+    /// Catppuccin Mocha
+    pub fn catppuccin() -> Self {
+        Self {
+            background: Color::Rgb(30, 30, 46),
+            foreground: Color::Rgb(205, 214, 244),
+            question: Color::Rgb(249, 226, 175),
+            exclamation: Color::Rgb(243, 139, 168),
+            in_progress: Color::Rgb(137, 220, 235),
+            cancelled: Color::Rgb(108, 112, 134),
+            status_bar_bg: Color::Rgb(49, 50, 68),
+            status_bar_fg: Color::Rgb(205, 214, 244),
+            priorities: priority_styles([
+                Color::Rgb(243, 139, 168),
+                Color::Rgb(250, 179, 135),
+                Color::Rgb(137, 180, 250),
+            ]),
+            glyphs: Glyphs::default(),
+        }
+    }
+
+    /// Gruvbox dark
+    pub fn gruvbox() -> Self {
+        Self {
+            background: Color::Rgb(40, 40, 40),
+            foreground: Color::Rgb(235, 219, 178),
+            question: Color::Rgb(250, 189, 47),
+            exclamation: Color::Rgb(251, 73, 52),
+            in_progress: Color::Rgb(131, 165, 152),
+            cancelled: Color::Rgb(146, 131, 116),
+            status_bar_bg: Color::Rgb(60, 56, 54),
+            status_bar_fg: Color::Rgb(235, 219, 178),
+            priorities: priority_styles([
+                Color::Rgb(251, 73, 52),
+                Color::Rgb(254, 128, 25),
+                Color::Rgb(131, 165, 152),
+            ]),
+            glyphs: Glyphs::default(),
+        }
+    }
+
+    /// Solarized dark
+    pub fn solarized() -> Self {
+        Self {
+            background: Color::Rgb(0, 43, 54),
+            foreground: Color::Rgb(147, 161, 161),
+            question: Color::Rgb(181, 137, 0),
+            exclamation: Color::Rgb(220, 50, 47),
+            in_progress: Color::Rgb(42, 161, 152),
+            cancelled: Color::Rgb(88, 110, 117),
+            status_bar_bg: Color::Rgb(7, 54, 66),
+            status_bar_fg: Color::Rgb(147, 161, 161),
+            priorities: priority_styles([
+                Color::Rgb(220, 50, 47),
+                Color::Rgb(203, 75, 22),
+                Color::Rgb(38, 139, 210),
+            ]),
+            glyphs: Glyphs::default(),
+        }
+    }
+
+    /// Pure black and white with bright accents, for low-vision setups and
+    /// terminals with washed-out palettes
+    pub fn high_contrast() -> Self {
+        Self {
+            background: Color::Black,
+            foreground: Color::White,
+            question: Color::LightYellow,
+            exclamation: Color::LightRed,
+            in_progress: Color::LightCyan,
+            cancelled: Color::Gray,
+            status_bar_bg: Color::White,
+            status_bar_fg: Color::Black,
+            priorities: priority_styles([Color::LightRed, Color::LightYellow, Color::LightCyan]),
+            glyphs: Glyphs::default(),
+        }
+    }
+
+    /// The built-in theme called `name`, see [`THEME_NAMES`].
+    pub fn by_name(name: &str) -> Option<Self> {
+        match name {
+            "default" => Some(Self::default_theme()),
+            "dark" => Some(Self::dark()),
+            "light" => Some(Self::light()),
+            "catppuccin" => Some(Self::catppuccin()),
+            "gruvbox" => Some(Self::gruvbox()),
+            "solarized" => Some(Self::solarized()),
+            "high-contrast" => Some(Self::high_contrast()),
+            _ => None,
         }
     }
 
     pub fn from_config(config: &Config) -> Self {
-        match config.theme.as_str() {
-            "dark" => Self::dark(),
-            "light" => Self::light(),
-            _ => Self::default_theme(),
+        Self::named(&config.theme, &config.priorities, &config.glyphs)
+    }
+
+    /// The theme called `name` (default when unknown) with the configured
+    /// priority levels and glyphs.
+    pub fn named(name: &str, priorities: &PrioritiesConfig, glyphs: &GlyphsConfig) -> Self {
+        let mut theme = Self::by_name(name).unwrap_or_default();
+        theme.apply_priority_levels(priorities);
+        theme.glyphs = Glyphs::from_config(glyphs);
+        theme
+    }
+
+    /// Replace the default P0-P2 badges with the configured levels. Levels
+    /// without a (valid) color reuse the theme's color for that level, or the
+    /// least urgent theme color for levels the theme doesn't cover.
+    fn apply_priority_levels(&mut self, config: &PrioritiesConfig) {
+        let levels = config.levels.iter().take(config.level_count() as usize);
+        let priorities = levels
+            .enumerate()
+            .map(|(level, level_config)| {
+                let fallback = self
+                    .priorities
+                    .get(level)
+                    .or(self.priorities.last())
+                    .map_or(self.foreground, |style| style.color);
+                let color = level_config
+                    .color
+                    .as_deref()
+                    .and_then(|c| Color::from_str(c).ok())
+                    .unwrap_or(fallback);
+                PriorityStyle {
+                    label: level_config.label.clone(),
+                    color,
+                }
+            })
+            .collect::<Vec<_>>();
+
+        if !priorities.is_empty() {
+            self.priorities = priorities;
         }
     }
+
+    /// Number of priority levels `p` cycles through.
+    pub fn priority_levels(&self) -> u8 {
+        u8::try_from(self.priorities.len().max(1)).unwrap_or(u8::MAX)
+    }
+
+    /// Badge label and color for a priority. Levels beyond the configured
+    /// ones (e.g. after removing a level) keep their P-number label.
+    pub fn priority_style(&self, priority: Priority) -> PriorityStyle {
+        match self.priorities.get(priority.level() as usize) {
+            Some(style) => style.clone(),
+            None => PriorityStyle {
+                label: priority.to_string(),
+                color: self.priorities.last().map_or(self.foreground, |s| s.color),
+            },
+        }
+    }
+}
+
+/// How far into a copied line the state glyph is looked for: past the
+/// indent, fold icon and priority badge, but not into the content
+const CANONICALIZE_PREFIX_BYTES: usize = 40;
+
+/// Characters drawn for item states, fold icons and priority badges. Display
+/// only: what is saved to markdown never depends on them.
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct Glyphs {
+    pub empty: String,
+    pub checked: String,
+    /// `{}` is replaced by an animated spinner
+    pub in_progress: String,
+    pub question: String,
+    pub exclamation: String,
+    pub cancelled: String,
+    /// Unchecked parent with some subtasks done
+    pub partial: String,
+    pub waiting: String,
+    pub expanded: String,
+    pub collapsed: String,
+    /// `{}` is replaced by the priority label
+    pub priority: String,
+}
+
+impl Glyphs {
+    /// The markdown-style checkboxes, `[ ]`, `[x]`, ...
+    pub fn brackets() -> Self {
+        Self {
+            empty: "[ ]".to_string(),
+            checked: "[x]".to_string(),
+            in_progress: "[{}]".to_string(),
+            question: "[?]".to_string(),
+            exclamation: "[!]".to_string(),
+            cancelled: "[-]".to_string(),
+            partial: "[-]".to_string(),
+            waiting: "[@]".to_string(),
+            expanded: "▼".to_string(),
+            collapsed: "▶".to_string(),
+            priority: "[{}]".to_string(),
+        }
+    }
+
+    /// Nothing outside ASCII, and no spinner
+    pub fn ascii() -> Self {
+        Self {
+            in_progress: "[*]".to_string(),
+            expanded: "v".to_string(),
+            collapsed: ">".to_string(),
+            ..Self::brackets()
+        }
+    }
+
+    /// Font Awesome icons from a Nerd Font
+    pub fn nerd_font() -> Self {
+        Self {
+            empty: "\u{f096}".to_string(),       // square-o
+            checked: "\u{f046}".to_string(),     // check-square-o
+            in_progress: "\u{f110}".to_string(), // spinner
+            question: "\u{f059}".to_string(),    // question-circle
+            exclamation: "\u{f06a}".to_string(), // exclamation-circle
+            cancelled: "\u{f05e}".to_string(),   // ban
+            partial: "\u{f147}".to_string(),     // minus-square-o
+            waiting: "\u{f252}".to_string(),     // hourglass-half
+            expanded: "\u{f078}".to_string(),    // chevron-down
+            collapsed: "\u{f054}".to_string(),   // chevron-right
+            priority: "\u{f024} {}".to_string(), // flag
+        }
+    }
+
+    /// The built-in set called `name`: `brackets`, `ascii` or `nerd-font`.
+    pub fn by_name(name: &str) -> Option<Self> {
+        match name {
+            "brackets" => Some(Self::brackets()),
+            "ascii" => Some(Self::ascii()),
+            "nerd-font" => Some(Self::nerd_font()),
+            _ => None,
+        }
+    }
+
+    /// The configured set (brackets when unset or unknown) with any single
+    /// glyphs overridden.
+    pub fn from_config(config: &GlyphsConfig) -> Self {
+        let mut glyphs = config
+            .set
+            .as_deref()
+            .and_then(Self::by_name)
+            .unwrap_or_default();
+        let overrides = [
+            (&mut glyphs.empty, &config.empty),
+            (&mut glyphs.checked, &config.checked),
+            (&mut glyphs.in_progress, &config.in_progress),
+            (&mut glyphs.question, &config.question),
+            (&mut glyphs.exclamation, &config.exclamation),
+            (&mut glyphs.cancelled, &config.cancelled),
+            (&mut glyphs.partial, &config.partial),
+            (&mut glyphs.waiting, &config.waiting),
+            (&mut glyphs.expanded, &config.expanded),
+            (&mut glyphs.collapsed, &config.collapsed),
+            (&mut glyphs.priority, &config.priority),
+        ];
+        for (glyph, configured) in overrides {
+            if let Some(configured) = configured {
+                *glyph = configured.clone();
+            }
+        }
+        glyphs
+    }
+
+    /// Glyph for an item in `state`; `spinner` fills in `in_progress`.
+    pub fn checkbox(&self, state: TodoState, spinner: char) -> String {
+        match state {
+            TodoState::Empty => self.empty.clone(),
+            TodoState::Checked => self.checked.clone(),
+            TodoState::InProgress => self.in_progress.replace("{}", &spinner.to_string()),
+            TodoState::Question => self.question.clone(),
+            TodoState::Exclamation => self.exclamation.clone(),
+            TodoState::Cancelled => self.cancelled.clone(),
+        }
+    }
+
+    /// Fold icon plus trailing space, padded so both icons take the same
+    /// width. Blank for items that cannot fold.
+    pub fn fold_icon(&self, collapsible: bool, collapsed: bool) -> String {
+        let width = self.fold_width();
+        let icon = match (collapsible, collapsed) {
+            (false, _) => "",
+            (true, true) => self.collapsed.as_str(),
+            (true, false) => self.expanded.as_str(),
+        };
+        format!("{icon}{}", " ".repeat(width - display_width(icon)))
+    }
+
+    /// Columns taken by the fold icon and its trailing space.
+    pub fn fold_width(&self) -> usize {
+        display_width(&self.expanded).max(display_width(&self.collapsed)) + 1
+    }
+
+    pub fn priority_badge(&self, label: &str) -> String {
+        self.priority.replace("{}", label)
+    }
+
+    /// Turn the fold icon and state glyph of copied item lines back into
+    /// the markdown forms, so a copied item pastes as `[x] Task`. Only the
+    /// start of each line is looked at, where the renderer puts them.
+    pub fn canonicalize(&self, text: &str) -> String {
+        let brackets = Self::brackets();
+        let folds = [
+            (&self.expanded, &brackets.expanded),
+            (&self.collapsed, &brackets.collapsed),
+        ];
+        let states = [
+            (&self.checked, "[x]"),
+            (&self.question, "[?]"),
+            (&self.exclamation, "[!]"),
+            (&self.cancelled, "[-]"),
+            (&self.partial, "[-]"),
+            (&self.waiting, "[@]"),
+            (&self.empty, "[ ]"),
+        ];
+        text.lines()
+            .map(|line| {
+                let mut line = line.to_string();
+                let indent = line.len() - line.trim_start().len();
+                for (glyph, canonical) in folds {
+                    if glyph != canonical && line[indent..].starts_with(&format!("{glyph} ")) {
+                        line.replace_range(indent..indent + glyph.len(), canonical);
+                        break;
+                    }
+                }
+                // The checkbox is the first state glyph on the line
+                let first = states
+                    .iter()
+                    .filter(|(glyph, _)| !glyph.trim().is_empty())
+                    .filter_map(|(glyph, canonical)| Some((line.find(glyph.as_str())?, *glyph, *canonical)))
+                    .min_by_key(|(pos, _, _)| *pos);
+                if let Some((pos, glyph, canonical)) = first
+                    && pos < CANONICALIZE_PREFIX_BYTES
+                {
+                    line.replace_range(pos..pos + glyph.len(), canonical);
+                }
+                line
+            })
+            .collect::<Vec<_>>()
+            .join("\n")
+    }
+}
+
+impl Default for Glyphs {
+    fn default() -> Self {
+        Self::brackets()
+    }
 }
 
 impl Default for Theme {
@@ -79,3 +457,92 @@ impl Default for Theme {
         Self::default_theme()
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::config::PriorityLevelConfig;
+
+    fn level(label: &str, color: Option<&str>) -> PriorityLevelConfig {
+        PriorityLevelConfig {
+            label: label.to_string(),
+            color: color.map(str::to_string),
+        }
+    }
+
+    #[test]
+    fn test_configured_priority_levels() {
+        let mut config = Config::default();
+        config.priorities.levels = vec![
+            level("Now", Some("#ff0000")),
+            level("Soon", None),
+            level("Later", Some("not-a-color")),
+            level("Someday", Some("gray")),
+        ];
+        let theme = Theme::from_config(&config);
+        let defaults = Theme::default_theme();
+
+        assert_eq!(theme.priority_levels(), 4);
+        assert_eq!(theme.priorities[0].color, Color::Rgb(255, 0, 0));
+        assert_eq!(theme.priorities[1].color, defaults.priorities[1].color);
+        assert_eq!(theme.priorities[2].color, defaults.priorities[2].color);
+        assert_eq!(theme.priorities[3].color, Color::Gray);
+        assert_eq!(theme.priority_style(Priority::P1).label, "Soon");
+    }
+
+    #[test]
+    fn test_every_theme_name_resolves() {
+        for name in THEME_NAMES {
+            assert!(Theme::by_name(name).is_some(), "{name}");
+        }
+        assert!(Theme::by_name("neon").is_none());
+        assert_eq!(
+            Theme::named("neon", &PrioritiesConfig::default(), &GlyphsConfig::default()).background,
+            Theme::default_theme().background
+        );
+    }
+
+    #[test]
+    fn test_unconfigured_level_keeps_p_label() {
+        let theme = Theme::default_theme();
+        let style = theme.priority_style(Priority::new(5).unwrap());
+        assert_eq!(style.label, "P5");
+        assert_eq!(style.color, theme.priorities[2].color);
+    }
+
+    #[test]
+    fn test_glyph_set_with_overrides() {
+        let config = GlyphsConfig {
+            set: Some("nerd-font".to_string()),
+            checked: Some("✔".to_string()),
+            ..Default::default()
+        };
+        let glyphs = Glyphs::from_config(&config);
+        assert_eq!(glyphs.checkbox(TodoState::Checked, '⠋'), "✔");
+        assert_eq!(glyphs.checkbox(TodoState::Question, '⠋'), "\u{f059}");
+        assert_eq!(glyphs.priority_badge("P1"), "\u{f024} P1");
+
+        let brackets = Glyphs::default();
+        assert_eq!(brackets.checkbox(TodoState::InProgress, '⠋'), "[⠋]");
+        assert_eq!(brackets.fold_icon(true, true), "▶ ");
+        assert_eq!(brackets.fold_icon(false, false), "  ");
+        assert_eq!(Glyphs::from_config(&GlyphsConfig {
+            set: Some("unknown".to_string()),
+            ..Default::default()
+        }), brackets);
+    }
+
+    #[test]
+    fn test_copied_items_get_markdown_checkboxes() {
+        let glyphs = Glyphs::from_config(&GlyphsConfig {
+            set: Some("nerd-font".to_string()),
+            checked: Some("✔".to_string()),
+            ..Default::default()
+        });
+        let copied = "\u{f054} \u{f024} P0 ✔ Ship it\n    \u{f096} Fix ✔ marks";
+        assert_eq!(
+            glyphs.canonicalize(copied),
+            "▶ \u{f024} P0 [x] Ship it\n    [ ] Fix ✔ marks"
+        );
+    }
+}
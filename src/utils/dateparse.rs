@@ -0,0 +1,246 @@
+use anyhow::{anyhow, Result};
+use chrono::{Datelike, Duration, Local, NaiveDate, Weekday};
+
+/// Parse a user-supplied date relative to today's local date.
+///
+/// See [`parse_date_relative_to`] for the accepted formats.
+pub fn parse_date(input: &str) -> Result<NaiveDate> {
+    parse_date_relative_to(input, Local::now().date_naive())
+}
+
+/// Parse a user-supplied date, resolving relative expressions against `today`.
+///
+/// Accepted formats:
+/// - `YYYY-MM-DD`
+/// - `today`, `tomorrow`, `yesterday`
+/// - weekday names (`friday`, `fri`): the next occurrence after today
+/// - `next friday` / `last friday`
+/// - `in 3 days`, `in 2 weeks`, `3 days ago`
+/// - shorthand offsets: `+3d`, `-1w`, `+2m` (months)
+pub fn parse_date_relative_to(input: &str, today: NaiveDate) -> Result<NaiveDate> {
+    let normalized = input.trim().to_lowercase();
+    let words: Vec<&str> = normalized.split_whitespace().collect();
+
+    if words.is_empty() {
+        return Err(anyhow!("Empty date"));
+    }
+
+    if let Ok(date) = NaiveDate::parse_from_str(&normalized, "%Y-%m-%d") {
+        return Ok(date);
+    }
+
+    let parsed = match words.as_slice() {
+        ["today"] | ["now"] => Some(today),
+        ["tomorrow"] | ["tmr"] => Some(today + Duration::days(1)),
+        ["yesterday"] => Some(today - Duration::days(1)),
+        [day] => parse_weekday(day)
+            .map(|wd| next_weekday(today, wd))
+            .or_else(|| parse_shorthand_offset(day, today)),
+        ["next", "week"] => Some(today + Duration::weeks(1)),
+        ["next", "month"] => shift_months(today, 1),
+        ["next", day] => parse_weekday(day).map(|wd| next_weekday(today, wd)),
+        ["last", day] => parse_weekday(day).map(|wd| prev_weekday(today, wd)),
+        ["in", amount, unit] => {
+            parse_amount(amount).and_then(|n| offset_by_unit(today, n, unit))
+        }
+        [amount, unit, "ago"] => {
+            parse_amount(amount).and_then(|n| offset_by_unit(today, n.checked_neg()?, unit))
+        }
+        _ => None,
+    };
+
+    parsed.ok_or_else(|| {
+        anyhow!(
+            "Unrecognised date '{}'. Use YYYY-MM-DD or phrases like 'tomorrow', 'next friday', 'in 3 days'",
+            input.trim()
+        )
+    })
+}
+
+/// Strip an `@due(...)` tag from free text, resolving its argument with [`parse_date`].
+///
+/// Returns the text without the tag and the parsed date. A tag with an
+/// unrecognised date is left in place so the user can see and fix it.
+pub fn extract_due_tag(text: &str) -> (String, Option<NaiveDate>) {
+    extract_due_tag_relative_to(text, Local::now().date_naive())
+}
+
+fn extract_due_tag_relative_to(text: &str, today: NaiveDate) -> (String, Option<NaiveDate>) {
+    let Some(start) = text.find("@due(") else {
+        return (text.to_string(), None);
+    };
+    let Some(len) = text[start..].find(')') else {
+        return (text.to_string(), None);
+    };
+    let arg = &text[start + 5..start + len];
+    let Ok(date) = parse_date_relative_to(arg, today) else {
+        return (text.to_string(), None);
+    };
+
+    let before = text[..start].trim_end();
+    let after = text[start + len + 1..].trim_start();
+    let cleaned = match (before.is_empty(), after.is_empty()) {
+        (false, false) => format!("{before} {after}"),
+        _ => format!("{before}{after}"),
+    };
+    (cleaned, Some(date))
+}
+
+fn parse_weekday(s: &str) -> Option<Weekday> {
+    match s {
+        "monday" | "mon" => Some(Weekday::Mon),
+        "tuesday" | "tue" | "tues" => Some(Weekday::Tue),
+        "wednesday" | "wed" => Some(Weekday::Wed),
+        "thursday" | "thu" | "thurs" => Some(Weekday::Thu),
+        "friday" | "fri" => Some(Weekday::Fri),
+        "saturday" | "sat" => Some(Weekday::Sat),
+        "sunday" | "sun" => Some(Weekday::Sun),
+        _ => None,
+    }
+}
+
+/// The next date strictly after `today` falling on `weekday`.
+fn next_weekday(today: NaiveDate, weekday: Weekday) -> NaiveDate {
+    let current = today.weekday().num_days_from_monday() as i64;
+    let target = weekday.num_days_from_monday() as i64;
+    let mut diff = (target - current).rem_euclid(7);
+    if diff == 0 {
+        diff = 7;
+    }
+    today + Duration::days(diff)
+}
+
+/// The most recent date strictly before `today` falling on `weekday`.
+fn prev_weekday(today: NaiveDate, weekday: Weekday) -> NaiveDate {
+    let current = today.weekday().num_days_from_monday() as i64;
+    let target = weekday.num_days_from_monday() as i64;
+    let mut diff = (current - target).rem_euclid(7);
+    if diff == 0 {
+        diff = 7;
+    }
+    today - Duration::days(diff)
+}
+
+fn parse_amount(s: &str) -> Option<i64> {
+    match s {
+        "a" | "an" | "one" => Some(1),
+        "two" => Some(2),
+        "three" => Some(3),
+        _ => s.parse::<i64>().ok(),
+    }
+}
+
+fn offset_by_unit(today: NaiveDate, amount: i64, unit: &str) -> Option<NaiveDate> {
+    match unit.trim_end_matches('s') {
+        "day" | "d" => today.checked_add_signed(Duration::try_days(amount)?),
+        "week" | "w" | "wk" => today.checked_add_signed(Duration::try_weeks(amount)?),
+        "month" | "m" | "mo" => shift_months(today, amount),
+        _ => None,
+    }
+}
+
+/// Parse shorthand offsets like `+3d`, `-1w`, `+2m`.
+fn parse_shorthand_offset(s: &str, today: NaiveDate) -> Option<NaiveDate> {
+    let (sign, rest) = match s.chars().next()? {
+        '+' => (1, &s[1..]),
+        '-' => (-1, &s[1..]),
+        _ => return None,
+    };
+    let unit_start = rest.find(|c: char| !c.is_ascii_digit())?;
+    let amount: i64 = rest[..unit_start].parse().ok()?;
+    offset_by_unit(today, sign * amount, &rest[unit_start..])
+}
+
+/// Shift by whole months, clamping to the last day of shorter months.
+fn shift_months(date: NaiveDate, months: i64) -> Option<NaiveDate> {
+    let shift = chrono::Months::new(u32::try_from(months.unsigned_abs()).ok()?);
+    if months >= 0 {
+        date.checked_add_months(shift)
+    } else {
+        date.checked_sub_months(shift)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn today() -> NaiveDate {
+        // Wednesday
+        NaiveDate::from_ymd_opt(2026, 1, 14).unwrap()
+    }
+
+    fn d(y: i32, m: u32, day: u32) -> NaiveDate {
+        NaiveDate::from_ymd_opt(y, m, day).unwrap()
+    }
+
+    #[test]
+    fn test_iso_date() {
+        assert_eq!(parse_date_relative_to("2026-03-01", today()).unwrap(), d(2026, 3, 1));
+    }
+
+    #[test]
+    fn test_keywords() {
+        assert_eq!(parse_date_relative_to("today", today()).unwrap(), today());
+        assert_eq!(parse_date_relative_to("Tomorrow", today()).unwrap(), d(2026, 1, 15));
+        assert_eq!(parse_date_relative_to(" yesterday ", today()).unwrap(), d(2026, 1, 13));
+    }
+
+    #[test]
+    fn test_weekdays() {
+        assert_eq!(parse_date_relative_to("friday", today()).unwrap(), d(2026, 1, 16));
+        assert_eq!(parse_date_relative_to("next fri", today()).unwrap(), d(2026, 1, 16));
+        // Same weekday as today means a week from now
+        assert_eq!(parse_date_relative_to("wednesday", today()).unwrap(), d(2026, 1, 21));
+        assert_eq!(parse_date_relative_to("last monday", today()).unwrap(), d(2026, 1, 12));
+    }
+
+    #[test]
+    fn test_relative_offsets() {
+        assert_eq!(parse_date_relative_to("in 3 days", today()).unwrap(), d(2026, 1, 17));
+        assert_eq!(parse_date_relative_to("in a week", today()).unwrap(), d(2026, 1, 21));
+        assert_eq!(parse_date_relative_to("2 days ago", today()).unwrap(), d(2026, 1, 12));
+        assert_eq!(parse_date_relative_to("in 1 month", today()).unwrap(), d(2026, 2, 14));
+        assert_eq!(parse_date_relative_to("next week", today()).unwrap(), d(2026, 1, 21));
+    }
+
+    #[test]
+    fn test_shorthand_offsets() {
+        assert_eq!(parse_date_relative_to("+3d", today()).unwrap(), d(2026, 1, 17));
+        assert_eq!(parse_date_relative_to("-1w", today()).unwrap(), d(2026, 1, 7));
+        assert_eq!(parse_date_relative_to("+2m", today()).unwrap(), d(2026, 3, 14));
+    }
+
+    #[test]
+    fn test_extract_due_tag() {
+        let (text, due) = extract_due_tag_relative_to("Call bob @due(next friday) about it", today());
+        assert_eq!(text, "Call bob about it");
+        assert_eq!(due, Some(d(2026, 1, 16)));
+
+        let (text, due) = extract_due_tag_relative_to("Ship it @due(2026-02-01)", today());
+        assert_eq!(text, "Ship it");
+        assert_eq!(due, Some(d(2026, 2, 1)));
+
+        let (text, due) = extract_due_tag_relative_to("Keep @due(someday)", today());
+        assert_eq!(text, "Keep @due(someday)");
+        assert_eq!(due, None);
+    }
+
+    #[test]
+    fn test_invalid_input() {
+        assert!(parse_date_relative_to("", today()).is_err());
+        assert!(parse_date_relative_to("someday", today()).is_err());
+        assert!(parse_date_relative_to("2026-13-01", today()).is_err());
+        assert!(parse_date_relative_to("in many days", today()).is_err());
+    }
+
+    #[test]
+    fn test_out_of_range_offsets_are_errors() {
+        assert!(parse_date_relative_to("in 99999999999 days", today()).is_err());
+        assert!(parse_date_relative_to("99999999999 weeks ago", today()).is_err());
+        assert!(parse_date_relative_to("in -9223372036854775808 days", today()).is_err());
+        assert!(parse_date_relative_to("-9223372036854775808 days ago", today()).is_err());
+        assert!(parse_date_relative_to("+99999999999d", today()).is_err());
+        assert!(parse_date_relative_to("+9999999999999m", today()).is_err());
+    }
+}
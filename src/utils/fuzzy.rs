@@ -0,0 +1,104 @@
+//! Fuzzy matching for completion lists.
+//!
+//! The query's characters must appear in the candidate in order, ignoring
+//! case. Prefixes, matches at word starts and runs of consecutive characters
+//! rank higher.
+
+/// Score of `query` against `candidate`, or None when it does not match.
+/// Higher is better; an empty query matches everything.
+pub fn fuzzy_score(candidate: &str, query: &str) -> Option<i64> {
+    let mut score = 0;
+    let mut query_chars = query.chars().flat_map(char::to_lowercase).peekable();
+    let mut previous: Option<char> = None;
+    let mut previous_matched = false;
+    let mut gap = 0;
+
+    for c in candidate.chars() {
+        let Some(&wanted) = query_chars.peek() else {
+            break;
+        };
+        let word_start = previous.is_none_or(|p| !p.is_alphanumeric());
+        if c.to_lowercase().eq(std::iter::once(wanted)) {
+            query_chars.next();
+            score += 1;
+            if previous_matched {
+                score += 5;
+            }
+            if word_start {
+                score += 10;
+            }
+            score -= gap.min(5);
+            gap = 0;
+            previous_matched = true;
+        } else {
+            previous_matched = false;
+            gap += 1;
+        }
+        previous = Some(c);
+    }
+
+    if query_chars.peek().is_some() {
+        return None;
+    }
+    // Typing the start of a candidate outright beats matching several word starts
+    if candidate.to_lowercase().starts_with(&query.to_lowercase()) {
+        score += 20;
+    }
+    // Prefer shorter candidates among equal matches
+    Some(score * 100 - candidate.chars().count() as i64)
+}
+
+/// `items` that match `query`, best first. Ties keep their original order.
+pub fn fuzzy_filter<T>(items: impl IntoIterator<Item = T>, query: &str, key: impl Fn(&T) -> &str) -> Vec<T> {
+    let mut scored: Vec<(i64, T)> = items
+        .into_iter()
+        .filter_map(|item| fuzzy_score(key(&item), query).map(|score| (score, item)))
+        .collect();
+    scored.sort_by(|(a, _), (b, _)| b.cmp(a));
+    scored.into_iter().map(|(_, item)| item).collect()
+}
+
+/// Character positions in `candidate` that `query` matches, for
+/// highlighting. Matches the same characters [`fuzzy_score`] does.
+pub fn fuzzy_positions(candidate: &str, query: &str) -> Option<Vec<usize>> {
+    let mut positions = Vec::new();
+    let mut query_chars = query.chars().flat_map(char::to_lowercase).peekable();
+    for (i, c) in candidate.chars().enumerate() {
+        let Some(&wanted) = query_chars.peek() else {
+            break;
+        };
+        if c.to_lowercase().eq(std::iter::once(wanted)) {
+            query_chars.next();
+            positions.push(i);
+        }
+    }
+    query_chars.peek().is_none().then_some(positions)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_subsequence_matches_ignoring_case() {
+        assert!(fuzzy_score("sort-by-priority", "SBP").is_some());
+        assert!(fuzzy_score("sort-by-priority", "pbs").is_none());
+        assert!(fuzzy_score("anything", "").is_some());
+        assert_eq!(fuzzy_positions("Work-Stuff", "ws"), Some(vec![0, 5]));
+        assert_eq!(fuzzy_positions("work", "wx"), None);
+    }
+
+    #[test]
+    fn test_word_starts_and_runs_rank_higher() {
+        let ranked = fuzzy_filter(
+            ["open-link", "toggle-pin", "move-to-project", "open-plugin-menu"],
+            "op",
+            |s| s,
+        );
+        assert_eq!(ranked[..2], ["open-link", "open-plugin-menu"]);
+        assert_eq!(ranked.len(), 4);
+
+        let ranked = fuzzy_filter(["go-to-today", "goto"], "goto", |s| s);
+        assert_eq!(ranked[0], "goto");
+    }
+}
@@ -1,5 +1,8 @@
 pub mod cursor;
+pub mod dateparse;
+pub mod fuzzy;
 pub mod paths;
+pub mod search;
 pub mod unicode;
 pub mod upgrade;
 pub mod version_check;
@@ -1,11 +1,116 @@
-use anyhow::{anyhow, Result};
+use anyhow::{anyhow, bail, Result};
 use chrono::{Local, NaiveDate};
 use std::fs;
-use std::path::PathBuf;
+use std::path::{Path, PathBuf};
+use std::sync::RwLock;
+
+/// Overrides the data directory (database, dailies, backups).
+pub const DATA_DIR_ENV: &str = "TOTUI_DATA_DIR";
+/// Overrides the directory holding config.toml.
+pub const CONFIG_DIR_ENV: &str = "TOTUI_CONFIG_DIR";
+/// Overrides the logs directory.
+pub const LOGS_DIR_ENV: &str = "TOTUI_LOGS_DIR";
+
+/// Storage locations set in config.toml. Environment variables win over these.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct PathOverrides {
+    pub data_dir: Option<PathBuf>,
+    pub logs_dir: Option<PathBuf>,
+    /// Where daily files go; see [`DEFAULT_DAILY_FILE_TEMPLATE`]
+    pub daily_file_template: Option<String>,
+}
+
+static OVERRIDES: RwLock<PathOverrides> = RwLock::new(PathOverrides {
+    data_dir: None,
+    logs_dir: None,
+    daily_file_template: None,
+});
+
+/// Apply the storage locations from the config. Called once at startup,
+/// after the config is loaded and before anything touches the data directory.
+pub fn set_overrides(overrides: PathOverrides) {
+    *OVERRIDES.write().unwrap_or_else(|e| e.into_inner()) = overrides;
+}
+
+fn overrides() -> PathOverrides {
+    OVERRIDES.read().unwrap_or_else(|e| e.into_inner()).clone()
+}
+
+fn home_dir() -> Result<PathBuf> {
+    dirs::home_dir().ok_or_else(|| anyhow!("Could not find home directory"))
+}
+
+/// Expand a leading `~` so paths in config.toml and env vars can be written
+/// relative to the home directory.
+pub fn expand_home(path: &Path) -> Result<PathBuf> {
+    match path.strip_prefix("~") {
+        Ok(rest) => Ok(home_dir()?.join(rest)),
+        Err(_) => Ok(path.to_path_buf()),
+    }
+}
 
+/// A directory from the environment, if set to something non-empty.
+///
+/// Tests isolate themselves by pointing HOME at a temp dir, so they ignore
+/// the environment: a developer's own TOTUI_* or XDG_* variables must not
+/// send them to real data.
+fn env_dir(name: &str) -> Option<PathBuf> {
+    if cfg!(test) {
+        return None;
+    }
+    let value = std::env::var_os(name).filter(|value| !value.is_empty())?;
+    expand_home(Path::new(&value)).ok()
+}
+
+/// `$XDG_*_HOME`, only when set to an absolute path as the spec requires.
+fn xdg_dir(name: &str) -> Option<PathBuf> {
+    env_dir(name).filter(|dir| dir.is_absolute())
+}
+
+fn resolve_dir(
+    explicit: Option<PathBuf>,
+    configured: Option<PathBuf>,
+    xdg_home: Option<PathBuf>,
+    home: &Path,
+) -> PathBuf {
+    explicit
+        .or(configured)
+        .or_else(|| xdg_home.map(|dir| dir.join("to-tui")))
+        .unwrap_or_else(|| home.join(".to-tui"))
+}
+
+/// The single directory totui used before storage locations were configurable.
+///
+/// Returns ~/.to-tui/
+pub fn get_legacy_to_tui_dir() -> Result<PathBuf> {
+    Ok(home_dir()?.join(".to-tui"))
+}
+
+/// Get the data directory: the database, dailies, backups and runtime files.
+///
+/// Resolved from TOTUI_DATA_DIR, then `data_dir` in config.toml, then
+/// $XDG_DATA_HOME/to-tui, then ~/.to-tui/.
 pub fn get_to_tui_dir() -> Result<PathBuf> {
-    let home = dirs::home_dir().ok_or_else(|| anyhow!("Could not find home directory"))?;
-    Ok(home.join(".to-tui"))
+    let configured = overrides().data_dir;
+    Ok(resolve_dir(
+        env_dir(DATA_DIR_ENV),
+        configured,
+        xdg_dir("XDG_DATA_HOME"),
+        &home_dir()?,
+    ))
+}
+
+/// Get the directory holding config.toml.
+///
+/// Resolved from TOTUI_CONFIG_DIR, then $XDG_CONFIG_HOME/to-tui, then
+/// ~/.to-tui/. The config file can't move itself, so there is no config key.
+pub fn get_config_dir() -> Result<PathBuf> {
+    Ok(resolve_dir(
+        env_dir(CONFIG_DIR_ENV),
+        None,
+        xdg_dir("XDG_CONFIG_HOME"),
+        &home_dir()?,
+    ))
 }
 
 pub fn get_projects_dir() -> Result<PathBuf> {
@@ -30,8 +135,7 @@ pub fn get_legacy_dailies_dir() -> Result<PathBuf> {
 }
 
 pub fn get_config_path() -> Result<PathBuf> {
-    let todo_dir = get_to_tui_dir()?;
-    Ok(todo_dir.join("config.toml"))
+    Ok(get_config_dir()?.join("config.toml"))
 }
 
 pub fn get_database_path() -> Result<PathBuf> {
@@ -39,30 +143,55 @@ pub fn get_database_path() -> Result<PathBuf> {
     Ok(todo_dir.join("todos.db"))
 }
 
+/// Get the directory for automatic database backups.
+///
+/// Returns ~/.to-tui/backups/
+pub fn get_backups_dir() -> Result<PathBuf> {
+    let todo_dir = get_to_tui_dir()?;
+    Ok(todo_dir.join("backups"))
+}
+
 pub fn get_pid_file_path() -> Result<PathBuf> {
     let todo_dir = get_to_tui_dir()?;
     Ok(todo_dir.join("server.pid"))
 }
 
+/// Admin token of the running API server, for `POST /api/admin/shutdown`.
+pub fn get_server_token_path() -> Result<PathBuf> {
+    let todo_dir = get_to_tui_dir()?;
+    Ok(todo_dir.join("server.token"))
+}
+
 /// Get the plugins directory path.
 ///
 /// Following CONTEXT.md: ~/.local/share/to-tui/plugins/
-/// This uses XDG data directory for cross-platform compatibility.
+/// This uses XDG data directory ($XDG_DATA_HOME when set) for cross-platform
+/// compatibility.
 pub fn get_plugins_dir() -> Result<PathBuf> {
-    let data_dir =
-        dirs::data_local_dir().ok_or_else(|| anyhow!("Could not find local data directory"))?;
+    let data_dir = match xdg_dir("XDG_DATA_HOME") {
+        Some(dir) => dir,
+        None => dirs::data_local_dir().ok_or_else(|| anyhow!("Could not find local data directory"))?,
+    };
     Ok(data_dir.join("to-tui").join("plugins"))
 }
 
+/// Get the directory holding all plugin configs.
+///
+/// Returns ~/.config/to-tui/plugins/ using XDG config directory
+/// ($XDG_CONFIG_HOME when set).
+pub fn get_plugins_config_dir() -> Result<PathBuf> {
+    let config_dir = match xdg_dir("XDG_CONFIG_HOME") {
+        Some(dir) => dir,
+        None => dirs::config_dir().ok_or_else(|| anyhow!("Could not find config directory"))?,
+    };
+    Ok(config_dir.join("to-tui").join("plugins"))
+}
+
 /// Get the config directory for a specific plugin.
 ///
 /// Returns ~/.config/to-tui/plugins/<name>/ using XDG config directory.
 pub fn get_plugin_config_dir(plugin_name: &str) -> Result<PathBuf> {
-    let config_dir = dirs::config_dir().ok_or_else(|| anyhow!("Could not find config directory"))?;
-    Ok(config_dir
-        .join("to-tui")
-        .join("plugins")
-        .join(plugin_name))
+    Ok(get_plugins_config_dir()?.join(plugin_name))
 }
 
 /// Get the config file path for a specific plugin.
@@ -72,20 +201,45 @@ pub fn get_plugin_config_path(plugin_name: &str) -> Result<PathBuf> {
     Ok(get_plugin_config_dir(plugin_name)?.join("config.toml"))
 }
 
+/// Get the content-addressed store of files attached to items.
+///
+/// Returns ~/.to-tui/attachments/
+pub fn get_attachments_dir() -> Result<PathBuf> {
+    let todo_dir = get_to_tui_dir()?;
+    Ok(todo_dir.join("attachments"))
+}
+
 pub fn get_ui_cache_path() -> Result<PathBuf> {
     let todo_dir = get_to_tui_dir()?;
     Ok(todo_dir.join("ui_cache.json"))
 }
 
+pub fn get_undo_history_path() -> Result<PathBuf> {
+    let todo_dir = get_to_tui_dir()?;
+    Ok(todo_dir.join("undo_history.json"))
+}
+
 pub fn get_crash_log_path() -> Result<PathBuf> {
     let todo_dir = get_to_tui_dir()?;
     Ok(todo_dir.join("crash.log"))
 }
 
+/// Get the crash-loop tracking file used for plugin quarantine.
+///
+/// Returns ~/.to-tui/crash_state.json
+pub fn get_crash_state_path() -> Result<PathBuf> {
+    let todo_dir = get_to_tui_dir()?;
+    Ok(todo_dir.join("crash_state.json"))
+}
+
 /// Get the logs directory for totui.
 ///
-/// Returns ~/.to-tui/logs/
+/// Resolved from TOTUI_LOGS_DIR, then `logs_dir` in config.toml, then the
+/// `logs` directory inside the data directory (~/.to-tui/logs/).
 pub fn get_logs_dir() -> Result<PathBuf> {
+    if let Some(dir) = env_dir(LOGS_DIR_ENV).or(overrides().logs_dir) {
+        return Ok(dir);
+    }
     let todo_dir = get_to_tui_dir()?;
     Ok(todo_dir.join("logs"))
 }
@@ -107,17 +261,239 @@ pub fn get_plugin_log_path(plugin_name: &str) -> Result<PathBuf> {
     Ok(plugin_logs_dir.join(format!("{}.{}.log", plugin_name, date)))
 }
 
+/// Where daily files go, relative to the projects directory. `{project}` is
+/// the project name, `{date}` is YYYY-MM-DD, `{year}`, `{month}` and `{day}`
+/// are its parts and `{ext}` is the extension of the daily file format.
+///
+/// Files written before `daily_file_template` existed follow this layout, so
+/// it is also where daily files are looked for when the configured one has
+/// none.
+pub const DEFAULT_DAILY_FILE_TEMPLATE: &str = "{project}/dailies/{date}.{ext}";
+
+const TEMPLATE_FIELDS: [&str; 6] = ["project", "date", "year", "month", "day", "ext"];
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum TemplatePart<'a> {
+    Literal(&'a str),
+    Field(&'a str),
+}
+
+fn template_parts(template: &str) -> Result<Vec<TemplatePart<'_>>> {
+    let mut parts = Vec::new();
+    let mut rest = template;
+    while let Some(start) = rest.find('{') {
+        if start > 0 {
+            parts.push(TemplatePart::Literal(&rest[..start]));
+        }
+        let Some(len) = rest[start..].find('}') else {
+            bail!("Unclosed {{ in daily_file_template \"{template}\"");
+        };
+        let field = &rest[start + 1..start + len];
+        if !TEMPLATE_FIELDS.contains(&field) {
+            bail!("Unknown placeholder {{{field}}} in daily_file_template \"{template}\"");
+        }
+        parts.push(TemplatePart::Field(field));
+        rest = &rest[start + len + 1..];
+    }
+    if !rest.is_empty() {
+        parts.push(TemplatePart::Literal(rest));
+    }
+    Ok(parts)
+}
+
+/// Check a `daily_file_template` from the config: every project and every
+/// day needs a file of its own.
+pub fn validate_daily_file_template(template: &str) -> Result<()> {
+    let parts = template_parts(template)?;
+    let has = |field| parts.contains(&TemplatePart::Field(field));
+    if !has("project") {
+        bail!("daily_file_template \"{template}\" needs {{project}}, or projects would share files");
+    }
+    if !(has("date") || has("year") && has("month") && has("day")) {
+        bail!("daily_file_template \"{template}\" needs {{date}}, or {{year}}, {{month}} and {{day}}");
+    }
+    Ok(())
+}
+
+/// The configured template, or the default one.
+fn daily_file_template() -> String {
+    overrides()
+        .daily_file_template
+        .unwrap_or_else(|| DEFAULT_DAILY_FILE_TEMPLATE.to_string())
+}
+
+/// The template as a full path: relative templates start in the projects
+/// directory, and a leading `~` is the home directory.
+fn template_path(template: &str) -> Result<String> {
+    let path = get_projects_dir()?.join(expand_home(Path::new(template))?);
+    Ok(path.to_string_lossy().into_owned())
+}
+
+fn daily_file_path(template: &str, project_name: &str, date: NaiveDate) -> Result<PathBuf> {
+    let extension = crate::storage::markdown::daily_file_format().dialect().extension();
+    let path: String = template_parts(&template_path(template)?)?
+        .into_iter()
+        .map(|part| match part {
+            TemplatePart::Literal(text) => text.to_string(),
+            TemplatePart::Field("project") => project_name.to_string(),
+            TemplatePart::Field("year") => date.format("%Y").to_string(),
+            TemplatePart::Field("month") => date.format("%m").to_string(),
+            TemplatePart::Field("day") => date.format("%d").to_string(),
+            TemplatePart::Field("ext") => extension.to_string(),
+            TemplatePart::Field(_) => date.format("%Y-%m-%d").to_string(),
+        })
+        .collect();
+    Ok(PathBuf::from(path))
+}
+
 pub fn get_daily_file_path_for_project(project_name: &str, date: NaiveDate) -> Result<PathBuf> {
-    let dailies_dir = get_dailies_dir_for_project(project_name)?;
-    let filename = format!("{}.md", date.format("%Y-%m-%d"));
-    Ok(dailies_dir.join(filename))
+    daily_file_path(&daily_file_template(), project_name, date)
+}
+
+/// The daily file of `date` to read: the one `daily_file_template` names, or
+/// failing that one in the default layout, written before the template was
+/// changed.
+pub fn find_daily_file_for_project(project_name: &str, date: NaiveDate) -> Result<Option<PathBuf>> {
+    for template in [daily_file_template(), DEFAULT_DAILY_FILE_TEMPLATE.to_string()] {
+        let path = daily_file_path(&template, project_name, date)?;
+        if path.exists() {
+            return Ok(Some(path));
+        }
+    }
+    Ok(None)
+}
+
+fn field_accepts(field: &str, value: &str) -> bool {
+    let digits = |len| value.len() == len && value.bytes().all(|b| b.is_ascii_digit());
+    match field {
+        "year" => digits(4),
+        "month" | "day" => digits(2),
+        "date" => value.len() == 10 && NaiveDate::parse_from_str(value, "%Y-%m-%d").is_ok(),
+        _ => !value.contains(std::path::MAIN_SEPARATOR) && !value.contains('/'),
+    }
+}
+
+/// Match `input` against the template parts, collecting placeholder values.
+fn match_template<'a>(parts: &[TemplatePart], input: &'a str, values: &mut Vec<(String, &'a str)>) -> bool {
+    let Some((part, rest)) = parts.split_first() else {
+        return input.is_empty();
+    };
+    match part {
+        TemplatePart::Literal(text) => input
+            .strip_prefix(text)
+            .is_some_and(|input| match_template(rest, input, values)),
+        TemplatePart::Field(field) => {
+            for end in (1..=input.len()).filter(|&end| input.is_char_boundary(end)) {
+                if !field_accepts(field, &input[..end]) {
+                    continue;
+                }
+                values.push((field.to_string(), &input[..end]));
+                if match_template(rest, &input[end..], values) {
+                    return true;
+                }
+                values.pop();
+            }
+            false
+        }
+    }
+}
+
+fn parse_with_template(template: &str, path: &Path) -> Option<(String, NaiveDate)> {
+    let template = template_path(template).ok()?;
+    let parts = template_parts(&template).ok()?;
+    let mut values = Vec::new();
+    if !match_template(&parts, path.to_str()?, &mut values) {
+        return None;
+    }
+    let value = |field: &str| values.iter().find(|(name, _)| name == field).map(|(_, value)| *value);
+    let date = match value("date") {
+        Some(date) => NaiveDate::parse_from_str(date, "%Y-%m-%d").ok()?,
+        None => NaiveDate::from_ymd_opt(
+            value("year")?.parse().ok()?,
+            value("month")?.parse().ok()?,
+            value("day")?.parse().ok()?,
+        )?,
+    };
+    Some((value("project")?.to_string(), date))
+}
+
+/// The project and day of a daily file, in the configured layout or the
+/// default one.
+pub fn parse_daily_file_path(path: &Path) -> Option<(String, NaiveDate)> {
+    parse_with_template(&daily_file_template(), path)
+        .or_else(|| parse_with_template(DEFAULT_DAILY_FILE_TEMPLATE, path))
+}
+
+/// Directories holding daily files, in the configured layout and the default
+/// one: each template up to its first placeholder.
+pub fn get_daily_file_roots() -> Result<Vec<PathBuf>> {
+    let mut roots: Vec<PathBuf> = Vec::new();
+    for template in [daily_file_template(), DEFAULT_DAILY_FILE_TEMPLATE.to_string()] {
+        let path = template_path(&template)?;
+        let fixed = &path[..path.find('{').unwrap_or(path.len())];
+        let root = match fixed.rfind(std::path::MAIN_SEPARATOR) {
+            Some(end) => PathBuf::from(&fixed[..end]),
+            None => PathBuf::from(fixed),
+        };
+        if !roots.iter().any(|known| root.starts_with(known)) {
+            roots.retain(|known| !known.starts_with(&root));
+            roots.push(root);
+        }
+    }
+    Ok(roots)
+}
+
+/// Every daily file of every project, in either layout.
+pub fn find_daily_files() -> Result<Vec<PathBuf>> {
+    let mut files = Vec::new();
+    for root in get_daily_file_roots()? {
+        collect_files(&root, &mut files)?;
+    }
+    files.retain(|path| {
+        crate::storage::markdown::dialect_for_path(path).is_some() && parse_daily_file_path(path).is_some()
+    });
+    Ok(files)
+}
+
+/// Every daily file of a project, oldest day first. A day with files in
+/// both layouts gets the one in the configured layout.
+pub fn find_daily_files_for_project(project_name: &str) -> Result<Vec<(NaiveDate, PathBuf)>> {
+    let mut days = std::collections::BTreeMap::new();
+    for path in find_daily_files()? {
+        let Some((project, date)) = parse_daily_file_path(&path) else {
+            continue;
+        };
+        if project != project_name {
+            continue;
+        }
+        let preferred = get_daily_file_path_for_project(project_name, date)?;
+        if path == preferred || !days.contains_key(&date) {
+            days.insert(date, path);
+        }
+    }
+    Ok(days.into_iter().collect())
+}
+
+fn collect_files(dir: &Path, files: &mut Vec<PathBuf>) -> Result<()> {
+    let Ok(entries) = fs::read_dir(dir) else {
+        return Ok(());
+    };
+    for entry in entries {
+        let path = entry?.path();
+        if path.is_dir() {
+            collect_files(&path, files)?;
+        } else {
+            files.push(path);
+        }
+    }
+    Ok(())
 }
 
 pub fn ensure_project_directories_exist(project_name: &str) -> Result<()> {
-    let dailies_dir = get_dailies_dir_for_project(project_name)?;
+    let project_dir = get_project_dir(project_name)?;
 
-    if !dailies_dir.exists() {
-        fs::create_dir_all(&dailies_dir)?;
+    if !project_dir.exists() {
+        fs::create_dir_all(&project_dir)?;
     }
 
     Ok(())
@@ -196,6 +572,26 @@ mod tests {
         assert!(path.to_string_lossy().ends_with("2025-12-31.md"));
     }
 
+    #[test]
+    fn test_daily_file_template_round_trips() {
+        let template = "{project}/{year}/{month}/{date}.{ext}";
+        let date = NaiveDate::from_ymd_opt(2026, 3, 9).unwrap();
+        let path = daily_file_path(template, "Work", date).unwrap();
+        assert!(path.ends_with("projects/Work/2026/03/2026-03-09.md"));
+        assert_eq!(parse_with_template(template, &path), Some(("Work".to_string(), date)));
+
+        // The default layout is still recognised, and isn't mistaken for this one
+        let old = daily_file_path(DEFAULT_DAILY_FILE_TEMPLATE, "Work", date).unwrap();
+        assert_eq!(parse_with_template(template, &old), None);
+        assert_eq!(parse_with_template(DEFAULT_DAILY_FILE_TEMPLATE, &old), Some(("Work".to_string(), date)));
+        assert_eq!(old, get_daily_file_path_for_project("Work", date).unwrap());
+
+        assert!(validate_daily_file_template("{project}/{year}/{month}-{day}.md").is_ok());
+        assert!(validate_daily_file_template("{year}/{date}.md").is_err());
+        assert!(validate_daily_file_template("{project}/{year}-{month}.md").is_err());
+        assert!(validate_daily_file_template("{project}/{week}/{date}.md").is_err());
+    }
+
     #[test]
     fn test_get_database_path() {
         let path = get_database_path().unwrap();
@@ -210,6 +606,34 @@ mod tests {
         assert!(path.to_string_lossy().ends_with("server.pid"));
     }
 
+    #[test]
+    fn test_resolve_dir_precedence() {
+        let home = Path::new("/home/me");
+        let env = Some(PathBuf::from("/env"));
+        let configured = Some(PathBuf::from("/configured"));
+        let xdg = Some(PathBuf::from("/xdg"));
+
+        assert_eq!(
+            resolve_dir(env.clone(), configured.clone(), xdg.clone(), home),
+            PathBuf::from("/env")
+        );
+        assert_eq!(
+            resolve_dir(None, configured, xdg.clone(), home),
+            PathBuf::from("/configured")
+        );
+        assert_eq!(resolve_dir(None, None, xdg, home), PathBuf::from("/xdg/to-tui"));
+        assert_eq!(resolve_dir(None, None, None, home), PathBuf::from("/home/me/.to-tui"));
+    }
+
+    #[test]
+    fn test_expand_home() {
+        let home = dirs::home_dir().unwrap();
+        assert_eq!(expand_home(Path::new("~/todos")).unwrap(), home.join("todos"));
+        assert_eq!(expand_home(Path::new("~")).unwrap(), home);
+        assert_eq!(expand_home(Path::new("/srv/todos")).unwrap(), PathBuf::from("/srv/todos"));
+        assert_eq!(expand_home(Path::new("~user/x")).unwrap(), PathBuf::from("~user/x"));
+    }
+
     #[test]
     fn test_get_plugins_dir() {
         let dir = get_plugins_dir().unwrap();
@@ -0,0 +1,80 @@
+//! Text matching for in-list search.
+//!
+//! Matching is smart-case like vim's `smartcase`: a query in all lowercase
+//! ignores case, a query containing an uppercase letter matches exactly.
+
+use std::ops::Range;
+
+/// Byte ranges of every non-overlapping match of `needle` in `haystack`.
+pub fn find_matches(haystack: &str, needle: &str) -> Vec<Range<usize>> {
+    if needle.is_empty() {
+        return Vec::new();
+    }
+    let ignore_case = !needle.chars().any(char::is_uppercase);
+
+    let mut ranges = Vec::new();
+    let mut start = 0;
+    while start < haystack.len() {
+        match match_len_at(&haystack[start..], needle, ignore_case) {
+            Some(len) => {
+                ranges.push(start..start + len);
+                start += len;
+            }
+            None => {
+                start += haystack[start..].chars().next().map_or(1, char::len_utf8);
+            }
+        }
+    }
+    ranges
+}
+
+/// Whether `needle` occurs in `haystack`.
+pub fn contains(haystack: &str, needle: &str) -> bool {
+    !find_matches(haystack, needle).is_empty()
+}
+
+/// Length in bytes of `needle` matched at the start of `text`, if it matches.
+fn match_len_at(text: &str, needle: &str, ignore_case: bool) -> Option<usize> {
+    let mut text_chars = text.char_indices();
+    for n in needle.chars() {
+        let (_, t) = text_chars.next()?;
+        let equal = if ignore_case {
+            t.to_lowercase().eq(n.to_lowercase())
+        } else {
+            t == n
+        };
+        if !equal {
+            return None;
+        }
+    }
+    Some(text_chars.next().map_or(text.len(), |(i, _)| i))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_lowercase_query_ignores_case() {
+        assert_eq!(find_matches("Fix Bug in bug tracker", "bug"), vec![4..7, 11..14]);
+    }
+
+    #[test]
+    fn test_uppercase_query_is_case_sensitive() {
+        assert_eq!(find_matches("Fix Bug in bug tracker", "Bug"), vec![4..7]);
+        assert!(!contains("fix bug", "Bug"));
+    }
+
+    #[test]
+    fn test_multibyte_text() {
+        let text = "Café ☕ and CAFÉ";
+        let ranges = find_matches(text, "café");
+        assert_eq!(ranges.len(), 2);
+        assert_eq!(&text[ranges[1].clone()], "CAFÉ");
+    }
+
+    #[test]
+    fn test_empty_query_matches_nothing() {
+        assert!(find_matches("anything", "").is_empty());
+    }
+}
@@ -1,3 +1,6 @@
+use std::ops::Range;
+use unicode_width::{UnicodeWidthChar, UnicodeWidthStr};
+
 pub fn prev_char_boundary(s: &str, byte_index: usize) -> usize {
     if byte_index == 0 {
         return 0;
@@ -83,6 +86,102 @@ pub fn next_word_boundary(s: &str, byte_index: usize) -> usize {
     chars.get(pos).map(|(i, _)| *i).unwrap_or(s.len())
 }
 
+/// Terminal columns `s` takes up. Wide characters such as CJK and most emoji
+/// count as two.
+pub fn display_width(s: &str) -> usize {
+    s.width()
+}
+
+/// Byte length of the longest prefix of `s` that fits in `max_width` columns.
+/// Always takes at least one character so wrapping makes progress.
+pub fn prefix_fitting(s: &str, max_width: usize) -> usize {
+    let mut used = 0;
+    for (i, c) in s.char_indices() {
+        used += c.width().unwrap_or(0);
+        if used > max_width && i > 0 {
+            return i;
+        }
+    }
+    s.len()
+}
+
+/// Word-wrap `text` to `max_width` columns, as byte ranges into `text`.
+///
+/// Lines break at whitespace, and every `\n` starts a new line. A word wider
+/// than the line is split between characters, which is how text without
+/// spaces, like Chinese or Japanese, wraps. Whitespace where a line breaks
+/// belongs to neither line. Rendering, row-height estimates and the edit
+/// cursor all go through this so they agree on where lines break.
+pub fn wrap_ranges(text: &str, max_width: usize) -> Vec<Range<usize>> {
+    if max_width == 0 {
+        return std::iter::once(0..text.len()).collect();
+    }
+
+    let mut lines = Vec::new();
+    let mut paragraph_start = 0;
+    for paragraph in text.split('\n') {
+        let mut line: Option<Range<usize>> = None;
+        let mut line_width = 0;
+
+        for word in word_ranges(paragraph) {
+            let word_width = display_width(&paragraph[word.clone()]);
+            if let Some(current) = line.as_mut() {
+                let gap_width = display_width(&paragraph[current.end..word.start]);
+                if line_width + gap_width + word_width <= max_width {
+                    current.end = word.end;
+                    line_width += gap_width + word_width;
+                    continue;
+                }
+                lines.push(current.start + paragraph_start..current.end + paragraph_start);
+            }
+
+            // Split a word too wide for a line of its own
+            let mut start = word.start;
+            while display_width(&paragraph[start..word.end]) > max_width {
+                let end = start + prefix_fitting(&paragraph[start..word.end], max_width);
+                lines.push(start + paragraph_start..end + paragraph_start);
+                start = end;
+            }
+            line = Some(start..word.end);
+            line_width = display_width(&paragraph[start..word.end]);
+        }
+
+        match line {
+            Some(current) => lines.push(current.start + paragraph_start..current.end + paragraph_start),
+            None => lines.push(paragraph_start..paragraph_start),
+        }
+        paragraph_start += paragraph.len() + 1;
+    }
+    lines
+}
+
+/// `text` word-wrapped to `max_width` columns. See [`wrap_ranges`].
+pub fn wrap_lines(text: &str, max_width: usize) -> Vec<&str> {
+    wrap_ranges(text, max_width)
+        .into_iter()
+        .map(|range| &text[range])
+        .collect()
+}
+
+/// Number of lines `text` takes when wrapped to `max_width` columns.
+pub fn wrapped_line_count(text: &str, max_width: usize) -> usize {
+    wrap_ranges(text, max_width).len()
+}
+
+/// Byte ranges of the runs of non-whitespace in `s`.
+fn word_ranges(s: &str) -> impl Iterator<Item = Range<usize>> + '_ {
+    let mut rest = s.char_indices().peekable();
+    std::iter::from_fn(move || {
+        while rest.next_if(|(_, c)| c.is_whitespace()).is_some() {}
+        let (start, _) = *rest.peek()?;
+        let mut end = start;
+        while let Some((i, c)) = rest.next_if(|(_, c)| !c.is_whitespace()) {
+            end = i + c.len_utf8();
+        }
+        Some(start..end)
+    })
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -154,4 +253,27 @@ mod tests {
         assert_eq!(next_word_boundary(s, 0), 7);
         assert_eq!(prev_word_boundary(s, 13), 7);
     }
+
+    #[test]
+    fn test_wrap_counts_columns_not_bytes() {
+        // Each of these characters is three bytes but two columns wide
+        assert_eq!(display_width("日本語"), 6);
+        assert_eq!(wrap_lines("日本 語です", 6), vec!["日本", "語です"]);
+        assert_eq!(wrap_lines("ok 👋🌍 done", 8), vec!["ok 👋🌍", "done"]);
+    }
+
+    #[test]
+    fn test_wrap_splits_text_without_spaces() {
+        assert_eq!(wrap_lines("日本語のテキスト", 5), vec!["日本", "語の", "テキ", "スト"]);
+        assert_eq!(wrap_lines("abcdefgh ij", 3), vec!["abc", "def", "gh", "ij"]);
+        assert_eq!(wrapped_line_count("one\n\ntwo three", 5), 4);
+    }
+
+    #[test]
+    fn test_wrap_ranges_point_into_the_text() {
+        let text = "héllo  wörld";
+        let ranges = wrap_ranges(text, 7);
+        assert_eq!(ranges, vec![0..6, 8..14]);
+        assert_eq!(&text[ranges[1].clone()], "wörld");
+    }
 }